@@ -94,11 +94,4 @@ impl ParserConfig {
         let config: Self = toml::from_str(&content)?;
         Ok(config)
     }
-    
-    /// Save configuration to TOML file
-    pub fn to_file(&self, path: PathBuf) -> Result<()> {
-        let content = toml::to_string_pretty(self)?;
-        std::fs::write(path, content)?;
-        Ok(())
-    }
 }
\ No newline at end of file
@@ -202,7 +202,7 @@ impl<'a> Visit<'a> for AstVisitor<'a> {
         // Check if this is a Bevy plugin implementation
         if self.config.extract_plugins && self.extractor.is_bevy_plugin(node) {
             let target_name = node.self_ty.to_token_stream().to_string();
-            let plugin_info = PluginInfo {
+            let _plugin_info = PluginInfo {
                 name: target_name,
                 systems: Vec::new(), // TODO: Extract from build method
                 resources: Vec::new(),
@@ -257,7 +257,7 @@ impl CodeExtractor {
         
         for attr in attrs {
             if attr.path().is_ident("doc") {
-                if let Ok(Meta::NameValue(meta_name_value)) = attr.meta.clone().try_into() {
+                if let Meta::NameValue(meta_name_value) = &attr.meta {
                     if let Expr::Lit(expr_lit) = &meta_name_value.value {
                         if let Lit::Str(lit_str) = &expr_lit.lit {
                             docs.push(lit_str.value());
@@ -279,7 +279,7 @@ impl CodeExtractor {
         
         for attr in attrs {
             if attr.path().is_ident("derive") {
-                if let Ok(Meta::List(meta_list)) = attr.meta.clone().try_into() {
+                if let Meta::List(meta_list) = &attr.meta {
                     let derive_str = meta_list.tokens.to_string();
                     // Simple parsing - split by comma and clean up
                     let derive_items: Vec<String> = derive_str
@@ -46,8 +46,7 @@ impl CodeParser {
             .filter_map(|entry| entry.ok())
             .filter(|entry| {
                 entry.path().extension()
-                    .and_then(|ext| ext.to_str())
-                    .map_or(false, |ext| ext == "rs")
+                    .and_then(|ext| ext.to_str()) == Some("rs")
             })
             .map(|entry| entry.path().to_path_buf())
             .collect();
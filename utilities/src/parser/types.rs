@@ -246,7 +246,7 @@ impl ParseResult {
                     let systems: Vec<&str> = module.bevy_systems.iter().map(|s| s.name.as_str()).collect();
                     md.push_str(&format!("- Systems: {}\n", systems.join(", ")));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
         }
         
@@ -266,7 +266,7 @@ impl ParseResult {
                     let systems: Vec<&str> = module.bevy_systems.iter().map(|s| s.name.as_str()).collect();
                     md.push_str(&format!("- Systems: {}\n", systems.join(", ")));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
         }
         
@@ -309,7 +309,7 @@ impl ParseResult {
             for system in all_systems {
                 md.push_str(&format!("- `{}`\n", system));
             }
-            md.push_str("\n");
+            md.push('\n');
         }
         
         md
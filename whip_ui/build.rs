@@ -119,7 +119,7 @@ fn compile_and_copy_shaders() {
         .unwrap_or_else(|| PathBuf::from("glslc")); // Fallback to assuming it's in PATH
 
     println!("cargo:warning=Using glslc path: {}", glslc_path.display());
-    if !glslc_path.exists() && glslc_path == PathBuf::from("glslc") {
+    if !glslc_path.exists() && glslc_path == std::path::Path::new("glslc") {
          println!("cargo:warning=glslc not found in PATH or VULKAN_SDK/Bin. Shader compilation might fail.");
          // Consider panicking here if compilation is mandatory:
          // panic!("glslc compiler not found. Please ensure it's in your PATH or set the VULKAN_SDK environment variable.");
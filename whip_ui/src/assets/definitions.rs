@@ -2,24 +2,52 @@ use bevy_asset::Asset;
 use bevy_reflect::TypePath;
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
-use crate::widgets::blueprint::{WidgetBlueprint, WidgetCollection, WidgetType, LayoutConfig, StyleConfig, BehaviorConfig};
+use crate::widgets::blueprint::{WidgetBlueprint, WidgetCollection, WidgetType, LayoutConfig, StyleConfig, StateStyles, BehaviorConfig};
+use crate::widgets::dropdown::validate_dropdown;
+use crate::widgets::numeric::validate_numeric_widget;
+use crate::widgets::popup::validate_popup;
+use crate::widgets::scroll_view::validate_scroll_view;
+use crate::widgets::tabs::validate_tabs;
+use crate::widgets::text_input::validate_text_input;
+use crate::widgets::virtual_list::validate_virtual_list;
 
-use super::{WindowConfig, UiRegistry};
+use super::{WindowConfig, WindowDeclaration, UiRegistry, VALID_EVENTS, ValidationReport, StateDeclaration, RepeatConfig, LocalizedText};
 
 /// New hierarchical UI definition that represents source data from TOML
 #[derive(Asset, TypePath, Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct UiDefinition {
+    /// Version of the definition format the source was written in. Older files are upgraded
+    /// while loading (see [`super::migrations`]), so a loaded definition is always current.
+    #[serde(default = "super::migrations::current_format_version")]
+    pub format_version: u32,
     /// Window configuration
     pub window: Option<WindowConfig>,
+    /// Additional windows to open, by id, each showing its own layout (see [`super::windows`])
+    pub windows: Option<HashMap<String, WindowDeclaration>>,
     /// Root widget node defining the UI hierarchy
     pub root: WidgetNode,
     /// Global styles that can be referenced by class name
     pub styles: Option<HashMap<String, StyleOverrides>>,
     /// Global actions that can be referenced by widgets
     pub actions: Option<HashMap<String, ActionBinding>>,
+    /// Design tokens referenced as `"$name"` from styles and layouts (see [`super::tokens`]).
+    /// After loading this holds the effective, already-substituted token values.
+    pub tokens: Option<HashMap<String, serde_json::Value>>,
+    /// Named, typed state values that widget properties bind to (see [`super::state`]),
+    /// keyed by dotted path such as `project.title`
+    #[serde(default, deserialize_with = "super::state::deserialize_state_table", skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<String, StateDeclaration>>,
+    /// The document as written, before includes, tokens, inline bindings and string table
+    /// references were resolved. Exports start from it so they keep those references.
+    #[serde(skip)]
+    pub authored: Option<serde_json::Value>,
 }
 
 /// Recursive widget node structure representing the UI hierarchy
+///
+/// In source files a node may instead be written as `include = "widgets/editable_label.toml"`
+/// plus any fields to override; the loader expands it into a full node before deserialization
+/// (see [`super::includes`]).
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct WidgetNode {
     /// Unique identifier for this widget
@@ -45,8 +73,24 @@ pub struct WidgetNode {
     /// Interaction bindings for this widget
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bindings: Option<HashMap<String, ActionBinding>>,
+    /// State values displayed by widget properties, keyed by property path such as
+    /// `widget_type.content`. Written inline in source files as `content = { bind = "project.title" }`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_bindings: Option<HashMap<String, String>>,
+    /// Spawn the children once per element of a `List` state value instead of once
+    /// (see [`super::dynamic::RepeatConfig`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<RepeatConfig>,
+    /// State condition under which the node is spawned, e.g. `"project.dirty"` or
+    /// `"view.mode == 'grid'"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// String table reference that supplies the text of a Text or Button node. Written inline in
+    /// source files as `content = { t = "toolbar.save" }` (see [`super::localization`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized: Option<LocalizedText>,
     /// Child widget nodes
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<WidgetNode>,
 }
 
@@ -74,6 +118,61 @@ pub struct StyleOverrides {
     /// Opacity override
     #[serde(skip_serializing_if = "Option::is_none")]
     pub opacity: Option<f32>,
+    /// Style classes this class builds on, applied in order before its own values.
+    /// Only valid on global style classes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Vec<String>>,
+    /// Hover, pressed, focused and disabled variants, merged into the widget's `StateStyles`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub states: Option<StateStyles>,
+}
+
+impl StyleOverrides {
+    /// Layer `other` on top of these overrides: values it sets win, and state variants
+    /// merge state by state
+    pub fn merge(&mut self, other: &StyleOverrides) {
+        if other.background_color.is_some() {
+            self.background_color = other.background_color.clone();
+        }
+        if other.border_color.is_some() {
+            self.border_color = other.border_color.clone();
+        }
+        if other.border_width.is_some() {
+            self.border_width = other.border_width;
+        }
+        if other.border_radius.is_some() {
+            self.border_radius = other.border_radius;
+        }
+        if other.text_color.is_some() {
+            self.text_color = other.text_color.clone();
+        }
+        if other.text_size.is_some() {
+            self.text_size = other.text_size;
+        }
+        if other.opacity.is_some() {
+            self.opacity = other.opacity;
+        }
+        self.states = match (self.states.take(), &other.states) {
+            (Some(base), Some(top)) => Some(base.merged_with(top)),
+            (base, top) => top.clone().or(base),
+        };
+    }
+}
+
+impl From<&StyleConfig> for StyleOverrides {
+    fn from(style: &StyleConfig) -> Self {
+        Self {
+            background_color: style.background_color.clone(),
+            border_color: style.border_color.clone(),
+            border_width: style.border_width,
+            border_radius: style.border_radius,
+            text_color: style.text_color.clone(),
+            text_size: style.text_size,
+            opacity: style.opacity,
+            extends: None,
+            states: style.states.clone(),
+        }
+    }
 }
 
 /// Action binding that connects UI events to actions
@@ -89,86 +188,178 @@ pub struct ActionBinding {
 }
 
 impl UiDefinition {
-    /// Validate the UI definition structure
+    /// Validate the UI definition structure, stopping at the first error
     pub fn validate(&self) -> Result<(), UiDefinitionError> {
+        match self.validation_report().first_error() {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate the UI definition structure with registry-based validation, stopping at the first error
+    pub fn validate_with_registry(&self, registry: &UiRegistry) -> Result<(), UiDefinitionError> {
+        match self.validation_report_with_registry(registry).first_error() {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Collect every structural error in the definition
+    pub fn validation_report(&self) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
         // Validate window config
-        self.validate_window_config()?;
-        
+        self.check_window_config(&mut report);
+
         // Validate global styles for internal consistency
-        self.validate_global_styles()?;
-        
+        self.check_global_styles(&mut report);
+
         // Validate global actions
-        self.validate_global_actions()?;
-        
+        self.check_global_actions(&mut report);
+
         // Validate widget hierarchy
-        self.validate_widget_node(&self.root, &HashSet::new())?;
-        
-        Ok(())
+        self.check_widget_node(&self.root, "root", &HashSet::new(), &mut report);
+
+        // Validate state declarations and bindings
+        self.check_state(&mut report);
+
+        // Validate conditional and repeated nodes
+        self.check_dynamic_nodes(&mut report);
+
+        report
     }
 
-    /// Validate the UI definition structure with registry-based validation
-    pub fn validate_with_registry(&self, registry: &UiRegistry) -> Result<(), UiDefinitionError> {
+    /// Collect every structural and registry error in the definition
+    pub fn validation_report_with_registry(&self, registry: &UiRegistry) -> ValidationReport {
         // First run basic validation
-        self.validate()?;
-        
+        let mut report = self.validation_report();
+
         // Then run registry-based validation
-        self.validate_widget_node_with_registry(&self.root, &HashSet::new(), registry, 0)?;
-        
+        self.check_widget_node_with_registry(&self.root, "root", registry, 0, &mut report);
+        self.check_state_with_registry(registry, &mut report);
+
         // Validate global actions against registry
         if let Some(ref actions) = self.actions {
-            for (_, action_binding) in actions {
+            for (action_name, action_binding) in sorted_entries(actions) {
                 if let Err(err) = registry.validate_action_binding(action_binding) {
-                    return Err(UiDefinitionError::RegistryValidation(err.to_string()));
+                    report.error(format!("actions.{}", action_name), UiDefinitionError::RegistryValidation(err.to_string()));
                 }
             }
         }
-        
-        Ok(())
+
+        report
     }
 
     /// Validate window configuration
-    fn validate_window_config(&self) -> Result<(), UiDefinitionError> {
+    fn check_window_config(&self, report: &mut ValidationReport) {
         if let Some(ref window) = self.window {
-            // Validate window size
-            if window.size[0] <= 0.0 || window.size[1] <= 0.0 {
-                return Err(UiDefinitionError::Validation(
-                    "Window size must be positive".to_string()
+            self.check_window(window, "window", report);
+        }
+
+        if let Some(ref windows) = self.windows {
+            for (id, declaration) in sorted_entries(windows) {
+                let path = format!("windows.{}", id);
+                if id.is_empty() {
+                    report.error(&path, UiDefinitionError::Validation(
+                        "Window id cannot be empty".to_string()
+                    ));
+                }
+                if declaration.layout.trim().is_empty() {
+                    report.error(format!("{}.layout", path), UiDefinitionError::Validation(
+                        "Window layout path cannot be empty".to_string()
+                    ));
+                }
+                self.check_window(&declaration.config, &path, report);
+            }
+        }
+    }
+
+    fn check_window(&self, window: &WindowConfig, path: &str, report: &mut ValidationReport) {
+        // Validate window size
+        if window.size[0] <= 0.0 || window.size[1] <= 0.0 {
+            report.error(format!("{}.size", path), UiDefinitionError::Validation(
+                "Window size must be positive".to_string()
+            ));
+        }
+
+        // Validate background color if present
+        if let Some(ref color) = window.background_color {
+            self.check_color_def(color, &format!("{}.background_color", path), report);
+        }
+
+        // Validate size limits
+        for (field, limit) in [("min_size", window.min_size), ("max_size", window.max_size)] {
+            if let Some([width, height]) = limit {
+                if width <= 0.0 || height <= 0.0 {
+                    report.error(format!("{}.{}", path, field), UiDefinitionError::Validation(
+                        format!("Window {} must be positive", field)
+                    ));
+                }
+            }
+        }
+        if let (Some(min_size), Some(max_size)) = (window.min_size, window.max_size) {
+            if min_size[0] > max_size[0] || min_size[1] > max_size[1] {
+                report.error(format!("{}.max_size", path), UiDefinitionError::Validation(
+                    "Window max_size must not be smaller than min_size".to_string()
                 ));
             }
-            
-            // Validate background color if present
-            if let Some(ref color) = window.background_color {
-                self.validate_color_def(color)?;
+        }
+        let below_min = window.min_size.is_some_and(|min_size| window.size[0] < min_size[0] || window.size[1] < min_size[1]);
+        let above_max = window.max_size.is_some_and(|max_size| window.size[0] > max_size[0] || window.size[1] > max_size[1]);
+        if below_min || above_max {
+            report.warning(format!("{}.size", path), "Window size is outside min_size..max_size and will be clamped");
+        }
+
+        // Validate icon path
+        if let Some(ref icon) = window.icon {
+            if icon.trim().is_empty() {
+                report.error(format!("{}.icon", path), UiDefinitionError::Validation(
+                    "Window icon path cannot be empty".to_string()
+                ));
+            } else if !icon.to_ascii_lowercase().ends_with(".png") {
+                report.warning(format!("{}.icon", path), "Window icons must be PNG images");
             }
         }
-        Ok(())
     }
 
     /// Validate global styles
-    fn validate_global_styles(&self) -> Result<(), UiDefinitionError> {
+    fn check_global_styles(&self, report: &mut ValidationReport) {
         if let Some(ref styles) = self.styles {
-            for (style_name, style_overrides) in styles {
+            for (style_name, style_overrides) in sorted_entries(styles) {
+                let path = format!("styles.{}", style_name);
                 if style_name.is_empty() {
-                    return Err(UiDefinitionError::Validation(
+                    report.error(&path, UiDefinitionError::Validation(
                         "Style class name cannot be empty".to_string()
                     ));
                 }
+
+                // Validate inheritance
+                for parent in style_overrides.extends.iter().flatten() {
+                    if !styles.contains_key(parent) {
+                        report.error(format!("{}.extends", path), UiDefinitionError::UnknownStyleClass(parent.clone()));
+                    }
+                }
+                if let Some(cycle) = style_class_cycle(styles, style_name) {
+                    report.error(format!("{}.extends", path), UiDefinitionError::Validation(
+                        format!("Style class inheritance cycle: {}", cycle)
+                    ));
+                }
                 
                 // Validate color definitions in style overrides
                 if let Some(ref color) = style_overrides.background_color {
-                    self.validate_color_def(color)?;
+                    self.check_color_def(color, &format!("{}.background_color", path), report);
                 }
                 if let Some(ref color) = style_overrides.border_color {
-                    self.validate_color_def(color)?;
+                    self.check_color_def(color, &format!("{}.border_color", path), report);
                 }
                 if let Some(ref color) = style_overrides.text_color {
-                    self.validate_color_def(color)?;
+                    self.check_color_def(color, &format!("{}.text_color", path), report);
                 }
                 
                 // Validate numeric values
                 if let Some(width) = style_overrides.border_width {
                     if width < 0.0 {
-                        return Err(UiDefinitionError::Validation(
+                        report.error(format!("{}.border_width", path), UiDefinitionError::Validation(
                             format!("Border width must be non-negative in style '{}'", style_name)
                         ));
                     }
@@ -176,7 +367,7 @@ impl UiDefinition {
                 
                 if let Some(radius) = style_overrides.border_radius {
                     if radius < 0.0 {
-                        return Err(UiDefinitionError::Validation(
+                        report.error(format!("{}.border_radius", path), UiDefinitionError::Validation(
                             format!("Border radius must be non-negative in style '{}'", style_name)
                         ));
                     }
@@ -184,7 +375,7 @@ impl UiDefinition {
                 
                 if let Some(size) = style_overrides.text_size {
                     if size <= 0.0 {
-                        return Err(UiDefinitionError::Validation(
+                        report.error(format!("{}.text_size", path), UiDefinitionError::Validation(
                             format!("Text size must be positive in style '{}'", style_name)
                         ));
                     }
@@ -192,165 +383,127 @@ impl UiDefinition {
                 
                 if let Some(opacity) = style_overrides.opacity {
                     if !(0.0..=1.0).contains(&opacity) {
-                        return Err(UiDefinitionError::Validation(
+                        report.error(format!("{}.opacity", path), UiDefinitionError::Validation(
                             format!("Opacity must be between 0.0 and 1.0 in style '{}'", style_name)
                         ));
                     }
                 }
             }
         }
-        Ok(())
     }
 
     /// Validate global actions
-    fn validate_global_actions(&self) -> Result<(), UiDefinitionError> {
+    fn check_global_actions(&self, report: &mut ValidationReport) {
         if let Some(ref actions) = self.actions {
-            for (action_name, action_binding) in actions {
+            for (action_name, action_binding) in sorted_entries(actions) {
+                let path = format!("actions.{}", action_name);
                 if action_name.is_empty() {
-                    return Err(UiDefinitionError::Validation(
+                    report.error(&path, UiDefinitionError::Validation(
                         "Action name cannot be empty".to_string()
                     ));
                 }
                 
                 if action_binding.event.is_empty() {
-                    return Err(UiDefinitionError::Validation(
+                    report.error(format!("{}.event", path), UiDefinitionError::Validation(
                         format!("Event type cannot be empty for action '{}'", action_name)
                     ));
+                } else {
+                    // Validate known event types
+                    if !VALID_EVENTS.contains(&action_binding.event.as_str()) {
+                        report.error(format!("{}.event", path), UiDefinitionError::Validation(
+                            format!("Unknown event type '{}' for action '{}'", action_binding.event, action_name)
+                        ));
+                    }
                 }
                 
                 if action_binding.action.is_empty() {
-                    return Err(UiDefinitionError::Validation(
+                    report.error(format!("{}.action", path), UiDefinitionError::Validation(
                         format!("Action string cannot be empty for action '{}'", action_name)
                     ));
                 }
-                
-                // Validate known event types
-                let valid_events = ["click", "hover", "focus", "blur", "change", "submit"];
-                if !valid_events.contains(&action_binding.event.as_str()) {
-                    return Err(UiDefinitionError::Validation(
-                        format!("Unknown event type '{}' for action '{}'", action_binding.event, action_name)
-                    ));
-                }
             }
         }
-        Ok(())
     }
 
-    /// Validate a color definition
-    fn validate_color_def(&self, color: &crate::widgets::blueprint::ColorDef) -> Result<(), UiDefinitionError> {
-        match color {
-            crate::widgets::blueprint::ColorDef::Hex(hex) => {
-                let hex = hex.trim_start_matches('#');
-                if hex.len() != 6 && hex.len() != 8 {
-                    return Err(UiDefinitionError::Validation(
-                        format!("Invalid hex color format: '{:?}'. Expected #RRGGBB or #RRGGBBAA", color)
-                    ));
-                }
-                
-                // Check if all characters are valid hex
-                if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                    return Err(UiDefinitionError::Validation(
-                        format!("Invalid hex color format: '{:?}'. Contains non-hex characters", color)
-                    ));
-                }
-            }
-            crate::widgets::blueprint::ColorDef::Rgba { a, .. } => {
-                if !(0.0..=1.0).contains(a) {
-                    return Err(UiDefinitionError::Validation(
-                        "Alpha channel must be between 0.0 and 1.0".to_string()
-                    ));
-                }
-            }
-            crate::widgets::blueprint::ColorDef::Named(name) => {
-                let valid_names = ["red", "green", "blue", "black", "white", "gray", "grey", 
-                                 "yellow", "cyan", "magenta", "orange", "transparent"];
-                if !valid_names.contains(&name.to_lowercase().as_str()) {
-                    return Err(UiDefinitionError::Validation(
-                        format!("Unknown named color: '{}'", name)
-                    ));
-                }
-            }
-            _ => {} // RGB is always valid since u8 values are constrained
+    /// Validate a color definition with the parser that converts it
+    fn check_color_def(&self, color: &crate::widgets::blueprint::ColorDef, path: &str, report: &mut ValidationReport) {
+        if let Err(e) = color.parse() {
+            report.error(path, UiDefinitionError::Validation(e.to_string()));
         }
-        Ok(())
     }
 
     /// Recursively validate a widget node and its children
-    fn validate_widget_node(&self, node: &WidgetNode, used_ids: &HashSet<String>) -> Result<(), UiDefinitionError> {
+    fn check_widget_node(&self, node: &WidgetNode, path: &str, used_ids: &HashSet<String>, report: &mut ValidationReport) {
         // Check for duplicate IDs
         if let Some(ref id) = node.id {
             if used_ids.contains(id) {
-                return Err(UiDefinitionError::DuplicateId(id.clone()));
+                report.error(format!("{}.id", path), UiDefinitionError::DuplicateId(id.clone()));
             }
             
             // Validate ID format
-            if id.is_empty() {
-                return Err(UiDefinitionError::Validation("Widget ID cannot be empty".to_string()));
-            }
-            
-            // ID should only contain alphanumeric characters, underscore, and hyphen
-            if !id.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
-                return Err(UiDefinitionError::Validation(
-                    format!("Widget ID '{}' contains invalid characters. Only alphanumeric, underscore, and hyphen are allowed", id)
-                ));
+            if let Some(problem) = widget_id_problem(id) {
+                report.error(format!("{}.id", path), UiDefinitionError::Validation(problem));
             }
         }
 
         // Validate widget type specific constraints
-        self.validate_widget_type(&node.widget_type)?;
+        self.check_widget_type(&node.widget_type, &format!("{}.widget_type", path), report);
+
+        // Tabs show their children as pages, one per tab, so the children have to line up
+        if let WidgetType::Tabs { tabs, .. } = &node.widget_type {
+            if node.children.len() != tabs.len() || node.children.iter().any(|child| child.when.is_some() || child.repeat.is_some()) {
+                report.error(format!("{}.children", path), UiDefinitionError::Validation(format!(
+                    "Tabs needs exactly one page per tab ({} tabs, {} children), without 'when' or 'repeat'",
+                    tabs.len(), node.children.len()
+                )));
+            }
+        }
 
         // Validate layout configuration
-        self.validate_layout_config(&node.layout)?;
+        self.check_layout_config(&node.layout, &format!("{}.layout", path), report);
 
         // Validate style configuration
-        self.validate_style_config(&node.style)?;
+        self.check_style_config(&node.style, &format!("{}.style", path), report);
 
         // Validate behavior configuration
-        self.validate_behavior_config(&node.behavior)?;
+        self.check_behavior_config(&node.behavior, &format!("{}.behavior", path), report);
 
         // Validate style classes exist
         if let Some(ref classes) = node.classes {
             if let Some(ref global_styles) = self.styles {
                 for class_name in classes {
                     if !global_styles.contains_key(class_name) {
-                        return Err(UiDefinitionError::UnknownStyleClass(class_name.clone()));
+                        report.error(format!("{}.classes", path), UiDefinitionError::UnknownStyleClass(class_name.clone()));
                     }
                 }
             } else if !classes.is_empty() {
-                return Err(UiDefinitionError::StyleClassesWithoutGlobalStyles);
+                report.error(format!("{}.classes", path), UiDefinitionError::StyleClassesWithoutGlobalStyles);
             }
         }
 
         // Validate style overrides
         if let Some(ref overrides) = node.style_overrides {
-            self.validate_style_overrides(overrides)?;
+            self.check_style_overrides(overrides, &format!("{}.style_overrides", path), report);
+            if overrides.extends.is_some() {
+                report.error(format!("{}.style_overrides.extends", path), UiDefinitionError::Validation(
+                    "'extends' is only allowed on style classes; list classes in 'classes' instead".to_string()
+                ));
+            }
         }
 
         // Validate action bindings
         if let Some(ref bindings) = node.bindings {
-            for (event_name, binding) in bindings {
+            for (event_name, binding) in sorted_entries(bindings) {
+                let binding_path = format!("{}.bindings.{}", path, event_name);
                 if event_name.is_empty() {
-                    return Err(UiDefinitionError::Validation("Binding event name cannot be empty".to_string()));
+                    report.error(&binding_path, UiDefinitionError::Validation("Binding event name cannot be empty".to_string()));
                 }
-                
-                // Check if action is defined in global actions first
-                let is_global_action = if let Some(ref global_actions) = self.actions {
-                    global_actions.contains_key(&binding.action)
-                } else {
-                    false
-                };
-                
-                // If not a global action, check if it's a built-in action
-                if !is_global_action {
-                    let builtin_actions = [
-                        "debug", "navigate", "toggle_visibility", "update_text", "set_focus",
-                        "navigate_home", "open_settings"
-                    ];
-                    
-                    if !builtin_actions.contains(&binding.action.as_str()) {
-                        return Err(UiDefinitionError::UnknownAction(binding.action.clone()));
-                    }
+                if binding.action.is_empty() {
+                    report.error(format!("{}.action", binding_path), UiDefinitionError::Validation("Binding action cannot be empty".to_string()));
                 }
+
+                // Whether the action exists is up to the registry, which also knows the actions
+                // added by manifests and extensions (see `validation_report_with_registry`)
             }
         }
 
@@ -362,254 +515,213 @@ impl UiDefinition {
 
         // Check for duplicate IDs among siblings
         let mut sibling_ids = HashSet::new();
-        for child in &node.children {
+        for (index, child) in node.children.iter().enumerate() {
             if let Some(ref child_id) = child.id {
-                if sibling_ids.contains(child_id) {
-                    return Err(UiDefinitionError::DuplicateId(child_id.clone()));
+                if !sibling_ids.insert(child_id.clone()) {
+                    report.error(format!("{}.children[{}].id", path, index), UiDefinitionError::DuplicateId(child_id.clone()));
                 }
-                sibling_ids.insert(child_id.clone());
             }
         }
 
-        for child in &node.children {
-            self.validate_widget_node(child, &child_ids)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_widget_node(child, &format!("{}.children[{}]", path, index), &child_ids, report);
         }
-
-        Ok(())
     }
 
     /// Validate widget type specific constraints
-    fn validate_widget_type(&self, widget_type: &WidgetType) -> Result<(), UiDefinitionError> {
+    fn check_widget_type(&self, widget_type: &WidgetType, path: &str, report: &mut ValidationReport) {
         match widget_type {
-            WidgetType::Button { text, .. } => {
-                if let Some(text) = text {
-                    if text.is_empty() {
-                        return Err(UiDefinitionError::Validation("Button text cannot be empty".to_string()));
-                    }
+            WidgetType::Button { text: Some(text), .. } if text.is_empty() => {
+                report.error(path, UiDefinitionError::Validation("Button text cannot be empty".to_string()));
+            }
+            WidgetType::Checkbox { label: Some(label), .. } | WidgetType::Toggle { label: Some(label), .. } if label.is_empty() => {
+                report.error(path, UiDefinitionError::Validation("Label cannot be empty; leave it out for no label".to_string()));
+            }
+            WidgetType::Slider { .. } | WidgetType::DragValue { .. } => {
+                if let Err(e) = validate_numeric_widget(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
+                }
+            }
+            WidgetType::TextInput { .. } => {
+                if let Err(e) = validate_text_input(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
                 }
             }
-            WidgetType::Text { content, .. } => {
-                // Text content can be empty (for placeholder text)
-                if content.len() > 10000 {
-                    return Err(UiDefinitionError::Validation("Text content too long (max 10000 characters)".to_string()));
+            WidgetType::Dropdown { .. } => {
+                if let Err(e) = validate_dropdown(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
                 }
             }
+            WidgetType::Popup { .. } => {
+                if let Err(e) = validate_popup(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
+                }
+            }
+            WidgetType::Tabs { .. } => {
+                if let Err(e) = validate_tabs(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
+                }
+            }
+            WidgetType::ScrollView { .. } => {
+                if let Err(e) = validate_scroll_view(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
+                }
+            }
+            WidgetType::VirtualList { .. } => {
+                if let Err(e) = validate_virtual_list(widget_type) {
+                    report.error(format!("{}.{}", path, e.property()), UiDefinitionError::Validation(e.to_string()));
+                }
+            }
+            // Text content can be empty (for placeholder text)
+            WidgetType::Text { content, .. } if content.len() > 10000 => {
+                report.error(path, UiDefinitionError::Validation("Text content too long (max 10000 characters)".to_string()));
+            }
             _ => {} // Other widget types don't have specific validation yet
         }
-        Ok(())
     }
 
     /// Validate layout configuration
-    fn validate_layout_config(&self, layout: &LayoutConfig) -> Result<(), UiDefinitionError> {
+    fn check_layout_config(&self, layout: &LayoutConfig, path: &str, report: &mut ValidationReport) {
         if let Some(size) = layout.size {
             if size.x < 0.0 || size.y < 0.0 {
-                return Err(UiDefinitionError::Validation("Layout size must be non-negative".to_string()));
+                report.error(format!("{}.size", path), UiDefinitionError::Validation("Layout size must be non-negative".to_string()));
             }
             if size.x > 10000.0 || size.y > 10000.0 {
-                return Err(UiDefinitionError::Validation("Layout size is unreasonably large".to_string()));
+                report.error(format!("{}.size", path), UiDefinitionError::Validation("Layout size is unreasonably large".to_string()));
             }
         }
 
         if let Some(flex_grow) = layout.flex_grow {
             if flex_grow < 0.0 {
-                return Err(UiDefinitionError::Validation("Flex grow must be non-negative".to_string()));
+                report.error(format!("{}.flex_grow", path), UiDefinitionError::Validation("Flex grow must be non-negative".to_string()));
             }
         }
 
         if let Some(flex_shrink) = layout.flex_shrink {
             if flex_shrink < 0.0 {
-                return Err(UiDefinitionError::Validation("Flex shrink must be non-negative".to_string()));
+                report.error(format!("{}.flex_shrink", path), UiDefinitionError::Validation("Flex shrink must be non-negative".to_string()));
             }
         }
-
-        Ok(())
     }
 
     /// Validate style configuration
-    fn validate_style_config(&self, style: &StyleConfig) -> Result<(), UiDefinitionError> {
+    fn check_style_config(&self, style: &StyleConfig, path: &str, report: &mut ValidationReport) {
         if let Some(ref color) = style.background_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.background_color", path), report);
         }
         if let Some(ref color) = style.border_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.border_color", path), report);
         }
         if let Some(ref color) = style.text_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.text_color", path), report);
         }
 
         if let Some(width) = style.border_width {
             if width < 0.0 {
-                return Err(UiDefinitionError::Validation("Border width must be non-negative".to_string()));
+                report.error(format!("{}.border_width", path), UiDefinitionError::Validation("Border width must be non-negative".to_string()));
             }
         }
 
         if let Some(radius) = style.border_radius {
             if radius < 0.0 {
-                return Err(UiDefinitionError::Validation("Border radius must be non-negative".to_string()));
+                report.error(format!("{}.border_radius", path), UiDefinitionError::Validation("Border radius must be non-negative".to_string()));
             }
         }
 
         if let Some(size) = style.text_size {
             if size <= 0.0 {
-                return Err(UiDefinitionError::Validation("Text size must be positive".to_string()));
+                report.error(format!("{}.text_size", path), UiDefinitionError::Validation("Text size must be positive".to_string()));
             }
         }
 
         if let Some(opacity) = style.opacity {
             if !(0.0..=1.0).contains(&opacity) {
-                return Err(UiDefinitionError::Validation("Opacity must be between 0.0 and 1.0".to_string()));
+                report.error(format!("{}.opacity", path), UiDefinitionError::Validation("Opacity must be between 0.0 and 1.0".to_string()));
             }
         }
-
-        Ok(())
     }
 
     /// Validate behavior configuration
-    fn validate_behavior_config(&self, _behavior: &BehaviorConfig) -> Result<(), UiDefinitionError> {
+    fn check_behavior_config(&self, _behavior: &BehaviorConfig, _path: &str, _report: &mut ValidationReport) {
         // Most behavior settings are just booleans, which are always valid
         // Could add validation for z_index ranges if needed
-        Ok(())
     }
 
     /// Validate style overrides
-    fn validate_style_overrides(&self, overrides: &StyleOverrides) -> Result<(), UiDefinitionError> {
+    fn check_style_overrides(&self, overrides: &StyleOverrides, path: &str, report: &mut ValidationReport) {
         if let Some(ref color) = overrides.background_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.background_color", path), report);
         }
         if let Some(ref color) = overrides.border_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.border_color", path), report);
         }
         if let Some(ref color) = overrides.text_color {
-            self.validate_color_def(color)?;
+            self.check_color_def(color, &format!("{}.text_color", path), report);
         }
 
         if let Some(width) = overrides.border_width {
             if width < 0.0 {
-                return Err(UiDefinitionError::Validation("Border width override must be non-negative".to_string()));
+                report.error(format!("{}.border_width", path), UiDefinitionError::Validation("Border width override must be non-negative".to_string()));
             }
         }
 
         if let Some(radius) = overrides.border_radius {
             if radius < 0.0 {
-                return Err(UiDefinitionError::Validation("Border radius override must be non-negative".to_string()));
+                report.error(format!("{}.border_radius", path), UiDefinitionError::Validation("Border radius override must be non-negative".to_string()));
             }
         }
 
         if let Some(size) = overrides.text_size {
             if size <= 0.0 {
-                return Err(UiDefinitionError::Validation("Text size override must be positive".to_string()));
+                report.error(format!("{}.text_size", path), UiDefinitionError::Validation("Text size override must be positive".to_string()));
             }
         }
 
         if let Some(opacity) = overrides.opacity {
             if !(0.0..=1.0).contains(&opacity) {
-                return Err(UiDefinitionError::Validation("Opacity override must be between 0.0 and 1.0".to_string()));
+                report.error(format!("{}.opacity", path), UiDefinitionError::Validation("Opacity override must be between 0.0 and 1.0".to_string()));
             }
         }
-
-        Ok(())
     }
 
-    /// Recursively validate a widget node with registry-based validation
-    fn validate_widget_node_with_registry(&self, node: &WidgetNode, used_ids: &HashSet<String>, registry: &UiRegistry, depth: usize) -> Result<(), UiDefinitionError> {
-        // Validate nesting depth
+    /// Recursively validate a widget node against the registry.
+    /// Structural checks are covered by [`Self::check_widget_node`].
+    fn check_widget_node_with_registry(&self, node: &WidgetNode, path: &str, registry: &UiRegistry, depth: usize, report: &mut ValidationReport) {
+        // Validate nesting depth; deeper nodes would only repeat the same error
         if let Err(err) = registry.validate_nesting_depth(depth) {
-            return Err(UiDefinitionError::RegistryValidation(err.to_string()));
-        }
-
-        // Check for duplicate IDs (same as regular validation)
-        if let Some(ref id) = node.id {
-            if used_ids.contains(id) {
-                return Err(UiDefinitionError::DuplicateId(id.clone()));
-            }
-            
-            // Validate ID format
-            if id.is_empty() {
-                return Err(UiDefinitionError::Validation("Widget ID cannot be empty".to_string()));
-            }
-            
-            // ID should only contain alphanumeric characters, underscore, and hyphen
-            if !id.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
-                return Err(UiDefinitionError::Validation(
-                    format!("Widget ID '{}' contains invalid characters. Only alphanumeric, underscore, and hyphen are allowed", id)
-                ));
-            }
+            report.error(path, UiDefinitionError::RegistryValidation(err.to_string()));
+            return;
         }
 
         // Registry-based widget type validation
         if let Err(err) = registry.validate_widget_type(&node.widget_type) {
-            return Err(UiDefinitionError::RegistryValidation(err.to_string()));
+            report.error(format!("{}.widget_type", path), UiDefinitionError::RegistryValidation(err.to_string()));
         }
 
         // Validate widget children capability
         let has_children = !node.children.is_empty();
         if let Err(err) = registry.validate_widget_children(&node.widget_type, has_children) {
-            return Err(UiDefinitionError::RegistryValidation(err.to_string()));
-        }
-
-        // Validate layout configuration (use existing validation)
-        self.validate_layout_config(&node.layout)?;
-
-        // Validate style configuration (use existing validation)
-        self.validate_style_config(&node.style)?;
-
-        // Validate behavior configuration (use existing validation)
-        self.validate_behavior_config(&node.behavior)?;
-
-        // Validate style classes exist (use existing validation)
-        if let Some(ref classes) = node.classes {
-            if let Some(ref global_styles) = self.styles {
-                for class_name in classes {
-                    if !global_styles.contains_key(class_name) {
-                        return Err(UiDefinitionError::UnknownStyleClass(class_name.clone()));
-                    }
-                }
-            } else if !classes.is_empty() {
-                return Err(UiDefinitionError::StyleClassesWithoutGlobalStyles);
-            }
-        }
-
-        // Validate style overrides (use existing validation)
-        if let Some(ref overrides) = node.style_overrides {
-            self.validate_style_overrides(overrides)?;
+            report.error(format!("{}.children", path), UiDefinitionError::RegistryValidation(err.to_string()));
         }
 
         // Registry-based action binding validation
         if let Some(ref bindings) = node.bindings {
-            for (event_name, binding) in bindings {
-                if event_name.is_empty() {
-                    return Err(UiDefinitionError::Validation("Binding event name cannot be empty".to_string()));
+            for (event_name, binding) in sorted_entries(bindings) {
+                // Global actions are validated on their own
+                if self.actions.as_ref().is_some_and(|actions| actions.contains_key(&binding.action)) {
+                    continue;
                 }
-                
                 // Use registry to validate action bindings
-                if let Err(err) = registry.validate_action_binding(binding) {
-                    return Err(UiDefinitionError::RegistryValidation(err.to_string()));
+                if let Err(err) = registry.validate_widget_action_binding(&node.widget_type, binding) {
+                    report.error(format!("{}.bindings.{}", path, event_name), UiDefinitionError::RegistryValidation(err.to_string()));
                 }
             }
         }
 
-        // Check for duplicate IDs among siblings (same as regular validation)
-        let mut sibling_ids = HashSet::new();
-        for child in &node.children {
-            if let Some(ref child_id) = child.id {
-                if sibling_ids.contains(child_id) {
-                    return Err(UiDefinitionError::DuplicateId(child_id.clone()));
-                }
-                sibling_ids.insert(child_id.clone());
-            }
-        }
-
         // Recursively validate children with registry
-        let mut child_ids = used_ids.clone();
-        if let Some(ref id) = node.id {
-            child_ids.insert(id.clone());
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_widget_node_with_registry(child, &format!("{}.children[{}]", path, index), registry, depth + 1, report);
         }
-
-        for child in &node.children {
-            self.validate_widget_node_with_registry(child, &child_ids, registry, depth + 1)?;
-        }
-
-        Ok(())
     }
 
     /// Convert this hierarchical definition to a flat widget collection for backward compatibility
@@ -652,31 +764,12 @@ impl UiDefinition {
             self.collect_widgets_recursive(child, &child_id, widgets, counter);
         }
 
-        // Apply style overrides to create final style config
-        let mut final_style = node.style.clone();
-        
-        // Apply global styles first
-        if let Some(ref classes) = node.classes {
-            if let Some(ref global_styles) = self.styles {
-                for class_name in classes {
-                    if let Some(class_style) = global_styles.get(class_name) {
-                        Self::apply_style_overrides(&mut final_style, class_style);
-                    }
-                }
-            }
-        }
-
-        // Apply local style overrides last (highest priority)
-        if let Some(ref overrides) = node.style_overrides {
-            Self::apply_style_overrides(&mut final_style, overrides);
-        }
-
         // Create widget blueprint
         let blueprint = WidgetBlueprint {
             id: node_id.to_string(),
             widget_type: node.widget_type.clone(),
             layout: node.layout.clone(),
-            style: final_style,
+            style: self.resolve_node_style(node),
             behavior: node.behavior.clone(),
             children: child_ids,
         };
@@ -684,6 +777,71 @@ impl UiDefinition {
         widgets.insert(node_id.to_string(), blueprint);
     }
 
+    /// Resolve a global style class, applying the classes it `extends` (in order) before its
+    /// own values. Inheritance cycles are cut where they close; validation reports them.
+    pub fn resolve_style_class(&self, name: &str) -> Option<StyleOverrides> {
+        self.resolve_style_class_recursive(name, &mut Vec::new())
+    }
+
+    fn resolve_style_class_recursive(&self, name: &str, chain: &mut Vec<String>) -> Option<StyleOverrides> {
+        let class = self.styles.as_ref()?.get(name)?;
+        chain.push(name.to_string());
+
+        let mut resolved = StyleOverrides::default();
+        for parent in class.extends.iter().flatten() {
+            if chain.contains(parent) {
+                continue;
+            }
+            if let Some(parent_style) = self.resolve_style_class_recursive(parent, chain) {
+                resolved.merge(&parent_style);
+            }
+        }
+        resolved.merge(class);
+        resolved.extends = None;
+
+        chain.pop();
+        Some(resolved)
+    }
+
+    /// Final style of a node. The cascade runs from its classes in listed order, to the
+    /// node's own `style`, to its `style_overrides`; state variants cascade the same way.
+    pub fn resolve_node_style(&self, node: &WidgetNode) -> StyleConfig {
+        let mut cascade = StyleOverrides::default();
+        for class_name in node.classes.iter().flatten() {
+            if let Some(class_style) = self.resolve_style_class(class_name) {
+                cascade.merge(&class_style);
+            }
+        }
+        cascade.merge(&StyleOverrides::from(&node.style));
+        if let Some(ref overrides) = node.style_overrides {
+            cascade.merge(overrides);
+        }
+
+        let mut style = StyleConfig::default();
+        Self::apply_style_overrides(&mut style, &cascade);
+        style
+    }
+
+    /// Copy of `node` without its children, with the style cascade resolved into `style`
+    pub fn styled_node(&self, node: &WidgetNode) -> WidgetNode {
+        let style = self.resolve_node_style(node);
+        WidgetNode {
+            id: node.id.clone(),
+            widget_type: node.widget_type.clone(),
+            layout: node.layout.clone(),
+            style,
+            behavior: node.behavior.clone(),
+            classes: node.classes.clone(),
+            style_overrides: node.style_overrides.clone(),
+            bindings: node.bindings.clone(),
+            state_bindings: node.state_bindings.clone(),
+            repeat: node.repeat.clone(),
+            when: node.when.clone(),
+            localized: node.localized.clone(),
+            children: vec![],
+        }
+    }
+
     /// Apply style overrides to a style config
     fn apply_style_overrides(style: &mut StyleConfig, overrides: &StyleOverrides) {
         if let Some(ref color) = overrides.background_color {
@@ -707,11 +865,17 @@ impl UiDefinition {
         if let Some(opacity) = overrides.opacity {
             style.opacity = Some(opacity);
         }
+        if let Some(ref states) = overrides.states {
+            style.states = Some(match style.states {
+                Some(ref base) => base.merged_with(states),
+                None => states.clone(),
+            });
+        }
     }
 }
 
 /// Errors that can occur during UI definition validation
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, PartialEq)]
 pub enum UiDefinitionError {
     #[error("Duplicate widget ID: {0}")]
     DuplicateId(String),
@@ -725,4 +889,55 @@ pub enum UiDefinitionError {
     Validation(String),
     #[error("Registry validation error: {0}")]
     RegistryValidation(String),
+}
+
+/// Pattern every widget ID has to match, also used for `id` in the JSON Schema
+pub const WIDGET_ID_PATTERN: &str = "^[A-Za-z_-][A-Za-z0-9_-]*$";
+
+/// Longest allowed widget ID, in characters
+pub const WIDGET_ID_MAX_LENGTH: usize = 100;
+
+/// What is wrong with a widget ID, or `None` if it follows `WIDGET_ID_PATTERN` and `WIDGET_ID_MAX_LENGTH`
+pub fn widget_id_problem(id: &str) -> Option<String> {
+    static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| regex::Regex::new(WIDGET_ID_PATTERN).expect("WIDGET_ID_PATTERN is a valid regex"));
+    if id.is_empty() {
+        Some("Widget ID cannot be empty".to_string())
+    } else if id.chars().count() > WIDGET_ID_MAX_LENGTH {
+        Some(format!("Widget ID '{}' is too long (max {} characters)", id, WIDGET_ID_MAX_LENGTH))
+    } else if !pattern.is_match(id) {
+        Some(format!("Widget ID '{}' must start with a letter, underscore or hyphen and only contain letters, digits, underscores and hyphens", id))
+    } else {
+        None
+    }
+}
+
+/// The inheritance cycle through style class `name`, as `a -> b -> a`, if there is one
+fn style_class_cycle(styles: &HashMap<String, StyleOverrides>, name: &str) -> Option<String> {
+    fn visit(styles: &HashMap<String, StyleOverrides>, current: &str, chain: &mut Vec<String>, visited: &mut HashSet<String>) -> Option<String> {
+        for parent in styles.get(current)?.extends.iter().flatten() {
+            if *parent == chain[0] {
+                chain.push(parent.clone());
+                return Some(chain.join(" -> "));
+            }
+            if !visited.insert(parent.clone()) {
+                continue;
+            }
+            chain.push(parent.clone());
+            if let Some(cycle) = visit(styles, parent, chain, visited) {
+                return Some(cycle);
+            }
+            chain.pop();
+        }
+        None
+    }
+
+    visit(styles, name, &mut vec![name.to_string()], &mut HashSet::new())
+}
+
+/// Map entries in key order, so reports list them deterministically
+pub(crate) fn sorted_entries<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
 }
\ No newline at end of file
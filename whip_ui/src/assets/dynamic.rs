@@ -0,0 +1,494 @@
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use crate::layout::{TaffyResource, UiNode};
+use crate::widgets::blueprint::WidgetType;
+use crate::widgets::components::{DynamicChildSlot, RepeatedItem, WidgetDynamicChildren, WidgetHierarchy};
+use crate::YrsDocResource;
+use super::{LoadingUiAssets, UiDefinition, UiDefinitionError, UiRegistry, UiState, ValidationReport, definitions::WidgetNode};
+use super::reload::{collect_subtree, despawn_widgets};
+use super::state::{is_valid_state_segment, STATE_KEY};
+use super::systems::spawn_child_widget;
+use super::windows::WidgetWindows;
+use super::widget_templates::{next_placeholder, scope_ids, whole_placeholder};
+
+/// Name items are referred to by in a repeat template when `as` is not given
+const DEFAULT_ITEM_NAME: &str = "item";
+
+/// Errors that can occur while evaluating `when` and `repeat` nodes
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum DynamicNodeError {
+    #[error("Invalid condition '{0}': expected \"path\", \"!path\", \"path == value\" or \"path != value\"")]
+    InvalidCondition(String),
+    #[error("Repeat over '{each}' uses '{placeholder}', which items don't have")]
+    UnknownItemField { each: String, placeholder: String },
+    #[error("Repeat over '{each}' did not expand to a valid widget node: {reason}")]
+    InvalidNode { each: String, reason: String },
+}
+
+/// The `repeat` table of a node: its children are spawned once per element of a `List` state value.
+///
+/// ```toml
+/// [[root.children]]
+/// id = "shots"
+/// widget_type = { type = "Container", direction = "Column" }
+/// repeat = { each = "project.shots", key = "id", as = "shot" }
+///
+/// [[root.children.children]]
+/// id = "name"
+/// widget_type = { type = "Text", content = "{{shot.name}}", editable = false }
+/// ```
+///
+/// Items are matched by `key` when the list changes, so unchanged items keep their widgets.
+/// Ids in the template are prefixed with the node id and the item key, as in `shots_12_name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepeatConfig {
+    /// Path of the `List` state value to repeat over
+    pub each: String,
+    /// Field of each item that identifies it; items are keyed by position without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Name of the item in `{{placeholders}}`
+    #[serde(default, rename = "as", skip_serializing_if = "Option::is_none")]
+    pub item_name: Option<String>,
+}
+
+impl RepeatConfig {
+    /// Name of the item in `{{placeholders}}`
+    pub fn item_name(&self) -> &str {
+        self.item_name.as_deref().unwrap_or(DEFAULT_ITEM_NAME)
+    }
+
+    /// The key identifying `item`, the element at `index`
+    pub fn item_key(&self, index: usize, item: &Value) -> String {
+        let key = self.key.as_ref().and_then(|field| item.get(field));
+        match key {
+            Some(Value::String(key)) => key.clone(),
+            Some(key) => key.to_string(),
+            None => index.to_string(),
+        }
+    }
+}
+
+/// Condition of a `when` node, read from strings such as `"project.dirty"`, `"!project.dirty"`
+/// or `"view.mode == 'grid'"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateCondition {
+    /// Path of the state value the condition reads
+    pub path: String,
+    pub test: ConditionTest,
+}
+
+/// How a `StateCondition` tests its state value
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionTest {
+    /// `true`, a non-zero number, or a non-empty string or list
+    Truthy,
+    Falsy,
+    Equals(Value),
+    NotEquals(Value),
+}
+
+impl StateCondition {
+    pub fn parse(source: &str) -> Result<Self, DynamicNodeError> {
+        let invalid = || DynamicNodeError::InvalidCondition(source.to_string());
+        let source = source.trim();
+
+        let (path, test) = if let Some((path, literal)) = source.split_once("==") {
+            (path, ConditionTest::Equals(parse_literal(literal)))
+        } else if let Some((path, literal)) = source.split_once("!=") {
+            (path, ConditionTest::NotEquals(parse_literal(literal)))
+        } else if let Some(path) = source.strip_prefix('!') {
+            (path, ConditionTest::Falsy)
+        } else {
+            (source, ConditionTest::Truthy)
+        };
+
+        let path = path.trim();
+        if path.split('.').any(|segment| !is_valid_state_segment(segment)) {
+            return Err(invalid());
+        }
+        Ok(Self { path: path.to_string(), test })
+    }
+
+    /// Whether the condition holds for the current state; undeclared values count as null
+    pub fn evaluate(&self, state: &UiState) -> bool {
+        let value = state.get(&self.path).unwrap_or(&Value::Null);
+        match &self.test {
+            ConditionTest::Truthy => is_truthy(value),
+            ConditionTest::Falsy => !is_truthy(value),
+            ConditionTest::Equals(expected) => values_equal(value, expected),
+            ConditionTest::NotEquals(expected) => !values_equal(value, expected),
+        }
+    }
+}
+
+/// A literal on the right of `==`/`!=`: JSON, a single-quoted string, or bare text
+fn parse_literal(literal: &str) -> Value {
+    let literal = literal.trim();
+    if let Some(text) = literal.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')) {
+        return Value::String(text.to_string());
+    }
+    serde_json::from_str(literal).unwrap_or_else(|_| Value::String(literal.to_string()))
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+/// Compare numbers by value so `1` equals `1.0`
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Sort a node's children into static children, spawned through `spawn_static`, and the dynamic
+/// slots that `update_dynamic_children_system` fills in.
+///
+/// Returns the static child entities and, if the node has any `when` children or is a `repeat`
+/// node, the component tracking its slots. Children with invalid conditions are skipped.
+pub(crate) fn dynamic_slots(
+    node: &WidgetNode,
+    mut spawn_static: impl FnMut(&WidgetNode) -> Entity,
+) -> (Vec<Entity>, Option<WidgetDynamicChildren>) {
+    let dynamic = |slots| WidgetDynamicChildren { slots, parent_position: node.layout.position };
+
+    // The child of a virtual list is its row template, spawned by virtual_list_rows_system
+    if matches!(node.widget_type, WidgetType::VirtualList { .. }) {
+        return (Vec::new(), None);
+    }
+
+    if let Some(ref config) = node.repeat {
+        let slot = DynamicChildSlot::Repeat {
+            config: config.clone(),
+            host_id: node.id.clone().unwrap_or_else(|| "repeat".to_string()),
+            template: node.children.clone(),
+            items: Vec::new(),
+        };
+        return (Vec::new(), Some(dynamic(vec![slot])));
+    }
+
+    let mut static_children = Vec::new();
+    let mut slots = Vec::new();
+    for child in &node.children {
+        match child.when.as_deref().map(StateCondition::parse) {
+            None => {
+                let entity = spawn_static(child);
+                static_children.push(entity);
+                slots.push(DynamicChildSlot::Static(entity));
+            }
+            Some(Ok(condition)) => slots.push(DynamicChildSlot::When { condition, node: Box::new(child.clone()), entity: None }),
+            Some(Err(e)) => error!("Skipping conditional widget {:?}: {}", child.id, e),
+        }
+    }
+
+    let has_dynamic = slots.iter().any(|slot| !matches!(slot, DynamicChildSlot::Static(_)));
+    (static_children, has_dynamic.then(|| dynamic(slots)))
+}
+
+/// Build the nodes spawned for one element of a repeat: the template with item placeholders
+/// filled in and ids scoped to `{host_id}_{key}`
+pub fn expand_repeat_item(
+    config: &RepeatConfig,
+    host_id: &str,
+    template: &[WidgetNode],
+    key: &str,
+    item: &Value,
+) -> Result<Vec<WidgetNode>, DynamicNodeError> {
+    let item_name = config.item_name();
+    let mut fields = HashMap::new();
+    flatten_item(item_name, item, &mut fields);
+
+    template.iter()
+        .map(|node| {
+            let invalid = |e: serde_json::Error| DynamicNodeError::InvalidNode { each: config.each.clone(), reason: e.to_string() };
+            let mut document = serde_json::to_value(node).map_err(invalid)?;
+            fill_item_placeholders(&mut document, item_name, &fields, config)?;
+            let mut node: WidgetNode = serde_json::from_value(document).map_err(invalid)?;
+            scope_ids(&mut node, Some(&format!("{}_{}", host_id, key)));
+            Ok(node)
+        })
+        .collect()
+}
+
+/// Index an item and its nested fields by placeholder name (`shot`, `shot.name`, ...)
+fn flatten_item(name: &str, value: &Value, fields: &mut HashMap<String, Value>) {
+    if let Value::Object(entries) = value {
+        for (key, entry) in entries {
+            flatten_item(&format!("{}.{}", name, key), entry, fields);
+        }
+    }
+    fields.insert(name.to_string(), value.clone());
+}
+
+/// Replace placeholders naming the item; others (e.g. for a nested repeat) are left alone
+fn fill_item_placeholders(value: &mut Value, item_name: &str, fields: &HashMap<String, Value>, config: &RepeatConfig) -> Result<(), DynamicNodeError> {
+    let refers_to_item = |name: &str| name == item_name || name.starts_with(&format!("{}.", item_name));
+    let lookup = |name: &str| fields.get(name).ok_or_else(|| DynamicNodeError::UnknownItemField {
+        each: config.each.clone(),
+        placeholder: name.to_string(),
+    });
+
+    if let Some(name) = value.as_str().and_then(whole_placeholder).filter(|name| refers_to_item(name)) {
+        *value = lookup(name)?.clone();
+        return Ok(());
+    }
+
+    match value {
+        Value::String(text) => {
+            let mut result = String::new();
+            let mut rest = text.as_str();
+            while let Some((before, name, after)) = next_placeholder(rest) {
+                result.push_str(before);
+                let consumed = rest.len() - after.len();
+                if refers_to_item(name) {
+                    result.push_str(&super::state::display_value(lookup(name)?));
+                } else {
+                    result.push_str(&rest[before.len()..consumed]);
+                }
+                rest = after;
+            }
+            result.push_str(rest);
+            *text = result;
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                fill_item_placeholders(item, item_name, fields, config)?;
+            }
+        }
+        Value::Object(entries) => {
+            for entry in entries.values_mut() {
+                fill_item_placeholders(entry, item_name, fields, config)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// What the system needs to spawn children of one dynamic widget
+struct DynamicSpawner<'a> {
+    ui_definition: &'a UiDefinition,
+    registry: &'a UiRegistry,
+    yrs_res: &'a YrsDocResource,
+    host: Entity,
+    window_height: f32,
+    parent_position: Option<bevy_math::Vec3>,
+}
+
+impl DynamicSpawner<'_> {
+    fn spawn(&self, commands: &mut Commands, node: &WidgetNode) -> Entity {
+        spawn_child_widget(commands, node, self.ui_definition, self.registry, self.yrs_res, self.host, self.window_height, self.parent_position)
+    }
+}
+
+/// System that spawns and despawns `when` and `repeat` children as the state they read changes.
+///
+/// Conditional children appear and disappear with their condition. Repeated items are matched by
+/// key: unchanged items keep their widgets, changed items are respawned and removed items are
+/// despawned along with their Taffy nodes and yrs text.
+pub fn update_dynamic_children_system(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    registry: Res<UiRegistry>,
+    yrs_res: Res<YrsDocResource>,
+    loading_assets: Res<LoadingUiAssets>,
+    ui_assets: Res<Assets<UiDefinition>>,
+    taffy_resource: Res<TaffyResource>,
+    mut hosts: Query<(Entity, &mut WidgetDynamicChildren)>,
+    hierarchies: Query<&WidgetHierarchy>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+    widget_windows: WidgetWindows,
+) {
+    for (host, mut dynamic) in hosts.iter_mut() {
+        // Newly spawned or reloaded widgets fill every slot; the rest react to state changes
+        if !dynamic.is_changed() && !dynamic.state_paths().any(|path| UiState::is_changed(&ui_state, path)) {
+            continue;
+        }
+        let Some(ui_definition) = owning_definition(host, &hierarchies, &loading_assets, &ui_assets) else {
+            continue;
+        };
+
+        // Syncing must not mark the component changed, or it would sync again every frame
+        let dynamic = dynamic.bypass_change_detection();
+        let spawner = DynamicSpawner {
+            ui_definition,
+            registry: &registry,
+            yrs_res: &yrs_res,
+            host,
+            window_height: widget_windows.height_of(host),
+            parent_position: dynamic.parent_position,
+        };
+
+        let mut removed = Vec::new();
+        for slot in dynamic.slots.iter_mut() {
+            match slot {
+                DynamicChildSlot::Static(_) => {}
+                DynamicChildSlot::When { condition, node, entity } => {
+                    match (condition.evaluate(&ui_state), *entity) {
+                        (true, None) => *entity = Some(spawner.spawn(&mut commands, node)),
+                        (false, Some(spawned)) => {
+                            removed.push(spawned);
+                            *entity = None;
+                        }
+                        _ => {}
+                    }
+                }
+                DynamicChildSlot::Repeat { config, host_id, template, items } => {
+                    sync_repeat(&mut commands, &spawner, &ui_state, config, host_id, template, items, &mut removed);
+                }
+            }
+        }
+
+        let mut stale = HashSet::new();
+        for entity in removed {
+            collect_subtree(entity, &hierarchies, &mut stale);
+        }
+        despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+
+        // Keep the widget hierarchy in slot order
+        let parent = hierarchies.get(host).ok().and_then(|hierarchy| hierarchy.parent);
+        commands.entity(host).insert(WidgetHierarchy {
+            parent,
+            children: dynamic.children(),
+        });
+    }
+}
+
+/// Bring the items of a repeat in line with its list
+#[allow(clippy::too_many_arguments)]
+fn sync_repeat(
+    commands: &mut Commands,
+    spawner: &DynamicSpawner,
+    ui_state: &UiState,
+    config: &RepeatConfig,
+    host_id: &str,
+    template: &[WidgetNode],
+    items: &mut Vec<RepeatedItem>,
+    removed: &mut Vec<Entity>,
+) {
+    let list = ui_state.get(&config.each).and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    let mut previous: HashMap<String, RepeatedItem> = items.drain(..)
+        .map(|item| (item.key.clone(), item))
+        .collect();
+
+    for (index, value) in list.iter().enumerate() {
+        let mut key = config.item_key(index, value);
+        if items.iter().any(|item| item.key == key) {
+            // Duplicate keys still need distinct widget ids
+            key = format!("{}-{}", key, index);
+        }
+        match previous.remove(&key) {
+            Some(item) if item.value == *value => items.push(item),
+            stale => {
+                removed.extend(stale.into_iter().flat_map(|item| item.entities));
+                let entities = match expand_repeat_item(config, host_id, template, &key, value) {
+                    Ok(nodes) => nodes.iter().map(|node| spawner.spawn(commands, node)).collect(),
+                    Err(e) => {
+                        error!("Failed to repeat item '{}' of '{}': {}", key, config.each, e);
+                        Vec::new()
+                    }
+                };
+                items.push(RepeatedItem { key, value: value.clone(), entities });
+            }
+        }
+    }
+
+    removed.extend(previous.into_values().flat_map(|item| item.entities));
+}
+
+/// The definition of the spawned tree `entity` belongs to
+pub(crate) fn owning_definition<'a>(
+    entity: Entity,
+    hierarchies: &Query<&WidgetHierarchy>,
+    loading_assets: &LoadingUiAssets,
+    ui_assets: &'a Assets<UiDefinition>,
+) -> Option<&'a UiDefinition> {
+    let mut current = entity;
+    loop {
+        if let Some(spawned) = loading_assets.spawned.iter().find(|spawned| spawned.root == current) {
+            return ui_assets.get(&spawned.handle);
+        }
+        current = hierarchies.get(current).ok()?.parent?;
+    }
+}
+
+impl UiDefinition {
+    /// Check `when` conditions and `repeat` tables against the declared state
+    pub(crate) fn check_dynamic_nodes(&self, report: &mut ValidationReport) {
+        if self.root.when.is_some() {
+            report.error("root.when", UiDefinitionError::Validation("The root node cannot be conditional".to_string()));
+        }
+        self.check_dynamic_node(&self.root, "root", report);
+    }
+
+    fn check_dynamic_node(&self, node: &WidgetNode, path: &str, report: &mut ValidationReport) {
+        if let Some(ref source) = node.when {
+            match StateCondition::parse(source) {
+                Ok(condition) => self.check_state_reference(&condition.path, None, &format!("{}.when", path), report),
+                Err(e) => report.error(format!("{}.when", path), UiDefinitionError::Validation(e.to_string())),
+            }
+        }
+
+        if let Some(ref config) = node.repeat {
+            let field = format!("{}.repeat", path);
+            self.check_state_reference(&config.each, Some("List"), &format!("{}.each", field), report);
+            if !is_valid_state_segment(config.item_name()) {
+                report.error(format!("{}.as", field), UiDefinitionError::Validation(format!(
+                    "Invalid item name '{}'", config.item_name()
+                )));
+            }
+            if node.children.is_empty() {
+                report.error(&field, UiDefinitionError::Validation(
+                    "A repeat node needs children to use as the item template".to_string()
+                ));
+            }
+        }
+
+        if let WidgetType::VirtualList { items, .. } = &node.widget_type {
+            if let Some(items) = items {
+                self.check_state_reference(items, Some("List"), &format!("{}.widget_type.items", path), report);
+            }
+            if node.children.len() != 1 {
+                report.error(format!("{}.children", path), UiDefinitionError::Validation(
+                    "A VirtualList needs exactly one child to use as the row template".to_string()
+                ));
+            }
+        }
+
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_dynamic_node(child, &format!("{}.children[{}]", path, index), report);
+        }
+    }
+
+    fn check_state_reference(&self, state_path: &str, expected_type: Option<&str>, field: &str, report: &mut ValidationReport) {
+        let Some(declaration) = self.state.as_ref().and_then(|state| state.get(state_path)) else {
+            report.error(field, UiDefinitionError::Validation(format!(
+                "State value '{}' is not declared in [{}]", state_path, STATE_KEY
+            )));
+            return;
+        };
+        if let Some(expected_type) = expected_type {
+            if declaration.state_type != expected_type {
+                report.error(field, UiDefinitionError::Validation(format!(
+                    "State value '{}' is of type {}, expected {}", state_path, declaration.state_type, expected_type
+                )));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,477 @@
+use bevy_asset::Assets;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::{SystemParam, SystemState};
+use bevy_hierarchy::Parent;
+use bevy_math::Vec3;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use serde_json::{Map, Value};
+use yrs::{GetString, Transact};
+use crate::assets::{LoadingUiAssets, UiDefinition, CURRENT_FORMAT_VERSION, UiDefinitionFormat, UiDefinitionLoaderError, definitions::WidgetNode, includes::{content_field, deep_merge, CONTENT_KEY, INCLUDE_KEY}, windows::WidgetWindows};
+use crate::layout::{BevyCoords, PositionControl};
+use crate::widgets::blueprint::{ColorDef, Spacing, WidgetType};
+use crate::widgets::components::*;
+use crate::widgets::templates::is_template_widget;
+use crate::widgets::dropdown::WidgetDropdown;
+use crate::widgets::numeric::WidgetNumeric;
+use crate::widgets::tabs::WidgetTabs;
+use crate::widgets::text_input::WidgetTextInput;
+use crate::widgets::toggle::WidgetToggle;
+use crate::YrsDocResource;
+
+/// Widget components read when writing the live tree back out
+type LiveWidgetQuery = (
+    &'static Widget,
+    &'static WidgetHierarchy,
+    &'static WidgetLayout,
+    &'static WidgetStyle,
+    &'static WidgetBehavior,
+    &'static Transform,
+    Option<&'static GlobalTransform>,
+    Option<&'static Parent>,
+    Option<&'static PositionControl>,
+    Option<&'static WidgetActionBindings>,
+    Option<&'static WidgetSourceNode>,
+    Option<&'static WidgetDynamicChildren>,
+    Option<&'static WidgetToggle>,
+    Option<&'static WidgetNumeric>,
+    Option<&'static WidgetDropdown>,
+);
+
+/// Read access to spawned widget trees for rebuilding their `UiDefinition`.
+///
+/// Each widget starts from the node it was spawned from and takes over its live state:
+/// dragged positions, text edited through yrs, the values of checkboxes, toggles, sliders, drag
+/// values, text inputs and dropdowns, whether popups are open, the order and active tab of tabs,
+/// and runtime changes to layout, style, behavior and action bindings.
+#[derive(SystemParam)]
+pub struct LiveUiTree<'w, 's> {
+    widgets: Query<'w, 's, LiveWidgetQuery>,
+    tabs: Query<'w, 's, &'static WidgetTabs>,
+    yrs_res: Res<'w, YrsDocResource>,
+    loading_assets: Res<'w, LoadingUiAssets>,
+    ui_assets: Res<'w, Assets<UiDefinition>>,
+    widget_windows: WidgetWindows<'w, 's>,
+}
+
+impl LiveUiTree<'_, '_> {
+    /// Roots of the trees spawned from UI definition assets
+    pub fn spawned_roots(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.loading_assets.spawned.iter().map(|spawned| spawned.root)
+    }
+
+    /// Rebuild a `UiDefinition` from the widget tree rooted at `root`.
+    ///
+    /// Window configuration, style classes, global actions, tokens and state declarations come
+    /// from the asset the tree was spawned from, if any. Values the live tree did not change are
+    /// written the way that asset's source wrote them, keeping `$token` references, `include`
+    /// nodes and inline bindings.
+    pub fn to_definition(&self, root: Entity) -> Option<UiDefinition> {
+        let root_node = self.to_node(root)?;
+        let source = self.loading_assets.spawned.iter()
+            .find(|spawned| spawned.root == root)
+            .and_then(|spawned| self.ui_assets.get(&spawned.handle));
+
+        let mut definition = UiDefinition {
+            format_version: CURRENT_FORMAT_VERSION,
+            window: source.and_then(|definition| definition.window.clone()),
+            windows: source.and_then(|definition| definition.windows.clone()),
+            root: root_node,
+            styles: source.and_then(|definition| definition.styles.clone()),
+            actions: source.and_then(|definition| definition.actions.clone()),
+            tokens: source.and_then(|definition| definition.tokens.clone()),
+            state: source.and_then(|definition| definition.state.clone()),
+            authored: None,
+        };
+        if let Some((source, authored)) = source.and_then(|source| Some((source, source.authored.as_ref()?))) {
+            let loaded = serde_json::to_value(source).ok()?;
+            let live = serde_json::to_value(&definition).ok()?;
+            definition.authored = Some(restore_authored(authored, &loaded, &live));
+        }
+        Some(definition)
+    }
+
+    /// Rebuild the node of a single widget and its widget children
+    pub fn to_node(&self, entity: Entity) -> Option<WidgetNode> {
+        let (widget, hierarchy, layout, style, behavior, transform, global_transform, parent, position_control, bindings, source, dynamic, toggle, numeric, dropdown) =
+            self.widgets.get(entity).ok()?;
+
+        let mut node = match source {
+            Some(source) => source.node.clone(),
+            None => WidgetNode {
+                id: (widget.id != "unnamed").then(|| widget.id.clone()),
+                widget_type: widget.blueprint.widget_type.clone(),
+                layout: widget.blueprint.layout.clone(),
+                style: widget.blueprint.style.clone(),
+                behavior: widget.blueprint.behavior.clone(),
+                classes: None,
+                style_overrides: None,
+                bindings: None,
+                state_bindings: None,
+                repeat: None,
+                when: None,
+                localized: None,
+                children: vec![],
+            },
+        };
+
+        // Live components are compared against what the blueprint produced at spawn time,
+        // so only values changed at runtime replace the authored ones
+        apply_layout_changes(&mut node, layout, &WidgetLayout::from(&widget.blueprint.layout));
+        // Checked toggles are drawn with their `checked` state style, which is not a change
+        let checked_style = toggle.filter(|toggle| toggle.checked)
+            .and_then(|_| widget.blueprint.style.states.as_ref()?.checked.as_ref())
+            .map(|checked| checked.apply_to(&widget.blueprint.style));
+        let authored_style = checked_style.as_ref().unwrap_or(&widget.blueprint.style);
+        apply_style_changes(&mut node, style, &WidgetStyle::from(authored_style));
+        apply_behavior_changes(&mut node, behavior, &WidgetBehavior::from(&widget.blueprint.behavior));
+
+        // Dragging turns LayoutThenManual into Manual, which has to be saved with the position.
+        // Popups and their contents are spawned Manual and place themselves, which is not a change.
+        let live_control = position_control.cloned().unwrap_or_default();
+        if widget.blueprint.behavior.position_control.clone().unwrap_or_default() != live_control {
+            node.behavior.position_control = Some(live_control.clone());
+        }
+        if node.behavior.position_control.as_ref().is_some_and(PositionControl::is_manual) {
+            // Widgets still attached to a Bevy parent (dragged out of a layout) move relative to it
+            let translation = match (parent, global_transform) {
+                (Some(_), Some(global)) => global.translation(),
+                _ => transform.translation,
+            };
+            let position = BevyCoords::new(translation.x, translation.y, translation.z)
+                .to_toml(self.widget_windows.height_of(entity))
+                .raw();
+            let z = node.layout.position.map(|authored| authored.z).unwrap_or(0.0);
+            let moved = node.layout.position
+                .map(|authored| (authored.x - position.x).abs() >= 0.5 || (authored.y - position.y).abs() >= 0.5)
+                .unwrap_or(true);
+            if moved {
+                node.layout.position = Some(Vec3::new(position.x, position.y, z));
+            }
+        }
+
+        if let WidgetType::Text { content, .. } = &mut node.widget_type {
+            if let Some(text) = self.live_text(entity) {
+                *content = text;
+            }
+        }
+        if let (WidgetType::Checkbox { checked, .. } | WidgetType::Toggle { checked, .. }, Some(toggle)) = (&mut node.widget_type, toggle) {
+            *checked = toggle.checked;
+        }
+        if let (WidgetType::Slider { value, .. } | WidgetType::DragValue { value, .. }, Some(numeric)) = (&mut node.widget_type, numeric) {
+            *value = numeric.value;
+        }
+        if let (WidgetType::Dropdown { selected, .. }, Some(dropdown)) = (&mut node.widget_type, dropdown) {
+            *selected = dropdown.selected_value().map(str::to_string);
+        }
+        // A popup is open while it is visible, which is saved as `open`
+        if let WidgetType::Popup { open, .. } = &mut node.widget_type {
+            *open = behavior.visible;
+            node.behavior.visible = source.and_then(|source| source.node.behavior.visible);
+        }
+        // Tabs are saved in their live order, closed ones left out, and the active one with them
+        let tabs = self.tabs.get(entity).ok();
+        if let (WidgetType::Tabs { tabs: authored, active, .. }, Some(tabs)) = (&mut node.widget_type, tabs) {
+            *authored = tabs.tabs.clone();
+            *active = tabs.active_id().map(str::to_string);
+        }
+        // A text input's value is the text typed into its field
+        if let WidgetType::TextInput { value, .. } = &mut node.widget_type {
+            if let Some(text) = WidgetTextInput::field(hierarchy).and_then(|field| self.live_text(field)) {
+                *value = text;
+            }
+        }
+
+        node.bindings = bindings.map(|bindings| bindings.bindings.clone());
+
+        // Template parts (e.g. a button's label) are recreated from the template, and instances
+        // of data-declared templates, repeat nodes and virtual lists keep their authored children
+        let template_instance = source.is_some_and(|source| source.template_instance);
+        let authored_children = node.repeat.is_some() || matches!(node.widget_type, WidgetType::VirtualList { .. });
+        if !is_template_widget(&node.widget_type) && !template_instance && !authored_children {
+            node.children = match dynamic {
+                Some(dynamic) => self.dynamic_children_to_nodes(dynamic),
+                // The header buttons of tabs are spawned for them
+                None => hierarchy.children.iter()
+                    .filter(|child| !tabs.is_some_and(|tabs| tabs.buttons.contains(child)))
+                    .filter_map(|&child| self.to_node(child))
+                    .collect(),
+            };
+        }
+
+        Some(node)
+    }
+
+    /// Rebuild the children of a widget with `when` children, including the ones not spawned
+    fn dynamic_children_to_nodes(&self, dynamic: &WidgetDynamicChildren) -> Vec<WidgetNode> {
+        dynamic.slots.iter()
+            .filter_map(|slot| match slot {
+                DynamicChildSlot::Static(child) => self.to_node(*child),
+                DynamicChildSlot::When { node, entity, .. } => entity
+                    .and_then(|child| self.to_node(child))
+                    .or_else(|| Some(node.as_ref().clone())),
+                DynamicChildSlot::Repeat { .. } => None,
+            })
+            .collect()
+    }
+
+    fn live_text(&self, entity: Entity) -> Option<String> {
+        let text_map = self.yrs_res.text_map.lock().ok()?;
+        let text_ref = text_map.get(&entity)?;
+        let txn = self.yrs_res.doc.transact();
+        Some(text_ref.get_string(&txn))
+    }
+}
+
+/// Rebuild a `UiDefinition` from the widget tree rooted at `root`, outside of a system
+pub fn serialize_ui_tree(world: &mut World, root: Entity) -> Option<UiDefinition> {
+    let mut state = SystemState::<LiveUiTree>::new(world);
+    let tree = state.get(world);
+    tree.to_definition(root)
+}
+
+impl UiDefinition {
+    /// Write the definition as TOML or JSON source text, starting from its authored form if
+    /// it has one
+    pub fn to_source_string(&self, format: UiDefinitionFormat) -> Result<String, UiDefinitionLoaderError> {
+        let mut document = match &self.authored {
+            Some(authored) => authored.clone(),
+            None => serde_json::to_value(self)
+                .map_err(|e| UiDefinitionLoaderError::Serialize(e.to_string()))?,
+        };
+        tidy_document(&mut document);
+
+        match format {
+            UiDefinitionFormat::Json => serde_json::to_string_pretty(&document)
+                .map_err(|e| UiDefinitionLoaderError::Serialize(e.to_string())),
+            UiDefinitionFormat::Toml => toml::to_string_pretty(&document)
+                .map_err(|e| UiDefinitionLoaderError::Serialize(e.to_string())),
+        }
+    }
+}
+
+fn apply_layout_changes(node: &mut WidgetNode, live: &WidgetLayout, authored: &WidgetLayout) {
+    let to_spacing = |(top, right, bottom, left): (f32, f32, f32, f32)| Spacing { top, right, bottom, left };
+
+    if live.size != authored.size {
+        node.layout.size = live.size;
+    }
+    if live.margin != authored.margin {
+        node.layout.margin = live.margin.map(to_spacing);
+    }
+    if live.padding != authored.padding {
+        node.layout.padding = live.padding.map(to_spacing);
+    }
+    if live.flex_grow != authored.flex_grow {
+        node.layout.flex_grow = live.flex_grow;
+    }
+    if live.flex_shrink != authored.flex_shrink {
+        node.layout.flex_shrink = live.flex_shrink;
+    }
+}
+
+/// `Some(new value)` if a live color differs from the authored one
+fn color_change(live: Option<Color>, authored: Option<Color>) -> Option<Option<ColorDef>> {
+    (live != authored).then(|| live.map(ColorDef::from_color))
+}
+
+fn apply_style_changes(node: &mut WidgetNode, live: &WidgetStyle, authored: &WidgetStyle) {
+    if let WidgetType::Button { background_color, border_color, border_width, border_radius, .. } = &mut node.widget_type {
+        // The button's shape carries its background and border
+        if let Some(color) = color_change(live.background_color, authored.background_color) {
+            *background_color = color;
+        }
+        if let Some(color) = color_change(live.border_color, authored.border_color) {
+            *border_color = color;
+        }
+        if live.border_width != authored.border_width {
+            *border_width = live.border_width;
+        }
+        if live.border_radius != authored.border_radius {
+            *border_radius = live.border_radius;
+        }
+    } else {
+        if let Some(color) = color_change(live.background_color, authored.background_color) {
+            node.style.background_color = color;
+        }
+        if let Some(color) = color_change(live.border_color, authored.border_color) {
+            node.style.border_color = color;
+        }
+        if let Some(color) = color_change(live.text_color, authored.text_color) {
+            node.style.text_color = color;
+        }
+        if live.border_width != authored.border_width {
+            node.style.border_width = live.border_width;
+        }
+        if live.border_radius != authored.border_radius {
+            node.style.border_radius = live.border_radius;
+        }
+        if live.text_size != authored.text_size {
+            node.style.text_size = live.text_size;
+        }
+    }
+    if live.opacity != authored.opacity {
+        node.style.opacity = live.opacity;
+    }
+}
+
+fn apply_behavior_changes(node: &mut WidgetNode, live: &WidgetBehavior, authored: &WidgetBehavior) {
+    if live.z_index != authored.z_index {
+        node.behavior.z_index = Some(live.z_index);
+    }
+    // Templates fix the remaining behavior of their parts
+    if is_template_widget(&node.widget_type) {
+        return;
+    }
+    if live.visible != authored.visible {
+        node.behavior.visible = Some(live.visible);
+    }
+    if live.interactive != authored.interactive {
+        node.behavior.interactive = Some(live.interactive);
+    }
+    if live.draggable != authored.draggable {
+        node.behavior.draggable = Some(live.draggable);
+    }
+    if live.clickable != authored.clickable {
+        node.behavior.clickable = Some(live.clickable);
+    }
+    if live.focusable != authored.focusable {
+        node.behavior.focusable = Some(live.focusable);
+    }
+}
+
+/// Write `live` the way `authored` wrote it wherever it still equals `loaded`, the document
+/// `authored` turned into once includes, tokens, bindings and string tables were resolved.
+fn restore_authored(authored: &Value, loaded: &Value, live: &Value) -> Value {
+    if live == loaded {
+        return authored.clone();
+    }
+    match (authored, loaded, live) {
+        (Value::Object(authored_fields), _, _) if authored_fields.contains_key(INCLUDE_KEY) => {
+            restore_include(authored_fields, loaded, live)
+        }
+        // `bind` and `localized` references keep following their state value or string table
+        (Value::Object(_), loaded, _) if !loaded.is_object() => authored.clone(),
+        (Value::Object(authored_fields), Value::Object(loaded_fields), Value::Object(live_fields)) => {
+            let mut fields = Map::new();
+            for (key, authored_value) in authored_fields {
+                match (loaded_fields.get(key), live_fields.get(key)) {
+                    (Some(loaded_value), Some(live_value)) => {
+                        fields.insert(key.clone(), restore_authored(authored_value, loaded_value, live_value));
+                    }
+                    (None, Some(live_value)) => {
+                        fields.insert(key.clone(), live_value.clone());
+                    }
+                    // Removed from the live tree
+                    (Some(_), None) => {}
+                    // Only meaningful to the loader, e.g. a token file include
+                    (None, None) => {
+                        fields.insert(key.clone(), authored_value.clone());
+                    }
+                }
+            }
+            for (key, live_value) in live_fields {
+                // Fields the loader filled in, such as `state_bindings`, are left to it again
+                if !authored_fields.contains_key(key) && loaded_fields.get(key) != Some(live_value) {
+                    fields.insert(key.clone(), live_value.clone());
+                }
+            }
+            Value::Object(fields)
+        }
+        (Value::Array(authored_items), Value::Array(loaded_items), Value::Array(live_items)) if authored_items.len() == loaded_items.len() => {
+            let same_length = live_items.len() == loaded_items.len();
+            let items = live_items.iter().enumerate().map(|(index, live_item)| {
+                // Items are matched by position, or by widget id once some were added or removed
+                let loaded_index = if same_length {
+                    Some(index)
+                } else {
+                    live_item.get("id").and_then(|id| loaded_items.iter().position(|loaded_item| loaded_item.get("id") == Some(id)))
+                };
+                match loaded_index {
+                    Some(loaded_index) => restore_authored(&authored_items[loaded_index], &loaded_items[loaded_index], live_item),
+                    None => live_item.clone(),
+                }
+            });
+            Value::Array(items.collect())
+        }
+        _ => live.clone(),
+    }
+}
+
+/// Keep an included node as an include, writing what changed at runtime as instance overrides.
+///
+/// Changes inside the blueprint's children cannot be written as overrides, so those nodes are
+/// written out in full instead.
+fn restore_include(authored: &Map<String, Value>, loaded: &Value, live: &Value) -> Value {
+    let (Some(loaded_fields), Some(live_fields)) = (loaded.as_object(), live.as_object()) else {
+        return live.clone();
+    };
+    if loaded_fields.get("children") != live_fields.get("children") {
+        return live.clone();
+    }
+
+    let mut overrides = Map::new();
+    for (key, live_value) in live_fields {
+        if key != "children" {
+            if let Some(changes) = changed_fields(loaded_fields.get(key), live_value) {
+                overrides.insert(key.clone(), changes);
+            }
+        }
+    }
+
+    let mut node = authored.clone();
+    // A changed label goes back into the `content` shorthand, which would otherwise win over it
+    if node.contains_key(CONTENT_KEY) {
+        let field = content_field(loaded.pointer("/widget_type/type").and_then(Value::as_str));
+        let content = overrides.get_mut("widget_type")
+            .and_then(Value::as_object_mut)
+            .and_then(|widget_type| widget_type.remove(field));
+        if let Some(content) = content {
+            node.insert(CONTENT_KEY.to_string(), content);
+        }
+        if overrides.get("widget_type").and_then(Value::as_object).is_some_and(Map::is_empty) {
+            overrides.remove("widget_type");
+        }
+    }
+
+    let mut node = Value::Object(node);
+    deep_merge(&mut node, Value::Object(overrides));
+    node
+}
+
+/// The parts of `live` that differ from `loaded`, as a table to merge over it
+fn changed_fields(loaded: Option<&Value>, live: &Value) -> Option<Value> {
+    match (loaded, live) {
+        (Some(loaded), live) if loaded == live => None,
+        (Some(Value::Object(loaded_fields)), Value::Object(live_fields)) => {
+            let changes: Map<String, Value> = live_fields.iter()
+                .filter_map(|(key, live_value)| Some((key.clone(), changed_fields(loaded_fields.get(key), live_value)?)))
+                .collect();
+            (!changes.is_empty()).then_some(Value::Object(changes))
+        }
+        _ => Some(live.clone()),
+    }
+}
+
+/// Drop unset fields and print f32 values without f64 noise (`0.1`, not `0.10000000149011612`)
+pub(crate) fn tidy_document(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, field| !field.is_null());
+            fields.values_mut().for_each(tidy_document);
+        }
+        Value::Array(items) => items.iter_mut().for_each(tidy_document),
+        Value::Number(number) if number.is_f64() => {
+            let Some(wide) = number.as_f64() else {
+                return;
+            };
+            let narrow = wide as f32;
+            if narrow as f64 == wide {
+                if let Some(tidy) = narrow.to_string().parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+                    *number = tidy;
+                }
+            }
+        }
+        _ => {}
+    }
+}
\ No newline at end of file
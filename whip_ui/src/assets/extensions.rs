@@ -0,0 +1,293 @@
+use bevy_app::App;
+use bevy_log::{error, info};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+use super::{UiDefinitionFormat, UiRegistry, UiRegistryError, WidgetTypeInfo, ActionInfo, definitions::sorted_entries, widget_templates::{BUILTIN_WIDGET_TYPES, TemplateError, WidgetTemplateDefinition}};
+
+/// Errors that can occur while loading a registry manifest
+#[derive(Error, Debug)]
+pub enum RegistryManifestError {
+    #[error("Failed to read registry manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse registry manifest: {0}")]
+    Parse(String),
+    #[error("Invalid registry manifest: {0}")]
+    Registry(#[from] UiRegistryError),
+    #[error("Failed to read widget template {path}: {source}")]
+    TemplateIo { path: String, source: std::io::Error },
+    #[error("Invalid widget template {path}: {error}")]
+    Template { path: String, error: TemplateError },
+}
+
+/// Something that contributes widget types, actions, state types or widget templates to the
+/// [`UiRegistry`], so that UI definitions using them pass validation.
+///
+/// Implement it on a downstream plugin and call [`UiExtensionAppExt::register_ui_extension`]
+/// from the plugin's `build`:
+///
+/// ```ignore
+/// impl RegisterUiExtension for TimelinePlugin {
+///     fn register(&self, registry: &mut UiRegistry) -> Result<(), UiRegistryError> {
+///         registry.register_action("play_clip", ActionInfo { /* ... */ });
+///         Ok(())
+///     }
+/// }
+///
+/// impl Plugin for TimelinePlugin {
+///     fn build(&self, app: &mut App) {
+///         app.register_ui_extension(TimelinePlugin);
+///     }
+/// }
+/// ```
+pub trait RegisterUiExtension {
+    /// Name used in logs
+    fn extension_name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    /// Add this extension's entries to `registry`
+    fn register(&self, registry: &mut UiRegistry) -> Result<(), UiRegistryError>;
+}
+
+impl UiRegistry {
+    /// Add the entries of a [`RegisterUiExtension`]
+    pub fn register_extension<E: RegisterUiExtension + ?Sized>(&mut self, extension: &E) -> Result<(), UiRegistryError> {
+        extension.register(self)?;
+        info!("Registered UI extension '{}'", extension.extension_name());
+        Ok(())
+    }
+
+    /// Read a registry manifest file and add its entries; `.toml` files are read as TOML, anything else as JSON
+    pub fn load_manifest_file(&mut self, path: impl AsRef<Path>) -> Result<(), RegistryManifestError> {
+        let manifest = RegistryManifest::load(path)?;
+        self.register_extension(&manifest)?;
+        Ok(())
+    }
+}
+
+/// `App` methods for registering [`RegisterUiExtension`]s
+pub trait UiExtensionAppExt {
+    /// Add an extension's entries to the app's `UiRegistry`, creating the registry if no
+    /// plugin has yet. Failures are logged and leave entries added before the failure in place.
+    fn register_ui_extension(&mut self, extension: impl RegisterUiExtension) -> &mut Self;
+}
+
+impl UiExtensionAppExt for App {
+    fn register_ui_extension(&mut self, extension: impl RegisterUiExtension) -> &mut Self {
+        if !self.world().contains_resource::<UiRegistry>() {
+            self.insert_resource(UiRegistry::new());
+        }
+        let mut registry = self.world_mut().resource_mut::<UiRegistry>();
+        if let Err(e) = registry.register_extension(&extension) {
+            error!("Failed to register UI extension '{}': {}", extension.extension_name(), e);
+        }
+        self
+    }
+}
+
+/// Registry entries declared in a file rather than code:
+///
+/// ```toml
+/// [config]
+/// allow_custom_actions = false
+/// max_nesting_depth = 32
+///
+/// [widgets.Timeline]
+/// required = ["track_count"]
+/// optional = ["zoom"]
+/// children = true
+///
+/// [actions.play_clip]
+/// description = "Start playback of a clip"
+/// params = { clip_id = "String" }
+///
+/// [state_types.Timecode]
+/// type_id = "f64"
+/// operations = ["set", "get", "increment", "decrement"]
+/// default = 0.0
+/// ```
+///
+/// Widget types declared here are used as `{ type = "Custom", component = "Timeline", ... }`.
+/// A top-level `templates = ["templates/card.toml"]` lists widget template files, relative to
+/// the manifest, which [`RegistryManifest::load`] reads and registration adds to the registry.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryManifest {
+    /// Overrides of `UiRegistryConfig` fields
+    #[serde(default)]
+    pub config: RegistryConfigManifest,
+    /// Custom widget types, by component name
+    #[serde(default)]
+    pub widgets: HashMap<String, WidgetTypeManifest>,
+    /// Action signatures, by action name
+    #[serde(default)]
+    pub actions: HashMap<String, ActionManifest>,
+    /// State types that `[state]` declarations may use, by type name
+    #[serde(default)]
+    pub state_types: HashMap<String, StateTypeManifest>,
+    /// Widget template files, relative to the manifest
+    #[serde(default)]
+    pub templates: Vec<String>,
+    /// The templates read from `templates`
+    #[serde(skip)]
+    pub loaded_templates: Vec<WidgetTemplateDefinition>,
+}
+
+/// `UiRegistryConfig` fields set by a manifest; unset fields keep their current value
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfigManifest {
+    pub strict_validation: Option<bool>,
+    pub allow_custom_widgets: Option<bool>,
+    pub allow_custom_actions: Option<bool>,
+    pub max_nesting_depth: Option<usize>,
+}
+
+/// A custom widget type declared in a manifest
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WidgetTypeManifest {
+    pub display_name: Option<String>,
+    /// Properties every instance must set
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub optional: Vec<String>,
+    /// Whether instances may have children
+    #[serde(default)]
+    pub children: bool,
+}
+
+/// An action signature declared in a manifest
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActionManifest {
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Parameter types by name, e.g. `"String"`, `"Integer"`, `"Any"` or a registered state type
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    /// Conditions the action requires to execute
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+/// A state type declared in a manifest
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateTypeManifest {
+    pub type_id: Option<String>,
+    /// Operations state actions may apply, e.g. `["set", "get", "toggle"]`
+    pub operations: Vec<String>,
+    pub default: Option<serde_json::Value>,
+}
+
+impl RegistryManifest {
+    /// Parse a manifest in the given format
+    pub fn parse(content: &str, format: UiDefinitionFormat) -> Result<Self, RegistryManifestError> {
+        match format {
+            UiDefinitionFormat::Json => serde_json::from_str(content).map_err(|e| RegistryManifestError::Parse(e.to_string())),
+            UiDefinitionFormat::Toml => toml::from_str(content).map_err(|e| RegistryManifestError::Parse(e.to_string())),
+        }
+    }
+
+    /// Read and parse a manifest file, along with the template files it lists
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegistryManifestError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let mut manifest = Self::parse(&content, UiDefinitionFormat::from_path(path))?;
+        manifest.load_templates(path.parent().unwrap_or(Path::new("")))?;
+        Ok(manifest)
+    }
+
+    /// Read the template files listed in `templates`, resolving them against `base_dir`
+    pub fn load_templates(&mut self, base_dir: &Path) -> Result<(), RegistryManifestError> {
+        self.loaded_templates = self.templates.iter()
+            .map(|template_path| {
+                let full_path = base_dir.join(template_path);
+                let content = std::fs::read_to_string(&full_path)
+                    .map_err(|source| RegistryManifestError::TemplateIo { path: template_path.clone(), source })?;
+                WidgetTemplateDefinition::parse(&content, UiDefinitionFormat::from_path(&full_path))
+                    .map_err(|error| RegistryManifestError::Template { path: template_path.clone(), error })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+}
+
+impl RegisterUiExtension for RegistryManifest {
+    fn extension_name(&self) -> String {
+        "registry manifest".to_string()
+    }
+
+    /// State types are added first so that action parameters can use them, and templates
+    /// last so that they can use the widget types
+    fn register(&self, registry: &mut UiRegistry) -> Result<(), UiRegistryError> {
+        let config = &self.config;
+        if let Some(strict_validation) = config.strict_validation {
+            registry.config.strict_validation = strict_validation;
+        }
+        if let Some(allow_custom_widgets) = config.allow_custom_widgets {
+            registry.config.allow_custom_widgets = allow_custom_widgets;
+        }
+        if let Some(allow_custom_actions) = config.allow_custom_actions {
+            registry.config.allow_custom_actions = allow_custom_actions;
+        }
+        if let Some(max_nesting_depth) = config.max_nesting_depth {
+            registry.config.max_nesting_depth = max_nesting_depth;
+        }
+
+        for (name, state_type) in sorted_entries(&self.state_types) {
+            registry.register_custom_state_type(
+                name,
+                state_type.type_id.as_deref().unwrap_or(name),
+                state_type.operations.clone(),
+                state_type.default.clone(),
+            )?;
+        }
+
+        for (name, widget) in sorted_entries(&self.widgets) {
+            if BUILTIN_WIDGET_TYPES.contains(&name.as_str()) {
+                return Err(UiRegistryError::ValidationError(format!(
+                    "Widget type '{}' is built in and cannot be redeclared", name
+                )));
+            }
+            registry.register_widget_type(name, WidgetTypeInfo {
+                display_name: widget.display_name.clone().unwrap_or_else(|| name.clone()),
+                asset_path: None,
+                required_properties: widget.required.clone(),
+                optional_properties: widget.optional.clone(),
+                can_have_children: widget.children,
+            });
+        }
+
+        for (name, action) in sorted_entries(&self.actions) {
+            if name.is_empty() {
+                return Err(UiRegistryError::ValidationError("Action name cannot be empty".to_string()));
+            }
+            for (param, param_type) in sorted_entries(&action.params) {
+                if !registry.is_parameter_type_known(param_type) {
+                    return Err(UiRegistryError::ValidationError(format!(
+                        "Parameter '{}' of action '{}' has unknown type '{}'", param, name, param_type
+                    )));
+                }
+            }
+            registry.register_action(name, ActionInfo {
+                display_name: action.display_name.clone().unwrap_or_else(|| name.clone()),
+                description: action.description.clone(),
+                parameter_types: action.params.clone(),
+                requires_conditions: action.requires.clone(),
+            });
+        }
+
+        for template in &self.loaded_templates {
+            registry.register_template(template.clone())
+                .map_err(|e| UiRegistryError::ValidationError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,216 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+/// Key that turns a widget node into a reference to a blueprint file
+pub const INCLUDE_KEY: &str = "include";
+
+/// Shorthand key on an include node that overrides the blueprint's text content
+pub const CONTENT_KEY: &str = "content";
+
+/// Errors that can occur while resolving widget blueprint includes
+#[derive(Error, Debug)]
+pub enum IncludeError {
+    #[error("Include cycle detected: {0}")]
+    Cycle(String),
+    #[error("Failed to read included blueprint '{path}': {reason}")]
+    Read { path: String, reason: String },
+    #[error("Failed to parse included blueprint '{path}': {reason}")]
+    Parse { path: String, reason: String },
+    #[error("Invalid include in '{path}': {reason}")]
+    Invalid { path: String, reason: String },
+}
+
+/// Resolves `include = "widgets/editable_label.toml"` nodes against pre-loaded blueprint sources.
+///
+/// Sources are keyed by their normalized asset path. Loading is left to the caller (the asset
+/// loader reads them through `LoadContext` so they become reload dependencies); expansion itself
+/// is synchronous and works on the untyped document before it is deserialized into a `UiDefinition`.
+#[derive(Debug, Default, Clone)]
+pub struct IncludeResolver {
+    sources: HashMap<PathBuf, Value>,
+}
+
+impl IncludeResolver {
+    /// Register the parsed contents of a blueprint file
+    pub fn insert_source(&mut self, path: impl AsRef<Path>, document: Value) {
+        self.sources.insert(normalize_asset_path(path.as_ref()), document);
+    }
+
+    /// Check whether a blueprint file has already been registered
+    pub fn contains_source(&self, path: impl AsRef<Path>) -> bool {
+        self.sources.contains_key(&normalize_asset_path(path.as_ref()))
+    }
+
+    /// Expand every include in a UI definition document located at `path`
+    pub fn expand_document(&self, mut document: Value, path: &Path) -> Result<Value, IncludeError> {
+        let path = normalize_asset_path(path);
+        let mut stack = vec![path.clone()];
+        if let Some(root) = document.get_mut("root") {
+            *root = self.expand_node(root.take(), &path, &mut stack)?;
+        }
+        Ok(document)
+    }
+
+    /// Expand a single node and its children, declared in `file`
+    fn expand_node(&self, node: Value, file: &Path, stack: &mut Vec<PathBuf>) -> Result<Value, IncludeError> {
+        let Value::Object(mut fields) = node else {
+            return Ok(node);
+        };
+
+        // Children are expanded in the context of the file that declares them
+        if let Some(children) = fields.get_mut("children").and_then(Value::as_array_mut) {
+            for child in children.iter_mut() {
+                *child = self.expand_node(child.take(), file, stack)?;
+            }
+        }
+
+        let Some(include) = fields.remove(INCLUDE_KEY) else {
+            return Ok(Value::Object(fields));
+        };
+        let include = include.as_str().ok_or_else(|| IncludeError::Invalid {
+            path: file.display().to_string(),
+            reason: "'include' must be a path string".to_string(),
+        })?;
+        let include_path = resolve_include_path(file, include);
+
+        if stack.contains(&include_path) {
+            let chain = stack.iter()
+                .chain(std::iter::once(&include_path))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(IncludeError::Cycle(chain));
+        }
+
+        let source = self.sources.get(&include_path).ok_or_else(|| IncludeError::Read {
+            path: include_path.display().to_string(),
+            reason: "blueprint was not loaded".to_string(),
+        })?;
+
+        stack.push(include_path.clone());
+        let blueprint = self.expand_node(blueprint_node(source).clone(), &include_path, stack);
+        stack.pop();
+
+        let blueprint = blueprint?;
+        if !blueprint.is_object() {
+            return Err(IncludeError::Invalid {
+                path: include_path.display().to_string(),
+                reason: "blueprint must be a table".to_string(),
+            });
+        }
+
+        Ok(apply_instance_overrides(blueprint, fields))
+    }
+}
+
+/// The node described by a blueprint file: its `[widget]` table, or the whole document
+pub fn blueprint_node(document: &Value) -> &Value {
+    document.get("widget").unwrap_or(document)
+}
+
+/// Check whether any node of a UI definition document uses an include
+pub fn document_has_includes(document: &Value) -> bool {
+    document.get("root").map(node_has_includes).unwrap_or(false)
+}
+
+fn node_has_includes(node: &Value) -> bool {
+    node.get(INCLUDE_KEY).is_some()
+        || node.get("children")
+            .and_then(Value::as_array)
+            .map(|children| children.iter().any(node_has_includes))
+            .unwrap_or(false)
+}
+
+/// Collect the resolved paths of every include directly referenced from `node`'s subtree
+pub fn collect_include_paths(node: &Value, file: &Path, paths: &mut Vec<PathBuf>) {
+    if let Some(include) = node.get(INCLUDE_KEY).and_then(Value::as_str) {
+        paths.push(resolve_include_path(file, include));
+    }
+    if let Some(children) = node.get("children").and_then(Value::as_array) {
+        for child in children {
+            collect_include_paths(child, file, paths);
+        }
+    }
+}
+
+/// Resolve an include relative to the directory of the including file.
+/// A leading `/` makes the path relative to the asset root instead.
+pub fn resolve_include_path(file: &Path, include: &str) -> PathBuf {
+    let joined = match include.strip_prefix('/') {
+        Some(rooted) => PathBuf::from(rooted),
+        None => file.parent().unwrap_or(Path::new("")).join(include),
+    };
+    normalize_asset_path(&joined)
+}
+
+/// Collapse `.` and `..` components so equal files compare equal
+pub fn normalize_asset_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Normal(part) => normalized.push(part),
+            Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    normalized
+}
+
+/// Merge an include node's own fields over its expanded blueprint.
+///
+/// Tables are merged recursively and scalars/arrays replace the blueprint's values, except
+/// `children`, which are appended after the blueprint's own children.
+fn apply_instance_overrides(mut blueprint: Value, mut overrides: Map<String, Value>) -> Value {
+    if let Some(content) = overrides.remove(CONTENT_KEY) {
+        let key = content_field(blueprint.pointer("/widget_type/type").and_then(Value::as_str));
+        let widget_type = overrides.entry("widget_type").or_insert_with(|| Value::Object(Map::new()));
+        if let Some(widget_type) = widget_type.as_object_mut() {
+            widget_type.insert(key.to_string(), content);
+        }
+    }
+
+    if let Some(Value::Array(extra_children)) = overrides.remove("children") {
+        let children = blueprint.as_object_mut()
+            .map(|fields| fields.entry("children").or_insert_with(|| Value::Array(Vec::new())));
+        if let Some(Value::Array(children)) = children {
+            children.extend(extra_children);
+        }
+    }
+
+    deep_merge(&mut blueprint, Value::Object(overrides));
+    blueprint
+}
+
+/// The `widget_type` field the `content` shorthand sets for a widget type.
+///
+/// Buttons carry their label in `text`, checkboxes and toggles in `label`, everything else in
+/// `content`.
+pub(crate) fn content_field(widget_type: Option<&str>) -> &'static str {
+    match widget_type {
+        Some("Button") => "text",
+        Some("Checkbox" | "Toggle") => "label",
+        _ => "content",
+    }
+}
+
+/// Recursively merge `patch` into `target`
+pub(crate) fn deep_merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target_fields), Value::Object(patch_fields)) => {
+            for (key, value) in patch_fields {
+                match target_fields.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() => deep_merge(existing, value),
+                    _ => {
+                        target_fields.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
\ No newline at end of file
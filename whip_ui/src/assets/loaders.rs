@@ -4,7 +4,7 @@ use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 use crate::widgets::blueprint::WidgetType;
 
-use super::{definitions::{UiDefinition, UiDefinitionError, WidgetNode, StyleOverrides}, registry::{UiRegistry, UiRegistryError}};
+use super::{definitions::{sorted_entries, widget_id_problem, UiDefinition, UiDefinitionError, WidgetNode, StyleOverrides}, includes::{self, IncludeError, IncludeResolver}, tokens::{self, TokenResolver}, state, localization, migrations::{self, MigrationError}, registry::{UiRegistry, UiRegistryError}, validation::{Diagnostic, ValidationReport}};
 
 /// Asset loader for hierarchical UI definitions
 #[derive(Default)]
@@ -17,6 +17,14 @@ pub enum UiDefinitionLoaderError {
     Io(#[from] std::io::Error),
     #[error("Failed to parse JSON: {0}")]
     JsonParse(#[from] serde_json::Error),
+    #[error("Failed to parse TOML at line {line}, column {column}: {message}")]
+    TomlParse {
+        message: String,
+        /// 1-based line of the offending token (0 if the parser reported no location)
+        line: usize,
+        /// 1-based column of the offending token (0 if the parser reported no location)
+        column: usize,
+    },
     #[error("Validation failed: {0}")]
     Validation(#[from] UiDefinitionError),
     #[error("Registry validation failed: {0}")]
@@ -29,6 +37,16 @@ pub enum UiDefinitionLoaderError {
     ActionValidation(String),
     #[error("Style validation failed: {0}")]
     StyleValidation(String),
+    #[error("Include resolution failed: {0}")]
+    Include(#[from] IncludeError),
+    #[error("Failed to load token file '{path}': {reason}")]
+    TokenFile { path: String, reason: String },
+    #[error("Validation failed:\n{0}")]
+    Report(ValidationReport),
+    #[error("Failed to serialize UI definition: {0}")]
+    Serialize(String),
+    #[error("Failed to upgrade UI definition: {0}")]
+    Migration(#[from] MigrationError),
 }
 
 
@@ -42,30 +60,21 @@ impl AssetLoader for UiDefinitionLoader {
         &self,
         reader: &mut dyn bevy_asset::io::Reader,
         _settings: &(),
-        _load_context: &mut LoadContext<'_>,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
-        let mut bytes = Vec::new();
-        reader.read_to_end(&mut bytes).await?;
-        let content_str = std::str::from_utf8(&bytes)
-            .map_err(|e| UiDefinitionLoaderError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        let content_str = read_source(reader).await?;
+        let format = UiDefinitionFormat::from_path(load_context.path());
 
-        // Parse the JSON directly into UiDefinition using serde
-        let ui_definition: UiDefinition = serde_json::from_str(content_str)
-            .map_err(|e| {
-                bevy_log::error!("Failed to parse UI definition JSON: {}", e);
-                UiDefinitionLoaderError::JsonParse(e)
-            })?;
+        // Parse the source into UiDefinition, pulling in any included widget blueprints
+        let ui_definition = self.parse_with_includes(&content_str, format, load_context).await?;
 
         bevy_log::info!("Successfully loaded UI definition with {} global styles and {} global actions", 
                         ui_definition.styles.as_ref().map(|s| s.len()).unwrap_or(0),
                         ui_definition.actions.as_ref().map(|a| a.len()).unwrap_or(0));
 
-        // Validate the loaded UI definition
-        ui_definition.validate()
-            .map_err(|e| {
-                bevy_log::error!("UI definition validation failed: {}", e);
-                UiDefinitionLoaderError::Validation(e)
-            })?;
+        // Validate the loaded UI definition, reporting every problem at once
+        let report = ui_definition.validation_report();
+        fail_on_errors(report, &content_str, format, load_context)?;
 
         bevy_log::info!("UI definition validation passed");
 
@@ -73,10 +82,82 @@ impl AssetLoader for UiDefinitionLoader {
     }
 
     fn extensions(&self) -> &[&str] {
-        &["json"]
+        &["json", "toml"]
+    }
+}
+
+/// Source formats understood by [`UiDefinitionLoader`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiDefinitionFormat {
+    Json,
+    Toml,
+}
+
+impl UiDefinitionFormat {
+    /// Pick the format from the asset path's extension, falling back to JSON
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => UiDefinitionFormat::Toml,
+            _ => UiDefinitionFormat::Json,
+        }
     }
 }
 
+/// Read the whole asset into a UTF-8 string
+async fn read_source(reader: &mut dyn bevy_asset::io::Reader) -> Result<String, UiDefinitionLoaderError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    String::from_utf8(bytes)
+        .map_err(|e| UiDefinitionLoaderError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+/// Deserialize source text in the given format, reporting TOML errors with their location
+pub(crate) fn parse_source<T: serde::de::DeserializeOwned>(content: &str, format: UiDefinitionFormat) -> Result<T, UiDefinitionLoaderError> {
+    match format {
+        UiDefinitionFormat::Json => serde_json::from_str(content)
+            .map_err(|e| {
+                bevy_log::error!("Failed to parse UI definition JSON: {}", e);
+                UiDefinitionLoaderError::JsonParse(e)
+            }),
+        UiDefinitionFormat::Toml => toml::from_str(content)
+            .map_err(|e| {
+                let (line, column) = e.span()
+                    .map(|span| line_column_at(content, span.start))
+                    .unwrap_or((0, 0));
+                let message = e.message().trim_end().to_string();
+                bevy_log::error!("Failed to parse UI definition TOML at {}:{}: {}", line, column, message);
+                UiDefinitionLoaderError::TomlParse { message, line, column }
+            }),
+    }
+}
+
+/// Locate and log a validation report, failing the load if it contains errors
+fn fail_on_errors(
+    report: ValidationReport,
+    source: &str,
+    format: UiDefinitionFormat,
+    load_context: &LoadContext<'_>,
+) -> Result<(), UiDefinitionLoaderError> {
+    if !report.has_errors() {
+        return Ok(());
+    }
+    let mut report = report.with_source_name(load_context.path().display().to_string());
+    report.locate(source, format);
+    report.log();
+    bevy_log::error!("UI definition validation failed with {} error(s)", report.error_count());
+    Err(UiDefinitionLoaderError::Report(report))
+}
+
+/// Convert a byte offset into a 1-based (line, column) pair
+fn line_column_at(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
 impl UiDefinitionLoader {
     /// Load and validate a UI definition with registry validation
     pub async fn load_with_registry(
@@ -84,90 +165,281 @@ impl UiDefinitionLoader {
         reader: &mut dyn bevy_asset::io::Reader,
         registry: &UiRegistry,
         _settings: &(),
-        _load_context: &mut LoadContext<'_>,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<UiDefinition, UiDefinitionLoaderError> {
-        let mut bytes = Vec::new();
-        reader.read_to_end(&mut bytes).await?;
-        let content_str = std::str::from_utf8(&bytes)
-            .map_err(|e| UiDefinitionLoaderError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        let content_str = read_source(reader).await?;
+        let format = UiDefinitionFormat::from_path(load_context.path());
 
-        // Parse the JSON directly
-        let ui_definition: UiDefinition = serde_json::from_str(content_str)
-            .map_err(|e| {
-                bevy_log::error!("Failed to parse UI definition JSON: {}", e);
-                UiDefinitionLoaderError::JsonParse(e)
-            })?;
+        let ui_definition = self.parse_with_includes(&content_str, format, load_context).await?;
 
         bevy_log::info!("Successfully loaded UI definition with {} global styles and {} global actions", 
                         ui_definition.styles.as_ref().map(|s| s.len()).unwrap_or(0),
                         ui_definition.actions.as_ref().map(|a| a.len()).unwrap_or(0));
 
         // Validate with registry
-        ui_definition.validate_with_registry(registry)
-            .map_err(|e| {
-                bevy_log::error!("UI definition registry validation failed: {}", e);
-                UiDefinitionLoaderError::Validation(e)
-            })?;
+        let report = ui_definition.validation_report_with_registry(registry);
+        fail_on_errors(report, &content_str, format, load_context)?;
 
         bevy_log::info!("UI definition validation with registry passed");
 
         Ok(ui_definition)
     }
 
+    /// Parse UI definition source text in the given format without validating it.
+    ///
+    /// Token references are resolved against the file's own `[tokens]` table; token files it
+    /// includes are not read, so use [`Self::parse_with_includes`] for those. Inline state
+    /// bindings are moved into the nodes' `state_bindings` and string table references into
+    /// their `localized` fields. Documents in an older format version are migrated first.
+    pub fn parse_definition(&self, content: &str, format: UiDefinitionFormat) -> Result<UiDefinition, UiDefinitionLoaderError> {
+        let mut document: serde_json::Value = parse_source(content, format)?;
+        let migration = migrations::migrate_document(&mut document)?;
+        let has_bindings = state::document_has_inline_bindings(&document);
+        let has_localized = localization::document_has_localized_text(&document);
+        if !migration.changed() && !has_bindings && !has_localized && !tokens::document_uses_tokens(&document) {
+            // Parse the original text so errors keep their source locations
+            return parse_source(content, format);
+        }
+
+        let authored = document.clone();
+        let mut report = ValidationReport::new();
+        if has_localized {
+            localization::lift_localized_text(&mut document, &mut report);
+        }
+        if has_bindings {
+            state::lift_inline_bindings(&mut document, &mut report);
+        }
+        if tokens::document_uses_tokens(&document) {
+            TokenResolver::default().resolve_document(&mut document, std::path::Path::new(""), &mut report);
+        }
+        if report.has_errors() {
+            return Err(UiDefinitionLoaderError::Report(report));
+        }
+        let mut ui_definition: UiDefinition = serde_json::from_value(document)?;
+        ui_definition.authored = Some(authored);
+        Ok(ui_definition)
+    }
 
+    /// Parse a UI definition, expanding `include` nodes into the widget blueprints they reference,
+    /// lifting inline state bindings and string table references and resolving design tokens.
+    ///
+    /// Included files and token files are read through the load context so that editing one
+    /// reloads every layout that uses it. Include paths are relative to the including file.
+    pub async fn parse_with_includes(
+        &self,
+        content: &str,
+        format: UiDefinitionFormat,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<UiDefinition, UiDefinitionLoaderError> {
+        let mut document: serde_json::Value = parse_source(content, format)?;
+        let migration = migrations::migrate_document(&mut document)?;
+        if migration.is_outdated() {
+            bevy_log::info!(
+                "{} uses UI definition format version {}; run `whip-ui-tool migrate` to upgrade it to version {}",
+                load_context.path().display(), migration.from_version, migrations::CURRENT_FORMAT_VERSION
+            );
+        }
+        let has_includes = includes::document_has_includes(&document);
+        if !migration.changed()
+            && !has_includes
+            && !tokens::document_uses_tokens(&document)
+            && !state::document_has_inline_bindings(&document)
+            && !localization::document_has_localized_text(&document)
+        {
+            // Parse the original text so errors keep their source locations
+            return self.parse_definition(content, format);
+        }
 
+        let authored = document.clone();
+        let root_path = load_context.path().to_path_buf();
+        if has_includes {
+            document = self.expand_includes(document, &root_path, load_context).await?;
+        }
 
+        if localization::document_has_localized_text(&document) {
+            let mut report = ValidationReport::new();
+            localization::lift_localized_text(&mut document, &mut report);
+            fail_on_errors(report, content, format, load_context)?;
+        }
 
+        // Included blueprints may bind to the including layout's state too
+        if state::document_has_inline_bindings(&document) {
+            let mut report = ValidationReport::new();
+            state::lift_inline_bindings(&mut document, &mut report);
+            fail_on_errors(report, content, format, load_context)?;
+        }
 
+        // Blueprints pulled in above pick up the including layout's tokens
+        if tokens::document_uses_tokens(&document) {
+            let token_resolver = self.load_token_files(&document, &root_path, load_context).await?;
+            let mut report = ValidationReport::new();
+            token_resolver.resolve_document(&mut document, &root_path, &mut report);
+            fail_on_errors(report, content, format, load_context)?;
+        }
 
-    /// Comprehensive validation of the loaded UI definition with registry
+        let mut ui_definition: UiDefinition = serde_json::from_value(document)
+            .map_err(|e| {
+                bevy_log::error!("Failed to build UI definition after expanding includes: {}", e);
+                UiDefinitionLoaderError::JsonParse(e)
+            })?;
+        ui_definition.authored = Some(authored);
+        Ok(ui_definition)
+    }
+
+    /// Read every blueprint a document includes and expand its `include` nodes
+    async fn expand_includes(
+        &self,
+        document: serde_json::Value,
+        root_path: &std::path::Path,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<serde_json::Value, UiDefinitionLoaderError> {
+        let mut resolver = IncludeResolver::default();
+        let mut pending = Vec::new();
+        if let Some(root) = document.get("root") {
+            includes::collect_include_paths(root, root_path, &mut pending);
+        }
+
+        while let Some(path) = pending.pop() {
+            if resolver.contains_source(&path) {
+                continue;
+            }
+
+            let bytes = load_context.read_asset_bytes(path.clone()).await
+                .map_err(|e| IncludeError::Read { path: path.display().to_string(), reason: e.to_string() })?;
+            let text = String::from_utf8(bytes)
+                .map_err(|e| IncludeError::Read { path: path.display().to_string(), reason: e.to_string() })?;
+            let blueprint: serde_json::Value = parse_source(&text, UiDefinitionFormat::from_path(&path))
+                .map_err(|e| IncludeError::Parse { path: path.display().to_string(), reason: e.to_string() })?;
+
+            includes::collect_include_paths(includes::blueprint_node(&blueprint), &path, &mut pending);
+            resolver.insert_source(&path, blueprint);
+        }
+
+        Ok(resolver.expand_document(document, root_path)?)
+    }
+
+    /// Read the token files a document includes, and the token files those include
+    async fn load_token_files(
+        &self,
+        document: &serde_json::Value,
+        root_path: &std::path::Path,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<TokenResolver, UiDefinitionLoaderError> {
+        let mut resolver = TokenResolver::default();
+        let mut pending = Vec::new();
+        tokens::collect_token_files(document, root_path, &mut pending);
+
+        while let Some(path) = pending.pop() {
+            if resolver.contains_source(&path) {
+                continue;
+            }
+
+            let token_file_error = |reason: String| UiDefinitionLoaderError::TokenFile { path: path.display().to_string(), reason };
+            let bytes = load_context.read_asset_bytes(path.clone()).await
+                .map_err(|e| token_file_error(e.to_string()))?;
+            let text = String::from_utf8(bytes)
+                .map_err(|e| token_file_error(e.to_string()))?;
+            let token_file: serde_json::Value = parse_source(&text, UiDefinitionFormat::from_path(&path))
+                .map_err(|e| token_file_error(e.to_string()))?;
+
+            tokens::collect_token_files(&token_file, &path, &mut pending);
+            resolver.insert_source(&path, token_file);
+        }
+
+        Ok(resolver)
+    }
+
+    /// Comprehensive validation of the loaded UI definition with registry.
+    /// Fails with the full [`ValidationReport`] if any check reports an error.
     pub fn validate_with_comprehensive_checks(&self, ui_definition: &UiDefinition, registry: &UiRegistry) -> Result<(), UiDefinitionLoaderError> {
         bevy_log::info!("Starting comprehensive validation with registry checks");
 
+        let report = self.comprehensive_validation_report(ui_definition, registry);
+        if report.has_errors() {
+            return Err(UiDefinitionLoaderError::Report(report));
+        }
+
+        bevy_log::info!("Comprehensive validation completed successfully");
+        Ok(())
+    }
+
+    /// Run every comprehensive check and collect all errors and warnings
+    pub fn comprehensive_validation_report(&self, ui_definition: &UiDefinition, registry: &UiRegistry) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
         // Step 1: Basic structure validation
-        self.validate_basic_structure(ui_definition)?;
+        self.check_basic_structure(ui_definition, &mut report);
 
         // Step 2: Widget type validation with registry
-        self.validate_widget_types_with_registry(&ui_definition.root, registry, 0)?;
+        self.check_widget_types_with_registry(&ui_definition.root, "root", registry, 0, &mut report);
 
         // Step 3: Style classes validation
-        self.validate_style_classes(ui_definition)?;
+        self.check_style_classes(ui_definition, &mut report);
 
         // Step 4: Action validation with registry
-        self.validate_actions_with_registry(ui_definition, registry)?;
+        self.check_actions_with_registry(ui_definition, registry, &mut report);
 
         // Step 5: Cross-references validation (style classes, action bindings)
-        self.validate_cross_references(ui_definition)?;
+        self.check_cross_references(ui_definition, &mut report);
 
         // Step 6: Semantic validation (layout consistency, hierarchy depth)
-        self.validate_semantic_constraints(&ui_definition.root, registry, 0)?;
+        self.check_semantic_constraints(&ui_definition.root, "root", &mut report);
 
-        bevy_log::info!("Comprehensive validation completed successfully");
-        Ok(())
+        // Step 7: Warnings and suggestions
+        report.extend(self.validation_warnings(ui_definition));
+
+        report
+    }
+
+    /// Parse and validate UI definition source text the way the asset loader does, returning
+    /// every problem found (parse errors, validation errors and warnings) with source locations.
+    ///
+    /// Includes and token files are not read, so only the file itself is checked.
+    pub fn check_source(&self, content: &str, format: UiDefinitionFormat) -> ValidationReport {
+        let mut report = match self.parse_definition(content, format) {
+            Ok(ui_definition) => {
+                let mut report = ui_definition.validation_report();
+                report.extend(self.validation_warnings(&ui_definition));
+                report
+            }
+            Err(UiDefinitionLoaderError::TomlParse { message, line, column }) => {
+                let mut report = ValidationReport::new();
+                report.push(Diagnostic::error("", format!("Failed to parse TOML: {}", message)).with_location(line, column));
+                report
+            }
+            Err(UiDefinitionLoaderError::JsonParse(e)) => {
+                let mut report = ValidationReport::new();
+                report.push(Diagnostic::error("", format!("Failed to parse JSON: {}", e)).with_location(e.line(), e.column()));
+                report
+            }
+            Err(UiDefinitionLoaderError::Report(report)) => report,
+            Err(e) => {
+                let mut report = ValidationReport::new();
+                report.push(Diagnostic::error("", e.to_string()));
+                report
+            }
+        };
+        report.locate(content, format);
+        report
     }
 
     /// Validate basic UI definition structure
-    fn validate_basic_structure(&self, ui_definition: &UiDefinition) -> Result<(), UiDefinitionLoaderError> {
+    fn check_basic_structure(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         // Check window configuration validity
         if let Some(ref window) = ui_definition.window {
             if window.size[0] <= 0.0 || window.size[1] <= 0.0 {
-                return Err(UiDefinitionLoaderError::InvalidConfiguration(
+                report.error("window.size", UiDefinitionError::Validation(
                     "Window size must be positive".to_string()
                 ));
             }
             if window.size[0] > 10000.0 || window.size[1] > 10000.0 {
-                return Err(UiDefinitionLoaderError::InvalidConfiguration(
+                report.error("window.size", UiDefinitionError::Validation(
                     "Window size is unreasonably large (max 10000x10000)".to_string()
                 ));
             }
         }
 
-        // Validate root widget exists and has required properties
-        if ui_definition.root.id.is_none() {
-            bevy_log::warn!("Root widget has no ID, this may cause issues with debugging");
-        }
-
-        Ok(())
+        // A missing root ID is reported by the widget tree warnings
     }
 
 }
@@ -181,6 +453,10 @@ pub struct LoadUiRequest {
     pub parent: Option<Entity>,
     /// Optional position override
     pub position_override: Option<bevy_math::Vec3>,
+    /// Optional name that [`UnloadUiRequest`] and [`ReplaceUiRequest`] can address the spawned tree by
+    pub scope: Option<String>,
+    /// Id of the declared window to show the UI in (see [`super::windows`]); `None` is the primary window
+    pub window: Option<String>,
 }
 
 impl LoadUiRequest {
@@ -190,6 +466,8 @@ impl LoadUiRequest {
             asset_path: asset_path.into(),
             parent: None,
             position_override: None,
+            scope: None,
+            window: None,
         }
     }
 
@@ -204,24 +482,125 @@ impl LoadUiRequest {
         self.position_override = Some(position);
         self
     }
+
+    /// Name the spawned tree, e.g. `"main_panel"`, so that it can be unloaded or replaced by name
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Show the loaded UI in a window opened from a `[windows.<id>]` declaration
+    pub fn in_window(mut self, window: impl Into<String>) -> Self {
+        self.window = Some(window.into());
+        self
+    }
+}
+
+/// The spawned UI definitions an [`UnloadUiRequest`] or [`ReplaceUiRequest`] applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UiTarget {
+    /// The tree with this root widget entity
+    Root(Entity),
+    /// Every tree spawned from this asset path
+    Asset(String),
+    /// Every tree loaded with this scope (see [`LoadUiRequest::with_scope`])
+    Scope(String),
+    /// Every tree shown in this declared window (see [`LoadUiRequest::in_window`])
+    Window(String),
+}
+
+impl UiTarget {
+    /// Whether a tree loaded by `request`, with the given root once spawned, is targeted
+    pub fn matches(&self, request: &LoadUiRequest, root: Option<Entity>) -> bool {
+        match self {
+            UiTarget::Root(entity) => root == Some(*entity),
+            UiTarget::Asset(asset_path) => request.asset_path == *asset_path,
+            UiTarget::Scope(scope) => request.scope.as_deref() == Some(scope.as_str()),
+            UiTarget::Window(window) => request.window.as_deref() == Some(window.as_str()),
+        }
+    }
+}
+
+/// Event to request despawning spawned UI definitions along with everything they allocated.
+/// Loads of the target that are still pending are cancelled.
+#[derive(Event, Debug, Clone)]
+pub struct UnloadUiRequest {
+    pub target: UiTarget,
+}
+
+impl UnloadUiRequest {
+    /// Unload the tree with this root widget entity
+    pub fn root(root: Entity) -> Self {
+        Self { target: UiTarget::Root(root) }
+    }
+
+    /// Unload every tree spawned from an asset path
+    pub fn asset(asset_path: impl Into<String>) -> Self {
+        Self { target: UiTarget::Asset(asset_path.into()) }
+    }
+
+    /// Unload every tree loaded with a scope
+    pub fn scope(scope: impl Into<String>) -> Self {
+        Self { target: UiTarget::Scope(scope.into()) }
+    }
+
+    /// Unload every tree shown in a declared window
+    pub fn window(window: impl Into<String>) -> Self {
+        Self { target: UiTarget::Window(window.into()) }
+    }
+}
+
+/// Event to request swapping spawned UI definitions for another one, e.g. one panel for another.
+///
+/// The replacement takes the parent, position override, scope and window of the first replaced tree
+/// unless it sets its own, so a scope can be replaced again and again.
+#[derive(Event, Debug, Clone)]
+pub struct ReplaceUiRequest {
+    pub target: UiTarget,
+    pub replacement: LoadUiRequest,
+}
+
+impl ReplaceUiRequest {
+    /// Replace the trees `target` names with the UI loaded by `replacement`
+    pub fn new(target: UiTarget, replacement: LoadUiRequest) -> Self {
+        Self { target, replacement }
+    }
+
+    /// Replace the tree loaded with `scope` by the UI at `asset_path`
+    pub fn scope(scope: impl Into<String>, asset_path: impl Into<String>) -> Self {
+        Self::new(UiTarget::Scope(scope.into()), LoadUiRequest::new(asset_path))
+    }
 }
 
 
 // Additional validation methods for UiDefinitionLoader
 impl UiDefinitionLoader {
     /// Validate widget types against registry
-    fn validate_widget_types_with_registry(&self, node: &WidgetNode, registry: &UiRegistry, depth: usize) -> Result<(), UiDefinitionLoaderError> {
-        // Check nesting depth
+    fn check_widget_types_with_registry(&self, node: &WidgetNode, path: &str, registry: &UiRegistry, depth: usize, report: &mut ValidationReport) {
+        // Check nesting depth; deeper nodes would only repeat the same error
         if depth > registry.config.max_nesting_depth {
-            return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+            report.error(path, UiDefinitionError::Validation(
                 format!("Nesting depth {} exceeds maximum of {}", depth, registry.config.max_nesting_depth)
             ));
+            return;
         }
 
+        let type_path = format!("{}.widget_type", path);
+
         // Validate widget type with registry
         let widget_type_name = match &node.widget_type {
             WidgetType::Container { .. } => "Container",
+            WidgetType::ScrollView { .. } => "ScrollView",
+            WidgetType::VirtualList { .. } => "VirtualList",
             WidgetType::Button { .. } => "Button",
+            WidgetType::Checkbox { .. } => "Checkbox",
+            WidgetType::Toggle { .. } => "Toggle",
+            WidgetType::Slider { .. } => "Slider",
+            WidgetType::DragValue { .. } => "DragValue",
+            WidgetType::TextInput { .. } => "TextInput",
+            WidgetType::Dropdown { .. } => "Dropdown",
+            WidgetType::Popup { .. } => "Popup",
+            WidgetType::Tabs { .. } => "Tabs",
             WidgetType::Text { .. } => "Text", 
             WidgetType::Shape { .. } => "Shape",
             WidgetType::Custom { component, .. } => component,
@@ -229,9 +608,9 @@ impl UiDefinitionLoader {
 
         if let Err(e) = registry.validate_widget_type(&node.widget_type) {
             if registry.config.strict_validation && !registry.config.allow_custom_widgets {
-                return Err(UiDefinitionLoaderError::RegistryValidation(e));
+                report.error(&type_path, UiDefinitionError::RegistryValidation(e.to_string()));
             } else {
-                bevy_log::warn!("Widget type validation warning for '{}': {}", widget_type_name, e);
+                report.warning(&type_path, format!("Widget type validation warning for '{}': {}", widget_type_name, e));
             }
         }
 
@@ -240,41 +619,77 @@ impl UiDefinitionLoader {
             WidgetType::Button { text, .. } => {
                 if let Some(text) = text {
                     if text.is_empty() {
-                        return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                        report.error(&type_path, UiDefinitionError::Validation(
                             "Button text cannot be empty".to_string()
                         ));
                     }
                     if text.len() > 1000 {
-                        return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                        report.error(&type_path, UiDefinitionError::Validation(
                             "Button text is too long (max 1000 characters)".to_string()
                         ));
                     }
                 }
                 // Note: Button actions are now handled via action bindings
             }
+            WidgetType::Checkbox { label, .. } | WidgetType::Toggle { label, .. } => {
+                if label.as_ref().is_some_and(|label| label.len() > 1000) {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        format!("{} label is too long (max 1000 characters)", widget_type_name)
+                    ));
+                }
+            }
+            WidgetType::Slider { format, .. } | WidgetType::DragValue { format, .. } => {
+                if format.as_ref().is_some_and(|format| format.len() > 1000) {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        format!("{} format is too long (max 1000 characters)", widget_type_name)
+                    ));
+                }
+            }
+            WidgetType::TextInput { value, placeholder, .. } => {
+                if value.len() > 50000 {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        "TextInput value is too long (max 50000 characters)".to_string()
+                    ));
+                }
+                if placeholder.as_ref().is_some_and(|placeholder| placeholder.len() > 1000) {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        "TextInput placeholder is too long (max 1000 characters)".to_string()
+                    ));
+                }
+            }
+            WidgetType::Dropdown { options, placeholder, .. } => {
+                if options.iter().any(|option| option.label().len() > 1000) {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        "Dropdown option label is too long (max 1000 characters)".to_string()
+                    ));
+                }
+                if placeholder.as_ref().is_some_and(|placeholder| placeholder.len() > 1000) {
+                    report.error(&type_path, UiDefinitionError::Validation(
+                        "Dropdown placeholder is too long (max 1000 characters)".to_string()
+                    ));
+                }
+            }
             WidgetType::Text { content, editable: _ } => {
                 if content.len() > 50000 {
-                    return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                    report.error(&type_path, UiDefinitionError::Validation(
                         "Text content is too long (max 50000 characters)".to_string()
                     ));
                 }
             }
-            WidgetType::Container { direction: _ } => {
-                // Containers should have children or be marked as empty
-                if node.children.is_empty() {
-                    bevy_log::debug!("Container widget has no children: {:?}", node.id);
-                }
+            WidgetType::Container { direction: _ } | WidgetType::ScrollView { .. } | WidgetType::VirtualList { .. }
+            | WidgetType::Popup { .. } | WidgetType::Tabs { .. } => {
+                // Empty containers are reported by the widget tree warnings
             }
             WidgetType::Shape { shape_type } => {
                 // Validate shape type specific constraints
                 if let crate::widgets::blueprint::ShapeType::Custom { vertices } = shape_type {
                     if vertices.len() < 3 {
-                        return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                        report.error(&type_path, UiDefinitionError::Validation(
                             "Custom shape must have at least 3 vertices".to_string()
                         ));
                     }
                     if vertices.len() > 1000 {
-                        return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                        report.error(&type_path, UiDefinitionError::Validation(
                             "Custom shape has too many vertices (max 1000)".to_string()
                         ));
                     }
@@ -282,12 +697,12 @@ impl UiDefinitionLoader {
             }
             WidgetType::Custom { component, properties } => {
                 if component.is_empty() {
-                    return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                    report.error(&type_path, UiDefinitionError::Validation(
                         "Custom widget component name cannot be empty".to_string()
                     ));
                 }
                 if properties.len() > 100 {
-                    return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                    report.error(&type_path, UiDefinitionError::Validation(
                         "Custom widget has too many properties (max 100)".to_string()
                     ));
                 }
@@ -295,118 +710,86 @@ impl UiDefinitionLoader {
         }
 
         // Recursively validate children
-        for child in &node.children {
-            self.validate_widget_types_with_registry(child, registry, depth + 1)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_widget_types_with_registry(child, &format!("{}.children[{}]", path, index), registry, depth + 1, report);
         }
-
-        Ok(())
     }
 
     /// Validate style classes reference existing global styles
-    fn validate_style_classes(&self, ui_definition: &UiDefinition) -> Result<(), UiDefinitionLoaderError> {
+    fn check_style_classes(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         if let Some(ref global_styles) = ui_definition.styles {
             // Validate that all style class definitions are valid
-            for (class_name, style_override) in global_styles {
+            for (class_name, style_override) in sorted_entries(global_styles) {
+                let path = format!("styles.{}", class_name);
                 if class_name.is_empty() {
-                    return Err(UiDefinitionLoaderError::StyleValidation(
+                    report.error(&path, UiDefinitionError::Validation(
                         "Style class name cannot be empty".to_string()
                     ));
                 }
                 
                 // Validate style override contents
-                self.validate_style_override(style_override, class_name)?;
+                self.check_style_override(style_override, class_name, &path, report);
             }
         }
 
         // Validate that all referenced style classes exist
-        self.validate_style_class_references(&ui_definition.root, ui_definition.styles.as_ref())?;
-
-        Ok(())
+        self.check_style_class_references(&ui_definition.root, "root", ui_definition.styles.as_ref(), report);
     }
 
     /// Validate style override configuration
-    fn validate_style_override(&self, style_override: &StyleOverrides, class_name: &str) -> Result<(), UiDefinitionLoaderError> {
+    fn check_style_override(&self, style_override: &StyleOverrides, class_name: &str, path: &str, report: &mut ValidationReport) {
         // Validate color definitions if present
         if let Some(ref color_def) = style_override.background_color {
-            self.validate_color_definition(color_def, &format!("Style class '{}' background_color", class_name))?;
+            self.check_color_definition(color_def, &format!("Style class '{}' background_color", class_name), &format!("{}.background_color", path), report);
         }
         if let Some(ref color_def) = style_override.text_color {
-            self.validate_color_definition(color_def, &format!("Style class '{}' text_color", class_name))?;
+            self.check_color_definition(color_def, &format!("Style class '{}' text_color", class_name), &format!("{}.text_color", path), report);
         }
         if let Some(ref color_def) = style_override.border_color {
-            self.validate_color_definition(color_def, &format!("Style class '{}' border_color", class_name))?;
+            self.check_color_definition(color_def, &format!("Style class '{}' border_color", class_name), &format!("{}.border_color", path), report);
         }
 
         // Validate numeric values
         if let Some(opacity) = style_override.opacity {
             if !(0.0..=1.0).contains(&opacity) {
-                return Err(UiDefinitionLoaderError::StyleValidation(
+                report.error(format!("{}.opacity", path), UiDefinitionError::Validation(
                     format!("Style class '{}' opacity must be between 0.0 and 1.0", class_name)
                 ));
             }
         }
         if let Some(border_width) = style_override.border_width {
-            if border_width < 0.0 || border_width > 100.0 {
-                return Err(UiDefinitionLoaderError::StyleValidation(
+            if !(0.0..=100.0).contains(&border_width) {
+                report.error(format!("{}.border_width", path), UiDefinitionError::Validation(
                     format!("Style class '{}' border_width must be between 0.0 and 100.0", class_name)
                 ));
             }
         }
         if let Some(text_size) = style_override.text_size {
             if text_size <= 0.0 || text_size > 200.0 {
-                return Err(UiDefinitionLoaderError::StyleValidation(
+                report.error(format!("{}.text_size", path), UiDefinitionError::Validation(
                     format!("Style class '{}' text_size must be between 0.0 and 200.0", class_name)
                 ));
             }
         }
-
-        Ok(())
     }
 
-    /// Validate color definition format
-    fn validate_color_definition(&self, color_def: &crate::widgets::blueprint::ColorDef, context: &str) -> Result<(), UiDefinitionLoaderError> {
-        match color_def {
-            crate::widgets::blueprint::ColorDef::Hex(hex) => {
-                let hex = hex.trim_start_matches('#');
-                if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                    return Err(UiDefinitionLoaderError::StyleValidation(
-                        format!("{}: Invalid hex color format '{}' (expected #RRGGBB)", context, hex)
-                    ));
-                }
-            }
-            crate::widgets::blueprint::ColorDef::Rgb { r: _, g: _, b: _ } => {
-                // RGB values are u8 (0-255) by type definition, no validation needed
-            }
-            crate::widgets::blueprint::ColorDef::Rgba { r: _, g: _, b: _, a } => {
-                // RGB values are u8 (0-255) by type definition, only validate alpha
-                if !(*a >= 0.0 && *a <= 1.0) {
-                    return Err(UiDefinitionLoaderError::StyleValidation(
-                        format!("{}: Alpha value must be between 0.0 and 1.0", context)
-                    ));
-                }
-            }
-            crate::widgets::blueprint::ColorDef::Named(name) => {
-                let valid_names = ["red", "green", "blue", "black", "white", "gray", "grey", "yellow", "cyan", "magenta", "orange"];
-                if !valid_names.contains(&name.to_lowercase().as_str()) {
-                    bevy_log::warn!("{}: Unknown named color '{}', will use white as fallback", context, name);
-                }
-            }
+    /// Validate color definition format with the parser that converts it
+    fn check_color_definition(&self, color_def: &crate::widgets::blueprint::ColorDef, context: &str, path: &str, report: &mut ValidationReport) {
+        if let Err(e) = color_def.parse() {
+            report.error(path, UiDefinitionError::Validation(format!("{}: {}", context, e)));
         }
-        Ok(())
     }
 
     /// Validate that referenced style classes exist in global styles
-    fn validate_style_class_references(&self, node: &WidgetNode, global_styles: Option<&HashMap<String, StyleOverrides>>) -> Result<(), UiDefinitionLoaderError> {
+    fn check_style_class_references(&self, node: &WidgetNode, path: &str, global_styles: Option<&HashMap<String, StyleOverrides>>, report: &mut ValidationReport) {
         if let Some(ref classes) = node.classes {
             for class_name in classes {
                 if let Some(global_styles) = global_styles {
                     if !global_styles.contains_key(class_name) {
-                        return Err(UiDefinitionLoaderError::StyleValidation(
-                            format!("Referenced style class '{}' does not exist in global styles", class_name)
-                        ));
+                        report.error(format!("{}.classes", path), UiDefinitionError::UnknownStyleClass(class_name.clone()));
                     }
                 } else {
-                    return Err(UiDefinitionLoaderError::StyleValidation(
+                    report.error(format!("{}.classes", path), UiDefinitionError::Validation(
                         format!("Widget references style class '{}' but no global styles are defined", class_name)
                     ));
                 }
@@ -414,20 +797,19 @@ impl UiDefinitionLoader {
         }
 
         // Recursively validate children
-        for child in &node.children {
-            self.validate_style_class_references(child, global_styles)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_style_class_references(child, &format!("{}.children[{}]", path, index), global_styles, report);
         }
-
-        Ok(())
     }
 
     /// Validate actions with registry
-    fn validate_actions_with_registry(&self, ui_definition: &UiDefinition, registry: &UiRegistry) -> Result<(), UiDefinitionLoaderError> {
+    fn check_actions_with_registry(&self, ui_definition: &UiDefinition, registry: &UiRegistry, report: &mut ValidationReport) {
         // Validate global actions
         if let Some(ref global_actions) = ui_definition.actions {
-            for (action_name, action_binding) in global_actions {
+            for (action_name, action_binding) in sorted_entries(global_actions) {
+                let path = format!("actions.{}", action_name);
                 if action_name.is_empty() {
-                    return Err(UiDefinitionLoaderError::ActionValidation(
+                    report.error(&path, UiDefinitionError::Validation(
                         "Global action name cannot be empty".to_string()
                     ));
                 }
@@ -435,56 +817,53 @@ impl UiDefinitionLoader {
                 // Validate action binding with registry
                 if let Err(e) = registry.validate_action_binding(action_binding) {
                     if registry.config.strict_validation && !registry.config.allow_custom_actions {
-                        return Err(UiDefinitionLoaderError::RegistryValidation(e));
+                        report.error(&path, UiDefinitionError::RegistryValidation(e.to_string()));
                     } else {
-                        bevy_log::warn!("Action validation warning for '{}': {}", action_name, e);
+                        report.warning(&path, format!("Action validation warning for '{}': {}", action_name, e));
                     }
                 }
             }
         }
 
         // Validate widget-level action bindings
-        self.validate_widget_action_bindings(&ui_definition.root, registry)?;
-
-        Ok(())
+        self.check_widget_action_bindings(&ui_definition.root, "root", registry, report);
     }
 
     /// Validate action bindings at widget level
-    fn validate_widget_action_bindings(&self, node: &WidgetNode, registry: &UiRegistry) -> Result<(), UiDefinitionLoaderError> {
+    fn check_widget_action_bindings(&self, node: &WidgetNode, path: &str, registry: &UiRegistry, report: &mut ValidationReport) {
         if let Some(ref bindings) = node.bindings {
-            for (event_name, action_binding) in bindings {
+            for (event_name, action_binding) in sorted_entries(bindings) {
+                let binding_path = format!("{}.bindings.{}", path, event_name);
                 if event_name.is_empty() {
-                    return Err(UiDefinitionLoaderError::ActionValidation(
+                    report.error(&binding_path, UiDefinitionError::Validation(
                         "Event name in widget binding cannot be empty".to_string()
                     ));
                 }
 
                 // Validate event type
                 if let Err(e) = registry.validate_event_type(event_name) {
-                    bevy_log::warn!("Event type validation warning: {}", e);
+                    report.warning(&binding_path, format!("Event type validation warning: {}", e));
                 }
 
                 // Validate action binding
-                if let Err(e) = registry.validate_action_binding(action_binding) {
+                if let Err(e) = registry.validate_widget_action_binding(&node.widget_type, action_binding) {
                     if registry.config.strict_validation && !registry.config.allow_custom_actions {
-                        return Err(UiDefinitionLoaderError::RegistryValidation(e));
+                        report.error(&binding_path, UiDefinitionError::RegistryValidation(e.to_string()));
                     } else {
-                        bevy_log::warn!("Widget action binding validation warning: {}", e);
+                        report.warning(&binding_path, format!("Widget action binding validation warning: {}", e));
                     }
                 }
             }
         }
 
         // Recursively validate children
-        for child in &node.children {
-            self.validate_widget_action_bindings(child, registry)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_widget_action_bindings(child, &format!("{}.children[{}]", path, index), registry, report);
         }
-
-        Ok(())
     }
 
     /// Validate cross-references between different parts of the definition
-    fn validate_cross_references(&self, ui_definition: &UiDefinition) -> Result<(), UiDefinitionLoaderError> {
+    fn check_cross_references(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         // Collect all action names (from global actions and widget button actions)
         let mut available_actions = HashSet::new();
         
@@ -495,39 +874,38 @@ impl UiDefinitionLoader {
         }
 
         // Validate action references in widgets
-        self.validate_action_references(&ui_definition.root, &available_actions)?;
-
-        Ok(())
+        self.check_action_references(&ui_definition.root, "root", &available_actions, report);
     }
 
     /// Validate that referenced actions exist
-    fn validate_action_references(&self, node: &WidgetNode, available_actions: &HashSet<String>) -> Result<(), UiDefinitionLoaderError> {
+    fn check_action_references(&self, node: &WidgetNode, path: &str, available_actions: &HashSet<String>, report: &mut ValidationReport) {
         // Check button actions
         // Note: Button templates no longer have action fields - actions are handled via bindings
 
         // Check action bindings
         if let Some(ref bindings) = node.bindings {
-            for (_, action_binding) in bindings {
+            for (event_name, action_binding) in sorted_entries(bindings) {
                 if !available_actions.contains(&action_binding.action) {
-                    bevy_log::warn!("Widget binding references undefined action '{}' - this may be a built-in action", action_binding.action);
+                    report.warning(
+                        format!("{}.bindings.{}.action", path, event_name),
+                        format!("Widget binding references undefined action '{}' - this may be a built-in action", action_binding.action),
+                    );
                 }
             }
         }
 
         // Recursively validate children
-        for child in &node.children {
-            self.validate_action_references(child, available_actions)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_action_references(child, &format!("{}.children[{}]", path, index), available_actions, report);
         }
-
-        Ok(())
     }
 
     /// Validate semantic constraints and layout consistency
-    fn validate_semantic_constraints(&self, node: &WidgetNode, registry: &UiRegistry, depth: usize) -> Result<(), UiDefinitionLoaderError> {
+    fn check_semantic_constraints(&self, node: &WidgetNode, path: &str, report: &mut ValidationReport) {
         // Validate layout constraints
         if let Some(size) = node.layout.size {
             if size.x < 0.0 || size.y < 0.0 {
-                return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                report.error(format!("{}.layout.size", path), UiDefinitionError::Validation(
                     "Widget size cannot be negative".to_string()
                 ));
             }
@@ -536,14 +914,14 @@ impl UiDefinitionLoader {
         // Validate flex properties
         if let Some(flex_grow) = node.layout.flex_grow {
             if flex_grow < 0.0 {
-                return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                report.error(format!("{}.layout.flex_grow", path), UiDefinitionError::Validation(
                     "Flex grow cannot be negative".to_string()
                 ));
             }
         }
         if let Some(flex_shrink) = node.layout.flex_shrink {
             if flex_shrink < 0.0 {
-                return Err(UiDefinitionLoaderError::WidgetTypeValidation(
+                report.error(format!("{}.layout.flex_shrink", path), UiDefinitionError::Validation(
                     "Flex shrink cannot be negative".to_string()
                 ));
             }
@@ -551,59 +929,35 @@ impl UiDefinitionLoader {
 
         // Validate widget type specific constraints
         match &node.widget_type {
-            WidgetType::Container { .. } => {
-                // Containers should have reasonable child limits
-                if node.children.len() > 1000 {
-                    return Err(UiDefinitionLoaderError::WidgetTypeValidation(
-                        "Container has too many children (max 1000)".to_string()
-                    ));
-                }
+            // Containers should have reasonable child limits
+            WidgetType::Container { .. } | WidgetType::ScrollView { .. } if node.children.len() > 1000 => {
+                report.error(format!("{}.children", path), UiDefinitionError::Validation(
+                    "Container has too many children (max 1000)".to_string()
+                ));
             }
-            WidgetType::Text { editable: true, .. } => {
-                // Editable text should have reasonable constraints
-                if node.children.len() > 0 {
-                    bevy_log::warn!("Editable text widget has children, which may cause interaction issues");
-                }
+            // Editable text should have reasonable constraints
+            WidgetType::Text { editable: true, .. } if !node.children.is_empty() => {
+                report.warning(format!("{}.children", path), "Editable text widget has children, which may cause interaction issues");
             }
             _ => {}
         }
 
         // Check for potential ID conflicts
         if let Some(ref id) = node.id {
-            self.validate_id_format(id)?;
+            self.check_id_format(id, &format!("{}.id", path), report);
         }
 
         // Recursively validate children
-        for child in &node.children {
-            self.validate_semantic_constraints(child, registry, depth + 1)?;
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_semantic_constraints(child, &format!("{}.children[{}]", path, index), report);
         }
-
-        Ok(())
     }
 
     /// Validate ID format and conventions
-    fn validate_id_format(&self, id: &str) -> Result<(), UiDefinitionLoaderError> {
-        if id.is_empty() {
-            return Err(UiDefinitionLoaderError::WidgetTypeValidation(
-                "Widget ID cannot be empty".to_string()
-            ));
-        }
-        if id.len() > 100 {
-            return Err(UiDefinitionLoaderError::WidgetTypeValidation(
-                "Widget ID is too long (max 100 characters)".to_string()
-            ));
+    fn check_id_format(&self, id: &str, path: &str, report: &mut ValidationReport) {
+        if let Some(problem) = widget_id_problem(id) {
+            report.error(path, UiDefinitionError::Validation(problem));
         }
-        if !id.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
-            return Err(UiDefinitionLoaderError::WidgetTypeValidation(
-                "Widget ID can only contain alphanumeric characters, underscores, and hyphens".to_string()
-            ));
-        }
-        if id.starts_with(char::is_numeric) {
-            return Err(UiDefinitionLoaderError::WidgetTypeValidation(
-                "Widget ID cannot start with a number".to_string()
-            ));
-        }
-        Ok(())
     }
 
     /// Enhanced error logging for detailed debugging and diagnostics
@@ -646,7 +1000,17 @@ impl UiDefinitionLoader {
         let indent = "  ".repeat(depth);
         let widget_type_name = match &node.widget_type {
             WidgetType::Container { direction } => format!("Container({:?})", direction),
+            WidgetType::ScrollView { direction, axes, .. } => format!("ScrollView({:?}, {:?})", direction, axes),
+            WidgetType::VirtualList { items, provider, .. } => format!("VirtualList(items={:?}, provider={:?})", items, provider),
             WidgetType::Button { text, .. } => format!("Button(text={:?})", text),
+            WidgetType::Checkbox { checked, label, .. } => format!("Checkbox(label={:?}, checked={})", label, checked),
+            WidgetType::Toggle { checked, label, .. } => format!("Toggle(label={:?}, checked={})", label, checked),
+            WidgetType::Slider { value, min, max, .. } => format!("Slider(value={}, range={}..={})", value, min, max),
+            WidgetType::DragValue { value, min, max, .. } => format!("DragValue(value={}, range={}..={})", value, min, max),
+            WidgetType::TextInput { placeholder, password, .. } => format!("TextInput(placeholder={:?}, password={})", placeholder, password),
+            WidgetType::Dropdown { options, selected, .. } => format!("Dropdown({} options, selected={:?})", options.len(), selected),
+            WidgetType::Popup { anchor, placement, .. } => format!("Popup(anchor={:?}, {:?})", anchor, placement),
+            WidgetType::Tabs { tabs, active, .. } => format!("Tabs({} tabs, active={:?})", tabs.len(), active),
             WidgetType::Text { content, editable } => format!("Text('{}', editable={})", 
                 if content.len() > 20 { format!("{}...", &content[..20]) } else { content.clone() }, editable),
             WidgetType::Shape { shape_type } => format!("Shape({:?})", shape_type),
@@ -691,6 +1055,10 @@ impl UiDefinitionLoader {
                 bevy_log::error!("Registry validation failed: {}", err);
                 // Registry errors already have good context
             }
+            UiDefinitionLoaderError::Report(report) => {
+                bevy_log::error!("Validation found {} error(s) and {} warning(s):", report.error_count(), report.warning_count());
+                report.log();
+            }
             UiDefinitionLoaderError::InvalidConfiguration(msg) => {
                 bevy_log::error!("Invalid configuration: {}", msg);
                 if let Some(ref window) = ui_definition.window {
@@ -720,7 +1088,17 @@ impl UiDefinitionLoader {
     fn count_widget_types(&self, node: &WidgetNode, counts: &mut HashMap<String, usize>) {
         let widget_type_name = match &node.widget_type {
             WidgetType::Container { .. } => "Container".to_string(),
+            WidgetType::ScrollView { .. } => "ScrollView".to_string(),
+            WidgetType::VirtualList { .. } => "VirtualList".to_string(),
             WidgetType::Button { .. } => "Button".to_string(),
+            WidgetType::Checkbox { .. } => "Checkbox".to_string(),
+            WidgetType::Toggle { .. } => "Toggle".to_string(),
+            WidgetType::Slider { .. } => "Slider".to_string(),
+            WidgetType::DragValue { .. } => "DragValue".to_string(),
+            WidgetType::TextInput { .. } => "TextInput".to_string(),
+            WidgetType::Dropdown { .. } => "Dropdown".to_string(),
+            WidgetType::Popup { .. } => "Popup".to_string(),
+            WidgetType::Tabs { .. } => "Tabs".to_string(),
             WidgetType::Text { .. } => "Text".to_string(),
             WidgetType::Shape { .. } => "Shape".to_string(),
             WidgetType::Custom { component, .. } => format!("Custom({})", component),
@@ -768,6 +1146,7 @@ impl UiDefinitionLoader {
         if style_override.border_radius.is_some() { count += 1; }
         if style_override.text_size.is_some() { count += 1; }
         if style_override.opacity.is_some() { count += 1; }
+        if style_override.states.is_some() { count += 1; }
         count
     }
 
@@ -834,64 +1213,82 @@ impl UiDefinitionLoader {
     pub fn log_validation_warnings(&self, ui_definition: &UiDefinition, _registry: &UiRegistry) {
         bevy_log::info!("=== Validation Warnings & Suggestions ===");
         
-        // Check for potential issues
-        self.check_widget_tree_issues(&ui_definition.root, 0);
-        self.check_style_consistency_issues(ui_definition);
-        self.check_action_consistency_issues(ui_definition);
-        self.check_performance_suggestions(ui_definition);
+        self.validation_warnings(ui_definition).log();
         
         bevy_log::info!("=== End Warnings & Suggestions ===");
     }
 
+    /// Collect warnings and suggestions that don't prevent the definition from loading
+    pub fn validation_warnings(&self, ui_definition: &UiDefinition) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        
+        // Check for potential issues
+        self.check_widget_tree_issues(&ui_definition.root, "root", 0, &mut report);
+        self.check_style_consistency_issues(ui_definition, &mut report);
+        self.check_action_consistency_issues(ui_definition, &mut report);
+        self.check_performance_suggestions(ui_definition, &mut report);
+        
+        report
+    }
+
     /// Check for widget tree issues
-    fn check_widget_tree_issues(&self, node: &WidgetNode, depth: usize) {
+    fn check_widget_tree_issues(&self, node: &WidgetNode, path: &str, depth: usize, report: &mut ValidationReport) {
+        let name = node.id.as_deref().unwrap_or("<anonymous>");
+
         // Check for anonymous widgets
         if node.id.is_none() {
-            bevy_log::warn!("Widget at depth {} has no ID, which may complicate debugging", depth);
+            report.warning(path, format!("Widget at depth {} has no ID, which may complicate debugging", depth));
         }
         
         // Check for empty containers
-        if let WidgetType::Container { .. } = &node.widget_type {
+        if let WidgetType::Container { .. } | WidgetType::ScrollView { .. } = &node.widget_type {
             if node.children.is_empty() {
-                bevy_log::warn!("Container widget '{}' has no children", 
-                               node.id.as_ref().unwrap_or(&"<anonymous>".to_string()));
+                report.warning(path, format!("Container widget '{}' has no children", name));
             }
         }
         
         // Check for deeply nested widgets
         if depth > 10 {
-            bevy_log::warn!("Widget '{}' is deeply nested (depth {}), consider flattening hierarchy", 
-                           node.id.as_ref().unwrap_or(&"<anonymous>".to_string()), depth);
+            report.warning(path, format!("Widget '{}' is deeply nested (depth {}), consider flattening hierarchy", name, depth));
         }
         
         // Check for widgets with many children
         if node.children.len() > 20 {
-            bevy_log::warn!("Widget '{}' has {} children, consider grouping or pagination", 
-                           node.id.as_ref().unwrap_or(&"<anonymous>".to_string()), node.children.len());
+            report.warning(path, format!("Widget '{}' has {} children, consider grouping or pagination", name, node.children.len()));
         }
         
-        for child in &node.children {
-            self.check_widget_tree_issues(child, depth + 1);
+        for (index, child) in node.children.iter().enumerate() {
+            self.check_widget_tree_issues(child, &format!("{}.children[{}]", path, index), depth + 1, report);
         }
     }
 
     /// Check for style consistency issues
-    fn check_style_consistency_issues(&self, ui_definition: &UiDefinition) {
+    fn check_style_consistency_issues(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         if let Some(ref global_styles) = ui_definition.styles {
             // Check for unused global styles
             let mut used_classes = HashSet::new();
             self.collect_used_style_classes(&ui_definition.root, &mut used_classes);
-            
-            for class_name in global_styles.keys() {
-                if !used_classes.contains(class_name) {
-                    bevy_log::warn!("Global style class '{}' is defined but never used", class_name);
+
+            // Classes extended by used classes are used too
+            let mut pending: Vec<String> = used_classes.iter().cloned().collect();
+            while let Some(class_name) = pending.pop() {
+                let parents = global_styles.get(&class_name).and_then(|style| style.extends.as_ref());
+                for parent in parents.into_iter().flatten() {
+                    if used_classes.insert(parent.clone()) {
+                        pending.push(parent.clone());
+                    }
                 }
             }
             
-            // Check for potential naming conflicts
-            for class_name in global_styles.keys() {
-                if class_name.contains(" ") || class_name.contains("-") {
-                    bevy_log::warn!("Style class '{}' contains spaces or hyphens, consider using underscores", class_name);
+            for (class_name, _) in sorted_entries(global_styles) {
+                let path = format!("styles.{}", class_name);
+                if !used_classes.contains(class_name) {
+                    report.warning(&path, format!("Global style class '{}' is defined but never used", class_name));
+                }
+                
+                // Check for potential naming conflicts
+                if class_name.contains(' ') || class_name.contains('-') {
+                    report.warning(&path, format!("Style class '{}' contains spaces or hyphens, consider using underscores", class_name));
                 }
             }
         }
@@ -911,15 +1308,15 @@ impl UiDefinitionLoader {
     }
 
     /// Check for action consistency issues
-    fn check_action_consistency_issues(&self, ui_definition: &UiDefinition) {
+    fn check_action_consistency_issues(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         if let Some(ref global_actions) = ui_definition.actions {
             // Check for unused global actions
             let mut used_actions = HashSet::new();
             self.collect_used_actions(&ui_definition.root, &mut used_actions);
             
-            for action_name in global_actions.keys() {
+            for (action_name, _) in sorted_entries(global_actions) {
                 if !used_actions.contains(action_name) {
-                    bevy_log::warn!("Global action '{}' is defined but never used", action_name);
+                    report.warning(format!("actions.{}", action_name), format!("Global action '{}' is defined but never used", action_name));
                 }
             }
         }
@@ -931,7 +1328,7 @@ impl UiDefinitionLoader {
         
         // Check widget bindings
         if let Some(ref bindings) = node.bindings {
-            for (_, action_binding) in bindings {
+            for action_binding in bindings.values() {
                 used_actions.insert(action_binding.action.clone());
             }
         }
@@ -942,16 +1339,16 @@ impl UiDefinitionLoader {
     }
 
     /// Check for performance suggestions
-    fn check_performance_suggestions(&self, ui_definition: &UiDefinition) {
+    fn check_performance_suggestions(&self, ui_definition: &UiDefinition, report: &mut ValidationReport) {
         let total_widgets = self.count_total_widgets(&ui_definition.root);
         
         if total_widgets > 100 {
-            bevy_log::warn!("UI definition contains {} widgets, consider virtualization for large lists", total_widgets);
+            report.warning("root", format!("UI definition contains {} widgets, consider virtualization for large lists", total_widgets));
         }
         
         if let Some(ref global_styles) = ui_definition.styles {
             if global_styles.len() > 50 {
-                bevy_log::warn!("Large number of global styles ({}), consider style consolidation", global_styles.len());
+                report.warning("styles", format!("Large number of global styles ({}), consider style consolidation", global_styles.len()));
             }
         }
     }
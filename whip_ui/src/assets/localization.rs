@@ -0,0 +1,386 @@
+use bevy_asset::{Asset, AssetEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext};
+use bevy_ecs::prelude::*;
+use bevy_log::{info, warn};
+use bevy_reflect::TypePath;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use thiserror::Error;
+use crate::gui_framework::components::Text;
+use crate::gui_framework::events::YrsTextChanged;
+use crate::widgets::components::{Widget, WidgetLocalizedText, WidgetText};
+use crate::{FontServerResource, YrsDocResource};
+use super::{UiDefinitionError, ValidationReport};
+use super::reload::set_static_text;
+
+/// Locale used until the application or a `set_locale` action picks another one
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// Key of an inline string table reference: `content = { t = "toolbar.save" }`
+pub const LOCALIZED_KEY: &str = "t";
+
+/// Key of the interpolation arguments next to [`LOCALIZED_KEY`]
+pub const ARGS_KEY: &str = "args";
+
+/// `widget_type` fields that hold a widget's text and may reference string tables
+const TEXT_FIELDS: &[&str] = &["content", "text", "label", "placeholder"];
+
+/// Errors that can occur while loading string tables
+#[derive(Error, Debug)]
+pub enum StringTableError {
+    #[error("Failed to read string table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse string table: {0}")]
+    Parse(String),
+    #[error("String '{0}' must be text or a table of strings")]
+    InvalidEntry(String),
+}
+
+/// A widget's text looked up in the current locale's string table.
+///
+/// Written inline in source files as `content = { t = "greeting", args = { name = "Ada" } }`
+/// (`text = ...` on buttons); the loader moves it into the node's `localized` field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalizedText {
+    /// String table key, e.g. `toolbar.save`
+    pub key: String,
+    /// Values for `{name}` placeholders in the translated string
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub args: HashMap<String, Value>,
+}
+
+/// The strings of one locale, loaded from a `.strings.toml` file:
+///
+/// ```toml
+/// locale = "de-DE"
+///
+/// [strings]
+/// greeting = "Hallo, {name}!"
+/// toolbar.save = "Speichern"
+/// ```
+///
+/// Nested tables (and dotted keys) are flattened into dotted string keys.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct StringTable {
+    pub locale: String,
+    pub strings: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct StringTableSource {
+    locale: String,
+    #[serde(default)]
+    strings: Map<String, Value>,
+}
+
+impl StringTable {
+    /// Parse a string table from TOML source text
+    pub fn parse(content: &str) -> Result<Self, StringTableError> {
+        let source: StringTableSource = toml::from_str(content)
+            .map_err(|e| StringTableError::Parse(e.message().trim_end().to_string()))?;
+        let mut strings = HashMap::new();
+        flatten_strings(&source.strings, "", &mut strings)?;
+        Ok(Self { locale: source.locale, strings })
+    }
+}
+
+fn flatten_strings(table: &Map<String, Value>, prefix: &str, strings: &mut HashMap<String, String>) -> Result<(), StringTableError> {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            Value::String(text) => {
+                strings.insert(key, text.clone());
+            }
+            Value::Object(nested) => flatten_strings(nested, &key, strings)?,
+            _ => return Err(StringTableError::InvalidEntry(key)),
+        }
+    }
+    Ok(())
+}
+
+/// Asset loader for `.strings.toml` string tables
+#[derive(Default)]
+pub struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn bevy_asset::io::Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let content = String::from_utf8(bytes)
+            .map_err(|e| StringTableError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        StringTable::parse(&content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings.toml"]
+    }
+}
+
+/// Event to request loading a string table asset
+#[derive(Event, Debug, Clone)]
+pub struct LoadStringTableRequest {
+    /// Path to the `.strings.toml` file
+    pub asset_path: String,
+}
+
+impl LoadStringTableRequest {
+    pub fn new(asset_path: impl Into<String>) -> Self {
+        Self { asset_path: asset_path.into() }
+    }
+}
+
+/// Resource holding the current locale and the string tables loaded for each locale
+#[derive(Resource, Debug, Clone)]
+pub struct Localization {
+    locale: String,
+    fallback_locale: String,
+    tables: HashMap<String, HashMap<String, String>>,
+    /// Handles that keep loaded tables alive (and hot reloading)
+    handles: Vec<Handle<StringTable>>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            locale: DEFAULT_LOCALE.to_string(),
+            fallback_locale: DEFAULT_LOCALE.to_string(),
+            tables: HashMap::new(),
+            handles: Vec::new(),
+        }
+    }
+}
+
+impl Localization {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current locale, e.g. `de-DE`
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Switch the current locale; text of localized widgets follows on the next update
+    pub fn set_locale(&mut self, locale: impl Into<String>) {
+        self.locale = locale.into();
+    }
+
+    /// Locale whose strings are used for keys missing from the current locale
+    pub fn fallback_locale(&self) -> &str {
+        &self.fallback_locale
+    }
+
+    pub fn set_fallback_locale(&mut self, locale: impl Into<String>) {
+        self.fallback_locale = locale.into();
+    }
+
+    /// Whether any strings are loaded for `locale`
+    pub fn has_locale(&self, locale: &str) -> bool {
+        self.tables.contains_key(locale)
+    }
+
+    /// Add a table's strings to its locale, replacing existing entries with the same key
+    pub fn add_table(&mut self, table: &StringTable) {
+        self.tables.entry(table.locale.clone())
+            .or_default()
+            .extend(table.strings.iter().map(|(key, text)| (key.clone(), text.clone())));
+    }
+
+    /// The string for `key` in the current locale, or else the fallback locale
+    pub fn translate(&self, key: &str) -> Option<&str> {
+        [&self.locale, &self.fallback_locale].into_iter()
+            .find_map(|locale| self.tables.get(locale)?.get(key))
+            .map(String::as_str)
+    }
+
+    /// Text to display for a localized widget; missing keys show the key itself
+    pub fn format(&self, text: &LocalizedText) -> String {
+        interpolate(self.translate(&text.key).unwrap_or(&text.key), &text.args)
+    }
+}
+
+/// Replace `{name}` placeholders with their arguments. `{{` and `}}` stand for literal braces;
+/// placeholders without an argument are kept as written.
+pub fn interpolate(template: &str, args: &HashMap<String, Value>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            result.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail.strip_prefix('{')
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]));
+        match placeholder {
+            Some(name) => {
+                match args.get(name.trim()) {
+                    Some(value) => result.push_str(&super::state::display_value(value)),
+                    None => result.push_str(&tail[..name.len() + 2]),
+                }
+                rest = &tail[name.len() + 2..];
+            }
+            None => {
+                result.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Check whether a UI definition document references string tables inline
+pub fn document_has_localized_text(document: &Value) -> bool {
+    fn has_localized(node: &Value) -> bool {
+        localized_fields(node).next().is_some()
+            || node.get("children").and_then(Value::as_array).is_some_and(|children| children.iter().any(has_localized))
+    }
+    document.get("root").is_some_and(has_localized)
+}
+
+/// Move inline `{ t = "key" }` text into each node's `localized` field, leaving the key as the
+/// text so the node deserializes and shows something until string tables are applied
+pub fn lift_localized_text(document: &mut Value, report: &mut ValidationReport) {
+    if let Some(root) = document.get_mut("root") {
+        lift_node(root, "root", report);
+    }
+}
+
+fn localized_fields(node: &Value) -> impl Iterator<Item = &'static str> + '_ {
+    let widget_type = node.get("widget_type").and_then(Value::as_object);
+    TEXT_FIELDS.iter().copied().filter(move |field| {
+        widget_type.and_then(|fields| fields.get(*field)).and_then(Value::as_object).is_some_and(|value| value.contains_key(LOCALIZED_KEY))
+    })
+}
+
+fn lift_node(node: &mut Value, path: &str, report: &mut ValidationReport) {
+    let fields: Vec<&'static str> = localized_fields(node).collect();
+    for field in fields {
+        let location = format!("{}.widget_type.{}", path, field);
+        let Some(slot) = node.pointer_mut(&format!("/widget_type/{}", field)) else {
+            continue;
+        };
+        let localized = serde_json::from_value::<InlineLocalizedText>(slot.clone());
+        match localized {
+            Ok(InlineLocalizedText { key, args }) => {
+                *slot = Value::String(key.clone());
+                if let Some(node) = node.as_object_mut() {
+                    node.insert("localized".to_string(), serde_json::json!(LocalizedText { key, args }));
+                }
+            }
+            Err(e) => report.error(location, UiDefinitionError::Validation(format!(
+                "Expected {{ {} = \"key\", {} = {{ ... }} }}: {}", LOCALIZED_KEY, ARGS_KEY, e
+            ))),
+        }
+    }
+
+    if let Some(Value::Array(children)) = node.get_mut("children") {
+        for (index, child) in children.iter_mut().enumerate() {
+            lift_node(child, &format!("{}.children[{}]", path, index), report);
+        }
+    }
+}
+
+/// The inline form of [`LocalizedText`]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InlineLocalizedText {
+    #[serde(rename = "t")]
+    key: String,
+    #[serde(default)]
+    args: HashMap<String, Value>,
+}
+
+/// System that starts loading requested string tables
+pub fn string_table_request_system(
+    mut requests: EventReader<LoadStringTableRequest>,
+    mut localization: ResMut<Localization>,
+    asset_server: Res<AssetServer>,
+) {
+    for request in requests.read() {
+        info!("Loading string table: {}", request.asset_path);
+        let handle = asset_server.load(&request.asset_path);
+        localization.handles.push(handle);
+    }
+}
+
+/// System that adds loaded (and hot reloaded) string tables to `Localization`
+pub fn string_table_loaded_system(
+    mut asset_events: EventReader<AssetEvent<StringTable>>,
+    mut localization: ResMut<Localization>,
+    tables: Res<Assets<StringTable>>,
+) {
+    for event in asset_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if let Some(table) = tables.get(*id) {
+            info!("String table loaded for locale {} ({} strings)", table.locale, table.strings.len());
+            localization.add_table(table);
+        }
+    }
+}
+
+/// System that shows the current locale's strings in localized text widgets.
+///
+/// Every localized widget is refreshed when the locale or the loaded tables change; newly
+/// spawned or reloaded widgets are filled in on their own.
+pub fn apply_localized_text_system(
+    localization: Res<Localization>,
+    yrs_res: Res<YrsDocResource>,
+    mut text_changed_writer: EventWriter<YrsTextChanged>,
+    mut widgets: Query<(Entity, Ref<WidgetLocalizedText>, &mut Widget, Option<&mut WidgetText>)>,
+) {
+    let refresh_all = localization.is_changed();
+    for (entity, localized, mut widget, mut widget_text) in widgets.iter_mut() {
+        if !refresh_all && !localized.is_changed() {
+            continue;
+        }
+        let content = localization.format(&localized.text);
+        if set_static_text(&yrs_res, entity, &mut widget, widget_text.as_deref_mut(), &content) {
+            text_changed_writer.send(YrsTextChanged { entity });
+        }
+    }
+}
+
+/// System that keeps the `FontSystem` locale in step with `Localization`.
+///
+/// The locale steers shaping and font fallback, so all text is laid out again when it changes.
+pub fn sync_font_locale_system(
+    localization: Res<Localization>,
+    font_server: Option<Res<FontServerResource>>,
+    texts: Query<Entity, With<Text>>,
+    mut text_changed_writer: EventWriter<YrsTextChanged>,
+) {
+    if !localization.is_changed() {
+        return;
+    }
+    let Some(font_server) = font_server else {
+        return;
+    };
+    let Ok(mut font_server) = font_server.0.lock() else {
+        warn!("Failed to lock FontServerResource; keeping the previous font locale");
+        return;
+    };
+    if font_server.locale() == localization.locale() {
+        return;
+    }
+
+    info!("Switching font locale to {}", localization.locale());
+    font_server.set_locale(localization.locale());
+    for entity in texts.iter() {
+        text_changed_writer.send(YrsTextChanged { entity });
+    }
+}
\ No newline at end of file
@@ -0,0 +1,271 @@
+use bevy_log::warn;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+use super::{UiDefinitionFormat, UiDefinitionLoaderError, export::tidy_document, loaders::parse_source};
+
+/// Version of the UI definition format this crate reads and writes.
+///
+/// Files without a `format_version` are version 1, or version 0 if they are `WidgetCollection`
+/// documents. Bump this together with a new entry in [`MIGRATIONS`] whenever `WidgetNode`,
+/// `WidgetType` or `LayoutConfig` change shape.
+pub const CURRENT_FORMAT_VERSION: u32 = 1;
+
+/// Top-level key holding a document's format version
+pub const FORMAT_VERSION_KEY: &str = "format_version";
+
+/// Serde default for `UiDefinition::format_version`
+pub(crate) fn current_format_version() -> u32 {
+    CURRENT_FORMAT_VERSION
+}
+
+/// Errors that can occur while upgrading a UI definition document
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    #[error("format_version must be a non-negative integer, found {0}")]
+    InvalidVersion(String),
+    #[error("format_version {found} is newer than this version of whip_ui supports ({supported})")]
+    UnsupportedVersion { found: u64, supported: u32 },
+    #[error("Failed to migrate from format version {version}: {reason}")]
+    Failed { version: u32, reason: String },
+}
+
+/// One upgrade step, from format version `from` to `from + 1`
+#[derive(Clone, Copy)]
+pub struct Migration {
+    pub from: u32,
+    /// What the step changes, shown when it is applied
+    pub description: &'static str,
+    /// Upgrade a document in place, returning whether anything changed
+    pub(crate) apply: fn(&mut Value) -> Result<bool, String>,
+}
+
+/// Every upgrade step, oldest first
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        description: "turned the WidgetCollection blueprints into a hierarchical root node",
+        apply: widget_collection_to_hierarchy,
+    },
+];
+
+/// The steps applied to a document by [`migrate_document`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    /// Format version the document was written in
+    pub from_version: u32,
+    /// Format version the document was upgraded to
+    pub to_version: u32,
+    /// Descriptions of the steps that changed the document
+    pub applied: Vec<&'static str>,
+}
+
+impl MigrationReport {
+    /// Whether the document's content changed, beyond its version number
+    pub fn changed(&self) -> bool {
+        !self.applied.is_empty()
+    }
+
+    /// Whether the document was written in an older format version
+    pub fn is_outdated(&self) -> bool {
+        self.from_version < self.to_version
+    }
+}
+
+/// The format version a document was written in
+pub fn document_format_version(document: &Value) -> Result<u32, MigrationError> {
+    format_version_up_to(document, CURRENT_FORMAT_VERSION)
+}
+
+fn format_version_up_to(document: &Value, supported: u32) -> Result<u32, MigrationError> {
+    match document.get(FORMAT_VERSION_KEY) {
+        Some(version) => {
+            let found = version.as_u64().ok_or_else(|| MigrationError::InvalidVersion(version.to_string()))?;
+            if found > supported as u64 {
+                return Err(MigrationError::UnsupportedVersion { found, supported });
+            }
+            Ok(found as u32)
+        }
+        None if is_widget_collection(document) => Ok(0),
+        None => Ok(1),
+    }
+}
+
+/// Upgrade a document to [`CURRENT_FORMAT_VERSION`] one step at a time and record the new
+/// version in it. Runs before deserialization, so older files keep loading as the types change.
+pub fn migrate_document(document: &mut Value) -> Result<MigrationReport, MigrationError> {
+    migrate_document_with(document, MIGRATIONS, CURRENT_FORMAT_VERSION)
+}
+
+/// Upgrade a document to `current_version` through `migrations`, which must run up to it
+pub(crate) fn migrate_document_with(document: &mut Value, migrations: &[Migration], current_version: u32) -> Result<MigrationReport, MigrationError> {
+    let from_version = format_version_up_to(document, current_version)?;
+    let mut report = MigrationReport { from_version, to_version: current_version, applied: Vec::new() };
+    for migration in migrations.iter().filter(|migration| migration.from >= from_version) {
+        let changed = (migration.apply)(document)
+            .map_err(|reason| MigrationError::Failed { version: migration.from, reason })?;
+        if changed {
+            report.applied.push(migration.description);
+        }
+    }
+    set_format_version(document, current_version);
+    Ok(report)
+}
+
+/// Upgrade UI definition source text, returning what was done and the rewritten text, or
+/// `None` if the file is already at the current version.
+///
+/// Files that only need their version recorded keep their formatting (and TOML comments);
+/// files whose content changed are written out again, which drops TOML comments.
+pub fn migrate_source(content: &str, format: UiDefinitionFormat) -> Result<(MigrationReport, Option<String>), UiDefinitionLoaderError> {
+    migrate_source_with(content, format, MIGRATIONS, CURRENT_FORMAT_VERSION)
+}
+
+/// [`migrate_source`] through `migrations`, up to `current_version`
+pub(crate) fn migrate_source_with(
+    content: &str,
+    format: UiDefinitionFormat,
+    migrations: &[Migration],
+    current_version: u32,
+) -> Result<(MigrationReport, Option<String>), UiDefinitionLoaderError> {
+    let mut document: Value = parse_source(content, format)?;
+    let report = migrate_document_with(&mut document, migrations, current_version)?;
+    if !report.is_outdated() {
+        return Ok((report, None));
+    }
+
+    // TOML has no null, and blueprints exported before version 1 wrote unset fields as null
+    tidy_document(&mut document);
+    let rewritten = match format {
+        UiDefinitionFormat::Toml if !report.changed() => set_toml_format_version(content, current_version),
+        UiDefinitionFormat::Toml => toml::to_string_pretty(&document)
+            .map_err(|e| UiDefinitionLoaderError::Serialize(e.to_string()))?,
+        UiDefinitionFormat::Json => serde_json::to_string_pretty(&document)
+            .map(|json| json + "\n")
+            .map_err(|e| UiDefinitionLoaderError::Serialize(e.to_string()))?,
+    };
+    Ok((report, Some(rewritten)))
+}
+
+/// Put `version` first in the document, where readers look for it
+fn set_format_version(document: &mut Value, version: u32) {
+    let Some(fields) = document.as_object_mut() else {
+        return;
+    };
+    let mut versioned = Map::new();
+    versioned.insert(FORMAT_VERSION_KEY.to_string(), json!(version));
+    versioned.extend(std::mem::take(fields).into_iter().filter(|(key, _)| key != FORMAT_VERSION_KEY));
+    *fields = versioned;
+}
+
+/// Replace or add the top-level `format_version` line of TOML source
+fn set_toml_format_version(content: &str, version: u32) -> String {
+    let version_line = format!("{} = {}", FORMAT_VERSION_KEY, version);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    // Top-level keys come before the first table header
+    let top_level = lines.iter().position(|line| line.trim_start().starts_with('[')).unwrap_or(lines.len());
+    let existing = lines[..top_level].iter().position(|line| {
+        line.trim_start().strip_prefix(FORMAT_VERSION_KEY).is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+    match existing {
+        Some(index) => lines[index] = version_line,
+        None => {
+            lines.insert(0, version_line);
+            lines.insert(1, String::new());
+        }
+    }
+    let mut rewritten = lines.join("\n");
+    if content.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+/// Rename a field of every widget node in the document, for migrations that rename a
+/// `WidgetNode` or config field. `from` is a dotted path within a node such as
+/// `"layout.padding"`, and `to` the new name of its last segment.
+pub fn rename_node_field(document: &mut Value, from: &str, to: &str) -> Result<bool, String> {
+    match document.get_mut("root") {
+        Some(root) => rename_in_node(root, from, to),
+        None => Ok(false),
+    }
+}
+
+fn rename_in_node(node: &mut Value, from: &str, to: &str) -> Result<bool, String> {
+    let (section, name) = match from.rsplit_once('.') {
+        Some((parents, name)) => (node.pointer_mut(&format!("/{}", parents.replace('.', "/"))), name),
+        None => (Some(&mut *node), from),
+    };
+    let mut changed = false;
+    if let Some(fields) = section.and_then(Value::as_object_mut).filter(|fields| fields.contains_key(name)) {
+        if fields.contains_key(to) {
+            return Err(format!("'{}' and its new name '{}' are both set", from, to));
+        }
+        // Rebuilt rather than removed and inserted, so the field keeps its place
+        *fields = std::mem::take(fields).into_iter()
+            .map(|(key, value)| if key == name { (to.to_string(), value) } else { (key, value) })
+            .collect();
+        changed = true;
+    }
+    if let Some(Value::Array(children)) = node.get_mut("children") {
+        for child in children {
+            changed |= rename_in_node(child, from, to)?;
+        }
+    }
+    Ok(changed)
+}
+
+fn is_widget_collection(document: &Value) -> bool {
+    document.get("widgets").is_some_and(Value::is_object) && !document.get("root").is_some_and(Value::is_object)
+}
+
+/// Version 0 -> 1: `{ widgets = { id = blueprint }, root = "id" }`, with blueprints listing their
+/// children by id, becomes a single `root` node with nested children
+fn widget_collection_to_hierarchy(document: &mut Value) -> Result<bool, String> {
+    if !is_widget_collection(document) {
+        return Ok(false);
+    }
+    let Some(fields) = document.as_object_mut() else {
+        return Ok(false);
+    };
+    let Some(Value::Object(mut widgets)) = fields.remove("widgets") else {
+        return Ok(false);
+    };
+    let root_id = match fields.remove("root") {
+        Some(Value::String(id)) => id,
+        Some(other) => return Err(format!("'root' must name a widget, found {}", other)),
+        None => return Err("the widget collection has no 'root' widget".to_string()),
+    };
+
+    let root = collection_node(&mut widgets, &root_id)?;
+    if !widgets.is_empty() {
+        let unreachable: Vec<&str> = widgets.keys().map(String::as_str).collect();
+        warn!("Dropping widgets not reachable from the root: {}", unreachable.join(", "));
+    }
+    fields.insert("root".to_string(), root);
+    Ok(true)
+}
+
+fn collection_node(widgets: &mut Map<String, Value>, id: &str) -> Result<Value, String> {
+    // Taking each blueprint out also stops cycles and widgets with two parents
+    let mut blueprint = widgets.remove(id)
+        .ok_or_else(|| format!("widget '{}' is not defined or has more than one parent", id))?;
+    let fields = blueprint.as_object_mut()
+        .ok_or_else(|| format!("widget '{}' must be a table", id))?;
+    fields.insert("id".to_string(), Value::String(id.to_string()));
+
+    let child_ids = match fields.remove("children") {
+        Some(Value::Array(child_ids)) => child_ids,
+        Some(other) => return Err(format!("children of widget '{}' must be a list of ids, found {}", id, other)),
+        None => Vec::new(),
+    };
+    let children = child_ids.iter()
+        .map(|child_id| match child_id.as_str() {
+            Some(child_id) => collection_node(widgets, child_id),
+            None => Err(format!("children of widget '{}' must be ids, found {}", id, child_id)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !children.is_empty() {
+        fields.insert("children".to_string(), Value::Array(children));
+    }
+    Ok(blueprint)
+}
\ No newline at end of file
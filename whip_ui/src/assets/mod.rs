@@ -3,6 +3,19 @@ pub mod plugin;
 pub mod definitions;
 pub mod registry;
 pub mod loaders;
+pub mod includes;
+pub mod tokens;
+pub mod reload;
+pub mod validation;
+pub mod schema;
+pub mod export;
+pub mod widget_templates;
+pub mod state;
+pub mod dynamic;
+pub mod localization;
+pub mod extensions;
+pub mod migrations;
+pub mod windows;
 
 #[cfg(test)]
 mod tests;
@@ -13,21 +26,74 @@ pub use plugin::*;
 pub use definitions::*;
 pub use registry::*;
 pub use loaders::*;
+pub use includes::*;
+pub use tokens::*;
+pub use reload::*;
+pub use validation::*;
+pub use schema::*;
+pub use export::*;
+pub use widget_templates::*;
+pub use state::*;
+pub use dynamic::*;
+pub use localization::*;
+pub use extensions::*;
+pub use migrations::*;
+pub use windows::*;
 
 /// Window configuration loaded from TOML
 #[derive(Debug, Clone, bevy_ecs::prelude::Resource, serde::Deserialize, serde::Serialize)]
 pub struct WindowConfig {
+    /// Window title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     /// Window size [width, height]
     pub size: [f32; 2],
     /// Background color for the window
     pub background_color: Option<crate::widgets::blueprint::ColorDef>,
+    /// Window icon image (PNG), relative to the assets folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Smallest size [width, height] the window can be resized to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<[f32; 2]>,
+    /// Largest size [width, height] the window can be resized to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<[f32; 2]>,
+    /// Whether the user can resize the window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resizable: Option<bool>,
+    /// Whether the window has a title bar and borders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decorations: Option<bool>,
+    /// Windowed or fullscreen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fullscreen: Option<WindowModeDef>,
+    /// How frames are presented (vsync)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub present_mode: Option<PresentModeDef>,
+    /// Whether the window shows what is behind it where the background is translucent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transparent: Option<bool>,
+    /// Initial position [x, y] of the window's top-left corner on screen, in physical pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<[i32; 2]>,
 }
 
 impl Default for WindowConfig {
     fn default() -> Self {
         Self {
+            title: None,
             size: [600.0, 300.0],
             background_color: Some(crate::widgets::blueprint::ColorDef::Rgba { r: 33, g: 41, b: 42, a: 1.0 }),
+            icon: None,
+            min_size: None,
+            max_size: None,
+            resizable: None,
+            decorations: None,
+            fullscreen: None,
+            present_mode: None,
+            transparent: None,
+            position: None,
         }
     }
 }
\ No newline at end of file
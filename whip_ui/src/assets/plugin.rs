@@ -5,12 +5,36 @@ use crate::assets::{
     UiDefinition,
     UiDefinitionLoader,
     LoadUiRequest,
+    UnloadUiRequest,
+    ReplaceUiRequest,
     LoadingUiAssets,
     UiRegistry,
+    UiState,
+    StringTable,
+    StringTableLoader,
+    LoadStringTableRequest,
+    Localization,
+    UiWindows,
+    ui_asset_unload_system,
     ui_asset_request_system,
     ui_asset_loaded_system,
     ui_asset_error_system,
+    ui_asset_reload_system,
+    update_dynamic_children_system,
+    apply_state_bindings_system,
+    string_table_request_system,
+    string_table_loaded_system,
+    apply_localized_text_system,
+    sync_font_locale_system,
+    target_window_propagation_system,
+    ui_window_closed_system,
+    WindowIconImage,
+    WindowIconLoader,
+    window_icon_request_system,
+    window_icon_system,
 };
+use crate::widgets::scroll_view::{scroll_into_view_system, scroll_view_measure_system};
+use crate::widgets::virtual_list::{VirtualListProviders, virtual_list_rows_system, virtual_list_layout_system};
 
 /// Plugin that adds UI asset loading capabilities to the app
 pub struct UiAssetPlugin;
@@ -20,24 +44,73 @@ impl Plugin for UiAssetPlugin {
         // Register the UiDefinition asset type and its loader
         app.init_asset::<UiDefinition>()
            .register_asset_loader(UiDefinitionLoader);
+        app.init_asset::<StringTable>()
+           .register_asset_loader(StringTableLoader);
+        app.init_asset::<WindowIconImage>()
+           .register_asset_loader(WindowIconLoader);
         
-        // Add the LoadUiRequest event
+        // Add the UI request and LoadStringTableRequest events
         app.add_event::<LoadUiRequest>();
+        app.add_event::<UnloadUiRequest>();
+        app.add_event::<ReplaceUiRequest>();
+        app.add_event::<LoadStringTableRequest>();
         
         // Add resources
         app.init_resource::<LoadingUiAssets>();
+        app.init_resource::<UiState>();
+        app.init_resource::<Localization>();
+        app.init_resource::<UiWindows>();
+        app.init_resource::<VirtualListProviders>();
         
-        // Initialize the UI registry with built-in types
-        app.insert_resource(UiRegistry::new());
+        // Initialize the UI registry with built-in types, unless a plugin added before this one
+        // already created it to register extensions
+        if !app.world().contains_resource::<UiRegistry>() {
+            app.insert_resource(UiRegistry::new());
+        }
         
         // Add systems for asset loading and processing
         app.add_systems(
             Update,
             (
+                ui_asset_unload_system,
                 ui_asset_request_system,
                 ui_asset_loaded_system,
                 ui_asset_error_system,
-            ).chain(), // Run in order: request -> loaded -> error handling
+                ui_asset_reload_system,
+                update_dynamic_children_system,
+                apply_state_bindings_system,
+            ).chain(), // Run in order: unload -> request -> loaded -> error handling -> hot reload -> dynamic children -> state bindings
+        );
+        
+        // Closing a declared window unloads its UI; widgets spawned above inherit their window.
+        // Window icons are set once their image is loaded and winit has created the windows.
+        app.add_systems(
+            Update,
+            (
+                ui_window_closed_system.before(ui_asset_unload_system),
+                target_window_propagation_system.after(update_dynamic_children_system),
+                (window_icon_request_system, window_icon_system).chain(),
+            ),
+        );
+        
+        // Virtual lists fill their rows once their scroll view is measured, and before it scrolls
+        app.add_systems(
+            Update,
+            (
+                virtual_list_rows_system,
+                virtual_list_layout_system,
+            ).chain().after(scroll_view_measure_system).before(scroll_into_view_system),
+        );
+        
+        // Localized text goes over the key placeholders and state written above
+        app.add_systems(
+            Update,
+            (
+                string_table_request_system,
+                string_table_loaded_system,
+                apply_localized_text_system,
+                sync_font_locale_system,
+            ).chain().after(apply_state_bindings_system),
         );
         
         // Add debug systems
@@ -2,11 +2,41 @@ use bevy_ecs::prelude::Resource;
 use std::collections::HashMap;
 use thiserror::Error;
 use crate::widgets::blueprint::WidgetType;
+use crate::widgets::dropdown::{validate_dropdown, DROPDOWN_VALUE_EVENTS};
+use crate::widgets::numeric::{validate_numeric_widget, NUMERIC_VALUE_EVENTS};
+use crate::widgets::popup::{validate_popup, POPUP_VALUE_EVENTS};
+use crate::widgets::scroll_view::validate_scroll_view;
+use crate::widgets::tabs::{validate_tabs, TABS_VALUE_EVENTS};
+use crate::widgets::text_input::{validate_text_input, TEXT_INPUT_VALUE_EVENTS};
+use crate::widgets::toggle::TOGGLE_VALUE_EVENTS;
+use crate::widgets::virtual_list::validate_virtual_list;
 
 use super::definitions::ActionBinding;
+use super::widget_templates::{BuiltinTemplateExpander, WidgetTemplateDefinition};
+
+/// Event names that action bindings can react to
+pub const VALID_EVENTS: &[&str] = &[
+    "click", "hover", "focus", "blur", "change", "submit",
+    "key_press", "key_release", "mouse_enter", "mouse_leave",
+    "drag_start", "drag_end", "resize", "scroll", "dismiss", "close", "reorder"
+];
+
+/// Events a widget of `widget_type` fires with its new value as the `value` parameter, so
+/// its bindings for them can leave `value` out
+pub fn value_events(widget_type: &WidgetType) -> &'static [&'static str] {
+    match widget_type {
+        WidgetType::Checkbox { .. } | WidgetType::Toggle { .. } => TOGGLE_VALUE_EVENTS,
+        WidgetType::Slider { .. } | WidgetType::DragValue { .. } => NUMERIC_VALUE_EVENTS,
+        WidgetType::TextInput { .. } => TEXT_INPUT_VALUE_EVENTS,
+        WidgetType::Dropdown { .. } => DROPDOWN_VALUE_EVENTS,
+        WidgetType::Popup { .. } => POPUP_VALUE_EVENTS,
+        WidgetType::Tabs { .. } => TABS_VALUE_EVENTS,
+        _ => &[],
+    }
+}
 
 /// Registry resource for widget type validation and state management
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Default)]
 pub struct UiRegistry {
     /// Map of widget type names to their asset paths for validation
     pub widget_type_mappings: HashMap<String, WidgetTypeInfo>,
@@ -14,6 +44,10 @@ pub struct UiRegistry {
     pub registered_state_types: HashMap<String, StateTypeInfo>,
     /// Map of valid action names to their descriptions
     pub valid_actions: HashMap<String, ActionInfo>,
+    /// Widget templates declared in data, by name
+    pub templates: HashMap<String, WidgetTemplateDefinition>,
+    /// Expanders of the built-in widget types drawn as several primitives, by type name
+    pub builtin_templates: HashMap<String, BuiltinTemplateExpander>,
     /// Registry configuration
     pub config: UiRegistryConfig,
 }
@@ -72,17 +106,6 @@ pub struct UiRegistryConfig {
     pub max_nesting_depth: usize,
 }
 
-impl Default for UiRegistry {
-    fn default() -> Self {
-        Self {
-            widget_type_mappings: HashMap::new(),
-            registered_state_types: HashMap::new(),
-            valid_actions: HashMap::new(),
-            config: UiRegistryConfig::default(),
-        }
-    }
-}
-
 impl Default for UiRegistryConfig {
     fn default() -> Self {
         Self {
@@ -123,6 +146,23 @@ impl UiRegistry {
             can_have_children: true,
         });
 
+        self.register_widget_type("ScrollView", WidgetTypeInfo {
+            display_name: "ScrollView".to_string(),
+            asset_path: None,
+            required_properties: vec!["direction".to_string()],
+            optional_properties: vec!["axes".to_string(), "scrollbar_width".to_string(), "scrollbar_color".to_string()],
+            can_have_children: true,
+        });
+
+        self.register_widget_type("VirtualList", WidgetTypeInfo {
+            display_name: "VirtualList".to_string(),
+            asset_path: None,
+            required_properties: vec![],
+            optional_properties: ["items", "provider", "as", "row_height", "overscan", "scrollbar_width", "scrollbar_color"]
+                .iter().map(|property| property.to_string()).collect(),
+            can_have_children: true,
+        });
+
         self.register_widget_type("Button", WidgetTypeInfo {
             display_name: "Button".to_string(),
             asset_path: None,
@@ -131,6 +171,73 @@ impl UiRegistry {
             can_have_children: false,
         });
 
+        for name in ["Checkbox", "Toggle"] {
+            self.register_widget_type(name, WidgetTypeInfo {
+                display_name: name.to_string(),
+                asset_path: None,
+                required_properties: vec![],
+                optional_properties: vec!["checked".to_string(), "label".to_string(), "disabled".to_string()],
+                can_have_children: false,
+            });
+        }
+
+        self.register_widget_type("Slider", WidgetTypeInfo {
+            display_name: "Slider".to_string(),
+            asset_path: None,
+            required_properties: vec!["min".to_string(), "max".to_string()],
+            optional_properties: vec![
+                "value".to_string(), "step".to_string(), "logarithmic".to_string(),
+                "orientation".to_string(), "format".to_string(), "disabled".to_string(),
+            ],
+            can_have_children: false,
+        });
+
+        self.register_widget_type("DragValue", WidgetTypeInfo {
+            display_name: "DragValue".to_string(),
+            asset_path: None,
+            required_properties: vec!["min".to_string(), "max".to_string()],
+            optional_properties: vec![
+                "value".to_string(), "step".to_string(), "logarithmic".to_string(),
+                "format".to_string(), "disabled".to_string(),
+            ],
+            can_have_children: false,
+        });
+
+        self.register_widget_type("TextInput", WidgetTypeInfo {
+            display_name: "TextInput".to_string(),
+            asset_path: None,
+            required_properties: vec![],
+            optional_properties: vec![
+                "value".to_string(), "placeholder".to_string(), "max_length".to_string(),
+                "filter".to_string(), "password".to_string(), "disabled".to_string(),
+            ],
+            can_have_children: false,
+        });
+
+        self.register_widget_type("Dropdown", WidgetTypeInfo {
+            display_name: "Dropdown".to_string(),
+            asset_path: None,
+            required_properties: vec!["options".to_string()],
+            optional_properties: vec!["selected".to_string(), "placeholder".to_string(), "disabled".to_string()],
+            can_have_children: false,
+        });
+
+        self.register_widget_type("Popup", WidgetTypeInfo {
+            display_name: "Popup".to_string(),
+            asset_path: None,
+            required_properties: vec![],
+            optional_properties: vec!["anchor".to_string(), "placement".to_string(), "gap".to_string(), "open".to_string()],
+            can_have_children: true,
+        });
+
+        self.register_widget_type("Tabs", WidgetTypeInfo {
+            display_name: "Tabs".to_string(),
+            asset_path: None,
+            required_properties: vec!["tabs".to_string()],
+            optional_properties: vec!["active".to_string(), "closable".to_string(), "reorderable".to_string()],
+            can_have_children: true,
+        });
+
         self.register_widget_type("Text", WidgetTypeInfo {
             display_name: "Text".to_string(),
             asset_path: None,
@@ -147,6 +254,11 @@ impl UiRegistry {
             can_have_children: false,
         });
 
+        // Register the built-in widget types drawn as several primitives
+        for widget_type in ["Button", "Checkbox", "Toggle", "Slider", "DragValue", "TextInput", "Dropdown"] {
+            self.register_builtin_template(widget_type, crate::widgets::templates::expand_template_node);
+        }
+
         // Register built-in state types
         self.register_state_type("String", StateTypeInfo {
             display_name: "String".to_string(),
@@ -169,6 +281,20 @@ impl UiRegistry {
             default_value: Some(serde_json::Value::Number(serde_json::Number::from(0))),
         });
 
+        self.register_state_type("Number", StateTypeInfo {
+            display_name: "Number".to_string(),
+            type_id: "f64".to_string(),
+            valid_operations: vec!["set".to_string(), "get".to_string(), "increment".to_string(), "decrement".to_string()],
+            default_value: serde_json::Number::from_f64(0.0).map(serde_json::Value::Number),
+        });
+
+        self.register_state_type("List", StateTypeInfo {
+            display_name: "List".to_string(),
+            type_id: "Vec<Value>".to_string(),
+            valid_operations: vec!["set".to_string(), "get".to_string(), "push".to_string()],
+            default_value: Some(serde_json::Value::Array(vec![])),
+        });
+
         // Register built-in actions
         self.register_action("navigate_home", ActionInfo {
             display_name: "Navigate Home".to_string(),
@@ -239,6 +365,71 @@ impl UiRegistry {
             },
             requires_conditions: vec!["target_exists".to_string()],
         });
+
+        // State actions apply the operation of the same name to a `UiState` value
+        self.register_action("set_state", ActionInfo {
+            display_name: "Set State".to_string(),
+            description: "Set a state value".to_string(),
+            parameter_types: {
+                let mut params = HashMap::new();
+                params.insert("path".to_string(), "String".to_string());
+                params.insert("value".to_string(), "Any".to_string());
+                params
+            },
+            requires_conditions: vec!["state_exists".to_string()],
+        });
+
+        for (name, display_name, description) in [
+            ("toggle_state", "Toggle State", "Toggle a Boolean state value"),
+            ("increment_state", "Increment State", "Add one to an Integer or Number state value"),
+            ("decrement_state", "Decrement State", "Subtract one from an Integer or Number state value"),
+        ] {
+            self.register_action(name, ActionInfo {
+                display_name: display_name.to_string(),
+                description: description.to_string(),
+                parameter_types: {
+                    let mut params = HashMap::new();
+                    params.insert("path".to_string(), "String".to_string());
+                    params
+                },
+                requires_conditions: vec!["state_exists".to_string()],
+            });
+        }
+
+        self.register_action("append_state", ActionInfo {
+            display_name: "Append State".to_string(),
+            description: "Append text to a String state value".to_string(),
+            parameter_types: {
+                let mut params = HashMap::new();
+                params.insert("path".to_string(), "String".to_string());
+                params.insert("text".to_string(), "String".to_string());
+                params
+            },
+            requires_conditions: vec!["state_exists".to_string()],
+        });
+
+        self.register_action("push_state", ActionInfo {
+            display_name: "Push State".to_string(),
+            description: "Add an item to the end of a List state value".to_string(),
+            parameter_types: {
+                let mut params = HashMap::new();
+                params.insert("path".to_string(), "String".to_string());
+                params.insert("item".to_string(), "Any".to_string());
+                params
+            },
+            requires_conditions: vec!["state_exists".to_string()],
+        });
+
+        self.register_action("set_locale", ActionInfo {
+            display_name: "Set Locale".to_string(),
+            description: "Switch the locale used for localized text and font shaping".to_string(),
+            parameter_types: {
+                let mut params = HashMap::new();
+                params.insert("locale".to_string(), "String".to_string());
+                params
+            },
+            requires_conditions: vec![],
+        });
     }
 
     /// Register a new widget type
@@ -271,6 +462,15 @@ impl UiRegistry {
         self.valid_actions.contains_key(action)
     }
 
+    /// Check if action parameters can be declared with this type
+    pub fn is_parameter_type_known(&self, parameter_type: &str) -> bool {
+        matches!(
+            parameter_type.to_lowercase().as_str(),
+            "string" | "integer" | "int" | "i32" | "i64" | "float" | "f32" | "f64"
+                | "boolean" | "bool" | "array" | "table" | "object" | "any"
+        ) || self.is_state_type_registered(parameter_type)
+    }
+
     /// Get widget type information
     pub fn get_widget_type_info(&self, widget_type: &str) -> Option<&WidgetTypeInfo> {
         self.widget_type_mappings.get(widget_type)
@@ -291,10 +491,8 @@ impl UiRegistry {
         let type_name = self.extract_widget_type_name(widget_type);
         
         // Check if widget type is registered
-        if !self.is_widget_type_registered(&type_name) {
-            if self.config.strict_validation && !self.config.allow_custom_widgets {
-                return Err(UiRegistryError::UnknownWidgetType(type_name));
-            }
+        if !self.is_widget_type_registered(&type_name) && self.config.strict_validation && !self.config.allow_custom_widgets {
+            return Err(UiRegistryError::UnknownWidgetType(type_name));
         }
 
         // Get widget type info for validation
@@ -307,10 +505,20 @@ impl UiRegistry {
     }
 
     /// Extract the widget type name from a WidgetType enum
-    fn extract_widget_type_name(&self, widget_type: &WidgetType) -> String {
+    pub(crate) fn extract_widget_type_name(&self, widget_type: &WidgetType) -> String {
         match widget_type {
             WidgetType::Container { .. } => "Container".to_string(),
+            WidgetType::ScrollView { .. } => "ScrollView".to_string(),
+            WidgetType::VirtualList { .. } => "VirtualList".to_string(),
             WidgetType::Button { .. } => "Button".to_string(),
+            WidgetType::Checkbox { .. } => "Checkbox".to_string(),
+            WidgetType::Toggle { .. } => "Toggle".to_string(),
+            WidgetType::Slider { .. } => "Slider".to_string(),
+            WidgetType::DragValue { .. } => "DragValue".to_string(),
+            WidgetType::TextInput { .. } => "TextInput".to_string(),
+            WidgetType::Dropdown { .. } => "Dropdown".to_string(),
+            WidgetType::Popup { .. } => "Popup".to_string(),
+            WidgetType::Tabs { .. } => "Tabs".to_string(),
             WidgetType::Text { .. } => "Text".to_string(),
             WidgetType::Shape { .. } => "Shape".to_string(),
             WidgetType::Custom { component, .. } => component.clone(),
@@ -345,6 +553,78 @@ impl UiRegistry {
                 
                 // Note: Button templates handle actions via bindings, not direct action field
             },
+            WidgetType::Checkbox { label, .. } | WidgetType::Toggle { label, .. } => {
+                if label.as_ref().is_some_and(|label| label.is_empty()) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: self.extract_widget_type_name(widget_type),
+                        property: "label".to_string(),
+                        reason: "Label cannot be empty; leave it out for no label".to_string(),
+                    });
+                }
+            },
+            WidgetType::Slider { .. } | WidgetType::DragValue { .. } => {
+                if let Err(e) = validate_numeric_widget(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: self.extract_widget_type_name(widget_type),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::ScrollView { .. } => {
+                if let Err(e) = validate_scroll_view(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "ScrollView".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::VirtualList { .. } => {
+                if let Err(e) = validate_virtual_list(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "VirtualList".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::TextInput { .. } => {
+                if let Err(e) = validate_text_input(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "TextInput".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::Dropdown { .. } => {
+                if let Err(e) = validate_dropdown(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "Dropdown".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::Popup { .. } => {
+                if let Err(e) = validate_popup(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "Popup".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
+            WidgetType::Tabs { .. } => {
+                if let Err(e) = validate_tabs(widget_type) {
+                    return Err(UiRegistryError::InvalidPropertyValue {
+                        widget_type: "Tabs".to_string(),
+                        property: e.property().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            },
             WidgetType::Text { content, editable: _ } => {
                 // Text validation - content length check
                 if content.len() > 10000 {
@@ -358,27 +638,21 @@ impl UiRegistry {
                 // editable is always present as it's a boolean, so no validation needed
             },
             WidgetType::Shape { shape_type } => {
-                // Shape validation could include checking for valid shape types
-                match shape_type {
-                    crate::widgets::blueprint::ShapeType::Custom { vertices } => {
-                        if vertices.len() < 3 {
-                            return Err(UiRegistryError::InvalidPropertyValue {
-                                widget_type: "Shape".to_string(),
-                                property: "vertices".to_string(),
-                                reason: "Custom shape must have at least 3 vertices".to_string(),
-                            });
-                        }
-                    },
-                    _ => {}, // Built-in shapes are always valid
+                // Shape validation could include checking for valid shape types; built-in shapes
+                // are always valid
+                if let crate::widgets::blueprint::ShapeType::Custom { vertices } = shape_type {
+                    if vertices.len() < 3 {
+                        return Err(UiRegistryError::InvalidPropertyValue {
+                            widget_type: "Shape".to_string(),
+                            property: "vertices".to_string(),
+                            reason: "Custom shape must have at least 3 vertices".to_string(),
+                        });
+                    }
                 }
             },
             WidgetType::Custom { component, properties } => {
-                // Custom widget validation
-                if !self.config.allow_custom_widgets && self.config.strict_validation {
-                    return Err(UiRegistryError::UnknownWidgetType(component.clone()));
-                }
-                
-                // If the custom widget is registered, validate its properties
+                // Unregistered components were already rejected by the caller in strict mode,
+                // so validate the properties of registered ones
                 if let Some(info) = self.get_widget_type_info(component) {
                     for required_prop in &info.required_properties {
                         if !properties.contains_key(required_prop) {
@@ -424,11 +698,19 @@ impl UiRegistry {
 
     /// Validate action bindings against registered actions and state types
     pub fn validate_action_binding(&self, binding: &ActionBinding) -> Result<(), UiRegistryError> {
+        self.check_action_binding(binding, &[])
+    }
+
+    /// Validate an action binding of a widget of `widget_type`, letting bindings for the events
+    /// the widget fires with a value leave the `value` parameter to it
+    pub fn validate_widget_action_binding(&self, widget_type: &WidgetType, binding: &ActionBinding) -> Result<(), UiRegistryError> {
+        self.check_action_binding(binding, value_events(widget_type))
+    }
+
+    fn check_action_binding(&self, binding: &ActionBinding, value_events: &[&str]) -> Result<(), UiRegistryError> {
         // Validate the action exists
-        if !self.is_action_registered(&binding.action) {
-            if self.config.strict_validation && !self.config.allow_custom_actions {
-                return Err(UiRegistryError::UnknownAction(binding.action.clone()));
-            }
+        if !self.is_action_registered(&binding.action) && self.config.strict_validation && !self.config.allow_custom_actions {
+            return Err(UiRegistryError::UnknownAction(binding.action.clone()));
         }
 
         // Validate the event type
@@ -436,7 +718,7 @@ impl UiRegistry {
 
         // If the action is registered, validate its parameters
         if let Some(action_info) = self.get_action_info(&binding.action) {
-            self.validate_action_parameters(binding, action_info)?;
+            self.validate_action_parameters(binding, action_info, value_events)?;
         }
 
         Ok(())
@@ -444,15 +726,9 @@ impl UiRegistry {
 
     /// Validate event type for action bindings
     pub fn validate_event_type(&self, event: &str) -> Result<(), UiRegistryError> {
-        let valid_events = [
-            "click", "hover", "focus", "blur", "change", "submit",
-            "key_press", "key_release", "mouse_enter", "mouse_leave",
-            "drag_start", "drag_end", "resize", "scroll"
-        ];
-
-        if !valid_events.contains(&event) {
+        if !VALID_EVENTS.contains(&event) {
             return Err(UiRegistryError::ValidationError(
-                format!("Unknown event type: '{}'. Valid events are: {}", event, valid_events.join(", "))
+                format!("Unknown event type: '{}'. Valid events are: {}", event, VALID_EVENTS.join(", "))
             ));
         }
 
@@ -460,10 +736,13 @@ impl UiRegistry {
     }
 
     /// Validate action parameters against expected types
-    fn validate_action_parameters(&self, binding: &ActionBinding, action_info: &ActionInfo) -> Result<(), UiRegistryError> {
+    fn validate_action_parameters(&self, binding: &ActionBinding, action_info: &ActionInfo, value_events: &[&str]) -> Result<(), UiRegistryError> {
         if let Some(ref params) = binding.params {
             // Check that all required parameters are provided
             for (param_name, param_type) in &action_info.parameter_types {
+                if param_name == "value" && !params.contains_key(param_name) && value_events.contains(&binding.event.as_str()) {
+                    continue;
+                }
                 if !params.contains_key(param_name) {
                     return Err(UiRegistryError::MissingRequiredProperty {
                         widget_type: format!("Action '{}'", binding.action),
@@ -503,6 +782,7 @@ impl UiRegistry {
             "boolean" | "bool" => matches!(value, serde_json::Value::Bool(_)),
             "array" => matches!(value, serde_json::Value::Array(_)),
             "table" | "object" => matches!(value, serde_json::Value::Object(_)),
+            "any" => true,
             _ => {
                 // Check if it's a registered state type
                 if self.is_state_type_registered(expected_type) {
@@ -0,0 +1,798 @@
+use bevy_ecs::prelude::*;
+use bevy_asset::{AssetEvent, Assets};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt, Parent};
+use bevy_log::{error, info, warn};
+use bevy_math::Vec3;
+use bevy_transform::prelude::Transform;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use yrs::{Transact, Text as YrsTextTrait};
+use crate::assets::{UiDefinition, UiRegistry, UiState, LoadingUiAssets, definitions::WidgetNode};
+use crate::assets::dynamic::dynamic_slots;
+use crate::assets::windows::WidgetWindows;
+use crate::assets::systems::{passes_registry_validation, spawn_widget_from_node};
+use crate::gui_framework::components::{ShapeData, Visibility, Interaction, InteractionState, Text, EditableText};
+use crate::gui_framework::events::YrsTextChanged;
+use crate::gui_framework::systems::ResolvedStyle;
+use crate::layout::{PositionControl, Styleable, TaffyResource, TomlCoords, UiNode};
+use crate::widgets::blueprint::{FlexDirection, ShapeType, WidgetBlueprint, WidgetType};
+use crate::widgets::components::*;
+use crate::widgets::systems::{convert_layout_config_to_taffy_style, create_shape_vertices};
+use crate::widgets::dropdown::WidgetDropdown;
+use crate::widgets::numeric::WidgetNumeric;
+use crate::widgets::popup::{overlay_node, positions_itself, WidgetPopup};
+use crate::widgets::scroll_view::{reload_scroll_view, WidgetScrollView};
+use crate::widgets::tabs::WidgetTabs;
+use crate::widgets::text_input::WidgetTextInput;
+use crate::widgets::toggle::WidgetToggle;
+use crate::widgets::virtual_list::WidgetVirtualList;
+use crate::YrsDocResource;
+
+/// Id given to widgets without one; such widgets cannot be matched and are always respawned
+const UNNAMED_WIDGET_ID: &str = "unnamed";
+
+/// Widget components that are patched in place when a definition is reloaded
+type ReloadWidgetQuery = (
+    &'static mut Widget,
+    &'static WidgetHierarchy,
+    &'static mut WidgetStyle,
+    &'static mut WidgetBehavior,
+    &'static mut WidgetLayout,
+    &'static mut Transform,
+    &'static mut Visibility,
+    &'static mut Interaction,
+    Option<&'static mut PositionControl>,
+    Option<&'static mut Styleable>,
+    Option<&'static mut ShapeData>,
+    Option<&'static mut Text>,
+    Option<&'static mut WidgetText>,
+    Option<&'static mut InteractionState>,
+);
+
+/// Bookkeeping for patching one spawned tree
+struct ReloadState<'a> {
+    yrs_res: &'a YrsDocResource,
+    /// The reloaded definition, whose style classes the patched widgets use
+    ui_definition: &'a UiDefinition,
+    /// Registry holding the widget templates instances expand to
+    registry: &'a UiRegistry,
+    window_height: f32,
+    /// Widgets of the live tree that can still be matched, by id
+    existing: HashMap<String, Entity>,
+    /// Every widget of the live tree before the reload
+    previous: Vec<Entity>,
+    /// Live widgets that were matched and survive the reload
+    kept: HashSet<Entity>,
+    /// Text entities whose layout must be rebuilt
+    changed_text: Vec<Entity>,
+}
+
+/// System that patches spawned UI trees in place when their definition asset changes.
+///
+/// Nodes are matched by `id`. Matched entities keep their identity, so manually positioned
+/// (dragged) widgets, focus and yrs text content survive; unmatched nodes are spawned and
+/// widgets missing from the new definition are despawned along with their Taffy nodes.
+pub fn ui_asset_reload_system(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<UiDefinition>>,
+    mut loading_assets: ResMut<LoadingUiAssets>,
+    ui_assets: Res<Assets<UiDefinition>>,
+    yrs_res: Res<YrsDocResource>,
+    registry: Res<UiRegistry>,
+    mut ui_state: ResMut<UiState>,
+    taffy_resource: Res<TaffyResource>,
+    mut text_changed_writer: EventWriter<YrsTextChanged>,
+    mut widgets: Query<ReloadWidgetQuery>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+    widget_windows: WidgetWindows,
+) {
+    let modified: HashSet<_> = asset_events.read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+
+    // Forget trees whose root has been despawned in the meantime
+    loading_assets.spawned.retain(|spawned| widgets.contains(spawned.root));
+
+    for spawned in loading_assets.spawned.iter_mut() {
+        if !modified.contains(&spawned.handle.id()) {
+            continue;
+        }
+        let Some(ui_definition) = ui_assets.get(&spawned.handle) else {
+            continue;
+        };
+        if !passes_registry_validation(ui_definition, &registry, &spawned.request.asset_path) {
+            warn!("Keeping the previous version of {}", spawned.request.asset_path);
+            continue;
+        }
+        info!("Hot reloading UI asset: {}", spawned.request.asset_path);
+        ui_state.declare_definition(ui_definition);
+
+        let mut state = ReloadState {
+            yrs_res: &yrs_res,
+            ui_definition,
+            registry: &registry,
+            window_height: widget_windows.height_of(spawned.root),
+            existing: HashMap::new(),
+            previous: Vec::new(),
+            kept: HashSet::new(),
+            changed_text: Vec::new(),
+        };
+        collect_live_widgets(&widgets, spawned.root, &mut state);
+
+        let root = patch_node(
+            &mut commands,
+            &mut widgets,
+            &parents,
+            &mut state,
+            &ui_definition.root,
+            spawned.request.parent,
+            None,
+            spawned.request.position_override.is_some(),
+        );
+        if root != spawned.root {
+            if let Some(position) = spawned.request.position_override {
+                commands.entity(root).insert(Transform::from_translation(position));
+            }
+            spawned.root = root;
+        }
+
+        // Despawn widgets that are no longer part of the definition
+        let stale: HashSet<Entity> = state.previous.iter()
+            .copied()
+            .filter(|entity| !state.kept.contains(entity))
+            .collect();
+        despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+
+        #[cfg(feature = "debug_logging")]
+        bevy_log::debug!("🔄 Reloaded '{}': kept {} widgets, removed {}",
+            spawned.request.asset_path, state.kept.len(), stale.len());
+
+        for entity in state.changed_text {
+            text_changed_writer.send(YrsTextChanged { entity });
+        }
+    }
+}
+
+/// Despawn a set of widgets along with their Taffy nodes and yrs text.
+/// Widgets whose Bevy parent is also in the set go with their parent.
+pub(crate) fn despawn_widgets(
+    commands: &mut Commands,
+    widgets: &HashSet<Entity>,
+    parents: &Query<&Parent>,
+    ui_nodes: &Query<&UiNode>,
+    taffy_resource: &TaffyResource,
+    yrs_res: &YrsDocResource,
+) {
+    for &entity in widgets {
+        if let Some(taffy_node) = ui_nodes.get(entity).ok().and_then(|ui_node| ui_node.taffy_node) {
+            taffy_resource.with_tree(|tree| {
+                if let Err(e) = tree.remove(taffy_node) {
+                    warn!("Failed to remove Taffy node for {:?}: {:?}", entity, e);
+                }
+            });
+        }
+        release_widget_text(yrs_res, entity);
+
+        let parent_in_set = parents.get(entity)
+            .map(|parent| widgets.contains(&parent.get()))
+            .unwrap_or(false);
+        if !parent_in_set {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Add `entity` and every widget below it in the widget hierarchy, including manually positioned
+/// widgets that are not Bevy children
+pub(crate) fn collect_subtree(entity: Entity, hierarchies: &Query<&WidgetHierarchy>, widgets: &mut HashSet<Entity>) {
+    if !widgets.insert(entity) {
+        return;
+    }
+    if let Ok(hierarchy) = hierarchies.get(entity) {
+        for &child in &hierarchy.children {
+            collect_subtree(child, hierarchies, widgets);
+        }
+    }
+}
+
+/// Record every widget reachable from `root` through the widget hierarchy
+fn collect_live_widgets(widgets: &Query<ReloadWidgetQuery>, root: Entity, state: &mut ReloadState) {
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        let Ok((widget, hierarchy, ..)) = widgets.get(entity) else {
+            continue;
+        };
+        state.previous.push(entity);
+        if widget.id != UNNAMED_WIDGET_ID {
+            state.existing.entry(widget.id.clone()).or_insert(entity);
+        }
+        stack.extend(hierarchy.children.iter().copied());
+    }
+}
+
+/// Patch (or spawn) the widget for `node` and its children, returning its entity
+#[allow(clippy::too_many_arguments)]
+fn patch_node(
+    commands: &mut Commands,
+    widgets: &mut Query<ReloadWidgetQuery>,
+    parents: &Query<&Parent>,
+    state: &mut ReloadState,
+    node: &WidgetNode,
+    parent: Option<Entity>,
+    parent_position: Option<Vec3>,
+    keep_transform: bool,
+) -> Entity {
+    let instance = expand_instance(state, node);
+    let authored = node;
+    let node = instance.as_ref().unwrap_or(authored);
+    let source = match instance {
+        Some(_) => WidgetSourceNode::template_instance(authored),
+        None => WidgetSourceNode::new(authored),
+    };
+    let popup = overlay_node(node);
+    let node = popup.as_ref().unwrap_or(node);
+
+    let styled_node = state.ui_definition.styled_node(node);
+    if let Some(expanded_nodes) = state.registry.expand_builtin_template(&styled_node) {
+        // Template widget - the root and every part have to match
+        let claimed: Vec<Option<Entity>> = expanded_nodes.iter()
+            .map(|expanded| claim_widget(widgets, state, expanded))
+            .collect();
+
+        if let Some(entities) = claimed.iter().copied().collect::<Option<Vec<Entity>>>() {
+            for (index, (&entity, expanded)) in entities.iter().zip(&expanded_nodes).enumerate() {
+                update_widget(commands, widgets, state, entity, expanded, index == 0 && keep_transform);
+            }
+            let root_entity = entities[0];
+            commands.entity(root_entity).insert(WidgetHierarchy {
+                parent,
+                children: entities[1..].to_vec(),
+            });
+            update_action_bindings(commands, root_entity, node);
+            update_state_bindings(commands, root_entity, node);
+            update_toggle(commands, root_entity, node);
+            update_numeric(commands, root_entity, node);
+            update_text_input(commands, root_entity, node);
+            update_dropdown(commands, root_entity, node);
+            commands.entity(root_entity).insert(source);
+            return root_entity;
+        }
+
+        for entity in claimed.into_iter().flatten() {
+            state.kept.remove(&entity);
+            release_widget_text(state.yrs_res, entity);
+        }
+        return spawn_fresh(commands, state, authored, parent, parent_position);
+    }
+
+    let Some(entity) = claim_widget(widgets, state, &styled_node) else {
+        return spawn_fresh(commands, state, authored, parent, parent_position);
+    };
+    update_widget(commands, widgets, state, entity, &styled_node, keep_transform);
+
+    // Dynamic children are not patched: the fresh `WidgetDynamicChildren` respawns them
+    // and the previous ones are despawned as stale
+    let (child_entities, dynamic_children) = dynamic_slots(node, |child_node| {
+        let child_entity = patch_node(commands, widgets, parents, state, child_node, Some(entity), node.layout.position, false);
+
+        // Mirror the spawn rules: Manual positioned children and popups are not Bevy children
+        let desired_parent = (!positions_itself(child_node)).then_some(entity);
+        let current_parent = parents.get(child_entity).ok().map(|p| p.get());
+        if desired_parent != current_parent {
+            match desired_parent {
+                Some(parent_entity) => {
+                    commands.entity(parent_entity).add_child(child_entity);
+                }
+                None => {
+                    commands.entity(child_entity).remove_parent();
+                }
+            }
+        }
+        child_entity
+    });
+    match dynamic_children {
+        Some(dynamic_children) => {
+            commands.entity(entity).insert(dynamic_children);
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetDynamicChildren>();
+        }
+    }
+
+    commands.entity(entity).insert(WidgetHierarchy {
+        parent,
+        children: child_entities,
+    });
+    update_action_bindings(commands, entity, node);
+    update_state_bindings(commands, entity, node);
+    update_scroll_view(commands, entity, node);
+    update_virtual_list(commands, entity, node);
+    update_popup(commands, entity, node);
+    update_tabs(commands, entity, node);
+    commands.entity(entity).insert(source);
+
+    entity
+}
+
+/// Expand `node` if it instantiates a data-declared widget template
+fn expand_instance(state: &ReloadState, node: &WidgetNode) -> Option<WidgetNode> {
+    state.registry.expand_template_instance(node).unwrap_or_else(|e| {
+        error!("Failed to expand widget template for '{:?}': {}", node.id, e);
+        None
+    })
+}
+
+/// Spawn a node that has no live counterpart
+fn spawn_fresh(
+    commands: &mut Commands,
+    state: &mut ReloadState,
+    node: &WidgetNode,
+    parent: Option<Entity>,
+    parent_position: Option<Vec3>,
+) -> Entity {
+    // Live widgets sharing ids with the new subtree are replaced, so their yrs text
+    // has to be cleared before spawning inserts the new content
+    release_subtree_ids(state, node);
+    spawn_widget_from_node(commands, node, state.ui_definition, state.registry, state.yrs_res, parent, state.window_height, parent_position)
+}
+
+fn release_subtree_ids(state: &mut ReloadState, node: &WidgetNode) {
+    let instance = expand_instance(state, node);
+    let node = instance.as_ref().unwrap_or(node);
+    let expanded_nodes = state.registry.expand_builtin_template(node).unwrap_or_else(|| vec![node.clone()]);
+    for expanded in expanded_nodes {
+        if let Some(entity) = expanded.id.as_ref().and_then(|id| state.existing.remove(id)) {
+            release_widget_text(state.yrs_res, entity);
+        }
+    }
+    for child in &node.children {
+        release_subtree_ids(state, child);
+    }
+}
+
+/// Find the live widget matching `node`, if it can be patched in place
+fn claim_widget(widgets: &Query<ReloadWidgetQuery>, state: &mut ReloadState, node: &WidgetNode) -> Option<Entity> {
+    let entity = state.existing.remove(node.id.as_deref()?)?;
+    let (widget, ..) = widgets.get(entity).ok()?;
+
+    // A different widget type or layout participation needs a fresh entity
+    let old = &widget.blueprint;
+    let old_uses_layout = old.behavior.position_control.clone().unwrap_or_default().uses_layout();
+    let new_uses_layout = node.behavior.position_control.clone().unwrap_or_default().uses_layout();
+    if std::mem::discriminant(&old.widget_type) != std::mem::discriminant(&node.widget_type)
+        || old_uses_layout != new_uses_layout
+    {
+        release_widget_text(state.yrs_res, entity);
+        return None;
+    }
+
+    state.kept.insert(entity);
+    Some(entity)
+}
+
+/// Update a matched widget's components from its new node, keeping runtime state
+fn update_widget(
+    commands: &mut Commands,
+    widgets: &mut Query<ReloadWidgetQuery>,
+    state: &mut ReloadState,
+    entity: Entity,
+    node: &WidgetNode,
+    keep_transform: bool,
+) {
+    let Ok((
+        mut widget,
+        _,
+        mut style,
+        mut behavior,
+        mut layout,
+        mut transform,
+        mut visibility,
+        mut interaction,
+        position_control,
+        styleable,
+        shape_data,
+        text,
+        widget_text,
+        interaction_state,
+    )) = widgets.get_mut(entity) else {
+        return;
+    };
+
+    let id = widget.id.clone();
+    let old_blueprint = std::mem::replace(&mut widget.blueprint, WidgetBlueprint {
+        id,
+        widget_type: node.widget_type.clone(),
+        layout: node.layout.clone(),
+        style: node.style.clone(),
+        behavior: node.behavior.clone(),
+        children: vec![], // Not used in unified architecture
+    });
+
+    *style = WidgetStyle::from(&node.style);
+
+    let mut new_behavior = WidgetBehavior::from(&node.behavior);
+    new_behavior.is_focused = behavior.is_focused;
+    new_behavior.is_hovered = behavior.is_hovered;
+    *behavior = new_behavior;
+
+    let computed_position = layout.computed_position;
+    *layout = WidgetLayout::from(&node.layout);
+    layout.computed_position = computed_position;
+
+    // A LayoutThenManual widget that has been dragged stays under manual control
+    let new_position_control = node.behavior.position_control.clone().unwrap_or(PositionControl::Layout);
+    let position_control = match position_control {
+        Some(mut current) => {
+            let dragged = current.is_manual() && !new_position_control.is_manual();
+            if !dragged && *current != new_position_control {
+                *current = new_position_control;
+            }
+            (*current).clone()
+        }
+        None => new_position_control,
+    };
+
+    if let Some(mut styleable) = styleable {
+        let new_style = convert_layout_config_to_taffy_style(&node.layout, &position_control);
+        if styleable.0 != new_style {
+            styleable.0 = new_style;
+        }
+    }
+
+    // Layout owns x/y of layout positioned widgets; manual ones only move if they were never dragged
+    if !keep_transform {
+        let z = behavior.z_index as f32;
+        if position_control.is_manual() {
+            let old_position = old_blueprint.layout.position
+                .map(|pos| TomlCoords::from(pos).to_bevy(state.window_height).0);
+            let at_old_position = old_position
+                .map(|old| (transform.translation.x - old.x).abs() < 0.5 && (transform.translation.y - old.y).abs() < 0.5)
+                .unwrap_or(true);
+            if let (true, Some(position)) = (at_old_position, layout.position) {
+                let position = position.to_bevy(state.window_height).0;
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+            }
+        }
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+
+    if visibility.0 != behavior.visible {
+        visibility.0 = behavior.visible;
+    }
+    interaction.clickable = behavior.clickable;
+    interaction.draggable = behavior.draggable;
+
+    // Re-resolve the style against the new blueprint
+    let is_interactive = behavior.clickable || behavior.draggable;
+    match (is_interactive, interaction_state) {
+        (true, Some(mut interaction_state)) => interaction_state.set_changed(),
+        (true, None) => {
+            commands.entity(entity).insert(InteractionState::new());
+        }
+        (false, existing) => {
+            if existing.is_some() {
+                commands.entity(entity).remove::<InteractionState>();
+            }
+            commands.entity(entity).insert(ResolvedStyle::new(node.style.clone()));
+        }
+    }
+
+    match &node.widget_type {
+        WidgetType::Shape { shape_type } => {
+            let vertices = create_shape_vertices(shape_type, layout.computed_size);
+            match (shape_data, style.background_color) {
+                (Some(mut shape_data), background_color) => {
+                    shape_data.vertices = Arc::new(vertices.clone());
+                    shape_data.original_vertices = None;
+                    if let Some(color) = background_color {
+                        shape_data.color = color;
+                    }
+                }
+                (None, Some(color)) => {
+                    commands.entity(entity).insert(ShapeData::new(vertices.clone(), color));
+                }
+                (None, None) => {}
+            }
+            commands.entity(entity).insert(WidgetShape {
+                shape_type: shape_type.clone(),
+                vertices,
+            });
+        }
+        WidgetType::Text { content, editable } => {
+            let mut relayout = false;
+            if let Some(mut text) = text {
+                let size = style.text_size.unwrap_or(16.0);
+                let color = style.text_color.unwrap_or(bevy_color::Color::BLACK);
+                if text.size != size || text.color != color {
+                    text.size = size;
+                    text.color = color;
+                    relayout = true;
+                }
+            }
+
+            // Editable text belongs to the user (and yrs peers); static text follows the file
+            let old_content = match &old_blueprint.widget_type {
+                WidgetType::Text { content, .. } => Some(content),
+                _ => None,
+            };
+            if !editable && old_content != Some(content) {
+                replace_widget_text(state.yrs_res, entity, content);
+                relayout = true;
+            }
+
+            if let Some(mut widget_text) = widget_text {
+                if !editable {
+                    widget_text.content = content.clone();
+                }
+                widget_text.editable = *editable;
+            }
+            if *editable {
+                commands.entity(entity).insert(EditableText);
+            } else {
+                commands.entity(entity).remove::<EditableText>();
+            }
+
+            // Re-inserted so the string table text goes back over the key the file just wrote
+            match &node.localized {
+                Some(localized) => {
+                    commands.entity(entity).insert(WidgetLocalizedText { text: localized.clone() });
+                }
+                None => {
+                    commands.entity(entity).remove::<WidgetLocalizedText>();
+                }
+            }
+
+            if relayout {
+                state.changed_text.push(entity);
+            }
+        }
+        WidgetType::Container { direction } => {
+            commands.entity(entity).insert(WidgetContainer {
+                flex_direction: direction.clone(),
+                computed_content_size: layout.computed_size,
+            });
+        }
+        WidgetType::ScrollView { .. } | WidgetType::VirtualList { .. } | WidgetType::Popup { .. } | WidgetType::Tabs { .. } => {
+            // Virtual lists, popups and tabs lay their children out in a column
+            let direction = match &node.widget_type {
+                WidgetType::ScrollView { direction, .. } => direction.clone(),
+                _ => FlexDirection::Column,
+            };
+            commands.entity(entity).insert(WidgetContainer {
+                flex_direction: direction,
+                computed_content_size: layout.computed_size,
+            });
+            let vertices = create_shape_vertices(&ShapeType::Rectangle, layout.computed_size);
+            match (shape_data, style.background_color) {
+                (Some(mut shape_data), Some(color)) => {
+                    shape_data.vertices = Arc::new(vertices);
+                    shape_data.original_vertices = None;
+                    shape_data.color = color;
+                }
+                (Some(_), None) => {
+                    commands.entity(entity).remove::<ShapeData>();
+                }
+                (None, Some(color)) => {
+                    commands.entity(entity).insert(ShapeData::new(vertices, color));
+                }
+                (None, None) => {}
+            }
+        }
+        WidgetType::Button { .. } | WidgetType::Checkbox { .. } | WidgetType::Toggle { .. }
+        | WidgetType::Slider { .. } | WidgetType::DragValue { .. } | WidgetType::TextInput { .. }
+        | WidgetType::Dropdown { .. } | WidgetType::Custom { .. } => {}
+    }
+}
+
+/// Keep `WidgetActionBindings` in sync with the node's bindings
+fn update_action_bindings(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match &node.bindings {
+        Some(bindings) => {
+            commands.entity(entity).insert(WidgetActionBindings {
+                bindings: bindings.clone(),
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetActionBindings>();
+        }
+    }
+}
+
+/// Keep `WidgetToggle` in sync with a checkbox or toggle node. The value belongs to the user,
+/// like edited text, so only whether the widget is disabled follows the file.
+fn update_toggle(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetToggle::from_widget_type(&node.widget_type) {
+        Some(toggle) => {
+            commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+                let checked = entity.get::<WidgetToggle>().map_or(toggle.checked, |live| live.checked);
+                entity.insert(WidgetToggle { checked, ..toggle });
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetToggle>();
+        }
+    }
+}
+
+/// Keep `WidgetNumeric` in sync with a slider or drag value node. Like a toggle's value, the
+/// value belongs to the user and is kept, moved into the new range.
+fn update_numeric(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetNumeric::from_widget_type(&node.widget_type) {
+        Some(numeric) => {
+            commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+                let value = entity.get::<WidgetNumeric>().map_or(numeric.value, |live| numeric.range.snap(live.value));
+                entity.insert(WidgetNumeric {
+                    value,
+                    drag_fraction: numeric.range.fraction(value),
+                    ..numeric
+                });
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetNumeric>();
+        }
+    }
+}
+
+/// Keep `WidgetTextInput` in sync with a text input node. The typed text stays in the field, so
+/// the last committed value is kept too.
+fn update_text_input(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetTextInput::from_widget_type(&node.widget_type) {
+        Some(text_input) => {
+            commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+                let committed = entity.get::<WidgetTextInput>().map_or(text_input.committed.clone(), |live| live.committed.clone());
+                entity.insert(WidgetTextInput { committed, ..text_input });
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetTextInput>();
+        }
+    }
+}
+
+/// Keep `WidgetDropdown` in sync with a dropdown node. The selection belongs to the user and is
+/// kept while its option still exists. An open list belongs to the old tree and is despawned as
+/// stale, so the dropdown comes back closed.
+fn update_dropdown(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetDropdown::from_widget_type(&node.widget_type) {
+        Some(mut dropdown) => {
+            commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+                let live = entity.get::<WidgetDropdown>().and_then(|live| live.selected_value().map(str::to_string));
+                if let Some(index) = live.and_then(|value| dropdown.options.iter().position(|option| option.value() == value)) {
+                    dropdown.selected = Some(index);
+                }
+                entity.insert(dropdown);
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetDropdown>();
+        }
+    }
+}
+
+/// Keep `WidgetPopup` in sync with a popup node. Whether it is open follows its behavior, which
+/// the file and the state bindings set.
+fn update_popup(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetPopup::from_widget_type(&node.widget_type) {
+        Some(popup) => {
+            commands.entity(entity).insert(popup);
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetPopup>();
+        }
+    }
+}
+
+/// Keep `WidgetTabs` in sync with a tabs node. The active tab belongs to the user and is kept
+/// while the tab still exists. The header buttons belong to the old tree and are despawned as
+/// stale, so the fresh component spawns them anew.
+fn update_tabs(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetTabs::from_widget_type(&node.widget_type) {
+        Some(mut tabs) => {
+            commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+                let live = entity.get::<WidgetTabs>().and_then(|live| live.active_id().map(str::to_string));
+                if let Some(index) = live.and_then(|id| tabs.position(&id)) {
+                    tabs.active = Some(index);
+                }
+                entity.insert(tabs);
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetTabs>();
+        }
+    }
+}
+
+/// Keep `WidgetScrollView` in sync with a scroll view node. How far the view is scrolled
+/// belongs to the user, so it is kept and clamped to the new settings.
+fn update_scroll_view(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    let scroll_view = WidgetScrollView::from_widget_type(&node.widget_type);
+    commands.entity(entity).queue(move |mut entity: EntityWorldMut| {
+        reload_scroll_view(&mut entity, scroll_view);
+    });
+}
+
+/// Keep `WidgetVirtualList` in sync with a virtual list node. Its rows belong to the old tree
+/// and are despawned as stale, so the fresh list spawns its rows anew.
+fn update_virtual_list(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match WidgetVirtualList::from_node(node) {
+        Some(virtual_list) => {
+            commands.entity(entity).insert(virtual_list);
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetVirtualList>();
+        }
+    }
+}
+
+/// Keep `WidgetStateBindings` in sync with the node's state bindings.
+///
+/// The component is always re-inserted so the bound values are applied again over the
+/// placeholders the reloaded definition just wrote.
+fn update_state_bindings(commands: &mut Commands, entity: Entity, node: &WidgetNode) {
+    match &node.state_bindings {
+        Some(state_bindings) => {
+            commands.entity(entity).insert(WidgetStateBindings::from_table(state_bindings));
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetStateBindings>();
+        }
+    }
+}
+
+/// Replace the yrs text mapped to `entity` with `content`
+pub(crate) fn replace_widget_text(yrs_res: &YrsDocResource, entity: Entity, content: &str) {
+    let Ok(text_map) = yrs_res.text_map.lock() else {
+        return;
+    };
+    if let Some(text_ref) = text_map.get(&entity) {
+        let mut txn = yrs_res.doc.transact_mut();
+        let len = text_ref.len(&txn);
+        text_ref.remove_range(&mut txn, 0, len);
+        text_ref.insert(&mut txn, 0, content);
+    }
+}
+
+/// Show `content` in a static text widget, keeping its blueprint, yrs text and `WidgetText` in
+/// step. Returns false if the widget is not a text widget.
+pub(crate) fn set_static_text(
+    yrs_res: &YrsDocResource,
+    entity: Entity,
+    widget: &mut Widget,
+    widget_text: Option<&mut WidgetText>,
+    content: &str,
+) -> bool {
+    let WidgetType::Text { content: blueprint_content, .. } = &mut widget.blueprint.widget_type else {
+        return false;
+    };
+    if blueprint_content.as_str() != content {
+        *blueprint_content = content.to_string();
+    }
+    replace_widget_text(yrs_res, entity, content);
+    if let Some(widget_text) = widget_text {
+        widget_text.content = content.to_string();
+    }
+    true
+}
+
+/// Clear and unmap the yrs text of a widget that is going away
+fn release_widget_text(yrs_res: &YrsDocResource, entity: Entity) {
+    let Ok(mut text_map) = yrs_res.text_map.lock() else {
+        return;
+    };
+    if let Some(text_ref) = text_map.remove(&entity) {
+        let mut txn = yrs_res.doc.transact_mut();
+        let len = text_ref.len(&txn);
+        text_ref.remove_range(&mut txn, 0, len);
+    }
+}
\ No newline at end of file
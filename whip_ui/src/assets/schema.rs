@@ -0,0 +1,877 @@
+use serde_json::{json, Map, Value};
+use crate::widgets::color::CSS_NAMED_COLORS;
+use super::{UiRegistry, VALID_EVENTS, WIDGET_ID_PATTERN, WIDGET_ID_MAX_LENGTH, BoundProperty, BIND_KEY, LOCALIZED_KEY, ARGS_KEY, CURRENT_FORMAT_VERSION, includes::{CONTENT_KEY, INCLUDE_KEY}, widget_templates::BUILTIN_WIDGET_TYPES};
+
+/// JSON Schema dialect used for exported schemas; draft-07 is understood by both JSON editors
+/// and TOML language servers such as Taplo
+pub const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Generate a JSON Schema describing UI definition files.
+///
+/// The structural part mirrors `UiDefinition` and the blueprint types; widget types, actions
+/// (with their parameters) and event names come from `registry`, so the schema matches what
+/// the loader accepts for this application.
+pub fn ui_definition_schema(registry: &UiRegistry) -> Value {
+    let mut definitions = Map::new();
+    definitions.insert("WindowConfig".to_string(), window_config_schema());
+    definitions.insert("WindowDeclaration".to_string(), window_declaration_schema());
+    definitions.insert("WidgetNode".to_string(), widget_node_schema());
+    definitions.insert("Node".to_string(), node_schema(registry));
+    definitions.insert("IncludeNode".to_string(), include_node_schema());
+    definitions.insert("WidgetType".to_string(), widget_type_schema(registry));
+    definitions.insert("LayoutConfig".to_string(), layout_config_schema());
+    definitions.insert("StyleConfig".to_string(), style_config_schema());
+    definitions.insert("StateStyles".to_string(), state_styles_schema());
+    definitions.insert("StyleOverrides".to_string(), style_overrides_schema());
+    definitions.insert("StyleClass".to_string(), style_class_schema());
+    definitions.insert("BehaviorConfig".to_string(), behavior_config_schema());
+    definitions.insert("ActionBinding".to_string(), action_binding_schema(registry));
+    definitions.insert("ColorDef".to_string(), color_def_schema());
+    definitions.insert("Spacing".to_string(), spacing_schema());
+    definitions.insert("Vec2".to_string(), vec_schema(2));
+    definitions.insert("Vec3".to_string(), vec_schema(3));
+    definitions.insert("TokenRef".to_string(), token_ref_schema());
+    definitions.insert("StateDeclaration".to_string(), state_declaration_schema(registry));
+    definitions.insert("StateBinding".to_string(), state_binding_schema());
+    definitions.insert("LocalizedText".to_string(), localized_text_schema());
+
+    json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": "Whip UI definition",
+        "description": "A UI layout file loaded by whip_ui (TOML or JSON)",
+        "type": "object",
+        "properties": {
+            "format_version": {
+                "description": "Definition format version; older files are upgraded by `whip-ui-tool migrate`",
+                "type": "integer",
+                "minimum": 0,
+                "maximum": CURRENT_FORMAT_VERSION
+            },
+            "window": { "$ref": "#/definitions/WindowConfig" },
+            "windows": {
+                "description": "Additional windows to open, by id, each showing its own layout",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/WindowDeclaration" }
+            },
+            "root": { "$ref": "#/definitions/WidgetNode" },
+            "styles": {
+                "description": "Global style classes referenced by `classes`",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/StyleClass" }
+            },
+            "actions": {
+                "description": "Global actions that widgets can reference",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/ActionBinding" }
+            },
+            "tokens": tokens_schema(),
+            "state": {
+                "description": "Named state values that widget properties bind to; tables nest into dotted paths",
+                "type": "object",
+                "additionalProperties": {
+                    "anyOf": [
+                        { "$ref": "#/definitions/StateDeclaration" },
+                        { "type": ["string", "number", "boolean", "object"] }
+                    ]
+                }
+            }
+        },
+        "required": ["root"],
+        "additionalProperties": false,
+        "definitions": definitions
+    })
+}
+
+/// Generate the UI definition schema as pretty-printed JSON
+pub fn ui_definition_schema_string(registry: &UiRegistry) -> String {
+    serde_json::to_string_pretty(&ui_definition_schema(registry))
+        .expect("JSON Schema values always serialize")
+}
+
+fn window_config_schema() -> Value {
+    json!({
+        "description": "Window configuration",
+        "type": "object",
+        "properties": window_config_properties(),
+        "required": ["size"],
+        "additionalProperties": false
+    })
+}
+
+fn window_config_properties() -> Map<String, Value> {
+    let mut properties = Map::new();
+    properties.insert("title".to_string(), json!({ "description": "Window title", "type": "string" }));
+    properties.insert("size".to_string(), json!({
+        "description": "Window size [width, height]",
+        "type": "array",
+        "items": { "type": "number", "exclusiveMinimum": 0.0, "maximum": 10000.0 },
+        "minItems": 2,
+        "maxItems": 2
+    }));
+    properties.insert("background_color".to_string(), json!({ "$ref": "#/definitions/ColorDef" }));
+    properties.insert("icon".to_string(), json!({
+        "description": "Window icon PNG image, relative to the assets folder",
+        "type": "string",
+        "minLength": 1
+    }));
+    for (field, description) in [
+        ("min_size", "Smallest size [width, height] the window can be resized to"),
+        ("max_size", "Largest size [width, height] the window can be resized to"),
+    ] {
+        properties.insert(field.to_string(), json!({
+            "description": description,
+            "type": "array",
+            "items": { "type": "number", "exclusiveMinimum": 0.0 },
+            "minItems": 2,
+            "maxItems": 2
+        }));
+    }
+    properties.insert("resizable".to_string(), json!({ "description": "Whether the user can resize the window", "type": "boolean" }));
+    properties.insert("decorations".to_string(), json!({ "description": "Whether the window has a title bar and borders", "type": "boolean" }));
+    properties.insert("fullscreen".to_string(), json!({
+        "description": "Windowed, borderless fullscreen on the current monitor, or exclusive fullscreen",
+        "type": "string",
+        "enum": ["Windowed", "Borderless", "Exclusive"]
+    }));
+    properties.insert("present_mode".to_string(), json!({
+        "description": "How frames are presented; unsupported modes fall back to Fifo",
+        "type": "string",
+        "enum": ["AutoVsync", "AutoNoVsync", "Fifo", "FifoRelaxed", "Immediate", "Mailbox"]
+    }));
+    properties.insert("transparent".to_string(), json!({
+        "description": "Whether the window shows what is behind it where the background is translucent",
+        "type": "boolean"
+    }));
+    properties.insert("position".to_string(), json!({
+        "description": "Initial position [x, y] of the window's top-left corner, in physical pixels",
+        "type": "array",
+        "items": { "type": "integer" },
+        "minItems": 2,
+        "maxItems": 2
+    }));
+    properties
+}
+
+/// A window configuration plus the layout shown in the window
+fn window_declaration_schema() -> Value {
+    let mut properties = window_config_properties();
+    properties.insert("layout".to_string(), json!({
+        "description": "Path of the UI definition shown in the window, relative to the assets folder",
+        "type": "string",
+        "minLength": 1
+    }));
+    json!({
+        "description": "An additional window and the layout it shows",
+        "type": "object",
+        "properties": properties,
+        "required": ["layout", "size"],
+        "additionalProperties": false
+    })
+}
+
+/// A node is either a full widget or a reference to a blueprint file
+fn widget_node_schema() -> Value {
+    json!({
+        "if": { "type": "object", "required": [INCLUDE_KEY] },
+        "then": { "$ref": "#/definitions/IncludeNode" },
+        "else": { "$ref": "#/definitions/Node" }
+    })
+}
+
+fn node_schema(registry: &UiRegistry) -> Value {
+    let mut schema = json!({
+        "description": "Widget node in the UI hierarchy",
+        "type": "object",
+        "properties": {
+            "id": {
+                "description": "Unique identifier for this widget",
+                "type": "string",
+                "pattern": WIDGET_ID_PATTERN,
+                "maxLength": WIDGET_ID_MAX_LENGTH
+            },
+            "widget_type": { "$ref": "#/definitions/WidgetType" },
+            "layout": { "$ref": "#/definitions/LayoutConfig" },
+            "style": { "$ref": "#/definitions/StyleConfig" },
+            "behavior": { "$ref": "#/definitions/BehaviorConfig" },
+            "classes": {
+                "description": "Style class names to apply",
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "style_overrides": {
+                "description": "Style that takes precedence over the node's classes and style",
+                "$ref": "#/definitions/StyleOverrides"
+            },
+            "bindings": {
+                "description": "Interaction bindings, keyed by name",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/ActionBinding" }
+            },
+            "state_bindings": {
+                "description": "State values driving widget properties, keyed by property path",
+                "type": "object",
+                "propertyNames": { "enum": BoundProperty::ALL.map(BoundProperty::path) },
+                "additionalProperties": { "type": "string" }
+            },
+            "repeat": {
+                "description": "Spawn the children once per element of a List state value",
+                "type": "object",
+                "properties": {
+                    "each": { "description": "Path of the List state value", "type": "string" },
+                    "key": { "description": "Item field identifying each element", "type": "string" },
+                    "as": { "description": "Name of the item in {{placeholders}} (default \"item\")", "type": "string" }
+                },
+                "required": ["each"],
+                "additionalProperties": false
+            },
+            "when": {
+                "description": "Spawn the node only while the condition holds: \"path\", \"!path\", \"path == value\" or \"path != value\"",
+                "type": "string"
+            },
+            "localized": {
+                "description": "String table entry supplying the node's text",
+                "type": "object",
+                "properties": {
+                    "key": { "type": "string" },
+                    "args": { "type": "object" }
+                },
+                "required": ["key"],
+                "additionalProperties": false
+            },
+            "children": {
+                "type": "array",
+                "items": { "$ref": "#/definitions/WidgetNode" }
+            }
+        },
+        "required": ["widget_type"],
+        "additionalProperties": false
+    });
+
+    // Widget types registered as leaves may not declare children
+    let leaf_types: Vec<&str> = BUILTIN_WIDGET_TYPES.iter()
+        .copied()
+        .filter(|name| registry.get_widget_type_info(name).is_some_and(|info| !info.can_have_children))
+        .collect();
+    if !leaf_types.is_empty() {
+        schema["if"] = json!({
+            "properties": {
+                "widget_type": {
+                    "properties": { "type": { "enum": leaf_types } },
+                    "required": ["type"]
+                }
+            },
+            "required": ["widget_type"]
+        });
+        schema["then"] = json!({
+            "properties": { "children": { "maxItems": 0 } }
+        });
+    }
+
+    schema
+}
+
+fn include_node_schema() -> Value {
+    json!({
+        "description": "Reference to a widget blueprint file; other fields override the blueprint",
+        "type": "object",
+        "properties": {
+            INCLUDE_KEY: {
+                "description": "Blueprint path, relative to this file",
+                "type": "string"
+            },
+            CONTENT_KEY: {
+                "description": "Shorthand override for the blueprint's text content",
+                "type": "string"
+            },
+            "id": { "type": "string" },
+            "children": {
+                "type": "array",
+                "items": { "$ref": "#/definitions/WidgetNode" }
+            }
+        },
+        "required": [INCLUDE_KEY]
+    })
+}
+
+fn widget_type_schema(registry: &UiRegistry) -> Value {
+    let mut variants = Vec::new();
+
+    for &name in BUILTIN_WIDGET_TYPES {
+        let Some(info) = registry.get_widget_type_info(name) else {
+            continue;
+        };
+        let mut variant = builtin_widget_type_schema(name);
+        variant["description"] = Value::String(info.display_name.clone());
+        variants.push(variant);
+    }
+
+    // Registered non built-in types are spawned through `Custom` components
+    let mut custom_types: Vec<_> = registry.widget_type_mappings.iter()
+        .filter(|(name, _)| !BUILTIN_WIDGET_TYPES.contains(&name.as_str()))
+        .collect();
+    custom_types.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (name, info) in &custom_types {
+        let mut properties = Map::new();
+        for property in info.required_properties.iter().chain(&info.optional_properties) {
+            properties.insert(property.clone(), json!({}));
+        }
+        variants.push(json!({
+            "description": info.display_name,
+            "type": "object",
+            "properties": {
+                "type": { "const": "Custom" },
+                "component": { "const": name },
+                "properties": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": info.required_properties
+                }
+            },
+            "required": ["type", "component", "properties"],
+            "additionalProperties": false
+        }));
+    }
+
+    if registry.config.allow_custom_widgets || !registry.config.strict_validation {
+        variants.push(json!({
+            "description": "Custom component",
+            "type": "object",
+            "properties": {
+                "type": { "const": "Custom" },
+                "component": { "type": "string" },
+                "properties": { "type": "object" }
+            },
+            "required": ["type", "component", "properties"],
+            "additionalProperties": false
+        }));
+    }
+
+    json!({
+        "description": "Widget type and its configuration",
+        "oneOf": variants
+    })
+}
+
+/// Schema of a built-in `WidgetType` variant, tagged by `type`
+fn builtin_widget_type_schema(name: &str) -> Value {
+    let (properties, required) = match name {
+        "Container" => (json!({
+            "direction": {
+                "type": "string",
+                "enum": ["Row", "Column", "RowReverse", "ColumnReverse"]
+            }
+        }), json!(["direction"])),
+        "ScrollView" => (json!({
+            "direction": {
+                "type": "string",
+                "enum": ["Row", "Column", "RowReverse", "ColumnReverse"]
+            },
+            "axes": { "type": "string", "enum": ["Vertical", "Horizontal", "Both"] },
+            "scrollbar_width": { "type": "number", "minimum": 0 },
+            "scrollbar_color": { "$ref": "#/definitions/ColorDef" }
+        }), json!(["direction"])),
+        "VirtualList" => (json!({
+            "items": { "description": "Path of the List state value", "type": "string" },
+            "provider": { "description": "Name of a registered VirtualListProvider", "type": "string" },
+            "as": { "description": "Name of the item in {{placeholders}} (default \"item\")", "type": "string" },
+            "row_height": { "type": "number", "exclusiveMinimum": 0 },
+            "overscan": { "type": "integer", "minimum": 0 },
+            "scrollbar_width": { "type": "number", "minimum": 0 },
+            "scrollbar_color": { "$ref": "#/definitions/ColorDef" }
+        }), json!([])),
+        "Text" => (json!({
+            "content": or_localized(or_bind(json!({ "type": "string", "maxLength": 10000 }))),
+            "editable": { "type": "boolean" }
+        }), json!(["content", "editable"])),
+        "Shape" => (json!({
+            "shape_type": {
+                "oneOf": [
+                    { "type": "string", "enum": ["Rectangle", "Circle", "Triangle"] },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "Custom": {
+                                "type": "object",
+                                "properties": {
+                                    "vertices": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/definitions/Vec2" },
+                                        "minItems": 3
+                                    }
+                                },
+                                "required": ["vertices"]
+                            }
+                        },
+                        "required": ["Custom"],
+                        "additionalProperties": false
+                    }
+                ]
+            }
+        }), json!(["shape_type"])),
+        "Button" => (json!({
+            "text": or_localized(json!({ "type": "string", "minLength": 1 })),
+            "background_color": { "$ref": "#/definitions/ColorDef" },
+            "text_color": { "$ref": "#/definitions/ColorDef" },
+            "size": { "$ref": "#/definitions/Vec2" },
+            "text_size": or_token(json!({ "type": "number", "exclusiveMinimum": 0.0 })),
+            "border_width": or_token(json!({ "type": "number", "minimum": 0.0 })),
+            "border_color": { "$ref": "#/definitions/ColorDef" },
+            "border_radius": or_token(json!({ "type": "number", "minimum": 0.0 }))
+        }), json!([])),
+        "Checkbox" | "Toggle" => (json!({
+            "checked": { "type": "boolean" },
+            "label": or_localized(json!({ "type": "string", "minLength": 1 })),
+            "disabled": { "type": "boolean" }
+        }), json!([])),
+        "Slider" | "DragValue" => {
+            let mut properties = json!({
+                "value": or_token(json!({ "type": "number" })),
+                "min": or_token(json!({ "type": "number" })),
+                "max": or_token(json!({ "type": "number" })),
+                "step": or_token(json!({ "type": "number", "exclusiveMinimum": 0.0 })),
+                "logarithmic": { "type": "boolean" },
+                "format": {
+                    "description": "Text around a {} or {:.N} placeholder for the value",
+                    "type": "string",
+                    "pattern": "^[^{}]*\\{(:\\.[0-9]+)?\\}[^{}]*$"
+                },
+                "disabled": { "type": "boolean" }
+            });
+            if name == "Slider" {
+                properties["orientation"] = json!({ "type": "string", "enum": ["Horizontal", "Vertical"] });
+            }
+            (properties, json!(["min", "max"]))
+        }
+        "TextInput" => (json!({
+            "value": { "type": "string" },
+            "placeholder": or_localized(json!({ "type": "string" })),
+            "max_length": { "type": "integer", "minimum": 1 },
+            "filter": {
+                "description": "What the field accepts; a Regex has to match the whole text",
+                "anyOf": [
+                    { "type": "string", "enum": ["Integer", "Numeric"] },
+                    {
+                        "type": "object",
+                        "properties": { "Regex": { "type": "string" } },
+                        "required": ["Regex"],
+                        "additionalProperties": false
+                    }
+                ]
+            },
+            "password": { "type": "boolean" },
+            "disabled": { "type": "boolean" }
+        }), json!([])),
+        "Dropdown" => (json!({
+            "options": {
+                "type": "array",
+                "items": {
+                    "anyOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "value": { "type": "string" },
+                                "label": { "type": "string" }
+                            },
+                            "required": ["value", "label"],
+                            "additionalProperties": false
+                        }
+                    ]
+                },
+                "minItems": 1
+            },
+            "selected": { "description": "Value of the option selected at first", "type": "string" },
+            "placeholder": { "type": "string" },
+            "disabled": { "type": "boolean" }
+        }), json!(["options"])),
+        "Popup" => (json!({
+            "anchor": { "description": "Id of the widget to open beside; the parent by default", "type": "string", "minLength": 1 },
+            "placement": { "type": "string", "enum": ["Below", "Above", "Right", "Left"] },
+            "gap": { "type": "number", "minimum": 0 },
+            "open": { "type": "boolean" }
+        }), json!([])),
+        "Tabs" => (json!({
+            "tabs": {
+                "description": "One tab per child page, in order",
+                "type": "array",
+                "items": {
+                    "anyOf": [
+                        { "type": "string", "minLength": 1 },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string", "minLength": 1 },
+                                "title": { "type": "string" }
+                            },
+                            "required": ["id", "title"],
+                            "additionalProperties": false
+                        }
+                    ]
+                },
+                "minItems": 1
+            },
+            "active": or_bind(json!({ "description": "Id of the tab shown at first; the first tab by default", "type": "string" })),
+            "closable": { "type": "boolean" },
+            "reorderable": { "type": "boolean" }
+        }), json!(["tabs"])),
+        _ => (json!({}), json!([])),
+    };
+
+    let mut properties = properties;
+    properties["type"] = json!({ "const": name });
+    let mut required = required;
+    if let Some(required) = required.as_array_mut() {
+        required.insert(0, json!("type"));
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false
+    })
+}
+
+fn layout_config_schema() -> Value {
+    json!({
+        "description": "Layout configuration",
+        "type": "object",
+        "properties": {
+            "position": {
+                "description": "Absolute position [x, y, z], y pointing down from the top of the window",
+                "$ref": "#/definitions/Vec3"
+            },
+            "size": or_token(json!({
+                "description": "Size [width, height]",
+                "type": "array",
+                "items": or_bind(or_token(json!({ "type": "number" }))),
+                "minItems": 2,
+                "maxItems": 2
+            })),
+            "margin": { "$ref": "#/definitions/Spacing" },
+            "padding": { "$ref": "#/definitions/Spacing" },
+            "flex_grow": or_token(json!({ "type": "number", "minimum": 0.0 })),
+            "flex_shrink": or_token(json!({ "type": "number", "minimum": 0.0 })),
+            "align_self": {
+                "type": "string",
+                "enum": ["Auto", "FlexStart", "FlexEnd", "Center", "Baseline", "Stretch"]
+            },
+            "grid_row": or_token(json!({
+                "description": "Grid row placement (1-based)",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 65535
+            })),
+            "grid_column": or_token(json!({
+                "description": "Grid column placement (1-based)",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 65535
+            }))
+        },
+        "additionalProperties": false
+    })
+}
+
+/// Style fields shared by `StyleConfig` and `StyleOverrides`
+fn style_properties() -> Value {
+    json!({
+        "background_color": { "$ref": "#/definitions/ColorDef" },
+        "border_color": { "$ref": "#/definitions/ColorDef" },
+        "border_width": or_token(json!({ "type": "number", "minimum": 0.0 })),
+        "border_radius": or_token(json!({ "type": "number", "minimum": 0.0 })),
+        "text_color": { "$ref": "#/definitions/ColorDef" },
+        "text_size": or_token(json!({ "type": "number", "exclusiveMinimum": 0.0 })),
+        "opacity": or_token(json!({ "type": "number", "minimum": 0.0, "maximum": 1.0 }))
+    })
+}
+
+fn style_config_schema() -> Value {
+    let mut properties = style_properties();
+    // A widget's own style can follow state; classes and overrides stay static
+    for field in ["background_color", "border_color", "text_color", "opacity"] {
+        properties[field] = or_bind(properties[field].take());
+    }
+    properties["states"] = json!({ "$ref": "#/definitions/StateStyles" });
+    json!({
+        "description": "Style configuration",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false
+    })
+}
+
+fn state_styles_schema() -> Value {
+    json!({
+        "description": "State-specific style overrides",
+        "type": "object",
+        "properties": {
+            "hover": { "$ref": "#/definitions/StyleOverrides" },
+            "pressed": { "$ref": "#/definitions/StyleOverrides" },
+            "focused": { "$ref": "#/definitions/StyleOverrides" },
+            "disabled": { "$ref": "#/definitions/StyleOverrides" },
+            "checked": { "$ref": "#/definitions/StyleOverrides" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn style_overrides_schema() -> Value {
+    json!({
+        "description": "Style overrides",
+        "type": "object",
+        "properties": style_properties(),
+        "additionalProperties": false
+    })
+}
+
+fn style_class_schema() -> Value {
+    let mut properties = style_properties();
+    properties["extends"] = json!({
+        "description": "Style classes this class builds on, applied in order before its own values",
+        "type": "array",
+        "items": { "type": "string" }
+    });
+    properties["states"] = json!({ "$ref": "#/definitions/StateStyles" });
+    json!({
+        "description": "Global style class",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false
+    })
+}
+
+fn behavior_config_schema() -> Value {
+    json!({
+        "description": "Behavior configuration",
+        "type": "object",
+        "properties": {
+            "visible": or_bind(json!({ "type": "boolean" })),
+            "interactive": { "type": "boolean" },
+            "draggable": { "type": "boolean" },
+            "clickable": { "type": "boolean" },
+            "focusable": { "type": "boolean" },
+            "z_index": { "type": "integer" },
+            "position_control": {
+                "type": "string",
+                "enum": ["Layout", "Manual", "LayoutThenManual"]
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn action_binding_schema(registry: &UiRegistry) -> Value {
+    let mut actions: Vec<_> = registry.valid_actions.iter().collect();
+    actions.sort_by(|a, b| a.0.cmp(b.0));
+
+    let names: Vec<&String> = actions.iter().map(|(name, _)| *name).collect();
+    let descriptions: Vec<&String> = actions.iter().map(|(_, info)| &info.description).collect();
+    let action = if registry.config.strict_validation && !registry.config.allow_custom_actions {
+        json!({ "type": "string", "enum": names, "enumDescriptions": descriptions })
+    } else {
+        json!({ "type": "string", "examples": names })
+    };
+
+    // Registered actions constrain their parameters
+    let parameter_rules: Vec<Value> = actions.iter()
+        .map(|(name, info)| {
+            let mut parameters: Vec<_> = info.parameter_types.iter().collect();
+            parameters.sort_by(|a, b| a.0.cmp(b.0));
+            let mut properties = Map::new();
+            for (parameter, parameter_type) in &parameters {
+                properties.insert((*parameter).clone(), parameter_type_schema(registry, parameter_type));
+            }
+            let required: Vec<&String> = parameters.iter().map(|(parameter, _)| *parameter).collect();
+
+            let params = json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false
+            });
+            let then = if required.is_empty() {
+                json!({ "properties": { "params": params } })
+            } else {
+                json!({ "properties": { "params": params }, "required": ["params"] })
+            };
+            json!({
+                "if": { "properties": { "action": { "const": name } }, "required": ["action"] },
+                "then": then
+            })
+        })
+        .collect();
+
+    let mut schema = json!({
+        "description": "Connects a UI event to an action",
+        "type": "object",
+        "properties": {
+            "event": { "type": "string", "enum": VALID_EVENTS },
+            "action": action,
+            "params": { "type": "object" }
+        },
+        "required": ["event", "action"],
+        "additionalProperties": false
+    });
+    if !parameter_rules.is_empty() {
+        schema["allOf"] = Value::Array(parameter_rules);
+    }
+    schema
+}
+
+/// Map an action parameter type name to a schema, the way `UiRegistry` validates parameters
+fn parameter_type_schema(registry: &UiRegistry, parameter_type: &str) -> Value {
+    match parameter_type.to_lowercase().as_str() {
+        "string" => json!({ "type": "string" }),
+        "integer" | "int" | "i32" | "i64" => json!({ "type": "integer" }),
+        "float" | "f32" | "f64" => json!({ "type": "number" }),
+        "boolean" | "bool" => json!({ "type": "boolean" }),
+        "array" => json!({ "type": "array" }),
+        "table" | "object" => json!({ "type": "object" }),
+        "any" => json!({}),
+        _ => match registry.get_state_type_info(parameter_type) {
+            Some(info) => json!({ "description": info.display_name }),
+            None => json!({}),
+        },
+    }
+}
+
+fn color_def_schema() -> Value {
+    let channel = json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+    let alpha = json!({ "type": "number", "minimum": 0.0, "maximum": 1.0 });
+    let channels = json!({
+        "type": "object",
+        "properties": { "r": channel, "g": channel, "b": channel, "a": alpha },
+        "required": ["r", "g", "b"],
+        "additionalProperties": false
+    });
+    let hex = json!({ "type": "string", "pattern": "^#([0-9A-Fa-f]{3,4}|[0-9A-Fa-f]{6}|[0-9A-Fa-f]{8})$" });
+    let names: Vec<&str> = CSS_NAMED_COLORS.iter().map(|(name, _)| *name).chain(["transparent"]).collect();
+    let named = json!({ "type": "string", "enum": names });
+    let function = json!({ "type": "string", "pattern": "^\\s*(rgba?|hsla?|oklch)\\(.*\\)\\s*$" });
+
+    json!({
+        "description": "Color as \"#RRGGBB\" (or #RGB, #RGBA, #RRGGBBAA), a CSS color name, rgb(), hsl() or oklch(), { r, g, b, a }, a tagged form like { Hex = \"#RRGGBB\" } or a token",
+        "anyOf": [
+            { "$ref": "#/definitions/TokenRef" },
+            hex,
+            named,
+            function,
+            channels,
+            { "type": "object", "properties": { "Hex": hex }, "required": ["Hex"], "additionalProperties": false },
+            { "type": "object", "properties": { "Named": named }, "required": ["Named"], "additionalProperties": false },
+            { "type": "object", "properties": { "Function": function }, "required": ["Function"], "additionalProperties": false },
+            { "type": "object", "properties": { "Rgb": channels }, "required": ["Rgb"], "additionalProperties": false },
+            { "type": "object", "properties": { "Rgba": channels }, "required": ["Rgba"], "additionalProperties": false }
+        ]
+    })
+}
+
+fn spacing_schema() -> Value {
+    let side = or_token(json!({ "type": "number" }));
+    json!({
+        "description": "Margin or padding; a single number or token applies to every side",
+        "anyOf": [
+            { "$ref": "#/definitions/TokenRef" },
+            { "type": "number" },
+            {
+                "type": "object",
+                "properties": { "top": side, "right": side, "bottom": side, "left": side },
+                "required": ["top", "right", "bottom", "left"],
+                "additionalProperties": false
+            }
+        ]
+    })
+}
+
+fn vec_schema(len: usize) -> Value {
+    or_token(json!({
+        "type": "array",
+        "items": or_token(json!({ "type": "number" })),
+        "minItems": len,
+        "maxItems": len
+    }))
+}
+
+/// The `[tokens]` table: token values plus an optional `include` of shared token files
+fn tokens_schema() -> Value {
+    let path_list = json!({
+        "anyOf": [
+            { "type": "string" },
+            { "type": "array", "items": { "type": "string" } }
+        ]
+    });
+    json!({
+        "description": "Design tokens referenced as \"$name\" from styles and layouts",
+        "type": "object",
+        "properties": { INCLUDE_KEY: path_list },
+        "propertyNames": { "pattern": "^[A-Za-z_][A-Za-z0-9_-]*$" },
+        "additionalProperties": {
+            "description": "A color, a number, a list of numbers, another token or a color/spacing table",
+            "type": ["string", "number", "array", "object"]
+        }
+    })
+}
+
+fn token_ref_schema() -> Value {
+    json!({
+        "description": "Reference to a design token declared in `[tokens]`",
+        "type": "string",
+        "pattern": "^\\$[A-Za-z_][A-Za-z0-9_-]*$"
+    })
+}
+
+fn state_declaration_schema(registry: &UiRegistry) -> Value {
+    let mut types: Vec<&String> = registry.registered_state_types.keys().collect();
+    types.sort();
+    json!({
+        "description": "A typed state value",
+        "type": "object",
+        "properties": {
+            "type": { "type": "string", "enum": types },
+            "default": {},
+            "description": { "type": "string" }
+        },
+        "required": ["type"],
+        "additionalProperties": false
+    })
+}
+
+fn state_binding_schema() -> Value {
+    json!({
+        "description": "Binds the property to a value declared in `[state]`",
+        "type": "object",
+        "properties": { BIND_KEY: { "type": "string" } },
+        "required": [BIND_KEY],
+        "additionalProperties": false
+    })
+}
+
+fn localized_text_schema() -> Value {
+    json!({
+        "description": "Looks the text up in the current locale's string table; `{name}` placeholders are filled from `args`",
+        "type": "object",
+        "properties": {
+            LOCALIZED_KEY: { "type": "string", "minLength": 1 },
+            ARGS_KEY: { "type": "object" }
+        },
+        "required": [LOCALIZED_KEY],
+        "additionalProperties": false
+    })
+}
+
+/// Allow a string table reference wherever `schema` is accepted
+fn or_localized(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "$ref": "#/definitions/LocalizedText" }] })
+}
+
+/// Allow a state binding wherever `schema` is accepted
+fn or_bind(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "$ref": "#/definitions/StateBinding" }] })
+}
+
+/// Allow a design token wherever `schema` is accepted
+fn or_token(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "$ref": "#/definitions/TokenRef" }] })
+}
\ No newline at end of file
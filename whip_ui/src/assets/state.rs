@@ -0,0 +1,666 @@
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_math::Vec2;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use crate::gui_framework::components::{ShapeData, Text, Visibility};
+use crate::gui_framework::events::YrsTextChanged;
+use crate::layout::{PositionControl, Styleable};
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+use crate::widgets::components::{Widget, WidgetBehavior, WidgetLayout, WidgetStateBindings, WidgetStyle, WidgetText};
+use crate::widgets::systems::convert_layout_config_to_taffy_style;
+use crate::widgets::tabs::WidgetTabs;
+use crate::YrsDocResource;
+use super::{UiDefinition, UiDefinitionError, UiRegistry, ValidationReport, definitions::{sorted_entries, WidgetNode}};
+use super::reload::set_static_text;
+
+/// Top-level table declaring state values, e.g. `[state] "project.title" = { type = "String" }`
+pub const STATE_KEY: &str = "state";
+
+/// Key of an inline state binding: `content = { bind = "project.title" }`
+pub const BIND_KEY: &str = "bind";
+
+/// A named, typed state value declared in a UI definition.
+///
+/// Values are declared as `{ type = "Boolean", default = true }`, or as a plain literal whose
+/// type is inferred. Dotted TOML keys nest, so `project.title = "Untitled"` under `[state]`
+/// declares `project.title`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateDeclaration {
+    /// Registered state type: "String", "Boolean", "Integer", "Number", "List" or a custom type
+    #[serde(rename = "type")]
+    pub state_type: String,
+    /// Initial value; the state type's default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Human-readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl StateDeclaration {
+    /// Declaration for a literal written without a type
+    fn inferred(value: &Value) -> Option<Self> {
+        let state_type = match value {
+            Value::String(_) => "String",
+            Value::Bool(_) => "Boolean",
+            Value::Number(number) if number.is_i64() => "Integer",
+            Value::Number(_) => "Number",
+            Value::Array(_) => "List",
+            _ => return None,
+        };
+        Some(Self { state_type: state_type.to_string(), default: Some(value.clone()), description: None })
+    }
+
+    /// The value the state starts out with
+    pub fn initial_value(&self) -> Value {
+        self.default.clone()
+            .or_else(|| builtin_default(&self.state_type))
+            .unwrap_or(Value::Null)
+    }
+}
+
+/// Widget properties that can be bound to state values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BoundProperty {
+    /// Text content of a `Text` widget
+    Content,
+    /// `behavior.visible`
+    Visible,
+    /// `style.background_color`
+    BackgroundColor,
+    /// `style.border_color`
+    BorderColor,
+    /// `style.text_color`
+    TextColor,
+    /// `style.opacity`
+    Opacity,
+    /// Width in `layout.size`
+    Width,
+    /// Height in `layout.size`
+    Height,
+    /// Id of the active tab of a `Tabs` widget
+    ActiveTab,
+}
+
+impl BoundProperty {
+    pub const ALL: [BoundProperty; 9] = [
+        BoundProperty::Content,
+        BoundProperty::Visible,
+        BoundProperty::BackgroundColor,
+        BoundProperty::BorderColor,
+        BoundProperty::TextColor,
+        BoundProperty::Opacity,
+        BoundProperty::Width,
+        BoundProperty::Height,
+        BoundProperty::ActiveTab,
+    ];
+
+    /// Property path used as the key of a node's `state_bindings`
+    pub fn path(self) -> &'static str {
+        match self {
+            BoundProperty::Content => "widget_type.content",
+            BoundProperty::Visible => "behavior.visible",
+            BoundProperty::BackgroundColor => "style.background_color",
+            BoundProperty::BorderColor => "style.border_color",
+            BoundProperty::TextColor => "style.text_color",
+            BoundProperty::Opacity => "style.opacity",
+            BoundProperty::Width => "layout.size[0]",
+            BoundProperty::Height => "layout.size[1]",
+            BoundProperty::ActiveTab => "widget_type.active",
+        }
+    }
+
+    /// Look up a property by its path
+    pub fn from_path(path: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|property| property.path() == path)
+    }
+
+    /// Location of the property in a widget node document
+    fn pointer(self) -> &'static str {
+        match self {
+            BoundProperty::Content => "/widget_type/content",
+            BoundProperty::Visible => "/behavior/visible",
+            BoundProperty::BackgroundColor => "/style/background_color",
+            BoundProperty::BorderColor => "/style/border_color",
+            BoundProperty::TextColor => "/style/text_color",
+            BoundProperty::Opacity => "/style/opacity",
+            BoundProperty::Width => "/layout/size/0",
+            BoundProperty::Height => "/layout/size/1",
+            BoundProperty::ActiveTab => "/widget_type/active",
+        }
+    }
+
+    /// Whether values of `state_type` can drive this property
+    pub fn accepts(self, state_type: &str) -> bool {
+        match self {
+            BoundProperty::Content => true,
+            BoundProperty::Visible => state_type == "Boolean",
+            BoundProperty::BackgroundColor | BoundProperty::BorderColor | BoundProperty::TextColor
+            | BoundProperty::ActiveTab => state_type == "String",
+            BoundProperty::Opacity | BoundProperty::Width | BoundProperty::Height => matches!(state_type, "Integer" | "Number"),
+        }
+    }
+
+    /// Value written in place of an inline binding so the node deserializes
+    fn placeholder(self, value: &Value) -> Value {
+        match self {
+            BoundProperty::Content => Value::String(display_value(value)),
+            BoundProperty::Visible => Value::Bool(value.as_bool().unwrap_or(true)),
+            BoundProperty::BackgroundColor | BoundProperty::BorderColor | BoundProperty::TextColor => {
+                value.as_str().map(|color| Value::String(color.to_string())).unwrap_or_else(|| Value::from("transparent"))
+            }
+            BoundProperty::Opacity => Value::from(value.as_f64().unwrap_or(1.0)),
+            BoundProperty::Width | BoundProperty::Height => Value::from(value.as_f64().unwrap_or(0.0)),
+            // Without a tab id to start on, the first tab is shown
+            BoundProperty::ActiveTab => value.as_str().map(Value::from).unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Operations that actions apply to state values, named as in `StateTypeInfo::valid_operations`
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateOperation {
+    Set(Value),
+    Toggle,
+    Increment,
+    Decrement,
+    Append(String),
+    Push(Value),
+}
+
+impl StateOperation {
+    /// Name of the operation in the registry
+    pub fn name(&self) -> &'static str {
+        match self {
+            StateOperation::Set(_) => "set",
+            StateOperation::Toggle => "toggle",
+            StateOperation::Increment => "increment",
+            StateOperation::Decrement => "decrement",
+            StateOperation::Append(_) => "append",
+            StateOperation::Push(_) => "push",
+        }
+    }
+}
+
+/// Errors that can occur when changing state values
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum StateError {
+    #[error("Unknown state value '{0}'")]
+    UnknownPath(String),
+    #[error("State value '{path}' is of type {state_type} and cannot hold {value}")]
+    TypeMismatch { path: String, state_type: String, value: String },
+    #[error("Operation '{operation}' is not valid for state value '{path}' of type {state_type}")]
+    InvalidOperation { path: String, operation: String, state_type: String },
+}
+
+#[derive(Debug, Clone)]
+struct StateEntry {
+    state_type: String,
+    value: Value,
+}
+
+/// Resource holding the state values declared by loaded UI definitions.
+///
+/// Widgets display values through their `WidgetStateBindings`; every change is recorded so
+/// [`apply_state_bindings_system`] only touches the widgets bound to changed values.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct UiState {
+    entries: HashMap<String, StateEntry>,
+    changed: HashSet<String>,
+}
+
+impl UiState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a state value. Re-declaring a value with the same type keeps its current value,
+    /// so reloading a layout does not reset it.
+    pub fn declare(&mut self, path: impl Into<String>, state_type: impl Into<String>, initial: Value) {
+        let path = path.into();
+        let state_type = state_type.into();
+        if self.entries.get(&path).is_some_and(|entry| entry.state_type == state_type) {
+            return;
+        }
+        self.entries.insert(path.clone(), StateEntry { state_type, value: initial });
+        self.changed.insert(path);
+    }
+
+    /// Declare every state value of a UI definition
+    pub fn declare_definition(&mut self, definition: &UiDefinition) {
+        for (path, declaration) in definition.state.iter().flatten() {
+            self.declare(path.clone(), declaration.state_type.clone(), declaration.initial_value());
+        }
+    }
+
+    /// Current value at `path`
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        self.entries.get(path).map(|entry| &entry.value)
+    }
+
+    /// Declared type of the value at `path`
+    pub fn state_type(&self, path: &str) -> Option<&str> {
+        self.entries.get(path).map(|entry| entry.state_type.as_str())
+    }
+
+    /// Replace the value at `path`
+    pub fn set(&mut self, path: &str, value: Value) -> Result<(), StateError> {
+        let entry = self.entries.get_mut(path)
+            .ok_or_else(|| StateError::UnknownPath(path.to_string()))?;
+        if !value_fits_state_type(&entry.state_type, &value) {
+            return Err(StateError::TypeMismatch {
+                path: path.to_string(),
+                state_type: entry.state_type.clone(),
+                value: value.to_string(),
+            });
+        }
+        if entry.value != value {
+            entry.value = value;
+            self.changed.insert(path.to_string());
+        }
+        Ok(())
+    }
+
+    /// Apply an operation to the value at `path`, if the registry allows it for the value's type
+    pub fn apply(&mut self, path: &str, operation: &StateOperation, registry: &UiRegistry) -> Result<(), StateError> {
+        let entry = self.entries.get(path)
+            .ok_or_else(|| StateError::UnknownPath(path.to_string()))?;
+        let invalid = || StateError::InvalidOperation {
+            path: path.to_string(),
+            operation: operation.name().to_string(),
+            state_type: entry.state_type.clone(),
+        };
+        registry.validate_state_operation(&entry.state_type, operation.name()).map_err(|_| invalid())?;
+
+        let value = match (operation, &entry.value) {
+            (StateOperation::Set(value), _) => value.clone(),
+            (StateOperation::Toggle, Value::Bool(current)) => Value::Bool(!current),
+            (StateOperation::Increment | StateOperation::Decrement, Value::Number(current)) => {
+                let step = if *operation == StateOperation::Increment { 1 } else { -1 };
+                match current.as_i64() {
+                    Some(current) => Value::from(current.saturating_add(step)),
+                    None => Value::from(current.as_f64().unwrap_or(0.0) + step as f64),
+                }
+            }
+            (StateOperation::Append(text), Value::String(current)) => Value::String(format!("{}{}", current, text)),
+            (StateOperation::Push(item), Value::Array(current)) => {
+                let mut items = current.clone();
+                items.push(item.clone());
+                Value::Array(items)
+            }
+            _ => return Err(invalid()),
+        };
+        self.set(path, value)
+    }
+
+    /// Whether the value at `path` changed since the last [`Self::take_changed`]
+    pub fn is_changed(&self, path: &str) -> bool {
+        self.changed.contains(path)
+    }
+
+    /// Paths of the values changed since the last call
+    pub fn take_changed(&mut self) -> HashSet<String> {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+/// Check whether a value fits a built-in state type; other registered types accept any value
+pub fn value_fits_state_type(state_type: &str, value: &Value) -> bool {
+    match state_type {
+        "String" => value.is_string(),
+        "Boolean" => value.is_boolean(),
+        "Integer" => value.is_i64(),
+        "Number" => value.is_number(),
+        "List" => value.is_array(),
+        _ => true,
+    }
+}
+
+fn builtin_default(state_type: &str) -> Option<Value> {
+    match state_type {
+        "String" => Some(Value::String(String::new())),
+        "Boolean" => Some(Value::Bool(false)),
+        "Integer" => Some(Value::from(0)),
+        "Number" => Some(Value::from(0.0)),
+        "List" => Some(Value::Array(vec![])),
+        _ => None,
+    }
+}
+
+/// A state value as text content
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Deserialize the `[state]` table, flattening nested tables into dotted paths
+pub(crate) fn deserialize_state_table<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<HashMap<String, StateDeclaration>>, D::Error> {
+    let Some(table) = Option::<Map<String, Value>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let mut declarations = HashMap::new();
+    flatten_state_table(&table, "", &mut declarations).map_err(serde::de::Error::custom)?;
+    Ok(Some(declarations))
+}
+
+fn flatten_state_table(table: &Map<String, Value>, prefix: &str, declarations: &mut HashMap<String, StateDeclaration>) -> Result<(), String> {
+    for (key, value) in table {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            Value::Object(fields) if fields.get("type").is_some_and(Value::is_string) => {
+                let declaration = serde_json::from_value(value.clone())
+                    .map_err(|e| format!("invalid state declaration '{}': {}", path, e))?;
+                declarations.insert(path, declaration);
+            }
+            Value::Object(fields) => flatten_state_table(fields, &path, declarations)?,
+            literal => {
+                let declaration = StateDeclaration::inferred(literal)
+                    .ok_or_else(|| format!("state '{}' must be a value, a declaration with a 'type', or a table of those", path))?;
+                declarations.insert(path, declaration);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check whether a UI definition document binds widget properties inline
+pub fn document_has_inline_bindings(document: &Value) -> bool {
+    fn has_binding(value: &Value) -> bool {
+        match value {
+            Value::Object(fields) => inline_binding(value).is_some() || fields.values().any(has_binding),
+            Value::Array(items) => items.iter().any(has_binding),
+            _ => false,
+        }
+    }
+    document.get("root").is_some_and(has_binding)
+}
+
+/// Move inline `{ bind = "path" }` properties into each node's `state_bindings` table.
+///
+/// The property keeps the bound value's initial state so the node deserializes and the first
+/// frame shows the right value. Bindings on properties that cannot be bound are reported.
+pub fn lift_inline_bindings(document: &mut Value, report: &mut ValidationReport) {
+    let mut declarations = HashMap::new();
+    if let Some(table) = document.get(STATE_KEY).and_then(Value::as_object) {
+        // Malformed tables are reported when the definition is deserialized
+        let _ = flatten_state_table(table, "", &mut declarations);
+    }
+    if let Some(root) = document.get_mut("root") {
+        lift_node(root, "root", &declarations, report);
+    }
+}
+
+fn lift_node(node: &mut Value, path: &str, declarations: &HashMap<String, StateDeclaration>, report: &mut ValidationReport) {
+    let mut lifted = Vec::new();
+    for property in BoundProperty::ALL {
+        let Some(slot) = node.pointer_mut(property.pointer()) else {
+            continue;
+        };
+        let Some(state_path) = inline_binding(slot).map(str::to_string) else {
+            continue;
+        };
+        let initial = declarations.get(&state_path)
+            .map(StateDeclaration::initial_value)
+            .unwrap_or(Value::Null);
+        *slot = property.placeholder(&initial);
+        lifted.push((property.path(), state_path));
+    }
+
+    let Some(fields) = node.as_object_mut() else {
+        return;
+    };
+    for section in ["widget_type", "layout", "style", "behavior"] {
+        if let Some(value) = fields.get(section) {
+            report_unbindable(value, &format!("{}.{}", path, section), report);
+        }
+    }
+    if !lifted.is_empty() {
+        let bindings = fields.entry("state_bindings").or_insert_with(|| Value::Object(Map::new()));
+        if let Some(bindings) = bindings.as_object_mut() {
+            for (property, state_path) in lifted {
+                bindings.insert(property.to_string(), Value::String(state_path));
+            }
+        }
+    }
+
+    if let Some(Value::Array(children)) = fields.get_mut("children") {
+        for (index, child) in children.iter_mut().enumerate() {
+            lift_node(child, &format!("{}.children[{}]", path, index), declarations, report);
+        }
+    }
+}
+
+/// Report inline bindings left on properties that cannot be bound
+fn report_unbindable(value: &Value, path: &str, report: &mut ValidationReport) {
+    if inline_binding(value).is_some() {
+        let bindable: Vec<&str> = BoundProperty::ALL.iter().map(|property| property.path()).collect();
+        report.error(path, UiDefinitionError::Validation(format!(
+            "This property cannot be bound to state; bindable properties are: {}", bindable.join(", ")
+        )));
+        return;
+    }
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                report_unbindable(field, &format!("{}.{}", path, key), report);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                report_unbindable(item, &format!("{}[{}]", path, index), report);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The state path of an inline `{ bind = "path" }` value
+fn inline_binding(value: &Value) -> Option<&str> {
+    let fields = value.as_object()?;
+    if fields.len() != 1 {
+        return None;
+    }
+    fields.get(BIND_KEY)?.as_str()
+}
+
+impl UiDefinition {
+    /// Check state declarations and the state bindings of every node
+    pub(crate) fn check_state(&self, report: &mut ValidationReport) {
+        let declarations = self.state.clone().unwrap_or_default();
+        for (path, declaration) in sorted_entries(&declarations) {
+            let field = format!("{}.{}", STATE_KEY, path);
+            if path.split('.').any(|segment| !is_valid_state_segment(segment)) {
+                report.error(&field, UiDefinitionError::Validation(format!(
+                    "Invalid state path '{}': use dot-separated names of letters, digits, '_' and '-'", path
+                )));
+            }
+            if let Some(ref default) = declaration.default {
+                if !value_fits_state_type(&declaration.state_type, default) {
+                    report.error(format!("{}.default", field), UiDefinitionError::Validation(format!(
+                        "Default {} does not match state type {}", default, declaration.state_type
+                    )));
+                }
+            }
+        }
+        check_node_state_bindings(&self.root, "root", &declarations, report);
+    }
+
+    /// Check that every state value has a registered type
+    pub(crate) fn check_state_with_registry(&self, registry: &UiRegistry, report: &mut ValidationReport) {
+        for (path, declaration) in sorted_entries(self.state.as_ref().unwrap_or(&HashMap::new())) {
+            if !registry.is_state_type_registered(&declaration.state_type) {
+                report.error(format!("{}.{}.type", STATE_KEY, path), UiDefinitionError::RegistryValidation(
+                    format!("Unknown state type: {}", declaration.state_type)
+                ));
+            }
+        }
+    }
+}
+
+fn check_node_state_bindings(node: &WidgetNode, path: &str, declarations: &HashMap<String, StateDeclaration>, report: &mut ValidationReport) {
+    for (property_path, state_path) in sorted_entries(node.state_bindings.as_ref().unwrap_or(&HashMap::new())) {
+        let field = format!("{}.state_bindings.{}", path, property_path);
+        let Some(property) = BoundProperty::from_path(property_path) else {
+            report.error(&field, UiDefinitionError::Validation(format!("Unknown bindable property '{}'", property_path)));
+            continue;
+        };
+        let Some(declaration) = declarations.get(state_path) else {
+            report.error(&field, UiDefinitionError::Validation(format!(
+                "State value '{}' is not declared in [{}]", state_path, STATE_KEY
+            )));
+            continue;
+        };
+        if !property.accepts(&declaration.state_type) {
+            report.error(&field, UiDefinitionError::Validation(format!(
+                "'{}' cannot be bound to '{}' of type {}", property_path, state_path, declaration.state_type
+            )));
+        }
+        if property == BoundProperty::Content && !matches!(node.widget_type, WidgetType::Text { .. }) {
+            report.warning(&field, "Only Text widgets display bound content".to_string());
+        }
+        if property == BoundProperty::ActiveTab && !matches!(node.widget_type, WidgetType::Tabs { .. }) {
+            report.warning(&field, "Only Tabs widgets have an active tab".to_string());
+        }
+    }
+    for (index, child) in node.children.iter().enumerate() {
+        check_node_state_bindings(child, &format!("{}.children[{}]", path, index), declarations, report);
+    }
+}
+
+pub(crate) fn is_valid_state_segment(segment: &str) -> bool {
+    !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Widget components written when a bound state value changes
+type BoundWidgetQuery = (
+    Entity,
+    Ref<'static, WidgetStateBindings>,
+    &'static mut Widget,
+    &'static mut WidgetStyle,
+    &'static mut WidgetBehavior,
+    &'static mut WidgetLayout,
+    Option<&'static mut Visibility>,
+    Option<&'static mut ShapeData>,
+    Option<&'static mut Text>,
+    Option<&'static mut WidgetText>,
+    Option<&'static mut Styleable>,
+    Option<&'static PositionControl>,
+    Option<&'static mut WidgetTabs>,
+);
+
+/// System that pushes changed state values into the widgets bound to them.
+///
+/// Newly spawned or re-bound widgets pick up every bound value; the rest only the changed ones.
+pub fn apply_state_bindings_system(
+    mut ui_state: ResMut<UiState>,
+    yrs_res: Res<YrsDocResource>,
+    mut text_changed_writer: EventWriter<YrsTextChanged>,
+    mut widgets: Query<BoundWidgetQuery>,
+) {
+    let changed = ui_state.take_changed();
+
+    for (entity, bindings, mut widget, mut style, mut behavior, mut layout, mut visibility, mut shape_data, mut text, mut widget_text, mut styleable, position_control, mut tabs) in widgets.iter_mut() {
+        let rebound = bindings.is_changed();
+        let mut relayout_text = false;
+        let mut restyle_layout = false;
+
+        for (property, state_path) in &bindings.bindings {
+            if !rebound && !changed.contains(state_path) {
+                continue;
+            }
+            let Some(value) = ui_state.get(state_path) else {
+                continue;
+            };
+
+            match property {
+                BoundProperty::Content => {
+                    let content = display_value(value);
+                    if set_static_text(&yrs_res, entity, &mut widget, widget_text.as_deref_mut(), &content) {
+                        relayout_text = true;
+                    }
+                }
+                BoundProperty::Visible => {
+                    let visible = value.as_bool().unwrap_or(true);
+                    widget.blueprint.behavior.visible = Some(visible);
+                    behavior.visible = visible;
+                    if let Some(visibility) = visibility.as_mut() {
+                        visibility.0 = visible;
+                    }
+                }
+                BoundProperty::BackgroundColor | BoundProperty::BorderColor | BoundProperty::TextColor => {
+                    let Ok(color_def) = serde_json::from_value::<ColorDef>(value.clone()) else {
+                        warn!("State value '{}' is not a color: {}", state_path, value);
+                        continue;
+                    };
+                    let color = color_def.to_color();
+                    match property {
+                        BoundProperty::BackgroundColor => {
+                            widget.blueprint.style.background_color = Some(color_def);
+                            style.background_color = Some(color);
+                            if let Some(shape_data) = shape_data.as_mut() {
+                                shape_data.color = color;
+                            }
+                        }
+                        BoundProperty::BorderColor => {
+                            widget.blueprint.style.border_color = Some(color_def);
+                            style.border_color = Some(color);
+                        }
+                        _ => {
+                            widget.blueprint.style.text_color = Some(color_def);
+                            style.text_color = Some(color);
+                            if let Some(text) = text.as_mut() {
+                                text.color = color;
+                                relayout_text = true;
+                            }
+                        }
+                    }
+                }
+                BoundProperty::Opacity => {
+                    let opacity = value.as_f64().unwrap_or(1.0).clamp(0.0, 1.0) as f32;
+                    widget.blueprint.style.opacity = Some(opacity);
+                    style.opacity = Some(opacity);
+                }
+                BoundProperty::Width | BoundProperty::Height => {
+                    let amount = value.as_f64().unwrap_or(0.0).max(0.0) as f32;
+                    let mut size = layout.size.unwrap_or(layout.computed_size);
+                    if *property == BoundProperty::Width {
+                        size.x = amount;
+                    } else {
+                        size.y = amount;
+                    }
+                    layout.size = Some(size);
+                    widget.blueprint.layout.size = Some(Vec2::new(size.x, size.y));
+                    restyle_layout = true;
+                }
+                BoundProperty::ActiveTab => {
+                    // Ids of closed tabs and other values leave the active tab as it is
+                    let Some(index) = value.as_str().and_then(|id| tabs.as_ref()?.position(id)) else {
+                        continue;
+                    };
+                    if let WidgetType::Tabs { active, .. } = &mut widget.blueprint.widget_type {
+                        *active = value.as_str().map(str::to_string);
+                    }
+                    if let Some(tabs) = tabs.as_mut() {
+                        tabs.activate(index);
+                    }
+                }
+            }
+        }
+
+        if restyle_layout {
+            if let Some(styleable) = styleable.as_mut() {
+                let position_control = position_control.cloned().unwrap_or_default();
+                styleable.0 = convert_layout_config_to_taffy_style(&widget.blueprint.layout, &position_control);
+            }
+        }
+        if relayout_text {
+            text_changed_writer.send(YrsTextChanged { entity });
+        }
+    }
+}
\ No newline at end of file
@@ -1,10 +1,14 @@
 use bevy_ecs::prelude::*;
-use bevy_asset::{Assets, AssetServer, Handle};
-use bevy_log::{info, error};
-use bevy_hierarchy::BuildChildren;
+use bevy_asset::{AssetId, Assets, AssetServer, Handle};
+use bevy_log::{info, error, warn};
+use bevy_hierarchy::{BuildChildren, Parent};
 use bevy_math::Vec3;
-use std::collections::HashMap;
-use crate::assets::{UiDefinition, LoadUiRequest};
+use std::collections::{HashMap, HashSet};
+use crate::assets::{UiDefinition, UiRegistry, UiState, LoadUiRequest, UnloadUiRequest, ReplaceUiRequest, UiTarget};
+use crate::assets::reload::{collect_subtree, despawn_widgets};
+use crate::assets::windows::{open_declared_windows, TargetWindow, UiWindows, WidgetWindows};
+use crate::layout::{TaffyResource, UiNode};
+use crate::widgets::components::WidgetHierarchy;
 use crate::widgets::systems;
 use crate::YrsDocResource;
 
@@ -13,6 +17,48 @@ use crate::YrsDocResource;
 pub struct LoadingUiAssets {
     /// Map of asset handles to pending load requests
     pub pending_loads: HashMap<Handle<UiDefinition>, LoadUiRequest>,
+    /// UI definitions that have been spawned, used to patch them on hot reload
+    pub spawned: Vec<SpawnedUi>,
+}
+
+/// A spawned UI definition and the root entity of its widget tree
+#[derive(Debug, Clone)]
+pub struct SpawnedUi {
+    /// Strong handle that keeps the definition loaded (and watched) while it is on screen
+    pub handle: Handle<UiDefinition>,
+    /// The request the tree was spawned from
+    pub request: LoadUiRequest,
+    /// Root widget entity
+    pub root: Entity,
+}
+
+impl LoadingUiAssets {
+    /// Spawned trees that `target` names
+    pub fn matching<'a>(&'a self, target: &'a UiTarget) -> impl Iterator<Item = &'a SpawnedUi> + 'a {
+        self.spawned.iter().filter(move |spawned| target.matches(&spawned.request, Some(spawned.root)))
+    }
+
+    /// Stop tracking the trees and cancel the pending loads that `target` names, returning the
+    /// trees. Dropping them releases their asset handles.
+    pub fn take_matching(&mut self, target: &UiTarget) -> Vec<SpawnedUi> {
+        self.pending_loads.retain(|_, request| !target.matches(request, None));
+        let (taken, kept) = std::mem::take(&mut self.spawned)
+            .into_iter()
+            .partition(|spawned| target.matches(&spawned.request, Some(spawned.root)));
+        self.spawned = kept;
+        taken
+    }
+
+    /// Whether the definition `id` was only requested for declared windows, so that its
+    /// `[window]` settings do not apply to the primary window
+    pub fn is_window_only(&self, id: AssetId<UiDefinition>) -> bool {
+        let mut requests = self.pending_loads.iter()
+            .filter(|(handle, _)| handle.id() == id)
+            .map(|(_, request)| request)
+            .chain(self.spawned.iter().filter(|spawned| spawned.handle.id() == id).map(|spawned| &spawned.request))
+            .peekable();
+        requests.peek().is_some() && requests.all(|request| request.window.is_some())
+    }
 }
 
 /// System to listen for LoadUiRequest events and initiate asset loading
@@ -32,28 +78,115 @@ pub fn ui_asset_request_system(
     }
 }
 
+/// System that despawns the trees named by `UnloadUiRequest` and `ReplaceUiRequest` events and
+/// requests the replacements.
+///
+/// Every widget of a tree goes, including manually positioned ones that are not Bevy children,
+/// along with its Taffy node and yrs text. The renderer's despawn cleanup then releases the GPU
+/// buffers of its shapes through `BufferManager::remove_entity_resources`.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_asset_unload_system(
+    mut commands: Commands,
+    mut unload_requests: EventReader<UnloadUiRequest>,
+    mut replace_requests: EventReader<ReplaceUiRequest>,
+    mut load_requests: EventWriter<LoadUiRequest>,
+    mut loading_assets: ResMut<LoadingUiAssets>,
+    yrs_res: Res<YrsDocResource>,
+    taffy_resource: Res<TaffyResource>,
+    hierarchies: Query<&WidgetHierarchy>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+) {
+    let requests = unload_requests.read()
+        .map(|request| (&request.target, None))
+        .chain(replace_requests.read().map(|request| (&request.target, Some(&request.replacement))));
+
+    for (target, replacement) in requests {
+        let removed = loading_assets.take_matching(target);
+        if removed.is_empty() && replacement.is_none() {
+            warn!("No spawned UI matches {:?}", target);
+        }
+
+        let mut stale = HashSet::new();
+        for spawned in &removed {
+            info!("Unloading UI asset: {}", spawned.request.asset_path);
+            collect_subtree(spawned.root, &hierarchies, &mut stale);
+        }
+        despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+
+        #[cfg(feature = "debug_logging")]
+        bevy_log::debug!("🗑️ Unloaded {} UI tree(s) with {} widgets for {:?}", removed.len(), stale.len(), target);
+
+        if let Some(replacement) = replacement {
+            let mut request = replacement.clone();
+            if let Some(previous) = removed.first().map(|spawned| &spawned.request) {
+                request.parent = request.parent.or(previous.parent);
+                request.position_override = request.position_override.or(previous.position_override);
+                request.scope = request.scope.or_else(|| previous.scope.clone());
+                request.window = request.window.or_else(|| previous.window.clone());
+            }
+            load_requests.send(request);
+        }
+    }
+}
+
 /// System to process loaded UI assets and spawn widgets
+#[allow(clippy::too_many_arguments)]
 pub fn ui_asset_loaded_system(
     mut commands: Commands,
     mut loading_assets: ResMut<LoadingUiAssets>,
     ui_assets: Res<Assets<UiDefinition>>,
     yrs_res: Res<YrsDocResource>,
-    window_query: Query<&bevy_window::Window, With<bevy_window::PrimaryWindow>>,
+    registry: Res<UiRegistry>,
+    mut ui_state: ResMut<UiState>,
+    mut ui_windows: ResMut<UiWindows>,
+    mut load_requests: EventWriter<LoadUiRequest>,
+    widget_windows: WidgetWindows,
 ) {
     let mut completed_loads = Vec::new();
+    let mut spawned = Vec::new();
+    let mut window_requests = Vec::new();
     
     // Check all pending loads for completion
     for (handle, request) in &loading_assets.pending_loads {
         if let Some(ui_definition) = ui_assets.get(handle) {
             info!("UI asset loaded successfully: {}", request.asset_path);
+            if !passes_registry_validation(ui_definition, &registry, &request.asset_path) {
+                error!("Not spawning UI asset: {}", request.asset_path);
+                completed_loads.push(handle.clone());
+                continue;
+            }
+
+            // Resolve the declared window the UI goes in; `None` is the primary window
+            let target_window = match request.window {
+                Some(ref id) => match ui_windows.get(id) {
+                    Some(window) => Some(window),
+                    None => {
+                        warn!("Not spawning UI asset {}: window '{}' is not open", request.asset_path, id);
+                        completed_loads.push(handle.clone());
+                        continue;
+                    }
+                },
+                None => None,
+            };
+
+            // State has to exist before bound widgets are first updated
+            ui_state.declare_definition(ui_definition);
             
             // Get window height for coordinate conversion
-            let window_height = window_query.get_single()
-                .map(|window| window.height())
-                .unwrap_or(300.0); // Default fallback
+            let window_height = widget_windows.height(target_window);
             
             // Spawn the UI definition
-            spawn_ui_definition(&mut commands, ui_definition, request, &yrs_res, window_height);
+            let root = spawn_ui_definition(&mut commands, ui_definition, request, &yrs_res, &registry, window_height);
+            if let Some(window) = target_window {
+                commands.entity(root).insert(TargetWindow(window));
+            }
+            window_requests.extend(open_declared_windows(&mut commands, &mut ui_windows, ui_definition));
+            spawned.push(SpawnedUi {
+                handle: handle.clone(),
+                request: request.clone(),
+                root,
+            });
             
             // Mark this load as completed
             completed_loads.push(handle.clone());
@@ -64,16 +197,37 @@ pub fn ui_asset_loaded_system(
     for handle in completed_loads {
         loading_assets.pending_loads.remove(&handle);
     }
+    
+    // Keep track of spawned trees so later asset changes can be patched in
+    loading_assets.spawned.extend(spawned);
+
+    // Layouts of newly opened windows load like any other
+    load_requests.send_batch(window_requests);
+}
+
+/// Check a loaded definition against the registry, logging every problem if it fails.
+///
+/// The asset loader only checks structure; which widget types, actions and state types exist
+/// is decided here, once built-ins, registry manifests and plugin extensions are registered.
+pub(crate) fn passes_registry_validation(ui_definition: &UiDefinition, registry: &UiRegistry, asset_path: &str) -> bool {
+    let report = ui_definition.validation_report_with_registry(registry).with_source_name(asset_path);
+    if !report.has_errors() {
+        return true;
+    }
+    report.log();
+    error!("UI definition registry validation failed with {} error(s)", report.error_count());
+    false
 }
 
-/// Spawn a UI definition from a loaded asset
+/// Spawn a UI definition from a loaded asset, returning the root widget entity
 fn spawn_ui_definition(
     commands: &mut Commands,
     ui_definition: &UiDefinition,
     request: &LoadUiRequest,
     yrs_res: &YrsDocResource,
+    registry: &UiRegistry,
     window_height: f32,
-) {
+) -> Entity {
     #[cfg(feature = "debug_logging")]
     {
         bevy_log::debug!("🎯 Spawning UI definition from: {}", request.asset_path);
@@ -86,6 +240,8 @@ fn spawn_ui_definition(
     let widget_entity = spawn_widget_from_node(
         commands,
         &ui_definition.root,
+        ui_definition,
+        registry,
         yrs_res,
         request.parent,
         window_height,
@@ -101,18 +257,25 @@ fn spawn_ui_definition(
     
     #[cfg(feature = "debug_logging")]
     bevy_log::debug!("✅ Successfully spawned UI definition root entity: {:?}", widget_entity);
+    
+    widget_entity
 }
 
-/// Spawn a widget from a WidgetNode using unified architecture
-fn spawn_widget_from_node(
+/// Spawn a widget from a WidgetNode using unified architecture.
+///
+/// Style classes come from `ui_definition`; the spawned blueprint carries the resolved cascade.
+/// Instances of widget templates registered in `registry` are expanded before spawning.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_widget_from_node(
     commands: &mut Commands,
     node: &crate::assets::definitions::WidgetNode,
+    ui_definition: &UiDefinition,
+    registry: &UiRegistry,
     yrs_res: &YrsDocResource,
     parent: Option<Entity>,
     window_height: f32,
     parent_position: Option<Vec3>,
 ) -> Entity {
-    use crate::widgets::templates::expand_template_node;
     
     #[cfg(any(feature = "trace_logging", feature = "debug_logging"))]
     let widget_id = node.id.clone().unwrap_or_else(|| "unnamed".to_string());
@@ -126,77 +289,80 @@ fn spawn_widget_from_node(
             node.behavior.visible, node.behavior.clickable, node.behavior.z_index);
     }
     
+    // Data-declared templates expand into a regular node tree
+    let instance = match registry.expand_template_instance(node) {
+        Ok(expanded) => expanded,
+        Err(e) => {
+            error!("Failed to expand widget template for '{:?}': {}", node.id, e);
+            None
+        }
+    };
+    let authored = node;
+    let node = instance.as_ref().unwrap_or(authored);
+    // Popups and everything inside them place themselves
+    let popup = crate::widgets::popup::overlay_node(node);
+    let node = popup.as_ref().unwrap_or(node);
+
     // Check if this is a template widget and expand it directly
-    let expanded_nodes = expand_template_node(node);
+    let styled_node = ui_definition.styled_node(node);
+    let expanded_nodes = registry.expand_builtin_template(&styled_node).unwrap_or_default();
     
-    let entity = if expanded_nodes.len() > 1 {
-        // Template widget - spawn hierarchy from expansion
-        let shape_node = &expanded_nodes[0];
-        let text_node = &expanded_nodes[1];
-        
+    let entity = if let Some((root_node, part_nodes)) = expanded_nodes.split_first() {
+        // Template widget - the first node is the root, the rest are its parts
         #[cfg(feature = "debug_logging")]
         {
             bevy_log::debug!("🎯 Widget '{}' is a template widget, expanding into {} components", widget_id, expanded_nodes.len());
-            bevy_log::debug!("   📦 Shape node: id={:?}, size={:?}, bg_color={:?}", 
-                shape_node.id, shape_node.layout.size, shape_node.style.background_color);
-            bevy_log::debug!("   📝 Text node: id={:?}, content={:?}, color={:?}", 
-                text_node.id, 
-                if let crate::widgets::blueprint::WidgetType::Text { content, .. } = &text_node.widget_type { 
-                    Some(content) 
-                } else { 
-                    None 
-                },
-                text_node.style.text_color);
-            bevy_log::debug!("   🎯 Original button position: {:?}", node.layout.position);
-            bevy_log::debug!("   🎯 Shape node position after expansion: {:?}", shape_node.layout.position);
+            bevy_log::debug!("   📦 Root node: id={:?}, size={:?}, bg_color={:?}", 
+                root_node.id, root_node.layout.size, root_node.style.background_color);
+            for part_node in part_nodes {
+                bevy_log::debug!("   🧩 Part node: id={:?}, type={:?}", part_node.id, part_node.widget_type);
+            }
+            bevy_log::debug!("   🎯 Original widget position: {:?}", node.layout.position);
+            bevy_log::debug!("   🎯 Root node position after expansion: {:?}", root_node.layout.position);
         }
         
-        // Spawn shape entity (background)
-        let shape_entity = systems::spawn_widget_entity_from_node(commands, shape_node, yrs_res, window_height, None, None);
+        // Spawn the root entity (e.g. a button's background shape)
+        let root_entity = systems::spawn_widget_entity_from_node(commands, root_node, yrs_res, window_height, None, None);
         #[cfg(feature = "debug_logging")]
-        bevy_log::debug!("   ✓ Shape entity created: {:?}", shape_entity);
+        bevy_log::debug!("   ✓ Root entity created: {:?}", root_entity);
         
-        // Spawn text entity (label)
-        let text_entity = systems::spawn_widget_entity_from_node(commands, text_node, yrs_res, window_height, Some(shape_entity), Some(shape_node.layout.position.unwrap_or(Vec3::ZERO)));
-        #[cfg(feature = "debug_logging")]
-        bevy_log::debug!("   ✓ Text entity created: {:?}", text_entity);
+        // Spawn the parts (e.g. a button's label) relative to the root
+        let root_position = root_node.layout.position.unwrap_or(Vec3::ZERO);
+        let mut part_entities = Vec::new();
+        for part_node in part_nodes {
+            let part_entity = systems::spawn_widget_entity_from_node(commands, part_node, yrs_res, window_height, Some(root_entity), Some(root_position));
+            #[cfg(feature = "debug_logging")]
+            bevy_log::debug!("   ✓ Part entity created: {:?}", part_entity);
+            
+            commands.entity(part_entity).insert(crate::widgets::components::WidgetHierarchy {
+                parent: Some(root_entity),
+                children: vec![],
+            });
+            
+            // Set up Bevy's built-in parent-child relationship for Transform inheritance
+            commands.entity(part_entity).set_parent(root_entity);
+            part_entities.push(part_entity);
+        }
         
         // Set up parent-child relationship for widget hierarchy
-        commands.entity(shape_entity).insert(crate::widgets::components::WidgetHierarchy {
+        commands.entity(root_entity).insert(crate::widgets::components::WidgetHierarchy {
             parent,
-            children: vec![text_entity],
+            children: part_entities,
         });
         
-        commands.entity(text_entity).insert(crate::widgets::components::WidgetHierarchy {
-            parent: Some(shape_entity),
-            children: vec![],
-        });
-        
-        // Set up Bevy's built-in parent-child relationship for Transform inheritance
-        commands.entity(text_entity).set_parent(shape_entity);
-        
         #[cfg(feature = "debug_logging")]
-        bevy_log::debug!("✓ Created template widget hierarchy: shape={:?}, text={:?}", shape_entity, text_entity);
-        shape_entity
+        bevy_log::debug!("✓ Created template widget hierarchy: root={:?}, parts={}", root_entity, part_nodes.len());
+        root_entity
     } else {
         // Regular widget - spawn directly
-        let entity = systems::spawn_widget_entity_from_node(commands, node, yrs_res, window_height, parent, parent_position);
+        let entity = systems::spawn_widget_entity_from_node(commands, &styled_node, yrs_res, window_height, parent, parent_position);
         
-        // Set up hierarchy for children
-        let mut child_entities = Vec::new();
-        for child_node in &node.children {
-            let child_entity = spawn_widget_from_node(commands, child_node, yrs_res, Some(entity), window_height, node.layout.position);
-            child_entities.push(child_entity);
-            
-            // Only set up Bevy's parent-child relationship if the child doesn't use Manual positioning
-            // Manual positioned widgets should not inherit transforms from layout-positioned parents
-            let child_position_control = child_node.behavior.position_control.clone().unwrap_or(crate::layout::PositionControl::Layout);
-            if !child_position_control.is_manual() {
-                commands.entity(entity).add_child(child_entity);
-            } else {
-                #[cfg(feature = "debug_logging")]
-                bevy_log::debug!("🔓 Skipping parent-child relationship for Manual positioned widget '{:?}' to prevent transform inheritance", child_node.id);
-            }
+        // Set up hierarchy for children; `when` and `repeat` children are spawned later from UiState
+        let (child_entities, dynamic_children) = crate::assets::dynamic::dynamic_slots(node, |child_node| {
+            spawn_child_widget(commands, child_node, ui_definition, registry, yrs_res, entity, window_height, node.layout.position)
+        });
+        if let Some(dynamic_children) = dynamic_children {
+            commands.entity(entity).insert(dynamic_children);
         }
         
         commands.entity(entity).insert(crate::widgets::components::WidgetHierarchy {
@@ -213,10 +379,80 @@ fn spawn_widget_from_node(
             bindings: bindings.clone(),
         });
     }
+
+    // Add state bindings if they exist
+    if let Some(ref state_bindings) = node.state_bindings {
+        commands.entity(entity).insert(crate::widgets::components::WidgetStateBindings::from_table(state_bindings));
+    }
+
+    // Checkboxes and toggles keep their value on the box they are clicked through
+    if let Some(toggle) = crate::widgets::toggle::WidgetToggle::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(toggle);
+    }
+    // So do sliders and drag values, on the track or box they are dragged by
+    if let Some(numeric) = crate::widgets::numeric::WidgetNumeric::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(numeric);
+    }
+    // Text inputs keep their settings on the box, and their text in the field
+    if let Some(text_input) = crate::widgets::text_input::WidgetTextInput::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(text_input);
+    }
+    // Scroll views get their content entity and scrollbars from scroll_view_setup_system
+    if let Some(scroll_view) = crate::widgets::scroll_view::WidgetScrollView::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(scroll_view);
+    }
+    // Virtual lists spawn rows from their template as they come into view
+    if let Some(virtual_list) = crate::widgets::virtual_list::WidgetVirtualList::from_node(node) {
+        commands.entity(entity).insert(virtual_list);
+    }
+    // Dropdowns keep their options and selection on the box, and popups their placement
+    if let Some(dropdown) = crate::widgets::dropdown::WidgetDropdown::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(dropdown);
+    }
+    if let Some(popup) = crate::widgets::popup::WidgetPopup::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(popup);
+    }
+    // Tabs spawn their header from tabs_header_system and show one child page at a time
+    if let Some(tabs) = crate::widgets::tabs::WidgetTabs::from_widget_type(&node.widget_type) {
+        commands.entity(entity).insert(tabs);
+    }
+
+    // Remember the authored node so the live tree can be written back out
+    let source = match instance {
+        Some(_) => crate::widgets::components::WidgetSourceNode::template_instance(authored),
+        None => crate::widgets::components::WidgetSourceNode::new(authored),
+    };
+    commands.entity(entity).insert(source);
     
     entity
 }
 
+/// Spawn a widget as a child of `parent`.
+///
+/// Only children that take part in layout become Bevy children; manually positioned widgets
+/// and popups should not inherit transforms from layout-positioned parents.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_child_widget(
+    commands: &mut Commands,
+    node: &crate::assets::definitions::WidgetNode,
+    ui_definition: &UiDefinition,
+    registry: &UiRegistry,
+    yrs_res: &YrsDocResource,
+    parent: Entity,
+    window_height: f32,
+    parent_position: Option<Vec3>,
+) -> Entity {
+    let child_entity = spawn_widget_from_node(commands, node, ui_definition, registry, yrs_res, Some(parent), window_height, parent_position);
+
+    if !crate::widgets::popup::positions_itself(node) {
+        commands.entity(parent).add_child(child_entity);
+    } else {
+        #[cfg(feature = "debug_logging")]
+        bevy_log::debug!("🔓 Skipping parent-child relationship for Manual positioned widget '{:?}' to prevent transform inheritance", node.id);
+    }
+    child_entity
+}
+
 /// System to handle asset loading errors
 pub fn ui_asset_error_system(
     mut loading_assets: ResMut<LoadingUiAssets>,
@@ -225,12 +461,10 @@ pub fn ui_asset_error_system(
     let mut failed_loads = Vec::new();
     
     for (handle, request) in &loading_assets.pending_loads {
-        match asset_server.load_state(handle) {
-            bevy_asset::LoadState::Failed(_) => {
-                error!("Failed to load UI asset: {}", request.asset_path);
-                failed_loads.push(handle.clone());
-            }
-            _ => {} // Still loading or loaded successfully
+        // Assets still loading or loaded successfully stay pending
+        if let bevy_asset::LoadState::Failed(_) = asset_server.load_state(handle) {
+            error!("Failed to load UI asset: {}", request.asset_path);
+            failed_loads.push(handle.clone());
         }
     }
     
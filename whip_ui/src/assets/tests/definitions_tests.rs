@@ -1,4 +1,5 @@
 use super::super::*;
+use crate::test_support::button;
 use crate::widgets::blueprint::{ColorDef, FlexDirection, WidgetType, LayoutConfig, StyleConfig, BehaviorConfig};
 use std::collections::HashMap;
 
@@ -181,6 +182,10 @@ fn test_validation_errors() {
         classes: None,
         style_overrides: None,
         bindings: None,
+        state_bindings: None,
+        repeat: None,
+        when: None,
+        localized: None,
         children: vec![],
     });
     
@@ -219,7 +224,8 @@ fn test_validation_errors() {
         let mut styles = HashMap::new();
         styles.insert("valid_class".to_string(), StyleOverrides { 
             background_color: None, border_color: None, border_width: None, 
-            border_radius: None, text_color: None, text_size: None, opacity: None 
+            border_radius: None, text_color: None, text_size: None, opacity: None,
+            extends: None, states: None
         });
         styles
     });
@@ -257,7 +263,9 @@ fn test_to_widget_collection() {
 #[test]
 fn test_style_override_application() {
     let mut ui_def = UiDefinition {
+        format_version: CURRENT_FORMAT_VERSION,
         window: None,
+        windows: None,
         root: WidgetNode {
             id: Some("root".to_string()),
             widget_type: WidgetType::Container { direction: FlexDirection::Column },
@@ -267,10 +275,17 @@ fn test_style_override_application() {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![],
         },
         styles: None,
         actions: None,
+        tokens: None,
+        state: None,
+        authored: None,
     };
     
     // Add global styles
@@ -283,6 +298,8 @@ fn test_style_override_application() {
         border_radius: None,
         text_color: None,
         opacity: None,
+        extends: None,
+        states: None,
     });
     ui_def.styles = Some(styles);
     
@@ -296,6 +313,8 @@ fn test_style_override_application() {
         border_width: None,
         text_color: None,
         opacity: None,
+        extends: None,
+        states: None,
     });
     
     let collection = ui_def.to_widget_collection();
@@ -311,7 +330,9 @@ fn test_style_override_application() {
 #[test]
 fn test_color_validation() {
     let ui_def = UiDefinition {
+        format_version: CURRENT_FORMAT_VERSION,
         window: None,
+        windows: None,
         root: WidgetNode {
             id: Some("test".to_string()),
             widget_type: WidgetType::Container { direction: FlexDirection::Column },
@@ -324,10 +345,17 @@ fn test_color_validation() {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![],
         },
         styles: None,
         actions: None,
+        tokens: None,
+        state: None,
+        authored: None,
     };
     
     let result = ui_def.validate();
@@ -338,10 +366,13 @@ fn test_color_validation() {
 /// Helper function to create a valid UI definition for testing
 fn create_valid_ui_definition() -> UiDefinition {
     UiDefinition {
+        format_version: CURRENT_FORMAT_VERSION,
         window: Some(WindowConfig {
             size: [800.0, 600.0],
             background_color: Some(ColorDef::Hex("#2D3748".to_string())),
+            ..Default::default()
         }),
+        windows: None,
         root: WidgetNode {
             id: Some("root_container".to_string()),
             widget_type: WidgetType::Container { direction: FlexDirection::Column },
@@ -360,24 +391,32 @@ fn create_valid_ui_definition() -> UiDefinition {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![
                 WidgetNode {
                     id: Some("test_button".to_string()),
-                    widget_type: WidgetType::Button {
-                        text: "Test Button".to_string(),
-                        action: Some("navigate_home".to_string()), // Use registered action
-                    },
+                    widget_type: button("Test Button"),
                     layout: LayoutConfig::default(),
                     style: StyleConfig::default(),
                     behavior: BehaviorConfig::default(),
                     classes: None,
                     style_overrides: None,
                     bindings: None,
+                    state_bindings: None,
+                    repeat: None,
+                    when: None,
+                    localized: None,
                     children: vec![],
                 },
             ],
         },
         styles: None,
         actions: None,
+        tokens: None,
+        state: None,
+        authored: None,
     }
 }
\ No newline at end of file
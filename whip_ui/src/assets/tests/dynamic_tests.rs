@@ -0,0 +1,110 @@
+use super::super::*;
+use crate::widgets::blueprint::WidgetType;
+use serde_json::json;
+
+const SHOT_LIST: &str = r##"
+[state]
+shots = [{ id = "s1", name = "Opening" }, { id = "s2", name = "Chase" }]
+view.mode = "list"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "shots"
+widget_type = { type = "Container", direction = "Column" }
+repeat = { each = "shots", key = "id", as = "shot" }
+
+[[root.children.children]]
+id = "name"
+widget_type = { type = "Text", content = "Shot: {{shot.name}} ({{other.name}})", editable = false }
+
+[[root.children]]
+id = "grid_hint"
+when = "view.mode == 'grid'"
+widget_type = { type = "Text", content = "Grid", editable = false }
+"##;
+
+fn text_content(node: &definitions::WidgetNode) -> &str {
+    match &node.widget_type {
+        WidgetType::Text { content, .. } => content,
+        other => panic!("Expected a text node, got {:?}", other),
+    }
+}
+
+/// Test condition parsing and evaluation against the state store
+#[test]
+fn test_state_conditions() {
+    let mut state = UiState::new();
+    state.declare("view.mode", "String", json!("list"));
+    state.declare("shots", "List", json!([]));
+    state.declare("zoom", "Number", json!(1.0));
+
+    let holds = |source: &str| StateCondition::parse(source).unwrap().evaluate(&state);
+    assert!(holds("view.mode"));
+    assert!(!holds("shots"), "Empty lists are falsy");
+    assert!(holds("!shots"));
+    assert!(holds("view.mode == 'list'"));
+    assert!(holds("view.mode != grid"));
+    assert!(holds("zoom == 1"), "Numbers compare by value");
+    assert!(!holds("missing"), "Undeclared values are null");
+
+    assert!(matches!(StateCondition::parse("view mode"), Err(DynamicNodeError::InvalidCondition(_))));
+    assert!(matches!(StateCondition::parse("== 'grid'"), Err(DynamicNodeError::InvalidCondition(_))));
+}
+
+/// Test that repeat items fill in item placeholders and scope their ids by key
+#[test]
+fn test_repeat_item_expansion() {
+    let ui_def = UiDefinitionLoader.parse_definition(SHOT_LIST, UiDefinitionFormat::Toml).unwrap();
+    assert!(!ui_def.validation_report().has_errors());
+    assert_eq!(ui_def.state.as_ref().unwrap()["shots"].state_type, "List");
+
+    let host = &ui_def.root.children[0];
+    let config = host.repeat.as_ref().unwrap();
+    let item = json!({ "id": "s2", "name": "Chase" });
+    assert_eq!(config.item_key(1, &item), "s2");
+    assert_eq!(config.item_key(1, &json!("plain")), "1", "Items without the key field are keyed by position");
+
+    let nodes = expand_repeat_item(config, "shots", &host.children, "s2", &item).unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].id.as_deref(), Some("shots_s2_name"));
+    assert_eq!(text_content(&nodes[0]), "Shot: Chase ({{other.name}})", "Placeholders of other items are left alone");
+
+    let mut missing = host.children.clone();
+    if let WidgetType::Text { content, .. } = &mut missing[0].widget_type {
+        *content = "{{shot.title}}".to_string();
+    }
+    assert_eq!(
+        expand_repeat_item(config, "shots", &missing, "s2", &item).unwrap_err(),
+        DynamicNodeError::UnknownItemField { each: "shots".to_string(), placeholder: "shot.title".to_string() }
+    );
+}
+
+/// Test that conditions and repeats must reference declared state of the right type
+#[test]
+fn test_dynamic_node_validation() {
+    let content = r##"
+[state]
+title = "Untitled"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+when = "title"
+
+[[root.children]]
+widget_type = { type = "Container", direction = "Column" }
+repeat = { each = "title" }
+
+[[root.children]]
+widget_type = { type = "Text", content = "Hint", editable = false }
+when = "missing"
+"##;
+    let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+    let report = ui_def.validation_report();
+    let paths: Vec<&str> = report.errors().map(|d| d.path.as_str()).collect();
+    assert!(paths.contains(&"root.when"), "{:?}", paths);
+    assert!(paths.contains(&"root.children[0].repeat.each"), "{:?}", paths);
+    assert!(paths.contains(&"root.children[0].repeat"), "A repeat needs an item template: {:?}", paths);
+    assert!(paths.contains(&"root.children[1].when"), "{:?}", paths);
+}
\ No newline at end of file
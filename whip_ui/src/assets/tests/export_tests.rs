@@ -0,0 +1,192 @@
+use super::super::*;
+use crate::test_support::setup_world;
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_transform::prelude::Transform;
+
+const LAYOUT: &str = r##"
+[root]
+id = "main"
+widget_type = { type = "Container", direction = "Column" }
+classes = ["panel"]
+
+[[root.children]]
+id = "notes"
+widget_type = { type = "Text", content = "Draft", editable = true }
+layout = { size = [200.0, 30.0] }
+style = { opacity = 0.1 }
+
+[[root.children]]
+id = "handle"
+widget_type = { type = "Shape", shape_type = "Circle" }
+layout = { position = [40.0, 60.0, 1.0], size = [20.0, 20.0] }
+style = { background_color = "#FF8800" }
+behavior = { draggable = true, position_control = "Manual" }
+
+[[root.children]]
+id = "save"
+widget_type = { type = "Button", text = "Save" }
+"##;
+
+/// Test that definitions written to TOML and JSON parse back unchanged
+#[test]
+fn test_definition_source_round_trip() {
+    let ui_def: UiDefinition = toml::from_str(LAYOUT).unwrap();
+
+    let toml_source = ui_def.to_source_string(UiDefinitionFormat::Toml).unwrap();
+    assert!(toml_source.contains("[[root.children]]"));
+    assert!(toml_source.contains("opacity = 0.1\n"), "f32 values should be written tidily:\n{}", toml_source);
+
+    for (source, format) in [
+        (toml_source, UiDefinitionFormat::Toml),
+        (ui_def.to_source_string(UiDefinitionFormat::Json).unwrap(), UiDefinitionFormat::Json),
+    ] {
+        let reparsed = UiDefinitionLoader.parse_definition(&source, format).unwrap();
+        assert_eq!(reparsed.root.classes, Some(vec!["panel".to_string()]));
+        assert_eq!(reparsed.root.children.len(), 3);
+        assert_eq!(reparsed.root.children[1].style.background_color, Some(ColorDef::Hex("#FF8800".to_string())));
+        assert!(matches!(reparsed.root.children[2].widget_type, WidgetType::Button { .. }));
+    }
+}
+
+/// Test that dragged positions and edited text are written back from the live tree
+#[test]
+fn test_live_tree_serialization() {
+    let ui_def: UiDefinition = toml::from_str(LAYOUT).unwrap();
+
+    let (mut world, yrs_res) = setup_world();
+
+    let root = {
+        let mut commands = world.commands();
+        spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), &yrs_res, None, 300.0, None)
+    };
+    world.flush();
+
+    let find = |world: &mut World, id: &str| {
+        world.query::<(Entity, &crate::widgets::components::Widget)>()
+            .iter(world)
+            .find(|(_, widget)| widget.id == id)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    };
+
+    // Edit the text through yrs and drag the manual shape 10px right and 20px down
+    let notes = find(&mut world, "notes");
+    {
+        use yrs::{Text, Transact};
+        let text_map = yrs_res.text_map.lock().unwrap();
+        let mut txn = yrs_res.doc.transact_mut();
+        text_map[&notes].insert(&mut txn, 5, " v2");
+    }
+    let handle = find(&mut world, "handle");
+    let mut transform = world.get_mut::<Transform>(handle).unwrap();
+    transform.translation.x += 10.0;
+    transform.translation.y -= 20.0;
+
+    let saved = serialize_ui_tree(&mut world, root).unwrap();
+    assert_eq!(saved.root.classes, Some(vec!["panel".to_string()]));
+    assert_eq!(saved.root.children.len(), 3, "Template parts must not be written as children");
+
+    match &saved.root.children[0].widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "Draft v2"),
+        other => panic!("Expected a Text widget, got {:?}", other),
+    }
+    let position = saved.root.children[1].layout.position.unwrap();
+    assert_eq!((position.x, position.y, position.z), (50.0, 80.0, 1.0));
+    assert!(matches!(saved.root.children[2].widget_type, WidgetType::Button { .. }));
+}
+
+const THEMED: &str = r##"
+[tokens]
+accent = "#FF8800"
+knob = 20.0
+
+[root]
+id = "main"
+widget_type = { type = "Container", direction = "Column" }
+style = { background_color = "$accent" }
+
+[[root.children]]
+id = "handle"
+widget_type = { type = "Shape", shape_type = "Circle" }
+layout = { position = [40.0, 60.0, 1.0], size = ["$knob", "$knob"] }
+style = { background_color = "$accent" }
+behavior = { draggable = true, position_control = "Manual" }
+
+[[root.children]]
+include = "label.toml"
+id = "notes"
+content = "Draft"
+"##;
+
+const LABEL: &str = r##"
+[widget]
+widget_type = { type = "Text", content = "Label", editable = true }
+style = { text_color = "$accent" }
+"##;
+
+/// Expand includes and tokens the way the asset loader does, keeping the authored document
+fn load_themed(source: &str) -> UiDefinition {
+    let path = std::path::Path::new("ui/main.toml");
+    let authored: serde_json::Value = toml::from_str(source).unwrap();
+    let mut resolver = IncludeResolver::default();
+    resolver.insert_source("ui/label.toml", toml::from_str(LABEL).unwrap());
+    let mut document = resolver.expand_document(authored.clone(), path).unwrap();
+    let mut report = ValidationReport::new();
+    TokenResolver::default().resolve_document(&mut document, path, &mut report);
+    assert!(!report.has_errors(), "{}", report);
+
+    let mut ui_def: UiDefinition = serde_json::from_value(document).unwrap();
+    ui_def.authored = Some(authored);
+    ui_def
+}
+
+/// Test that exporting a live tree keeps token references and includes for values that did not
+/// change, and that the written source loads back into the live state
+#[test]
+fn test_export_keeps_tokens_and_includes() {
+    let ui_def = load_themed(THEMED);
+
+    let (mut world, yrs_res) = setup_world();
+    let root = {
+        let mut commands = world.commands();
+        spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), &yrs_res, None, 300.0, None)
+    };
+    world.flush();
+    let handle = world.resource_mut::<Assets<UiDefinition>>().add(ui_def);
+    world.resource_mut::<LoadingUiAssets>().spawned.push(SpawnedUi { handle, request: LoadUiRequest::new("ui/main.toml"), root });
+
+    let find = |world: &mut World, id: &str| {
+        world.query::<(Entity, &crate::widgets::components::Widget)>()
+            .iter(world)
+            .find(|(_, widget)| widget.id == id)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    };
+    let notes = find(&mut world, "notes");
+    crate::assets::reload::replace_widget_text(&yrs_res, notes, "Draft v2");
+    let knob = find(&mut world, "handle");
+    world.get_mut::<Transform>(knob).unwrap().translation.x += 10.0;
+
+    let saved = serialize_ui_tree(&mut world, root).unwrap();
+    let document = saved.authored.as_ref().unwrap();
+    assert_eq!(document["tokens"]["knob"], serde_json::json!(20.0));
+    assert_eq!(document["root"]["style"]["background_color"], "$accent");
+    let knob_node = &document["root"]["children"][0];
+    assert_eq!(knob_node["layout"]["size"], serde_json::json!(["$knob", "$knob"]));
+    assert_eq!(knob_node["layout"]["position"], serde_json::json!([50.0, 60.0, 1.0]));
+    assert_eq!(knob_node["style"]["background_color"], "$accent");
+    assert_eq!(
+        document["root"]["children"][1],
+        serde_json::json!({ "include": "label.toml", "id": "notes", "content": "Draft v2" }),
+        "The edited label stays an include with its new content"
+    );
+
+    let source = saved.to_source_string(UiDefinitionFormat::Toml).unwrap();
+    let reloaded = load_themed(&source);
+    let position = reloaded.root.children[0].layout.position.unwrap();
+    assert_eq!((position.x, position.y), (50.0, 60.0));
+    assert_eq!(reloaded.root.children[0].style.background_color, Some(ColorDef::Hex("#FF8800".to_string())));
+    assert!(matches!(&reloaded.root.children[1].widget_type, WidgetType::Text { content, .. } if content == "Draft v2"));
+}
\ No newline at end of file
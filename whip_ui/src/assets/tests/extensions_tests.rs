@@ -0,0 +1,133 @@
+use super::super::*;
+use bevy_app::App;
+use std::collections::HashMap;
+
+const MANIFEST: &str = r##"
+[config]
+max_nesting_depth = 8
+
+[widgets.Timeline]
+required = ["track_count"]
+optional = ["zoom"]
+children = true
+
+[actions.play_clip]
+description = "Start playback of a clip"
+params = { clip_id = "String", from = "Timecode" }
+
+[state_types.Timecode]
+type_id = "f64"
+operations = ["set", "get", "increment", "decrement"]
+default = 0.0
+"##;
+
+const EDITOR: &str = r##"
+[state]
+playhead = { type = "Timecode", default = 12.5 }
+
+[root]
+widget_type = { type = "Custom", component = "Timeline", properties = { track_count = 3 } }
+
+[root.bindings.click]
+event = "click"
+action = "play_clip"
+params = { clip_id = "c1", from = 0.0 }
+
+[[root.children]]
+widget_type = { type = "Text", content = "Clip", editable = false }
+"##;
+
+struct ClipBinPlugin;
+
+impl RegisterUiExtension for ClipBinPlugin {
+    fn register(&self, registry: &mut UiRegistry) -> Result<(), UiRegistryError> {
+        registry.register_action("reveal_clip", ActionInfo {
+            display_name: "Reveal Clip".to_string(),
+            description: "Show a clip in the bin".to_string(),
+            parameter_types: HashMap::from([("clip_id".to_string(), "String".to_string())]),
+            requires_conditions: vec![],
+        });
+        Ok(())
+    }
+}
+
+/// Test that entries declared in a manifest are accepted by registry validation
+#[test]
+fn test_manifest_entries_pass_validation() {
+    let ui_def = UiDefinitionLoader.parse_definition(EDITOR, UiDefinitionFormat::Toml).unwrap();
+    assert!(!ui_def.validation_report().has_errors(), "Structural validation leaves names to the registry");
+
+    let mut registry = UiRegistry::new();
+    assert!(ui_def.validation_report_with_registry(&registry).has_errors());
+
+    let manifest = RegistryManifest::parse(MANIFEST, UiDefinitionFormat::Toml).unwrap();
+    registry.register_extension(&manifest).unwrap();
+    assert_eq!(registry.config.max_nesting_depth, 8);
+    assert!(registry.config.strict_validation, "Unset config fields keep their value");
+    assert_eq!(registry.get_action_info("play_clip").unwrap().display_name, "play_clip");
+    assert!(registry.get_widget_type_info("Timeline").unwrap().can_have_children);
+
+    let report = ui_def.validation_report_with_registry(&registry);
+    assert!(!report.has_errors(), "{:?}", report.errors().collect::<Vec<_>>());
+}
+
+/// Test that manifests cannot redeclare built-ins or use unknown types
+#[test]
+fn test_invalid_manifests_are_rejected() {
+    let mut registry = UiRegistry::new();
+
+    let builtin = RegistryManifest::parse("[widgets.Button]\nrequired = [\"label\"]\n", UiDefinitionFormat::Toml).unwrap();
+    assert!(matches!(registry.register_extension(&builtin), Err(UiRegistryError::ValidationError(_))));
+
+    let unknown_param = RegistryManifest::parse("[actions.seek]\nparams = { to = \"Timecode\" }\n", UiDefinitionFormat::Toml).unwrap();
+    assert!(registry.register_extension(&unknown_param).is_err());
+    assert!(!registry.is_action_registered("seek"));
+
+    assert!(matches!(
+        RegistryManifest::parse("[widgets.Timeline]\nchildren = true\ncolour = \"red\"\n", UiDefinitionFormat::Toml),
+        Err(RegistryManifestError::Parse(_))
+    ));
+}
+
+/// Test that template files listed in a manifest are read relative to it and registered
+#[test]
+fn test_manifest_templates_are_loaded() {
+    let dir = std::env::temp_dir().join(format!("whip_ui_manifest_templates_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("templates")).unwrap();
+    std::fs::write(dir.join("registry.toml"), "templates = [\"templates/badge.toml\"]\n").unwrap();
+    std::fs::write(dir.join("templates/badge.toml"), r##"
+[template]
+name = "Badge"
+
+[template.params]
+label = {}
+
+[template.root]
+widget_type = { type = "Text", content = "{{label}}", editable = false }
+"##).unwrap();
+
+    let mut registry = UiRegistry::new();
+    let loaded = registry.load_manifest_file(dir.join("registry.toml"));
+    std::fs::remove_dir_all(&dir).unwrap();
+    loaded.unwrap();
+
+    let node: definitions::WidgetNode = toml::from_str(
+        "widget_type = { type = \"Custom\", component = \"Badge\", properties = { label = \"New\" } }"
+    ).unwrap();
+    let expanded = registry.expand_template_instance(&node).unwrap().unwrap();
+    assert!(matches!(expanded.widget_type, crate::widgets::blueprint::WidgetType::Text { ref content, .. } if content == "New"));
+
+    let mut missing = RegistryManifest::parse("templates = [\"missing.toml\"]\n", UiDefinitionFormat::Toml).unwrap();
+    assert!(matches!(missing.load_templates(&std::env::temp_dir()), Err(RegistryManifestError::TemplateIo { .. })));
+}
+
+/// Test that plugins can contribute entries through the App
+#[test]
+fn test_app_extension_registration() {
+    let mut app = App::new();
+    app.register_ui_extension(ClipBinPlugin);
+
+    let registry = app.world().resource::<UiRegistry>();
+    assert!(registry.is_action_registered("reveal_clip"));
+    assert!(registry.is_action_registered("debug"), "The registry is created with the built-ins");
+}
\ No newline at end of file
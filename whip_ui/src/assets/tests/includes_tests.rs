@@ -0,0 +1,132 @@
+use super::super::*;
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+use std::path::{Path, PathBuf};
+
+const EDITABLE_LABEL: &str = r##"
+[widget]
+widget_type = { type = "Text", content = "Default Text", editable = true }
+layout = { size = [200.0, 30.0], position = [0.0, 0.0, 0.0] }
+style = { text_color = { Named = "black" }, text_size = 16.0, background_color = { Named = "white" } }
+behavior = { visible = true, clickable = true, focusable = true }
+"##;
+
+/// Test that include paths resolve relative to the including file
+#[test]
+fn test_include_path_resolution() {
+    let layout = Path::new("ui/layouts/main.toml");
+    assert_eq!(resolve_include_path(layout, "../widgets/editable_label.toml"), PathBuf::from("ui/widgets/editable_label.toml"));
+    assert_eq!(resolve_include_path(layout, "./row.toml"), PathBuf::from("ui/layouts/row.toml"));
+    assert_eq!(resolve_include_path(layout, "/ui/widgets/draggable_shape.toml"), PathBuf::from("ui/widgets/draggable_shape.toml"));
+}
+
+/// Test that instance fields deep-merge over the included blueprint
+#[test]
+fn test_include_overrides_merge_over_blueprint() {
+    let layout = r##"
+[root]
+id = "main"
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+include = "widgets/editable_label.toml"
+id = "title_label"
+content = "Project title"
+layout = { position = [20.0, 40.0, 0.0] }
+style = { text_size = 24.0 }
+"##;
+
+    let document: serde_json::Value = toml::from_str(layout).unwrap();
+    assert!(document_has_includes(&document));
+
+    let mut resolver = IncludeResolver::default();
+    resolver.insert_source("ui/widgets/editable_label.toml", toml::from_str(EDITABLE_LABEL).unwrap());
+
+    let expanded = resolver.expand_document(document, Path::new("ui/example_ui.toml")).unwrap();
+    let ui_def: UiDefinition = serde_json::from_value(expanded).unwrap();
+    assert!(ui_def.validate().is_ok());
+
+    let label = &ui_def.root.children[0];
+    assert_eq!(label.id.as_deref(), Some("title_label"));
+    match &label.widget_type {
+        WidgetType::Text { content, editable } => {
+            assert_eq!(content, "Project title");
+            assert!(*editable, "Blueprint fields not overridden must be kept");
+        }
+        other => panic!("Expected a Text widget, got {:?}", other),
+    }
+
+    // Overridden leaf values replace, untouched siblings survive the merge
+    assert_eq!(label.layout.position.map(|p| p.y), Some(40.0));
+    assert_eq!(label.layout.size.map(|s| s.x), Some(200.0));
+    assert_eq!(label.style.text_size, Some(24.0));
+    assert_eq!(label.style.text_color, Some(ColorDef::Named("black".to_string())));
+    assert_eq!(label.behavior.focusable, Some(true));
+}
+
+/// Test that include cycles are reported instead of recursing forever
+#[test]
+fn test_include_cycle_detection() {
+    let layout = r##"
+[root]
+include = "widgets/a.toml"
+"##;
+    let widget_a = r##"
+[widget]
+widget_type = { type = "Container", direction = "Row" }
+
+[[widget.children]]
+include = "b.toml"
+"##;
+    let widget_b = r##"
+[widget]
+include = "a.toml"
+"##;
+
+    let mut resolver = IncludeResolver::default();
+    resolver.insert_source("ui/widgets/a.toml", toml::from_str(widget_a).unwrap());
+    resolver.insert_source("ui/widgets/b.toml", toml::from_str(widget_b).unwrap());
+
+    let document: serde_json::Value = toml::from_str(layout).unwrap();
+    match resolver.expand_document(document, Path::new("ui/layout.toml")) {
+        Err(IncludeError::Cycle(chain)) => {
+            assert!(chain.contains("a.toml") && chain.contains("b.toml"), "Cycle chain should name both files: {}", chain);
+        }
+        other => panic!("Expected an include cycle error, got {:?}", other),
+    }
+}
+
+/// Test that the blueprint files shipped with the example parse, and that the example layout's
+/// include expands into a valid definition
+#[test]
+fn test_example_blueprints_from_disk() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../whip_ui_example/assets");
+    let read = |path: &str| -> serde_json::Value {
+        let text = std::fs::read_to_string(assets.join(path))
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e));
+        toml::from_str(&text).unwrap_or_else(|e| panic!("Failed to parse {}: {}", path, e))
+    };
+
+    let mut resolver = IncludeResolver::default();
+    for path in ["ui/widgets/editable_label.toml", "ui/widgets/draggable_shape.toml"] {
+        let blueprint = read(path);
+        let node: WidgetNode = serde_json::from_value(blueprint_node(&blueprint).clone())
+            .unwrap_or_else(|e| panic!("{} is not a widget node: {}", path, e));
+        assert!(node.layout.size.is_some() && node.behavior.visible == Some(true), "{} lost its sub-tables", path);
+        resolver.insert_source(path, blueprint);
+    }
+
+    let layout = read("ui/example_ui.toml");
+    let mut paths = Vec::new();
+    collect_include_paths(&layout["root"], Path::new("ui/example_ui.toml"), &mut paths);
+    assert_eq!(paths, [PathBuf::from("ui/widgets/editable_label.toml")]);
+
+    let expanded = resolver.expand_document(layout, Path::new("ui/example_ui.toml")).unwrap();
+    let ui_def: UiDefinition = serde_json::from_value(expanded).unwrap();
+    assert!(ui_def.validate().is_ok());
+
+    let notes = &ui_def.root.children[1].children[0];
+    assert_eq!(notes.id.as_deref(), Some("notes_label"));
+    assert!(matches!(&notes.widget_type, WidgetType::Text { content, editable: true } if content == "Click to edit notes"));
+    assert_eq!(notes.style.text_color, Some(ColorDef::Hex("#1A202C".to_string())));
+    assert_eq!(notes.style.text_size, Some(16.0), "Blueprint style survives the instance override");
+}
\ No newline at end of file
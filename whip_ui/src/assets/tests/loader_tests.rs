@@ -1,5 +1,7 @@
 use super::super::*;
-use crate::widgets::blueprint::{ColorDef, FlexDirection};
+use super::super::loaders::parse_source;
+use crate::test_support::button;
+use crate::widgets::blueprint::{ColorDef, FlexDirection, WidgetType, LayoutConfig, StyleConfig, BehaviorConfig};
 use std::collections::HashMap;
 
 /// Test comprehensive validation with registry checks
@@ -15,10 +17,7 @@ fn test_comprehensive_validation() {
 
     // Test invalid widget type validation
     let mut invalid_widget_ui_def = create_valid_ui_definition();
-    invalid_widget_ui_def.root.widget_type = WidgetType::Button {
-        text: "".to_string(), // Empty text should fail
-        action: None,
-    };
+    invalid_widget_ui_def.root.widget_type = button("");
     let result = loader.validate_with_comprehensive_checks(&invalid_widget_ui_def, &registry);
     assert!(result.is_err(), "Empty button text should fail validation");
     
@@ -40,6 +39,8 @@ fn test_comprehensive_validation() {
             border_radius: None,
             text_size: None,
             opacity: None,
+            extends: None,
+            states: None,
         });
         styles
     });
@@ -68,6 +69,10 @@ fn test_comprehensive_validation() {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![],
         };
         current_node.children.push(child);
@@ -97,10 +102,7 @@ fn test_detailed_error_logging() {
     
     // Create an invalid UI definition for error logging
     let mut invalid_ui_def = create_valid_ui_definition();
-    invalid_ui_def.root.widget_type = WidgetType::Button {
-        text: "".to_string(), // This will trigger validation error
-        action: None,
-    };
+    invalid_ui_def.root.widget_type = button("");
     
     // Test error validation and logging
     let validation_result = loader.validate_with_comprehensive_checks(&invalid_ui_def, &registry);
@@ -123,6 +125,8 @@ fn test_detailed_error_logging() {
             border_radius: None,
             text_size: None,
             opacity: None,
+            extends: None,
+            states: None,
         });
         styles
     });
@@ -147,6 +151,8 @@ fn test_detailed_error_logging() {
             border_radius: None,
             text_size: None,
             opacity: None,
+            extends: None,
+            states: None,
         });
         styles
     });
@@ -195,7 +201,7 @@ action = "open_settings"
 
 [actions.navigate_back]
 event = "click"
-action = "navigate_back"
+action = "navigate"
 params = { target = "home" }
 
 [root]
@@ -219,7 +225,7 @@ classes = ["container_style"]
     
         [root.children.bindings.hover]
         event = "hover"
-        action = "show_settings"
+        action = "open_settings"
 
     [[root.children]]
     id = "nested_container"
@@ -322,23 +328,9 @@ classes = ["good_style"]  # Valid reference
     classes = ["bad_style"]  # Will reference style with invalid hex
 "##;
 
-    // Test error recovery parsing
-    let recovery_result = loader.parse_with_error_recovery(recovery_toml);
-    match recovery_result {
-        Ok(recovered_ui_def) => {
-            bevy_log::info!("Error recovery parsing succeeded");
-            loader.log_validation_summary(&recovered_ui_def, &registry);
-            
-            // Even with recovery, some validation errors may remain
-            let validation_result = loader.validate_with_comprehensive_checks(&recovered_ui_def, &registry);
-            if let Err(error) = validation_result {
-                loader.log_validation_error_with_context(&error, &recovered_ui_def, "Error recovery test");
-            }
-        }
-        Err(error) => {
-            bevy_log::warn!("Error recovery parsing failed: {}", error);
-        }
-    }
+    // Test that checking the source reports every problem instead of stopping at the first
+    let report = loader.check_source(recovery_toml, UiDefinitionFormat::Toml);
+    assert!(report.errors().count() >= 2, "Both the empty button text and the bad color should be reported:\n{}", report);
 
     // Test 5: Deeply nested structure
     let deep_nested_toml = r##"
@@ -423,13 +415,67 @@ widget_type = { type = "Container", direction = "Column" }
     bevy_log::info!("All TOML file loading tests passed!");
 }
 
+/// Test that TOML sources go through the native loader path and report parse locations
+#[test]
+fn test_toml_loader_format() {
+    let loader = UiDefinitionLoader;
+
+    assert_eq!(UiDefinitionFormat::from_path(std::path::Path::new("ui/example_ui.toml")), UiDefinitionFormat::Toml);
+    assert_eq!(UiDefinitionFormat::from_path(std::path::Path::new("ui/layouts/main.json")), UiDefinitionFormat::Json);
+
+    // The shipped example layout: table-array children and the externally tagged color forms.
+    // Its include node is expanded by the asset loader, so the nodes are checked one by one here.
+    let source = read_example_asset("ui/example_ui.toml");
+    let document: serde_json::Value = parse_source(&source, UiDefinitionFormat::Toml)
+        .expect("example_ui.toml should parse");
+    let window: WindowConfig = serde_json::from_value(document["window"].clone()).unwrap();
+    assert_eq!(window.background_color, Some(ColorDef::Hex("#2D3748".to_string())));
+    assert_eq!(document["root"]["id"], "main_container");
+
+    let children = document["root"]["children"].as_array().unwrap();
+    let ids: Vec<_> = children.iter().map(|child| child["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["header", "content_area", "footer"]);
+    let header: WidgetNode = serde_json::from_value(children[0].clone()).unwrap();
+    assert_eq!(header.children[0].id.as_deref(), Some("title"));
+    assert_eq!(header.children[0].style.text_color, Some(ColorDef::Named("white".to_string())));
+    let footer: WidgetNode = serde_json::from_value(children[2].clone()).unwrap();
+    assert_eq!(footer.style.border_width, Some(1.0));
+    assert_eq!(footer.style.border_color, Some(ColorDef::Hex("#4A5568".to_string())));
+    assert_eq!(
+        children[1]["style"]["background_color"],
+        serde_json::json!({ "Rgba": { "r": 255, "g": 255, "b": 255, "a": 0.05 } })
+    );
+
+    // Syntax errors carry the line and column of the offending token
+    let broken_source = r##"[root]
+id = "main"
+widget_type = { type = "Container", direction = }
+"##;
+    match loader.parse_definition(broken_source, UiDefinitionFormat::Toml) {
+        Err(UiDefinitionLoaderError::TomlParse { line, column, .. }) => {
+            assert_eq!(line, 3);
+            assert!(column > 1);
+        }
+        other => panic!("Expected a TOML parse error, got {:?}", other),
+    }
+}
+
+/// Read a file from the example crate's assets folder
+fn read_example_asset(path: &str) -> String {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../whip_ui_example/assets").join(path);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e))
+}
+
 /// Helper function to create a valid UI definition for testing
 fn create_valid_ui_definition() -> UiDefinition {
     UiDefinition {
+        format_version: CURRENT_FORMAT_VERSION,
         window: Some(WindowConfig {
             size: [800.0, 600.0],
             background_color: Some(ColorDef::Hex("#2D3748".to_string())),
+            ..Default::default()
         }),
+        windows: None,
         root: WidgetNode {
             id: Some("root_container".to_string()),
             widget_type: WidgetType::Container { direction: FlexDirection::Column },
@@ -448,24 +494,32 @@ fn create_valid_ui_definition() -> UiDefinition {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![
                 WidgetNode {
                     id: Some("test_button".to_string()),
-                    widget_type: WidgetType::Button {
-                        text: "Test Button".to_string(),
-                        action: Some("navigate_home".to_string()), // Use registered action
-                    },
+                    widget_type: button("Test Button"),
                     layout: LayoutConfig::default(),
                     style: StyleConfig::default(),
                     behavior: BehaviorConfig::default(),
                     classes: None,
                     style_overrides: None,
                     bindings: None,
+                    state_bindings: None,
+                    repeat: None,
+                    when: None,
+                    localized: None,
                     children: vec![],
                 },
             ],
         },
         styles: None,
         actions: None,
+        tokens: None,
+        state: None,
+        authored: None,
     }
 }
\ No newline at end of file
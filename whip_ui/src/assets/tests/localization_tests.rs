@@ -0,0 +1,102 @@
+use super::super::*;
+use crate::widgets::blueprint::WidgetType;
+use crate::widgets::templates::expand_template_node;
+use serde_json::json;
+use std::collections::HashMap;
+
+const TOOLBAR: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Row" }
+
+[[root.children]]
+id = "greeting"
+widget_type = { type = "Text", content = { t = "greeting", args = { name = "Ada" } }, editable = false }
+
+[[root.children]]
+id = "save"
+widget_type = { type = "Button", text = { t = "toolbar.save" } }
+"##;
+
+/// Test string table parsing, fallback lookup and argument interpolation
+#[test]
+fn test_string_table_lookup() {
+    let english = StringTable::parse(r#"
+locale = "en-US"
+
+[strings]
+greeting = "Hello, {name}!"
+toolbar.save = "Save"
+braces = "{{literal}} and {missing}"
+"#).unwrap();
+    assert_eq!(english.strings["toolbar.save"], "Save", "Nested tables flatten into dotted keys");
+
+    let german = StringTable::parse(r#"
+locale = "de-DE"
+
+[strings.toolbar]
+save = "Speichern"
+"#).unwrap();
+
+    let mut localization = Localization::new();
+    localization.add_table(&english);
+    localization.add_table(&german);
+    localization.set_locale("de-DE");
+
+    let save = LocalizedText { key: "toolbar.save".to_string(), args: HashMap::new() };
+    assert_eq!(localization.format(&save), "Speichern");
+    let greeting = LocalizedText {
+        key: "greeting".to_string(),
+        args: HashMap::from([("name".to_string(), json!("Ada"))]),
+    };
+    assert_eq!(localization.format(&greeting), "Hello, Ada!", "Missing keys fall back to the fallback locale");
+    let braces = LocalizedText { key: "braces".to_string(), args: HashMap::new() };
+    assert_eq!(localization.format(&braces), "{literal} and {missing}");
+    let unknown = LocalizedText { key: "menu.quit".to_string(), args: HashMap::new() };
+    assert_eq!(localization.format(&unknown), "menu.quit", "Unknown keys show the key");
+
+    assert!(matches!(
+        StringTable::parse("locale = \"en-US\"\n[strings]\ncount = 3\n"),
+        Err(StringTableError::InvalidEntry(key)) if key == "count"
+    ));
+}
+
+/// Test that inline string table references are lifted into `localized`
+#[test]
+fn test_localized_text_is_lifted() {
+    let ui_def = UiDefinitionLoader.parse_definition(TOOLBAR, UiDefinitionFormat::Toml).unwrap();
+    assert!(!ui_def.validation_report().has_errors());
+
+    let greeting = &ui_def.root.children[0];
+    match &greeting.widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "greeting", "The key stands in until a locale is applied"),
+        other => panic!("Expected a text node, got {:?}", other),
+    }
+    let localized = greeting.localized.as_ref().unwrap();
+    assert_eq!(localized.key, "greeting");
+    assert_eq!(localized.args["name"], json!("Ada"));
+
+    // A button's label part carries the localized text, not its background shape
+    let save = &ui_def.root.children[1];
+    assert_eq!(save.localized.as_ref().map(|l| l.key.as_str()), Some("toolbar.save"));
+    let parts = expand_template_node(save);
+    assert!(parts[0].localized.is_none());
+    assert_eq!(parts[1].localized.as_ref().map(|l| l.key.as_str()), Some("toolbar.save"));
+}
+
+/// Test that malformed string table references are reported at their location
+#[test]
+fn test_invalid_localized_text_is_reported() {
+    let content = r##"
+[root]
+widget_type = { type = "Container", direction = "Row" }
+
+[[root.children]]
+widget_type = { type = "Text", content = { t = "greeting", lang = "de" }, editable = false }
+"##;
+    match UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml) {
+        Err(UiDefinitionLoaderError::Report(report)) => {
+            assert_eq!(report.errors().next().map(|d| d.path.as_str()), Some("root.children[0].widget_type.content"));
+        }
+        other => panic!("Expected a report, got {:?}", other.map(|_| ())),
+    }
+}
\ No newline at end of file
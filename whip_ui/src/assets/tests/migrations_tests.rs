@@ -0,0 +1,129 @@
+use super::super::*;
+use crate::widgets::blueprint::WidgetType;
+
+/// Test that a version 0 widget collection loads as a hierarchy
+#[test]
+fn test_widget_collection_is_migrated() {
+    let content = r##"{
+        "root": "panel",
+        "widgets": {
+            "title": { "widget_type": { "type": "Text", "content": "Title", "editable": false } },
+            "panel": {
+                "widget_type": { "type": "Container", "direction": "Column" },
+                "children": ["title", "save"]
+            },
+            "save": { "widget_type": { "type": "Button", "text": "Save" } },
+            "orphan": { "widget_type": { "type": "Text", "content": "Unused", "editable": false } }
+        }
+    }"##;
+
+    let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Json).unwrap();
+    assert_eq!(ui_def.format_version, CURRENT_FORMAT_VERSION);
+    assert_eq!(ui_def.root.id.as_deref(), Some("panel"));
+    let child_ids: Vec<_> = ui_def.root.children.iter().map(|child| child.id.as_deref()).collect();
+    assert_eq!(child_ids, vec![Some("title"), Some("save")], "Children keep their listed order");
+    assert!(matches!(ui_def.root.children[0].widget_type, WidgetType::Text { .. }));
+
+    let cyclic = r#"{ "root": "a", "widgets": { "a": { "widget_type": { "type": "Container", "direction": "Row" }, "children": ["a"] } } }"#;
+    assert!(matches!(
+        UiDefinitionLoader.parse_definition(cyclic, UiDefinitionFormat::Json),
+        Err(UiDefinitionLoaderError::Migration(MigrationError::Failed { version: 0, .. }))
+    ));
+}
+
+/// Test that upgrading TOML source rewrites it only as much as needed
+#[test]
+fn test_migrate_source() {
+    let current = "# Toolbar layout\n[root]\nwidget_type = { type = \"Container\", direction = \"Row\" }\n";
+    let (report, rewritten) = migrate_source(current, UiDefinitionFormat::Toml).unwrap();
+    assert_eq!(report.from_version, 1);
+    assert!(!report.is_outdated());
+    assert!(rewritten.is_none(), "Current files are left alone");
+
+    let stamped = format!("format_version = 0\n{}", current);
+    let (report, rewritten) = migrate_source(&stamped, UiDefinitionFormat::Toml).unwrap();
+    assert!(!report.changed());
+    let rewritten = rewritten.unwrap();
+    assert!(rewritten.starts_with("format_version = 1\n"));
+    assert!(rewritten.contains("# Toolbar layout"), "Comments survive when only the version changes");
+
+    let collection = r##"
+root = "toolbar"
+
+[widgets.toolbar]
+widget_type = { type = "Container", direction = "Row" }
+"##;
+    let (report, rewritten) = migrate_source(collection, UiDefinitionFormat::Toml).unwrap();
+    assert_eq!(report.applied.len(), 1);
+    let ui_def = UiDefinitionLoader.parse_definition(&rewritten.unwrap(), UiDefinitionFormat::Toml).unwrap();
+    assert_eq!(ui_def.root.id.as_deref(), Some("toolbar"));
+}
+
+/// Test that files from a newer version are rejected rather than misread
+#[test]
+fn test_newer_format_version_is_rejected() {
+    let content = "format_version = 99\n\n[root]\nwidget_type = { type = \"Container\", direction = \"Row\" }\n";
+    assert!(matches!(
+        UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml),
+        Err(UiDefinitionLoaderError::Migration(MigrationError::UnsupportedVersion { found: 99, .. }))
+    ));
+
+    let invalid = serde_json::json!({ "format_version": "two", "root": {} });
+    assert!(matches!(document_format_version(&invalid), Err(MigrationError::InvalidVersion(_))));
+}
+
+/// Version 1 -> 2 of a format that renamed `layout.size` to `layout.extent`, to run the chain
+/// past the steps the crate ships
+fn rename_size(document: &mut serde_json::Value) -> Result<bool, String> {
+    rename_node_field(document, "layout.size", "extent")
+}
+
+const RENAMED_SIZE: &[Migration] = &[
+    MIGRATIONS[0],
+    Migration { from: 1, description: "renamed layout.size to layout.extent", apply: rename_size },
+];
+
+/// Test that a version 1 file is upgraded through a later step that renames a field
+#[test]
+fn test_migration_chain_renames_field() {
+    let content = r##"# Toolbar layout
+format_version = 1
+
+[root]
+widget_type = { type = "Container", direction = "Row" }
+layout = { size = [300.0, 40.0], margin = 4.0 }
+
+[[root.children]]
+widget_type = { type = "Button", text = "Save" }
+layout = { size = [80.0, 30.0] }
+"##;
+    let (report, rewritten) = migrate_source_with(content, UiDefinitionFormat::Toml, RENAMED_SIZE, 2).unwrap();
+    assert_eq!((report.from_version, report.to_version), (1, 2));
+    assert_eq!(report.applied, vec!["renamed layout.size to layout.extent"]);
+    let document: serde_json::Value = toml::from_str(&rewritten.unwrap()).unwrap();
+    assert_eq!(document["format_version"], 2);
+    let layout = document["root"]["layout"].as_object().unwrap();
+    assert_eq!(layout.keys().collect::<Vec<_>>(), vec!["extent", "margin"], "Renamed fields keep their place");
+    assert_eq!(document["root"]["children"][0]["layout"]["extent"], serde_json::json!([80.0, 30.0]));
+
+    // Files the step leaves alone only have their version line replaced
+    let without_sizes = content.replace("layout = { size = [300.0, 40.0], margin = 4.0 }\n", "").replace("layout = { size = [80.0, 30.0] }\n", "");
+    let (report, rewritten) = migrate_source_with(&without_sizes, UiDefinitionFormat::Toml, RENAMED_SIZE, 2).unwrap();
+    assert!(report.is_outdated() && !report.changed());
+    assert_eq!(rewritten.unwrap(), without_sizes.replace("format_version = 1", "format_version = 2"));
+
+    // Version 0 collections go through both steps
+    let collection = "root = \"toolbar\"\n\n[widgets.toolbar]\nwidget_type = { type = \"Container\", direction = \"Row\" }\nlayout = { size = [300.0, 40.0] }\n";
+    let (report, rewritten) = migrate_source_with(collection, UiDefinitionFormat::Toml, RENAMED_SIZE, 2).unwrap();
+    assert_eq!(report.applied.len(), 2);
+    let document: serde_json::Value = toml::from_str(&rewritten.unwrap()).unwrap();
+    assert_eq!(document["root"]["layout"]["extent"], serde_json::json!([300.0, 40.0]));
+
+    let mut clash = serde_json::json!({ "root": { "layout": { "size": [1.0, 1.0], "extent": [2.0, 2.0] } } });
+    assert!(rename_node_field(&mut clash, "layout.size", "extent").is_err());
+    let mut current = serde_json::json!({ "format_version": 2, "root": {} });
+    assert!(matches!(
+        migrate_document(&mut current),
+        Err(MigrationError::UnsupportedVersion { found: 2, supported: CURRENT_FORMAT_VERSION })
+    ));
+}
\ No newline at end of file
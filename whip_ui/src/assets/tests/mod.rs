@@ -5,4 +5,49 @@ mod definitions_tests;
 mod registry_tests;
 
 #[cfg(test)]
-mod loader_tests;
\ No newline at end of file
+mod loader_tests;
+
+#[cfg(test)]
+mod includes_tests;
+
+#[cfg(test)]
+mod reload_tests;
+
+#[cfg(test)]
+mod validation_tests;
+
+#[cfg(test)]
+mod schema_tests;
+
+#[cfg(test)]
+mod export_tests;
+
+#[cfg(test)]
+mod tokens_tests;
+
+#[cfg(test)]
+mod style_cascade_tests;
+
+#[cfg(test)]
+mod widget_templates_tests;
+
+#[cfg(test)]
+mod state_tests;
+
+#[cfg(test)]
+mod dynamic_tests;
+
+#[cfg(test)]
+mod localization_tests;
+
+#[cfg(test)]
+mod extensions_tests;
+
+#[cfg(test)]
+mod migrations_tests;
+
+#[cfg(test)]
+mod unload_tests;
+
+#[cfg(test)]
+mod windows_tests;
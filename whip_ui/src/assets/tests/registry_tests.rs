@@ -1,5 +1,6 @@
 use super::super::*;
-use crate::widgets::blueprint::{ColorDef, FlexDirection};
+use crate::test_support::button;
+use crate::widgets::blueprint::{ColorDef, FlexDirection, WidgetType, LayoutConfig, StyleConfig, BehaviorConfig};
 use std::collections::HashMap;
 
 /// Test UiRegistry basic functionality
@@ -33,14 +34,14 @@ fn test_widget_type_validation() {
     let container = WidgetType::Container { direction: FlexDirection::Column };
     assert!(registry.validate_widget_type(&container).is_ok());
     
-    let button = WidgetType::Button { text: "Click me".to_string(), action: Some("navigate_home".to_string()) };
-    assert!(registry.validate_widget_type(&button).is_ok());
+    let click_button = button("Click me");
+    assert!(registry.validate_widget_type(&click_button).is_ok());
     
     let text = WidgetType::Text { content: "Hello".to_string(), editable: false };
     assert!(registry.validate_widget_type(&text).is_ok());
     
     // Test invalid widget types
-    let empty_button = WidgetType::Button { text: "".to_string(), action: None };
+    let empty_button = button("");
     assert!(registry.validate_widget_type(&empty_button).is_err());
     
     let long_text = WidgetType::Text { content: "x".repeat(10001), editable: false };
@@ -58,9 +59,9 @@ fn test_widget_children_validation() {
     assert!(registry.validate_widget_children(&container, false).is_ok());
     
     // Button cannot have children
-    let button = WidgetType::Button { text: "Click".to_string(), action: None };
-    assert!(registry.validate_widget_children(&button, false).is_ok());
-    assert!(registry.validate_widget_children(&button, true).is_err());
+    let click_button = button("Click");
+    assert!(registry.validate_widget_children(&click_button, false).is_ok());
+    assert!(registry.validate_widget_children(&click_button, true).is_err());
 }
 
 /// Test action binding validation
@@ -100,7 +101,7 @@ fn test_action_parameters_validation() {
     
     // Action with required parameters
     let mut params = HashMap::new();
-    params.insert("target".to_string(), toml::Value::String("main_panel".to_string()));
+    params.insert("target_id".to_string(), serde_json::Value::String("main_panel".to_string()));
     
     let binding_with_params = ActionBinding {
         event: "click".to_string(),
@@ -128,7 +129,7 @@ fn test_custom_state_type_registration() {
         "CustomCounter",
         "u32",
         vec!["get".to_string(), "set".to_string(), "increment".to_string()],
-        Some(toml::Value::Integer(0))
+        Some(serde_json::Value::from(0))
     );
     assert!(result.is_ok());
     assert!(registry.is_state_type_registered("CustomCounter"));
@@ -169,10 +170,7 @@ fn test_ui_definition_registry_validation() {
     
     // UI definition with invalid widget type should fail
     let mut invalid_ui_def = create_valid_ui_definition();
-    invalid_ui_def.root.widget_type = WidgetType::Button { 
-        text: "".to_string(), // Empty text should fail validation
-        action: None 
-    };
+    invalid_ui_def.root.widget_type = button("");
     assert!(invalid_ui_def.validate_with_registry(&registry).is_err());
 }
 
@@ -207,10 +205,13 @@ fn test_registry_configuration() {
 /// Helper function to create a valid UI definition for testing
 fn create_valid_ui_definition() -> UiDefinition {
     UiDefinition {
+        format_version: CURRENT_FORMAT_VERSION,
         window: Some(WindowConfig {
             size: [800.0, 600.0],
             background_color: Some(ColorDef::Hex("#2D3748".to_string())),
+            ..Default::default()
         }),
+        windows: None,
         root: WidgetNode {
             id: Some("root_container".to_string()),
             widget_type: WidgetType::Container { direction: FlexDirection::Column },
@@ -229,24 +230,32 @@ fn create_valid_ui_definition() -> UiDefinition {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![
                 WidgetNode {
                     id: Some("test_button".to_string()),
-                    widget_type: WidgetType::Button {
-                        text: "Test Button".to_string(),
-                        action: Some("navigate_home".to_string()), // Use registered action
-                    },
+                    widget_type: button("Test Button"),
                     layout: LayoutConfig::default(),
                     style: StyleConfig::default(),
                     behavior: BehaviorConfig::default(),
                     classes: None,
                     style_overrides: None,
                     bindings: None,
+                    state_bindings: None,
+                    repeat: None,
+                    when: None,
+                    localized: None,
                     children: vec![],
                 },
             ],
         },
         styles: None,
         actions: None,
+        tokens: None,
+        state: None,
+        authored: None,
     }
 }
\ No newline at end of file
@@ -0,0 +1,153 @@
+use super::super::*;
+use crate::test_support::setup_world;
+use super::super::reload::replace_widget_text;
+use crate::gui_framework::events::YrsTextChanged;
+use crate::layout::{TaffyResource, UiNode};
+use crate::widgets::components::{Widget, WidgetText};
+use crate::YrsDocResource;
+use bevy_asset::{AssetEvent, Assets};
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_transform::prelude::Transform;
+use yrs::{GetString, Transact};
+
+const SCENE: &str = r##"
+[root]
+id = "scene"
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "title"
+widget_type = { type = "Text", content = "Scene 1", editable = false }
+
+[[root.children]]
+id = "notes"
+widget_type = { type = "Text", content = "Draft", editable = true }
+
+[[root.children]]
+id = "handle"
+widget_type = { type = "Shape", shape_type = "Circle" }
+layout = { position = [40.0, 60.0, 1.0], size = [20.0, 20.0] }
+behavior = { position_control = "Manual" }
+
+[[root.children]]
+id = "marker"
+widget_type = { type = "Text", content = "Marker", editable = false }
+"##;
+
+/// Spawn `source` as a loaded asset, giving every widget a Taffy node like the layout system does
+fn spawn_scene(world: &mut World, yrs_res: &YrsDocResource, source: &str) -> Entity {
+    let ui_def = UiDefinitionLoader.parse_definition(source, UiDefinitionFormat::Toml).unwrap();
+    let root = {
+        let mut commands = world.commands();
+        spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+    };
+    world.flush();
+
+    let widgets: Vec<Entity> = world.query_filtered::<Entity, With<Widget>>().iter(world).collect();
+    for entity in widgets {
+        let taffy_node = world.resource::<TaffyResource>().with_tree(|tree| tree.new_leaf(taffy::Style::default()).unwrap());
+        world.entity_mut(entity).insert(UiNode { taffy_node: Some(taffy_node), needs_layout: false });
+    }
+
+    let handle = world.resource_mut::<Assets<UiDefinition>>().add(ui_def);
+    world.resource_mut::<LoadingUiAssets>().spawned.push(SpawnedUi {
+        handle,
+        request: LoadUiRequest::new("ui/scene.toml"),
+        root,
+    });
+    root
+}
+
+/// Swap the spawned definition for `source` and run the reload system
+fn reload_scene(world: &mut World, source: &str) {
+    let ui_def = UiDefinitionLoader.parse_definition(source, UiDefinitionFormat::Toml).unwrap();
+    let id = world.resource::<LoadingUiAssets>().spawned[0].handle.id();
+    world.resource_mut::<Assets<UiDefinition>>().insert(id, ui_def);
+    world.send_event(AssetEvent::Modified { id });
+    world.run_system_once(ui_asset_reload_system).unwrap();
+}
+
+fn widget_named(world: &mut World, id: &str) -> Option<Entity> {
+    world.query::<(Entity, &Widget)>().iter(world)
+        .find(|(_, widget)| widget.id == id)
+        .map(|(entity, _)| entity)
+}
+
+fn yrs_text(yrs_res: &YrsDocResource, entity: Entity) -> Option<String> {
+    let text_map = yrs_res.text_map.lock().unwrap();
+    text_map.get(&entity).map(|text_ref| text_ref.get_string(&yrs_res.doc.transact()))
+}
+
+fn taffy_node_count(world: &World) -> usize {
+    world.resource::<TaffyResource>().with_tree(|tree| tree.total_node_count())
+}
+
+/// Test that matched widgets are patched in place, keeping edited text and dragged positions
+/// while static text follows the file
+#[test]
+fn test_reload_patches_matched_widgets_in_place() {
+    let (mut world, yrs_res) = setup_world();
+    let root = spawn_scene(&mut world, &yrs_res, SCENE);
+    let title = widget_named(&mut world, "title").unwrap();
+    let notes = widget_named(&mut world, "notes").unwrap();
+    let handle = widget_named(&mut world, "handle").unwrap();
+
+    // The user edits the notes and drags the handle
+    replace_widget_text(&yrs_res, notes, "Draft with notes");
+    world.get_mut::<Transform>(handle).unwrap().translation.x += 25.0;
+    let dragged = world.get::<Transform>(handle).unwrap().translation;
+
+    let reloaded = SCENE
+        .replace("\"Scene 1\"", "\"Scene 2\"")
+        .replace("content = \"Draft\"", "content = \"Outline\"")
+        .replace("position = [40.0, 60.0, 1.0]", "position = [80.0, 60.0, 1.0]");
+    reload_scene(&mut world, &reloaded);
+
+    assert_eq!(widget_named(&mut world, "scene"), Some(root), "Matched widgets keep their entity");
+    assert_eq!(widget_named(&mut world, "title"), Some(title));
+    assert_eq!(widget_named(&mut world, "notes"), Some(notes));
+    assert_eq!(widget_named(&mut world, "handle"), Some(handle));
+    assert_eq!(world.resource::<LoadingUiAssets>().spawned[0].root, root);
+
+    assert_eq!(yrs_text(&yrs_res, title).as_deref(), Some("Scene 2"));
+    assert_eq!(world.get::<WidgetText>(title).unwrap().content, "Scene 2");
+    assert_eq!(yrs_text(&yrs_res, notes).as_deref(), Some("Draft with notes"), "Editable text belongs to the user");
+    assert_eq!(world.get::<Transform>(handle).unwrap().translation, dragged, "Dragged widgets stay where they were put");
+
+    let changed: Vec<_> = world.resource_mut::<Events<YrsTextChanged>>().drain().map(|event| event.entity).collect();
+    assert_eq!(changed, [title]);
+    assert_eq!(taffy_node_count(&world), 5, "No widget was removed");
+}
+
+/// Test that widgets missing from the new definition, or whose type changed, are despawned
+/// along with their Taffy nodes and yrs text, and that new nodes are spawned
+#[test]
+fn test_reload_despawns_removed_and_retyped_widgets() {
+    let (mut world, yrs_res) = setup_world();
+    spawn_scene(&mut world, &yrs_res, SCENE);
+    let title = widget_named(&mut world, "title").unwrap();
+    let handle = widget_named(&mut world, "handle").unwrap();
+    let marker = widget_named(&mut world, "marker").unwrap();
+    assert_eq!(taffy_node_count(&world), 5);
+
+    let marker_node = SCENE.find("[[root.children]]\nid = \"marker\"").unwrap();
+    let reloaded = format!(
+        "{}{}",
+        &SCENE[..marker_node],
+        "[[root.children]]\nid = \"footer\"\nwidget_type = { type = \"Text\", content = \"The end\", editable = false }\n"
+    ).replace("type = \"Text\", content = \"Scene 1\", editable = false", "type = \"Shape\", shape_type = \"Rectangle\"");
+    reload_scene(&mut world, &reloaded);
+
+    assert!(world.get_entity(marker).is_err(), "Removed widgets are despawned");
+    assert_eq!(yrs_text(&yrs_res, marker), None);
+    assert!(world.get_entity(title).is_err(), "A widget whose type changed is respawned");
+    assert_eq!(yrs_text(&yrs_res, title), None);
+    assert_eq!(widget_named(&mut world, "handle"), Some(handle));
+
+    let footer = widget_named(&mut world, "footer").unwrap();
+    assert_eq!(yrs_text(&yrs_res, footer).as_deref(), Some("The end"));
+    assert!(widget_named(&mut world, "title").is_some_and(|retyped| retyped != title));
+    assert_eq!(taffy_node_count(&world), 3, "The Taffy nodes of the marker and the old title are released");
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use super::super::*;
+
+/// Test that registry actions, their parameters and event names end up in the schema
+#[test]
+fn test_schema_uses_registry_actions_and_events() {
+    let registry = UiRegistry::new();
+    let schema = ui_definition_schema(&registry);
+    let binding = &schema["definitions"]["ActionBinding"];
+
+    let actions = binding["properties"]["action"]["enum"].as_array().unwrap();
+    assert!(actions.contains(&serde_json::json!("navigate")));
+    assert_eq!(actions.len(), registry.valid_actions.len());
+
+    let events = binding["properties"]["event"]["enum"].as_array().unwrap();
+    assert_eq!(events.len(), VALID_EVENTS.len());
+
+    // `update_text` requires both of its String parameters
+    let rule = binding["allOf"].as_array().unwrap().iter()
+        .find(|rule| rule["if"]["properties"]["action"]["const"] == "update_text")
+        .unwrap();
+    let params = &rule["then"]["properties"]["params"];
+    assert_eq!(params["required"], serde_json::json!(["target_id", "text"]));
+    assert_eq!(params["properties"]["text"]["type"], "string");
+}
+
+/// Test that registered custom widget types become `Custom` variants of the widget type schema
+#[test]
+fn test_schema_includes_registered_widget_types() {
+    let mut registry = UiRegistry::new();
+    registry.register_widget_type("ColorPicker", WidgetTypeInfo {
+        display_name: "Color Picker".to_string(),
+        asset_path: None,
+        required_properties: vec!["initial_color".to_string()],
+        optional_properties: vec!["show_alpha".to_string()],
+        can_have_children: false,
+    });
+
+    let schema = ui_definition_schema(&registry);
+    let variants = schema["definitions"]["WidgetType"]["oneOf"].as_array().unwrap();
+
+    let picker = variants.iter()
+        .find(|variant| variant["properties"]["component"]["const"] == "ColorPicker")
+        .expect("Registered widget type should be in the schema");
+    assert_eq!(picker["properties"]["properties"]["required"], serde_json::json!(["initial_color"]));
+
+    let container = variants.iter()
+        .find(|variant| variant["properties"]["type"]["const"] == "Container")
+        .unwrap();
+    assert_eq!(container["required"], serde_json::json!(["type", "direction"]));
+
+    // Strict registries reject unregistered components, so there is no open `Custom` variant
+    assert!(!variants.iter().any(|variant| variant["properties"]["component"]["type"] == "string"));
+
+    let lenient = UiRegistry::with_config(UiRegistryConfig {
+        allow_custom_widgets: true,
+        ..UiRegistryConfig::default()
+    });
+    let schema = ui_definition_schema(&lenient);
+    let variants = schema["definitions"]["WidgetType"]["oneOf"].as_array().unwrap();
+    assert!(variants.iter().any(|variant| variant["properties"]["component"]["type"] == "string"));
+}
+
+/// Test that the schema and the validators agree on widget ids, global action events and
+/// the shape of `style_overrides`
+#[test]
+fn test_schema_matches_validation_rules() {
+    let schema = ui_definition_schema(&UiRegistry::new());
+    let node = &schema["definitions"]["Node"]["properties"];
+    assert_eq!(node["id"]["pattern"], WIDGET_ID_PATTERN);
+    assert_eq!(node["id"]["maxLength"], WIDGET_ID_MAX_LENGTH);
+    assert_eq!(node["style_overrides"]["$ref"], "#/definitions/StyleOverrides");
+
+    let pattern = regex::Regex::new(node["id"]["pattern"].as_str().unwrap()).unwrap();
+    for id in ["title", "_private", "row-2", "1st", "naïve", "two words", ""] {
+        assert_eq!(pattern.is_match(id), widget_id_problem(id).is_none(), "Schema and validation disagree on '{}'", id);
+    }
+    assert!(widget_id_problem(&"a".repeat(WIDGET_ID_MAX_LENGTH + 1)).is_some());
+
+    let source = r##"
+[actions.close_panel]
+event = "close"
+action = "navigate"
+
+[actions.typo]
+event = "clik"
+action = "navigate"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+"##;
+    let ui_def: UiDefinition = toml::from_str(source).unwrap();
+    let paths: Vec<_> = ui_def.validation_report().errors().map(|error| error.path.clone()).collect();
+    assert_eq!(paths, ["actions.typo.event"], "Global actions accept every event bindings do");
+}
\ No newline at end of file
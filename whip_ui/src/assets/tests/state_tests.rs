@@ -0,0 +1,124 @@
+use super::super::*;
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+use serde_json::json;
+
+const EDITOR: &str = r##"
+[state]
+accent = "#3182CE"
+
+[state.project]
+title = { type = "String", default = "Untitled" }
+dirty = false
+zoom = { type = "Number", default = 1.0 }
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+style = { background_color = { bind = "accent" } }
+
+[[root.children]]
+id = "title"
+widget_type = { type = "Text", content = { bind = "project.title" }, editable = false }
+behavior = { visible = { bind = "project.dirty" } }
+"##;
+
+/// Test that inline bindings are lifted into `state_bindings` and replaced by the initial values
+#[test]
+fn test_inline_bindings_are_lifted() {
+    let ui_def = UiDefinitionLoader.parse_definition(EDITOR, UiDefinitionFormat::Toml).unwrap();
+
+    let state = ui_def.state.as_ref().unwrap();
+    assert_eq!(state["project.title"].state_type, "String");
+    assert_eq!(state["project.dirty"].state_type, "Boolean", "Literal values infer their type");
+    assert_eq!(state["accent"].state_type, "String");
+
+    assert_eq!(ui_def.root.style.background_color, Some(ColorDef::Hex("#3182CE".to_string())));
+    assert_eq!(
+        ui_def.root.state_bindings.as_ref().and_then(|bindings| bindings.get("style.background_color")).map(String::as_str),
+        Some("accent")
+    );
+
+    let title = &ui_def.root.children[0];
+    match &title.widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "Untitled"),
+        other => panic!("Expected a text node, got {:?}", other),
+    }
+    assert_eq!(title.behavior.visible, Some(false));
+    let bindings = title.state_bindings.as_ref().unwrap();
+    assert_eq!(bindings["widget_type.content"], "project.title");
+    assert_eq!(bindings["behavior.visible"], "project.dirty");
+
+    assert!(!ui_def.validation_report().has_errors());
+}
+
+/// Test that bindings to undeclared values, mismatched types and unbindable properties are reported
+#[test]
+fn test_invalid_bindings_are_reported() {
+    let unbindable = r##"
+[state]
+label = "Hi"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+style = { border_width = { bind = "label" } }
+"##;
+    match UiDefinitionLoader.parse_definition(unbindable, UiDefinitionFormat::Toml) {
+        Err(UiDefinitionLoaderError::Report(report)) => {
+            assert_eq!(report.errors().next().map(|d| d.path.as_str()), Some("root.style.border_width"));
+        }
+        other => panic!("Expected a report, got {:?}", other.map(|_| ())),
+    }
+
+    let mismatched = r##"
+[state]
+label = "Hi"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+behavior = { visible = { bind = "label" } }
+
+[[root.children]]
+widget_type = { type = "Text", content = { bind = "missing" }, editable = false }
+"##;
+    let ui_def = UiDefinitionLoader.parse_definition(mismatched, UiDefinitionFormat::Toml).unwrap();
+    let report = ui_def.validation_report();
+    let paths: Vec<&str> = report.errors().map(|d| d.path.as_str()).collect();
+    assert!(paths.contains(&"root.state_bindings.behavior.visible"), "{:?}", paths);
+    assert!(paths.contains(&"root.children[0].state_bindings.widget_type.content"), "{:?}", paths);
+}
+
+/// Test the registered operations on state values
+#[test]
+fn test_state_operations() {
+    let registry = UiRegistry::new();
+    let ui_def = UiDefinitionLoader.parse_definition(EDITOR, UiDefinitionFormat::Toml).unwrap();
+    let mut state = UiState::new();
+    state.declare_definition(&ui_def);
+    state.take_changed();
+
+    state.apply("project.dirty", &StateOperation::Toggle, &registry).unwrap();
+    assert_eq!(state.get("project.dirty"), Some(&json!(true)));
+    assert!(state.is_changed("project.dirty"));
+
+    state.apply("project.zoom", &StateOperation::Increment, &registry).unwrap();
+    assert_eq!(state.get("project.zoom").and_then(|v| v.as_f64()), Some(2.0));
+
+    state.apply("project.title", &StateOperation::Append(" *".to_string()), &registry).unwrap();
+    assert_eq!(state.get("project.title"), Some(&json!("Untitled *")));
+
+    assert!(matches!(
+        state.apply("project.title", &StateOperation::Toggle, &registry),
+        Err(StateError::InvalidOperation { .. })
+    ));
+    assert!(matches!(
+        state.apply("project.dirty", &StateOperation::Set(json!("yes")), &registry),
+        Err(StateError::TypeMismatch { .. })
+    ));
+    assert_eq!(
+        state.set("project.missing", json!(1)),
+        Err(StateError::UnknownPath("project.missing".to_string()))
+    );
+
+    // Re-declaring keeps the live value, as on hot reload
+    state.declare_definition(&ui_def);
+    assert_eq!(state.get("project.dirty"), Some(&json!(true)));
+}
\ No newline at end of file
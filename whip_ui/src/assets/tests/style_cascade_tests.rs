@@ -0,0 +1,82 @@
+use super::super::*;
+use crate::widgets::blueprint::ColorDef;
+
+const LAYOUT: &str = r##"
+[styles.base_button]
+background_color = "#FF0000"
+text_size = 14.0
+border_radius = 4.0
+states = { hover = { background_color = "#0000FF", opacity = 0.8 } }
+
+[styles.primary_button]
+extends = ["base_button"]
+background_color = "#00FF00"
+states = { hover = { background_color = "#FFFF00" } }
+
+[styles.wide]
+border_radius = 8.0
+
+[root]
+id = "root"
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "save"
+widget_type = { type = "Text", content = "Save", editable = false }
+classes = ["primary_button", "wide"]
+style = { text_size = 18.0 }
+style_overrides = { opacity = 0.5 }
+"##;
+
+/// Test that classes extend their parents and cascade before the node's style and overrides
+#[test]
+fn test_class_extends_and_cascade_order() {
+    let ui_def: UiDefinition = toml::from_str(LAYOUT).unwrap();
+    assert!(ui_def.validate().is_ok());
+
+    let class = ui_def.resolve_style_class("primary_button").unwrap();
+    assert_eq!(class.background_color, Some(ColorDef::Hex("#00FF00".to_string())));
+    assert_eq!(class.text_size, Some(14.0), "Values not set by the class come from its parent");
+
+    let style = ui_def.resolve_node_style(&ui_def.root.children[0]);
+    assert_eq!(style.background_color, Some(ColorDef::Hex("#00FF00".to_string())));
+    assert_eq!(style.border_radius, Some(8.0), "Later classes win over earlier ones");
+    assert_eq!(style.text_size, Some(18.0), "The node's own style wins over its classes");
+    assert_eq!(style.opacity, Some(0.5));
+
+    // State variants merge state by state along the same cascade
+    let hover = style.states.as_ref().and_then(|states| states.hover.as_ref()).unwrap();
+    assert_eq!(hover.background_color, Some(ColorDef::Hex("#FFFF00".to_string())));
+    assert_eq!(hover.opacity, Some(0.8));
+
+    let collection = ui_def.to_widget_collection();
+    assert_eq!(collection.widgets["save"].style.text_size, Some(18.0));
+}
+
+/// Test that broken inheritance is reported at the class's `extends`
+#[test]
+fn test_class_inheritance_errors() {
+    let content = r##"
+[styles.a]
+extends = ["b"]
+
+[styles.b]
+extends = ["a", "missing"]
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+classes = ["a"]
+style_overrides = { extends = ["b"] }
+"##;
+
+    let ui_def: UiDefinition = toml::from_str(content).unwrap();
+    let report = ui_def.validation_report();
+    let errors: Vec<_> = report.errors().collect();
+
+    assert!(errors.iter().any(|d| d.path == "styles.a.extends" && d.message.contains("a -> b -> a")));
+    assert!(errors.iter().any(|d| d.path == "styles.b.extends" && d.error == Some(UiDefinitionError::UnknownStyleClass("missing".to_string()))));
+    assert!(errors.iter().any(|d| d.path == "root.style_overrides.extends"));
+
+    // Resolution still terminates and keeps what it can
+    assert!(ui_def.resolve_style_class("a").is_some());
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use super::super::*;
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+use std::path::Path;
+
+const THEME: &str = r##"
+[tokens]
+primary = "#3182CE"
+accent = "$primary"
+spacing_md = 12
+button_size = [120.0, 40.0]
+"##;
+
+/// Test that token references, aliases and single-number spacing resolve at parse time
+#[test]
+fn test_local_tokens_resolve() {
+    let content = r##"
+[tokens]
+primary = "#3182CE"
+accent = "$primary"
+spacing_md = 12
+heading = 24.0
+
+[styles.title]
+text_size = "$heading"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+layout = { padding = "$spacing_md", size = ["$heading", 100.0] }
+style = { background_color = "$accent" }
+
+[[root.children]]
+id = "price"
+widget_type = { type = "Text", content = "$$5.00", editable = false }
+style = { text_color = "$primary" }
+"##;
+
+    let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+    assert_eq!(ui_def.root.style.background_color, Some(ColorDef::Hex("#3182CE".to_string())));
+    assert_eq!(ui_def.root.layout.padding.as_ref().map(|p| (p.top, p.left)), Some((12.0, 12.0)));
+    assert_eq!(ui_def.root.layout.size.map(|s| s.x), Some(24.0));
+    assert_eq!(ui_def.styles.as_ref().unwrap()["title"].text_size, Some(24.0));
+
+    // Text content is never treated as a token
+    match &ui_def.root.children[0].widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "$$5.00"),
+        other => panic!("Expected a Text widget, got {:?}", other),
+    }
+
+    // The effective token set is kept, with aliases resolved
+    assert_eq!(ui_def.tokens.as_ref().unwrap()["accent"], serde_json::json!("#3182CE"));
+}
+
+/// Test that unknown tokens and tokens of the wrong kind are reported with their paths
+#[test]
+fn test_token_errors_have_paths() {
+    let content = r##"
+[tokens]
+primary = "#3182CE"
+loop_a = "$loop_b"
+loop_b = "$loop_a"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+style = { background_color = "$secondary", text_size = "$primary" }
+"##;
+
+    let report = UiDefinitionLoader.check_source(content, UiDefinitionFormat::Toml);
+    let errors: Vec<_> = report.errors().collect();
+
+    let unknown = errors.iter().find(|d| d.path == "root.style.background_color").unwrap();
+    assert!(unknown.message.contains("Unknown token '$secondary'"));
+    let mismatch = errors.iter().find(|d| d.path == "root.style.text_size").unwrap();
+    assert!(mismatch.message.contains("is a color, which cannot be used for 'text_size'"));
+    assert!(errors.iter().any(|d| d.message.contains("Token alias cycle")));
+
+    // Errors point at the offending line
+    assert_eq!(mismatch.location.map(|l| l.line), Some(9));
+}
+
+/// Test that tokens from included token files are shared and overridden by local ones
+#[test]
+fn test_token_file_include() {
+    let content = r##"
+[tokens]
+include = "../theme.toml"
+spacing_md = 16
+
+[root]
+widget_type = { type = "Button", text = "OK", background_color = "$accent", size = "$button_size" }
+layout = { margin = "$spacing_md" }
+"##;
+
+    let mut document: serde_json::Value = toml::from_str(content).unwrap();
+    assert!(document_uses_tokens(&document));
+
+    let layout_path = Path::new("ui/layouts/main.toml");
+    let mut files = Vec::new();
+    collect_token_files(&document, layout_path, &mut files);
+    assert_eq!(files, vec![Path::new("ui/theme.toml").to_path_buf()]);
+
+    let mut resolver = TokenResolver::default();
+    resolver.insert_source("ui/theme.toml", toml::from_str(THEME).unwrap());
+    let mut report = ValidationReport::new();
+    resolver.resolve_document(&mut document, layout_path, &mut report);
+    assert!(!report.has_errors(), "{}", report);
+
+    let ui_def: UiDefinition = serde_json::from_value(document).unwrap();
+    assert_eq!(ui_def.root.layout.margin.as_ref().map(|m| m.bottom), Some(16.0));
+    match &ui_def.root.widget_type {
+        WidgetType::Button { background_color, size, .. } => {
+            assert_eq!(*background_color, Some(ColorDef::Hex("#3182CE".to_string())));
+            assert_eq!(size.map(|s| s.y), Some(40.0));
+        }
+        other => panic!("Expected a Button widget, got {:?}", other),
+    }
+
+    // A token file that was never loaded is an error, not a silent fallback
+    let mut document: serde_json::Value = toml::from_str(content).unwrap();
+    let mut report = ValidationReport::new();
+    TokenResolver::default().resolve_document(&mut document, layout_path, &mut report);
+    assert!(report.errors().any(|d| d.path == "tokens.include"));
+}
\ No newline at end of file
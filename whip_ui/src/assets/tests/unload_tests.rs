@@ -0,0 +1,123 @@
+use super::super::*;
+use crate::test_support::setup_world;
+use crate::gui_framework::components::Text;
+use crate::gui_framework::plugins::core::text_despawn_cleanup_system;
+use crate::layout::{TaffyResource, UiNode};
+use crate::widgets::components::Widget;
+use crate::{RemovedTextResource, YrsDocResource};
+use bevy_asset::Handle;
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+
+const PANEL: &str = r##"
+[root]
+id = "panel"
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "notes"
+widget_type = { type = "Text", content = "Draft", editable = true }
+
+[[root.children]]
+id = "handle"
+widget_type = { type = "Shape", shape_type = "Circle" }
+layout = { position = [40.0, 60.0, 1.0], size = [20.0, 20.0] }
+behavior = { position_control = "Manual" }
+"##;
+
+/// Spawn `PANEL` as if it had been loaded by `request`
+fn spawn_panel(world: &mut World, yrs_res: &YrsDocResource, request: LoadUiRequest, handle: u128) -> Entity {
+    let ui_def: UiDefinition = toml::from_str(PANEL).unwrap();
+    let root = {
+        let mut commands = world.commands();
+        spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+    };
+    world.flush();
+
+    let taffy_node = world.resource::<TaffyResource>().with_tree(|tree| tree.new_leaf(taffy::Style::default()).unwrap());
+    world.entity_mut(root).insert(UiNode { taffy_node: Some(taffy_node), needs_layout: false });
+    world.resource_mut::<LoadingUiAssets>().spawned.push(SpawnedUi {
+        handle: Handle::weak_from_u128(handle),
+        request,
+        root,
+    });
+    root
+}
+
+fn widget_count(world: &mut World) -> usize {
+    world.query::<&Widget>().iter(world).count()
+}
+
+/// Test that unloading a scope despawns its whole tree and nothing else
+#[test]
+fn test_unload_despawns_tree_and_resources() {
+    let (mut world, yrs_res) = setup_world();
+    let script = spawn_panel(&mut world, &yrs_res, LoadUiRequest::new("ui/script.toml").with_scope("main"), 1);
+    let sidebar = spawn_panel(&mut world, &yrs_res, LoadUiRequest::new("ui/sidebar.toml"), 2);
+    let widgets_per_tree = widget_count(&mut world) / 2;
+    assert_eq!(yrs_res.text_map.lock().unwrap().len(), 2);
+
+    world.send_event(UnloadUiRequest::scope("main"));
+    world.run_system_once(ui_asset_unload_system).unwrap();
+
+    assert!(world.get_entity(script).is_err());
+    assert!(world.get_entity(sidebar).is_ok());
+    assert_eq!(widget_count(&mut world), widgets_per_tree, "Manually positioned widgets go with their tree");
+    assert_eq!(yrs_res.text_map.lock().unwrap().len(), 1);
+    assert_eq!(world.resource::<TaffyResource>().with_tree(|tree| tree.total_node_count()), 1);
+
+    let loading_assets = world.resource::<LoadingUiAssets>();
+    assert_eq!(loading_assets.spawned.len(), 1);
+    assert_eq!(loading_assets.matching(&UiTarget::Root(sidebar)).count(), 1);
+}
+
+/// Test that the text of unloaded trees is handed to the renderer to free its GPU resources
+#[test]
+fn test_unload_releases_text_render_resources() {
+    let (mut world, yrs_res) = setup_world();
+    world.init_resource::<RemovedTextResource>();
+    spawn_panel(&mut world, &yrs_res, LoadUiRequest::new("ui/script.toml").with_scope("main"), 1);
+    spawn_panel(&mut world, &yrs_res, LoadUiRequest::new("ui/sidebar.toml"), 2);
+    let texts: Vec<Entity> = world.query_filtered::<Entity, With<Text>>().iter(&world).collect();
+    assert_eq!(texts.len(), 2);
+
+    world.send_event(UnloadUiRequest::scope("main"));
+    world.run_system_once(ui_asset_unload_system).unwrap();
+    world.run_system_once(text_despawn_cleanup_system).unwrap();
+
+    let unloaded: Vec<Entity> = texts.into_iter().filter(|&entity| world.get_entity(entity).is_err()).collect();
+    assert_eq!(unloaded.len(), 1);
+    assert_eq!(world.resource::<RemovedTextResource>().0, unloaded);
+}
+
+/// Test that a replacement takes over the replaced tree's parent and scope
+#[test]
+fn test_replace_requests_the_replacement() {
+    let (mut world, yrs_res) = setup_world();
+    let parent = world.spawn_empty().id();
+    let script = spawn_panel(&mut world, &yrs_res, LoadUiRequest::new("ui/script.toml").with_parent(parent).with_scope("main"), 1);
+
+    world.send_event(ReplaceUiRequest::scope("main", "ui/storyboard.toml"));
+    world.run_system_once(ui_asset_unload_system).unwrap();
+
+    assert!(world.get_entity(script).is_err());
+    let requests: Vec<_> = world.resource_mut::<Events<LoadUiRequest>>().drain().collect();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].asset_path, "ui/storyboard.toml");
+    assert_eq!(requests[0].parent, Some(parent));
+    assert_eq!(requests[0].scope.as_deref(), Some("main"));
+}
+
+/// Test that unloading cancels loads that have not finished yet
+#[test]
+fn test_unload_cancels_pending_loads() {
+    let mut loading_assets = LoadingUiAssets::default();
+    loading_assets.pending_loads.insert(Handle::weak_from_u128(1), LoadUiRequest::new("ui/script.toml").with_scope("main"));
+    loading_assets.pending_loads.insert(Handle::weak_from_u128(2), LoadUiRequest::new("ui/sidebar.toml"));
+
+    assert!(loading_assets.take_matching(&UiTarget::Scope("main".to_string())).is_empty());
+    assert_eq!(loading_assets.pending_loads.len(), 1);
+    assert!(loading_assets.take_matching(&UiTarget::Asset("ui/sidebar.toml".to_string())).is_empty());
+    assert!(loading_assets.pending_loads.is_empty());
+}
\ No newline at end of file
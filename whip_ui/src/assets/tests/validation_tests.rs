@@ -0,0 +1,110 @@
+use super::super::*;
+
+const INVALID_TOML: &str = r##"
+[window]
+size = [0.0, 600.0]
+
+[root]
+id = "main"
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "title"
+widget_type = { type = "Text", content = "Title", editable = false }
+
+[[root.children]]
+id = "faded"
+widget_type = { type = "Text", content = "Faded", editable = false }
+style = { opacity = 1.5, text_size = -2.0 }
+"##;
+
+/// Test that every error is collected with its node path instead of stopping at the first
+#[test]
+fn test_report_accumulates_errors_with_paths() {
+    let ui_def: UiDefinition = toml::from_str(INVALID_TOML).unwrap();
+    let report = ui_def.validation_report();
+
+    assert_eq!(report.error_count(), 3, "Expected every error to be reported: {}", report);
+    let paths: Vec<&str> = report.errors().map(|d| d.path.as_str()).collect();
+    assert!(paths.contains(&"window.size"));
+    assert!(paths.contains(&"root.children[1].style.opacity"));
+    assert!(paths.contains(&"root.children[1].style.text_size"));
+
+    // The fail-fast API still reports the first error
+    assert_eq!(ui_def.validate().err(), report.first_error().cloned());
+}
+
+/// Test that diagnostics are located in TOML sources, including array-of-tables entries
+#[test]
+fn test_check_source_locates_toml_diagnostics() {
+    let report = UiDefinitionLoader.check_source(INVALID_TOML, UiDefinitionFormat::Toml);
+
+    let window = report.errors().find(|d| d.path == "window.size").unwrap();
+    assert_eq!(window.location.map(|l| l.line), Some(3));
+
+    // Inline table fields resolve to the line declaring the table
+    let opacity = report.errors().find(|d| d.path == "root.children[1].style.opacity").unwrap();
+    assert_eq!(opacity.location.map(|l| l.line), Some(16));
+
+    let rendered = report.to_string();
+    assert!(rendered.contains("error: "));
+    assert!(rendered.contains(":16:"), "Rendered report should point at the source line: {}", rendered);
+}
+
+/// Test that parse errors become located diagnostics
+#[test]
+fn test_check_source_reports_parse_errors() {
+    let report = UiDefinitionLoader.check_source("{\n  \"root\": {\n    \"id\": \n}", UiDefinitionFormat::Json);
+    assert!(report.has_errors());
+    assert_eq!(report.diagnostics()[0].location.map(|l| l.line), Some(4));
+}
+
+/// Test that multi-line strings and dotted keys do not throw off TOML locations
+#[test]
+fn test_check_source_locates_past_multiline_strings() {
+    let source = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "notes"
+widget_type = { type = "Text", editable = false, content = """
+[[root.children]]
+style = { opacity = 2.0 }
+""" }
+
+[[root.children]]
+id = "faded"
+widget_type = { type = "Text", content = "Faded", editable = false }
+style.text_size = 12.0
+style.opacity = 1.5
+"##;
+    let report = UiDefinitionLoader.check_source(source, UiDefinitionFormat::Toml);
+    let paths: Vec<&str> = report.errors().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, ["root.children[1].style.opacity"]);
+    assert_eq!(report.diagnostics()[0].location.map(|l| (l.line, l.column)), Some((16, 7)));
+}
+
+/// Test that diagnostics are located in JSON sources, down to array entries
+#[test]
+fn test_check_source_locates_json_diagnostics() {
+    let source = r##"{
+  "root": {
+    "widget_type": { "type": "Container", "direction": "Column" },
+    "children": [
+      { "id": "title", "widget_type": { "type": "Text", "content": "Title", "editable": false } },
+      {
+        "id": "faded",
+        "widget_type": { "type": "Text", "content": "Faded", "editable": false },
+        "style": {
+          "opacity":
+            1.5
+        }
+      }
+    ]
+  }
+}"##;
+    let report = UiDefinitionLoader.check_source(source, UiDefinitionFormat::Json);
+    let opacity = report.errors().find(|d| d.path == "root.children[1].style.opacity").unwrap();
+    assert_eq!(opacity.location.map(|l| (l.line, l.column)), Some((11, 13)));
+}
\ No newline at end of file
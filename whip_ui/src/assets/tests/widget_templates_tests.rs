@@ -0,0 +1,159 @@
+use super::super::*;
+use crate::widgets::blueprint::{ColorDef, WidgetType};
+
+const CARD_TEMPLATE: &str = r##"
+[template]
+name = "Card"
+description = "Titled panel"
+
+[template.params]
+title = {}
+width = { default = 200.0 }
+accent = { default = "#3182CE" }
+
+[template.root]
+widget_type = { type = "Container", direction = "Column" }
+layout = { size = ["{{width}}", 120.0] }
+style = { background_color = "{{accent}}" }
+
+[[template.root.children]]
+id = "title"
+widget_type = { type = "Text", content = "Title: {{title}}", editable = false }
+"##;
+
+const TOOLBAR_TEMPLATE: &str = r##"
+[template]
+name = "Toolbar"
+
+[template.params]
+label = { default = "Tools" }
+
+[template.root]
+widget_type = { type = "Container", direction = "Row" }
+
+[[template.root.children]]
+id = "card"
+widget_type = { type = "Custom", component = "Card", properties = { title = "{{label}}", width = 80.0 } }
+"##;
+
+fn instance(source: &str) -> definitions::WidgetNode {
+    toml::from_str(source).unwrap()
+}
+
+/// Test that instances take defaults, typed and interpolated arguments, and scoped ids
+#[test]
+fn test_template_registration_and_expansion() {
+    let mut registry = UiRegistry::new();
+    registry.register_template_source(CARD_TEMPLATE, UiDefinitionFormat::Toml).unwrap();
+
+    let info = registry.widget_type_mappings.get("Card").unwrap();
+    assert_eq!(info.required_properties, vec!["title".to_string()]);
+    assert_eq!(info.optional_properties, vec!["accent".to_string(), "width".to_string()]);
+    assert!(info.can_have_children);
+
+    let node = instance(r##"
+        id = "settings"
+        widget_type = { type = "Custom", component = "Card", properties = { title = "Settings", width = 320.0 } }
+        style = { border_width = 2.0 }
+        [[children]]
+        id = "extra"
+        widget_type = { type = "Text", content = "More", editable = false }
+    "##);
+    let expanded = registry.expand_template_instance(&node).unwrap().unwrap();
+
+    assert_eq!(expanded.id.as_deref(), Some("settings"));
+    assert!(matches!(expanded.widget_type, WidgetType::Container { .. }));
+    assert_eq!(expanded.layout.size.map(|size| size.x), Some(320.0), "A whole placeholder keeps the argument's type");
+    assert_eq!(expanded.style.background_color, Some(ColorDef::Hex("#3182CE".to_string())), "Defaults fill unset parameters");
+    assert_eq!(expanded.style.border_width, Some(2.0), "The instance's own style is merged over the template root");
+
+    assert_eq!(expanded.children.len(), 2);
+    assert_eq!(expanded.children[0].id.as_deref(), Some("settings_title"));
+    match &expanded.children[0].widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "Title: Settings"),
+        other => panic!("Expected a text node, got {:?}", other),
+    }
+    assert_eq!(expanded.children[1].id.as_deref(), Some("extra"), "Instance children are appended unchanged");
+
+    // Nodes that don't name a template are left alone
+    let plain = instance(r##"widget_type = { type = "Text", content = "Plain", editable = false }"##);
+    assert!(registry.expand_template_instance(&plain).unwrap().is_none());
+}
+
+/// Test that templates used inside templates are expanded too
+#[test]
+fn test_nested_template_expansion() {
+    let mut registry = UiRegistry::new();
+    registry.register_template_source(CARD_TEMPLATE, UiDefinitionFormat::Toml).unwrap();
+    registry.register_template_source(TOOLBAR_TEMPLATE, UiDefinitionFormat::Toml).unwrap();
+
+    let node = instance(r##"
+        id = "main"
+        widget_type = { type = "Custom", component = "Toolbar", properties = {} }
+    "##);
+    let expanded = registry.expand_template_instance(&node).unwrap().unwrap();
+
+    let card = &expanded.children[0];
+    assert_eq!(card.id.as_deref(), Some("main_card"));
+    assert!(matches!(card.widget_type, WidgetType::Container { .. }));
+    assert_eq!(card.layout.size.map(|size| size.x), Some(80.0));
+    assert_eq!(card.children[0].id.as_deref(), Some("main_card_title"));
+    match &card.children[0].widget_type {
+        WidgetType::Text { content, .. } => assert_eq!(content, "Title: Tools"),
+        other => panic!("Expected a text node, got {:?}", other),
+    }
+}
+
+/// Test the errors for bad templates and bad instances
+#[test]
+fn test_template_errors() {
+    let mut registry = UiRegistry::new();
+    registry.register_template_source(CARD_TEMPLATE, UiDefinitionFormat::Toml).unwrap();
+
+    let missing = instance(r##"widget_type = { type = "Custom", component = "Card", properties = {} }"##);
+    assert_eq!(
+        registry.expand_template_instance(&missing).unwrap_err(),
+        TemplateError::MissingParameter { template: "Card".to_string(), param: "title".to_string() }
+    );
+
+    let unknown = instance(r##"widget_type = { type = "Custom", component = "Card", properties = { title = "A", color = "red" } }"##);
+    assert!(matches!(registry.expand_template_instance(&unknown), Err(TemplateError::UnknownParameter { .. })));
+
+    let undeclared = r##"
+        [template]
+        name = "Label"
+        [template.root]
+        widget_type = { type = "Text", content = "{{text}}", editable = false }
+    "##;
+    assert!(matches!(
+        registry.register_template_source(undeclared, UiDefinitionFormat::Toml),
+        Err(TemplateError::UndeclaredParameter { .. })
+    ));
+
+    let reserved = r##"
+        [template]
+        name = "Button"
+        [template.root]
+        widget_type = { type = "Container", direction = "Row" }
+    "##;
+    assert!(matches!(
+        registry.register_template_source(reserved, UiDefinitionFormat::Toml),
+        Err(TemplateError::ReservedName(_))
+    ));
+
+    // A template that instantiates itself
+    let recursive = r##"
+        [template]
+        name = "Loop"
+        [template.root]
+        widget_type = { type = "Container", direction = "Row" }
+        [[template.root.children]]
+        widget_type = { type = "Custom", component = "Loop", properties = {} }
+    "##;
+    registry.register_template_source(recursive, UiDefinitionFormat::Toml).unwrap();
+    let looping = instance(r##"widget_type = { type = "Custom", component = "Loop", properties = {} }"##);
+    assert_eq!(
+        registry.expand_template_instance(&looping).unwrap_err(),
+        TemplateError::Cycle("Loop -> Loop".to_string())
+    );
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use super::super::*;
+use crate::layout::UiNode;
+use crate::widgets::components::WidgetHierarchy;
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_window::{PrimaryWindow, Window};
+
+const EDITOR: &str = r##"
+[window]
+size = [800.0, 600.0]
+title = "Editor"
+
+[windows.script_reader]
+layout = "ui/layouts/script_reader.toml"
+title = "Script Reader"
+size = [480.0, 640.0]
+background_color = { Hex = "#1E1E1E" }
+
+[windows.notes]
+layout = "ui/layouts/notes.toml"
+size = [320.0, 480.0]
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+"##;
+
+/// Test that window declarations parse with their config and are validated
+#[test]
+fn test_window_declarations_parse_and_validate() {
+    let ui_def = UiDefinitionLoader.parse_definition(EDITOR, UiDefinitionFormat::Toml).unwrap();
+    assert!(!ui_def.validation_report().has_errors());
+    assert_eq!(ui_def.window.as_ref().unwrap().title.as_deref(), Some("Editor"));
+
+    let windows = ui_def.windows.as_ref().unwrap();
+    let script_reader = &windows["script_reader"];
+    assert_eq!(script_reader.layout, "ui/layouts/script_reader.toml");
+    assert_eq!(script_reader.config.size, [480.0, 640.0]);
+    assert_eq!(script_reader.config.to_window("script_reader").title, "Script Reader");
+    assert_eq!(windows["notes"].config.to_window("notes").title, "notes", "Untitled windows are titled by id");
+
+    let invalid = "[windows.empty]\nlayout = \"\"\nsize = [0.0, 640.0]\n\n[root]\nwidget_type = { type = \"Container\", direction = \"Row\" }\n";
+    let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+    let report = ui_def.validation_report();
+    let paths: Vec<_> = report.errors().map(|diagnostic| diagnostic.path.as_str()).collect();
+    assert_eq!(paths, vec!["windows.empty.layout", "windows.empty.size"]);
+}
+
+/// Test that declared windows open once and request their layouts
+#[test]
+fn test_declared_windows_open_once() {
+    let ui_def = UiDefinitionLoader.parse_definition(EDITOR, UiDefinitionFormat::Toml).unwrap();
+    let mut world = World::new();
+    let mut ui_windows = UiWindows::default();
+
+    let requests = open_declared_windows(&mut world.commands(), &mut ui_windows, &ui_def);
+    world.flush();
+    let windows: Vec<_> = requests.iter().map(|request| request.window.as_deref()).collect();
+    assert_eq!(windows, vec![Some("notes"), Some("script_reader")], "Windows open in id order");
+    assert!(UiTarget::Window("notes".to_string()).matches(&requests[0], None));
+    assert!(!UiTarget::Window("notes".to_string()).matches(&requests[1], None));
+
+    let script_reader = ui_windows.get("script_reader").unwrap();
+    assert!(world.get::<Window>(script_reader).is_some());
+    assert!(world.get::<UiWindow>(script_reader).unwrap().background.is_some());
+
+    assert!(open_declared_windows(&mut world.commands(), &mut ui_windows, &ui_def).is_empty());
+    world.flush();
+    assert_eq!(world.query::<&UiWindow>().iter(&world).count(), 2);
+
+    assert_eq!(ui_windows.remove_window(script_reader).as_deref(), Some("script_reader"));
+    assert!(ui_windows.get("script_reader").is_none());
+
+    // Layouts requested only for declared windows leave the primary window alone
+    let mut loading_assets = LoadingUiAssets::default();
+    let handle = Handle::<UiDefinition>::weak_from_u128(1);
+    loading_assets.pending_loads.insert(handle.clone(), requests[0].clone());
+    assert!(loading_assets.is_window_only(handle.id()));
+    loading_assets.pending_loads.insert(Handle::weak_from_u128(2), LoadUiRequest::new("ui/layouts/notes.toml"));
+    assert!(!loading_assets.is_window_only(Handle::<UiDefinition>::weak_from_u128(2).id()));
+    assert!(!loading_assets.is_window_only(Handle::<UiDefinition>::weak_from_u128(3).id()));
+}
+
+/// Test that widgets inherit the window of their hierarchy parent
+#[test]
+fn test_target_window_propagates_to_widgets() {
+    let mut world = World::new();
+    let primary = world.spawn((Window::default(), PrimaryWindow)).id();
+    let script_reader = world.spawn(Window::default()).id();
+    let root = world.spawn((UiNode::default(), TargetWindow(script_reader))).id();
+    let handle = world.spawn((UiNode::default(), WidgetHierarchy { parent: Some(root), children: vec![] })).id();
+    let sidebar = world.spawn(UiNode::default()).id();
+
+    world.run_system_once(target_window_propagation_system).unwrap();
+
+    assert_eq!(world.get::<TargetWindow>(handle), Some(&TargetWindow(script_reader)));
+    assert!(world.get::<TargetWindow>(sidebar).is_none(), "Widgets without a target stay in the primary window");
+
+    let windows = world.run_system_once(move |widget_windows: WidgetWindows| {
+        (widget_windows.window_of(handle), widget_windows.window_of(sidebar))
+    }).unwrap();
+    assert_eq!(windows, (Some(script_reader), Some(primary)));
+}
+
+/// Test that the optional window settings reach the Bevy window and are validated
+#[test]
+fn test_window_settings_apply_and_validate() {
+    let content = r##"
+[window]
+size = [800.0, 600.0]
+icon = "icons/whip.png"
+min_size = [400.0, 300.0]
+max_size = [1600.0, 1200.0]
+resizable = false
+decorations = false
+fullscreen = "Borderless"
+present_mode = "Mailbox"
+transparent = true
+position = [100, 50]
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+"##;
+    let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+    assert!(!ui_def.validation_report().has_errors());
+
+    let config = ui_def.window.as_ref().unwrap();
+    let window = config.to_window("Editor");
+    assert_eq!(window.title, "Editor");
+    assert_eq!((window.resize_constraints.min_width, window.resize_constraints.max_height), (400.0, 1200.0));
+    assert!(!window.resizable && !window.decorations && window.transparent);
+    assert_eq!(window.mode, bevy_window::WindowMode::BorderlessFullscreen(bevy_window::MonitorSelection::Current));
+    assert_eq!(window.present_mode, bevy_window::PresentMode::Mailbox);
+    assert_eq!(window.position, bevy_window::WindowPosition::At(bevy_math::IVec2::new(100, 50)));
+
+    // Unset settings keep the window's own
+    let defaults = WindowConfig::default().to_window("Editor");
+    assert!(defaults.resizable && defaults.decorations);
+
+    let mut invalid = config.clone();
+    invalid.min_size = Some([2000.0, 300.0]);
+    invalid.icon = Some(String::new());
+    let report = UiDefinition { window: Some(invalid), ..ui_def }.validation_report();
+    let paths: Vec<_> = report.errors().map(|diagnostic| diagnostic.path.as_str()).collect();
+    assert_eq!(paths, vec!["window.max_size", "window.icon"]);
+    assert!(report.warnings().any(|diagnostic| diagnostic.path == "window.size"));
+
+    // Transparency is fixed when the window is created
+    let mut open = WindowConfig::default().to_window("Editor");
+    config.apply_to_window(&mut open);
+    assert!(!open.transparent && !open.resizable);
+}
+
+/// Test that window icons decode to RGBA for winit
+#[test]
+fn test_window_icon_decode() {
+    let mut png = Vec::new();
+    image::RgbaImage::from_pixel(2, 3, image::Rgba([255, 136, 0, 255]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+
+    let icon = WindowIconImage::decode(&png).unwrap();
+    assert_eq!((icon.width, icon.height), (2, 3));
+    assert_eq!(&icon.rgba[..4], &[255, 136, 0, 255]);
+    assert!(icon.to_icon().is_ok());
+    assert!(WindowIconImage::decode(b"not a png").is_err());
+}
\ No newline at end of file
@@ -0,0 +1,355 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use super::{UiDefinitionError, ValidationReport};
+use super::includes::{normalize_asset_path, resolve_include_path, INCLUDE_KEY};
+
+/// Top-level table holding design tokens, e.g. `[tokens] primary = "#3182CE"`
+pub const TOKENS_KEY: &str = "tokens";
+
+/// Prefix that turns a string value into a token reference, e.g. `"$primary"`.
+/// A doubled prefix (`"$$"`) escapes a literal `$`.
+pub const TOKEN_PREFIX: char = '$';
+
+/// Style fields that hold a `ColorDef`
+const COLOR_FIELDS: &[&str] = &["background_color", "border_color", "text_color"];
+
+/// Fields that hold a single number
+const NUMBER_FIELDS: &[&str] = &[
+    "border_width", "border_radius", "text_size", "opacity",
+    "flex_grow", "flex_shrink", "grid_row", "grid_column",
+    "top", "right", "bottom", "left",
+];
+
+/// Fields that hold a `Spacing`; a single number applies to all four sides
+const SPACING_FIELDS: &[&str] = &["margin", "padding"];
+
+/// Fields that hold a vector (`[x, y]` or `[x, y, z]`)
+const VECTOR_FIELDS: &[&str] = &["size", "position"];
+
+/// `widget_type` fields that carry text or identifiers rather than style values
+const LITERAL_WIDGET_TYPE_FIELDS: &[&str] = &["type", "content", "text", "label", "format", "placeholder", "filter", "component", "properties"];
+
+/// Resolves `"$name"` token references in UI definition documents.
+///
+/// Tokens come from the document's `[tokens]` table and from the token files it lists under
+/// `tokens.include`; local tokens win over included ones. Token files are registered up front
+/// by their normalized asset path, like blueprints for [`super::IncludeResolver`].
+#[derive(Debug, Default, Clone)]
+pub struct TokenResolver {
+    sources: HashMap<PathBuf, Value>,
+}
+
+impl TokenResolver {
+    /// Register the parsed contents of a token file
+    pub fn insert_source(&mut self, path: impl AsRef<Path>, document: Value) {
+        self.sources.insert(normalize_asset_path(path.as_ref()), document);
+    }
+
+    /// Check whether a token file has already been registered
+    pub fn contains_source(&self, path: impl AsRef<Path>) -> bool {
+        self.sources.contains_key(&normalize_asset_path(path.as_ref()))
+    }
+
+    /// Replace every token reference in the style and layout sections of a UI definition
+    /// document located at `path`.
+    ///
+    /// The `[tokens]` table is replaced by the effective token set. Problems are added to
+    /// `report` with the path of the offending value.
+    pub fn resolve_document(&self, document: &mut Value, path: &Path, report: &mut ValidationReport) {
+        let path = normalize_asset_path(path);
+        let mut stack = vec![path.clone()];
+        let tokens = self.token_table(document, &path, &mut stack, report);
+
+        if let Some(fields) = document.as_object_mut() {
+            if let Some(window) = fields.get_mut("window") {
+                substitute_fields(window, "window", &tokens, report);
+            }
+            if let Some(Value::Object(windows)) = fields.get_mut("windows") {
+                for (id, window) in windows.iter_mut() {
+                    substitute_fields(window, &format!("windows.{}", id), &tokens, report);
+                }
+            }
+            if let Some(Value::Object(styles)) = fields.get_mut("styles") {
+                for (name, style) in styles.iter_mut() {
+                    substitute_fields(style, &format!("styles.{}", name), &tokens, report);
+                }
+            }
+            if let Some(root) = fields.get_mut("root") {
+                substitute_node(root, "root", &tokens, report);
+            }
+            if !tokens.is_empty() {
+                let mut tokens: Vec<_> = tokens.into_iter().collect();
+                tokens.sort_by(|a, b| a.0.cmp(&b.0));
+                fields.insert(TOKENS_KEY.to_string(), Value::Object(tokens.into_iter().collect()));
+            }
+        }
+    }
+
+    /// Effective tokens of the document declared in `file`, with aliases resolved
+    fn token_table(&self, document: &Value, file: &Path, stack: &mut Vec<PathBuf>, report: &mut ValidationReport) -> HashMap<String, Value> {
+        let mut raw = HashMap::new();
+        let Some(table) = document.get(TOKENS_KEY) else {
+            return raw;
+        };
+        let Some(table) = table.as_object() else {
+            report.error(TOKENS_KEY, UiDefinitionError::Validation("'tokens' must be a table".to_string()));
+            return raw;
+        };
+
+        // Included token files first, so the document's own tokens override them
+        for include in include_list(table, report) {
+            let include_path = resolve_include_path(file, &include);
+            if stack.contains(&include_path) {
+                let chain = stack.iter()
+                    .chain(std::iter::once(&include_path))
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                report.error(format!("{}.{}", TOKENS_KEY, INCLUDE_KEY), UiDefinitionError::Validation(
+                    format!("Token file include cycle detected: {}", chain)
+                ));
+                continue;
+            }
+            let Some(source) = self.sources.get(&include_path) else {
+                report.error(format!("{}.{}", TOKENS_KEY, INCLUDE_KEY), UiDefinitionError::Validation(
+                    format!("Token file '{}' was not loaded", include_path.display())
+                ));
+                continue;
+            };
+
+            stack.push(include_path.clone());
+            let mut included = ValidationReport::new();
+            raw.extend(self.token_table(source, &include_path, stack, &mut included));
+            stack.pop();
+            for mut diagnostic in included.diagnostics().iter().cloned() {
+                diagnostic.message = format!("{} (in token file '{}')", diagnostic.message, include_path.display());
+                diagnostic.path = format!("{}.{}", TOKENS_KEY, INCLUDE_KEY);
+                report.push(diagnostic);
+            }
+        }
+
+        for (name, value) in table {
+            if name == INCLUDE_KEY {
+                continue;
+            }
+            let path = format!("{}.{}", TOKENS_KEY, name);
+            if !is_valid_token_name(name) {
+                report.error(&path, UiDefinitionError::Validation(format!(
+                    "Invalid token name '{}': use letters, digits, '_' and '-', not starting with a digit", name
+                )));
+                continue;
+            }
+            match value {
+                Value::String(_) | Value::Number(_) | Value::Array(_) | Value::Object(_) => {
+                    raw.insert(name.clone(), value.clone());
+                }
+                _ => report.error(&path, UiDefinitionError::Validation(format!(
+                    "Token '{}' must be a color, a number or a list of numbers", name
+                ))),
+            }
+        }
+
+        // Tokens may alias other tokens: `accent = "$primary"`
+        let mut resolved = HashMap::new();
+        let mut names: Vec<&String> = raw.keys().collect();
+        names.sort();
+        for name in names {
+            let mut chain = vec![name.as_str()];
+            let mut value = &raw[name];
+            loop {
+                let Some(target) = value.as_str().and_then(token_reference) else {
+                    resolved.insert(name.clone(), unescape(value));
+                    break;
+                };
+                if chain.contains(&target) {
+                    chain.push(target);
+                    report.error(format!("{}.{}", TOKENS_KEY, name), UiDefinitionError::Validation(
+                        format!("Token alias cycle: {}", chain.join(" -> "))
+                    ));
+                    break;
+                }
+                let Some(next) = raw.get(target) else {
+                    report.error(format!("{}.{}", TOKENS_KEY, name), UiDefinitionError::Validation(
+                        format!("Token '{}' refers to unknown token '{}{}'", name, TOKEN_PREFIX, target)
+                    ));
+                    break;
+                };
+                chain.push(target);
+                value = next;
+            }
+        }
+        resolved
+    }
+}
+
+/// Check whether a UI definition document declares or references design tokens
+pub fn document_uses_tokens(document: &Value) -> bool {
+    fn has_reference(value: &Value) -> bool {
+        match value {
+            Value::String(text) => text.starts_with(TOKEN_PREFIX),
+            Value::Array(items) => items.iter().any(has_reference),
+            Value::Object(fields) => fields.values().any(has_reference),
+            _ => false,
+        }
+    }
+
+    document.get(TOKENS_KEY).is_some()
+        || ["window", "windows", "styles", "root"].iter()
+            .filter_map(|section| document.get(section))
+            .any(has_reference)
+}
+
+/// Collect the resolved paths of the token files a document includes
+pub fn collect_token_files(document: &Value, file: &Path, paths: &mut Vec<PathBuf>) {
+    let Some(table) = document.get(TOKENS_KEY).and_then(Value::as_object) else {
+        return;
+    };
+    for include in include_list(table, &mut ValidationReport::new()) {
+        paths.push(resolve_include_path(file, &include));
+    }
+}
+
+/// Paths listed under `tokens.include`, either a single path or a list of paths
+fn include_list(table: &Map<String, Value>, report: &mut ValidationReport) -> Vec<String> {
+    let path = format!("{}.{}", TOKENS_KEY, INCLUDE_KEY);
+    match table.get(INCLUDE_KEY) {
+        None => Vec::new(),
+        Some(Value::String(include)) => vec![include.clone()],
+        Some(Value::Array(includes)) => includes.iter()
+            .filter_map(|include| {
+                let include = include.as_str().map(str::to_string);
+                if include.is_none() {
+                    report.error(&path, UiDefinitionError::Validation("Token file paths must be strings".to_string()));
+                }
+                include
+            })
+            .collect(),
+        Some(_) => {
+            report.error(&path, UiDefinitionError::Validation(
+                "'tokens.include' must be a path or a list of paths".to_string()
+            ));
+            Vec::new()
+        }
+    }
+}
+
+/// Substitute tokens in the style and layout sections of a widget node and its children
+fn substitute_node(node: &mut Value, path: &str, tokens: &HashMap<String, Value>, report: &mut ValidationReport) {
+    let Some(fields) = node.as_object_mut() else {
+        return;
+    };
+
+    for section in ["layout", "style", "style_overrides"] {
+        if let Some(value) = fields.get_mut(section) {
+            substitute_fields(value, &format!("{}.{}", path, section), tokens, report);
+        }
+    }
+
+    // Template overrides such as a button's `background_color` or `size`
+    if let Some(Value::Object(widget_type)) = fields.get_mut("widget_type") {
+        for (key, value) in widget_type.iter_mut() {
+            if !LITERAL_WIDGET_TYPE_FIELDS.contains(&key.as_str()) {
+                substitute_value(value, key, &format!("{}.widget_type.{}", path, key), tokens, report);
+            }
+        }
+    }
+
+    if let Some(Value::Array(children)) = fields.get_mut("children") {
+        for (i, child) in children.iter_mut().enumerate() {
+            substitute_node(child, &format!("{}.children[{}]", path, i), tokens, report);
+        }
+    }
+}
+
+/// Substitute tokens in every field of a table, recursing into nested tables like `states`
+fn substitute_fields(value: &mut Value, path: &str, tokens: &HashMap<String, Value>, report: &mut ValidationReport) {
+    if let Some(fields) = value.as_object_mut() {
+        for (key, field) in fields.iter_mut() {
+            substitute_value(field, key, &format!("{}.{}", path, key), tokens, report);
+        }
+    }
+}
+
+fn substitute_value(value: &mut Value, key: &str, path: &str, tokens: &HashMap<String, Value>, report: &mut ValidationReport) {
+    if let Some(name) = value.as_str().and_then(token_reference).map(str::to_string) {
+        let Some(token) = tokens.get(&name) else {
+            report.error(path, UiDefinitionError::Validation(
+                format!("Unknown token '{}{}'", TOKEN_PREFIX, name)
+            ));
+            return;
+        };
+        if let Some(problem) = kind_mismatch(key, token) {
+            report.error(path, UiDefinitionError::Validation(
+                format!("Token '{}{}' {}", TOKEN_PREFIX, name, problem)
+            ));
+        }
+        *value = token.clone();
+    } else {
+        match value {
+            Value::String(_) => *value = unescape(value),
+            // Vector and spacing components may be tokens too: `size = ["$button_width", 40.0]`
+            Value::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    substitute_value(item, "", &format!("{}[{}]", path, i), tokens, report);
+                }
+            }
+            Value::Object(_) if !COLOR_FIELDS.contains(&key) => substitute_fields(value, path, tokens, report),
+            _ => {}
+        }
+    }
+
+    // A single spacing value applies to every side
+    if SPACING_FIELDS.contains(&key) {
+        if let Some(amount) = value.as_f64() {
+            let side = Value::from(amount);
+            *value = serde_json::json!({ "top": side, "right": side, "bottom": side, "left": side });
+        }
+    }
+}
+
+/// Describe why `token` cannot be used for the field `key`, if it cannot
+fn kind_mismatch(key: &str, token: &Value) -> Option<String> {
+    let fits = if COLOR_FIELDS.contains(&key) {
+        token.is_string() || token.is_object()
+    } else if NUMBER_FIELDS.contains(&key) {
+        token.is_number()
+    } else if SPACING_FIELDS.contains(&key) {
+        token.is_number() || token.is_object()
+    } else if VECTOR_FIELDS.contains(&key) {
+        token.is_array()
+    } else {
+        true
+    };
+    if fits {
+        return None;
+    }
+
+    let actual = match token {
+        Value::String(_) => "a color",
+        Value::Number(_) => "a number",
+        Value::Array(_) => "a list",
+        _ => "a table",
+    };
+    Some(format!("is {}, which cannot be used for '{}'", actual, key))
+}
+
+/// The token name of a `"$name"` reference, ignoring escaped `"$$"` strings
+fn token_reference(text: &str) -> Option<&str> {
+    let name = text.strip_prefix(TOKEN_PREFIX)?;
+    (!name.starts_with(TOKEN_PREFIX)).then_some(name)
+}
+
+/// Turn an escaped `"$$..."` string into its literal `"$..."` value
+fn unescape(value: &Value) -> Value {
+    match value.as_str().and_then(|text| text.strip_prefix(TOKEN_PREFIX)) {
+        Some(rest) if rest.starts_with(TOKEN_PREFIX) => Value::String(rest.to_string()),
+        _ => value.clone(),
+    }
+}
+
+fn is_valid_token_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
\ No newline at end of file
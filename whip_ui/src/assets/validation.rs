@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::fmt;
+use super::{definitions::UiDefinitionError, loaders::UiDefinitionFormat};
+
+/// How serious a validation finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// 1-based position in a UI definition source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A single validation finding
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Human readable description
+    pub message: String,
+    /// Path of the offending item, e.g. `root.children[2].children[0].style`
+    pub path: String,
+    /// Where the item is declared, once the report has been located against its source
+    pub location: Option<SourceLocation>,
+    /// The validation error behind an error diagnostic, if it came from definition checks
+    pub error: Option<UiDefinitionError>,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic from a definition error
+    pub fn from_error(path: impl Into<String>, error: UiDefinitionError) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: error.to_string(),
+            path: path.into(),
+            location: None,
+            error: Some(error),
+        }
+    }
+
+    /// Create an error diagnostic that has no definition error behind it, such as a parse error
+    pub fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            path: path.into(),
+            location: None,
+            error: None,
+        }
+    }
+
+    /// Create a warning diagnostic
+    pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            path: path.into(),
+            location: None,
+            error: None,
+        }
+    }
+
+    /// Attach a source location
+    pub fn with_location(mut self, line: usize, column: usize) -> Self {
+        self.location = Some(SourceLocation { line, column });
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+        match self.location {
+            Some(location) => write!(f, " (at {}, line {}, column {})", self.path, location.line, location.column),
+            None if !self.path.is_empty() => write!(f, " (at {})", self.path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Every error and warning found while validating a UI definition.
+///
+/// Unlike the fail-fast `validate` methods, a report keeps going after the first problem so a
+/// single pass shows everything wrong with a file.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// Name of the validated source (usually its asset path), used when printing
+    pub source_name: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the source name shown when printing the report
+    pub fn with_source_name(mut self, name: impl Into<String>) -> Self {
+        self.source_name = Some(name.into());
+        self
+    }
+
+    /// Record a diagnostic
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Record an error
+    pub fn error(&mut self, path: impl Into<String>, error: UiDefinitionError) {
+        self.push(Diagnostic::from_error(path, error));
+    }
+
+    /// Record a warning
+    pub fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Diagnostic::warning(path, message));
+    }
+
+    /// Append all diagnostics of another report
+    pub fn extend(&mut self, other: ValidationReport) {
+        self.diagnostics.extend(other.diagnostics);
+    }
+
+    /// All diagnostics in the order they were found
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Error diagnostics only
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    /// Warning diagnostics only
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors().count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.warnings().count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// True when there are neither errors nor warnings
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The first definition error found, as returned by the fail-fast validators
+    pub fn first_error(&self) -> Option<&UiDefinitionError> {
+        self.errors().find_map(|d| d.error.as_ref())
+    }
+
+    /// `Ok` (possibly still carrying warnings) if no errors were found
+    pub fn into_result(self) -> Result<ValidationReport, ValidationReport> {
+        if self.has_errors() {
+            Err(self)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Fill in source locations by resolving each diagnostic's path against the source text.
+    ///
+    /// Paths that cannot be found (e.g. nodes expanded from an include or declared inline)
+    /// fall back to their nearest located ancestor.
+    pub fn locate(&mut self, source: &str, format: UiDefinitionFormat) {
+        let source_map = SourceMap::new(source, format);
+        for diagnostic in &mut self.diagnostics {
+            if diagnostic.location.is_none() {
+                diagnostic.location = source_map.locate(&diagnostic.path);
+            }
+        }
+    }
+
+    /// Write every diagnostic to the log at its severity
+    pub fn log(&self) {
+        for diagnostic in &self.diagnostics {
+            match diagnostic.severity {
+                Severity::Error => bevy_log::error!("{}", self.describe(diagnostic)),
+                Severity::Warning => bevy_log::warn!("{}", self.describe(diagnostic)),
+            }
+        }
+    }
+
+    /// One-line description including the source name, e.g. `ui/main.toml:12:1: error: ...`
+    fn describe(&self, diagnostic: &Diagnostic) -> String {
+        let source = self.source_name.as_deref().unwrap_or("<ui definition>");
+        match diagnostic.location {
+            Some(location) => format!("{}:{}:{}: {}: {} [{}]",
+                source, location.line, location.column, diagnostic.severity, diagnostic.message, diagnostic.path),
+            None => format!("{}: {}: {} [{}]", source, diagnostic.severity, diagnostic.message, diagnostic.path),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = self.source_name.as_deref().unwrap_or("<ui definition>");
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}: {}", diagnostic.severity, diagnostic.message)?;
+            match diagnostic.location {
+                Some(location) => writeln!(f, "  --> {}:{}:{}", source, location.line, location.column)?,
+                None => writeln!(f, "  --> {}", source)?,
+            }
+            if !diagnostic.path.is_empty() {
+                writeln!(f, "   = at {}", diagnostic.path)?;
+            }
+        }
+        write!(f, "{}: {} error(s), {} warning(s)", source, self.error_count(), self.warning_count())
+    }
+}
+
+/// Maps item paths (`root.children[1].style`) to where they are declared in a source file
+enum SourceMap<'a> {
+    /// Spans of every key, table and value, taken from the parsed TOML document
+    Toml(HashMap<String, SourceLocation>),
+    /// serde_json keeps no spans, so JSON paths are resolved one at a time against the source
+    Json(&'a str),
+}
+
+impl<'a> SourceMap<'a> {
+    fn new(source: &'a str, format: UiDefinitionFormat) -> Self {
+        match format {
+            UiDefinitionFormat::Toml => {
+                let mut locations = HashMap::new();
+                // Sources that fail to parse have their parse error located already
+                if let Ok(document) = toml_edit::ImDocument::parse(source) {
+                    index_toml_table(&mut locations, source, document.as_table(), "");
+                }
+                SourceMap::Toml(locations)
+            }
+            UiDefinitionFormat::Json => SourceMap::Json(source),
+        }
+    }
+
+    /// Find the location of `path`, or of its nearest located ancestor
+    fn locate(&self, path: &str) -> Option<SourceLocation> {
+        let mut path = path;
+        loop {
+            let location = match self {
+                SourceMap::Toml(locations) => locations.get(path).copied(),
+                SourceMap::Json(source) => locate_json(source, path),
+            };
+            if location.is_some() {
+                return location;
+            }
+            let cut = path.rfind(['.', '['])?;
+            path = &path[..cut];
+        }
+    }
+}
+
+/// Record where every key of a TOML table is declared, then index its values.
+///
+/// Keys point at their own span, so dotted and quoted keys resolve like the deserializer sees
+/// them and nothing inside multi-line strings is mistaken for a key.
+fn index_toml_table(locations: &mut HashMap<String, SourceLocation>, source: &str, table: &toml_edit::Table, parent: &str) {
+    for (name, item) in table.iter() {
+        let path = join_path(parent, name);
+        let span = table.key(name).and_then(toml_edit::Key::span).or_else(|| item.span());
+        insert_span(locations, source, &path, span);
+        index_toml_item(locations, source, item, &path);
+    }
+}
+
+fn index_toml_item(locations: &mut HashMap<String, SourceLocation>, source: &str, item: &toml_edit::Item, path: &str) {
+    match item {
+        toml_edit::Item::Table(table) => index_toml_table(locations, source, table, path),
+        toml_edit::Item::ArrayOfTables(tables) => {
+            // Each entry points at its own `[[...]]` header
+            for (index, table) in tables.iter().enumerate() {
+                let entry = format!("{}[{}]", path, index);
+                insert_span(locations, source, &entry, table.span());
+                index_toml_table(locations, source, table, &entry);
+            }
+        }
+        toml_edit::Item::Value(value) => index_toml_value(locations, source, value, path),
+        toml_edit::Item::None => {}
+    }
+}
+
+fn index_toml_value(locations: &mut HashMap<String, SourceLocation>, source: &str, value: &toml_edit::Value, path: &str) {
+    match value {
+        toml_edit::Value::InlineTable(table) => {
+            for (name, value) in table.iter() {
+                let entry = join_path(path, name);
+                let span = table.key(name).and_then(toml_edit::Key::span).or_else(|| value.span());
+                insert_span(locations, source, &entry, span);
+                index_toml_value(locations, source, value, &entry);
+            }
+        }
+        toml_edit::Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                let entry = format!("{}[{}]", path, index);
+                insert_span(locations, source, &entry, value.span());
+                index_toml_value(locations, source, value, &entry);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn insert_span(locations: &mut HashMap<String, SourceLocation>, source: &str, path: &str, span: Option<std::ops::Range<usize>>) {
+    if let Some(span) = span {
+        locations.entry(path.to_string()).or_insert_with(|| location_at(source, span.start));
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Convert a byte offset into a 1-based line/column
+fn location_at(source: &str, offset: usize) -> SourceLocation {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    SourceLocation {
+        line,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Error message that marks the item being located, as opposed to a real parse error
+const JSON_TARGET_REACHED: &str = "validation target reached";
+
+/// One step of an item path: an object key or an array index
+#[derive(Debug, Clone, Copy)]
+enum PathSegment<'p> {
+    Key(&'p str),
+    Index(usize),
+}
+
+fn path_segments(path: &str) -> Option<Vec<PathSegment<'_>>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let mut pieces = part.split('[');
+        segments.push(PathSegment::Key(pieces.next()?));
+        for index in pieces {
+            segments.push(PathSegment::Index(index.strip_suffix(']')?.parse().ok()?));
+        }
+    }
+    Some(segments)
+}
+
+/// Find where the value at `path` starts in a JSON source.
+///
+/// serde_json only reports positions in its errors, so the path is walked with a seed that fails
+/// on purpose once it reaches the item; the position of that error is where the value begins.
+fn locate_json(source: &str, path: &str) -> Option<SourceLocation> {
+    use serde::de::DeserializeSeed;
+
+    if path.is_empty() {
+        return None;
+    }
+    let segments = path_segments(path)?;
+    let mut deserializer = serde_json::Deserializer::from_str(source);
+    let error = JsonTarget(&segments).deserialize(&mut deserializer).err()?;
+    if !error.is_data() || !error.to_string().starts_with(JSON_TARGET_REACHED) {
+        return None;
+    }
+
+    // The reported column counts the bytes before the position, and may stop ahead of whitespace
+    let line_start = source.split_inclusive('\n').take(error.line().saturating_sub(1)).map(str::len).sum::<usize>();
+    let offset = (line_start + error.column()).min(source.len());
+    let value_start = source[offset..].find(|c: char| !c.is_whitespace()).map_or(offset, |skip| offset + skip);
+    Some(location_at(source, value_start))
+}
+
+/// Walks a JSON document down a path and fails with `JSON_TARGET_REACHED` at its end
+struct JsonTarget<'s, 'p>(&'s [PathSegment<'p>]);
+
+impl<'de> serde::de::DeserializeSeed<'de> for JsonTarget<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        match self.0.first() {
+            None => Err(serde::de::Error::custom(JSON_TARGET_REACHED)),
+            Some(PathSegment::Key(_)) => deserializer.deserialize_map(self),
+            Some(PathSegment::Index(_)) => deserializer.deserialize_seq(self),
+        }
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for JsonTarget<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an object or array on the validated path")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Some(PathSegment::Key(target)) = self.0.first() else { return Ok(()) };
+        while let Some(key) = map.next_key::<String>()? {
+            if key == *target {
+                return map.next_value_seed(JsonTarget(&self.0[1..]));
+            }
+            map.next_value::<serde::de::IgnoredAny>()?;
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let Some(PathSegment::Index(target)) = self.0.first() else { return Ok(()) };
+        for _ in 0..*target {
+            if seq.next_element::<serde::de::IgnoredAny>()?.is_none() {
+                return Ok(());
+            }
+        }
+        seq.next_element_seed(JsonTarget(&self.0[1..])).map(|_| ())
+    }
+}
\ No newline at end of file
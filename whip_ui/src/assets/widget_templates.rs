@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use thiserror::Error;
+use crate::widgets::blueprint::WidgetType;
+use super::{UiDefinitionFormat, UiRegistry, definitions::WidgetNode, includes::deep_merge};
+
+/// Opening and closing delimiters of a parameter placeholder, e.g. `"{{title}}"`
+const PLACEHOLDER_OPEN: &str = "{{";
+const PLACEHOLDER_CLOSE: &str = "}}";
+
+/// Widget types that templates cannot shadow
+pub(crate) const BUILTIN_WIDGET_TYPES: &[&str] = &["Container", "Text", "Shape", "Button", "Checkbox", "Toggle", "Slider", "DragValue", "TextInput", "Dropdown", "ScrollView", "VirtualList", "Popup", "Tabs"];
+
+/// Expands a built-in template widget, such as a `Button`, into its root node followed by
+/// the parts it is drawn with
+pub type BuiltinTemplateExpander = fn(&WidgetNode) -> Vec<WidgetNode>;
+
+/// Errors that can occur while registering or expanding widget templates
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    #[error("Failed to parse widget template: {0}")]
+    Parse(String),
+    #[error("Widget template name '{0}' is reserved for a built-in widget type")]
+    ReservedName(String),
+    #[error("Widget template '{template}' uses undeclared parameter '{param}'")]
+    UndeclaredParameter { template: String, param: String },
+    #[error("Widget template '{template}' is missing required parameter '{param}'")]
+    MissingParameter { template: String, param: String },
+    #[error("Widget template '{template}' has no parameter '{param}'")]
+    UnknownParameter { template: String, param: String },
+    #[error("Widget template expansion cycle: {0}")]
+    Cycle(String),
+    #[error("Widget template '{template}' did not expand to a valid widget node: {reason}")]
+    InvalidNode { template: String, reason: String },
+}
+
+/// A reusable widget declared in data rather than Rust.
+///
+/// Template files hold a single `[template]` table:
+///
+/// ```toml
+/// [template]
+/// name = "Card"
+///
+/// [template.params]
+/// title = {}                          # required
+/// accent = { default = "#3182CE" }
+///
+/// [template.root]
+/// widget_type = { type = "Container", direction = "Column" }
+/// style = { background_color = "{{accent}}" }
+///
+/// [[template.root.children]]
+/// id = "title"
+/// widget_type = { type = "Text", content = "{{title}}", editable = false }
+/// ```
+///
+/// Once registered, layouts instantiate it as
+/// `widget_type = { type = "Custom", component = "Card", properties = { title = "Settings" } }`.
+/// A string that is exactly one placeholder takes the argument's value and type; placeholders
+/// inside longer strings are replaced by the argument's text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetTemplateDefinition {
+    /// Name used as the `component` of `Custom` widget types
+    pub name: String,
+    /// Human-readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Parameters the node tree refers to as `{{name}}`
+    #[serde(default)]
+    pub params: HashMap<String, TemplateParam>,
+    /// Widget node tree with parameter placeholders
+    pub root: Value,
+}
+
+/// A template parameter; parameters without a default are required
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateParam {
+    /// Value used when an instance does not set the parameter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Human-readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// On-disk layout of a template file
+#[derive(Deserialize)]
+struct TemplateFile {
+    template: WidgetTemplateDefinition,
+}
+
+impl WidgetTemplateDefinition {
+    /// Parse a template file in the given format
+    pub fn parse(content: &str, format: UiDefinitionFormat) -> Result<Self, TemplateError> {
+        let file: TemplateFile = match format {
+            UiDefinitionFormat::Json => serde_json::from_str(content).map_err(|e| TemplateError::Parse(e.to_string()))?,
+            UiDefinitionFormat::Toml => toml::from_str(content).map_err(|e| TemplateError::Parse(e.to_string()))?,
+        };
+        Ok(file.template)
+    }
+
+    /// Check that the name is free and that every placeholder refers to a declared parameter
+    pub fn check(&self) -> Result<(), TemplateError> {
+        if BUILTIN_WIDGET_TYPES.contains(&self.name.as_str()) {
+            return Err(TemplateError::ReservedName(self.name.clone()));
+        }
+        let mut placeholders = Vec::new();
+        collect_placeholders(&self.root, &mut placeholders);
+        match placeholders.into_iter().find(|param| !self.params.contains_key(param)) {
+            Some(param) => Err(TemplateError::UndeclaredParameter { template: self.name.clone(), param }),
+            None => Ok(()),
+        }
+    }
+
+    /// Names of the parameters an instance has to set
+    pub fn required_params(&self) -> Vec<String> {
+        let mut required: Vec<String> = self.params.iter()
+            .filter(|(_, param)| param.default.is_none())
+            .map(|(name, _)| name.clone())
+            .collect();
+        required.sort();
+        required
+    }
+
+    /// Names of the parameters that have a default
+    pub fn optional_params(&self) -> Vec<String> {
+        let mut optional: Vec<String> = self.params.iter()
+            .filter(|(_, param)| param.default.is_some())
+            .map(|(name, _)| name.clone())
+            .collect();
+        optional.sort();
+        optional
+    }
+
+    /// Whether instances may declare children of their own
+    pub fn accepts_children(&self) -> bool {
+        self.root.pointer("/widget_type/type").and_then(Value::as_str) == Some("Container")
+    }
+
+    /// The template's node tree with every placeholder replaced by the instance's arguments
+    fn instantiate(&self, properties: &HashMap<String, Value>) -> Result<WidgetNode, TemplateError> {
+        if let Some(param) = properties.keys().find(|param| !self.params.contains_key(*param)) {
+            return Err(TemplateError::UnknownParameter { template: self.name.clone(), param: param.clone() });
+        }
+
+        let mut args = HashMap::new();
+        for (name, param) in &self.params {
+            let value = properties.get(name).or(param.default.as_ref()).ok_or_else(|| {
+                TemplateError::MissingParameter { template: self.name.clone(), param: name.clone() }
+            })?;
+            args.insert(name.clone(), value.clone());
+        }
+
+        let mut root = self.root.clone();
+        substitute_placeholders(&mut root, &args, &self.name)?;
+        serde_json::from_value(root)
+            .map_err(|e| TemplateError::InvalidNode { template: self.name.clone(), reason: e.to_string() })
+    }
+}
+
+impl UiRegistry {
+    /// Register a widget template, making it available as a `Custom` widget type
+    pub fn register_template(&mut self, template: WidgetTemplateDefinition) -> Result<(), TemplateError> {
+        template.check()?;
+        self.register_widget_type(&template.name, super::WidgetTypeInfo {
+            display_name: template.name.clone(),
+            asset_path: None,
+            required_properties: template.required_params(),
+            optional_properties: template.optional_params(),
+            can_have_children: template.accepts_children(),
+        });
+        bevy_log::info!("Registered widget template '{}'", template.name);
+        self.templates.insert(template.name.clone(), template);
+        Ok(())
+    }
+
+    /// Parse and register a template file
+    pub fn register_template_source(&mut self, content: &str, format: UiDefinitionFormat) -> Result<(), TemplateError> {
+        self.register_template(WidgetTemplateDefinition::parse(content, format)?)
+    }
+
+    /// Get a registered widget template
+    pub fn get_template(&self, name: &str) -> Option<&WidgetTemplateDefinition> {
+        self.templates.get(name)
+    }
+
+    /// Set the function that expands a built-in widget type into primitives, replacing any
+    /// previous one
+    pub fn register_builtin_template(&mut self, widget_type: &str, expander: BuiltinTemplateExpander) {
+        self.builtin_templates.insert(widget_type.to_string(), expander);
+    }
+
+    /// Expand `node` into its root node followed by its parts if its widget type is a
+    /// built-in template, or return `None` for widgets spawned as a single entity
+    pub fn expand_builtin_template(&self, node: &WidgetNode) -> Option<Vec<WidgetNode>> {
+        let expander = self.builtin_templates.get(&self.extract_widget_type_name(&node.widget_type))?;
+        Some(expander(node))
+    }
+
+    /// Expand `node` if it instantiates a registered template.
+    ///
+    /// Templates used inside the template are expanded too, to any depth. The instance's own
+    /// fields (layout, style, behavior, classes, bindings, `when`, `repeat`) are merged over the template's root,
+    /// and its children are appended to the root's children unexpanded, like any authored node.
+    /// Ids inside the template are prefixed with the instance id, as in `card_title`.
+    pub fn expand_template_instance(&self, node: &WidgetNode) -> Result<Option<WidgetNode>, TemplateError> {
+        self.instantiate_node(node, &mut Vec::new())
+    }
+
+    fn instantiate_node(&self, node: &WidgetNode, stack: &mut Vec<String>) -> Result<Option<WidgetNode>, TemplateError> {
+        let WidgetType::Custom { component, properties } = &node.widget_type else {
+            return Ok(None);
+        };
+        let Some(template) = self.templates.get(component) else {
+            return Ok(None);
+        };
+        if stack.contains(component) {
+            let chain = stack.iter().chain(std::iter::once(component)).cloned().collect::<Vec<_>>().join(" -> ");
+            return Err(TemplateError::Cycle(chain));
+        }
+
+        stack.push(component.clone());
+        let expanded = template.instantiate(properties)
+            .and_then(|root| self.expand_declared(root, stack));
+        stack.pop();
+        let mut expanded = expanded?;
+
+        for child in expanded.children.iter_mut() {
+            scope_ids(child, node.id.as_deref());
+        }
+        let mut merged = serde_json::to_value(&expanded)
+            .map_err(|e| TemplateError::InvalidNode { template: component.clone(), reason: e.to_string() })?;
+        deep_merge(&mut merged, instance_overrides(node));
+        let mut merged: WidgetNode = serde_json::from_value(merged)
+            .map_err(|e| TemplateError::InvalidNode { template: component.clone(), reason: e.to_string() })?;
+
+        merged.id = node.id.clone();
+        merged.children.extend(node.children.iter().cloned());
+        Ok(Some(merged))
+    }
+
+    /// Expand every template instance in a node tree that a template declares
+    fn expand_declared(&self, node: WidgetNode, stack: &mut Vec<String>) -> Result<WidgetNode, TemplateError> {
+        let mut node = self.instantiate_node(&node, stack)?.unwrap_or(node);
+        node.children = std::mem::take(&mut node.children).into_iter()
+            .map(|child| self.expand_declared(child, stack))
+            .collect::<Result<_, _>>()?;
+        Ok(node)
+    }
+}
+
+/// The fields an instance node sets itself, as a document to merge over the template's root
+fn instance_overrides(node: &WidgetNode) -> Value {
+    let mut fields = Map::new();
+    for (key, value) in [
+        ("layout", serde_json::to_value(&node.layout)),
+        ("style", serde_json::to_value(&node.style)),
+        ("behavior", serde_json::to_value(&node.behavior)),
+        ("classes", serde_json::to_value(&node.classes)),
+        ("style_overrides", serde_json::to_value(&node.style_overrides)),
+        ("bindings", serde_json::to_value(&node.bindings)),
+        ("state_bindings", serde_json::to_value(&node.state_bindings)),
+        ("repeat", serde_json::to_value(&node.repeat)),
+        ("when", serde_json::to_value(&node.when)),
+        ("localized", serde_json::to_value(&node.localized)),
+    ] {
+        if let Ok(mut value) = value {
+            if !value.is_null() {
+                strip_nulls(&mut value);
+                fields.insert(key.to_string(), value);
+            }
+        }
+    }
+    Value::Object(fields)
+}
+
+/// Prefix the ids of a template's inner nodes with the instance id so instances don't collide.
+/// Without an instance id the inner nodes stay anonymous.
+pub(crate) fn scope_ids(node: &mut WidgetNode, instance_id: Option<&str>) {
+    node.id = match (instance_id, node.id.take()) {
+        (Some(instance_id), Some(id)) => Some(format!("{}_{}", instance_id, id)),
+        _ => None,
+    };
+    for child in node.children.iter_mut() {
+        scope_ids(child, instance_id);
+    }
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, field| !field.is_null());
+            fields.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+fn substitute_placeholders(value: &mut Value, args: &HashMap<String, Value>, template: &str) -> Result<(), TemplateError> {
+    let undeclared = |param: &str| TemplateError::UndeclaredParameter { template: template.to_string(), param: param.to_string() };
+
+    if let Some(name) = value.as_str().and_then(whole_placeholder).map(str::to_string) {
+        *value = args.get(&name).cloned().ok_or_else(|| undeclared(&name))?;
+        return Ok(());
+    }
+
+    match value {
+        Value::String(text) if text.contains(PLACEHOLDER_OPEN) => {
+            let mut result = String::new();
+            let mut rest = text.as_str();
+            while let Some((before, name, after)) = next_placeholder(rest) {
+                let arg = args.get(name).ok_or_else(|| undeclared(name))?;
+                result.push_str(before);
+                match arg {
+                    Value::String(arg) => result.push_str(arg),
+                    other => result.push_str(&other.to_string()),
+                }
+                rest = after;
+            }
+            result.push_str(rest);
+            *text = result;
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                substitute_placeholders(item, args, template)?;
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute_placeholders(field, args, template)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn collect_placeholders(value: &Value, placeholders: &mut Vec<String>) {
+    match value {
+        Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some((_, name, after)) = next_placeholder(rest) {
+                placeholders.push(name.to_string());
+                rest = after;
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_placeholders(item, placeholders)),
+        Value::Object(fields) => fields.values().for_each(|field| collect_placeholders(field, placeholders)),
+        _ => {}
+    }
+}
+
+/// The parameter name if `text` is exactly one placeholder
+pub(crate) fn whole_placeholder(text: &str) -> Option<&str> {
+    let (before, name, after) = next_placeholder(text)?;
+    (before.is_empty() && after.is_empty()).then_some(name)
+}
+
+/// Split `text` around its first placeholder into (text before, parameter name, text after)
+pub(crate) fn next_placeholder(text: &str) -> Option<(&str, &str, &str)> {
+    let start = text.find(PLACEHOLDER_OPEN)?;
+    let inner = &text[start + PLACEHOLDER_OPEN.len()..];
+    let end = inner.find(PLACEHOLDER_CLOSE)?;
+    Some((&text[..start], inner[..end].trim(), &inner[end + PLACEHOLDER_CLOSE.len()..]))
+}
\ No newline at end of file
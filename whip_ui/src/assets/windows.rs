@@ -0,0 +1,429 @@
+use bevy_asset::{Asset, AssetEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext};
+use bevy_color::Color;
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+use bevy_hierarchy::Parent;
+use bevy_log::{info, error, warn};
+use bevy_math::{IVec2, Vec2};
+use bevy_reflect::TypePath;
+use bevy_utils::default;
+use bevy_window::{MonitorSelection, PresentMode, PrimaryWindow, Window, WindowClosed, WindowMode, WindowPosition, WindowResolution};
+use bevy_winit::WinitWindows;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+use crate::assets::{LoadUiRequest, UiDefinition, UiDefinitionFormat, UiDefinitionLoaderError, UnloadUiRequest, WindowConfig, definitions::sorted_entries, loaders::parse_source};
+use crate::gui_framework::components::{ShapeData, Text};
+use crate::layout::UiNode;
+use crate::widgets::components::WidgetHierarchy;
+
+/// Height used for coordinate conversion when a widget's window cannot be found
+const FALLBACK_WINDOW_HEIGHT: f32 = 300.0;
+
+/// Width used to keep popups inside a window that cannot be found
+const FALLBACK_WINDOW_WIDTH: f32 = 400.0;
+
+/// Longest widget ancestry walked when looking for a widget's window
+const MAX_WINDOW_LOOKUP_DEPTH: usize = 256;
+
+/// An additional window declared by a UI definition, showing its own layout:
+///
+/// ```toml
+/// [windows.script_reader]
+/// layout = "ui/layouts/script_reader.toml"
+/// title = "Script Reader"
+/// size = [480.0, 640.0]
+/// background_color = { Hex = "#1E1E1E" }
+/// ```
+///
+/// The window opens when the declaring definition is spawned, and its layout is unloaded
+/// again when the user closes it. Requests can target it with [`LoadUiRequest::in_window`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WindowDeclaration {
+    /// UI definition spawned in the window
+    pub layout: String,
+    #[serde(flatten)]
+    pub config: WindowConfig,
+}
+
+/// Fullscreen setting of a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum WindowModeDef {
+    Windowed,
+    /// Borderless window covering the current monitor
+    Borderless,
+    /// Exclusive fullscreen at the monitor's current video mode
+    Exclusive,
+}
+
+impl WindowModeDef {
+    pub fn to_window_mode(self) -> WindowMode {
+        match self {
+            WindowModeDef::Windowed => WindowMode::Windowed,
+            WindowModeDef::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            WindowModeDef::Exclusive => WindowMode::Fullscreen(MonitorSelection::Current),
+        }
+    }
+}
+
+/// Presentation mode of a window, as in Bevy's `PresentMode`. Modes the GPU does not support
+/// fall back to `Fifo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PresentModeDef {
+    /// Vsync, without waiting for the next frame when a frame is late if possible
+    AutoVsync,
+    /// No vsync, avoiding tearing if possible
+    AutoNoVsync,
+    Fifo,
+    FifoRelaxed,
+    Immediate,
+    Mailbox,
+}
+
+impl PresentModeDef {
+    pub fn to_present_mode(self) -> PresentMode {
+        match self {
+            PresentModeDef::AutoVsync => PresentMode::AutoVsync,
+            PresentModeDef::AutoNoVsync => PresentMode::AutoNoVsync,
+            PresentModeDef::Fifo => PresentMode::Fifo,
+            PresentModeDef::FifoRelaxed => PresentMode::FifoRelaxed,
+            PresentModeDef::Immediate => PresentMode::Immediate,
+            PresentModeDef::Mailbox => PresentMode::Mailbox,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// A Bevy window with this configuration, titled `default_title` unless a title is set
+    pub fn to_window(&self, default_title: &str) -> Window {
+        let mut window = Window {
+            title: default_title.to_string(),
+            resolution: WindowResolution::new(self.size[0], self.size[1]),
+            transparent: self.transparent.unwrap_or_default(),
+            ..default()
+        };
+        self.apply_to_window(&mut window);
+        window
+    }
+
+    /// Apply the optional settings of this configuration to an open `window`, leaving the ones it
+    /// does not set as they are. The size is not applied, and neither is `transparent`: winit
+    /// fixes transparency when it creates the window, so a change is only logged.
+    pub fn apply_to_window(&self, window: &mut Window) {
+        if let Some(ref title) = self.title {
+            window.title = title.clone();
+        }
+        if let Some([min_width, min_height]) = self.min_size {
+            window.resize_constraints.min_width = min_width;
+            window.resize_constraints.min_height = min_height;
+        }
+        if let Some([max_width, max_height]) = self.max_size {
+            window.resize_constraints.max_width = max_width;
+            window.resize_constraints.max_height = max_height;
+        }
+        if let Some(resizable) = self.resizable {
+            window.resizable = resizable;
+        }
+        if let Some(decorations) = self.decorations {
+            window.decorations = decorations;
+        }
+        if let Some(fullscreen) = self.fullscreen {
+            window.mode = fullscreen.to_window_mode();
+        }
+        if let Some(present_mode) = self.present_mode {
+            window.present_mode = present_mode.to_present_mode();
+        }
+        if let Some(transparent) = self.transparent.filter(|transparent| *transparent != window.transparent) {
+            warn!("Ignoring transparent = {} for open window '{}': transparency is set when a window is created",
+                  transparent, window.title);
+        }
+        if let Some([x, y]) = self.position {
+            window.position = WindowPosition::At(IVec2::new(x, y));
+        }
+    }
+}
+
+/// The `window` settings of the UI definition file at `path`, read before the app runs so that
+/// the primary window is created with the settings winit only takes at creation
+pub fn read_window_config(path: &Path) -> Result<Option<WindowConfig>, UiDefinitionLoaderError> {
+    let content = std::fs::read_to_string(path)?;
+    let document: serde_json::Value = parse_source(&content, UiDefinitionFormat::from_path(path))?;
+    let Some(window) = document.get("window") else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_value(window.clone())?))
+}
+
+/// Errors that can occur while loading a window icon
+#[derive(Error, Debug)]
+pub enum WindowIconError {
+    #[error("Failed to read icon image: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to decode icon image: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("Invalid icon image: {0}")]
+    Icon(#[from] winit::window::BadIcon),
+}
+
+/// A window icon image, decoded to RGBA
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct WindowIconImage {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowIconImage {
+    /// Decode an icon from the contents of an image file
+    pub fn decode(bytes: &[u8]) -> Result<Self, WindowIconError> {
+        let image = image::load_from_memory(bytes)?.into_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(Self { rgba: image.into_raw(), width, height })
+    }
+
+    /// The winit icon showing this image
+    pub fn to_icon(&self) -> Result<winit::window::Icon, WindowIconError> {
+        Ok(winit::window::Icon::from_rgba(self.rgba.clone(), self.width, self.height)?)
+    }
+}
+
+/// Asset loader for PNG window icons
+#[derive(Default)]
+pub struct WindowIconLoader;
+
+impl AssetLoader for WindowIconLoader {
+    type Asset = WindowIconImage;
+    type Settings = ();
+    type Error = WindowIconError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn bevy_asset::io::Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        WindowIconImage::decode(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
+
+/// Icon (relative to the assets folder) to load for a window
+#[derive(Component, Debug, Clone)]
+pub struct PendingWindowIcon(pub String);
+
+/// Icon of a window, loaded through the asset server. Bevy windows have no icon setting, so it
+/// is set on the winit window once both exist, and again when the image is reloaded.
+#[derive(Component, Debug, Clone)]
+pub struct WindowIcon {
+    pub handle: Handle<WindowIconImage>,
+    applied: bool,
+}
+
+/// Marks a window opened from a [`WindowDeclaration`]
+#[derive(Component, Debug, Clone)]
+pub struct UiWindow {
+    /// Id the window is declared under
+    pub id: String,
+    /// Color the renderer clears the window to behind its widgets
+    pub background: Option<Color>,
+}
+
+/// The window a widget is drawn in and receives input from. Widgets without one, and without
+/// an ancestor that has one, belong to the primary window.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetWindow(pub Entity);
+
+/// Windows opened from declarations, by id
+#[derive(Resource, Debug, Default)]
+pub struct UiWindows {
+    windows: HashMap<String, Entity>,
+}
+
+impl UiWindows {
+    /// The window entity declared under `id`, if it is open
+    pub fn get(&self, id: &str) -> Option<Entity> {
+        self.windows.get(id).copied()
+    }
+
+    /// The id a window entity was declared under
+    pub fn id_of(&self, window: Entity) -> Option<&str> {
+        self.windows.iter().find(|(_, entity)| **entity == window).map(|(id, _)| id.as_str())
+    }
+
+    /// Stop tracking a window, returning the id it was declared under
+    pub fn remove_window(&mut self, window: Entity) -> Option<String> {
+        let id = self.id_of(window)?.to_string();
+        self.windows.remove(&id);
+        Some(id)
+    }
+}
+
+/// Open the windows `ui_definition` declares that are not open yet, returning the requests
+/// that spawn their layouts
+pub(crate) fn open_declared_windows(commands: &mut Commands, ui_windows: &mut UiWindows, ui_definition: &UiDefinition) -> Vec<LoadUiRequest> {
+    let Some(ref windows) = ui_definition.windows else {
+        return Vec::new();
+    };
+
+    let mut requests = Vec::new();
+    for (id, declaration) in sorted_entries(windows) {
+        // Spawning the declaring definition again must not open the window twice
+        if ui_windows.get(id).is_some() {
+            continue;
+        }
+        let mut window = commands.spawn((
+            declaration.config.to_window(id),
+            UiWindow {
+                id: id.clone(),
+                background: declaration.config.background_color.as_ref().map(|color| color.to_color()),
+            },
+            Name::new(format!("Window: {}", id)),
+        ));
+        if let Some(ref icon) = declaration.config.icon {
+            window.insert(PendingWindowIcon(icon.clone()));
+        }
+        let window = window.id();
+        info!("Opened window '{}' for {}", id, declaration.layout);
+        ui_windows.windows.insert(id.clone(), window);
+        requests.push(LoadUiRequest::new(&declaration.layout).in_window(id.clone()));
+    }
+    requests
+}
+
+/// Looks up which window widgets belong to, and how tall that window is
+#[derive(SystemParam)]
+pub struct WidgetWindows<'w, 's> {
+    links: Query<'w, 's, (Option<&'static TargetWindow>, Option<&'static WidgetHierarchy>, Option<&'static Parent>)>,
+    windows: Query<'w, 's, &'static Window>,
+    primary: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+impl WidgetWindows<'_, '_> {
+    /// The `TargetWindow` of `entity` or of its closest ancestor; `None` means the primary window
+    pub fn target_of(&self, entity: Entity) -> Option<Entity> {
+        let mut current = entity;
+        for _ in 0..MAX_WINDOW_LOOKUP_DEPTH {
+            let Ok((target, hierarchy, parent)) = self.links.get(current) else {
+                return None;
+            };
+            if let Some(target) = target {
+                return Some(target.0);
+            }
+            // Manually positioned widgets are linked to their parent only through the hierarchy
+            current = hierarchy.and_then(|hierarchy| hierarchy.parent).or_else(|| parent.map(Parent::get))?;
+        }
+        None
+    }
+
+    /// The window `entity` is drawn in
+    pub fn window_of(&self, entity: Entity) -> Option<Entity> {
+        self.target_of(entity).or_else(|| self.primary())
+    }
+
+    pub fn primary(&self) -> Option<Entity> {
+        self.primary.get_single().ok()
+    }
+
+    /// Logical height of `window`, or of the primary window if `None`
+    pub fn height(&self, window: Option<Entity>) -> f32 {
+        window.or_else(|| self.primary())
+            .and_then(|window| self.windows.get(window).ok())
+            .map(Window::height)
+            .unwrap_or(FALLBACK_WINDOW_HEIGHT)
+    }
+
+    /// Logical height of the window `entity` is drawn in
+    pub fn height_of(&self, entity: Entity) -> f32 {
+        self.height(self.window_of(entity))
+    }
+
+    /// Logical size of the window `entity` is drawn in
+    pub fn size_of(&self, entity: Entity) -> Vec2 {
+        self.window_of(entity)
+            .and_then(|window| self.windows.get(window).ok())
+            .map(|window| Vec2::new(window.width(), window.height()))
+            .unwrap_or(Vec2::new(FALLBACK_WINDOW_WIDTH, FALLBACK_WINDOW_HEIGHT))
+    }
+}
+
+/// System that gives new widgets, shapes and text the `TargetWindow` of their ancestors, so
+/// that rendering and input can group them by window without walking the hierarchy
+pub fn target_window_propagation_system(
+    mut commands: Commands,
+    added: Query<Entity, (Or<(Added<UiNode>, Added<ShapeData>, Added<Text>)>, Without<TargetWindow>)>,
+    widget_windows: WidgetWindows,
+) {
+    for entity in added.iter() {
+        if let Some(window) = widget_windows.target_of(entity) {
+            commands.entity(entity).try_insert(TargetWindow(window));
+        }
+    }
+}
+
+/// System that unloads the UI shown in declared windows once they are closed
+pub fn ui_window_closed_system(
+    mut closed_events: EventReader<WindowClosed>,
+    mut ui_windows: ResMut<UiWindows>,
+    mut unload_requests: EventWriter<UnloadUiRequest>,
+) {
+    for event in closed_events.read() {
+        if let Some(id) = ui_windows.remove_window(event.window) {
+            info!("Window '{}' closed, unloading its UI", id);
+            unload_requests.send(UnloadUiRequest::window(id));
+        }
+    }
+}
+
+/// System that starts loading the icons requested for windows
+pub fn window_icon_request_system(
+    mut commands: Commands,
+    pending: Query<(Entity, &PendingWindowIcon)>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, icon) in pending.iter() {
+        commands.entity(entity)
+            .remove::<PendingWindowIcon>()
+            .insert(WindowIcon { handle: asset_server.load(&icon.0), applied: false });
+    }
+}
+
+/// System that sets the icons of windows once their image is loaded and winit has created the
+/// window, and sets them again when the image changes
+pub fn window_icon_system(
+    mut icon_events: EventReader<AssetEvent<WindowIconImage>>,
+    mut windows: Query<(Entity, &mut WindowIcon)>,
+    icons: Res<Assets<WindowIconImage>>,
+    winit_windows: Option<NonSend<WinitWindows>>,
+) {
+    for event in icon_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            for (_, mut icon) in windows.iter_mut().filter(|(_, icon)| icon.handle.id() == *id) {
+                icon.applied = false;
+            }
+        }
+    }
+
+    let Some(winit_windows) = winit_windows else {
+        return;
+    };
+    for (entity, mut icon) in windows.iter_mut().filter(|(_, icon)| !icon.applied) {
+        let (Some(winit_window), Some(image)) = (winit_windows.get_window(entity), icons.get(&icon.handle)) else {
+            continue;
+        };
+        match image.to_icon() {
+            Ok(window_icon) => {
+                winit_window.set_window_icon(Some(window_icon));
+                info!("Set window icon: {:?}", icon.handle.path());
+            }
+            Err(e) => error!("Invalid window icon {:?}: {}", icon.handle.path(), e),
+        }
+        icon.applied = true;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use whip_ui::assets::{migrate_source, ui_definition_schema_string, UiDefinitionFormat, UiRegistry, CURRENT_FORMAT_VERSION};
+
+#[derive(Parser)]
+#[command(name = "whip-ui-tool")]
+#[command(about = "Tooling for whip_ui definition files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Export the JSON Schema for UI definition files (TOML and JSON)
+    Schema {
+        /// File to write the schema to; printed to stdout when omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Registry manifest whose widget types, actions and state types the schema should accept
+        #[arg(short, long)]
+        registry: Option<PathBuf>,
+    },
+    /// Upgrade UI definition files in place to the current format version
+    Migrate {
+        /// UI definition files (`.toml` or `.json`) to upgrade
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Only report which files are outdated, and exit with status 1 if any are
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Schema { output, registry: manifest } => {
+            let mut registry = UiRegistry::new();
+            if let Some(manifest) = manifest {
+                registry.load_manifest_file(&manifest)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", manifest.display(), e)))?;
+            }
+            let schema = ui_definition_schema_string(&registry);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, schema)?;
+                    eprintln!("Wrote UI definition schema to {}", path.display());
+                }
+                None => println!("{}", schema),
+            }
+        }
+        Commands::Migrate { files, check } => {
+            let mut outdated = 0;
+            for path in &files {
+                let content = std::fs::read_to_string(path)?;
+                let (report, rewritten) = migrate_source(&content, UiDefinitionFormat::from_path(path))
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?;
+                let Some(rewritten) = rewritten else {
+                    eprintln!("{}: already at format version {}", path.display(), CURRENT_FORMAT_VERSION);
+                    continue;
+                };
+                outdated += 1;
+                if check {
+                    eprintln!("{}: format version {} is outdated", path.display(), report.from_version);
+                    continue;
+                }
+                std::fs::write(path, rewritten)?;
+                eprintln!("{}: upgraded from format version {} to {}", path.display(), report.from_version, CURRENT_FORMAT_VERSION);
+                for step in &report.applied {
+                    eprintln!("  - {}", step);
+                }
+            }
+            if check && outdated > 0 {
+                std::process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -44,8 +44,8 @@ impl CommandParser {
         let input = input.trim();
         
         // Handle slash commands
-        if input.starts_with('/') {
-            let parts: Vec<&str> = input[1..].split_whitespace().collect();
+        if let Some(command) = input.strip_prefix('/') {
+            let parts: Vec<&str> = command.split_whitespace().collect();
             if parts.is_empty() {
                 return None;
             }
@@ -155,19 +155,14 @@ impl CliState {
                     }
                 }
             }
-            KeyCode::Up => {
-                // Only navigate logs when input is empty
-                if self.input_buffer.is_empty() && self.scroll_offset > 0 {
-                    self.scroll_offset -= 1;
-                    self.needs_redraw = true;
-                }
+            // Only navigate logs when input is empty
+            KeyCode::Up if self.input_buffer.is_empty() && self.scroll_offset > 0 => {
+                self.scroll_offset -= 1;
+                self.needs_redraw = true;
             }
-            KeyCode::Down => {
-                // Only navigate logs when input is empty
-                if self.input_buffer.is_empty() && self.scroll_offset < self.cached_logs.len().saturating_sub(1) {
-                    self.scroll_offset += 1;
-                    self.needs_redraw = true;
-                }
+            KeyCode::Down if self.input_buffer.is_empty() && self.scroll_offset < self.cached_logs.len().saturating_sub(1) => {
+                self.scroll_offset += 1;
+                self.needs_redraw = true;
             }
             KeyCode::Left => {
                 self.move_cursor_left();
@@ -198,38 +193,32 @@ impl CliState {
                     }
                 }
             }
-            KeyCode::PageUp => {
-                if self.input_buffer.is_empty() {
-                    let page_size = self.terminal_size.1.saturating_sub(6) as usize;
-                    let new_offset = self.scroll_offset.saturating_sub(page_size);
-                    if new_offset != self.scroll_offset {
-                        self.scroll_offset = new_offset;
-                        self.needs_redraw = true;
-                    }
+            KeyCode::PageUp if self.input_buffer.is_empty() => {
+                let page_size = self.terminal_size.1.saturating_sub(6) as usize;
+                let new_offset = self.scroll_offset.saturating_sub(page_size);
+                if new_offset != self.scroll_offset {
+                    self.scroll_offset = new_offset;
+                    self.needs_redraw = true;
                 }
             }
-            KeyCode::PageDown => {
-                if self.input_buffer.is_empty() {
-                    let page_size = self.terminal_size.1.saturating_sub(6) as usize;
-                    let new_offset = (self.scroll_offset + page_size)
-                        .min(self.cached_logs.len().saturating_sub(1));
-                    if new_offset != self.scroll_offset {
-                        self.scroll_offset = new_offset;
-                        self.needs_redraw = true;
-                    }
+            KeyCode::PageDown if self.input_buffer.is_empty() => {
+                let page_size = self.terminal_size.1.saturating_sub(6) as usize;
+                let new_offset = (self.scroll_offset + page_size)
+                    .min(self.cached_logs.len().saturating_sub(1));
+                if new_offset != self.scroll_offset {
+                    self.scroll_offset = new_offset;
+                    self.needs_redraw = true;
                 }
             }
             KeyCode::Backspace => {
                 self.delete_char();
                 self.needs_redraw = true;
             }
-            KeyCode::Delete => {
-                if self.cursor_position < self.input_buffer.chars().count() {
-                    // Move cursor right then delete to simulate delete key
-                    self.move_cursor_right();
-                    self.delete_char();
-                    self.needs_redraw = true;
-                }
+            KeyCode::Delete if self.cursor_position < self.input_buffer.chars().count() => {
+                // Move cursor right then delete to simulate delete key
+                self.move_cursor_right();
+                self.delete_char();
+                self.needs_redraw = true;
             }
             KeyCode::Esc => {
                 let had_input = !self.input_buffer.is_empty();
@@ -400,13 +389,11 @@ fn cli_event_loop(receiver: Receiver<CliThreadCommand>) -> Result<(), Box<dyn st
         // Only read if an event is available to prevent double processing
         if event::poll(Duration::from_millis(50))? {
             match event::read()? {
-                Event::Key(key) => {
-                    // Only process key press events to prevent double typing
-                    if key.kind == KeyEventKind::Press {
-                        if let Some(command) = state.handle_key(key) {
-                            if state.process_command(command, &log_store) {
-                                break; // Quit command
-                            }
+                // Only process key press events to prevent double typing
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if let Some(command) = state.handle_key(key) {
+                        if state.process_command(command, &log_store) {
+                            break; // Quit command
                         }
                     }
                 }
@@ -461,17 +448,12 @@ fn cli_event_loop(receiver: Receiver<CliThreadCommand>) -> Result<(), Box<dyn st
 }
 
 /// CLI plugin for Bevy
+#[derive(Default)]
 pub struct CliPlugin {
     /// Channel sender for controlling the CLI thread
     pub sender: Option<Sender<CliThreadCommand>>,
 }
 
-impl Default for CliPlugin {
-    fn default() -> Self {
-        Self { sender: None }
-    }
-}
-
 impl bevy_app::Plugin for CliPlugin {
     fn build(&self, _app: &mut bevy_app::App) {
         // The plugin doesn't add systems, just provides the launch capability
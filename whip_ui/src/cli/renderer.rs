@@ -51,6 +51,12 @@ pub struct BasicTerminalRenderer {
     terminal_size: (u16, u16),
 }
 
+impl Default for BasicTerminalRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BasicTerminalRenderer {
     /// Create a new basic terminal renderer
     pub fn new() -> Self {
@@ -143,8 +149,7 @@ impl TerminalRenderer for BasicTerminalRenderer {
             .skip(state.scroll_offset)
             .take(log_area_height as usize);
         
-        let mut line_num = header_lines;
-        for (idx, log) in visible_logs.enumerate() {
+        for (line_num, (idx, log)) in (header_lines..).zip(visible_logs.enumerate()) {
             stdout.queue(MoveTo(0, line_num))?;
             
             // Highlight selected log
@@ -167,8 +172,6 @@ impl TerminalRenderer for BasicTerminalRenderer {
                 stdout.queue(style::ResetColor)?;
             }
             stdout.queue(ResetColor)?;
-            
-            line_num += 1;
         }
         
         // Draw input area with borders
@@ -306,7 +309,7 @@ impl TerminalRenderer for RatatuiTerminalRenderer {
 
 impl RatatuiTerminalRenderer {
     /// Format a log entry as a ListItem (static version to avoid borrowing issues)
-    fn format_log_item_static(log: &LogData) -> ListItem {
+    fn format_log_item_static(log: &LogData) -> ListItem<'_> {
         let timestamp = log.metadata.timestamp
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
@@ -0,0 +1,19 @@
+use bevy_ecs::prelude::Component;
+use bevy_math::{Rect, Vec2};
+
+/// Component limiting where an entity is drawn and can be hit, in world coordinates.
+/// Scroll views give it to everything inside them.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ClipRect(pub Rect);
+
+impl ClipRect {
+    /// Whether a world position lies inside the clip region
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.0.contains(point)
+    }
+
+    /// The part of `self` that also lies inside `other`, for clips nested in one another
+    pub fn intersect(&self, other: &ClipRect) -> ClipRect {
+        ClipRect(self.0.intersect(other.0))
+    }
+}
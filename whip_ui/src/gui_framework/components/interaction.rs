@@ -1,4 +1,5 @@
 use bevy_ecs::prelude::Component;
+use bevy_math::Rect;
 use bevy_reflect::Reflect;
 
 /// Component defining how an entity can be interacted with via mouse/input.
@@ -17,4 +18,11 @@ impl Default for Interaction {
             draggable: false,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Component on entities that take mouse wheel and trackpad scrolling while the cursor is over
+/// `area`, given in world coordinates
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Scrollable {
+    pub area: Rect,
+}
@@ -0,0 +1,7 @@
+use bevy_ecs::prelude::Component;
+
+/// Marker on entities in the overlay layer. They are drawn after the rest of the widget tree,
+/// over it whatever their depth, and take clicks before it. Popups give it to everything inside
+/// them.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverlayLayer;
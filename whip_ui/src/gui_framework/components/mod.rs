@@ -2,12 +2,16 @@ pub mod shape_data;
 pub mod visibility;
 pub mod interaction;
 pub mod interaction_state;
+pub mod clip;
+pub mod layer;
 mod text_data;
 mod text_layout;
 
 pub use shape_data::{ShapeData, ShapeScaling};
 pub use visibility::Visibility;
-pub use interaction::Interaction;
+pub use interaction::{Interaction, Scrollable};
 pub use interaction_state::{InteractionState, InteractionStateChanged};
-pub use text_data::{Text, FontId, TextAlignment, EditableText, Focus, CursorState, CursorVisual, TextSelection};
+pub use clip::ClipRect;
+pub use layer::OverlayLayer;
+pub use text_data::{Text, FontId, TextAlignment, EditableText, Focus, CursorState, CursorVisual, TextSelection, SingleLineText, TextFilter, TextPattern};
 pub use text_layout::{TextLayoutOutput, PositionedGlyph, TextRenderData, TextBufferCache};
\ No newline at end of file
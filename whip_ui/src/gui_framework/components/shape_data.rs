@@ -6,9 +6,10 @@ use bevy_color::Color;
 use serde::{Deserialize, Serialize};
 
 /// Enum defining shape scaling behavior for Taffy layout integration
-#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize, Default)]
 pub enum ShapeScaling {
     /// No scaling - use vertices as-is (for hardcoded shapes)
+    #[default]
     Fixed,
     /// Scale vertices uniformly based on Taffy computed size
     Uniform,
@@ -16,12 +17,6 @@ pub enum ShapeScaling {
     Stretch,
 }
 
-impl Default for ShapeScaling {
-    fn default() -> Self {
-        ShapeScaling::Fixed
-    }
-}
-
 /// Component holding the visual representation data for an entity.
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct ShapeData {
@@ -159,16 +154,10 @@ impl ShapeData {
         self.vertices = Arc::new(scaled_vertices);
     }
     
-    /// Parse hex color string to Color (e.g., "#FF0000" -> red)
+    /// Parse hex color string to Color (e.g., "#FF0000" -> red), with the parser UI definitions use
     pub fn from_hex_color(hex: &str) -> Result<Color, &'static str> {
-        if !hex.starts_with('#') || hex.len() != 7 {
-            return Err("Invalid hex color format. Expected #RRGGBB");
-        }
-        
-        let r = u8::from_str_radix(&hex[1..3], 16).map_err(|_| "Invalid red component")?;
-        let g = u8::from_str_radix(&hex[3..5], 16).map_err(|_| "Invalid green component")?;
-        let b = u8::from_str_radix(&hex[5..7], 16).map_err(|_| "Invalid blue component")?;
-        
-        Ok(Color::srgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0))
+        hex.strip_prefix('#')
+            .and_then(|digits| crate::widgets::color::parse_hex_color(digits).ok())
+            .ok_or("Invalid hex color format. Expected #RGB, #RGBA, #RRGGBB or #RRGGBBAA")
     }
 }
\ No newline at end of file
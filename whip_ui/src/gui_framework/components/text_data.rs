@@ -77,4 +77,144 @@ pub struct CursorVisual;
 pub struct TextSelection {
     pub start: usize,
     pub end: usize,
+}
+
+/// Check made on the text of a [`SingleLineText`]. Typing only has to leave the start of an
+/// accepted text; the whole text is checked when it is submitted or committed.
+#[derive(Debug, Clone)]
+pub enum TextFilter {
+    /// Whole numbers, optionally negative
+    Integer,
+    /// Decimal numbers, optionally negative
+    Numeric,
+    /// Text a regular expression matches in full
+    Regex(TextPattern),
+}
+
+impl TextFilter {
+    /// Whether `text` can still become an accepted text as more is typed
+    pub fn accepts(&self, text: &str) -> bool {
+        let unsigned = text.strip_prefix('-').unwrap_or(text);
+        match self {
+            TextFilter::Integer => unsigned.chars().all(|c| c.is_ascii_digit()),
+            TextFilter::Numeric => {
+                unsigned.chars().all(|c| c.is_ascii_digit() || c == '.') && unsigned.matches('.').count() <= 1
+            }
+            TextFilter::Regex(pattern) => pattern.is_prefix_match(text),
+        }
+    }
+
+    /// Whether `text` is a complete accepted text; empty text always is
+    pub fn matches(&self, text: &str) -> bool {
+        let unsigned = text.strip_prefix('-').unwrap_or(text);
+        match self {
+            _ if text.is_empty() => true,
+            TextFilter::Integer | TextFilter::Numeric => {
+                self.accepts(text) && unsigned.chars().any(|c| c.is_ascii_digit())
+            }
+            TextFilter::Regex(pattern) => pattern.is_match(text),
+        }
+    }
+}
+
+/// A regular expression compiled twice: anchored at both ends for whole texts, and as a lazy
+/// DFA that tells whether a text is the start of a match
+#[derive(Debug, Clone)]
+pub struct TextPattern {
+    whole: regex::Regex,
+    prefix: Box<regex_automata::hybrid::dfa::DFA>,
+}
+
+impl TextPattern {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        use regex_automata::{hybrid::dfa::DFA, MatchKind};
+
+        let whole = regex::Regex::new(&format!("^(?:{})$", pattern))?;
+        // Anchored at the end, and reporting every match, the DFA only dies once no
+        // continuation of the text can match
+        let prefix = DFA::builder()
+            .configure(DFA::config().match_kind(MatchKind::All).unicode_word_boundary(true))
+            .build(&format!("(?:{})\\z", pattern))
+            .map_err(|e| regex::Error::Syntax(e.to_string()))?;
+        Ok(Self { whole, prefix: Box::new(prefix) })
+    }
+
+    /// Whether the pattern matches all of `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        self.whole.is_match(text)
+    }
+
+    /// Whether `text`, or some text starting with it, matches the pattern in full
+    pub fn is_prefix_match(&self, text: &str) -> bool {
+        use regex_automata::{util::start, Anchored};
+
+        // The lazy DFA gives up on inputs it cannot decide, such as word boundaries next to
+        // non-ASCII text; those are let through and left to the whole-text check
+        let mut cache = self.prefix.create_cache();
+        let Ok(mut state) = self.prefix.start_state(&mut cache, &start::Config::new().anchored(Anchored::Yes)) else {
+            return true;
+        };
+        for byte in text.bytes() {
+            match self.prefix.next_state(&mut cache, state, byte) {
+                Ok(next) if next.is_dead() => return false,
+                Ok(next) if next.is_quit() => return true,
+                Ok(next) => state = next,
+                Err(_) => return true,
+            }
+        }
+        true
+    }
+}
+
+/// Makes an editable text a single-line input: Enter sends `TextSubmitted` instead of inserting
+/// a newline, Tab is left alone, and typing is limited by `max_length` and `filter`.
+/// The text is shown on one line, masked if it has a `mask`, and scrolled sideways within `width`.
+#[derive(Component, Debug, Clone, Default)]
+pub struct SingleLineText {
+    /// Most characters the text takes
+    pub max_length: Option<usize>,
+    pub filter: Option<TextFilter>,
+    /// Character shown in place of every character of the text, for passwords
+    pub mask: Option<char>,
+    /// Width the text is visible in; glyphs scrolled out of it are not drawn
+    pub width: Option<f32>,
+    /// How far the text is scrolled to the left
+    pub scroll: f32,
+}
+
+impl SingleLineText {
+    /// The text as it is shown, with one character for every character of `text`
+    pub fn display_text(&self, text: &str) -> String {
+        text.chars().map(|c| match self.mask {
+            Some(mask) => mask,
+            None if c == '\n' => ' ',
+            None => c,
+        }).collect()
+    }
+
+    /// Scroll that keeps the cursor at `cursor_x` visible, without scrolling past the end of a
+    /// line `line_width` wide
+    pub fn scroll_to_show(&self, cursor_x: Option<f32>, line_width: f32) -> f32 {
+        let Some(width) = self.width else {
+            return 0.0;
+        };
+        let mut scroll = self.scroll;
+        if let Some(cursor_x) = cursor_x {
+            if cursor_x < scroll {
+                scroll = cursor_x;
+            } else if cursor_x > scroll + width {
+                scroll = cursor_x - width;
+            }
+        }
+        scroll.clamp(0.0, (line_width - width).max(0.0))
+    }
+
+    /// Whether a glyph from `x` to `x + glyph_width` lies within the visible width, give or take
+    /// half a pixel of rounding
+    pub fn shows(&self, x: f32, glyph_width: f32) -> bool {
+        match self.width {
+            Some(width) => x >= self.scroll - 0.5 && x + glyph_width <= self.scroll + width + 0.5,
+            None => true,
+        }
+    }
 }
\ No newline at end of file
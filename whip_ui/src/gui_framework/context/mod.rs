@@ -1,5 +1,5 @@
 pub mod vulkan_context;
 pub mod vulkan_setup;
 
-pub use self::vulkan_context::VulkanContext;
-pub use self::vulkan_setup::{setup_vulkan, cleanup_vulkan};
\ No newline at end of file
+pub use self::vulkan_context::{VulkanContext, WindowSurface};
+pub use self::vulkan_setup::{setup_vulkan, create_surface, cleanup_vulkan};
\ No newline at end of file
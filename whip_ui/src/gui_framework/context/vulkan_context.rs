@@ -2,6 +2,9 @@ use ash::vk;
 use ash::{Entry, Instance};
 use ash::khr::{surface, swapchain};
 use ash::ext::debug_utils;
+use bevy_ecs::entity::Entity;
+use bevy_window::PresentMode;
+use std::collections::HashMap;
 use std::sync::Arc;
 use vk_mem::Allocator;
 
@@ -10,54 +13,93 @@ pub struct VulkanContext {
     pub entry: Option<Entry>,
     pub instance: Option<Instance>,
     pub surface_loader: Option<surface::Instance>,
-    pub surface: Option<vk::SurfaceKHR>,
     pub device: Option<ash::Device>,
     pub physical_device: Option<vk::PhysicalDevice>,
     pub queue: Option<vk::Queue>,
     pub queue_family_index: Option<u32>,
     pub allocator: Option<Arc<Allocator>>,
     pub swapchain_loader: Option<swapchain::Device>,
-    pub swapchain: Option<vk::SwapchainKHR>,
-    pub current_swap_extent: vk::Extent2D,
-    pub images: Vec<vk::Image>,
-    pub image_views: Vec<vk::ImageView>,
-    // --- Depth Buffer Resources ---
-    pub depth_image: Option<vk::Image>,
-    pub depth_image_allocation: Option<vk_mem::Allocation>,
-    pub depth_image_view: Option<vk::ImageView>,
+    // Presentation resources of every window being drawn to, by window entity
+    pub surfaces: HashMap<Entity, WindowSurface>,
     pub depth_format: Option<vk::Format>,
-    // --- End Depth Buffer ---
     pub vertex_buffer: Option<vk::Buffer>,
     pub vertex_allocation: Option<vk_mem::Allocation>,
-    pub render_pass: Option<vk::RenderPass>,
-    pub framebuffers: Vec<vk::Framebuffer>,
+    pub render_pass: Option<vk::RenderPass>, // Shared by all windows
     pub shape_pipeline_layout: Option<vk::PipelineLayout>,
     pub text_pipeline_layout: Option<vk::PipelineLayout>,
     pub command_pool: Option<vk::CommandPool>,
-    pub command_buffers: Vec<vk::CommandBuffer>,
-    pub image_available_semaphore: Option<vk::Semaphore>,
-    pub render_finished_semaphore: Option<vk::Semaphore>,
-    pub fence: Option<vk::Fence>,
-    pub current_image: usize,
     // --- Debug Messenger Fields ---
     pub debug_utils_loader: Option<debug_utils::Instance>,
     pub debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
     pub debug_utils_device: Option<debug_utils::Device>,
 }
 
+impl Default for VulkanContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl VulkanContext {
     pub fn new() -> Self {
         Self {
             entry: None,
             instance: None,
             surface_loader: None,
-            surface: None,
             device: None,
             physical_device: None, 
             queue: None,
             queue_family_index: None,
             allocator: None,
             swapchain_loader: None,
+            surfaces: HashMap::new(),
+            depth_format: None,
+            vertex_buffer: None,
+            vertex_allocation: None,
+            render_pass: None,
+            shape_pipeline_layout: None,
+            text_pipeline_layout: None,
+            command_pool: None,
+            // --- Debug Messenger Fields ---
+            debug_utils_loader: None,
+            debug_messenger: None,
+            debug_utils_device: None,
+        }
+    }
+}
+
+/// Presentation resources of one window. The device, pipelines and render pass are shared
+/// through `VulkanContext`; everything a window presents with is kept here.
+pub struct WindowSurface {
+    pub surface: vk::SurfaceKHR,
+    pub swapchain: Option<vk::SwapchainKHR>,
+    pub current_swap_extent: vk::Extent2D,
+    pub images: Vec<vk::Image>,
+    pub image_views: Vec<vk::ImageView>,
+    // --- Depth Buffer Resources ---
+    pub depth_image: Option<vk::Image>,
+    pub depth_image_allocation: Option<vk_mem::Allocation>,
+    pub depth_image_view: Option<vk::ImageView>,
+    // --- End Depth Buffer ---
+    pub framebuffers: Vec<vk::Framebuffer>,
+    pub command_buffers: Vec<vk::CommandBuffer>,
+    pub image_available_semaphore: Option<vk::Semaphore>,
+    pub render_finished_semaphore: Option<vk::Semaphore>,
+    pub fence: Option<vk::Fence>,
+    pub current_image: usize,
+    // Orthographic projection for this window's logical size (Set 0, binding 0)
+    pub projection_buffer: Option<vk::Buffer>,
+    pub projection_allocation: Option<vk_mem::Allocation>,
+    pub clear_color: [f32; 4],
+    // Taken from the window when the swapchain is created
+    pub present_mode: PresentMode,
+    pub transparent: bool,
+}
+
+impl WindowSurface {
+    pub fn new(surface: vk::SurfaceKHR) -> Self {
+        Self {
+            surface,
             swapchain: None,
             current_swap_extent: vk::Extent2D { width: 0, height: 0 },
             images: Vec::new(),
@@ -66,24 +108,18 @@ impl VulkanContext {
             depth_image: None,
             depth_image_allocation: None,
             depth_image_view: None,
-            depth_format: None,
             // --- End Depth Buffer ---
-            vertex_buffer: None,
-            vertex_allocation: None,
-            render_pass: None,
             framebuffers: Vec::new(),
-            shape_pipeline_layout: None,
-            text_pipeline_layout: None,
-            command_pool: None,
             command_buffers: Vec::new(),
             image_available_semaphore: None,
             render_finished_semaphore: None,
             fence: None,
             current_image: 0,
-            // --- Debug Messenger Fields ---
-            debug_utils_loader: None,
-            debug_messenger: None,
-            debug_utils_device: None,
+            projection_buffer: None,
+            projection_allocation: None,
+            clear_color: [0.1, 0.1, 0.1, 1.0],
+            present_mode: PresentMode::AutoVsync,
+            transparent: false,
         }
     }
 }
\ No newline at end of file
@@ -102,7 +102,9 @@ pub fn set_debug_object_name<T: vk::Handle>(
     }
 }
 
-pub fn setup_vulkan(app: &mut VulkanContext, window: &winit::window::Window) {
+// Creates the instance, device and allocator, returning the surface of `window`, which the device
+// was chosen to present to.
+pub fn setup_vulkan(app: &mut VulkanContext, window: &winit::window::Window) -> vk::SurfaceKHR {
     // Get handles directly from the window reference
     let display_handle = window.display_handle()
         .expect("Failed to get display handle from winit window")
@@ -133,9 +135,7 @@ pub fn setup_vulkan(app: &mut VulkanContext, window: &winit::window::Window) {
     //let layers = []; // Use this if no validation required
     // Enable validation layers in debug builds
     #[cfg(debug_assertions)]
-    let layers = unsafe {
-        [std::ffi::CStr::from_bytes_with_nul_unchecked(b"VK_LAYER_KHRONOS_validation\0").as_ptr()]
-    };
+    let layers = [c"VK_LAYER_KHRONOS_validation".as_ptr()];
     #[cfg(not(debug_assertions))]
     let layers = [];
     #[cfg(debug_assertions)]
@@ -203,7 +203,6 @@ pub fn setup_vulkan(app: &mut VulkanContext, window: &winit::window::Window) {
         )
     }
     .expect("Failed to create Vulkan surface");
-    app.surface = Some(surface);
     info!("[setup_vulkan] Vulkan surface created.");
 
     info!("[setup_vulkan] Selecting physical device and queue family...");
@@ -297,6 +296,30 @@ pub fn setup_vulkan(app: &mut VulkanContext, window: &winit::window::Window) {
     app.allocator = Some(allocator);
     info!("[setup_vulkan] vk-mem allocator created.");
     info!("[setup_vulkan] Setup complete.");
+    surface
+}
+
+// Creates a surface for a window opened after setup, checking that the selected queue family
+// can present to it.
+pub fn create_surface(app: &VulkanContext, window: &winit::window::Window) -> Result<vk::SurfaceKHR, vk::Result> {
+    let entry = app.entry.as_ref().expect("Entry not available for surface creation");
+    let instance = app.instance.as_ref().expect("Instance not available for surface creation");
+    let surface_loader = app.surface_loader.as_ref().expect("Surface loader not available");
+    let physical_device = app.physical_device.expect("Physical device not set in VulkanContext");
+    let queue_family_index = app.queue_family_index.expect("Queue family index not set");
+
+    let display_handle = window.display_handle().map_err(|_| vk::Result::ERROR_INITIALIZATION_FAILED)?.as_raw();
+    let window_handle = window.window_handle().map_err(|_| vk::Result::ERROR_INITIALIZATION_FAILED)?.as_raw();
+    let surface = unsafe { ash_window::create_surface(entry, instance, display_handle, window_handle, None) }?;
+
+    let supported = unsafe {
+        surface_loader.get_physical_device_surface_support(physical_device, queue_family_index, surface)
+    }.unwrap_or(false);
+    if !supported {
+        unsafe { surface_loader.destroy_surface(surface, None); }
+        return Err(vk::Result::ERROR_INCOMPATIBLE_DISPLAY_KHR);
+    }
+    Ok(surface)
 }
 
 // This function now takes the VulkanContext by value, consuming it.
@@ -310,21 +333,26 @@ pub fn cleanup_vulkan(mut app: VulkanContext) {
     let allocator_to_drop = app.allocator.take();
     let device_to_destroy = app.device.take();
     let instance_to_destroy = app.instance.take();
-    let surface_loader_to_drop = app.surface_loader.take();
-    let surface_to_destroy = app.surface.take();
     let debug_loader_to_drop = app.debug_utils_loader.take();
     let debug_messenger_to_destroy = app.debug_messenger.take();
 
     // The order of destruction is critical:
-    // 1. Destroy objects created from the device (command pools, sync objects).
+    // 1. Destroy objects created from the device (render pass, layouts, command pool).
     // 2. Drop the Allocator Arc.
     // 3. Destroy the logical device.
-    // 4. Destroy the surface.
-    // 5. Destroy the debug messenger.
-    // 6. Destroy the instance.
+    // 4. Destroy the debug messenger.
+    // 5. Destroy the instance.
+    // Window surfaces and their sync objects are destroyed by the caller beforehand.
+    if !app.surfaces.is_empty() {
+        warn!("[cleanup_vulkan] {} window surface(s) were not destroyed before cleanup.", app.surfaces.len());
+    }
 
     if let Some(device) = &device_to_destroy {
         unsafe {
+            if let Some(rp) = app.render_pass.take() {
+                info!("[cleanup_vulkan] Destroying RenderPass {:?}.", rp);
+                device.destroy_render_pass(rp, None);
+            }
             if let Some(layout) = app.shape_pipeline_layout.take() {
                 device.destroy_pipeline_layout(layout, None);
             }
@@ -334,15 +362,6 @@ pub fn cleanup_vulkan(mut app: VulkanContext) {
             if let Some(pool) = app.command_pool.take() {
                 device.destroy_command_pool(pool, None);
             }
-            if let Some(sema) = app.image_available_semaphore.take() {
-                device.destroy_semaphore(sema, None);
-            }
-            if let Some(sema) = app.render_finished_semaphore.take() {
-                device.destroy_semaphore(sema, None);
-            }
-            if let Some(fen) = app.fence.take() {
-                device.destroy_fence(fen, None);
-            }
         }
     }
 
@@ -358,13 +377,8 @@ pub fn cleanup_vulkan(mut app: VulkanContext) {
         unsafe { device.destroy_device(None); }
     }
 
-    // Destroy the surface and debug messenger (they depend on the instance).
-    if let (Some(_instance), Some(surface_loader), Some(surface)) =
-        (&instance_to_destroy, surface_loader_to_drop, surface_to_destroy)
-    {
-        info!("[cleanup_vulkan] Destroying surface...");
-        unsafe { surface_loader.destroy_surface(surface, None); }
-    }
+    // The surface loader is no longer needed; the debug messenger depends on the instance.
+    app.surface_loader = None;
 
     #[cfg(debug_assertions)]
     if let (Some(loader), Some(messenger)) = (debug_loader_to_drop, debug_messenger_to_destroy) {
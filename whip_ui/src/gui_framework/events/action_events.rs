@@ -86,6 +86,10 @@ pub enum BuiltinAction {
     UpdateText { target_id: String, new_text: String },
     /// Trigger focus on an entity
     SetFocus { target_id: String },
+    /// Apply a registered operation to a `UiState` value
+    UpdateState { path: String, operation: crate::assets::StateOperation },
+    /// Switch the locale of localized text and of font shaping
+    SetLocale { locale: String },
 }
 
 impl BuiltinAction {
@@ -114,6 +118,23 @@ impl BuiltinAction {
                 let target_id = event.get_string_param("target_id")?;
                 Some(BuiltinAction::SetFocus { target_id })
             }
+            "set_state" | "toggle_state" | "increment_state" | "decrement_state" | "append_state" | "push_state" => {
+                use crate::assets::StateOperation;
+                let path = event.get_string_param("path")?;
+                let operation = match event.action.as_str() {
+                    "set_state" => StateOperation::Set(event.params.as_ref()?.get("value")?.clone()),
+                    "toggle_state" => StateOperation::Toggle,
+                    "increment_state" => StateOperation::Increment,
+                    "decrement_state" => StateOperation::Decrement,
+                    "push_state" => StateOperation::Push(event.params.as_ref()?.get("item")?.clone()),
+                    _ => StateOperation::Append(event.get_string_param("text")?),
+                };
+                Some(BuiltinAction::UpdateState { path, operation })
+            }
+            "set_locale" => {
+                let locale = event.get_string_param("locale")?;
+                Some(BuiltinAction::SetLocale { locale })
+            }
             _ => None,
         }
     }
@@ -153,7 +174,8 @@ pub trait ActionHandler: Send + Sync {
 impl BuiltinAction {
     /// Check if an action name corresponds to a builtin action
     pub fn is_builtin(action_name: &str) -> bool {
-        matches!(action_name, "debug" | "navigate" | "toggle_visibility" | "update_text" | "set_focus")
+        matches!(action_name, "debug" | "navigate" | "toggle_visibility" | "update_text" | "set_focus"
+            | "set_state" | "toggle_state" | "increment_state" | "decrement_state" | "append_state" | "push_state" | "set_locale")
     }
 }
 
@@ -185,8 +207,8 @@ mod tests {
         let entity = world.spawn_empty().id();
         
         let mut params = HashMap::new();
-        params.insert("message".to_string(), toml::Value::String("Hello".to_string()));
-        params.insert("count".to_string(), toml::Value::Integer(42));
+        params.insert("message".to_string(), serde_json::Value::String("Hello".to_string()));
+        params.insert("count".to_string(), serde_json::Value::from(42));
         
         let action = ActionEvent::new(
             "debug".to_string(),
@@ -205,7 +227,7 @@ mod tests {
         let entity = world.spawn_empty().id();
         
         let mut params = HashMap::new();
-        params.insert("message".to_string(), toml::Value::String("Test message".to_string()));
+        params.insert("message".to_string(), serde_json::Value::String("Test message".to_string()));
         
         let action_event = ActionEvent::new(
             "debug".to_string(),
@@ -9,6 +9,17 @@ pub struct EntityClicked {
     // Add button, click count, etc. if needed later
 }
 
+/// Event sent for every left mouse button press in a window, whatever it lands on. Popups use it
+/// to close when the press is outside them.
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct PointerPressed {
+    pub window: Entity,
+    /// Where the press happened, in world coordinates
+    pub position: Vec2,
+    /// The topmost widget under the pointer, if any
+    pub entity: Option<Entity>,
+}
+
 /// Event sent when a draggable entity is being dragged.
 #[derive(Event, Debug, Clone, Copy, Reflect)] // Added Reflect
 pub struct EntityDragged {
@@ -17,6 +28,15 @@ pub struct EntityDragged {
     pub delta: Vec2,
 }
 
+/// Event sent when the mouse wheel or trackpad scrolls over a `Scrollable` entity.
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct EntityScrolled {
+    pub entity: Entity,
+    /// Distance scrolled in pixels; positive values scroll up and left, towards the start of the
+    /// content.
+    pub delta: Vec2,
+}
+
 /// Event sent when a configured hotkey combination is pressed.
 #[derive(Event, Debug, Clone, Reflect)] // Added Reflect
 pub struct HotkeyActionTriggered {
@@ -35,4 +55,10 @@ pub struct YrsTextChanged {
 pub struct TextFocusChanged {
     /// The entity that gained focus, or None if focus was lost.
     pub entity: Option<Entity>,
+}
+
+/// Event sent when Enter is pressed in a focused single-line text.
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct TextSubmitted {
+    pub entity: Entity,
 }
\ No newline at end of file
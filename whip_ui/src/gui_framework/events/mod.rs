@@ -1,5 +1,5 @@
 pub mod interaction_events;
 pub mod action_events;
 
-pub use interaction_events::{EntityClicked, EntityDragged, HotkeyActionTriggered, YrsTextChanged, TextFocusChanged};
+pub use interaction_events::{EntityClicked, EntityDragged, EntityScrolled, HotkeyActionTriggered, PointerPressed, YrsTextChanged, TextFocusChanged, TextSubmitted};
 pub use action_events::{ActionEvent, BuiltinAction, ActionRegistry, ActionHandler};
\ No newline at end of file
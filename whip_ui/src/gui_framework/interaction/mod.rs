@@ -1,4 +1,5 @@
 pub mod hotkeys;
 pub mod utils;
 pub mod text_drag;
-pub mod text_editing;
\ No newline at end of file
+pub mod text_editing;
+pub mod single_line;
\ No newline at end of file
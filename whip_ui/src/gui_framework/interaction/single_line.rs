@@ -0,0 +1,119 @@
+use bevy_input::keyboard::Key;
+
+use crate::gui_framework::components::SingleLineText;
+
+/// Key presses a single-line text reacts to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineEditKey {
+    Insert(String),
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Submit,
+}
+
+impl LineEditKey {
+    pub fn from_key(key: &Key) -> Option<Self> {
+        match key {
+            Key::Character(chars) => Some(LineEditKey::Insert(chars.to_string())),
+            Key::Space => Some(LineEditKey::Insert(" ".to_string())),
+            Key::Backspace => Some(LineEditKey::Backspace),
+            Key::Delete => Some(LineEditKey::Delete),
+            Key::ArrowLeft => Some(LineEditKey::Left),
+            Key::ArrowRight => Some(LineEditKey::Right),
+            Key::Home => Some(LineEditKey::Home),
+            Key::End => Some(LineEditKey::End),
+            Key::Enter => Some(LineEditKey::Submit),
+            _ => None,
+        }
+    }
+}
+
+/// A change made to the text by a [`LineEditor`], in bytes of the text before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEdit {
+    pub offset: usize,
+    pub removed: usize,
+    pub inserted: String,
+}
+
+/// Text of a single-line text being edited, with the cursor and the selection in characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEditor {
+    pub text: String,
+    /// Number of characters before the cursor
+    pub cursor: usize,
+    /// Where the selection started; the same as `cursor` when nothing is selected
+    pub anchor: usize,
+}
+
+impl LineEditor {
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn selection(&self) -> (usize, usize) {
+        (self.cursor.min(self.anchor), self.cursor.max(self.anchor))
+    }
+
+    /// Apply a key press, extending the selection if `select` is set, and return the change it
+    /// made to the text. Typed text is cut to `limits.max_length` and dropped if
+    /// `limits.filter` rejects the result.
+    pub fn apply(&mut self, key: &LineEditKey, select: bool, limits: &SingleLineText) -> Option<LineEdit> {
+        let (start, end) = self.selection();
+        let collapse = start != end && !select;
+        match key {
+            LineEditKey::Left => self.move_to(if collapse { start } else { self.cursor.saturating_sub(1) }, select),
+            LineEditKey::Right => self.move_to(if collapse { end } else { (self.cursor + 1).min(self.len()) }, select),
+            LineEditKey::Home => self.move_to(0, select),
+            LineEditKey::End => self.move_to(self.len(), select),
+            LineEditKey::Backspace if start != end => self.replace(start, end, ""),
+            LineEditKey::Backspace if start > 0 => self.replace(start - 1, start, ""),
+            LineEditKey::Delete if start != end => self.replace(start, end, ""),
+            LineEditKey::Delete if end < self.len() => self.replace(end, end + 1, ""),
+            LineEditKey::Insert(chars) => {
+                let room = limits.max_length.map_or(usize::MAX, |max| max.saturating_sub(self.len() - (end - start)));
+                let typed: String = chars.chars().filter(|c| !c.is_control()).take(room).collect();
+                if typed.is_empty() {
+                    return None;
+                }
+                let mut result = self.text.clone();
+                result.replace_range(byte_offset(&self.text, start)..byte_offset(&self.text, end), &typed);
+                if limits.filter.as_ref().is_some_and(|filter| !filter.accepts(&result)) {
+                    return None;
+                }
+                self.replace(start, end, &typed)
+            }
+            _ => None,
+        }
+    }
+
+    fn move_to(&mut self, cursor: usize, select: bool) -> Option<LineEdit> {
+        self.cursor = cursor;
+        if !select {
+            self.anchor = cursor;
+        }
+        None
+    }
+
+    fn replace(&mut self, start: usize, end: usize, inserted: &str) -> Option<LineEdit> {
+        let (from, to) = (byte_offset(&self.text, start), byte_offset(&self.text, end));
+        self.text.replace_range(from..to, inserted);
+        self.cursor = start + inserted.chars().count();
+        self.anchor = self.cursor;
+        Some(LineEdit { offset: from, removed: to - from, inserted: inserted.to_string() })
+    }
+}
+
+/// Number of characters of `text` before byte `offset`
+pub fn char_index(text: &str, offset: usize) -> usize {
+    text.char_indices().take_while(|(index, _)| *index < offset).count()
+}
+
+/// Byte offset of character `index` of `text`, or its length past the last character
+pub fn byte_offset(text: &str, index: usize) -> usize {
+    text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+}
\ No newline at end of file
@@ -1,12 +1,13 @@
 use bevy_ecs::prelude::*;
-use bevy_window::{PrimaryWindow, Window, CursorMoved};
+use bevy_window::CursorMoved;
 use bevy_transform::prelude::GlobalTransform;
 use bevy_math::{Vec2, Affine3A};
 
 use crate::gui_framework::{
-    components::{Focus, TextSelection, TextBufferCache},
+    components::{Focus, TextSelection, TextBufferCache, SingleLineText},
     interaction::utils::get_cursor_at_position,
 };
+use crate::assets::WidgetWindows;
 use super::super::plugins::interaction::MouseContext; // Access MouseContext defined in interaction plugin
 
 /// System responsible for handling text selection via dragging.
@@ -14,12 +15,12 @@ use super::super::plugins::interaction::MouseContext; // Access MouseContext def
 pub(crate) fn text_drag_selection_system(
     // Input resources & events
     mut cursor_moved_events: EventReader<CursorMoved>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    widget_windows: WidgetWindows,
     mouse_context: Res<MouseContext>,
     // Use ParamSet for conflicting TextSelection access
     mut text_queries: ParamSet<(
         // p0: Query for focused entity
-        Query<(Entity, &GlobalTransform, &TextBufferCache), With<Focus>>,
+        Query<(Entity, &GlobalTransform, &TextBufferCache, Option<&SingleLineText>), With<Focus>>,
         // p1: Query for mutable access to TextSelection (used after finding the entity)
         Query<&mut TextSelection>,
     )>,
@@ -30,11 +31,12 @@ pub(crate) fn text_drag_selection_system(
         return;
     }
 
-    let Ok(primary_window) = windows.get_single() else { return; };
-    let window_height = primary_window.height();
+    // Selection follows the cursor in the window the focused text is drawn in
+    let drag_window = text_queries.p0().get_single().ok().and_then(|(entity, _, _, _)| widget_windows.window_of(entity));
+    let window_height = widget_windows.height(drag_window);
 
     // Get the latest cursor position from the events
-    let latest_cursor_pos_window = cursor_moved_events.read().last().map(|e| e.position);
+    let latest_cursor_pos_window = cursor_moved_events.read().filter(|e| Some(e.window) == drag_window).last().map(|e| e.position);
     cursor_moved_events.clear(); // Consume events
 
     if let Some(cursor_pos_window) = latest_cursor_pos_window {
@@ -42,7 +44,7 @@ pub(crate) fn text_drag_selection_system(
 
         // Find the focused entity
         // Use p0 (immutable query) to find the focused entity first
-        if let Ok((focused_entity, transform, text_cache)) = text_queries.p0().get_single() {
+        if let Ok((focused_entity, transform, text_cache, single_line)) = text_queries.p0().get_single() {
             // Clone necessary data because we can't hold the immutable borrow from p0
             // while trying to get a mutable borrow from p1 later.
             let focused_entity_id = focused_entity; // Clone Entity ID
@@ -53,8 +55,9 @@ pub(crate) fn text_drag_selection_system(
             let inverse_transform: Affine3A = transform.affine().inverse();
             let cursor_pos_local_yup = inverse_transform.transform_point3(cursor_pos_world.extend(0.0)).truncate();
 
-            // Convert to local Y-down for cosmic-text
-            let cursor_pos_local_ydown = Vec2::new(cursor_pos_local_yup.x, -cursor_pos_local_yup.y);
+            // Convert to local Y-down for cosmic-text, in view of any scrolling
+            let scroll = single_line.map_or(0.0, |single_line| single_line.scroll);
+            let cursor_pos_local_ydown = Vec2::new(cursor_pos_local_yup.x + scroll, -cursor_pos_local_yup.y);
 
             // Use the utility function to find the character index under the cursor
             if let Some(hit_cursor) = get_cursor_at_position(buffer, cursor_pos_local_ydown) {
@@ -3,15 +3,16 @@ use bevy_input::{keyboard::{KeyCode, KeyboardInput, Key}, ButtonInput};
 use bevy_log::info;
 use cosmic_text::{Editor, Motion, Action, CacheKey, CacheKeyFlags, Edit};
 use bevy_math::Vec2;
-use yrs::{Transact, Text};
+use yrs::{GetString, Transact, Text};
 use similar::{ChangeTag, TextDiff};
 
 use crate::{
     YrsDocResource,
     gui_framework::{
-        components::{Focus, CursorState, TextSelection, TextBufferCache, EditableText, TextLayoutOutput, PositionedGlyph},
-        events::YrsTextChanged,
+        components::{Focus, CursorState, TextSelection, TextBufferCache, EditableText, TextLayoutOutput, PositionedGlyph, SingleLineText},
+        events::{YrsTextChanged, TextSubmitted},
         interaction::utils::{global_to_local_cursor, cosmic_cursor_to_global_index},
+        interaction::single_line::{byte_offset, char_index, LineEditKey, LineEditor},
     },
     FontServerResource,
     GlyphAtlasResource,
@@ -22,15 +23,16 @@ pub(crate) fn text_editing_system(
     mut commands: Commands,
     mut keyboard_input_events: EventReader<KeyboardInput>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut focused_query: Query<(Entity, &mut CursorState, &mut TextSelection, &mut TextBufferCache), (With<Focus>, With<EditableText>)>,
+    mut focused_query: Query<(Entity, &mut CursorState, &mut TextSelection, &mut TextBufferCache, Option<&SingleLineText>), (With<Focus>, With<EditableText>)>,
     yrs_doc_res: Res<YrsDocResource>,
     mut yrs_text_changed_writer: EventWriter<YrsTextChanged>,
+    mut text_submitted_writer: EventWriter<TextSubmitted>,
     font_system_res: Res<FontServerResource>,
     glyph_atlas_res: Res<GlyphAtlasResource>,
     swash_cache_res: Res<SwashCacheResource>,
     vk_context_res: Res<crate::VulkanContextResource>,
 ) {
-    let Ok((entity, mut cursor_state, mut selection, mut text_cache)) = focused_query.get_single_mut() else {
+    let Ok((entity, mut cursor_state, mut selection, mut text_cache, single_line)) = focused_query.get_single_mut() else {
         keyboard_input_events.clear();
         return;
     };
@@ -38,6 +40,12 @@ pub(crate) fn text_editing_system(
     let mut action_taken = false;
     let shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
 
+    if let Some(single_line) = single_line {
+        let key_events: Vec<KeyboardInput> = keyboard_input_events.read().cloned().collect();
+        edit_single_line(entity, single_line, &key_events, shift_pressed, &mut cursor_state, &mut selection, &yrs_doc_res, &mut yrs_text_changed_writer, &mut text_submitted_writer);
+        return;
+    }
+
     // --- Determine if any modification action should be taken ---
     let mut cosmic_action: Option<Action> = None;
     if keyboard_input.just_pressed(KeyCode::Backspace) { cosmic_action = Some(Action::Backspace); }
@@ -118,7 +126,7 @@ pub(crate) fn text_editing_system(
                 for layout_glyph in run.glyphs.iter() {
                     let (cache_key, _, _) = CacheKey::new(layout_glyph.font_id, layout_glyph.glyph_id, layout_glyph.font_size, (layout_glyph.x, layout_glyph.y), CacheKeyFlags::empty());
                     let Some(swash_image) = swash_cache.get_image(&mut font_system_guard.font_system, cache_key) else { continue; };
-                    if let Ok(glyph_info) = glyph_atlas.add_glyph(&device, queue, command_pool, &allocator, cache_key, &swash_image) {
+                    if let Ok(glyph_info) = glyph_atlas.add_glyph(&device, queue, command_pool, &allocator, cache_key, swash_image) {
                         let placement = swash_image.placement;
                         let width = placement.width as f32;
                         let height = placement.height as f32;
@@ -197,4 +205,67 @@ pub(crate) fn text_editing_system(
     if action_taken {
         keyboard_input_events.clear();
     }
+}
+
+/// Edit a focused single-line text straight in yrs, one key press at a time so that held keys
+/// repeat. `text_layout_system` lays the text out again from yrs.
+#[allow(clippy::too_many_arguments)]
+fn edit_single_line(
+    entity: Entity,
+    single_line: &SingleLineText,
+    key_events: &[KeyboardInput],
+    select: bool,
+    cursor_state: &mut CursorState,
+    selection: &mut TextSelection,
+    yrs_doc_res: &YrsDocResource,
+    yrs_text_changed_writer: &mut EventWriter<YrsTextChanged>,
+    text_submitted_writer: &mut EventWriter<TextSubmitted>,
+) {
+    let keys: Vec<LineEditKey> = key_events.iter()
+        .filter(|event| event.state.is_pressed())
+        .filter_map(|event| LineEditKey::from_key(&event.logical_key))
+        .collect();
+    if keys.is_empty() {
+        return;
+    }
+    let Ok(text_map) = yrs_doc_res.text_map.lock() else {
+        return;
+    };
+    let Some(yrs_text) = text_map.get(&entity) else {
+        return;
+    };
+
+    // The selection is in byte offsets into the shown text, which may be masked; its end is
+    // where the cursor is, also after a drag selection
+    let text = yrs_text.get_string(&yrs_doc_res.doc.transact());
+    let shown = single_line.display_text(&text);
+    let mut editor = LineEditor {
+        cursor: char_index(&shown, selection.end),
+        anchor: char_index(&shown, selection.start),
+        text,
+    };
+
+    let mut changed = false;
+    for key in &keys {
+        if *key == LineEditKey::Submit {
+            text_submitted_writer.send(TextSubmitted { entity });
+            continue;
+        }
+        if let Some(edit) = editor.apply(key, select, single_line) {
+            let mut txn = yrs_doc_res.doc.transact_mut();
+            yrs_text.remove_range(&mut txn, edit.offset as u32, edit.removed as u32);
+            yrs_text.insert(&mut txn, edit.offset as u32, &edit.inserted);
+            changed = true;
+        }
+    }
+
+    let shown = single_line.display_text(&editor.text);
+    cursor_state.position = byte_offset(&shown, editor.cursor);
+    cursor_state.line = 0;
+    cursor_state.x_goal = None;
+    selection.start = byte_offset(&shown, editor.anchor);
+    selection.end = cursor_state.position;
+    if changed {
+        yrs_text_changed_writer.send(YrsTextChanged { entity });
+    }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use bevy_app::{App, AppExit, Plugin, Startup, Update, Last};
 use bevy_ecs::prelude::*;
 use bevy_ecs::schedule::{SystemSet, common_conditions::{not, on_event}};
 use bevy_log::{info, error, warn, trace};
-use bevy_window::{PrimaryWindow, Window};
+use bevy_window::{PrimaryWindow, Window, WindowClosed, WindowClosing};
 use bevy_winit::WinitWindows;
 #[cfg(feature = "debug_logging")]
 use bevy_diagnostic::{DiagnosticsPlugin, FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin};
@@ -10,8 +10,8 @@ use bevy_transform::prelude::{GlobalTransform, Transform};
 use std::sync::{Arc, Mutex};
 use std::collections::{HashMap, HashSet};
 use ash::vk;
-use bevy_color::Color;
-use bevy_math::{Vec2, IVec2, Mat4};
+use bevy_color::{Color, ColorToComponents};
+use bevy_math::{Vec2, IVec2};
 use cosmic_text::{Attrs, Shaping, SwashCache, Wrap, Color as CosmicColor, Font, Buffer, Metrics};
 use swash::FontRef;
 use vk_mem::Alloc;
@@ -26,7 +26,6 @@ use crate::gui_framework::interaction::utils::global_to_local_cursor;
 // Import types from the crate root (lib.rs)
 use crate::{
     Vertex, RenderCommandData, TextVertex,
-    GlobalProjectionUboResource,
     TextRenderingResources, // Keep this if cleanup needs it, otherwise remove
     YrsDocResource,
 };
@@ -41,30 +40,42 @@ use crate::gui_framework::systems::{
     style_resolution_system, apply_resolved_styles_system, style_resolution_debug_system,
     StyleChanged, StateChangeTracker
 };
+use crate::widgets::toggle::{toggle_click_system, toggle_knob_system};
+use crate::widgets::numeric::{numeric_drag_system, drag_value_text_system, slider_thumb_system, numeric_label_system};
+use crate::widgets::scroll_view::{
+    ScrollIntoView, scroll_view_setup_system, scroll_view_adopt_system, scroll_view_measure_system,
+    scroll_view_input_system, scroll_into_view_system, scroll_view_apply_system, scroll_view_clip_system,
+};
+use crate::widgets::popup::{popup_overlay_system, popup_layout_system, popup_dismiss_system};
+use crate::widgets::dropdown::{dropdown_click_system, dropdown_focus_system, dropdown_keyboard_system, dropdown_list_system, dropdown_label_system};
+use crate::widgets::tabs::{tabs_click_system, tabs_drag_system, tabs_header_system, tabs_layout_system, tabs_page_system};
+use crate::widgets::text_input::{text_input_field_system, text_input_focus_system, text_input_submit_system, text_input_blur_system, text_input_placeholder_system};
 // DebugRingBuffer system removed - replaced by CentralLogStore
 // Temporarily comment out custom diagnostics until we get the basic ones working
 // use crate::gui_framework::diagnostics::{UiDiagnosticsPlugin, UiDiagnosticsTimer};
 // #[cfg(feature = "debug_logging")]
 // use crate::gui_framework::diagnostics::ui_diagnostics_log_system;
 use crate::gui_framework::{
-    context::vulkan_context::VulkanContext,
-    context::vulkan_setup::{setup_vulkan, cleanup_vulkan},
-    rendering::render_engine::Renderer,
+    context::vulkan_context::WindowSurface,
+    context::vulkan_setup::{setup_vulkan, create_surface, cleanup_vulkan},
+    rendering::render_engine::{Renderer, WindowFrame},
+    rendering::swapchain::{create_window_resources, write_projection, destroy_window_surface},
+    rendering::resize_handler::ResizeHandler,
     rendering::glyph_atlas::GlyphAtlas,
     rendering::font_server::FontServer,
-    components::{ShapeData, Visibility, Text, FontId, TextAlignment, TextLayoutOutput, PositionedGlyph, TextBufferCache, TextSelection, Focus, Interaction, CursorVisual, CursorState},
+    components::{ShapeData, Visibility, Text, FontId, TextAlignment, TextLayoutOutput, PositionedGlyph, TextBufferCache, TextSelection, Focus, Interaction, CursorVisual, CursorState, SingleLineText, ClipRect, OverlayLayer},
     rendering::shader_utils,
 };
 
 // Import resources used/managed by this plugin's systems
 use crate::{VulkanContextResource, RendererResource, GlyphAtlasResource, FontServerResource, SwashCacheResource};
+use crate::assets::{TargetWindow, UiWindow};
 
 // --- System Sets ---
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CoreSet {
     // Startup sequence
     SetupVulkan,
-    CreateGlobalUbo,
     CreateRenderer,         // needs Vulkan setup
     CreateGlyphAtlas,       // needs Vulkan setup
     CreateFontServer,       // no Vulkan deps
@@ -73,7 +84,7 @@ pub enum CoreSet {
 
     // Update sequence
     ApplyUpdateCommands,    // Apply commands from layout/cursor systems before rendering
-    HandleResize,           // Handle window resize events, update global UBO
+    HandleResize,           // Handle window resize events, update window projection UBOs
     ApplyInputCommands,     // Apply commands from input/focus/cursor systems before layout/positioning
     ActionProcessing,       // Process interaction events and execute actions
     StateTracking,          // Track interaction state changes
@@ -85,6 +96,7 @@ pub enum CoreSet {
 
     // Last sequence
     PreRenderCleanup,       // New set for despawn cleanup before rendering
+    ManageWindowSurfaces,   // Create surfaces for new windows, destroy those of closed ones
     Render,                 // Perform rendering using prepared data
     Cleanup,                // Cleanup resources on AppExit
 }
@@ -122,6 +134,7 @@ impl Plugin for GuiFrameworkCorePlugin {
         app.add_event::<ActionEvent>();
         app.add_event::<InteractionStateChanged>();
         app.add_event::<StyleChanged>();
+        app.add_event::<ScrollIntoView>();
 
         // --- Resource Registration ---
         app.init_resource::<ActionRegistry>();
@@ -139,7 +152,6 @@ impl Plugin for GuiFrameworkCorePlugin {
                 CoreSet::CreateSwashCache,
             ).chain())
             .configure_sets(Startup, (
-                CoreSet::CreateGlobalUbo.after(CoreSet::CreateRenderer),
                 CoreSet::CreateTextResources
                     .after(CoreSet::CreateRenderer)
                     .after(CoreSet::CreateGlyphAtlas),
@@ -150,10 +162,10 @@ impl Plugin for GuiFrameworkCorePlugin {
                 create_glyph_atlas_system.in_set(CoreSet::CreateGlyphAtlas),
                 create_font_server_system.in_set(CoreSet::CreateFontServer),
                 create_swash_cache_system.in_set(CoreSet::CreateSwashCache),
-                create_global_ubo_system.in_set(CoreSet::CreateGlobalUbo),
                 create_text_rendering_resources_system.in_set(CoreSet::CreateTextResources),
             ))
-            .init_resource::<crate::PreparedTextDrawsResource>();
+            .init_resource::<crate::PreparedTextDrawsResource>()
+            .init_resource::<crate::RemovedTextResource>();
 
             // == Update Systems (This is the corrected ordering) ==
             app.configure_sets(Update, (
@@ -201,8 +213,10 @@ impl Plugin for GuiFrameworkCorePlugin {
                 update_cursor_transform_system.in_set(CoreSet::UpdateCursorTransform),
                 apply_deferred.in_set(CoreSet::ApplyInputCommands),
                 buffer_manager_despawn_cleanup_system.in_set(CoreSet::PreRenderCleanup),
+                text_despawn_cleanup_system.in_set(CoreSet::PreRenderCleanup),
                 // Action systems
                 interaction_to_action_system.in_set(CoreSet::ActionProcessing),
+                toggle_click_system.in_set(CoreSet::ActionProcessing).before(action_execution_system),
                 action_execution_system.in_set(CoreSet::ActionProcessing),
                 // State tracking systems
                 interaction_state_tracking_system.in_set(CoreSet::StateTracking),
@@ -215,8 +229,35 @@ impl Plugin for GuiFrameworkCorePlugin {
                 style_resolution_system.in_set(CoreSet::StyleResolution),
                 apply_resolved_styles_system.in_set(CoreSet::StyleResolution),
                 style_resolution_debug_system.in_set(CoreSet::StyleResolution),
+                toggle_knob_system.in_set(CoreSet::StyleResolution),
                 // Debug systems
                 // update_debug_ring_buffer_system removed - replaced by CentralLogStore
+            ))
+            .add_systems(Update, (
+                // Slider and drag value systems
+                (numeric_drag_system, drag_value_text_system).in_set(CoreSet::ActionProcessing).before(action_execution_system),
+                (slider_thumb_system, numeric_label_system).in_set(CoreSet::StyleResolution),
+                // Text input systems
+                (text_input_focus_system, text_input_submit_system, text_input_blur_system).in_set(CoreSet::ActionProcessing).before(action_execution_system),
+                (text_input_field_system, text_input_placeholder_system).in_set(CoreSet::StyleResolution),
+                // Scroll view systems
+                scroll_view_input_system.in_set(CoreSet::ActionProcessing),
+                (
+                    scroll_view_setup_system,
+                    scroll_view_adopt_system,
+                    scroll_view_measure_system,
+                    scroll_into_view_system,
+                    scroll_view_apply_system,
+                    scroll_view_clip_system,
+                ).chain().in_set(CoreSet::StyleResolution),
+                // Popup and dropdown systems: lists open and close before popups are shown and placed
+                (popup_dismiss_system, dropdown_focus_system, dropdown_click_system, dropdown_keyboard_system).in_set(CoreSet::ActionProcessing).before(action_execution_system),
+                (dropdown_list_system, popup_overlay_system, popup_layout_system).chain().in_set(CoreSet::StyleResolution),
+                dropdown_label_system.in_set(CoreSet::StyleResolution),
+                // Tabs systems: pages are shown and hidden before scroll views measure them
+                (tabs_click_system, tabs_drag_system).in_set(CoreSet::ActionProcessing).before(action_execution_system),
+                (tabs_header_system, tabs_layout_system).chain().in_set(CoreSet::StyleResolution),
+                tabs_page_system.in_set(CoreSet::StyleResolution).before(scroll_view_measure_system),
             ));
 
         #[cfg(feature = "debug_logging")]
@@ -226,10 +267,13 @@ impl Plugin for GuiFrameworkCorePlugin {
 
             // == Last Schedule Systems (This part is correct and remains unchanged) ==
             app.configure_sets(Last, (
-                CoreSet::Render.after(CoreSet::PreRenderCleanup),
+                CoreSet::ManageWindowSurfaces.after(CoreSet::PreRenderCleanup),
+                CoreSet::Render.after(CoreSet::PreRenderCleanup).after(CoreSet::ManageWindowSurfaces),
                 CoreSet::Cleanup.after(CoreSet::Render),
             ))
             .add_systems(Last, (
+                (destroy_closed_window_surfaces_system, create_window_surfaces_system, sync_window_presentation_system).chain()
+                    .run_if(not(on_event::<AppExit>)).in_set(CoreSet::ManageWindowSurfaces),
                 rendering_system.run_if(not(on_event::<AppExit>)).in_set(CoreSet::Render),
                 cleanup_trigger_system.run_if(on_event::<AppExit>).in_set(CoreSet::Cleanup),
             ));
@@ -239,31 +283,36 @@ impl Plugin for GuiFrameworkCorePlugin {
 // Startup system: Initializes Vulkan using the primary window handle.
 fn setup_vulkan_system(
     vk_context_res: Res<VulkanContextResource>,
-    primary_window_q: Query<Entity, With<PrimaryWindow>>,
+    primary_window_q: Query<(Entity, &Window), With<PrimaryWindow>>,
     winit_windows: NonSend<WinitWindows>,
 ) {
-    let primary_entity = primary_window_q.get_single()
+    let (primary_entity, primary_window) = primary_window_q.get_single()
         .expect("Failed to get primary window entity");
     let winit_window = winit_windows.get_window(primary_entity)
         .expect("Failed to get winit window reference from WinitWindows");
 
     let mut vk_ctx_guard = vk_context_res.0.lock().expect("Failed to lock VulkanContext mutex for setup");
 
-    setup_vulkan(&mut vk_ctx_guard, winit_window);
+    let surface = setup_vulkan(&mut vk_ctx_guard, winit_window);
+    let mut window_surface = WindowSurface::new(surface);
+    window_surface.present_mode = primary_window.present_mode;
+    window_surface.transparent = primary_window.transparent;
+    vk_ctx_guard.surfaces.insert(primary_entity, window_surface);
 }
 
 // Startup system (piped): Creates the Renderer instance resource.
 fn create_renderer_system(
     mut commands: Commands,
     vk_context_res: Res<VulkanContextResource>,
-    primary_window_q: Query<&Window, With<PrimaryWindow>>,
+    primary_window_q: Query<(Entity, &Window), With<PrimaryWindow>>,
 ) {
-    let primary_window = primary_window_q.get_single().expect("Primary window not found");
+    let (primary_entity, primary_window) = primary_window_q.get_single().expect("Primary window not found");
     let extent = vk::Extent2D { width: primary_window.physical_width(), height: primary_window.physical_height() };
+    let logical_size = Vec2::new(primary_window.width(), primary_window.height());
 
     let mut vk_ctx_guard = vk_context_res.0.lock().expect("Failed to lock VulkanContext for renderer creation");
 
-    let renderer_instance = Renderer::new(&mut commands, &mut vk_ctx_guard, extent);
+    let renderer_instance = Renderer::new(&mut commands, &mut vk_ctx_guard, primary_entity, extent, logical_size);
 
     let renderer_arc = Arc::new(Mutex::new(renderer_instance));
     commands.insert_resource(RendererResource(renderer_arc.clone()));
@@ -323,115 +372,119 @@ fn buffer_manager_despawn_cleanup_system(
     }
 }
 
-fn create_global_ubo_system(
-    mut commands: Commands,
+// Collects entities that lost their `Text`, so the renderer frees their text resources
+pub(crate) fn text_despawn_cleanup_system(
+    mut removed_texts: RemovedComponents<Text>,
+    mut removed_text_res: ResMut<crate::RemovedTextResource>,
+) {
+    removed_text_res.0.extend(removed_texts.read());
+}
+
+// Last system: Creates surfaces and swapchains for windows opened after startup, once winit has
+// created them. Windows opened from UI declarations are cleared to their background color.
+fn create_window_surfaces_system(
     vk_context_res: Res<VulkanContextResource>,
-    renderer_res: Res<RendererResource>, // Need renderer for descriptor pool access
-    primary_window_q: Query<&Window, With<PrimaryWindow>>, // Add window query parameter
+    renderer_res_opt: Option<Res<RendererResource>>,
+    windows: Query<(Entity, &Window, Option<&UiWindow>), Without<PrimaryWindow>>,
+    winit_windows: NonSend<WinitWindows>,
 ) {
-    info!("Running create_global_ubo_system (Core Plugin)...");
-    let Ok(vk_ctx_guard) = vk_context_res.0.lock() else {
-        error!("Failed to lock VulkanContext in create_global_ubo_system");
+    // The shared render pass and command pool come with the renderer
+    if renderer_res_opt.is_none() {
         return;
-    };
-    let Ok(renderer_guard) = renderer_res.0.lock() else {
-        error!("Failed to lock RendererResource in create_global_ubo_system");
-        return;
-    };
-    // Get primary window dimensions
-    let Ok(primary_window) = primary_window_q.get_single() else { // Query result assigned to primary_window
-        error!("Failed to get primary window in create_global_ubo_system");
-        // Cannot proceed without window dimensions for initial projection
+    }
+    let Ok(mut vk_ctx_guard) = vk_context_res.0.lock() else {
+        error!("Failed to lock VulkanContext in create_window_surfaces_system");
         return;
     };
-    // Use the 'primary_window' variable obtained from the query
-    let initial_logical_width = primary_window.width();
-    let initial_logical_height = primary_window.height();
-    let device = vk_ctx_guard.device.as_ref().expect("Device missing");
-    let allocator = vk_ctx_guard.allocator.as_ref().expect("Allocator missing");
 
-    // 1. Create Buffer & Allocation
-    let buffer_size = std::mem::size_of::<Mat4>() as vk::DeviceSize;
-    let (buffer, allocation) = unsafe {
-        let buffer_info = vk::BufferCreateInfo {
-            s_type: vk::StructureType::BUFFER_CREATE_INFO,
-            size: buffer_size,
-            usage: vk::BufferUsageFlags::UNIFORM_BUFFER,
-            sharing_mode: vk::SharingMode::EXCLUSIVE,
-            ..Default::default()
+    for (entity, window, ui_window) in windows.iter() {
+        if vk_ctx_guard.surfaces.contains_key(&entity) {
+            continue;
+        }
+        // winit creates the window after the entity is spawned
+        let Some(winit_window) = winit_windows.get_window(entity) else {
+            continue;
         };
-        let allocation_info = vk_mem::AllocationCreateInfo {
-            usage: vk_mem::MemoryUsage::AutoPreferDevice,
-            flags: vk_mem::AllocationCreateFlags::MAPPED
-            | vk_mem::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE,
-            ..Default::default()
+        let surface = match create_surface(&vk_ctx_guard, winit_window) {
+            Ok(surface) => surface,
+            Err(e) => {
+                error!("Failed to create a Vulkan surface for window {:?}: {:?}", entity, e);
+                continue;
+            }
         };
-        allocator.create_buffer(&buffer_info, &allocation_info)
-        .expect("Global UBO buffer creation failed")
-    };
-    // --- NAME Global UBO & Memory ---
-    #[cfg(debug_assertions)]
-    if let Some(debug_device_ext) = vk_ctx_guard.debug_utils_device.as_ref() { // Get Device ext
-        let mem_handle = allocator.get_allocation_info(&allocation).device_memory;
-        set_debug_object_name(debug_device_ext, buffer, vk::ObjectType::BUFFER, "GlobalProjectionUBO"); // Pass ext
-        set_debug_object_name(debug_device_ext, mem_handle, vk::ObjectType::DEVICE_MEMORY, "GlobalProjectionUBO_Mem"); // Pass ext
+
+        let mut window_surface = WindowSurface::new(surface);
+        window_surface.present_mode = window.present_mode;
+        window_surface.transparent = window.transparent;
+        if let Some(color) = ui_window.and_then(|ui_window| ui_window.background) {
+            window_surface.clear_color = color.to_srgba().to_f32_array();
+        }
+        let extent = vk::Extent2D { width: window.physical_width(), height: window.physical_height() };
+        create_window_resources(&mut vk_ctx_guard, &mut window_surface, extent, Vec2::new(window.width(), window.height()));
+        vk_ctx_guard.surfaces.insert(entity, window_surface);
+        info!("Created Vulkan surface for window {:?} ({}x{})", entity, extent.width, extent.height);
     }
-    // --- END NAME ---
+}
 
-    // 2. Allocate Descriptor Set (using per-entity layout)
-    let set_layouts = [renderer_guard.descriptor_set_layout]; // This is now per_entity_layout
-    let alloc_info = vk::DescriptorSetAllocateInfo {
-        s_type: vk::StructureType::DESCRIPTOR_SET_ALLOCATE_INFO,
-        descriptor_pool: renderer_guard.descriptor_pool, // Access pool field
-        descriptor_set_count: 1,
-        p_set_layouts: set_layouts.as_ptr(),
-        ..Default::default()
-    };
-    let descriptor_set = unsafe {
-        device.allocate_descriptor_sets(&alloc_info)
-            .expect("Failed to allocate global UBO descriptor set")
-            .remove(0)
+// Last system: Recreates the swapchains of windows whose present mode or transparency changed,
+// e.g. when a layout's window config is applied.
+fn sync_window_presentation_system(
+    vk_context_res: Res<VulkanContextResource>,
+    windows: Query<(Entity, &Window), Changed<Window>>,
+) {
+    let Ok(mut vk_ctx_guard) = vk_context_res.0.lock() else {
+        error!("Failed to lock VulkanContext in sync_window_presentation_system");
+        return;
     };
 
-    // 3. Update Descriptor Set (Initial Binding)
-    let buffer_info = vk::DescriptorBufferInfo {
-        buffer,
-        offset: 0,
-        range: buffer_size,
-    };
-    let write_set = vk::WriteDescriptorSet {
-        s_type: vk::StructureType::WRITE_DESCRIPTOR_SET,
-        dst_set: descriptor_set,
-        dst_binding: 0, // Binding 0 for global projection
-        dst_array_element: 0,
-        descriptor_count: 1,
-        descriptor_type: vk::DescriptorType::UNIFORM_BUFFER,
-        p_buffer_info: &buffer_info,
-        ..Default::default()
+    for (entity, window) in windows.iter() {
+        let Some(window_surface) = vk_ctx_guard.surfaces.get_mut(&entity) else {
+            continue;
+        };
+        if window_surface.swapchain.is_none()
+            || (window_surface.present_mode == window.present_mode && window_surface.transparent == window.transparent) {
+            continue;
+        }
+        window_surface.present_mode = window.present_mode;
+        window_surface.transparent = window.transparent;
+        let extent = window_surface.current_swap_extent;
+        ResizeHandler::resize(&mut vk_ctx_guard, entity, extent);
+        info!("Recreated swapchain of window {:?} for present mode {:?}", entity, window.present_mode);
+    }
+}
+
+// Last system: Destroys the surfaces of windows that are closing, before winit drops them.
+fn destroy_closed_window_surfaces_system(
+    mut closing_events: EventReader<WindowClosing>,
+    mut closed_events: EventReader<WindowClosed>,
+    vk_context_res: Res<VulkanContextResource>,
+) {
+    let closed: HashSet<Entity> = closing_events.read().map(|event| event.window)
+        .chain(closed_events.read().map(|event| event.window))
+        .collect();
+    if closed.is_empty() {
+        return;
+    }
+    let Ok(mut vk_ctx_guard) = vk_context_res.0.lock() else {
+        error!("Failed to lock VulkanContext in destroy_closed_window_surfaces_system");
+        return;
     };
-    unsafe { device.update_descriptor_sets(&[write_set], &[]); }
 
-    // 4. Initial Write (Perform *before* inserting the resource)
-    let proj = Mat4::orthographic_rh(0.0, initial_logical_width, 0.0, initial_logical_height, 1024.0, 0.0);
-    let flip_y = Mat4::from_scale(bevy_math::Vec3::new(1.0, -1.0, 1.0));
-    let proj_matrix = flip_y * proj;
-    unsafe {
-        // Use the 'allocation' variable directly here, before it's moved
-        let info = allocator.get_allocation_info(&allocation);
-        if !info.mapped_data.is_null() {
-            let data_ptr = info.mapped_data.cast::<f32>();
-            data_ptr.copy_from_nonoverlapping(proj_matrix.to_cols_array().as_ptr(), 16);
-        } else {
-            error!("[create_global_ubo_system] Failed to get mapped pointer for initial uniform buffer write.");
+    for window in closed {
+        let Some(window_surface) = vk_ctx_guard.surfaces.remove(&window) else {
+            continue;
+        };
+        // The window's last frame may still be in flight
+        if let Some(device) = vk_ctx_guard.device.as_ref() {
+            unsafe {
+                if let Err(e) = device.device_wait_idle() {
+                    error!("Failed to wait for device idle before destroying window surface: {:?}", e);
+                }
+            }
         }
+        destroy_window_surface(&vk_ctx_guard, window_surface);
+        info!("Destroyed Vulkan surface for closed window {:?}", window);
     }
-
-    // 5. Insert Resource (Now move buffer, allocation, descriptor_set)
-    commands.insert_resource(GlobalProjectionUboResource {
-        buffer,
-        allocation, // 'allocation' is moved here
-        descriptor_set,
-    });
 }
 
 fn create_glyph_atlas_system(
@@ -455,10 +508,13 @@ fn create_glyph_atlas_system(
     }
 }
 
-fn create_font_server_system(mut commands: Commands) {
-    // FontServer::new() can take some time if loading many system fonts.
+fn create_font_server_system(mut commands: Commands, localization: Option<Res<crate::assets::Localization>>) {
+    // FontServer::with_locale() can take some time if loading many system fonts.
     // Consider running this asynchronously or loading fewer fonts if startup time is critical.
-    let font_server = FontServer::new();
+    let font_server = match localization {
+        Some(localization) => FontServer::with_locale(localization.locale()),
+        None => FontServer::new(),
+    };
     let font_server_arc = Arc::new(Mutex::new(font_server));
     commands.insert_resource(FontServerResource(font_server_arc));
 }
@@ -518,7 +574,7 @@ fn create_text_rendering_resources_system(
         let vert_shader_module = shader_utils::load_shader(device, "text.vert.spv");
         let frag_shader_module = shader_utils::load_shader(device, "text.frag.spv");
         // --- Define Pipeline Stages ---
-        let shader_stages = [ vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: vert_shader_module, stage: vk::ShaderStageFlags::VERTEX, p_name: b"main\0".as_ptr() as _, ..Default::default() }, vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: frag_shader_module, stage: vk::ShaderStageFlags::FRAGMENT, p_name: b"main\0".as_ptr() as _, ..Default::default() }, ];
+        let shader_stages = [ vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: vert_shader_module, stage: vk::ShaderStageFlags::VERTEX, p_name: c"main".as_ptr(), ..Default::default() }, vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: frag_shader_module, stage: vk::ShaderStageFlags::FRAGMENT, p_name: c"main".as_ptr(), ..Default::default() }, ];
         // --- Define Vertex Input State ---
         let vertex_attr_descs = [ vk::VertexInputAttributeDescription { location: 0, binding: 0, format: vk::Format::R32G32_SFLOAT, offset: 0 }, vk::VertexInputAttributeDescription { location: 1, binding: 0, format: vk::Format::R32G32_SFLOAT, offset: std::mem::size_of::<[f32; 2]>() as u32 }, ];
         let vertex_binding_descs = [ vk::VertexInputBindingDescription { binding: 0, stride: std::mem::size_of::<TextVertex>() as u32, input_rate: vk::VertexInputRate::VERTEX } ];
@@ -587,46 +643,26 @@ fn create_text_rendering_resources_system(
     info!("TextRenderingResources inserted (Core Plugin).");
 }
 
-// Update system: Handles window resize events, updates global UBO, and calls Renderer resize.
+// Update system: Handles window resize events, updates the window's projection UBO, and calls Renderer resize.
 fn handle_resize_system(
     mut resize_reader: EventReader<bevy_window::WindowResized>,
     renderer_res_opt: Option<ResMut<RendererResource>>,
-    // vk_context_res_opt: Option<Res<VulkanContextResource>>, // Removed Option<>
-    global_ubo_res_opt: Option<Res<GlobalProjectionUboResource>>,
     vk_context_res: Res<VulkanContextResource>, // Get the resource directly
 ) {
-    // Check if necessary resources are available (Renderer and Global UBO)
-    let (Some(renderer_res), Some(global_ubo_res)) =
-        (renderer_res_opt, global_ubo_res_opt) else { return; };
+    // Check if the renderer is available
+    let Some(renderer_res) = renderer_res_opt else { return; };
 
     for event in resize_reader.read() {
         if event.width > 0.0 && event.height > 0.0 {
-            // --- Update Global Projection UBO ---
-            let logical_width = event.width;
-            let logical_height = event.height;
-            // Get allocator via VulkanContext (Need to lock briefly just for this)
-            // Use the vk_context_res passed directly into the system
-            let allocator_opt = vk_context_res.0.lock().ok().and_then(|ctx| ctx.allocator.clone()); // Lock, get Arc, drop lock
-            let Some(allocator) = allocator_opt else {
-                 warn!("Could not get allocator from VulkanContext during handle_resize_system.");
-                 continue; // Skip this event if allocator isn't ready
-            };
-            let proj = Mat4::orthographic_rh(0.0, logical_width, 0.0, logical_height, 1024.0, 0.0);
-            let flip_y = Mat4::from_scale(bevy_math::Vec3::new(1.0, -1.0, 1.0));
-            let proj_matrix = flip_y * proj;
-
-            unsafe {
-                // Use the allocation from the resource
-                let info = allocator.get_allocation_info(&global_ubo_res.allocation);
-                if !info.mapped_data.is_null() {
-                    let data_ptr = info.mapped_data.cast::<f32>();
-                    data_ptr.copy_from_nonoverlapping(proj_matrix.to_cols_array().as_ptr(), 16);
-                    // Optional flush if not HOST_COHERENT
-                    // if allocator.flush_allocation(&global_ubo_res.allocation, 0, vk::WHOLE_SIZE).is_err() {
-                    //     error!("Failed to flush global UBO allocation on resize");
-                    // }
-                } else {
-                    error!("[handle_resize_system] Failed to get mapped pointer for global UBO update.");
+            // --- Update the window's Projection UBO ---
+            match vk_context_res.0.lock() {
+                Ok(vk_ctx_guard) => match vk_ctx_guard.surfaces.get(&event.window) {
+                    Some(window_surface) => write_projection(&vk_ctx_guard, window_surface, Vec2::new(event.width, event.height)),
+                    None => continue, // Not drawn to (yet); its resources are created at the new size
+                },
+                Err(_) => {
+                    warn!("Could not lock VulkanContext during handle_resize_system.");
+                    continue;
                 }
             }
 
@@ -637,6 +673,7 @@ fn handle_resize_system(
                 // Pass VulkanContextResource directly (using the system parameter vk_context_res)
                 renderer_guard.resize_renderer(
                     &vk_context_res, // Pass the resource itself
+                    event.window,
                     event.width as u32,
                     event.height as u32,
                 );
@@ -723,12 +760,12 @@ fn manage_cursor_visual_system(
 
 /// System to update the visual cursor's position and size based on `CursorState` and `TextBufferCache`.
 fn update_cursor_transform_system(
-    focused_query: Query<(Entity, &CursorState, &TextBufferCache), With<Focus>>,
+    focused_query: Query<(Entity, &CursorState, &TextBufferCache, Option<&SingleLineText>), With<Focus>>,
     mut cursor_visual_query: Query<&mut Transform, With<CursorVisual>>,
     children_query: Query<&Children>,
 ) {
     // There should only be one focused entity.
-    if let Ok((focused_entity, cursor_state, text_cache)) = focused_query.get_single() {
+    if let Ok((focused_entity, cursor_state, text_cache, single_line)) = focused_query.get_single() {
         // The buffer cache must exist to calculate positions.
         if let Some(buffer) = text_cache.buffer.as_ref() {
             // Find the child entity that is the visual cursor.
@@ -760,8 +797,9 @@ fn update_cursor_transform_system(
                             let cursor_width = 1.0;
                             let cursor_top_y_ydown = run.line_top;
 
-                            // Calculate the center of the cursor rectangle.
-                            let center_x_ydown = cursor_x_ydown + (cursor_width / 2.0);
+                            // Calculate the center of the cursor rectangle, in view of any scrolling.
+                            let scroll = single_line.map_or(0.0, |single_line| single_line.scroll);
+                            let center_x_ydown = cursor_x_ydown - scroll + (cursor_width / 2.0);
                             let center_y_ydown = cursor_top_y_ydown + (line_visual_height / 2.0);
 
                             // Update the transform.
@@ -805,6 +843,7 @@ fn text_layout_system(
     text_component_query: Query<(&Text, &Transform, &Visibility)>,
     new_text_component_query: Query<Entity, Added<Text>>,
     mut text_buffer_cache_query: Query<&mut TextBufferCache>,
+    mut single_line_query: Query<(Entity, &mut SingleLineText, Option<Ref<CursorState>>)>,
     yrs_doc_res: Res<YrsDocResource>,
     font_server_res: Res<FontServerResource>,
     glyph_atlas_res: Res<GlyphAtlasResource>,
@@ -825,6 +864,12 @@ fn text_layout_system(
     let mut entities_to_process: HashSet<Entity> = HashSet::new();
     for event in event_reader.read() { entities_to_process.insert(event.entity); }
     for entity in new_text_component_query.iter() { entities_to_process.insert(entity); }
+    // Single-line texts are also laid out again when their cursor moves, to keep it in view
+    for (entity, single_line, cursor_state) in single_line_query.iter_mut() {
+        if single_line.is_changed() || cursor_state.is_some_and(|cursor_state| cursor_state.is_changed()) {
+            entities_to_process.insert(entity);
+        }
+    }
     if entities_to_process.is_empty() { return; }
 
     // Now, if there are entities to process, get Vulkan handles.
@@ -832,7 +877,7 @@ fn text_layout_system(
     let (device_clone, queue_opt, command_pool_opt, allocator_clone_opt) = {
         match vk_context_res.0.lock() {
             Ok(vk_guard) => (
-                vk_guard.device.as_ref().map(|d| d.clone()),
+                vk_guard.device.clone(),
                 vk_guard.queue,
                 vk_guard.command_pool,
                 vk_guard.allocator.as_ref().map(|a| a.clone()),
@@ -857,23 +902,6 @@ fn text_layout_system(
         return;
     };
 
-    // --- Determine which entities need processing ---
-    let mut entities_to_process: HashSet<Entity> = HashSet::new();
-
-    // Add entities from events
-    for event in event_reader.read() {
-        entities_to_process.insert(event.entity);
-    }
-
-    // Add newly added entities
-    for entity in new_text_component_query.iter() { // <-- Use renamed parameter
-        entities_to_process.insert(entity);
-    }
-
-    if entities_to_process.is_empty() {
-        return; // Nothing to do
-    }
-
     // --- Loop through Entities with Text that has been updated ---
     for entity in entities_to_process {
         // Get the components for the specific entity
@@ -902,6 +930,13 @@ fn text_layout_system(
             }
         };
 
+        // Single-line texts are shown on one line, masked if they have a mask
+        let mut single_line = single_line_query.get_mut(entity).ok();
+        let text_content = match single_line {
+            Some((_, ref single_line, _)) => single_line.display_text(&text_content),
+            None => text_content,
+        };
+
         // --- Create Cosmic Text Buffer PER ENTITY being processed ---
         let metrics = Metrics::new(text.size, text.size * 1.2); // Use Metrics here
         let mut buffer = Buffer::new(&mut font_server.font_system, metrics); // Create buffer inside the loop
@@ -923,7 +958,7 @@ fn text_layout_system(
         buffer.set_text(&mut font_server.font_system, &text_content, &attrs, Shaping::Advanced);
 
         // --- Set Wrapping ---
-        if let Some(bounds) = text.bounds {
+        if let Some(bounds) = text.bounds.filter(|_| single_line.is_none()) {
             buffer.set_size(&mut font_server.font_system, Some(bounds.x), Some(bounds.y));
             buffer.set_wrap(&mut font_server.font_system, Wrap::Word);
         } else {
@@ -934,6 +969,22 @@ fn text_layout_system(
         // --- Shape the Text ---
         buffer.shape_until_scroll(&mut font_server.font_system, true);
 
+        // --- Scroll Single-Line Text to Keep the Cursor in View ---
+        if let Some((_, ref mut single_line, ref cursor_state)) = single_line {
+            let run = buffer.layout_runs().next();
+            let line_width = run.as_ref().map_or(0.0, |run| run.line_w);
+            let cursor_x = cursor_state.as_ref().zip(run.as_ref()).map(|(cursor_state, run)| {
+                let index = global_to_local_cursor(&buffer, cursor_state.position).index;
+                run.glyphs.iter().find(|g| g.start == index)
+                    .map_or_else(|| run.glyphs.first().map_or(0.0, |g| g.x) + run.line_w, |g| g.x)
+            });
+            let scroll = single_line.scroll_to_show(cursor_x, line_width);
+            if scroll != single_line.scroll {
+                // Not a change to react to; the layout below already uses it
+                single_line.bypass_change_detection().scroll = scroll;
+            }
+        }
+
         // --- Prepare to collect glyphs for THIS entity ---
         let mut positioned_glyphs = Vec::new();
 
@@ -943,6 +994,12 @@ fn text_layout_system(
 
             // --- Loop through Glyphs in the Run ---
             for layout_glyph in run.glyphs.iter() {
+                // Glyphs scrolled out of a single-line text's width are not drawn
+                let scroll = match single_line {
+                    Some((_, ref single_line, _)) if !single_line.shows(layout_glyph.x, layout_glyph.w) => continue,
+                    Some((_, ref single_line, _)) => single_line.scroll,
+                    None => 0.0,
+                };
                 let flags = cosmic_text::CacheKeyFlags::empty();
                 let (cache_key, _x_int_offset, _y_int_offset) = cosmic_text::CacheKey::new(
                     layout_glyph.font_id,
@@ -964,7 +1021,7 @@ fn text_layout_system(
                     command_pool,
                     &allocator,
                     cache_key,
-                    &swash_image
+                    swash_image
                 );
 
                 match add_result {
@@ -985,7 +1042,7 @@ fn text_layout_system(
 
                         if units_per_em == 0.0 { warn!("Units per em is 0 for font ID {:?}.", layout_glyph.font_id); continue; }
 
-                        let relative_left_x = layout_glyph.x;
+                        let relative_left_x = layout_glyph.x - scroll;
                         let relative_right_x = relative_left_x + width;
                         let relative_top_y = baseline_y + placement.top as f32;
                         let relative_bottom_y = relative_top_y - height;
@@ -1035,6 +1092,24 @@ pub struct TextLayoutInfo {
     pub transform: GlobalTransform,
     pub layout: Arc<TextLayoutOutput>,
     pub visibility: Visibility,
+    pub scissor: Option<vk::Rect2D>, // Clip region from the entity's ClipRect
+    pub overlay: bool, // Whether the entity is in the OverlayLayer
+}
+
+/// Scissor rectangle in framebuffer pixels for a clip region in world coordinates, or `None`
+/// when nothing of the region lies inside the window
+fn clip_scissor(clip: &ClipRect, window: &Window) -> Option<vk::Rect2D> {
+    let scale = window.scale_factor();
+    let (width, height) = (window.physical_width() as f32, window.physical_height() as f32);
+    // World coordinates grow upwards from the bottom of the window, framebuffer ones downwards
+    let left = (clip.0.min.x * scale).floor().clamp(0.0, width);
+    let right = (clip.0.max.x * scale).ceil().clamp(0.0, width);
+    let top = ((window.height() - clip.0.max.y) * scale).floor().clamp(0.0, height);
+    let bottom = ((window.height() - clip.0.min.y) * scale).ceil().clamp(0.0, height);
+    (right > left && bottom > top).then_some(vk::Rect2D {
+        offset: vk::Offset2D { x: left as i32, y: top as i32 },
+        extent: vk::Extent2D { width: (right - left) as u32, height: (bottom - top) as u32 },
+    })
 }
 
 fn rendering_system(
@@ -1042,15 +1117,19 @@ fn rendering_system(
     renderer_res_opt: Option<ResMut<RendererResource>>,
     vk_context_res_opt: Option<Res<VulkanContextResource>>,
     buffer_manager_res_opt: Option<Res<BufferManagerResource>>,
-    global_ubo_res_opt: Option<Res<GlobalProjectionUboResource>>,
     text_res_opt: Option<Res<TextRenderingResources>>, // Still need pipeline/atlas set
+    mut removed_text_res: ResMut<crate::RemovedTextResource>,
     // debug_buffer_opt removed - replaced by tracing
 
     // Queries for scene data
-    shape_query: Query<(Entity, &GlobalTransform, &ShapeData, &Visibility), (Without<TextLayoutOutput>, Or<(With<ShapeData>, With<CursorVisual>)>)>, // Query shapes/cursors without TextLayoutOutput
+    shape_query: Query<(Entity, &GlobalTransform, &ShapeData, &Visibility, Option<&TargetWindow>, Option<&ClipRect>, Has<OverlayLayer>), (Without<TextLayoutOutput>, Or<(With<ShapeData>, With<CursorVisual>)>)>, // Query shapes/cursors without TextLayoutOutput
     shape_change_query: Query<Entity, (With<Visibility>, Changed<ShapeData>)>,
     // Query for text entities that have layout output ready
-    text_layout_query: Query<(Entity, &GlobalTransform, &TextLayoutOutput, &Visibility)>, // Query layout output
+    text_layout_query: Query<(Entity, &GlobalTransform, &TextLayoutOutput, &Visibility, Option<&TargetWindow>, Option<&ClipRect>, Has<OverlayLayer>)>, // Query layout output
+    // Entities without a TargetWindow are drawn in the primary window
+    primary_window_q: Query<Entity, With<PrimaryWindow>>,
+    // Clip regions are converted to scissors with the size of the window they are drawn in
+    windows: Query<&Window>,
     
     // Add frame counter for periodic logging
     mut frame_count: Local<u32>,
@@ -1060,18 +1139,18 @@ fn rendering_system(
         Some(renderer_res),
         Some(vk_context_res),
         Some(buffer_manager_res),
-        Some(global_ubo_res),
-    ) = (renderer_res_opt, vk_context_res_opt, buffer_manager_res_opt, global_ubo_res_opt) else {
+    ) = (renderer_res_opt, vk_context_res_opt, buffer_manager_res_opt) else {
         warn!("[rendering_system] Required non-text resources not available. Skipping render.");
         return;
     };
 
     *frame_count += 1;
-    let should_log = *frame_count <= 5 || *frame_count % 120 == 0; // Log first 5 frames, then every 2 seconds
+    let should_log = *frame_count <= 5 || (*frame_count).is_multiple_of(120); // Log first 5 frames, then every 2 seconds
     
     // --- Collect Shape Render Data ---
     let changed_shape_entities: HashSet<Entity> = shape_change_query.iter().collect();
-    let mut shape_render_commands: Vec<RenderCommandData> = Vec::new();
+    let primary_window = primary_window_q.get_single().ok();
+    let mut frames: HashMap<Entity, WindowFrame> = HashMap::new();
     
     // Debug: Log all entities with ShapeData for troubleshooting (reduced frequency)
     let all_shape_entities: Vec<_> = shape_query.iter().collect();
@@ -1079,46 +1158,69 @@ fn rendering_system(
         trace!("[rendering_system] Frame {}: Found {} entities with ShapeData+GlobalTransform", *frame_count, all_shape_entities.len());
     }
     
-    for (entity, global_transform, shape, visibility) in shape_query.iter() {
+    for (entity, global_transform, shape, visibility, target_window, clip, overlay) in shape_query.iter() {
         if should_log {
             trace!("   Shape Entity {:?}: visible={}, pos={:?}, vertices={}", 
                 entity, visibility.is_visible(), global_transform.translation(), shape.vertices.len());
         }
             
+        let Some(window) = target_window.map(|target| target.0).or(primary_window) else {
+            continue;
+        };
         if visibility.is_visible() {
+            // Shapes clipped away entirely are not drawn at all
+            let scissor = match clip.map(|clip| windows.get(window).ok().and_then(|window| clip_scissor(clip, window))) {
+                Some(None) => continue,
+                Some(scissor) => scissor,
+                None => None,
+            };
             let vertices_changed = changed_shape_entities.contains(&entity);
-            shape_render_commands.push(RenderCommandData {
+            frames.entry(window).or_default().shape_commands.push(RenderCommandData {
                 entity_id: entity,
                 transform_matrix: global_transform.compute_matrix(),
                 vertices: shape.vertices.clone(),
                 color: shape.color, // Get color from ShapeData
                 depth: global_transform.translation().z,
                 vertices_changed,
+                scissor,
+                overlay,
             });
         }
     }
     
     if should_log {
-        trace!("[rendering_system] Collected {} visible shapes for rendering", shape_render_commands.len());
+        let shape_count: usize = frames.values().map(|frame| frame.shape_commands.len()).sum();
+        trace!("[rendering_system] Collected {} visible shapes in {} window(s) for rendering", shape_count, frames.len());
         
         // Log a warning if no widgets are being rendered after startup
-        if *frame_count > 60 && shape_render_commands.len() <= 1 {
-            warn!("[rendering_system] Only {} shape(s) rendering after frame {} - widgets may be missing components", shape_render_commands.len(), *frame_count);
+        if *frame_count > 60 && shape_count <= 1 {
+            warn!("[rendering_system] Only {} shape(s) rendering after frame {} - widgets may be missing components", shape_count, *frame_count);
         }
     }
     // Sort shapes by depth (optional, but good practice)
-    shape_render_commands.sort_unstable_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap_or(std::cmp::Ordering::Equal));
+    for frame in frames.values_mut() {
+        frame.shape_commands.sort_unstable_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap_or(std::cmp::Ordering::Equal));
+    }
 
     // --- Collect Text Layout Info ---
     // Collect layout data for visible text entities. Renderer will handle resource creation/update.
-    let mut text_layout_infos: Vec<TextLayoutInfo> = Vec::new();
-    for (entity, transform, layout_output, visibility) in text_layout_query.iter() {
+    for (entity, transform, layout_output, visibility, target_window, clip, overlay) in text_layout_query.iter() {
+        let Some(window) = target_window.map(|target| target.0).or(primary_window) else {
+            continue;
+        };
         if visibility.is_visible() {
-            text_layout_infos.push(TextLayoutInfo {
+            let scissor = match clip.map(|clip| windows.get(window).ok().and_then(|window| clip_scissor(clip, window))) {
+                Some(None) => continue,
+                Some(scissor) => scissor,
+                None => None,
+            };
+            frames.entry(window).or_default().text_layout_infos.push(TextLayoutInfo {
                 entity,
                 transform: *transform, // Copy GlobalTransform
                 layout: Arc::new(layout_output.clone()), // Clone layout into an Arc
                 visibility: *visibility, // Copy Visibility
+                scissor,
+                overlay,
             });
         }
     }
@@ -1127,12 +1229,11 @@ fn rendering_system(
     // --- Call Custom Renderer ---
     let renderer_guard_opt = renderer_res.0.lock().ok(); // Bind Option<Guard> to variable first
     if let Some(mut renderer_guard) = renderer_guard_opt {
+        renderer_guard.queue_text_removals(removed_text_res.0.drain(..));
         renderer_guard.render(
             &vk_context_res,
             &buffer_manager_res,
-            &shape_render_commands,
-            &text_layout_infos, // Still empty if text_layout_system disabled
-            &global_ubo_res,
+            &frames,
             text_res_opt.as_deref(), // Pass Option<&TextRenderingResources>
             // debug_buffer_opt removed - using tracing instead
         );
@@ -1151,7 +1252,6 @@ fn cleanup_trigger_system(world: &mut World) {
     let renderer_res_opt = world.remove_resource::<RendererResource>();
     let buffer_manager_res_opt = world.remove_resource::<BufferManagerResource>();
     let text_rendering_res_opt = world.remove_resource::<TextRenderingResources>();
-    let global_ubo_res_opt = world.remove_resource::<GlobalProjectionUboResource>();
     let glyph_atlas_res_opt = world.remove_resource::<GlyphAtlasResource>();
     world.remove_resource::<crate::PreparedTextDrawsResource>();
 
@@ -1167,7 +1267,7 @@ fn cleanup_trigger_system(world: &mut World) {
 
     // Move the context out of the Mutex. This is the only time it's safe to do so.
     let mut vk_context = match vk_context_res.0.lock() {
-        Ok(mut guard) => std::mem::replace(&mut *guard, VulkanContext::new()),
+        Ok(mut guard) => std::mem::take(&mut *guard),
         Err(_) => {
             error!("[Cleanup] VulkanContext Mutex was poisoned. Cleanup cannot proceed safely.");
             return;
@@ -1210,13 +1310,6 @@ fn cleanup_trigger_system(world: &mut World) {
         }
     }
 
-    if let Some(mut global_ubo_res) = global_ubo_res_opt {
-        info!("[Cleanup] Cleaning up GlobalProjectionUboResource...");
-        unsafe {
-            allocator.destroy_buffer(global_ubo_res.buffer, &mut global_ubo_res.allocation);
-        }
-    }
-
     if let Some(atlas_res) = glyph_atlas_res_opt {
         if let Ok(mut atlas_guard) = atlas_res.0.lock() {
             atlas_guard.cleanup(device, allocator);
@@ -1224,8 +1317,12 @@ fn cleanup_trigger_system(world: &mut World) {
     }
 
     // --- Final Swapchain and Context Cleanup ---
-    // This function now takes the mutable context directly.
-    crate::gui_framework::rendering::swapchain::cleanup_swapchain_resources(&mut vk_context);
+    // Every window's swapchain, sync objects, projection UBO and surface go before the device.
+    let window_surfaces = std::mem::take(&mut vk_context.surfaces);
+    info!("[Cleanup] Destroying {} window surface(s)...", window_surfaces.len());
+    for (_, window_surface) in window_surfaces {
+        destroy_window_surface(&vk_context, window_surface);
+    }
 
     // Now, call the final cleanup function with the deconstructed context.
     cleanup_vulkan(vk_context);
@@ -7,7 +7,7 @@ use bevy_input::{
     keyboard::KeyCode, 
     ButtonInput,
     ButtonState,
-    mouse::{MouseButton, MouseButtonInput},
+    mouse::{MouseButton, MouseButtonInput, MouseScrollUnit, MouseWheel},
 };
 use bevy_math::{Vec2, Rect, Affine3A};
 use std::path::PathBuf;
@@ -19,6 +19,7 @@ use crate::HotkeyResource;
 use crate::gui_framework::interaction::utils::get_cursor_at_position;
 use crate::gui_framework::interaction::text_drag::text_drag_selection_system;
 use crate::gui_framework::interaction::text_editing::text_editing_system;
+use crate::assets::WidgetWindows;
 
 // Import types from the crate root (lib.rs)
 // (No specific types needed directly from lib.rs for this plugin)
@@ -26,14 +27,17 @@ use crate::gui_framework::interaction::text_editing::text_editing_system;
 // Import types/functions from the gui_framework
 use crate::gui_framework::{
     interaction::hotkeys::{HotkeyConfig, HotkeyError},
-    components::{Interaction, Visibility, Focus, EditableText, TextBufferCache, ShapeData},
-    events::{EntityClicked, EntityDragged, HotkeyActionTriggered, YrsTextChanged, TextFocusChanged},
+    components::{Interaction, Visibility, Focus, EditableText, TextBufferCache, ShapeData, SingleLineText, ClipRect, Scrollable, OverlayLayer},
+    events::{EntityClicked, EntityDragged, EntityScrolled, HotkeyActionTriggered, PointerPressed, YrsTextChanged, TextFocusChanged, TextSubmitted},
 };
 
 // Import resources used/managed by this plugin's systems
 // HotkeyResource is defined in main.rs for now, but inserted by this plugin
 use super::core::CoreSet;
 
+/// Pixels scrolled per line by mouse wheels that scroll in lines
+pub const SCROLL_LINE_PIXELS: f32 = 40.0;
+
 // --- Local Resources ---
 
 /// Enum describing the current high-level mouse interaction state.
@@ -72,6 +76,8 @@ impl Plugin for GuiFrameworkInteractionPlugin {
             .register_type::<HotkeyConfig>()   // Register the inner config struct
             .register_type::<EntityClicked>()
             .register_type::<EntityDragged>()
+            .register_type::<EntityScrolled>()
+            .register_type::<PointerPressed>()
             .register_type::<HotkeyActionTriggered>()
             .register_type::<EditableText>() 
             .register_type::<Focus>()
@@ -82,9 +88,12 @@ impl Plugin for GuiFrameworkInteractionPlugin {
         // (App::add_event is idempotent)
         app.add_event::<EntityClicked>()
             .add_event::<EntityDragged>()
+            .add_event::<EntityScrolled>()
+            .add_event::<PointerPressed>()
             .add_event::<HotkeyActionTriggered>()
             .add_event::<YrsTextChanged>()
-            .add_event::<TextFocusChanged>();
+            .add_event::<TextFocusChanged>()
+            .add_event::<TextSubmitted>();
         app.init_resource::<MouseContext>();
 
         // --- System Setup ---
@@ -102,7 +111,7 @@ impl Plugin for GuiFrameworkInteractionPlugin {
             .add_systems(Update,
                 (
                     // interaction_system sets the context, text_drag_selection_system reads it
-                    (interaction_system, hotkey_system, text_editing_system, text_drag_selection_system).chain().in_set(InteractionSet::InputHandling),
+                    (interaction_system, scroll_wheel_system, hotkey_system, text_editing_system, text_drag_selection_system).chain().in_set(InteractionSet::InputHandling),
                     handle_close_request.in_set(InteractionSet::WindowClose),
                 )
             );
@@ -192,17 +201,18 @@ fn load_hotkeys_system(mut commands: Commands) {
 // Processes mouse input for clicks, drags, and text focus.
 pub(crate) fn interaction_system(
     // Input resources
-    _yrs_text_changed_writer: EventWriter<YrsTextChanged>,
+    mut pointer_pressed_writer: EventWriter<PointerPressed>,
     mut mouse_button_input_events: EventReader<MouseButtonInput>,
     mut cursor_moved_events: EventReader<CursorMoved>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    widget_windows: WidgetWindows,
     // Output events
     mut entity_clicked_writer: EventWriter<EntityClicked>,
     mut entity_dragged_writer: EventWriter<EntityDragged>,
     mut text_focus_writer: EventWriter<TextFocusChanged>,
     // Queries for entities
-    interaction_query: Query<(Entity, &GlobalTransform, &Interaction, &Visibility, Option<&crate::gui_framework::components::ShapeData>), (Without<EditableText>, Without<CursorVisual>)>,
-    editable_text_query: Query<(Entity, &GlobalTransform, &TextBufferCache, &Visibility), With<EditableText>>,
+    interaction_query: Query<(Entity, &GlobalTransform, &Interaction, &Visibility, Option<&crate::gui_framework::components::ShapeData>, Option<&ClipRect>, Has<OverlayLayer>), (Without<EditableText>, Without<CursorVisual>)>,
+    editable_text_query: Query<(Entity, &GlobalTransform, &TextBufferCache, &Visibility, Option<&SingleLineText>, Option<&ClipRect>, Has<OverlayLayer>), With<EditableText>>,
     focus_query: Query<Entity, With<Focus>>,
     // Resources
     mut mouse_context: ResMut<MouseContext>,
@@ -217,14 +227,34 @@ pub(crate) fn interaction_system(
         Query<&TextSelection>,     // p1: Immutable access
     )>,
 ) {
-    let Ok(primary_window) = windows.get_single() else { return; };
-    let window_height = primary_window.height();
-    let cursor_pos_window_opt = primary_window.cursor_position();
-
     // Helper enum for the unified hit-test result
     enum HitResult {
-        Text { entity: Entity, z_depth: f32, cursor: Cursor },
-        Shape { entity: Entity, z_depth: f32, interaction: Interaction },
+        Text { entity: Entity, overlay: bool, z_depth: f32, cursor: Cursor },
+        Shape { entity: Entity, overlay: bool, z_depth: f32, interaction: Interaction },
+    }
+
+    impl HitResult {
+        /// Whether the hit is in the overlay layer, and whether it is on something that takes
+        /// input there rather than on the popup behind it
+        fn layer(&self) -> (bool, bool) {
+            match self {
+                HitResult::Text { overlay, .. } => (*overlay, *overlay),
+                HitResult::Shape { overlay, interaction, .. } => (*overlay, *overlay && (interaction.clickable || interaction.draggable)),
+            }
+        }
+
+        fn z_depth(&self) -> f32 {
+            match self {
+                HitResult::Text { z_depth, .. } | HitResult::Shape { z_depth, .. } => *z_depth,
+            }
+        }
+    }
+
+    // Hits in the overlay layer win over the rest of the tree; within a layer, the lowest z does
+    fn is_above(top_hit: &Option<HitResult>, hit: &HitResult) -> bool {
+        top_hit.as_ref().is_none_or(|prev_hit| {
+            hit.layer() > prev_hit.layer() || (hit.layer() == prev_hit.layer() && hit.z_depth() < prev_hit.z_depth())
+        })
     }
 
     // --- Process Mouse Button Events ---
@@ -232,15 +262,21 @@ pub(crate) fn interaction_system(
         if event.button == MouseButton::Left {
             match event.state {
                 ButtonState::Pressed => {
-                    if let Some(cursor_pos_window) = cursor_pos_window_opt {
+                    // Clicks only hit widgets drawn in the window they happened in
+                    let clicked_window = windows.get(event.window).ok()
+                        .and_then(|window| Some((window.height(), window.cursor_position()?)));
+                    if let Some((window_height, cursor_pos_window)) = clicked_window {
                         let cursor_pos_world = Vec2::new(cursor_pos_window.x, window_height - cursor_pos_window.y);
                         let mut top_hit: Option<HitResult> = None;
 
                         // --- 1. UNIFIED HIT-TESTING ---
 
                         // First, check for text hits
-                        for (entity, transform, text_cache, visibility) in editable_text_query.iter() {
+                        for (entity, transform, text_cache, visibility, single_line, clip, overlay) in editable_text_query.iter() {
                             if !visibility.is_visible() { continue; }
+                            // Widgets scrolled out of view cannot be hit where they are clipped
+                            if clip.is_some_and(|clip| !clip.contains(cursor_pos_world)) { continue; }
+                            if widget_windows.window_of(entity) != Some(event.window) { continue; }
                             if let Some(buffer) = text_cache.buffer.as_ref() {
                                 if buffer.layout_runs().next().is_none() { continue; }
 
@@ -253,21 +289,25 @@ pub(crate) fn interaction_system(
                                     bounds_min.y = bounds_min.y.min(run.line_top);
                                     bounds_max.y = bounds_max.y.max(run.line_y);
                                 }
+                                // Scrolled single-line text only shows the part inside its width
+                                let scroll = single_line.map_or(0.0, |single_line| single_line.scroll);
+                                if let Some(width) = single_line.and_then(|single_line| single_line.width) {
+                                    bounds_min.x = bounds_min.x.max(scroll);
+                                    bounds_max.x = bounds_max.x.min(scroll + width);
+                                }
                                 let mut local_bounds_ydown = Rect { min: bounds_min, max: bounds_max };
                                 local_bounds_ydown.min -= Vec2::splat(2.0);
                                 local_bounds_ydown.max += Vec2::splat(2.0);
 
                                 let inverse_transform: Affine3A = transform.affine().inverse();
                                 let cursor_pos_local_yup = inverse_transform.transform_point3(cursor_pos_world.extend(0.0)).truncate();
-                                let cursor_pos_local_ydown = Vec2::new(cursor_pos_local_yup.x, -cursor_pos_local_yup.y);
+                                let cursor_pos_local_ydown = Vec2::new(cursor_pos_local_yup.x + scroll, -cursor_pos_local_yup.y);
 
                                 if local_bounds_ydown.contains(cursor_pos_local_ydown) {
                                     if let Some(hit_cursor) = get_cursor_at_position(buffer, cursor_pos_local_ydown) {
-                                        let z_depth = transform.translation().z;
-                                        if top_hit.as_ref().map_or(true, |prev_hit| z_depth < match prev_hit {
-                                            HitResult::Text { z_depth, .. } | HitResult::Shape { z_depth, .. } => *z_depth,
-                                        }) {
-                                            top_hit = Some(HitResult::Text { entity, z_depth, cursor: hit_cursor });
+                                        let hit = HitResult::Text { entity, overlay, z_depth: transform.translation().z, cursor: hit_cursor };
+                                        if is_above(&top_hit, &hit) {
+                                            top_hit = Some(hit);
                                         }
                                     }
                                 }
@@ -275,8 +315,10 @@ pub(crate) fn interaction_system(
                         }
 
                         // Second, check for shape hits
-                        for (entity, transform, interaction, visibility, shape_data_opt) in interaction_query.iter() {
+                        for (entity, transform, interaction, visibility, shape_data_opt, clip, overlay) in interaction_query.iter() {
                             if !visibility.is_visible() { continue; }
+                            if clip.is_some_and(|clip| !clip.contains(cursor_pos_world)) { continue; }
+                            if widget_windows.window_of(entity) != Some(event.window) { continue; }
                             let inverse_transform: Affine3A = transform.affine().inverse();
                             let cursor_pos_local = inverse_transform.transform_point3(cursor_pos_world.extend(0.0)).truncate();
                             
@@ -289,15 +331,21 @@ pub(crate) fn interaction_system(
                             };
 
                             if bounds.contains(cursor_pos_local) {
-                                let z_depth = transform.translation().z;
-                                if top_hit.as_ref().map_or(true, |prev_hit| z_depth < match prev_hit {
-                                    HitResult::Text { z_depth, .. } | HitResult::Shape { z_depth, .. } => *z_depth,
-                                }) {
-                                    top_hit = Some(HitResult::Shape { entity, z_depth, interaction: *interaction });
+                                let hit = HitResult::Shape { entity, overlay, z_depth: transform.translation().z, interaction: *interaction };
+                                if is_above(&top_hit, &hit) {
+                                    top_hit = Some(hit);
                                 }
                             }
                         }
 
+                        pointer_pressed_writer.send(PointerPressed {
+                            window: event.window,
+                            position: cursor_pos_world,
+                            entity: top_hit.as_ref().map(|hit| match hit {
+                                HitResult::Text { entity, .. } | HitResult::Shape { entity, .. } => *entity,
+                            }),
+                        });
+
                         // --- 2. CENTRALIZED DECISION LOGIC ---
 
                         let previously_focused = focus_query.get_single().ok();
@@ -312,7 +360,7 @@ pub(crate) fn interaction_system(
                                 let mut global_byte_offset = 0;
                                 let mut new_x_goal: Option<i32> = None;
 
-                                if let Ok((_, _, text_cache, _)) = editable_text_query.get(target_entity) {
+                                if let Ok((_, _, text_cache, _, _, _, _)) = editable_text_query.get(target_entity) {
                                     if let Some(buffer) = text_cache.buffer.as_ref() {
                                         // Calculate global byte offset
                                         for i in 0..cursor.line {
@@ -417,8 +465,10 @@ pub(crate) fn interaction_system(
     if mouse_context.context == MouseContextType::DraggingShape {
         if let (Some(drag_entity), Some(start_pos)) = (*dragged_entity, *drag_start_position) {
             let mut last_cursor_pos = start_pos;
+            let drag_window = widget_windows.window_of(drag_entity);
+            let window_height = widget_windows.height(drag_window);
 
-            if let Some(cursor_pos_window) = cursor_moved_events.read().last().map(|e| e.position) {
+            if let Some(cursor_pos_window) = cursor_moved_events.read().filter(|e| Some(e.window) == drag_window).last().map(|e| e.position) {
                 last_cursor_pos = Vec2::new(cursor_pos_window.x, window_height - cursor_pos_window.y);
             }
 
@@ -437,6 +487,43 @@ pub(crate) fn interaction_system(
     }
 }
 
+/// Sends `EntityScrolled` for mouse wheel and trackpad input to the innermost scrollable entity
+/// under the cursor.
+fn scroll_wheel_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    windows: Query<&Window>,
+    widget_windows: WidgetWindows,
+    scrollable_query: Query<(Entity, &Scrollable, &Visibility, Option<&ClipRect>)>,
+    mut entity_scrolled_writer: EventWriter<EntityScrolled>,
+) {
+    for event in mouse_wheel_events.read() {
+        let delta = match event.unit {
+            MouseScrollUnit::Line => Vec2::new(event.x, event.y) * SCROLL_LINE_PIXELS,
+            MouseScrollUnit::Pixel => Vec2::new(event.x, event.y),
+        };
+        let scrolled_window = windows.get(event.window).ok()
+            .and_then(|window| Some((window.height(), window.cursor_position()?)));
+        let Some((window_height, cursor_pos_window)) = scrolled_window.filter(|_| delta != Vec2::ZERO) else {
+            continue;
+        };
+        let cursor_pos_world = Vec2::new(cursor_pos_window.x, window_height - cursor_pos_window.y);
+
+        // Nested scroll areas lie inside the ones around them, so the smallest is the innermost
+        let target = scrollable_query.iter()
+            .filter(|(entity, scrollable, visibility, clip)| {
+                visibility.is_visible()
+                    && widget_windows.window_of(*entity) == Some(event.window)
+                    && scrollable.area.contains(cursor_pos_world)
+                    && clip.is_none_or(|clip| clip.contains(cursor_pos_world))
+            })
+            .min_by(|(_, a, ..), (_, b, ..)| (a.area.width() * a.area.height()).total_cmp(&(b.area.width() * b.area.height())))
+            .map(|(entity, ..)| entity);
+        if let Some(entity) = target {
+            entity_scrolled_writer.send(EntityScrolled { entity, delta });
+        }
+    }
+}
+
 /// Update system: Detects keyboard input and sends HotkeyActionTriggered events.
 fn hotkey_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
@@ -490,10 +577,10 @@ fn hotkey_system(
 fn handle_close_request (
     mut ev_close: EventReader<WindowCloseRequested>,
     mut ev_app_exit: EventWriter<AppExit>,
+    primary_window: Query<(), With<PrimaryWindow>>,
 ) {
-    // Check if any WindowCloseRequested event occurred this frame
-    // Using read().next().is_some() is efficient as it stops after finding one event.
-    if ev_close.read().next().is_some() {
+    // Check if the primary window was asked to close this frame. Other windows just close.
+    if ev_close.read().any(|event| primary_window.contains(event.window)) {
         info!("WindowCloseRequested detected, sending AppExit (Interaction Plugin).");
         ev_app_exit.send(AppExit::Success);
     }
@@ -8,6 +8,10 @@ use crate::gui_framework::events::EntityDragged;
 use super::interaction::InteractionSet; // Use super:: to access sibling module
 // Import layout position control
 use crate::layout::{PositionControl, LayoutPositioned};
+// Sliders, drag values, scrollbar thumbs and tab buttons turn drags into values instead of moving
+use crate::widgets::numeric::WidgetNumeric;
+use crate::widgets::scroll_view::ScrollThumb;
+use crate::widgets::tabs::TabButton;
 
 // --- System Sets ---
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -34,7 +38,7 @@ info!("GuiFrameworkDefaultMovementPlugin built.");
 /// Update system: Applies movement deltas from EntityDragged events to Transform components.
 fn movement_system(
     mut drag_evr: EventReader<EntityDragged>,
-    mut query: Query<(&mut Transform, Option<&mut PositionControl>)>,
+    mut query: Query<(&mut Transform, Option<&mut PositionControl>, Has<WidgetNumeric>, Has<ScrollThumb>, Has<TabButton>)>,
     mut commands: Commands,
 ) {
     // Check if the system is running at all
@@ -42,7 +46,10 @@ fn movement_system(
         info!("[MovementSystem] Received EntityDragged: Entity={:?}, Delta={:?}", ev.entity, ev.delta);
 
         info!("[MovementSystem] Attempting to get Transform and PositionControl for {:?}", ev.entity);
-        if let Ok((mut transform, position_control)) = query.get_mut(ev.entity) {
+        if let Ok((mut transform, position_control, is_numeric, is_thumb, is_tab)) = query.get_mut(ev.entity) {
+            if is_numeric || is_thumb || is_tab {
+                continue;
+            }
             let old_pos = transform.translation; // Store old position for logging
             info!("[MovementSystem] Got Transform for {:?}. Before: {:?}", ev.entity, old_pos);
 
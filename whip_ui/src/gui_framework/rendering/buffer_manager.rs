@@ -7,7 +7,6 @@ use std::collections::HashMap;
 use bevy_ecs::prelude::Entity;
 use crate::{Vertex, Color}; // Import Vertex and Color
 use crate::{PreparedDrawData, RenderCommandData}; // Import command/prepared data structs
-use crate::GlobalProjectionUboResource;
 use crate::gui_framework::rendering::shader_utils; // Keep shader_utils for loading the single shader set
 use bevy_color::ColorToComponents;
 use std::sync::Arc;
@@ -77,7 +76,7 @@ impl BufferManager {
         &mut self,
         platform: &mut VulkanContext,
         render_commands: &[RenderCommandData], // Use updated RenderCommandData
-        global_ubo_res: &GlobalProjectionUboResource,
+        projection_buffer: vk::Buffer, // Projection UBO of the window being drawn
     ) -> Vec<PreparedDrawData> {
         let device = platform.device.as_ref().expect("Device missing in prepare_frame_resources");
         let allocator = platform.allocator.as_ref().expect("Allocator missing in prepare_frame_resources");
@@ -237,7 +236,7 @@ impl BufferManager {
             let offset_buffer_info_single = vk::DescriptorBufferInfo { buffer: resources.offset_uniform, offset: 0, range: std::mem::size_of::<Mat4>() as u64 };
             let writes_single = [
                 // Binding 0: Global UBO
-                vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: resources.descriptor_set, dst_binding: 0, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &vk::DescriptorBufferInfo { buffer: global_ubo_res.buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 }, ..Default::default() },
+                vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: resources.descriptor_set, dst_binding: 0, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &vk::DescriptorBufferInfo { buffer: projection_buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 }, ..Default::default() },
                 // Binding 1: Offset UBO
                 vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: resources.descriptor_set, dst_binding: 1, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &offset_buffer_info_single, ..Default::default() },
             ];
@@ -252,7 +251,7 @@ impl BufferManager {
 
                 // Create pipeline (using logic from previous attempt)
                 let pipeline = unsafe {
-                    let shader_stages = [ vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: vert_shader_module, stage: vk::ShaderStageFlags::VERTEX, p_name: b"main\0".as_ptr() as _, ..Default::default() }, vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: frag_shader_module, stage: vk::ShaderStageFlags::FRAGMENT, p_name: b"main\0".as_ptr() as _, ..Default::default() }, ];
+                    let shader_stages = [ vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: vert_shader_module, stage: vk::ShaderStageFlags::VERTEX, p_name: c"main".as_ptr(), ..Default::default() }, vk::PipelineShaderStageCreateInfo { s_type: vk::StructureType::PIPELINE_SHADER_STAGE_CREATE_INFO, module: frag_shader_module, stage: vk::ShaderStageFlags::FRAGMENT, p_name: c"main".as_ptr(), ..Default::default() }, ];
                     let vertex_attr_descs = [ vk::VertexInputAttributeDescription { location: 0, binding: 0, format: vk::Format::R32G32_SFLOAT, offset: 0 }, ];
                     let vertex_binding_descs = [ vk::VertexInputBindingDescription { binding: 0, stride: std::mem::size_of::<Vertex>() as u32, input_rate: vk::VertexInputRate::VERTEX } ];
                     let vertex_input_info = vk::PipelineVertexInputStateCreateInfo { s_type: vk::StructureType::PIPELINE_VERTEX_INPUT_STATE_CREATE_INFO, vertex_binding_description_count: vertex_binding_descs.len() as u32, p_vertex_binding_descriptions: vertex_binding_descs.as_ptr(), vertex_attribute_description_count: vertex_attr_descs.len() as u32, p_vertex_attribute_descriptions: vertex_attr_descs.as_ptr(), ..Default::default() };
@@ -293,6 +292,8 @@ impl BufferManager {
                 vertex_count: resources.vertex_count,
                 descriptor_set: resources.descriptor_set,
                 color: color_rgba, // Add color data
+                scissor: command.scissor,
+                overlay: command.overlay,
             });
         } // End of loop through render_commands
 
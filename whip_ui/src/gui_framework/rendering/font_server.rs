@@ -12,8 +12,19 @@ pub struct FontServer {
     pub font_database: fontdb::Database,
 }
 
+impl Default for FontServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FontServer {
     pub fn new() -> Self {
+        Self::with_locale(crate::assets::DEFAULT_LOCALE)
+    }
+
+    // Locale steers script shaping and font fallback, e.g. "ja-JP" prefers Japanese glyph variants
+    pub fn with_locale(locale: &str) -> Self {
         // --- Load Fonts using fontdb ---
         let mut db = fontdb::Database::new();
         // Load system fonts. This can take a moment.
@@ -30,7 +41,7 @@ impl FontServer {
         // --- Create FontSystem ---
         // Pass the fontdb Database to FontSystem.
         // FontSystem uses this database to find appropriate fonts for characters.
-        let font_system = FontSystem::new_with_locale_and_db("en-US".into(), db.clone());
+        let font_system = FontSystem::new_with_locale_and_db(locale.into(), db.clone());
 
         Self {
             font_system,
@@ -38,6 +49,15 @@ impl FontServer {
         }
     }
 
+    pub fn locale(&self) -> &str {
+        self.font_system.locale()
+    }
+
+    // FontSystem fixes its locale on creation, so switching rebuilds it over the loaded fonts
+    pub fn set_locale(&mut self, locale: &str) {
+        self.font_system = FontSystem::new_with_locale_and_db(locale.into(), self.font_database.clone());
+    }
+
     // Add methods later to query fonts, get font IDs, etc. if needed directly
     // pub fn get_font_id(&self, /* query criteria */) -> Option<fontdb::ID> { ... }
 }
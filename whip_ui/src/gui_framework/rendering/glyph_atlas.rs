@@ -206,8 +206,8 @@ impl GlyphAtlas {
                // packed_locations maps RectId (CacheKey) -> (BinId, PackedLocation)
                if let Some((_bin_id, packed_location)) = pack_result.packed_locations().get(&cache_key) {
                     // Log the raw coordinates returned by the packer
-                    let pixel_x = packed_location.x() as u32;
-                    let pixel_y = packed_location.y() as u32;
+                    let pixel_x = packed_location.x();
+                    let pixel_y = packed_location.y();
 
                     // --- 4. Upload Bitmap ---
                     self.upload_glyph_bitmap(
@@ -219,7 +219,7 @@ impl GlyphAtlas {
                         pixel_y,
                         width,
                         height,
-                        &bitmap_data,
+                        bitmap_data,
                     )?; // Propagate upload errors
 
                     // --- 5. Calculate UVs ---
@@ -1,7 +1,6 @@
 use ash::vk;
 use crate::gui_framework::context::vulkan_context::VulkanContext;
-use crate::gui_framework::rendering::swapchain::create_swapchain;
-use crate::gui_framework::rendering::swapchain::create_framebuffers;
+use crate::gui_framework::rendering::swapchain::create_window_resources;
 // Removed direct import of cleanup_swapchain_resources, it's called by ResizeHandler
 use crate::gui_framework::rendering::command_buffers::record_command_buffers;
 use crate::gui_framework::rendering::text_renderer::TextRenderer;
@@ -11,12 +10,20 @@ use crate::gui_framework::rendering::resize_handler::ResizeHandler;
 use bevy_log::{warn, error, info};
 use crate::{RenderCommandData, VulkanContextResource, TextRenderingResources}; 
 use crate::gui_framework::plugins::core::TextLayoutInfo;
-use crate::GlobalProjectionUboResource;
 use crate::BufferManagerResource;
-use bevy_ecs::prelude::Commands;
+use bevy_ecs::prelude::{Commands, Entity};
+use bevy_math::Vec2;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::sync::Arc;
 
+/// What one window draws this frame
+#[derive(Default)]
+pub struct WindowFrame {
+    pub shape_commands: Vec<RenderCommandData>,
+    pub text_layout_infos: Vec<TextLayoutInfo>,
+}
+
 
 pub struct Renderer {
     // Store pool and layouts needed for cleanup
@@ -27,10 +34,13 @@ pub struct Renderer {
 }
 
 impl Renderer {
+    // `window` is the primary window, whose surface `setup_vulkan` created
     pub fn new(
         commands: &mut Commands,
         platform: &mut VulkanContext,
+        window: Entity,
         extent: vk::Extent2D,
+        logical_size: Vec2,
     ) -> Self {
         // --- Create Command Pool (Once) and store in VulkanContext ---
         // This must happen before create_framebuffers if it allocates command buffers from this pool.
@@ -56,13 +66,12 @@ impl Renderer {
             info!("[Renderer::new] Command pool already exists in VulkanContext.");
         }
 
-        // Create swapchain (populates VulkanContext swapchain fields)
-        let surface_format = create_swapchain(platform, extent); // We need surface_format now
-        info!("[Renderer::new] Swapchain created.");
-
-        // Explicitly call create_framebuffers AFTER swapchain and its dependencies are set up
-        create_framebuffers(platform, surface_format);
-        info!("[Renderer::new] Framebuffers and command buffers created via explicit call.");
+        // Create swapchain, framebuffers (and the shared render pass), sync objects and projection UBO
+        let mut window_surface = platform.surfaces.remove(&window)
+            .expect("Primary window surface not available for renderer creation");
+        create_window_resources(platform, &mut window_surface, extent, logical_size);
+        platform.surfaces.insert(window, window_surface);
+        info!("[Renderer::new] Swapchain, framebuffers and command buffers created for the primary window.");
     
         // Create PipelineManager temporarily to get layout/pool
         let pipeline_mgr = PipelineManager::new(platform);
@@ -92,23 +101,6 @@ impl Renderer {
         let per_entity_layout = pipeline_mgr.per_entity_layout;
         let atlas_layout = pipeline_mgr.atlas_layout;
     
-        // Create sync objects
-        platform.image_available_semaphore = Some(unsafe {
-            platform.device.as_ref().unwrap().create_semaphore(&vk::SemaphoreCreateInfo::default(), None).expect("Failed to create image available semaphore")
-        });
-        platform.render_finished_semaphore = Some(unsafe {
-             platform.device.as_ref().unwrap().create_semaphore(&vk::SemaphoreCreateInfo::default(), None).expect("Failed to create render finished semaphore")
-        });
-        platform.fence = Some(unsafe {
-            platform.device.as_ref().unwrap().create_fence(
-                &vk::FenceCreateInfo {
-                    s_type: vk::StructureType::FENCE_CREATE_INFO,
-                    flags: vk::FenceCreateFlags::SIGNALED, // Start signaled
-                    p_next: std::ptr::null(),
-                    _marker: std::marker::PhantomData,
-                }, None).expect("Failed to create fence")
-        });
-    
         // Initialize Renderer struct
         Self {
             text_renderer: text_renderer_instance,
@@ -118,10 +110,11 @@ impl Renderer {
         }
     }
 
-    // Accept &mut VulkanContext and GlobalProjectionUboResource
+    // Recreates the swapchain of one window
     pub fn resize_renderer(
         &mut self,
         vk_context_res: &VulkanContextResource, // <-- Accept the resource
+        window: Entity,
         width: u32,
         height: u32,
     ) {
@@ -136,6 +129,7 @@ impl Renderer {
         if let Ok(mut vk_ctx_guard) = vk_context_res.0.lock() {
             ResizeHandler::resize(
                 &mut vk_ctx_guard, // <-- Pass mutable context guard
+                window,
                 logical_extent,
             );
             // vk_ctx_guard lock released here
@@ -144,22 +138,21 @@ impl Renderer {
         }
     }
 
+    // Queues the text resources of entities that lost their `Text` for deletion after the next fence wait
+    pub fn queue_text_removals(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.text_renderer.queue_removals(entities);
+    }
+
+    // Draws every window that has a surface; windows without an entry in `frames` are only cleared
     pub fn render(
         &mut self,
         vk_context_res: &VulkanContextResource,
         buffer_manager_res: &BufferManagerResource,
-        shape_commands: &[RenderCommandData],
-        text_layout_infos: &[TextLayoutInfo],
-        global_ubo_res: &GlobalProjectionUboResource,
+        frames: &HashMap<Entity, WindowFrame>,
         text_global_res: Option<&TextRenderingResources>, // TEMP: Accept Option
         // debug_buffer parameter removed - using tracing instead
     ) {
-        // --- Get essential handles that are relatively stable or cloneable ---
-        // These are fetched once to avoid repeated locking if possible.
-        // Device, Queue, Semaphores, Fence, Allocator can be cloned/copied.
-        // Swapchain KHR and SwapchainLoader need careful handling due to resize.
-
-        let (device, queue, image_available_semaphore, render_finished_semaphore, fence, allocator_arc, initial_swapchain_loader, initial_swapchain_khr, initial_current_extent) = {
+        let (device, allocator_arc, windows, fences) = {
             let temp_platform_guard = match vk_context_res.0.lock() {
                 Ok(guard) => guard,
                 Err(poisoned) => {
@@ -170,28 +163,22 @@ impl Renderer {
 
             (
                 temp_platform_guard.device.as_ref().expect("Device missing").clone(),
-                temp_platform_guard.queue.expect("Queue missing"),
-                temp_platform_guard.image_available_semaphore.expect("Image available semaphore missing"),
-                temp_platform_guard.render_finished_semaphore.expect("Render finished semaphore missing"),
-                temp_platform_guard.fence.expect("Fence missing"),
                 temp_platform_guard.allocator.as_ref().expect("Allocator missing").clone(),
-                temp_platform_guard.swapchain_loader.as_ref().expect("Swapchain loader missing").clone(),
-                temp_platform_guard.swapchain.expect("Swapchain KHR missing"),
-                temp_platform_guard.current_swap_extent,
+                temp_platform_guard.surfaces.keys().copied().collect::<Vec<Entity>>(),
+                temp_platform_guard.surfaces.values().filter_map(|window| window.fence).collect::<Vec<vk::Fence>>(),
             )
             // temp_platform_guard is dropped here
         };
-
-        // --- 1. Wait for previous frame's fence ---
-        if let Err(e) = unsafe { device.wait_for_fences(&[fence], true, u64::MAX) } {
-            error!("[Renderer::render] Error waiting for fence: {:?}. Skipping frame.", e);
+        if windows.is_empty() {
             return;
         }
-        if let Err(e) = unsafe { device.reset_fences(&[fence]) } {
-            error!("[Renderer::render] Error resetting fence: {:?}. Skipping frame.", e);
+
+        // --- 1. Wait for every window's previous frame ---
+        if let Err(e) = unsafe { device.wait_for_fences(&fences, true, u64::MAX) } {
+            error!("[Renderer::render] Error waiting for fences: {:?}. Skipping frame.", e);
             return;
         }
-        
+
         // --- Process pending deletions after fence wait ---
         // This ensures GPU has finished with resources from previous frame
         {
@@ -204,6 +191,51 @@ impl Renderer {
             };
             buffer_manager_guard.process_pending_deletions(&device, &allocator_arc);
         }
+        self.text_renderer.process_pending_removals(&device, &allocator_arc);
+
+        let empty_frame = WindowFrame::default();
+        for window in windows {
+            let frame = frames.get(&window).unwrap_or(&empty_frame);
+            self.render_window(vk_context_res, buffer_manager_res, window, frame, text_global_res, &device, &allocator_arc);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_window(
+        &mut self,
+        vk_context_res: &VulkanContextResource,
+        buffer_manager_res: &BufferManagerResource,
+        window: Entity,
+        frame: &WindowFrame,
+        text_global_res: Option<&TextRenderingResources>,
+        device: &ash::Device,
+        allocator_arc: &Arc<vk_mem::Allocator>,
+    ) {
+        // --- Get the window's handles ---
+        // Swapchain KHR and SwapchainLoader need careful handling due to resize.
+        let (queue, image_available_semaphore, render_finished_semaphore, fence, initial_swapchain_loader, initial_swapchain_khr, initial_current_extent) = {
+            let temp_platform_guard = match vk_context_res.0.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => {
+                    error!("[Renderer::render] Lock to get window handles failed (poisoned): {:?}. Skipping window.", poisoned);
+                    return;
+                }
+            };
+            let Some(window_surface) = temp_platform_guard.surfaces.get(&window) else {
+                return;
+            };
+
+            (
+                temp_platform_guard.queue.expect("Queue missing"),
+                window_surface.image_available_semaphore.expect("Image available semaphore missing"),
+                window_surface.render_finished_semaphore.expect("Render finished semaphore missing"),
+                window_surface.fence.expect("Fence missing"),
+                temp_platform_guard.swapchain_loader.as_ref().expect("Swapchain loader missing").clone(),
+                window_surface.swapchain.expect("Swapchain KHR missing"),
+                window_surface.current_swap_extent,
+            )
+            // temp_platform_guard is dropped here
+        };
 
         // --- 2. Acquire Swapchain Image ---
         // We use the initially fetched swapchain_loader and swapchain_khr.
@@ -219,7 +251,7 @@ impl Renderer {
                 warn!("[Renderer::render] Swapchain out of date during acquire. Triggering resize.");
                 // Lock VulkanContext to perform resize
                 match vk_context_res.0.lock() {
-                    Ok(mut platform_guard) => ResizeHandler::resize(&mut platform_guard, initial_current_extent),
+                    Ok(mut platform_guard) => ResizeHandler::resize(&mut platform_guard, window, initial_current_extent),
                     Err(_) => error!("[Renderer::render] Failed to lock context for OOD resize during acquire!"),
                 }
                 return; // Skip rest of the frame
@@ -239,7 +271,11 @@ impl Renderer {
             }
         };
 
-        platform_guard.current_image = image_index as usize;
+        let Some(window_surface) = platform_guard.surfaces.get_mut(&window) else {
+            return;
+        };
+        window_surface.current_image = image_index as usize;
+        let projection_buffer = window_surface.projection_buffer.expect("Projection UBO missing");
 
         // --- 4. Prepare Frame Resources (Buffers, Descriptors) ---
         // Lock BufferManagerResource to call prepare_frame_resources
@@ -248,8 +284,8 @@ impl Renderer {
             let mut bm_guard = buffer_manager_res.0.lock().expect("Failed to lock BufferManagerResource in render");
             bm_guard.prepare_frame_resources(
                 &mut platform_guard, 
-                shape_commands,
-                global_ubo_res,
+                &frame.shape_commands,
+                projection_buffer,
             )
         }; // bm_guard dropped here
 
@@ -258,11 +294,11 @@ impl Renderer {
                 // Get debug device extension struct reference from locked context guard
                 let debug_device_ext = platform_guard.debug_utils_device.as_ref(); // Get Option<&Device>
                 self.text_renderer.prepare_text_draws(
-                    &device, // Pass base device
-                    &allocator_arc,
+                    device, // Pass base device
+                    allocator_arc,
                     debug_device_ext, // Pass the Option<&Device>
-                    text_layout_infos,
-                    global_ubo_res,
+                    &frame.text_layout_infos,
+                    projection_buffer,
                     text_res, // Pass the unwrapped &TextRenderingResources
                     // debug_buffer removed - using tracing
                 )
@@ -271,21 +307,28 @@ impl Renderer {
         };
 
         // --- 5. Reset and Record Command Buffer ---
-        let current_command_buffer = platform_guard.command_buffers[platform_guard.current_image];
+        let window_surface = platform_guard.surfaces.get(&window).expect("Window surface removed while rendering");
+        let current_command_buffer = window_surface.command_buffers[window_surface.current_image];
         unsafe {
             device.reset_command_buffer(current_command_buffer, vk::CommandBufferResetFlags::empty())
                 .expect("Failed to reset command buffer");
         }
 
         record_command_buffers(
-            &*platform_guard, // Pass &VulkanContext
+            &platform_guard, // Pass &VulkanContext
+            window_surface,
             &prepared_shape_draws,
             &prepared_text_draws,
-            platform_guard.current_swap_extent, // Get current extent from context
+            window_surface.current_swap_extent, // Get current extent from the window
             // debug_buffer removed - using tracing
         );
 
         // --- 6. Submit Queue ---
+        // The fence is only reset once work that signals it is about to be submitted
+        if let Err(e) = unsafe { device.reset_fences(&[fence]) } {
+            error!("[Renderer::render] Error resetting fence: {:?}. Skipping frame.", e);
+            return;
+        }
         let wait_semaphores = [image_available_semaphore]; // Semaphore to wait on
         let wait_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT]; // Stage to wait at
         let signal_semaphores = [render_finished_semaphore]; // Semaphore to signal when done
@@ -308,7 +351,7 @@ impl Renderer {
 
         // --- 7. Present Queue ---
         // Use the swapchain KHR from the locked context, as it might have been updated by a resize
-        let current_swapchain_khr_for_present = platform_guard.swapchain.expect("Swapchain KHR missing for present");
+        let current_swapchain_khr_for_present = window_surface.swapchain.expect("Swapchain KHR missing for present");
         let present_info = vk::PresentInfoKHR {
             s_type: vk::StructureType::PRESENT_INFO_KHR,
             p_wait_semaphores: signal_semaphores.as_ptr(),
@@ -331,14 +374,14 @@ impl Renderer {
             Ok(suboptimal) if suboptimal => {
                 warn!("[Renderer::render] Swapchain suboptimal during present. Triggering resize.");
                 match vk_context_res.0.lock() {
-                    Ok(mut guard) => ResizeHandler::resize(&mut guard, initial_current_extent),
+                    Ok(mut guard) => ResizeHandler::resize(&mut guard, window, initial_current_extent),
                     Err(_) => error!("[Renderer::render] Failed to lock context for OOD resize (suboptimal)!"),
                 }
             }
             Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
                 warn!("[Renderer::render] Swapchain out of date during present. Triggering resize.");
                 match vk_context_res.0.lock() {
-                    Ok(mut guard) => ResizeHandler::resize(&mut guard, initial_current_extent),
+                    Ok(mut guard) => ResizeHandler::resize(&mut guard, window, initial_current_extent),
                     Err(_) => error!("[Renderer::render] Failed to lock context for OOD resize (OOD_KHR)!"),
                 }
             }
@@ -1,16 +1,25 @@
 use ash::vk;
+use bevy_ecs::entity::Entity;
+use bevy_log::warn;
 use crate::gui_framework::context::vulkan_context::VulkanContext;
 use crate::gui_framework::rendering::swapchain::{create_swapchain, create_framebuffers, cleanup_swapchain_resources}; // Import new functions
 
 pub struct ResizeHandler;
 
 impl ResizeHandler {
-    // Only handles swapchain/framebuffer recreation.
+    // Only handles swapchain/framebuffer recreation for one window.
     pub fn resize(
         vulkan_context: &mut VulkanContext,
+        window: Entity,
         logical_extent: vk::Extent2D,
         // Removed: uniform_allocation: &mut vk_mem::Allocation,
     ) {
+        // Take the window's surface out so the context can be borrowed alongside it
+        let Some(mut window_surface) = vulkan_context.surfaces.remove(&window) else {
+            warn!("[ResizeHandler::resize] No surface for window {:?}. Resize skipped.", window);
+            return;
+        };
+
         // Get device early for wait_idle
         let device = vulkan_context.device.as_ref().expect("Device not available for resize").clone(); // Clone device handle
 
@@ -20,13 +29,15 @@ impl ResizeHandler {
 
         // --- Perform operations requiring mutable access to vulkan_context ---
 
-        // 1. Cleanup old swapchain resources (Framebuffers, ImageViews, RenderPass, Swapchain)
-        cleanup_swapchain_resources(vulkan_context);
+        // 1. Cleanup old swapchain resources (Framebuffers, ImageViews, Swapchain)
+        cleanup_swapchain_resources(vulkan_context, &mut window_surface);
 
         // 2. Recreate swapchain with the new extent, get actual chosen extent back
-        let surface_format = create_swapchain(vulkan_context, logical_extent);
+        let surface_format = create_swapchain(vulkan_context, &mut window_surface, logical_extent);
+
+        // 3. Recreate framebuffers uses the extent stored in the window surface
+        create_framebuffers(vulkan_context, &mut window_surface, surface_format);
 
-        // 3. Recreate framebuffers uses the extent stored in vulkan_context
-        create_framebuffers(vulkan_context, surface_format);
+        vulkan_context.surfaces.insert(window, window_surface);
     }
 }
\ No newline at end of file
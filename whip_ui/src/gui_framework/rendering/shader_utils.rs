@@ -11,7 +11,7 @@ pub fn load_shader(device: &ash::Device, filename: &str) -> vk::ShaderModule {
         .to_path_buf();
     shader_path.push("shaders");
     shader_path.push(filename);
-    let shader_code = fs::read(&shader_path).expect(&format!("Failed to read shader file: {:?}", shader_path));
+    let shader_code = fs::read(&shader_path).unwrap_or_else(|_| panic!("Failed to read shader file: {:?}", shader_path));
     let shader_module_info = vk::ShaderModuleCreateInfo {
         s_type: vk::StructureType::SHADER_MODULE_CREATE_INFO,
         p_next: std::ptr::null(),
@@ -21,5 +21,5 @@ pub fn load_shader(device: &ash::Device, filename: &str) -> vk::ShaderModule {
         _marker: PhantomData,
     };
     unsafe { device.create_shader_module(&shader_module_info, None) }
-        .expect(&format!("Failed to create shader module from: {}", filename))
+        .unwrap_or_else(|_| panic!("Failed to create shader module from: {}", filename))
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 use ash::vk;
-use crate::gui_framework::context::vulkan_context::VulkanContext;
+use crate::gui_framework::context::vulkan_context::{VulkanContext, WindowSurface};
 use bevy_log::{info, error, warn};
+use bevy_math::{Mat4, Vec2};
+use bevy_window::PresentMode;
 use vk_mem::{Alloc, AllocationCreateInfo}; // For depth image allocation
 use crate::gui_framework::context::vulkan_setup::set_debug_object_name;
 
@@ -22,12 +24,44 @@ fn find_supported_format(
     })
 }
 
-// Only return format, store chosen extent in platform
-pub fn create_swapchain(platform: &mut VulkanContext, extent: vk::Extent2D) -> vk::SurfaceFormatKHR {
+// Vulkan present mode for a window's requested mode, falling back to FIFO, which is always supported
+fn choose_present_mode(requested: PresentMode, available: &[vk::PresentModeKHR]) -> vk::PresentModeKHR {
+    let preferred: &[vk::PresentModeKHR] = match requested {
+        // Lowest latency without tearing, as the renderer has always preferred
+        PresentMode::AutoVsync => &[vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::FIFO_RELAXED],
+        PresentMode::AutoNoVsync => &[vk::PresentModeKHR::IMMEDIATE, vk::PresentModeKHR::MAILBOX],
+        PresentMode::Fifo => &[],
+        PresentMode::FifoRelaxed => &[vk::PresentModeKHR::FIFO_RELAXED],
+        PresentMode::Immediate => &[vk::PresentModeKHR::IMMEDIATE],
+        PresentMode::Mailbox => &[vk::PresentModeKHR::MAILBOX],
+    };
+    preferred.iter().copied()
+        .find(|mode| available.contains(mode))
+        .unwrap_or(vk::PresentModeKHR::FIFO)
+}
+
+// Composite alpha for a window, blending with what is behind it if it is transparent and the surface allows it
+fn choose_composite_alpha(transparent: bool, supported: vk::CompositeAlphaFlagsKHR) -> vk::CompositeAlphaFlagsKHR {
+    let blended = [
+        vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+        vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        vk::CompositeAlphaFlagsKHR::INHERIT,
+    ];
+    if transparent {
+        if let Some(mode) = blended.into_iter().find(|mode| supported.contains(*mode)) {
+            return mode;
+        }
+        warn!("Surface does not support transparency; the window will be opaque");
+    }
+    vk::CompositeAlphaFlagsKHR::OPAQUE
+}
+
+// Only return format, store chosen extent in the window surface
+pub fn create_swapchain(platform: &mut VulkanContext, window: &mut WindowSurface, extent: vk::Extent2D) -> vk::SurfaceFormatKHR {
     let instance = platform.instance.as_ref().expect("Instance not available for swapchain creation");
     let device = platform.device.as_ref().expect("Device not available for swapchain creation");
     let surface_loader = platform.surface_loader.as_ref().expect("Surface loader not available");
-    let surface = platform.surface.expect("Surface not available");
+    let surface = window.surface;
     let queue_family_index = platform.queue_family_index.expect("Queue family index not set");
     let physical_device = platform.physical_device.expect("Physical device not set in VulkanContext");
 
@@ -43,25 +77,20 @@ pub fn create_swapchain(platform: &mut VulkanContext, extent: vk::Extent2D) -> v
         surface_loader.get_physical_device_surface_present_modes(physical_device, surface)
     }.expect("Failed to query present modes");
 
-    let surface_format = surface_formats
+    let surface_format = *surface_formats
         .iter()
         .find(|f| {
             f.format == vk::Format::B8G8R8A8_SRGB && f.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
         })
-        .unwrap_or_else(|| surface_formats.first().expect("No surface formats available"))
-        .clone();
+        .unwrap_or_else(|| surface_formats.first().expect("No surface formats available"));
 
-    let present_mode = present_modes
-        .iter()
-        .cloned()
-        .find(|&m| m == vk::PresentModeKHR::MAILBOX)
-        .unwrap_or(vk::PresentModeKHR::FIFO);
+    let present_mode = choose_present_mode(window.present_mode, &present_modes);
 
     let swap_extent = vk::Extent2D {
         width: extent.width.clamp(surface_caps.min_image_extent.width, surface_caps.max_image_extent.width),
         height: extent.height.clamp(surface_caps.min_image_extent.height, surface_caps.max_image_extent.height),
     };
-    platform.current_swap_extent = swap_extent;
+    window.current_swap_extent = swap_extent;
 
     let mut image_count = surface_caps.min_image_count + 1;
     if surface_caps.max_image_count > 0 && image_count > surface_caps.max_image_count {
@@ -84,20 +113,20 @@ pub fn create_swapchain(platform: &mut VulkanContext, extent: vk::Extent2D) -> v
         queue_family_index_count: 1,
         p_queue_family_indices: &queue_family_index,
         pre_transform: surface_caps.current_transform,
-        composite_alpha: vk::CompositeAlphaFlagsKHR::OPAQUE,
+        composite_alpha: choose_composite_alpha(window.transparent, surface_caps.supported_composite_alpha),
         present_mode,
         clipped: vk::TRUE,
         old_swapchain: vk::SwapchainKHR::null(),
         ..Default::default()
     };
 
-    platform.swapchain = Some(unsafe { swapchain_loader.create_swapchain(&swapchain_create_info, None) }
+    window.swapchain = Some(unsafe { swapchain_loader.create_swapchain(&swapchain_create_info, None) }
         .expect("Failed to create swapchain"));
 
-    platform.images = unsafe { swapchain_loader.get_swapchain_images(platform.swapchain.unwrap()) }
+    window.images = unsafe { swapchain_loader.get_swapchain_images(window.swapchain.unwrap()) }
         .expect("Failed to get swapchain images");
 
-    platform.image_views = platform.images.iter().map(|&image| {
+    window.image_views = window.images.iter().map(|&image| {
         let view_info = vk::ImageViewCreateInfo {
             s_type: vk::StructureType::IMAGE_VIEW_CREATE_INFO,
             image,
@@ -161,8 +190,8 @@ pub fn create_swapchain(platform: &mut VulkanContext, extent: vk::Extent2D) -> v
         set_debug_object_name(debug_device_ext, mem_handle, vk::ObjectType::DEVICE_MEMORY, "DepthImage_Mem"); // Pass ext
     }
         // --- END NAME ---
-        platform.depth_image = Some(depth_image);
-        platform.depth_image_allocation = Some(depth_image_allocation);
+        window.depth_image = Some(depth_image);
+        window.depth_image_allocation = Some(depth_image_allocation);
     
     
         let depth_image_view = unsafe {
@@ -182,14 +211,14 @@ pub fn create_swapchain(platform: &mut VulkanContext, extent: vk::Extent2D) -> v
         };
         device.create_image_view(&view_info, None)
     }.expect("Failed to create depth image view");
-    platform.depth_image_view = Some(depth_image_view);
+    window.depth_image_view = Some(depth_image_view);
 
     surface_format
 }
 
 
-// Uses the extent stored in platform
-pub fn create_framebuffers(platform: &mut VulkanContext, surface_format: vk::SurfaceFormatKHR) {
+// Uses the extent stored in the window surface. The render pass is created with the first window.
+pub fn create_framebuffers(platform: &mut VulkanContext, window: &mut WindowSurface, surface_format: vk::SurfaceFormatKHR) {
     let device = platform.device.as_ref().expect("Device not available for framebuffer creation");
 
     // Create Render Pass (if it doesn't exist). Includes depth
@@ -264,63 +293,63 @@ pub fn create_framebuffers(platform: &mut VulkanContext, surface_format: vk::Sur
     }
 
     // Create Framebuffers - Includes depth view
-    let depth_view = platform.depth_image_view.expect("Depth image view missing for framebuffer creation");
-    info!("[Swapchain::create_framebuffers] Created {} framebuffers.", platform.framebuffers.len()); // Log framebuffer count
-    platform.framebuffers = platform.image_views.iter().map(|&color_view| {
+    let depth_view = window.depth_image_view.expect("Depth image view missing for framebuffer creation");
+    window.framebuffers = window.image_views.iter().map(|&color_view| {
         let attachments = [color_view, depth_view]; // Color attachment 0, Depth attachment 1
         let framebuffer_info = vk::FramebufferCreateInfo {
             s_type: vk::StructureType::FRAMEBUFFER_CREATE_INFO,
             render_pass: platform.render_pass.unwrap(),
             attachment_count: attachments.len() as u32,
             p_attachments: attachments.as_ptr(),
-            width: platform.current_swap_extent.width,
-            height: platform.current_swap_extent.height,
+            width: window.current_swap_extent.width,
+            height: window.current_swap_extent.height,
             layers: 1,
             ..Default::default()
         };
         unsafe { device.create_framebuffer(&framebuffer_info, None) }.expect("Failed to create framebuffer")
     }).collect();
+    info!("[Swapchain::create_framebuffers] Created {} framebuffers.", window.framebuffers.len()); // Log framebuffer count
 
     // --- Allocate Command Buffers (one per framebuffer/swapchain image) ---
     // Ensure command pool exists
     let command_pool = platform.command_pool.expect("Command pool not available for command buffer allocation");
 
     // Free old command buffers if they exist
-    if !platform.command_buffers.is_empty() {
+    if !window.command_buffers.is_empty() {
         unsafe {
-            device.free_command_buffers(command_pool, &platform.command_buffers);
+            device.free_command_buffers(command_pool, &window.command_buffers);
         }
-        platform.command_buffers.clear();
+        window.command_buffers.clear();
         info!("[Swapchain::create_framebuffers] Freed old command buffers.");
     }
 
     // This check is now more critical: if framebuffers weren't created, we MUST NOT allocate 0 command buffers.
-    if platform.framebuffers.is_empty() {
+    if window.framebuffers.is_empty() {
         warn!("[Swapchain::create_framebuffers] No framebuffers were created (e.g., image_views might be empty). Skipping command buffer allocation.");
-        platform.command_buffers = Vec::new(); // Ensure it's empty
+        window.command_buffers = Vec::new(); // Ensure it's empty
     } else {
         let command_buffer_allocate_info = vk::CommandBufferAllocateInfo {
             s_type: vk::StructureType::COMMAND_BUFFER_ALLOCATE_INFO,
             p_next: std::ptr::null(),
             command_pool,
             level: vk::CommandBufferLevel::PRIMARY,
-            command_buffer_count: platform.framebuffers.len() as u32, // This should now be > 0
+            command_buffer_count: window.framebuffers.len() as u32, // This should now be > 0
             _marker: std::marker::PhantomData,
         };
 
-        platform.command_buffers = unsafe {
+        window.command_buffers = unsafe {
             device
                 .allocate_command_buffers(&command_buffer_allocate_info)
                 .expect("Failed to allocate command buffers")
         };
-        info!("[Swapchain::create_framebuffers] Allocated {} command buffers.", platform.command_buffers.len());
+        info!("[Swapchain::create_framebuffers] Allocated {} command buffers.", window.command_buffers.len());
     }
 }
 
 
-/// Cleans up swapchain-related resources (ImageViews, Framebuffers, RenderPass, Swapchain itself).
-/// Assumes device is idle.
-pub fn cleanup_swapchain_resources(platform: &mut VulkanContext) {
+/// Cleans up a window's swapchain-related resources (ImageViews, Framebuffers, Swapchain itself).
+/// The shared RenderPass is kept for the recreated swapchain. Assumes device is idle.
+pub fn cleanup_swapchain_resources(platform: &VulkanContext, window: &mut WindowSurface) {
     let device = match platform.device.as_ref() {
         Some(d) => d,
         None => {
@@ -339,25 +368,25 @@ pub fn cleanup_swapchain_resources(platform: &mut VulkanContext) {
 
     unsafe {
         if let Some(pool) = platform.command_pool {
-            if !platform.command_buffers.is_empty() {
-                device.free_command_buffers(pool, &platform.command_buffers);
-                platform.command_buffers.clear();
+            if !window.command_buffers.is_empty() {
+                device.free_command_buffers(pool, &window.command_buffers);
+                window.command_buffers.clear();
                 info!("[cleanup_swapchain_resources] Freed command buffers.");
             }
         } else {
-            if !platform.command_buffers.is_empty() {
+            if !window.command_buffers.is_empty() {
                 warn!("[cleanup_swapchain_resources] Command buffers exist but command pool is None. Buffers not freed.");
-                platform.command_buffers.clear();
+                window.command_buffers.clear();
             }
         }
 
         // --- Destroy Depth Buffer Resources ---
-        if let Some(view) = platform.depth_image_view.take() {
+        if let Some(view) = window.depth_image_view.take() {
             info!("[cleanup_swapchain_resources] Destroying DepthImageView {:?}.", view);
             device.destroy_image_view(view, None);
         }
-        let depth_image_opt = platform.depth_image.take();
-        let mut depth_alloc_opt = platform.depth_image_allocation.take();
+        let depth_image_opt = window.depth_image.take();
+        let mut depth_alloc_opt = window.depth_image_allocation.take();
         if let (Some(image), Some(allocation)) = (depth_image_opt, depth_alloc_opt.as_mut()) {
             if let Some(allocator) = platform.allocator.as_ref() {
                 info!("[cleanup_swapchain_resources] Destroying DepthImage {:?} and its allocation.", image);
@@ -366,28 +395,20 @@ pub fn cleanup_swapchain_resources(platform: &mut VulkanContext) {
                 error!("[cleanup_swapchain_resources] Allocator not available to destroy depth image!");
             }
         }
-        platform.depth_format = None;
         // --- End Depth Buffer Cleanup ---
 
-        info!("[cleanup_swapchain_resources] Destroying {} framebuffers.", platform.framebuffers.len());
-        for fb in platform.framebuffers.drain(..) {
+        info!("[cleanup_swapchain_resources] Destroying {} framebuffers.", window.framebuffers.len());
+        for fb in window.framebuffers.drain(..) {
             device.destroy_framebuffer(fb, None);
         }
 
-        info!("[cleanup_swapchain_resources] Destroying {} image views.", platform.image_views.len());
-        for view in platform.image_views.drain(..) {
+        info!("[cleanup_swapchain_resources] Destroying {} image views.", window.image_views.len());
+        for view in window.image_views.drain(..) {
             device.destroy_image_view(view, None);
         }
-        platform.images.clear();
-
-        if let Some(rp) = platform.render_pass.take() {
-            info!("[cleanup_swapchain_resources] Destroying RenderPass {:?}.", rp);
-            device.destroy_render_pass(rp, None);
-        } else {
-            info!("[cleanup_swapchain_resources] RenderPass was already None.");
-        }
+        window.images.clear();
 
-        if let Some(sc) = platform.swapchain.take() {
+        if let Some(sc) = window.swapchain.take() {
             // Need swapchain_loader for this
             if let Some(loader) = platform.swapchain_loader.as_ref() {
                 info!("[cleanup_swapchain_resources] Destroying Swapchain {:?}.", sc);
@@ -400,4 +421,102 @@ pub fn cleanup_swapchain_resources(platform: &mut VulkanContext) {
         }
     }
     info!("[cleanup_swapchain_resources] Finished cleaning up swapchain resources.");
+}
+
+/// Creates everything a window with a surface needs to be drawn to: swapchain, framebuffers,
+/// command buffers, sync objects and its projection uniform buffer.
+pub fn create_window_resources(platform: &mut VulkanContext, window: &mut WindowSurface, extent: vk::Extent2D, logical_size: Vec2) {
+    let surface_format = create_swapchain(platform, window, extent);
+    create_framebuffers(platform, window, surface_format);
+
+    let device = platform.device.as_ref().expect("Device not available for window resource creation");
+    unsafe {
+        window.image_available_semaphore = Some(device.create_semaphore(&vk::SemaphoreCreateInfo::default(), None).expect("Failed to create image available semaphore"));
+        window.render_finished_semaphore = Some(device.create_semaphore(&vk::SemaphoreCreateInfo::default(), None).expect("Failed to create render finished semaphore"));
+        window.fence = Some(device.create_fence(
+            &vk::FenceCreateInfo {
+                s_type: vk::StructureType::FENCE_CREATE_INFO,
+                flags: vk::FenceCreateFlags::SIGNALED, // Start signaled
+                p_next: std::ptr::null(),
+                _marker: std::marker::PhantomData,
+            }, None).expect("Failed to create fence"));
+    }
+
+    // --- Create Projection UBO ---
+    let allocator = platform.allocator.as_ref().expect("Allocator missing");
+    let (buffer, allocation) = unsafe {
+        let buffer_info = vk::BufferCreateInfo {
+            s_type: vk::StructureType::BUFFER_CREATE_INFO,
+            size: std::mem::size_of::<Mat4>() as vk::DeviceSize,
+            usage: vk::BufferUsageFlags::UNIFORM_BUFFER,
+            sharing_mode: vk::SharingMode::EXCLUSIVE,
+            ..Default::default()
+        };
+        let allocation_info = AllocationCreateInfo {
+            usage: vk_mem::MemoryUsage::AutoPreferDevice,
+            flags: vk_mem::AllocationCreateFlags::MAPPED
+            | vk_mem::AllocationCreateFlags::HOST_ACCESS_SEQUENTIAL_WRITE,
+            ..Default::default()
+        };
+        allocator.create_buffer(&buffer_info, &allocation_info)
+    }.expect("Projection UBO buffer creation failed");
+    // --- NAME Projection UBO & Memory ---
+    #[cfg(debug_assertions)]
+    if let Some(debug_device_ext) = platform.debug_utils_device.as_ref() {
+        let mem_handle = allocator.get_allocation_info(&allocation).device_memory;
+        set_debug_object_name(debug_device_ext, buffer, vk::ObjectType::BUFFER, "ProjectionUBO");
+        set_debug_object_name(debug_device_ext, mem_handle, vk::ObjectType::DEVICE_MEMORY, "ProjectionUBO_Mem");
+    }
+    // --- END NAME ---
+    window.projection_buffer = Some(buffer);
+    window.projection_allocation = Some(allocation);
+    write_projection(platform, window, logical_size);
+}
+
+/// Writes the orthographic projection for a window's logical size into its projection UBO
+pub fn write_projection(platform: &VulkanContext, window: &WindowSurface, logical_size: Vec2) {
+    let (Some(allocator), Some(allocation)) = (platform.allocator.as_ref(), window.projection_allocation.as_ref()) else {
+        warn!("[write_projection] Allocator or projection allocation missing. Projection not updated.");
+        return;
+    };
+    let proj = Mat4::orthographic_rh(0.0, logical_size.x, 0.0, logical_size.y, 1024.0, 0.0);
+    let flip_y = Mat4::from_scale(bevy_math::Vec3::new(1.0, -1.0, 1.0));
+    let proj_matrix = flip_y * proj;
+    unsafe {
+        let info = allocator.get_allocation_info(allocation);
+        if !info.mapped_data.is_null() {
+            let data_ptr = info.mapped_data.cast::<f32>();
+            data_ptr.copy_from_nonoverlapping(proj_matrix.to_cols_array().as_ptr(), 16);
+        } else {
+            error!("[write_projection] Failed to get mapped pointer for projection UBO write.");
+        }
+    }
+}
+
+/// Destroys a window's swapchain resources, sync objects, projection UBO and surface.
+/// Assumes device is idle.
+pub fn destroy_window_surface(platform: &VulkanContext, mut window: WindowSurface) {
+    cleanup_swapchain_resources(platform, &mut window);
+    if let Some(device) = platform.device.as_ref() {
+        unsafe {
+            if let Some(sema) = window.image_available_semaphore.take() {
+                device.destroy_semaphore(sema, None);
+            }
+            if let Some(sema) = window.render_finished_semaphore.take() {
+                device.destroy_semaphore(sema, None);
+            }
+            if let Some(fen) = window.fence.take() {
+                device.destroy_fence(fen, None);
+            }
+        }
+    }
+    if let (Some(allocator), Some(buffer), Some(mut allocation)) =
+        (platform.allocator.as_ref(), window.projection_buffer.take(), window.projection_allocation.take())
+    {
+        unsafe { allocator.destroy_buffer(buffer, &mut allocation); }
+    }
+    if let Some(surface_loader) = platform.surface_loader.as_ref() {
+        info!("[destroy_window_surface] Destroying surface {:?}.", window.surface);
+        unsafe { surface_loader.destroy_surface(window.surface, None); }
+    }
 }
\ No newline at end of file
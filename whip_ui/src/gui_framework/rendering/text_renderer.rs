@@ -12,12 +12,14 @@ use crate::{
         components::TextRenderData,
         plugins::core::TextLayoutInfo,
     },
-    GlobalProjectionUboResource, PreparedTextDrawData, TextRenderingResources, TextVertex,
+    PreparedTextDrawData, TextRenderingResources, TextVertex,
     // VulkanContextResource, // Not needed directly here, device/allocator passed in
 };
 
 pub struct TextRenderer {
     text_render_resources: HashMap<Entity, TextRenderData>,
+    // Entities whose text is gone, freed once the GPU has finished the frames using them
+    pending_removals: Vec<Entity>,
     descriptor_pool: vk::DescriptorPool,
     per_entity_layout_set0: vk::DescriptorSetLayout,
 }
@@ -29,11 +31,56 @@ impl TextRenderer {
     ) -> Self {
         Self {
             text_render_resources: HashMap::new(),
+            pending_removals: Vec::new(),
             descriptor_pool,
             per_entity_layout_set0,
         }
     }
 
+    /// Queue the resources of entities that lost their `Text` for deletion by `process_pending_removals`
+    pub fn queue_removals(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.pending_removals.extend(entities);
+    }
+
+    /// Free the vertex buffers, transform UBOs and descriptor sets of queued entities.
+    /// Call after waiting for the fences of the frames that drew them.
+    pub fn process_pending_removals(
+        &mut self,
+        device: &ash::Device,
+        allocator: &Arc<vk_mem::Allocator>,
+    ) {
+        for entity in std::mem::take(&mut self.pending_removals) {
+            self.remove_entity_resources(entity, device, allocator);
+        }
+    }
+
+    fn remove_entity_resources(
+        &mut self,
+        entity: Entity,
+        device: &ash::Device,
+        allocator: &Arc<vk_mem::Allocator>,
+    ) {
+        let Some(mut removed_data) = self.text_render_resources.remove(&entity) else {
+            return;
+        };
+        unsafe {
+            // Using explicit destroy_buffer and free_memory as per our last successful step
+            if removed_data.transform_ubo != vk::Buffer::null() {
+                device.destroy_buffer(removed_data.transform_ubo, None);
+                allocator.free_memory(&mut removed_data.transform_alloc);
+            }
+            if removed_data.vertex_buffer != vk::Buffer::null() {
+                device.destroy_buffer(removed_data.vertex_buffer, None);
+                allocator.free_memory(&mut removed_data.vertex_alloc);
+            }
+            if removed_data.descriptor_set_0 != vk::DescriptorSet::null() {
+                if let Err(e) = device.free_descriptor_sets(self.descriptor_pool, &[removed_data.descriptor_set_0]) {
+                    error!("[TextRenderer] Failed to free text descriptor set for {:?}: {:?}", entity, e);
+                }
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn prepare_text_draws(
         &mut self,
@@ -41,7 +88,7 @@ impl TextRenderer {
         allocator: &Arc<vk_mem::Allocator>,
         debug_device_ext: Option<&debug_utils::Device>, // Corrected type
         text_layout_infos: &[TextLayoutInfo],
-        global_ubo_res: &GlobalProjectionUboResource,
+        projection_buffer: vk::Buffer, // Projection UBO of the window being drawn
         text_global_res: &TextRenderingResources,
         // debug_buffer parameter removed - using tracing instead
     ) -> Vec<PreparedTextDrawData> {
@@ -108,24 +155,9 @@ impl TextRenderer {
             }
 
             if vertex_count == 0 {
-                if let Some(mut removed_data) = self.text_render_resources.remove(&entity) {
+                if self.text_render_resources.contains_key(&entity) {
                     warn!("[TextRenderer] Cleaning up TextRenderData for entity {:?} with 0 vertices.", entity);
-                    unsafe {
-                        // Using explicit destroy_buffer and free_memory as per our last successful step
-                        if removed_data.transform_ubo != vk::Buffer::null() {
-                            device.destroy_buffer(removed_data.transform_ubo, None);
-                            allocator.free_memory(&mut removed_data.transform_alloc);
-                        }
-                        if removed_data.vertex_buffer != vk::Buffer::null() {
-                            device.destroy_buffer(removed_data.vertex_buffer, None);
-                            allocator.free_memory(&mut removed_data.vertex_alloc);
-                        }
-                        if removed_data.descriptor_set_0 != vk::DescriptorSet::null() {
-                            if let Err(e) = device.free_descriptor_sets(self.descriptor_pool, &[removed_data.descriptor_set_0]) {
-                                error!("[TextRenderer] Failed to free text descriptor set for {:?}: {:?}", entity, e);
-                            }
-                        }
-                    }
+                    self.remove_entity_resources(entity, device, allocator);
                 }
                 continue;
             }
@@ -189,7 +221,7 @@ impl TextRenderer {
                 }
 
                 let transform_buffer_info = vk::DescriptorBufferInfo { buffer: render_data.transform_ubo, offset: 0, range: std::mem::size_of::<Mat4>() as u64 };
-                let global_buffer_info = vk::DescriptorBufferInfo { buffer: global_ubo_res.buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 };
+                let global_buffer_info = vk::DescriptorBufferInfo { buffer: projection_buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 };
                 let writes = [
                     vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: render_data.descriptor_set_0, dst_binding: 0, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &global_buffer_info, ..Default::default() },
                     vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: render_data.descriptor_set_0, dst_binding: 1, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &transform_buffer_info, ..Default::default() },
@@ -202,6 +234,8 @@ impl TextRenderer {
                     vertex_count: render_data.vertex_count,
                     projection_descriptor_set: render_data.descriptor_set_0,
                     atlas_descriptor_set: text_global_res.atlas_descriptor_set,
+                    scissor: layout_info.scissor,
+                    overlay: layout_info.overlay,
                 });
             } else {
                 // Create New Entity Resources
@@ -287,7 +321,7 @@ impl TextRenderer {
                 let descriptor_set_0 = unsafe { device.allocate_descriptor_sets(&alloc_info_for_desc).expect("Failed to allocate text descriptor set 0").remove(0) };
 
                 let transform_buffer_info_desc = vk::DescriptorBufferInfo { buffer: transform_ubo, offset: 0, range: std::mem::size_of::<Mat4>() as u64 }; // Distinct name
-                let global_buffer_info_desc = vk::DescriptorBufferInfo { buffer: global_ubo_res.buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 }; // Distinct name
+                let global_buffer_info_desc = vk::DescriptorBufferInfo { buffer: projection_buffer, offset: 0, range: std::mem::size_of::<Mat4>() as u64 }; // Distinct name
                 let writes = [
                     vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: descriptor_set_0, dst_binding: 0, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &global_buffer_info_desc, ..Default::default() },
                     vk::WriteDescriptorSet { s_type: vk::StructureType::WRITE_DESCRIPTOR_SET, dst_set: descriptor_set_0, dst_binding: 1, descriptor_count: 1, descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, p_buffer_info: &transform_buffer_info_desc, ..Default::default() },
@@ -309,6 +343,8 @@ impl TextRenderer {
                     vertex_count: new_render_data.vertex_count,
                     projection_descriptor_set: new_render_data.descriptor_set_0,
                     atlas_descriptor_set: text_global_res.atlas_descriptor_set,
+                    scissor: layout_info.scissor,
+                    overlay: layout_info.overlay,
                 });
 
                 self.text_render_resources.insert(entity, new_render_data);
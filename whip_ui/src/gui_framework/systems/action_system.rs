@@ -1,5 +1,6 @@
 use bevy_ecs::prelude::*;
 use bevy_log::{debug, info, warn};
+use crate::assets::{Localization, UiRegistry, UiState};
 use crate::gui_framework::events::{ActionEvent, BuiltinAction, ActionRegistry};
 use crate::Visibility;
 use bevy_hierarchy::Children;
@@ -8,6 +9,9 @@ use bevy_hierarchy::Children;
 pub fn action_execution_system(
     mut action_events: EventReader<ActionEvent>,
     action_registry: Option<Res<ActionRegistry>>,
+    mut ui_state: Option<ResMut<UiState>>,
+    ui_registry: Option<Res<UiRegistry>>,
+    mut localization: Option<ResMut<Localization>>,
     mut visibility_query: Query<&mut Visibility>,
     children_query: Query<&Children>,
 ) {
@@ -16,6 +20,35 @@ pub fn action_execution_system(
         
         // First try to handle as builtin action
         if let Some(builtin_action) = BuiltinAction::from_action_event(action_event) {
+            // State actions need the state store and the registry's operation table
+            if let BuiltinAction::UpdateState { path, operation } = &builtin_action {
+                match (ui_state.as_deref_mut(), ui_registry.as_deref()) {
+                    (Some(ui_state), Some(ui_registry)) => {
+                        match ui_state.apply(path, operation, ui_registry) {
+                            Ok(()) => debug!("🗃️ ACTION {}: '{}' is now {:?}", action_event.action, path, ui_state.get(path)),
+                            Err(e) => warn!("Action '{}' failed: {}", action_event.action, e),
+                        }
+                    }
+                    _ => warn!("Action '{}' needs the UiState and UiRegistry resources", action_event.action),
+                }
+                continue;
+            }
+
+            // Localized text and the font locale follow the Localization resource
+            if let BuiltinAction::SetLocale { locale } = &builtin_action {
+                match localization.as_deref_mut() {
+                    Some(localization) => {
+                        if !localization.has_locale(locale) {
+                            warn!("No string table loaded for locale '{}'; showing {} strings", locale, localization.fallback_locale());
+                        }
+                        info!("🌐 ACTION SET_LOCALE: {}", locale);
+                        localization.set_locale(locale.clone());
+                    }
+                    None => warn!("Action '{}' needs the Localization resource", action_event.action),
+                }
+                continue;
+            }
+
             execute_builtin_action(
                 builtin_action,
                 action_event,
@@ -72,6 +105,10 @@ fn execute_builtin_action(
             // TODO: Implement focus management
             // For now, just log the action
         }
+
+        BuiltinAction::UpdateState { .. } | BuiltinAction::SetLocale { .. } => {
+            // Applied by action_execution_system, which owns the state resources
+        }
     }
 }
 
@@ -110,9 +147,34 @@ pub fn interaction_to_action_system(
     }
 }
 
+/// The action a widget's `change` binding fires when its value becomes `value`.
+///
+/// The new value is passed as the `value` parameter, so `set_state` bindings store it directly.
+pub fn change_action(
+    bindings: Option<&crate::widgets::components::WidgetActionBindings>,
+    entity: Entity,
+    value: serde_json::Value,
+) -> Option<ActionEvent> {
+    value_action(bindings, entity, "change", value)
+}
+
+/// The action a widget's binding for `event` fires, with `value` as its `value` parameter
+pub fn value_action(
+    bindings: Option<&crate::widgets::components::WidgetActionBindings>,
+    entity: Entity,
+    event: &str,
+    value: serde_json::Value,
+) -> Option<ActionEvent> {
+    let binding = bindings?.bindings.get(event)?;
+    let mut params = binding.params.clone().unwrap_or_default();
+    params.insert("value".to_string(), value);
+    Some(ActionEvent::new(binding.action.clone(), entity, event.to_string()).with_params(params))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy_ecs::system::RunSystemOnce;
     use bevy_ecs::world::World;
     use std::collections::HashMap;
 
@@ -122,7 +184,7 @@ mod tests {
         let entity = world.spawn_empty().id();
         
         let mut params = HashMap::new();
-        params.insert("message".to_string(), toml::Value::String("Test debug message".to_string()));
+        params.insert("message".to_string(), serde_json::Value::String("Test debug message".to_string()));
         
         let action_event = ActionEvent::new(
             "debug".to_string(),
@@ -155,7 +217,7 @@ mod tests {
             action: "debug".to_string(),
             params: Some({
                 let mut params = HashMap::new();
-                params.insert("message".to_string(), toml::Value::String("Test message".to_string()));
+                params.insert("message".to_string(), serde_json::Value::String("Test message".to_string()));
                 params
             }),
         });
@@ -167,11 +229,11 @@ mod tests {
             .send(click_event);
         
         // Run the interaction to action system
-        world.run_system_once(interaction_to_action_system);
+        world.run_system_once(interaction_to_action_system).unwrap();
         
         // Check that an action event was generated
         let action_events = world.resource::<Events<ActionEvent>>();
-        let mut reader = action_events.get_reader();
+        let mut reader = action_events.get_cursor();
         let events: Vec<_> = reader.read(action_events).collect();
         
         assert_eq!(events.len(), 1);
@@ -194,11 +256,11 @@ mod tests {
             .send(click_event);
         
         // Run the interaction to action system
-        world.run_system_once(interaction_to_action_system);
+        world.run_system_once(interaction_to_action_system).unwrap();
         
         // Check that NO action event was generated
         let action_events = world.resource::<Events<ActionEvent>>();
-        let mut reader = action_events.get_reader();
+        let mut reader = action_events.get_cursor();
         let events: Vec<_> = reader.read(action_events).collect();
         
         assert_eq!(events.len(), 0); // No actions should be generated
@@ -2,7 +2,7 @@ pub mod action_system;
 pub mod state_tracking;
 pub mod style_resolver;
 
-pub use action_system::{action_execution_system, interaction_to_action_system};
+pub use action_system::{action_execution_system, interaction_to_action_system, change_action, value_action};
 pub use state_tracking::{
     interaction_state_tracking_system, hover_detection_system, press_detection_system,
     focus_detection_system, drag_detection_system, interaction_state_debug_system,
@@ -3,7 +3,9 @@ use bevy_ecs::prelude::*;
 use bevy_utils::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use bevy_input::{mouse::MouseButton, ButtonInput};
 use crate::gui_framework::components::{InteractionState, InteractionStateChanged, Interaction};
+use crate::gui_framework::events::EntityDragged;
 
 /// Resource for tracking state changes and preventing duplicate logs
 #[derive(Resource, Default)]
@@ -63,7 +65,7 @@ pub fn interaction_state_tracking_system(
 pub fn hover_detection_system(
     mut state_query: Query<(Entity, &mut InteractionState, &Interaction)>,
     mut state_change_events: EventWriter<InteractionStateChanged>,
-    tracker: ResMut<StateChangeTracker>,
+    _tracker: ResMut<StateChangeTracker>,
     // TODO: Add cursor position and window query when integrating with input
 ) {
     for (entity, mut interaction_state, interaction) in state_query.iter_mut() {
@@ -102,7 +104,7 @@ pub fn hover_detection_system(
 pub fn press_detection_system(
     mut state_query: Query<(Entity, &mut InteractionState, &Interaction)>,
     mut state_change_events: EventWriter<InteractionStateChanged>,
-    mut tracker: ResMut<StateChangeTracker>,
+    _tracker: ResMut<StateChangeTracker>,
     // TODO: Add mouse button input when integrating with input
 ) {
     for (entity, mut interaction_state, interaction) in state_query.iter_mut() {
@@ -141,7 +143,7 @@ pub fn press_detection_system(
 pub fn focus_detection_system(
     mut state_query: Query<(Entity, &mut InteractionState)>,
     mut state_change_events: EventWriter<InteractionStateChanged>,
-    mut tracker: ResMut<StateChangeTracker>,
+    _tracker: ResMut<StateChangeTracker>,
     // TODO: Add focus manager resource when implementing focus system
 ) {
     for (entity, mut interaction_state) in state_query.iter_mut() {
@@ -172,13 +174,17 @@ pub fn focus_detection_system(
 }
 
 /// System that handles drag state detection
-/// This would integrate with Bevy's input systems in a full implementation
+/// A widget is dragged from its first `EntityDragged` event until the left mouse button is released
 pub fn drag_detection_system(
     mut state_query: Query<(Entity, &mut InteractionState, &Interaction)>,
     mut state_change_events: EventWriter<InteractionStateChanged>,
-    mut tracker: ResMut<StateChangeTracker>,
-    // TODO: Add mouse input and drag threshold when integrating with input
+    _tracker: ResMut<StateChangeTracker>,
+    mut drag_events: EventReader<EntityDragged>,
+    mouse_buttons: Option<Res<ButtonInput<MouseButton>>>,
 ) {
+    let dragged_entities: Vec<Entity> = drag_events.read().map(|event| event.entity).collect();
+    let button_held = mouse_buttons.is_some_and(|buttons| buttons.pressed(MouseButton::Left));
+
     for (entity, mut interaction_state, interaction) in state_query.iter_mut() {
         if !interaction.draggable {
             continue;
@@ -186,11 +192,7 @@ pub fn drag_detection_system(
         
         let previous_state = interaction_state.clone();
         
-        // TODO: Implement actual drag detection using mouse movement + press state
-        // For now, this is a placeholder that demonstrates the structure
-        
-        // Placeholder: In a real implementation, this would check mouse movement while pressed
-        let is_dragged = false; // This would be calculated from mouse movement + press state
+        let is_dragged = dragged_entities.contains(&entity) || (interaction_state.dragged && button_held);
         
         if interaction_state.set_dragged(is_dragged) {
             #[cfg(feature = "debug_logging")]
@@ -233,6 +235,7 @@ pub fn interaction_state_debug_system(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy_ecs::system::RunSystemOnce;
     use bevy_ecs::world::World;
 
     #[test]
@@ -249,7 +252,7 @@ mod tests {
         )).id();
         
         // Run the tracking system
-        world.run_system_once(interaction_state_tracking_system);
+        world.run_system_once(interaction_state_tracking_system).unwrap();
         
         // Check that the entity still exists and has the expected state
         let state = world.get::<InteractionState>(entity).unwrap();
@@ -260,6 +263,7 @@ mod tests {
     fn test_state_change_event_firing() {
         let mut world = World::new();
         world.init_resource::<Events<InteractionStateChanged>>();
+        world.init_resource::<StateChangeTracker>();
         
         // Create entity with interaction state that will change
         let entity = world.spawn((
@@ -274,7 +278,7 @@ mod tests {
         }
         
         // Run the debug system to verify events can be processed
-        world.run_system_once(interaction_state_debug_system);
+        world.run_system_once(interaction_state_debug_system).unwrap();
         
         // Verify the entity exists and has expected state
         let final_state = world.get::<InteractionState>(entity).unwrap();
@@ -1,10 +1,14 @@
 use bevy_ecs::prelude::*;
 use bevy_log::debug;
 use crate::{
-    gui_framework::components::InteractionState,
+    gui_framework::components::{InteractionState, ShapeData},
     widgets::{
-        blueprint::{StyleConfig, StateStyles, StyleOverrides},
+        blueprint::StyleConfig,
         components::{Widget, WidgetStyle},
+        dropdown::WidgetDropdown,
+        numeric::WidgetNumeric,
+        text_input::WidgetTextInput,
+        toggle::WidgetToggle,
     },
 };
 
@@ -46,21 +50,34 @@ pub struct StyleChanged {
     pub new_style: StyleConfig,
 }
 
+/// Widget components that decide which state style applies
+type StateStyledWidgetQuery = (
+    Entity,
+    &'static Widget,
+    &'static InteractionState,
+    Option<&'static WidgetToggle>,
+    Option<&'static WidgetNumeric>,
+    Option<&'static WidgetTextInput>,
+    Option<&'static WidgetDropdown>,
+    Option<&'static mut ResolvedStyle>,
+);
+
+/// Widgets whose state changed
+type StateStyledWidgetFilter = Or<(Changed<InteractionState>, Added<InteractionState>, Changed<WidgetToggle>, Changed<WidgetNumeric>, Changed<WidgetTextInput>, Changed<WidgetDropdown>)>;
+
 /// System that resolves styles based on interaction states
-/// This implements the cascading resolution: base → class → state → override
+/// Classes, the node's own style and its overrides are folded into the blueprint style at
+/// spawn time (see `UiDefinition::resolve_node_style`); this layers the active state on top
 pub fn style_resolution_system(
     mut commands: Commands,
     mut style_changed_events: EventWriter<StyleChanged>,
     // Query widgets with interaction states
-    mut widget_query: Query<
-        (Entity, &Widget, &InteractionState, Option<&mut ResolvedStyle>),
-        Or<(Changed<InteractionState>, Added<InteractionState>)>
-    >,
+    mut widget_query: Query<StateStyledWidgetQuery, StateStyledWidgetFilter>,
     // Query all widgets that might need initial style resolution
     widget_init_query: Query<(Entity, &Widget), (Without<ResolvedStyle>, Without<InteractionState>)>,
 ) {
     // Handle widgets with interaction state changes
-    for (entity, widget, interaction_state, resolved_style) in widget_query.iter_mut() {
+    for (entity, widget, interaction_state, toggle, numeric, text_input, dropdown, resolved_style) in widget_query.iter_mut() {
         let base_style = &widget.blueprint.style;
         
         // Resolve style based on current interaction state
@@ -68,8 +85,11 @@ pub fn style_resolution_system(
             base_style,
             interaction_state.hovered,
             interaction_state.pressed,
-            interaction_state.focused,
-            false, // TODO: Add disabled state to InteractionState
+            // Dropdowns take keys while focused, without going through the focus system
+            interaction_state.focused || dropdown.is_some_and(|dropdown| dropdown.focused),
+            toggle.is_some_and(|toggle| toggle.disabled) || numeric.is_some_and(|numeric| numeric.disabled)
+                || text_input.is_some_and(|text_input| text_input.disabled) || dropdown.is_some_and(|dropdown| dropdown.disabled),
+            toggle.is_some_and(|toggle| toggle.checked),
         );
 
         if let Some(mut resolved) = resolved_style {
@@ -101,14 +121,20 @@ pub fn style_resolution_system(
     }
 }
 
-/// System that updates widget style components when resolved styles change
+/// System that updates widget style components, and the color shapes are drawn in, when
+/// resolved styles change
 pub fn apply_resolved_styles_system(
-    mut widget_style_query: Query<(&mut WidgetStyle, &mut ResolvedStyle), Changed<ResolvedStyle>>,
+    mut widget_style_query: Query<(&mut WidgetStyle, &mut ResolvedStyle, Option<&mut ShapeData>), Changed<ResolvedStyle>>,
 ) {
-    for (mut widget_style, mut resolved_style) in widget_style_query.iter_mut() {
+    for (mut widget_style, mut resolved_style, shape_data) in widget_style_query.iter_mut() {
         if resolved_style.is_dirty() {
             // Update the widget style component from resolved style
             update_widget_style_from_config(&mut widget_style, &resolved_style.style);
+            if let (Some(mut shape_data), Some(color)) = (shape_data, widget_style.background_color) {
+                if shape_data.color != color {
+                    shape_data.color = color;
+                }
+            }
             resolved_style.clear_dirty();
             debug!("Applied resolved style to widget style component");
         }
@@ -122,12 +148,17 @@ fn resolve_style_for_state(
     pressed: bool,
     focused: bool,
     disabled: bool,
+    checked: bool,
 ) -> StyleConfig {
     // Start with base style
     let mut resolved = base_style.clone();
 
     // Apply state-specific overrides if they exist
     if let Some(ref state_styles) = base_style.states {
+        // Checked is a value rather than an interaction, so interaction states go on top of it
+        if let Some(checked_override) = state_styles.checked.as_ref().filter(|_| checked) {
+            resolved = checked_override.apply_to(&resolved);
+        }
         if let Some(state_override) = state_styles.get_for_state(hovered, pressed, focused, disabled) {
             resolved = state_override.apply_to(&resolved);
         }
@@ -206,7 +237,6 @@ pub fn style_resolution_debug_system(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bevy_ecs::world::World;
     use crate::widgets::blueprint::{ColorDef, StateStyles, StyleOverrides};
 
     #[test]
@@ -217,7 +247,7 @@ mod tests {
             ..Default::default()
         };
 
-        let resolved = resolve_style_for_state(&base_style, false, false, false, false);
+        let resolved = resolve_style_for_state(&base_style, false, false, false, false, false);
         
         assert_eq!(resolved.background_color, base_style.background_color);
         assert_eq!(resolved.opacity, base_style.opacity);
@@ -242,7 +272,7 @@ mod tests {
             ..Default::default()
         });
 
-        let resolved = resolve_style_for_state(&base_style, true, false, false, false);
+        let resolved = resolve_style_for_state(&base_style, true, false, false, false, false);
         
         assert_eq!(resolved.background_color, Some(ColorDef::Named("lightblue".to_string())));
         assert_eq!(resolved.opacity, Some(0.8));
@@ -272,7 +302,7 @@ mod tests {
         });
 
         // When both hovered and pressed, pressed should take priority
-        let resolved = resolve_style_for_state(&base_style, true, true, false, false);
+        let resolved = resolve_style_for_state(&base_style, true, true, false, false, false);
         
         assert_eq!(resolved.background_color, Some(ColorDef::Named("darkblue".to_string())));
     }
@@ -313,29 +343,4 @@ mod tests {
         resolved.mark_dirty();
         assert!(resolved.is_dirty());
     }
-}
-
-impl Default for StyleOverrides {
-    fn default() -> Self {
-        Self {
-            background_color: None,
-            border_color: None,
-            border_width: None,
-            border_radius: None,
-            text_color: None,
-            text_size: None,
-            opacity: None,
-        }
-    }
-}
-
-impl Default for StateStyles {
-    fn default() -> Self {
-        Self {
-            hover: None,
-            pressed: None,
-            focused: None,
-            disabled: None,
-        }
-    }
 }
\ No newline at end of file
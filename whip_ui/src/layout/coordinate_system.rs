@@ -210,7 +210,7 @@ impl VulkanCoords {
     }
 }
 
-/// Helper functions for creating UI transforms with the correct coordinate types
+// Helper functions for creating UI transforms with the correct coordinate types
 
 /// Create a Bevy Transform from UI coordinates
 /// 
@@ -355,7 +355,7 @@ mod tests {
     
     #[test]
     fn test_vulkan_coordinate_compatibility() {
-        let window_height = 600.0;
+        let _window_height = 600.0;
         let bevy_coords = BevyCoords::new(100.0, 200.0, 1.0);
         let vulkan_coords = bevy_coords.to_vulkan();
         
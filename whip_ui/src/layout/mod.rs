@@ -3,8 +3,10 @@ use bevy_hierarchy::{Children, Parent};
 use bevy_transform::prelude::Transform;
 use bevy_math::Vec3;
 use bevy_window;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use taffy::{TaffyTree, Style, NodeId};
+use crate::assets::WidgetWindows;
 
 pub mod plugin;
 pub mod position_control;
@@ -33,15 +35,9 @@ impl Default for UiNode {
 }
 
 /// Component that wraps Taffy's Style for layout properties
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Default)]
 pub struct Styleable(pub Style);
 
-impl Default for Styleable {
-    fn default() -> Self {
-        Self(Style::default())
-    }
-}
-
 /// Component that stores the Taffy node reference for an entity
 #[derive(Component, Debug)]
 pub struct TaffyNode(pub NodeId);
@@ -104,6 +100,8 @@ pub struct WindowRootNode {
     pub node_id: Option<NodeId>,
     pub current_width: f32,
     pub current_height: f32,
+    /// Root containers of the other windows widgets are shown in, by window entity
+    pub window_nodes: HashMap<Entity, NodeId>,
 }
 
 impl Default for WindowRootNode {
@@ -112,6 +110,7 @@ impl Default for WindowRootNode {
             node_id: None,
             current_width: 0.0,
             current_height: 0.0,
+            window_nodes: HashMap::new(),
         }
     }
 }
@@ -128,12 +127,46 @@ impl WindowRootNode {
     }
 }
 
+/// Style of a window root container: a 10x10 grid filling the window
+pub fn window_root_style(width: f32, height: f32) -> Style {
+    Style {
+        position: taffy::Position::Relative,
+        display: taffy::Display::Grid,            // Use Grid container for proper alignment
+        size: taffy::Size {
+            width: taffy::Dimension::Length(width),
+            height: taffy::Dimension::Length(height),
+        },
+        // Create a 10x10 grid to allow flexible positioning
+        grid_template_columns: vec![
+            taffy::TrackSizingFunction::Single(taffy::MinMax {
+                min: taffy::MinTrackSizingFunction::Auto,
+                max: taffy::MaxTrackSizingFunction::Fraction(1.0),
+            }); 10
+        ],
+        grid_template_rows: vec![
+            taffy::TrackSizingFunction::Single(taffy::MinMax {
+                min: taffy::MinTrackSizingFunction::Auto,
+                max: taffy::MaxTrackSizingFunction::Fraction(1.0),
+            }); 10
+        ],
+        // Align items to start (top-left) by default
+        align_items: Some(taffy::AlignItems::Start),
+        justify_items: Some(taffy::JustifyItems::Start),
+        // No margins or padding on the container itself
+        margin: taffy::Rect::zero(),
+        padding: taffy::Rect::zero(),
+        ..Default::default()
+    }
+}
+
 /// System that builds the Taffy layout tree from the Bevy ECS hierarchy
 pub fn build_taffy_tree_system(
     taffy_resource: ResMut<TaffyResource>,
     mut window_root: ResMut<WindowRootNode>,
     mut ui_node_query: Query<(Entity, &mut UiNode, &Styleable), Added<UiNode>>,
     window_query: Query<&bevy_window::Window, bevy_ecs::query::With<bevy_window::PrimaryWindow>>,
+    all_windows_query: Query<&bevy_window::Window>,
+    widget_windows: WidgetWindows,
     _children_query: Query<&Children>,
     _parent_query: Query<&Parent>,
     // debug_buffer parameter removed - using tracing instead
@@ -142,34 +175,7 @@ pub fn build_taffy_tree_system(
         // Create window root container if it doesn't exist
         if window_root.node_id.is_none() {
             if let Ok(window) = window_query.get_single() {
-                let window_style = taffy::Style {
-                    position: taffy::Position::Relative,
-                    display: taffy::Display::Grid,            // Use Grid container for proper alignment
-                    size: taffy::Size {
-                        width: taffy::Dimension::Length(window.width()),
-                        height: taffy::Dimension::Length(window.height()),
-                    },
-                    // Create a 10x10 grid to allow flexible positioning
-                    grid_template_columns: vec![
-                        taffy::TrackSizingFunction::Single(taffy::MinMax {
-                            min: taffy::MinTrackSizingFunction::Auto,
-                            max: taffy::MaxTrackSizingFunction::Fraction(1.0),
-                        }); 10
-                    ],
-                    grid_template_rows: vec![
-                        taffy::TrackSizingFunction::Single(taffy::MinMax {
-                            min: taffy::MinTrackSizingFunction::Auto,
-                            max: taffy::MaxTrackSizingFunction::Fraction(1.0),
-                        }); 10
-                    ],
-                    // Align items to start (top-left) by default
-                    align_items: Some(taffy::AlignItems::Start),
-                    justify_items: Some(taffy::JustifyItems::Start),
-                    // No margins or padding on the container itself
-                    margin: taffy::Rect::zero(),
-                    padding: taffy::Rect::zero(),
-                    ..Default::default()
-                };
+                let window_style = window_root_style(window.width(), window.height());
                 
                 // Use new_with_children instead of new_leaf to create a proper container
                 let root_node = tree.new_with_children(window_style, &[]).unwrap();
@@ -180,9 +186,24 @@ pub fn build_taffy_tree_system(
             }
         }
         
-        // Process newly added UI nodes - REVERT TO ORIGINAL: Add all to window root for now
+        // Process newly added UI nodes - REVERT TO ORIGINAL: Add all to their window's root for now
         for (entity, mut ui_node, styleable) in ui_node_query.iter_mut() {
-            if let Some(root_node) = window_root.node_id {
+            // Widgets shown in other windows go under that window's own root container
+            let window_root_node = match widget_windows.target_of(entity) {
+                Some(window) if Some(window) != widget_windows.primary() => {
+                    match window_root.window_nodes.get(&window) {
+                        Some(node) => Some(*node),
+                        None => all_windows_query.get(window).ok().map(|window_component| {
+                            let node = tree.new_with_children(window_root_style(window_component.width(), window_component.height()), &[]).unwrap();
+                            window_root.window_nodes.insert(window, node);
+                            bevy_log::debug!("Created root grid container for window {:?}", window);
+                            node
+                        }),
+                    }
+                }
+                _ => window_root.node_id,
+            };
+            if let Some(root_node) = window_root_node {
                 let _is_red_rect = entity.index() == 8; // Based on logs showing 8v1#4294967304
                 
                 if _is_red_rect {
@@ -235,6 +256,26 @@ pub fn build_taffy_tree_system(
     });
 }
 
+/// System that pushes `Styleable` changes made after creation (e.g. by hot reload) into the Taffy tree
+pub fn sync_taffy_styles_system(
+    taffy_resource: Res<TaffyResource>,
+    changed_query: Query<(Entity, &UiNode, &Styleable), Changed<Styleable>>,
+) {
+    taffy_resource.with_tree(|tree| {
+        for (entity, ui_node, styleable) in changed_query.iter() {
+            // Newly added nodes have no Taffy node yet; build_taffy_tree_system creates them
+            let Some(taffy_node) = ui_node.taffy_node else {
+                continue;
+            };
+            if let Err(e) = tree.set_style(taffy_node, styleable.0.clone()) {
+                bevy_log::error!("Failed to update Taffy style for entity {:?}: {:?}", entity, e);
+            } else {
+                bevy_log::debug!("Synced Taffy style for entity {:?}", entity);
+            }
+        }
+    });
+}
+
 /// System that computes layout using Taffy and applies results to Transform components
 pub fn compute_and_apply_layout_system(
     taffy_resource: Res<TaffyResource>,
@@ -242,42 +283,40 @@ pub fn compute_and_apply_layout_system(
     mut ui_node_query: Query<(Entity, &mut UiNode, &mut Transform, Option<&PositionControl>, Option<&mut LayoutPositioned>), With<Styleable>>,
     mut commands: Commands,
     _children_query: Query<&Children>,
-    window_query: Query<&bevy_window::Window, bevy_ecs::query::With<bevy_window::PrimaryWindow>>,
+    widget_windows: WidgetWindows,
     // debug_buffer parameter removed - using tracing instead
 ) {
     taffy_resource.with_tree(|tree| {
-        // Get window dimensions for coordinate conversion
-        let window_height = if let Ok(window) = window_query.get_single() {
-            window.height()
-        } else {
-            300.0 // fallback
+        // Compute layout for the root container of every window
+        let available_space = taffy::Size {
+            width: taffy::AvailableSpace::MaxContent,
+            height: taffy::AvailableSpace::MaxContent,
         };
+        let mut computed = false;
+        for root_node in window_root.node_id.iter().chain(window_root.window_nodes.values()) {
+            if tree.compute_layout(*root_node, available_space).is_ok() {
+                bevy_log::debug!("Computed layout for window root container {:?}", root_node);
+                computed = true;
+            }
+        }
 
-        // Compute layout for the window root container
-        if let Some(root_node) = window_root.node_id {
-            let available_space = taffy::Size {
-                width: taffy::AvailableSpace::MaxContent,
-                height: taffy::AvailableSpace::MaxContent,
-            };
-            
-            if let Ok(_) = tree.compute_layout(root_node, available_space) {
-                bevy_log::debug!("Computed layout for window root container");
-                
-                // Phase 1: Collect entities that need layout updates (immutable borrow)
-                let mut entities_to_update = Vec::new();
-                for (entity, ui_node, _, position_control, _) in ui_node_query.iter() {
-                    if let Some(taffy_node) = ui_node.taffy_node {
-                        let control = position_control.unwrap_or(&PositionControl::Layout);
-                        if control.uses_layout() {
-                            entities_to_update.push((entity, taffy_node));
-                        }
+        if computed {
+            // Phase 1: Collect entities that need layout updates (immutable borrow)
+            let mut entities_to_update = Vec::new();
+            for (entity, ui_node, _, position_control, _) in ui_node_query.iter() {
+                if let Some(taffy_node) = ui_node.taffy_node {
+                    let control = position_control.unwrap_or(&PositionControl::Layout);
+                    if control.uses_layout() {
+                        entities_to_update.push((entity, taffy_node));
                     }
                 }
-                
-                // Phase 2: Apply layout updates (mutable borrow)
-                for (entity, taffy_node) in entities_to_update {
-                    apply_layout_to_entity(tree, taffy_node, entity, &mut ui_node_query, &mut commands, window_height);
-                }
+            }
+            
+            // Phase 2: Apply layout updates (mutable borrow), converting coordinates with
+            // the height of the window each entity is drawn in
+            for (entity, taffy_node) in entities_to_update {
+                let window_height = widget_windows.height_of(entity);
+                apply_layout_to_entity(tree, taffy_node, entity, &mut ui_node_query, &mut commands, window_height);
             }
         }
     });
@@ -288,9 +327,23 @@ pub fn window_root_resize_system(
     mut resize_reader: EventReader<bevy_window::WindowResized>,
     taffy_resource: ResMut<TaffyResource>,
     mut window_root: ResMut<WindowRootNode>,
+    primary_window_query: Query<(), bevy_ecs::query::With<bevy_window::PrimaryWindow>>,
 ) {
     for event in resize_reader.read() {
         if event.width > 0.0 && event.height > 0.0 {
+            // Other windows only need their own root container resized
+            if let Some(root_node) = window_root.window_nodes.get(&event.window).copied() {
+                taffy_resource.with_tree(|tree| {
+                    if let Err(e) = tree.set_style(root_node, window_root_style(event.width, event.height)) {
+                        bevy_log::error!("Failed to update root style of window {:?}: {:?}", event.window, e);
+                    }
+                });
+                continue;
+            }
+            if !primary_window_query.contains(event.window) {
+                continue;
+            }
+
             // Check if window size actually changed
             if window_root.needs_resize(event.width, event.height) {
                 bevy_log::debug!("Window resized: {}x{} -> {}x{}", 
@@ -300,35 +353,7 @@ pub fn window_root_resize_system(
                 // Update the Taffy root node with new window dimensions
                 if let Some(root_node) = window_root.node_id {
                     taffy_resource.with_tree(|tree| {
-                        let new_style = taffy::Style {
-                            position: taffy::Position::Relative,
-                            display: taffy::Display::Grid,             // Maintain grid layout
-                            size: taffy::Size {
-                                width: taffy::Dimension::Length(event.width),
-                                height: taffy::Dimension::Length(event.height),
-                            },
-                            // Maintain 10x10 grid configuration  
-                            grid_template_columns: vec![
-                                taffy::TrackSizingFunction::Single(taffy::MinMax {
-                                    min: taffy::MinTrackSizingFunction::Auto,
-                                    max: taffy::MaxTrackSizingFunction::Fraction(1.0),
-                                }); 10
-                            ],
-                            grid_template_rows: vec![
-                                taffy::TrackSizingFunction::Single(taffy::MinMax {
-                                    min: taffy::MinTrackSizingFunction::Auto,
-                                    max: taffy::MaxTrackSizingFunction::Fraction(1.0),
-                                }); 10
-                            ],
-                            align_items: Some(taffy::AlignItems::Start),
-                            justify_items: Some(taffy::JustifyItems::Start),
-                            // No margins or padding
-                            margin: taffy::Rect::zero(),
-                            padding: taffy::Rect::zero(),
-                            ..Default::default()
-                        };
-                        
-                        if let Err(e) = tree.set_style(root_node, new_style) {
+                        if let Err(e) = tree.set_style(root_node, window_root_style(event.width, event.height)) {
                             bevy_log::error!("Failed to update window root style: {:?}", e);
                         } else {
                             bevy_log::debug!("Updated window root container to: {}x{}", event.width, event.height);
@@ -343,6 +368,23 @@ pub fn window_root_resize_system(
     }
 }
 
+/// System that drops the root containers of closed windows
+pub fn window_root_cleanup_system(
+    mut closed_events: EventReader<bevy_window::WindowClosed>,
+    taffy_resource: ResMut<TaffyResource>,
+    mut window_root: ResMut<WindowRootNode>,
+) {
+    for event in closed_events.read() {
+        if let Some(root_node) = window_root.window_nodes.remove(&event.window) {
+            taffy_resource.with_tree(|tree| {
+                if let Err(e) = tree.remove(root_node) {
+                    bevy_log::warn!("Failed to remove root container of window {:?}: {:?}", event.window, e);
+                }
+            });
+        }
+    }
+}
+
 /// Helper function to recursively apply layout to an entity and its children
 fn apply_layout_to_entity(
     tree: &TaffyTree<Entity>,
@@ -539,4 +581,3 @@ pub fn update_shape_vertices_system(
         }
     }
 }
-
@@ -4,9 +4,11 @@ use crate::layout::{
     TaffyResource, 
     WindowRootNode,
     build_taffy_tree_system,
+    sync_taffy_styles_system,
     compute_and_apply_layout_system,
     update_shape_vertices_system,
     window_root_resize_system,
+    window_root_cleanup_system,
 };
 
 /// Plugin that provides Taffy layout integration for UI elements
@@ -24,9 +26,12 @@ impl Plugin for TaffyLayoutPlugin {
             (
                 // First: Handle window resize events and update root container
                 window_root_resize_system,
+                window_root_cleanup_system,
                 // Second: Build the Taffy tree from ECS hierarchy
                 build_taffy_tree_system,
-                // Third: Compute layout and apply to transforms
+                // Third: Push style changes on existing nodes into the tree
+                sync_taffy_styles_system,
+                // Fourth: Compute layout and apply to transforms
                 compute_and_apply_layout_system,
             ).chain()
         );
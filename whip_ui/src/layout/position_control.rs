@@ -3,9 +3,10 @@ use bevy_reflect::Reflect;
 use serde::{Deserialize, Serialize};
 
 /// Component that defines which system controls an entity's position
-#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize, Default)]
 pub enum PositionControl {
     /// Taffy layout system controls position (default for TOML widgets)
+    #[default]
     Layout,
     /// Manual/interaction system controls position (for draggable items)
     Manual,
@@ -13,12 +14,6 @@ pub enum PositionControl {
     LayoutThenManual,
 }
 
-impl Default for PositionControl {
-    fn default() -> Self {
-        PositionControl::Layout
-    }
-}
-
 impl PositionControl {
     /// Check if this entity should be positioned by the layout system
     pub fn uses_layout(&self) -> bool {
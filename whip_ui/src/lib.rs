@@ -14,6 +14,9 @@ pub mod logging;
 pub mod cli;
 pub mod whip_ui_plugin;
 
+#[cfg(test)]
+mod test_support;
+
 // Re-export commonly used types and components
 pub use gui_framework::{
     VulkanContext,
@@ -31,6 +34,14 @@ pub use widgets::{
     blueprint::*,
     components::*,
     systems::*,
+    toggle::{WidgetToggle, ToggleKind},
+    numeric::{WidgetNumeric, NumericKind, NumericRange, ValueFormat},
+    text_input::WidgetTextInput,
+    scroll_view::{WidgetScrollView, ScrollIntoView},
+    virtual_list::{WidgetVirtualList, VirtualListProvider, VirtualListProviders, VirtualListAppExt, LogStoreProvider},
+    popup::WidgetPopup,
+    dropdown::WidgetDropdown,
+    tabs::WidgetTabs,
 };
 
 // Re-export layout system
@@ -51,8 +62,32 @@ pub use layout::{
 pub use assets::{
     UiDefinition,
     WindowConfig,
+    WindowModeDef,
+    PresentModeDef,
     UiDefinitionLoader,
+    UiDefinitionFormat,
+    ValidationReport,
+    Diagnostic,
+    Severity,
+    ui_definition_schema,
+    LiveUiTree,
+    serialize_ui_tree,
+    UiState,
+    StateOperation,
+    Localization,
+    LoadStringTableRequest,
+    UiRegistry,
+    RegistryManifest,
+    RegisterUiExtension,
+    UiExtensionAppExt,
     LoadUiRequest,
+    UnloadUiRequest,
+    ReplaceUiRequest,
+    UiTarget,
+    WindowDeclaration,
+    UiWindow,
+    UiWindows,
+    TargetWindow,
     LoadingUiAssets,
     UiAssetPlugin,
     ui_asset_request_system,
@@ -113,19 +148,12 @@ pub struct PreparedTextDrawData {
     pub vertex_count: u32,             // Number of vertices for this entity
     pub projection_descriptor_set: vk::DescriptorSet, // Set 0: Global Projection UBO + Entity Transform UBO
     pub atlas_descriptor_set: vk::DescriptorSet,    // Set 1: Glyph Atlas Sampler
+    pub scissor: Option<vk::Rect2D>,       // Clip region in framebuffer pixels, None draws unclipped
+    pub overlay: bool,                     // Drawn with the overlay layer, after everything else
 }
 
 // --- Resources needed across framework/app ---
 
-// Resource holding the global projection uniform buffer, its allocation, and descriptor set.
-// Managed by BufferManager, used by shapes and text rendering prep.
-#[derive(bevy_ecs::prelude::Resource)]
-pub struct GlobalProjectionUboResource {
-    pub buffer: vk::Buffer,
-    pub allocation: vk_mem::Allocation,
-    pub descriptor_set: vk::DescriptorSet,
-}
-
 // Resource holding Vulkan resources specifically for text rendering.
 // Managed by a dedicated system in core plugin.
 #[derive(bevy_ecs::prelude::Resource)]
@@ -141,6 +169,11 @@ pub struct TextRenderingResources {
 #[derive(bevy_ecs::prelude::Resource, Default, Debug)]
 pub struct PreparedTextDrawsResource(pub Vec<PreparedTextDrawData>);
 
+// Resource listing entities that lost their `Text` since the last frame; the renderer frees their
+// vertex buffers, transform UBOs and descriptor sets once the GPU is done with them
+#[derive(bevy_ecs::prelude::Resource, Default, Debug)]
+pub struct RemovedTextResource(pub Vec<bevy_ecs::entity::Entity>);
+
 
 // --- Resources needed across framework/app ---
 // Resource holding the Arc<Mutex<VulkanContext>>
@@ -173,6 +206,8 @@ pub struct PreparedDrawData {
     pub vertex_count: u32,
     pub descriptor_set: vk::DescriptorSet, // Per-entity set (bindings 0=global proj, 1=entity transform)
     pub color: [f32; 4], // Added color for push constants
+    pub scissor: Option<vk::Rect2D>, // Clip region in framebuffer pixels, None draws unclipped
+    pub overlay: bool, // Drawn with the overlay layer, after everything else
 }
 
 /// Holds the data needed to prepare Vulkan resources for a shape entity.
@@ -186,4 +221,6 @@ pub struct RenderCommandData {
     pub color: Color, // Added Bevy Color
     pub depth: f32, // For sorting
     pub vertices_changed: bool, // For background quad resizing
+    pub scissor: Option<vk::Rect2D>, // Clip region from the entity's ClipRect
+    pub overlay: bool, // Whether the entity is in the OverlayLayer
 }
\ No newline at end of file
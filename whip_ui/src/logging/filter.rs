@@ -50,11 +50,6 @@ impl LogFilter {
         Self { config, exact_level: None }
     }
     
-    /// Create a filter with default configuration
-    pub fn default() -> Self {
-        Self::new(FilterConfig::default())
-    }
-    
     /// Check if a log passes the filter
     pub fn should_include(&self, log: &LogData) -> bool {
         // Check exact level first if specified
@@ -192,7 +187,7 @@ mod tests {
     
     #[test]
     fn test_level_filtering() {
-        let mut filter = LogFilter::for_level(LogLevel::Warn);
+        let filter = LogFilter::for_level(LogLevel::Warn);
         
         let meta = LogMetadata::new("test".to_string());
         let debug_log = LogData::new(1, LogLevel::Debug, "Debug".to_string(), meta.clone());
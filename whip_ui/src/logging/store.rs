@@ -4,6 +4,7 @@ use crate::logging::types::{LogData, LogLevel, LogMetadata};
 use crate::logging::filter::{LogFilter, FilterConfig};
 use crossbeam_channel::{bounded, Sender};
 use std::collections::VecDeque;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread;
 use std::time::{Duration, SystemTime};
@@ -45,6 +46,8 @@ enum LogStoreMessage {
 /// Thread-safe central log storage
 pub struct CentralLogStore {
     sender: Sender<LogStoreMessage>,
+    /// Bumped by the worker whenever the stored logs change
+    revision: Arc<AtomicU64>,
     _worker_handle: thread::JoinHandle<()>,
 }
 
@@ -209,6 +212,8 @@ impl CentralLogStore {
     /// Create a new central log store with the given capacity
     pub fn new(capacity: usize) -> Self {
         let (sender, receiver) = bounded::<LogStoreMessage>(1000);
+        let revision = Arc::new(AtomicU64::new(0));
+        let worker_revision = revision.clone();
         
         let worker_handle = thread::spawn(move || {
             let mut worker = LogStoreWorker::new(capacity);
@@ -217,6 +222,7 @@ impl CentralLogStore {
                 match receiver.recv() {
                     Ok(LogStoreMessage::AddLog(log)) => {
                         worker.add_log(log);
+                        worker_revision.fetch_add(1, Ordering::Release);
                     }
                     Ok(LogStoreMessage::GetLogs { filter, start, count, response }) => {
                         let logs = worker.get_logs(filter, start, count);
@@ -234,6 +240,7 @@ impl CentralLogStore {
                     }
                     Ok(LogStoreMessage::Clear) => {
                         worker.clear();
+                        worker_revision.fetch_add(1, Ordering::Release);
                     }
                     Ok(LogStoreMessage::Shutdown) => {
                         break;
@@ -248,6 +255,7 @@ impl CentralLogStore {
         
         Self {
             sender,
+            revision,
             _worker_handle: worker_handle,
         }
     }
@@ -297,6 +305,12 @@ impl CentralLogStore {
         self.get_logs(LogFilter::for_exact_level(level), 0, usize::MAX)
     }
     
+    /// Number that changes whenever logs are added, repeated or cleared. Unlike the other
+    /// queries it does not wait for the worker, so it is cheap enough to check every frame.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+    
     /// Get statistics about the log store
     pub fn get_stats(&self) -> LogStoreStats {
         let (response_tx, response_rx) = bounded(1);
@@ -409,4 +423,21 @@ mod tests {
         let stats = store.get_stats();
         assert_eq!(stats.duplicates_detected, 1);
     }
+    
+    #[test]
+    fn test_revision_follows_changes() {
+        let store = CentralLogStore::new(100);
+        assert_eq!(store.revision(), 0);
+        
+        let metadata = LogMetadata::new("test".to_string());
+        for _ in 0..2 {
+            store.add_log(LogData::new(1, LogLevel::Info, "Repeated message".to_string(), metadata.clone()));
+        }
+        thread::sleep(Duration::from_millis(50)); // Let worker process
+        assert_eq!(store.revision(), 2, "Repeats count as changes too");
+        
+        store.clear();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(store.revision(), 3);
+    }
 }
\ No newline at end of file
@@ -5,7 +5,7 @@ use tracing::{field::Visit, Event, Subscriber};
 use tracing_subscriber::layer::{Context, Layer};
 
 /// Custom tracing layer that captures events and forwards them to CentralLogStore
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct WhipUiTracingLayer;
 
 impl WhipUiTracingLayer {
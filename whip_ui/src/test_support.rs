@@ -0,0 +1,71 @@
+//! Fixtures shared by the unit tests across the crate
+
+use crate::assets::{spawn_widget_from_node, LoadUiRequest, LoadingUiAssets, ReplaceUiRequest, UiDefinition, UiRegistry, UiState, UnloadUiRequest};
+use crate::gui_framework::events::{ActionEvent, EntityClicked, EntityDragged, EntityScrolled, PointerPressed, TextFocusChanged, TextSubmitted, YrsTextChanged};
+use crate::gui_framework::systems::StyleChanged;
+use crate::layout::TaffyResource;
+use crate::widgets::blueprint::WidgetType;
+use crate::widgets::scroll_view::ScrollIntoView;
+use crate::YrsDocResource;
+use bevy_asset::{AssetEvent, Assets};
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A world with the resources and events the widget and asset systems under test use, and a
+/// handle on its yrs document for reading and editing widget text
+pub(crate) fn setup_world() -> (World, YrsDocResource) {
+    let mut world = World::new();
+    let yrs_res = YrsDocResource { doc: Arc::new(yrs::Doc::new()), text_map: Arc::new(Mutex::new(HashMap::new())) };
+    world.insert_resource(YrsDocResource { doc: yrs_res.doc.clone(), text_map: yrs_res.text_map.clone() });
+    world.init_resource::<TaffyResource>();
+    world.insert_resource(UiRegistry::new());
+    world.insert_resource(UiState::new());
+    world.init_resource::<LoadingUiAssets>();
+    world.init_resource::<Assets<UiDefinition>>();
+    world.init_resource::<Events<AssetEvent<UiDefinition>>>();
+    world.init_resource::<Events<LoadUiRequest>>();
+    world.init_resource::<Events<UnloadUiRequest>>();
+    world.init_resource::<Events<ReplaceUiRequest>>();
+    world.init_resource::<Events<PointerPressed>>();
+    world.init_resource::<Events<EntityClicked>>();
+    world.init_resource::<Events<EntityDragged>>();
+    world.init_resource::<Events<EntityScrolled>>();
+    world.init_resource::<Events<ScrollIntoView>>();
+    world.init_resource::<Events<TextFocusChanged>>();
+    world.init_resource::<Events<TextSubmitted>>();
+    world.init_resource::<Events<YrsTextChanged>>();
+    world.init_resource::<Events<ActionEvent>>();
+    world.init_resource::<Events<StyleChanged>>();
+    (world, yrs_res)
+}
+
+/// Spawn the child of the definition's root at `index` on its own
+pub(crate) fn spawn_child(world: &mut World, yrs_res: &YrsDocResource, ui_def: &UiDefinition, index: usize) -> Entity {
+    let root = {
+        let mut commands = world.commands();
+        spawn_widget_from_node(&mut commands, &ui_def.root.children[index], ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+    };
+    world.flush();
+    root
+}
+
+/// The actions sent since the last call
+pub(crate) fn drain_actions(world: &mut World) -> Vec<ActionEvent> {
+    world.resource_mut::<Events<ActionEvent>>().drain().collect()
+}
+
+/// A Button widget type showing `text`, with the template's defaults for everything else
+pub(crate) fn button(text: &str) -> WidgetType {
+    WidgetType::Button {
+        text: Some(text.to_string()),
+        background_color: None,
+        text_color: None,
+        size: None,
+        text_size: None,
+        border_width: None,
+        border_color: None,
+        border_radius: None,
+    }
+}
@@ -25,7 +25,7 @@ use crate::{
         bindings::GuiFrameworkDefaultBindingsPlugin,
     },
     layout::TaffyLayoutPlugin,
-    assets::{UiAssetPlugin, LoadUiRequest},
+    assets::{UiAssetPlugin, LoadUiRequest, RegistryManifest, UiExtensionAppExt},
     ShapeData,
     Vertex,
     Visibility,
@@ -37,38 +37,61 @@ struct BackgroundQuad;
 
 pub struct WhipUiPlugin {
     root_layout_path: String,
+    registry_manifest_path: Option<String>,
 }
 
 impl WhipUiPlugin {
     pub fn new(root_layout_path: &str) -> Self {
         Self {
             root_layout_path: root_layout_path.to_string(),
+            registry_manifest_path: None,
         }
     }
+
+    /// Add the widget types, actions and state types declared in a registry manifest file
+    /// (relative to the assets folder) to the UI registry at startup
+    pub fn with_registry_manifest(mut self, manifest_path: &str) -> Self {
+        self.registry_manifest_path = Some(manifest_path.to_string());
+        self
+    }
 }
 
 impl Plugin for WhipUiPlugin {
     fn build(&self, app: &mut App) {
         info!("Initializing WhipUiPlugin with layout: {}", self.root_layout_path);
 
-        // Add all essential Bevy plugins that the framework requires
-        app.add_plugins((
-            TaskPoolPlugin::default(),
-            bevy_time::TimePlugin::default(),
-            TransformPlugin::default(),
-            InputPlugin::default(),
-            WindowPlugin {
-                primary_window: Some(Window {
+        // Settings winit only takes when it creates a window (transparency) come from the root
+        // layout before the app runs; the others are applied again once the layout asset loads
+        let root_layout_full_path = bevy_asset::io::file::FileAssetReader::get_base_path()
+            .join("assets")
+            .join(&self.root_layout_path);
+        let primary_window = match crate::assets::read_window_config(&root_layout_full_path) {
+            Ok(Some(window_config)) => window_config.to_window("WhipUI Application"),
+            Ok(None) => crate::assets::WindowConfig::default().to_window("WhipUI Application"),
+            Err(e) => {
+                warn!("Failed to read the window settings of {}: {}", self.root_layout_path, e);
+                Window {
                     title: "WhipUI Application".into(),
                     resolution: WindowResolution::new(600.0, 300.0), // Default, will be updated from TOML
                     present_mode: PresentMode::AutoVsync,
                     ..default()
-                }),
+                }
+            }
+        };
+
+        // Add all essential Bevy plugins that the framework requires
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy_time::TimePlugin,
+            TransformPlugin,
+            InputPlugin,
+            WindowPlugin {
+                primary_window: Some(primary_window),
                 ..default()
             },
             AccessibilityPlugin,
             WinitPlugin::<WakeUp>::default(),
-            HierarchyPlugin::default(),
+            HierarchyPlugin,
             AssetPlugin::default(),
         ));
 
@@ -88,6 +111,20 @@ impl Plugin for WhipUiPlugin {
            .add_plugins(UiAssetPlugin)
            .add_plugins(TaffyLayoutPlugin);
 
+        // Registry entries from the manifest must be in place before the first layout is validated
+        if let Some(ref manifest_path) = self.registry_manifest_path {
+            let full_path = bevy_asset::io::file::FileAssetReader::get_base_path()
+                .join("assets")
+                .join(manifest_path);
+            match RegistryManifest::load(&full_path) {
+                Ok(manifest) => {
+                    info!("Loaded UI registry manifest: {}", manifest_path);
+                    app.register_ui_extension(manifest);
+                }
+                Err(e) => error!("Failed to load UI registry manifest {}: {}", manifest_path, e),
+            }
+        }
+
         // Store the layout path for startup system
         app.insert_resource(RootLayoutPath(self.root_layout_path.clone()));
 
@@ -163,12 +200,17 @@ fn load_root_layout(
 fn apply_window_config_from_asset(
     mut window_config_events: EventReader<bevy_asset::AssetEvent<crate::assets::UiDefinition>>,
     ui_definitions: Res<bevy_asset::Assets<crate::assets::UiDefinition>>,
-    mut primary_window_q: Query<&mut Window, With<PrimaryWindow>>,
+    mut primary_window_q: Query<(Entity, &mut Window), With<PrimaryWindow>>,
     mut background_query: Query<&mut ShapeData, With<BackgroundQuad>>,
+    loading_assets: Res<crate::assets::LoadingUiAssets>,
     mut commands: Commands,
 ) {
     for event in window_config_events.read() {
         if let bevy_asset::AssetEvent::LoadedWithDependencies { id } = event {
+            // Layouts of declared windows are configured by their declaration instead
+            if loading_assets.is_window_only(*id) {
+                continue;
+            }
             if let Some(ui_definition) = ui_definitions.get(*id) {
                 let default_window_config = crate::assets::WindowConfig::default();
                 let window_config = ui_definition.window.as_ref().unwrap_or(&default_window_config);
@@ -177,10 +219,14 @@ fn apply_window_config_from_asset(
                 // Insert window config as resource
                 commands.insert_resource(window_config.clone());
                 
-                // Apply window size
-                if let Ok(mut window) = primary_window_q.get_single_mut() {
+                // Apply window size and the other settings the config makes
+                if let Ok((window_entity, mut window)) = primary_window_q.get_single_mut() {
                     window.resolution.set(window_config.size[0], window_config.size[1]);
                     info!("Updated window size to: {}x{}", window_config.size[0], window_config.size[1]);
+                    window_config.apply_to_window(&mut window);
+                    if let Some(ref icon) = window_config.icon {
+                        commands.entity(window_entity).insert(crate::assets::PendingWindowIcon(icon.clone()));
+                    }
                 }
                 
                 // Update background color if specified
@@ -198,9 +244,11 @@ fn apply_window_config_from_asset(
 fn background_resize_system(
     mut resize_reader: EventReader<WindowResized>,
     mut background_query: Query<&mut ShapeData, With<BackgroundQuad>>,
+    primary_window_q: Query<(), With<PrimaryWindow>>,
 ) {
     for event in resize_reader.read() {
-        if event.width > 0.0 && event.height > 0.0 {
+        // The background quad covers the primary window only
+        if event.width > 0.0 && event.height > 0.0 && primary_window_q.contains(event.window) {
             let logical_width = event.width;
             let logical_height = event.height;
 
@@ -4,6 +4,7 @@ use bevy_color::Color;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::layout::PositionControl;
+use crate::widgets::color::{ColorParseError, parse_css_color, parse_hex_color, parse_named_color};
 
 /// Represents a widget definition loaded from JSON
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +26,42 @@ pub enum WidgetType {
     Container {
         direction: FlexDirection,
     },
+    /// Container showing its children through a viewport of its own size. The content scrolls
+    /// with the mouse wheel, the trackpad and by dragging the scrollbars.
+    ScrollView {
+        direction: FlexDirection,
+        /// Directions the content scrolls in
+        #[serde(default)]
+        axes: ScrollAxes,
+        /// Thickness of the scrollbars; 0 hides them
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scrollbar_width: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scrollbar_color: Option<ColorDef>,
+    },
+    /// Scrolling column that only spawns the rows in view, for lists too long to spawn whole.
+    /// Its children are the row template, filled in like a `repeat` with each row's item.
+    VirtualList {
+        /// Path of the `List` state value the rows show
+        #[serde(skip_serializing_if = "Option::is_none")]
+        items: Option<String>,
+        /// Name of a registered `VirtualListProvider` the rows come from instead
+        #[serde(skip_serializing_if = "Option::is_none")]
+        provider: Option<String>,
+        /// Name of the item in `{{placeholders}}`, `item` by default
+        #[serde(rename = "as", skip_serializing_if = "Option::is_none")]
+        item_name: Option<String>,
+        /// Height of every row; rows are measured once drawn when left out
+        #[serde(skip_serializing_if = "Option::is_none")]
+        row_height: Option<f32>,
+        /// Rows kept spawned beyond each edge of the viewport
+        #[serde(skip_serializing_if = "Option::is_none")]
+        overscan: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scrollbar_width: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scrollbar_color: Option<ColorDef>,
+    },
     Text {
         content: String,
         editable: bool,
@@ -61,10 +98,141 @@ pub enum WidgetType {
         #[serde(skip_serializing_if = "Option::is_none")]
         border_radius: Option<f32>,
     },
+    /// Box that is checked and unchecked by clicking it
+    Checkbox {
+        #[serde(default)]
+        checked: bool,
+        /// Text shown beside the box
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        /// Ignore clicks and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Switch whose knob slides across its track when clicked
+    Toggle {
+        #[serde(default)]
+        checked: bool,
+        /// Text shown beside the track
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        /// Ignore clicks and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Track with a thumb that is dragged along it to pick a value between `min` and `max`
+    Slider {
+        #[serde(default)]
+        value: f64,
+        min: f64,
+        max: f64,
+        /// Values snap to multiples of `step` above `min`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        step: Option<f64>,
+        /// Spread the range logarithmically, e.g. for frequencies and gains; needs a positive `min`
+        #[serde(default)]
+        logarithmic: bool,
+        #[serde(default)]
+        orientation: SliderOrientation,
+        /// How the value is shown beside the track, e.g. `"{:.1} dB"`; no value is shown without one
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+        /// Ignore drags and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Number field that is changed by dragging it sideways or by typing a new value into it
+    DragValue {
+        #[serde(default)]
+        value: f64,
+        min: f64,
+        max: f64,
+        /// Values snap to multiples of `step` above `min`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        step: Option<f64>,
+        /// Spread the range logarithmically, e.g. for frequencies and gains; needs a positive `min`
+        #[serde(default)]
+        logarithmic: bool,
+        /// How the value is shown, e.g. `"{:.2} s"`; by default with as many decimals as `step`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+        /// Ignore drags and typing and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Single-line text field. Enter fires its `submit` binding and leaving it fires `change`,
+    /// both with the text as the `value` parameter.
+    TextInput {
+        #[serde(default)]
+        value: String,
+        /// Text shown while the field is empty and not focused
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<String>,
+        /// Most characters the field takes
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+        /// What the field accepts; typing that the filter rejects is ignored
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filter: Option<InputFilter>,
+        /// Show every character as a dot
+        #[serde(default)]
+        password: bool,
+        /// Ignore typing and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Box showing the selected option, which lists every option in a popup when clicked. Picking
+    /// one fires its `change` binding with the option's value as the `value` parameter.
+    Dropdown {
+        options: Vec<DropdownOption>,
+        /// Value of the option selected at first
+        #[serde(skip_serializing_if = "Option::is_none")]
+        selected: Option<String>,
+        /// Text shown while no option is selected
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<String>,
+        /// Ignore clicks and keys and show the `disabled` state style
+        #[serde(default)]
+        disabled: bool,
+    },
+    /// Overlay drawn above the rest of the tree, placed beside its anchor and flipped to the other
+    /// side where the window has no room. Its children stack in a column. `behavior.visible`
+    /// opens and closes it; pressing outside it and its anchor closes it and fires its `dismiss`
+    /// binding.
+    Popup {
+        /// Id of the widget the popup is placed beside; its parent by default
+        #[serde(skip_serializing_if = "Option::is_none")]
+        anchor: Option<String>,
+        #[serde(default)]
+        placement: PopupPlacement,
+        /// Space between the anchor and the popup
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gap: Option<f32>,
+        /// Show the popup when it is spawned
+        #[serde(default)]
+        open: bool,
+    },
+    /// Header row of tab buttons above stacked pages, one child per tab in the same order. Only
+    /// the active page is shown and kept in layout; the others keep their state while hidden.
+    /// Clicking a tab fires its `change` binding with the tab's id as the `value` parameter.
+    Tabs {
+        tabs: Vec<TabItem>,
+        /// Id of the tab shown at first; the first tab by default
+        #[serde(skip_serializing_if = "Option::is_none")]
+        active: Option<String>,
+        /// Give each tab a close button, which despawns its page and fires the `close` binding
+        /// with the tab's id
+        #[serde(default)]
+        closable: bool,
+        /// Let tabs be dragged along the header, firing the `reorder` binding with the tab ids in
+        /// their new order
+        #[serde(default)]
+        reorderable: bool,
+    },
 }
 
 /// Layout configuration for widgets
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LayoutConfig {
     pub position: Option<Vec3>, // Absolute position (x, y, z)
     pub size: Option<Vec2>,     // Width, height
@@ -80,7 +248,7 @@ pub struct LayoutConfig {
 }
 
 /// Style configuration for widgets  
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StyleConfig {
     pub background_color: Option<ColorDef>,
     pub border_color: Option<ColorDef>,
@@ -95,7 +263,7 @@ pub struct StyleConfig {
 }
 
 /// State-based style variants
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StateStyles {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hover: Option<StyleOverrides>,
@@ -105,10 +273,13 @@ pub struct StateStyles {
     pub focused: Option<StyleOverrides>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub disabled: Option<StyleOverrides>,
+    /// Applied to checked checkboxes and toggles, beneath the interaction states
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<StyleOverrides>,
 }
 
 /// Style overrides for specific states
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StyleOverrides {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background_color: Option<ColorDef>,
@@ -147,6 +318,79 @@ pub enum FlexDirection {
     ColumnReverse,
 }
 
+/// Direction a slider's track runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SliderOrientation {
+    /// Minimum on the left
+    #[default]
+    Horizontal,
+    /// Minimum at the bottom
+    Vertical,
+}
+
+/// Directions the content of a `ScrollView` scrolls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScrollAxes {
+    #[default]
+    Vertical,
+    Horizontal,
+    Both,
+}
+
+impl ScrollAxes {
+    /// Whether the content scrolls sideways
+    pub fn horizontal(self) -> bool {
+        matches!(self, ScrollAxes::Horizontal | ScrollAxes::Both)
+    }
+
+    /// Whether the content scrolls up and down
+    pub fn vertical(self) -> bool {
+        matches!(self, ScrollAxes::Vertical | ScrollAxes::Both)
+    }
+}
+
+/// Text a `TextInput` accepts. Typing is ignored once the text can no longer become accepted
+/// text, and submitted or committed text has to be accepted in full; empty text always is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputFilter {
+    /// Whole numbers, optionally negative
+    Integer,
+    /// Decimal numbers, optionally negative
+    Numeric,
+    /// Text the regular expression matches in full, e.g. `{ Regex = "[a-z0-9_]*" }`
+    Regex(String),
+}
+
+/// Side of its anchor a popup opens on, when the window has room for it there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PopupPlacement {
+    /// Under the anchor, lined up with its left edge
+    #[default]
+    Below,
+    Above,
+    /// Right of the anchor, lined up with its top edge
+    Right,
+    Left,
+}
+
+/// One of the options of a `Dropdown`: either a value shown as is, or
+/// `{ value = "fr", label = "Français" }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DropdownOption {
+    Value(String),
+    Labeled { value: String, label: String },
+}
+
+/// One of the tabs of a `Tabs` widget: either an id shown as its title, or
+/// `{ id = "script", title = "Script" }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TabItem {
+    Id(String),
+    Titled { id: String, title: String },
+}
+
 /// Shape types for shape widgets
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ShapeType {
@@ -179,10 +423,11 @@ pub enum AlignSelf {
 /// Color definition that supports multiple formats
 #[derive(Debug, Clone, PartialEq)]
 pub enum ColorDef {
-    Hex(String),        // "#FF0000"
+    Hex(String),        // "#FF0000", "#F00", "#FF000080"
     Rgb { r: u8, g: u8, b: u8 },
     Rgba { r: u8, g: u8, b: u8, a: f32 },
-    Named(String),      // "red", "blue", etc.
+    Named(String),      // "red", "rebeccapurple", etc.
+    Function(String),   // "rgb(255 0 0 / 50%)", "hsl(210, 50%, 40%)", "oklch(0.7 0.15 240)"
 }
 
 impl<'de> serde::Deserialize<'de> for ColorDef {
@@ -198,20 +443,14 @@ impl<'de> serde::Deserialize<'de> for ColorDef {
             type Value = ColorDef;
             
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("a color definition (hex string, named color, or RGB/RGBA object)")
+                formatter.write_str("a color definition (hex string, named color, color function, or RGB/RGBA object)")
             }
             
             fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                // Check if it's a hex color (starts with #)
-                if value.starts_with('#') {
-                    Ok(ColorDef::Hex(value.to_string()))
-                } else {
-                    // Otherwise treat as named color
-                    Ok(ColorDef::Named(value.to_string()))
-                }
+                Ok(ColorDef::from_css(value))
             }
             
             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -222,9 +461,30 @@ impl<'de> serde::Deserialize<'de> for ColorDef {
                 let mut g = None;
                 let mut b = None;
                 let mut a = None;
+                let mut tagged = None;
                 
+                // Channel fields of the externally tagged `{ Rgb = { ... } }` form
+                #[derive(Deserialize)]
+                struct Channels {
+                    r: u8,
+                    g: u8,
+                    b: u8,
+                    a: Option<f32>,
+                }
+
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
+                        // Externally tagged forms, as written in TOML: `{ Hex = "#2D3748" }`
+                        "Hex" => tagged = Some(ColorDef::Hex(map.next_value()?)),
+                        "Named" => tagged = Some(ColorDef::Named(map.next_value()?)),
+                        "Function" => tagged = Some(ColorDef::Function(map.next_value()?)),
+                        "Rgb" | "Rgba" => {
+                            let c: Channels = map.next_value()?;
+                            tagged = Some(match c.a {
+                                Some(a) => ColorDef::Rgba { r: c.r, g: c.g, b: c.b, a },
+                                None => ColorDef::Rgb { r: c.r, g: c.g, b: c.b },
+                            });
+                        }
                         "r" => r = Some(map.next_value()?),
                         "g" => g = Some(map.next_value()?),
                         "b" => b = Some(map.next_value()?),
@@ -235,6 +495,10 @@ impl<'de> serde::Deserialize<'de> for ColorDef {
                     }
                 }
                 
+                if let Some(color) = tagged {
+                    return Ok(color);
+                }
+                
                 let r = r.ok_or_else(|| de::Error::missing_field("r"))?;
                 let g = g.ok_or_else(|| de::Error::missing_field("g"))?;
                 let b = b.ok_or_else(|| de::Error::missing_field("b"))?;
@@ -259,6 +523,7 @@ impl serde::Serialize for ColorDef {
         match self {
             ColorDef::Hex(s) => serializer.serialize_str(s),
             ColorDef::Named(s) => serializer.serialize_str(s),
+            ColorDef::Function(s) => serializer.serialize_str(s),
             ColorDef::Rgb { r, g, b } => {
                 use serde::ser::SerializeStruct;
                 let mut state = serializer.serialize_struct("Rgb", 3)?;
@@ -280,37 +545,6 @@ impl serde::Serialize for ColorDef {
     }
 }
 
-impl Default for LayoutConfig {
-    fn default() -> Self {
-        Self {
-            position: None,
-            size: None,
-            margin: None,
-            padding: None,
-            flex_grow: None,
-            flex_shrink: None,
-            align_self: None,
-            grid_row: None,
-            grid_column: None,
-        }
-    }
-}
-
-impl Default for StyleConfig {
-    fn default() -> Self {
-        Self {
-            background_color: None,
-            border_color: None,
-            border_width: None,
-            border_radius: None,
-            text_color: None,
-            text_size: None,
-            opacity: None,
-            states: None,
-        }
-    }
-}
-
 impl Default for BehaviorConfig {
     fn default() -> Self {
         Self {
@@ -326,45 +560,49 @@ impl Default for BehaviorConfig {
 }
 
 impl ColorDef {
-    /// Convert to Bevy Color
-    pub fn to_color(&self) -> Color {
+    /// Create a color definition from a Bevy Color, as `#RRGGBB` when opaque
+    pub fn from_color(color: Color) -> Self {
+        let srgba = color.to_srgba();
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let (r, g, b) = (channel(srgba.red), channel(srgba.green), channel(srgba.blue));
+        if srgba.alpha >= 1.0 {
+            ColorDef::Hex(format!("#{:02X}{:02X}{:02X}", r, g, b))
+        } else {
+            ColorDef::Rgba { r, g, b, a: srgba.alpha }
+        }
+    }
+
+    /// Color definition for a CSS color string, by its syntax
+    pub fn from_css(value: &str) -> Self {
+        if value.trim_start().starts_with('#') {
+            ColorDef::Hex(value.to_string())
+        } else if value.contains('(') {
+            ColorDef::Function(value.to_string())
+        } else {
+            ColorDef::Named(value.to_string())
+        }
+    }
+
+    /// Parse into a Bevy Color. Validation uses this too, so anything it accepts converts.
+    pub fn parse(&self) -> Result<Color, ColorParseError> {
         match self {
-            ColorDef::Hex(hex) => {
-                // Parse hex string like "#FF0000" or "FF0000"
-                let hex = hex.trim_start_matches('#');
-                if hex.len() == 6 {
-                    let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0) as f32 / 255.0;
-                    let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0) as f32 / 255.0;
-                    let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0) as f32 / 255.0;
-                    Color::srgb(r, g, b)
-                } else {
-                    Color::WHITE // fallback
-                }
-            }
-            ColorDef::Rgb { r, g, b } => {
-                Color::srgb(*r as f32 / 255.0, *g as f32 / 255.0, *b as f32 / 255.0)
-            }
+            ColorDef::Hex(hex) => parse_hex_color(hex.trim().trim_start_matches('#')),
+            ColorDef::Rgb { r, g, b } => Ok(Color::srgb_u8(*r, *g, *b)),
             ColorDef::Rgba { r, g, b, a } => {
-                Color::srgba(*r as f32 / 255.0, *g as f32 / 255.0, *b as f32 / 255.0, *a)
-            }
-            ColorDef::Named(name) => {
-                // Basic named color support
-                match name.to_lowercase().as_str() {
-                    "red" => Color::srgb(1.0, 0.0, 0.0),
-                    "green" => Color::srgb(0.0, 1.0, 0.0),
-                    "blue" => Color::srgb(0.0, 0.0, 1.0),
-                    "black" => Color::BLACK,
-                    "white" => Color::WHITE,
-                    "gray" | "grey" => Color::srgb(0.5, 0.5, 0.5),
-                    "yellow" => Color::srgb(1.0, 1.0, 0.0),
-                    "cyan" => Color::srgb(0.0, 1.0, 1.0),
-                    "magenta" => Color::srgb(1.0, 0.0, 1.0),
-                    "orange" => Color::srgb(1.0, 0.5, 0.0),
-                    _ => Color::WHITE, // fallback
+                if !(0.0..=1.0).contains(a) {
+                    return Err(ColorParseError::AlphaOutOfRange(*a));
                 }
+                Ok(Color::srgba(*r as f32 / 255.0, *g as f32 / 255.0, *b as f32 / 255.0, *a))
             }
+            ColorDef::Named(name) => parse_named_color(name),
+            ColorDef::Function(function) => parse_css_color(function),
         }
     }
+
+    /// Convert to Bevy Color; invalid definitions, which validation reports, become white
+    pub fn to_color(&self) -> Color {
+        self.parse().unwrap_or(Color::WHITE)
+    }
 }
 
 impl StyleOverrides {
@@ -381,9 +619,40 @@ impl StyleOverrides {
             states: base.states.clone(), // Keep original state definitions
         }
     }
+
+    /// Combine two overrides; values set in `other` win
+    pub fn merged_with(&self, other: &StyleOverrides) -> StyleOverrides {
+        StyleOverrides {
+            background_color: other.background_color.clone().or_else(|| self.background_color.clone()),
+            border_color: other.border_color.clone().or_else(|| self.border_color.clone()),
+            border_width: other.border_width.or(self.border_width),
+            border_radius: other.border_radius.or(self.border_radius),
+            text_color: other.text_color.clone().or_else(|| self.text_color.clone()),
+            text_size: other.text_size.or(self.text_size),
+            opacity: other.opacity.or(self.opacity),
+        }
+    }
 }
 
 impl StateStyles {
+    /// Combine two sets of state variants state by state; values set in `other` win
+    pub fn merged_with(&self, other: &StateStyles) -> StateStyles {
+        fn merge(base: &Option<StyleOverrides>, top: &Option<StyleOverrides>) -> Option<StyleOverrides> {
+            match (base, top) {
+                (Some(base), Some(top)) => Some(base.merged_with(top)),
+                _ => top.clone().or_else(|| base.clone()),
+            }
+        }
+
+        StateStyles {
+            hover: merge(&self.hover, &other.hover),
+            pressed: merge(&self.pressed, &other.pressed),
+            focused: merge(&self.focused, &other.focused),
+            disabled: merge(&self.disabled, &other.disabled),
+            checked: merge(&self.checked, &other.checked),
+        }
+    }
+
     /// Get the appropriate style override for the given interaction state
     pub fn get_for_state(&self, hovered: bool, pressed: bool, focused: bool, disabled: bool) -> Option<&StyleOverrides> {
         // Priority order: disabled > pressed > focused > hover
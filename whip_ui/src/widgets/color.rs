@@ -0,0 +1,280 @@
+use bevy_color::{Color, ColorToComponents, Hsla, Oklcha, Srgba};
+use thiserror::Error;
+
+/// CSS named colors (CSS Color Module Level 4) as `0xRRGGBB`, sorted by name.
+/// `transparent` is handled separately since it is the only one with alpha.
+pub const CSS_NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF), ("antiquewhite", 0xFAEBD7), ("aqua", 0x00FFFF), ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF), ("beige", 0xF5F5DC), ("bisque", 0xFFE4C4), ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD), ("blue", 0x0000FF), ("blueviolet", 0x8A2BE2), ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887), ("cadetblue", 0x5F9EA0), ("chartreuse", 0x7FFF00), ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50), ("cornflowerblue", 0x6495ED), ("cornsilk", 0xFFF8DC), ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF), ("darkblue", 0x00008B), ("darkcyan", 0x008B8B), ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9), ("darkgreen", 0x006400), ("darkgrey", 0xA9A9A9), ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B), ("darkolivegreen", 0x556B2F), ("darkorange", 0xFF8C00), ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000), ("darksalmon", 0xE9967A), ("darkseagreen", 0x8FBC8F), ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F), ("darkslategrey", 0x2F4F4F), ("darkturquoise", 0x00CED1), ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493), ("deepskyblue", 0x00BFFF), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF), ("firebrick", 0xB22222), ("floralwhite", 0xFFFAF0), ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF), ("gainsboro", 0xDCDCDC), ("ghostwhite", 0xF8F8FF), ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520), ("gray", 0x808080), ("green", 0x008000), ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080), ("honeydew", 0xF0FFF0), ("hotpink", 0xFF69B4), ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082), ("ivory", 0xFFFFF0), ("khaki", 0xF0E68C), ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5), ("lawngreen", 0x7CFC00), ("lemonchiffon", 0xFFFACD), ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080), ("lightcyan", 0xE0FFFF), ("lightgoldenrodyellow", 0xFAFAD2), ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90), ("lightgrey", 0xD3D3D3), ("lightpink", 0xFFB6C1), ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA), ("lightskyblue", 0x87CEFA), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE), ("lightyellow", 0xFFFFE0), ("lime", 0x00FF00), ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6), ("magenta", 0xFF00FF), ("maroon", 0x800000), ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD), ("mediumorchid", 0xBA55D3), ("mediumpurple", 0x9370DB), ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE), ("mediumspringgreen", 0x00FA9A), ("mediumturquoise", 0x48D1CC), ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xF5FFFA), ("mistyrose", 0xFFE4E1), ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD), ("navy", 0x000080), ("oldlace", 0xFDF5E6), ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23), ("orange", 0xFFA500), ("orangered", 0xFF4500), ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA), ("palegreen", 0x98FB98), ("paleturquoise", 0xAFEEEE), ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5), ("peachpuff", 0xFFDAB9), ("peru", 0xCD853F), ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD), ("powderblue", 0xB0E0E6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000), ("rosybrown", 0xBC8F8F), ("royalblue", 0x4169E1), ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072), ("sandybrown", 0xF4A460), ("seagreen", 0x2E8B57), ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D), ("silver", 0xC0C0C0), ("skyblue", 0x87CEEB), ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xFFFAFA), ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4), ("tan", 0xD2B48C), ("teal", 0x008080), ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347), ("turquoise", 0x40E0D0), ("violet", 0xEE82EE), ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF), ("whitesmoke", 0xF5F5F5), ("yellow", 0xFFFF00), ("yellowgreen", 0x9ACD32),
+];
+
+/// Errors that can occur while parsing a color
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ColorParseError {
+    #[error("Invalid hex color '{0}'. Expected #RGB, #RGBA, #RRGGBB or #RRGGBBAA")]
+    InvalidHex(String),
+    #[error("Unknown named color '{0}'")]
+    UnknownName(String),
+    #[error("Unknown color function '{0}'. Expected rgb(), rgba(), hsl(), hsla() or oklch()")]
+    UnknownFunction(String),
+    #[error("Invalid color '{input}': {reason}")]
+    InvalidFunction { input: String, reason: &'static str },
+    #[error("Alpha channel must be between 0.0 and 1.0, found {0}")]
+    AlphaOutOfRange(f32),
+}
+
+/// Parse a CSS color: `#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`, a named color, or an `rgb()`,
+/// `rgba()`, `hsl()`, `hsla()` or `oklch()` function in either the comma or the space syntax.
+///
+/// Colors are returned in sRGB, which the renderer draws directly; out of range components are
+/// clamped as in CSS.
+pub fn parse_css_color(input: &str) -> Result<Color, ColorParseError> {
+    let input = input.trim();
+    if let Some(digits) = input.strip_prefix('#') {
+        parse_hex_color(digits)
+    } else if input.contains('(') {
+        parse_color_function(input)
+    } else {
+        parse_named_color(input)
+    }
+}
+
+/// Parse the digits of a hex color, without the leading `#`
+pub fn parse_hex_color(digits: &str) -> Result<Color, ColorParseError> {
+    let invalid = || ColorParseError::InvalidHex(format!("#{}", digits));
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let value = |range: std::ops::Range<usize>| u8::from_str_radix(&digits[range], 16).map_err(|_| invalid());
+    let [r, g, b, a] = match digits.len() {
+        // Short forms repeat each digit: #F80 is #FF8800
+        3 | 4 => {
+            let mut channels = [255u8; 4];
+            for (index, channel) in channels.iter_mut().take(digits.len()).enumerate() {
+                *channel = value(index..index + 1)? * 17;
+            }
+            channels
+        }
+        6 | 8 => {
+            let mut channels = [255u8; 4];
+            for (index, channel) in channels.iter_mut().take(digits.len() / 2).enumerate() {
+                *channel = value(index * 2..index * 2 + 2)?;
+            }
+            channels
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(Color::srgba_u8(r, g, b, a))
+}
+
+/// Look up a CSS named color, ignoring case
+pub fn parse_named_color(name: &str) -> Result<Color, ColorParseError> {
+    let lowercase = name.trim().to_ascii_lowercase();
+    if lowercase == "transparent" {
+        return Ok(Color::srgba(0.0, 0.0, 0.0, 0.0));
+    }
+    CSS_NAMED_COLORS.binary_search_by(|(candidate, _)| (*candidate).cmp(lowercase.as_str()))
+        .map(|index| {
+            let rgb = CSS_NAMED_COLORS[index].1;
+            Color::srgb_u8((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+        })
+        .map_err(|_| ColorParseError::UnknownName(name.to_string()))
+}
+
+fn parse_color_function(input: &str) -> Result<Color, ColorParseError> {
+    let invalid = |reason| ColorParseError::InvalidFunction { input: input.to_string(), reason };
+    let (name, body) = input.split_once('(').ok_or_else(|| invalid("missing '('"))?;
+    let body = body.strip_suffix(')').ok_or_else(|| invalid("missing closing ')'"))?;
+    let (components, alpha) = split_components(body);
+    if components.len() != 3 {
+        return Err(invalid("expected three components"));
+    }
+    let alpha = match alpha {
+        Some(alpha) => number_or_percentage(alpha, 1.0).ok_or_else(|| invalid("alpha must be a number or percentage"))?.clamp(0.0, 1.0),
+        None => 1.0,
+    };
+
+    let srgba = match name.trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => {
+            let mut channels = [0.0; 3];
+            for (channel, component) in channels.iter_mut().zip(&components) {
+                *channel = number_or_percentage(component, 255.0)
+                    .ok_or_else(|| invalid("rgb() channels must be numbers or percentages"))?
+                    .clamp(0.0, 255.0) / 255.0;
+            }
+            Srgba::new(channels[0], channels[1], channels[2], alpha)
+        }
+        "hsl" | "hsla" => {
+            let hue = hue(components[0]).ok_or_else(|| invalid("hsl() hue must be an angle"))?;
+            // Saturation and lightness are percentages; CSS also allows plain numbers from 0 to 100
+            let percent = |component: &str| number_or_percentage(component, 100.0).map(|value| (value / 100.0).clamp(0.0, 1.0));
+            let saturation = percent(components[1]).ok_or_else(|| invalid("hsl() saturation must be a percentage"))?;
+            let lightness = percent(components[2]).ok_or_else(|| invalid("hsl() lightness must be a percentage"))?;
+            Hsla::new(hue, saturation, lightness, alpha).into()
+        }
+        "oklch" => {
+            let lightness = number_or_percentage(components[0], 1.0)
+                .ok_or_else(|| invalid("oklch() lightness must be a number or percentage"))?
+                .clamp(0.0, 1.0);
+            // 100% chroma is 0.4
+            let chroma = number_or_percentage(components[1], 0.4)
+                .ok_or_else(|| invalid("oklch() chroma must be a number or percentage"))?
+                .max(0.0);
+            let hue = hue(components[2]).ok_or_else(|| invalid("oklch() hue must be an angle"))?;
+            let srgba: Srgba = Oklcha::new(lightness, chroma, hue, alpha).into();
+            // Colors outside the sRGB gamut are clipped to it
+            Srgba::from_f32_array(srgba.to_f32_array().map(|component| component.clamp(0.0, 1.0)))
+        }
+        other => return Err(ColorParseError::UnknownFunction(other.to_string())),
+    };
+    Ok(Color::Srgba(srgba))
+}
+
+/// Split function arguments into components and alpha, accepting both `rgb(1, 2, 3, 0.5)` and
+/// `rgb(1 2 3 / 50%)`
+fn split_components(body: &str) -> (Vec<&str>, Option<&str>) {
+    if body.contains(',') {
+        let mut components: Vec<&str> = body.split(',').map(str::trim).collect();
+        let alpha = if components.len() == 4 { components.pop() } else { None };
+        (components, alpha)
+    } else {
+        let (components, alpha) = match body.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None => (body, None),
+        };
+        (components.split_whitespace().collect(), alpha)
+    }
+}
+
+/// A plain number, or a percentage of `full`
+fn number_or_percentage(component: &str, full: f32) -> Option<f32> {
+    let value = match component.strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().ok()? / 100.0 * full,
+        None => component.parse::<f32>().ok()?,
+    };
+    value.is_finite().then_some(value)
+}
+
+/// An angle in degrees, from a plain number or one with a `deg`, `rad`, `grad` or `turn` unit
+fn hue(component: &str) -> Option<f32> {
+    let component = component.to_ascii_lowercase();
+    let degrees = if let Some(value) = component.strip_suffix("deg") {
+        value.parse::<f32>().ok()?
+    } else if let Some(value) = component.strip_suffix("grad") {
+        value.parse::<f32>().ok()? * 0.9
+    } else if let Some(value) = component.strip_suffix("rad") {
+        value.parse::<f32>().ok()?.to_degrees()
+    } else if let Some(value) = component.strip_suffix("turn") {
+        value.parse::<f32>().ok()? * 360.0
+    } else {
+        component.parse::<f32>().ok()?
+    };
+    degrees.is_finite().then(|| degrees.rem_euclid(360.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::widgets::blueprint::ColorDef;
+    use bevy_color::{Color, ColorToComponents};
+
+    fn rgba(color: Color) -> [u8; 4] {
+        color.to_srgba().to_f32_array().map(|component| (component * 255.0).round() as u8)
+    }
+
+    /// Test the hex forms and the named color table
+    #[test]
+    fn test_hex_and_named_colors() {
+        assert_eq!(rgba(parse_css_color("#F80").unwrap()), [255, 136, 0, 255]);
+        assert_eq!(rgba(parse_css_color("#F808").unwrap()), [255, 136, 0, 136]);
+        assert_eq!(rgba(parse_css_color("#2D3748").unwrap()), [45, 55, 72, 255]);
+        assert_eq!(rgba(ColorDef::Hex("#2D374880".to_string()).to_color()), [45, 55, 72, 128], "8-digit hex keeps its alpha");
+        assert!(matches!(parse_css_color("#12345"), Err(ColorParseError::InvalidHex(_))));
+        assert!(parse_css_color("#GGGGGG").is_err());
+
+        assert_eq!(rgba(parse_css_color("RebeccaPurple").unwrap()), [102, 51, 153, 255]);
+        assert_eq!(rgba(parse_css_color("green").unwrap()), [0, 128, 0, 255]);
+        assert_eq!(rgba(parse_css_color("transparent").unwrap())[3], 0);
+        assert!(matches!(parse_css_color("blurple"), Err(ColorParseError::UnknownName(_))));
+        assert_eq!(CSS_NAMED_COLORS.len(), 148);
+        assert!(CSS_NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0), "Names must stay sorted for lookup");
+    }
+
+    /// Test the rgb(), hsl() and oklch() functions in both syntaxes
+    #[test]
+    fn test_color_functions() {
+        assert_eq!(rgba(parse_css_color("rgb(255, 136, 0)").unwrap()), [255, 136, 0, 255]);
+        assert_eq!(rgba(parse_css_color("rgba(255, 136, 0, 0.5)").unwrap()), [255, 136, 0, 128]);
+        assert_eq!(rgba(parse_css_color("rgb(100% 0% 0% / 25%)").unwrap()), [255, 0, 0, 64]);
+        assert_eq!(rgba(parse_css_color("hsl(120deg 100% 50%)").unwrap()), [0, 255, 0, 255]);
+        assert_eq!(rgba(parse_css_color("hsla(0.5turn, 100%, 50%, 1)").unwrap()), [0, 255, 255, 255]);
+        assert_eq!(rgba(parse_css_color("oklch(100% 0 0)").unwrap()), [255, 255, 255, 255]);
+        assert_eq!(rgba(parse_css_color("oklch(0 0 0 / 0.5)").unwrap()), [0, 0, 0, 128]);
+
+        assert!(matches!(parse_css_color("lab(50 0 0)"), Err(ColorParseError::UnknownFunction(_))));
+        assert!(matches!(parse_css_color("rgb(1, 2)"), Err(ColorParseError::InvalidFunction { .. })));
+        assert!(parse_css_color("rgb(1 2 3").is_err());
+    }
+
+    /// Test that validation accepts exactly what conversion understands
+    #[test]
+    fn test_color_validation_uses_the_parser() {
+        let content = r##"
+[styles.accent]
+background_color = "#3182CE80"
+border_color = "hsl(210 60% 50%)"
+text_color = "slategray"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+style = { background_color = { Function = "oklch(0.7 0.15 240)" } }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+        assert!(!ui_def.validation_report().has_errors());
+        assert_eq!(ui_def.styles.as_ref().unwrap()["accent"].border_color, Some(ColorDef::Function("hsl(210 60% 50%)".to_string())));
+
+        let mut ui_def = ui_def;
+        for invalid in [ColorDef::Named("blurple".to_string()), ColorDef::Function("rgb(1, 2)".to_string()), ColorDef::Rgba { r: 0, g: 0, b: 0, a: 1.5 }] {
+            assert!(invalid.parse().is_err());
+            ui_def.root.style.background_color = Some(invalid);
+            let report = ui_def.validation_report();
+            assert_eq!(report.errors().map(|diagnostic| diagnostic.path.as_str()).collect::<Vec<_>>(), vec!["root.style.background_color"]);
+        }
+    }
+}
\ No newline at end of file
@@ -69,6 +69,118 @@ pub struct WidgetActionBindings {
     pub bindings: HashMap<String, crate::assets::definitions::ActionBinding>,
 }
 
+/// Component mapping widget properties to the `UiState` values they display
+#[derive(Component, Debug, Clone, Default)]
+pub struct WidgetStateBindings {
+    pub bindings: Vec<(crate::assets::state::BoundProperty, String)>,
+}
+
+impl WidgetStateBindings {
+    /// Bindings from a node's `state_bindings` table; unknown properties are skipped
+    pub fn from_table(table: &HashMap<String, String>) -> Self {
+        let mut bindings: Vec<_> = table.iter()
+            .filter_map(|(property, state_path)| {
+                Some((crate::assets::state::BoundProperty::from_path(property)?, state_path.clone()))
+            })
+            .collect();
+        bindings.sort();
+        Self { bindings }
+    }
+}
+
+/// Component for text widgets whose content comes from the current locale's string table
+#[derive(Component, Debug, Clone)]
+pub struct WidgetLocalizedText {
+    pub text: crate::assets::localization::LocalizedText,
+}
+
+/// Component for widgets whose children come and go with `UiState`: `when` children and the
+/// items of a `repeat` node. Static children keep their place between the dynamic ones.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetDynamicChildren {
+    pub slots: Vec<DynamicChildSlot>,
+    /// Authored position of the owning widget, which children are laid out relative to
+    pub parent_position: Option<bevy_math::Vec3>,
+}
+
+/// One child position of a `WidgetDynamicChildren` widget
+#[derive(Debug, Clone)]
+pub enum DynamicChildSlot {
+    /// A child that is always present
+    Static(Entity),
+    /// A `when` node, spawned while its condition holds
+    When {
+        condition: crate::assets::dynamic::StateCondition,
+        node: Box<crate::assets::definitions::WidgetNode>,
+        entity: Option<Entity>,
+    },
+    /// The item template of a `repeat` node, spawned once per list element
+    Repeat {
+        config: crate::assets::dynamic::RepeatConfig,
+        /// Prefix for the ids of spawned items
+        host_id: String,
+        template: Vec<crate::assets::definitions::WidgetNode>,
+        items: Vec<RepeatedItem>,
+    },
+}
+
+/// Widgets spawned for one element of a repeated list
+#[derive(Debug, Clone)]
+pub struct RepeatedItem {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub entities: Vec<Entity>,
+}
+
+impl WidgetDynamicChildren {
+    /// Entities currently spawned for every slot, in order
+    pub fn children(&self) -> Vec<Entity> {
+        self.slots.iter()
+            .flat_map(|slot| -> Vec<Entity> {
+                match slot {
+                    DynamicChildSlot::Static(entity) => vec![*entity],
+                    DynamicChildSlot::When { entity, .. } => entity.iter().copied().collect(),
+                    DynamicChildSlot::Repeat { items, .. } => items.iter().flat_map(|item| item.entities.iter().copied()).collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// State values the dynamic slots depend on
+    pub fn state_paths(&self) -> impl Iterator<Item = &str> {
+        self.slots.iter().filter_map(|slot| match slot {
+            DynamicChildSlot::Static(_) => None,
+            DynamicChildSlot::When { condition, .. } => Some(condition.path.as_str()),
+            DynamicChildSlot::Repeat { config, .. } => Some(config.each.as_str()),
+        })
+    }
+}
+
+/// Component holding the definition node a widget was spawned from, without its children.
+/// Keeps what the live components lose (classes, overrides, template types) so the tree
+/// can be written back out.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetSourceNode {
+    pub node: crate::assets::definitions::WidgetNode,
+    /// Whether the node instantiates a data-declared widget template
+    pub template_instance: bool,
+}
+
+impl WidgetSourceNode {
+    /// Capture `node` without its children, which are tracked through `WidgetHierarchy`
+    pub fn new(node: &crate::assets::definitions::WidgetNode) -> Self {
+        let mut node = node.clone();
+        node.children.clear();
+        Self { node, template_instance: false }
+    }
+
+    /// Capture a template instance with its children, since the live widgets below it were
+    /// expanded from the template rather than spawned from those children
+    pub fn template_instance(node: &crate::assets::definitions::WidgetNode) -> Self {
+        Self { node: node.clone(), template_instance: true }
+    }
+}
+
 /// Component for container widgets that manage child layout
 #[derive(Component, Debug)]
 pub struct WidgetContainer {
@@ -110,7 +222,7 @@ impl From<&LayoutConfig> for WidgetLayout {
         let padding = config.padding.as_ref().map(|s| (s.top, s.right, s.bottom, s.left));
         
         // Convert Vec3 position to TomlCoords if present
-        let toml_position = config.position.map(|pos| TomlCoords::from(pos));
+        let toml_position = config.position.map(TomlCoords::from);
         
         // For computed_position, we need to convert to Bevy coordinates
         // This will be properly set by the layout system with window height
@@ -0,0 +1,490 @@
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Parent};
+use bevy_input::keyboard::{Key, KeyboardInput};
+use bevy_math::Vec2;
+use bevy_transform::prelude::Transform;
+use std::collections::HashSet;
+use thiserror::Error;
+use crate::assets::definitions::WidgetNode;
+use crate::assets::reload::{collect_subtree, despawn_widgets, set_static_text};
+use crate::assets::windows::WidgetWindows;
+use crate::gui_framework::components::{ShapeData, Text};
+use crate::gui_framework::events::{ActionEvent, EntityClicked, PointerPressed, YrsTextChanged};
+use crate::gui_framework::systems::change_action;
+use crate::layout::{PositionControl, TaffyResource, UiNode};
+use crate::widgets::blueprint::{BehaviorConfig, DropdownOption, LayoutConfig, PopupPlacement, ShapeType, StyleConfig, WidgetType};
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetBehavior, WidgetHierarchy, WidgetSourceNode, WidgetStyle, WidgetText};
+use crate::widgets::popup::WidgetPopup;
+use crate::widgets::systems::spawn_widget_entity_from_node;
+use crate::widgets::templates::{get_widget_templates, DropdownTemplate};
+use crate::YrsDocResource;
+
+/// Seconds after the last typed character before type-ahead starts a new search
+pub const TYPE_AHEAD_TIMEOUT: f64 = 1.0;
+
+/// Problems with a `Dropdown` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum DropdownError {
+    #[error("a dropdown needs at least one option")]
+    EmptyOptions,
+    #[error("option value '{0}' is used more than once")]
+    DuplicateValue(String),
+    #[error("selected value '{0}' is not one of the options")]
+    UnknownSelection(String),
+}
+
+impl DropdownError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            DropdownError::EmptyOptions | DropdownError::DuplicateValue(_) => "options",
+            DropdownError::UnknownSelection(_) => "selected",
+        }
+    }
+}
+
+/// Events a dropdown fires with the selected value as the `value` parameter
+pub const DROPDOWN_VALUE_EVENTS: &[&str] = &["change"];
+
+/// Check the options and selection of a `Dropdown`; other widget types pass
+pub fn validate_dropdown(widget_type: &WidgetType) -> Result<(), DropdownError> {
+    let WidgetType::Dropdown { options, selected, .. } = widget_type else {
+        return Ok(());
+    };
+    if options.is_empty() {
+        return Err(DropdownError::EmptyOptions);
+    }
+    let mut values = HashSet::new();
+    if let Some(option) = options.iter().find(|option| !values.insert(option.value())) {
+        return Err(DropdownError::DuplicateValue(option.value().to_string()));
+    }
+    if let Some(selected) = selected.as_ref().filter(|selected| !values.contains(selected.as_str())) {
+        return Err(DropdownError::UnknownSelection(selected.clone()));
+    }
+    Ok(())
+}
+
+impl DropdownOption {
+    /// The value sent to the `change` binding when the option is picked
+    pub fn value(&self) -> &str {
+        match self {
+            DropdownOption::Value(value) | DropdownOption::Labeled { value, .. } => value,
+        }
+    }
+
+    /// The text the option is shown with
+    pub fn label(&self) -> &str {
+        match self {
+            DropdownOption::Value(label) | DropdownOption::Labeled { label, .. } => label,
+        }
+    }
+}
+
+/// Component on the box of a `Dropdown` widget holding its options, its selection and its open
+/// list
+#[derive(Component, Debug, Clone)]
+pub struct WidgetDropdown {
+    pub options: Vec<DropdownOption>,
+    pub selected: Option<usize>,
+    pub placeholder: Option<String>,
+    pub disabled: bool,
+    /// Whether the option list is shown; `dropdown_list_system` spawns and despawns it
+    pub open: bool,
+    /// Option under the keyboard highlight while the list is open
+    pub highlighted: Option<usize>,
+    /// Whether key presses go to this dropdown; set when it or its list is pressed
+    pub focused: bool,
+    /// Popup listing the options while the dropdown is open
+    pub list: Option<Entity>,
+    /// Characters typed for type-ahead, lowercased, and when the last one was typed
+    pub typed: String,
+    pub typed_at: Option<f64>,
+}
+
+impl WidgetDropdown {
+    /// The dropdown state of a `Dropdown` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let WidgetType::Dropdown { options, selected, placeholder, disabled } = widget_type else {
+            return None;
+        };
+        // Unknown selections are reported by validation and leave nothing selected here
+        let selected = selected.as_deref().and_then(|selected| options.iter().position(|option| option.value() == selected));
+        Some(Self {
+            options: options.clone(),
+            selected,
+            placeholder: placeholder.clone(),
+            disabled: *disabled,
+            open: false,
+            highlighted: None,
+            focused: false,
+            list: None,
+            typed: String::new(),
+            typed_at: None,
+        })
+    }
+
+    /// Value of the selected option
+    pub fn selected_value(&self) -> Option<&str> {
+        self.selected.and_then(|index| self.options.get(index)).map(DropdownOption::value)
+    }
+
+    /// Text shown in the box: the selected option's label, or else the placeholder
+    pub fn shown_label(&self) -> &str {
+        match self.selected.and_then(|index| self.options.get(index)) {
+            Some(option) => option.label(),
+            None => self.placeholder.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// Select the option at `index`, returning its value if the selection changed
+    pub fn select(&mut self, index: usize) -> Option<String> {
+        if self.selected == Some(index) || index >= self.options.len() {
+            return None;
+        }
+        self.selected = Some(index);
+        self.selected_value().map(str::to_string)
+    }
+
+    /// Open the list with the selected option highlighted
+    pub fn open_list(&mut self) {
+        self.open = true;
+        self.highlighted = self.selected;
+    }
+
+    /// The option keys move from: the highlighted one while open, the selected one otherwise
+    fn current(&self) -> Option<usize> {
+        match self.open {
+            true => self.highlighted.or(self.selected),
+            false => self.selected,
+        }
+    }
+
+    /// The option matching the characters typed so far, starting after the current one.
+    /// Typing the same character again steps through the options starting with it.
+    pub fn type_ahead(&mut self, text: &str, now: f64) -> Option<usize> {
+        if self.typed_at.is_some_and(|typed_at| now - typed_at > TYPE_AHEAD_TIMEOUT) {
+            self.typed.clear();
+        }
+        self.typed.push_str(&text.to_lowercase());
+        self.typed_at = Some(now);
+
+        let first = self.typed.chars().next()?;
+        let (prefix, skip) = match self.typed.chars().all(|c| c == first) {
+            true => (first.to_string(), 1),
+            // A longer search stays on the current option while it still matches
+            false => (self.typed.clone(), 0),
+        };
+        let count = self.options.len();
+        let start = self.current().map_or(0, |current| current + skip);
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&index| self.options[index].label().to_lowercase().starts_with(&prefix))
+    }
+
+    /// React to a key press: arrows, Home, End and type-ahead move the highlight while the list is
+    /// open and the selection while it is closed, Enter and Space open the list or pick the
+    /// highlighted option, and Escape closes it. Returns the new value if the selection changed.
+    pub fn press_key(&mut self, key: &Key, now: f64) -> Option<String> {
+        if self.disabled || self.options.is_empty() {
+            return None;
+        }
+        let last = self.options.len() - 1;
+        let target = match key {
+            Key::ArrowDown => self.current().map_or(0, |current| (current + 1).min(last)),
+            Key::ArrowUp => self.current().map_or(last, |current| current.saturating_sub(1)),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Enter | Key::Space if self.open => {
+                self.open = false;
+                return self.highlighted.and_then(|index| self.select(index));
+            }
+            Key::Enter | Key::Space => {
+                self.open_list();
+                return None;
+            }
+            Key::Escape => {
+                self.open = false;
+                return None;
+            }
+            Key::Character(text) => self.type_ahead(text, now)?,
+            _ => return None,
+        };
+        match self.open {
+            true => {
+                self.highlighted = Some(target);
+                None
+            }
+            false => self.select(target),
+        }
+    }
+}
+
+/// Component on a row of an open dropdown's option list
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropdownRow {
+    pub dropdown: Entity,
+    pub index: usize,
+}
+
+/// System that opens and closes dropdowns when their box is clicked, and picks options when
+/// their row is clicked, firing the dropdown's `change` binding with the option's value
+pub fn dropdown_click_system(
+    mut click_events: EventReader<EntityClicked>,
+    rows: Query<&DropdownRow>,
+    mut dropdowns: Query<(&mut WidgetDropdown, Option<&WidgetActionBindings>)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for click_event in click_events.read() {
+        if let Ok(row) = rows.get(click_event.entity) {
+            let Ok((mut dropdown, bindings)) = dropdowns.get_mut(row.dropdown) else {
+                continue;
+            };
+            dropdown.open = false;
+            if let Some(value) = dropdown.select(row.index) {
+                bevy_log::debug!("Dropdown {:?} changed to '{}'", row.dropdown, value);
+                if let Some(action) = change_action(bindings, row.dropdown, serde_json::Value::String(value)) {
+                    action_events.send(action);
+                }
+            }
+            continue;
+        }
+
+        let Ok((mut dropdown, _)) = dropdowns.get_mut(click_event.entity) else {
+            continue;
+        };
+        if dropdown.disabled {
+            continue;
+        }
+        match dropdown.open {
+            true => dropdown.open = false,
+            false => dropdown.open_list(),
+        }
+    }
+}
+
+/// System that gives keyboard focus to the dropdown pressed, or to the dropdown whose list was
+/// pressed, and takes it from the others
+pub fn dropdown_focus_system(
+    mut pressed_events: EventReader<PointerPressed>,
+    mut dropdowns: Query<(Entity, &mut WidgetDropdown)>,
+    links: Query<(Option<&WidgetHierarchy>, Option<&Parent>)>,
+) {
+    for pressed_event in pressed_events.read() {
+        // The closest dropdown around the pressed widget
+        let mut current = pressed_event.entity;
+        while let Some(entity) = current.filter(|entity| !dropdowns.contains(*entity)) {
+            current = links.get(entity).ok().and_then(|(hierarchy, parent)| {
+                hierarchy.and_then(|hierarchy| hierarchy.parent).or(parent.map(Parent::get))
+            });
+        }
+        for (entity, mut dropdown) in dropdowns.iter_mut() {
+            let focused = current == Some(entity) && !dropdown.disabled;
+            if dropdown.focused != focused {
+                dropdown.focused = focused;
+            }
+        }
+    }
+}
+
+/// System that sends key presses to the focused dropdown, firing its `change` binding when they
+/// change its selection
+pub fn dropdown_keyboard_system(
+    mut key_events: EventReader<KeyboardInput>,
+    time: Option<Res<bevy_time::Time>>,
+    mut dropdowns: Query<(Entity, &mut WidgetDropdown, Option<&WidgetActionBindings>)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    let keys: Vec<Key> = key_events.read()
+        .filter(|event| event.state.is_pressed())
+        .map(|event| event.logical_key.clone())
+        .collect();
+    if keys.is_empty() {
+        return;
+    }
+    let now = time.map_or(0.0, |time| time.elapsed_secs_f64());
+
+    for (entity, mut dropdown, bindings) in dropdowns.iter_mut() {
+        if !dropdown.focused {
+            continue;
+        }
+        for key in &keys {
+            let Some(value) = dropdown.press_key(key, now) else {
+                continue;
+            };
+            bevy_log::debug!("Dropdown {:?} changed to '{}'", entity, value);
+            if let Some(action) = change_action(bindings, entity, serde_json::Value::String(value)) {
+                action_events.send(action);
+            }
+        }
+    }
+}
+
+/// Node of a part of a dropdown's option list
+fn list_node(id: String, widget_type: WidgetType, size: Option<Vec2>, style: StyleConfig, clickable: bool) -> WidgetNode {
+    WidgetNode {
+        id: Some(id),
+        widget_type,
+        layout: LayoutConfig { size, ..Default::default() },
+        style,
+        behavior: BehaviorConfig {
+            clickable: Some(clickable),
+            interactive: Some(clickable),
+            z_index: Some(1),
+            position_control: Some(PositionControl::Manual),
+            ..Default::default()
+        },
+        classes: None,
+        style_overrides: None,
+        bindings: None,
+        state_bindings: None,
+        repeat: None,
+        when: None,
+        localized: None,
+        children: vec![],
+    }
+}
+
+/// Spawn the option list of a dropdown: a popup under it with a row per option
+#[allow(clippy::too_many_arguments)]
+fn spawn_list(
+    commands: &mut Commands,
+    yrs_res: &YrsDocResource,
+    template: &DropdownTemplate,
+    dropdown_entity: Entity,
+    dropdown: &WidgetDropdown,
+    id: &str,
+    width: f32,
+    window_height: f32,
+) -> Entity {
+    let popup_type = WidgetType::Popup { anchor: None, placement: PopupPlacement::Below, gap: Some(0.0), open: true };
+    let popup_style = StyleConfig { background_color: Some(template.list_color.clone()), ..Default::default() };
+    let popup_node = list_node(format!("{}_list", id), popup_type.clone(), None, popup_style, false);
+    let list = spawn_widget_entity_from_node(commands, &popup_node, yrs_res, window_height, None, None);
+
+    let row_size = Vec2::new(width, template.row_height);
+    let mut rows = Vec::new();
+    for (index, option) in dropdown.options.iter().enumerate() {
+        let row_id = format!("{}_option_{}", id, index);
+        let row_style = StyleConfig { background_color: Some(template.list_color.clone()), ..Default::default() };
+        let row_node = list_node(row_id.clone(), WidgetType::Shape { shape_type: ShapeType::Rectangle }, Some(row_size), row_style, true);
+        let row = spawn_widget_entity_from_node(commands, &row_node, yrs_res, window_height, None, None);
+
+        let label_style = StyleConfig {
+            text_color: Some(template.text_color.clone()),
+            text_size: Some(template.text_size),
+            ..Default::default()
+        };
+        let label_type = WidgetType::Text { content: option.label().to_string(), editable: false };
+        let label_node = list_node(format!("{}_label", row_id), label_type, None, label_style, false);
+        let label = spawn_widget_entity_from_node(commands, &label_node, yrs_res, window_height, None, None);
+        // Text is drawn down from its top-left, so this centers the label on its row
+        let label_position = Transform::from_xyz(-width / 2.0 + template.padding, template.text_size * 0.6, 0.1);
+        commands.entity(label).insert((label_position, WidgetHierarchy { parent: Some(row), children: vec![] })).set_parent(row);
+
+        commands.entity(row).insert((DropdownRow { dropdown: dropdown_entity, index }, WidgetHierarchy { parent: Some(list), children: vec![label] }));
+        rows.push(row);
+    }
+
+    // Placed under the dropdown, its parent, by popup_layout_system
+    let popup = WidgetPopup::from_widget_type(&popup_type).expect("popup widget type");
+    commands.entity(list).insert((popup, WidgetHierarchy { parent: Some(dropdown_entity), children: rows }));
+    list
+}
+
+/// System that spawns the option list of dropdowns that open, despawns it when they close, and
+/// shows the keyboard highlight. A list dismissed by a press outside it closes its dropdown.
+#[allow(clippy::too_many_arguments)]
+pub fn dropdown_list_system(
+    mut commands: Commands,
+    yrs_res: Res<YrsDocResource>,
+    taffy_resource: Res<TaffyResource>,
+    mut dropdowns: Query<(Entity, &mut WidgetDropdown, &Widget, &WidgetHierarchy, Option<&WidgetSourceNode>)>,
+    lists: Query<(&WidgetBehavior, &WidgetHierarchy), With<WidgetPopup>>,
+    mut rows: Query<(&DropdownRow, &mut ShapeData)>,
+    hierarchies: Query<&WidgetHierarchy>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+    widget_windows: WidgetWindows,
+) {
+    let template = &get_widget_templates().dropdown;
+    for (entity, mut dropdown, widget, hierarchy, source) in dropdowns.iter_mut() {
+        // Lists pressed away from, or despawned by a reload, close their dropdown
+        if let Some(list) = dropdown.list {
+            match lists.get(list) {
+                Ok((behavior, _)) if behavior.visible => {}
+                Ok(_) => dropdown.open = false,
+                Err(_) => {
+                    dropdown.list = None;
+                    dropdown.open = false;
+                }
+            }
+        }
+
+        match (dropdown.open, dropdown.list) {
+            (true, None) => {
+                let id = source.and_then(|source| source.node.id.clone()).unwrap_or_else(|| widget.id.clone());
+                let width = widget.blueprint.layout.size.unwrap_or(template.size).x;
+                let list = spawn_list(&mut commands, &yrs_res, template, entity, &dropdown, &id, width, widget_windows.height_of(entity));
+                dropdown.list = Some(list);
+                let mut children = hierarchy.children.clone();
+                children.push(list);
+                commands.entity(entity).insert(WidgetHierarchy { parent: hierarchy.parent, children });
+            }
+            (false, Some(list)) => {
+                let mut stale = HashSet::new();
+                collect_subtree(list, &hierarchies, &mut stale);
+                despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+                dropdown.list = None;
+                let children = hierarchy.children.iter().copied().filter(|child| *child != list).collect();
+                commands.entity(entity).insert(WidgetHierarchy { parent: hierarchy.parent, children });
+            }
+            (true, Some(list)) => {
+                let highlight = template.highlight_color.to_color();
+                let background = template.list_color.to_color();
+                for &row in lists.get(list).map(|(_, list)| list.children.as_slice()).unwrap_or_default() {
+                    let Ok((row, mut shape)) = rows.get_mut(row) else {
+                        continue;
+                    };
+                    let color = if dropdown.highlighted == Some(row.index) { highlight } else { background };
+                    if shape.color != color {
+                        shape.color = color;
+                    }
+                }
+            }
+            (false, None) => {}
+        }
+    }
+}
+
+/// System that shows the selected option's label in the box of dropdowns, or their placeholder
+/// in the placeholder color
+pub fn dropdown_label_system(
+    yrs_res: Res<YrsDocResource>,
+    dropdowns: Query<(&WidgetDropdown, &WidgetHierarchy), Changed<WidgetDropdown>>,
+    mut labels: Query<(&mut Widget, Option<&mut WidgetText>, &WidgetStyle, &mut Text)>,
+    mut text_events: EventWriter<YrsTextChanged>,
+) {
+    let template = &get_widget_templates().dropdown;
+    for (dropdown, hierarchy) in dropdowns.iter() {
+        // The label is the dropdown's first part
+        let Some(&label) = hierarchy.children.first() else {
+            continue;
+        };
+        let Ok((mut widget, mut widget_text, style, mut text)) = labels.get_mut(label) else {
+            continue;
+        };
+        let content = dropdown.shown_label();
+        let changed = !matches!(&widget.blueprint.widget_type, WidgetType::Text { content: current, .. } if current == content);
+        if changed && set_static_text(&yrs_res, label, &mut widget, widget_text.as_deref_mut(), content) {
+            text_events.send(YrsTextChanged { entity: label });
+        }
+
+        let color = match dropdown.selected {
+            Some(_) => style.text_color.unwrap_or_else(|| template.text_color.to_color()),
+            None => template.placeholder_color.to_color(),
+        };
+        if text.color != color {
+            text.color = color;
+        }
+    }
+}
\ No newline at end of file
@@ -1,9 +1,27 @@
 pub mod blueprint;
+pub mod color;
 pub mod components;
+pub mod dropdown;
+pub mod numeric;
+pub mod popup;
+pub mod scroll_view;
 pub mod systems;
+pub mod tabs;
 pub mod templates;
+pub mod text_input;
+pub mod toggle;
+pub mod virtual_list;
 
 pub use blueprint::*;
+pub use color::*;
 pub use components::*;
+pub use dropdown::*;
+pub use numeric::*;
+pub use popup::*;
+pub use scroll_view::*;
 pub use systems::*;
-pub use templates::*;
\ No newline at end of file
+pub use tabs::*;
+pub use templates::*;
+pub use text_input::*;
+pub use toggle::*;
+pub use virtual_list::*;
\ No newline at end of file
@@ -0,0 +1,598 @@
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_transform::prelude::Transform;
+use serde_json::Value;
+use thiserror::Error;
+use yrs::{GetString, Transact};
+use crate::assets::reload::set_static_text;
+use crate::gui_framework::events::{ActionEvent, EntityClicked, EntityDragged, TextFocusChanged, YrsTextChanged};
+use crate::gui_framework::systems::change_action;
+use crate::widgets::blueprint::{SliderOrientation, WidgetType};
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetHierarchy, WidgetText};
+use crate::YrsDocResource;
+
+/// Pixels a drag value is dragged across to go from its minimum to its maximum
+pub const DRAG_VALUE_RANGE_PIXELS: f32 = 300.0;
+
+/// Decimals shown for values without a `step` or a format that sets them
+const DEFAULT_DECIMALS: usize = 2;
+
+/// Most decimals derived from a `step`
+const MAX_STEP_DECIMALS: usize = 6;
+
+/// Errors in the `min`, `max` and `step` of a slider or drag value
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum NumericRangeError {
+    #[error("min ({min}) must be less than max ({max})")]
+    EmptyRange { min: f64, max: f64 },
+    #[error("step must be positive, found {0}")]
+    InvalidStep(f64),
+    #[error("A logarithmic range needs a positive min, found {0}")]
+    NonPositiveLogarithmicMin(f64),
+    #[error("value {value} is outside the range {min}..={max}")]
+    ValueOutOfRange { value: f64, min: f64, max: f64 },
+}
+
+/// Errors in the `format` of a slider or drag value
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ValueFormatError {
+    #[error("Format '{0}' has no {{}} placeholder for the value")]
+    MissingPlaceholder(String),
+    #[error("Unsupported placeholder '{{{placeholder}}}' in format '{format}'. Expected {{}} or {{:.N}}")]
+    UnsupportedPlaceholder { format: String, placeholder: String },
+    #[error("Format '{0}' may only contain one placeholder")]
+    ExtraBraces(String),
+}
+
+/// Events a slider or drag value fires with its new value as the `value` parameter
+pub const NUMERIC_VALUE_EVENTS: &[&str] = &["change"];
+
+/// Problems with a `Slider` or `DragValue` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum NumericWidgetError {
+    #[error(transparent)]
+    Range(#[from] NumericRangeError),
+    #[error(transparent)]
+    Format(#[from] ValueFormatError),
+}
+
+impl NumericWidgetError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            NumericWidgetError::Range(NumericRangeError::EmptyRange { .. }) => "max",
+            NumericWidgetError::Range(NumericRangeError::InvalidStep(_)) => "step",
+            NumericWidgetError::Range(NumericRangeError::NonPositiveLogarithmicMin(_)) => "min",
+            NumericWidgetError::Range(NumericRangeError::ValueOutOfRange { .. }) => "value",
+            NumericWidgetError::Format(_) => "format",
+        }
+    }
+}
+
+/// Check the range, value and format of a `Slider` or `DragValue`; other widget types pass
+pub fn validate_numeric_widget(widget_type: &WidgetType) -> Result<(), NumericWidgetError> {
+    let (value, min, max, step, logarithmic, format) = match widget_type {
+        WidgetType::Slider { value, min, max, step, logarithmic, format, .. }
+        | WidgetType::DragValue { value, min, max, step, logarithmic, format, .. } => (value, min, max, step, logarithmic, format),
+        _ => return Ok(()),
+    };
+    NumericRange { min: *min, max: *max, step: *step, logarithmic: *logarithmic }.validate_value(*value)?;
+    if let Some(format) = format {
+        ValueFormat::parse(format)?;
+    }
+    Ok(())
+}
+
+/// Values a slider or drag value can take
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericRange {
+    pub min: f64,
+    pub max: f64,
+    pub step: Option<f64>,
+    pub logarithmic: bool,
+}
+
+impl NumericRange {
+    /// Check that the range is usable. Validation uses this too, so anything it accepts works
+    /// at runtime.
+    pub fn validate(&self) -> Result<(), NumericRangeError> {
+        if !self.min.is_finite() || !self.max.is_finite() || self.min >= self.max {
+            return Err(NumericRangeError::EmptyRange { min: self.min, max: self.max });
+        }
+        if let Some(step) = self.step.filter(|step| !step.is_finite() || *step <= 0.0) {
+            return Err(NumericRangeError::InvalidStep(step));
+        }
+        if self.logarithmic && self.min <= 0.0 {
+            return Err(NumericRangeError::NonPositiveLogarithmicMin(self.min));
+        }
+        Ok(())
+    }
+
+    /// Check that `value` lies in a usable range
+    pub fn validate_value(&self, value: f64) -> Result<(), NumericRangeError> {
+        self.validate()?;
+        if !(self.min..=self.max).contains(&value) {
+            return Err(NumericRangeError::ValueOutOfRange { value, min: self.min, max: self.max });
+        }
+        Ok(())
+    }
+
+    /// Invalid logarithmic ranges are reported by validation and treated as linear
+    fn is_logarithmic(&self) -> bool {
+        self.logarithmic && self.min > 0.0
+    }
+
+    pub fn clamp(&self, value: f64) -> f64 {
+        if self.min <= self.max {
+            value.clamp(self.min, self.max)
+        } else {
+            value
+        }
+    }
+
+    /// `value` clamped into the range and snapped to the nearest step
+    pub fn snap(&self, value: f64) -> f64 {
+        let value = self.clamp(value);
+        match self.step.filter(|step| *step > 0.0) {
+            Some(step) => self.clamp(self.min + ((value - self.min) / step).round() * step),
+            None => value,
+        }
+    }
+
+    /// How far along the range `value` lies, from 0.0 at `min` to 1.0 at `max`
+    pub fn fraction(&self, value: f64) -> f64 {
+        let value = self.clamp(value);
+        let fraction = if self.is_logarithmic() {
+            (value.ln() - self.min.ln()) / (self.max.ln() - self.min.ln())
+        } else {
+            (value - self.min) / (self.max - self.min)
+        };
+        if fraction.is_finite() { fraction.clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    /// The snapped value `fraction` of the way along the range
+    pub fn value_at(&self, fraction: f64) -> f64 {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let value = if self.is_logarithmic() {
+            (self.min.ln() + fraction * (self.max.ln() - self.min.ln())).exp()
+        } else {
+            self.min + fraction * (self.max - self.min)
+        };
+        self.snap(value)
+    }
+
+    /// Whether every value in the range is a whole number
+    pub fn is_integral(&self) -> bool {
+        self.step.is_some_and(|step| step.fract() == 0.0) && self.min.fract() == 0.0
+    }
+
+    /// `value` as sent in `change` actions; whole-number ranges send integers, so that they can
+    /// be stored in `Integer` state values
+    pub fn to_json(&self, value: f64) -> Value {
+        if self.is_integral() {
+            Value::from(value.round() as i64)
+        } else {
+            Value::from(value)
+        }
+    }
+
+    /// Decimals needed to show every step
+    fn step_decimals(&self) -> usize {
+        match self.step {
+            Some(step) => step.to_string().split_once('.')
+                .map_or(0, |(_, decimals)| decimals.len())
+                .min(MAX_STEP_DECIMALS),
+            None => DEFAULT_DECIMALS,
+        }
+    }
+}
+
+/// Parsed `format` of a slider or drag value: text around a `{}` or `{:.N}` placeholder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValueFormat {
+    pub prefix: String,
+    /// Decimals set by a `{:.N}` placeholder
+    pub decimals: Option<usize>,
+    pub suffix: String,
+}
+
+impl ValueFormat {
+    pub fn parse(format: &str) -> Result<Self, ValueFormatError> {
+        let missing = || ValueFormatError::MissingPlaceholder(format.to_string());
+        let (prefix, rest) = format.split_once('{').ok_or_else(missing)?;
+        let (placeholder, suffix) = rest.split_once('}').ok_or_else(missing)?;
+        if [prefix, suffix].iter().any(|text| text.contains(['{', '}'])) {
+            return Err(ValueFormatError::ExtraBraces(format.to_string()));
+        }
+        let decimals = match placeholder {
+            "" => None,
+            spec => Some(spec.strip_prefix(":.").and_then(|digits| digits.parse().ok()).ok_or_else(|| {
+                ValueFormatError::UnsupportedPlaceholder { format: format.to_string(), placeholder: spec.to_string() }
+            })?),
+        };
+        Ok(Self { prefix: prefix.to_string(), decimals, suffix: suffix.to_string() })
+    }
+
+    pub fn format(&self, value: f64, range: &NumericRange) -> String {
+        let decimals = self.decimals.unwrap_or_else(|| range.step_decimals());
+        format!("{}{:.*}{}", self.prefix, decimals, value, self.suffix)
+    }
+
+    /// Read a typed value, with or without the format's prefix and suffix
+    pub fn parse_value(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let text = text.strip_prefix(self.prefix.trim()).unwrap_or(text);
+        let text = text.strip_suffix(self.suffix.trim()).unwrap_or(text);
+        text.trim().parse::<f64>().ok().filter(|value| value.is_finite())
+    }
+}
+
+/// Which widget a [`WidgetNumeric`] was spawned for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericKind {
+    Slider(SliderOrientation),
+    DragValue,
+}
+
+/// Component on the track of a `Slider` or the box of a `DragValue` holding its value
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WidgetNumeric {
+    pub kind: NumericKind,
+    pub value: f64,
+    pub range: NumericRange,
+    /// How the value is shown; sliders without one show no value
+    pub format: Option<ValueFormat>,
+    pub disabled: bool,
+    /// Unsnapped position along the range during a drag, so that drags shorter than a step
+    /// still add up
+    pub drag_fraction: f64,
+}
+
+impl WidgetNumeric {
+    /// The value and range of a `Slider` or `DragValue` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let (kind, value, min, max, step, logarithmic, format, disabled) = match widget_type {
+            WidgetType::Slider { value, min, max, step, logarithmic, orientation, format, disabled } => {
+                (NumericKind::Slider(*orientation), value, min, max, step, logarithmic, format, disabled)
+            }
+            WidgetType::DragValue { value, min, max, step, logarithmic, format, disabled } => {
+                (NumericKind::DragValue, value, min, max, step, logarithmic, format, disabled)
+            }
+            _ => return None,
+        };
+        let range = NumericRange { min: *min, max: *max, step: *step, logarithmic: *logarithmic };
+        let value = range.snap(*value);
+        Some(Self {
+            kind,
+            value,
+            range,
+            // Invalid formats are reported by validation; the value is shown plainly instead
+            format: format.as_deref().map(|format| ValueFormat::parse(format).unwrap_or_default()),
+            disabled: *disabled,
+            drag_fraction: range.fraction(value),
+        })
+    }
+
+    /// Whether the widget shows its value as text
+    pub fn shows_value(&self) -> bool {
+        self.kind == NumericKind::DragValue || self.format.is_some()
+    }
+
+    /// The value as the widget shows it
+    pub fn display_text(&self) -> String {
+        match self.format {
+            Some(ref format) => format.format(self.value, &self.range),
+            None => ValueFormat::default().format(self.value, &self.range),
+        }
+    }
+}
+
+/// Offset of a slider's thumb from the center of a track of `track_size`, `fraction` of the way
+/// along it
+pub fn thumb_offset(track_size: Vec2, orientation: SliderOrientation, fraction: f64) -> Vec2 {
+    let (length, thickness) = match orientation {
+        SliderOrientation::Horizontal => (track_size.x, track_size.y),
+        SliderOrientation::Vertical => (track_size.y, track_size.x),
+    };
+    let along = (fraction as f32 - 0.5) * (length - thickness).max(0.0);
+    match orientation {
+        SliderOrientation::Horizontal => Vec2::new(along, 0.0),
+        SliderOrientation::Vertical => Vec2::new(0.0, along),
+    }
+}
+
+/// System that turns drags on sliders and drag values into new values and fires their `change`
+/// binding with them
+pub fn numeric_drag_system(
+    mut click_events: EventReader<EntityClicked>,
+    mut drag_events: EventReader<EntityDragged>,
+    mut numerics: Query<(&mut WidgetNumeric, &Widget, Option<&WidgetActionBindings>)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    // A press starts a drag from the current value
+    for click_event in click_events.read() {
+        if let Ok((mut numeric, _, _)) = numerics.get_mut(click_event.entity) {
+            let numeric = numeric.bypass_change_detection();
+            numeric.drag_fraction = numeric.range.fraction(numeric.value);
+        }
+    }
+
+    for drag_event in drag_events.read() {
+        let Ok((mut numeric, widget, bindings)) = numerics.get_mut(drag_event.entity) else {
+            continue;
+        };
+        if numeric.disabled {
+            continue;
+        }
+        let size = widget.blueprint.layout.size.unwrap_or(Vec2::ZERO);
+        let (moved, travel) = match numeric.kind {
+            NumericKind::Slider(SliderOrientation::Horizontal) => (drag_event.delta.x, size.x - size.y),
+            NumericKind::Slider(SliderOrientation::Vertical) => (drag_event.delta.y, size.y - size.x),
+            NumericKind::DragValue => (drag_event.delta.x, DRAG_VALUE_RANGE_PIXELS),
+        };
+        if travel <= 0.0 {
+            continue;
+        }
+
+        let drag_fraction = (numeric.drag_fraction + (moved / travel) as f64).clamp(0.0, 1.0);
+        let value = numeric.range.value_at(drag_fraction);
+        numeric.bypass_change_detection().drag_fraction = drag_fraction;
+        if value == numeric.value {
+            continue;
+        }
+        numeric.value = value;
+        bevy_log::debug!("Numeric widget {:?} is now {}", drag_event.entity, value);
+
+        if let Some(action) = change_action(bindings, drag_event.entity, numeric.range.to_json(value)) {
+            action_events.send(action);
+        }
+    }
+}
+
+/// System that applies values typed into drag values once their text loses focus
+pub fn drag_value_text_system(
+    mut focus_events: EventReader<TextFocusChanged>,
+    mut editing: Local<Option<Entity>>,
+    labels: Query<&WidgetHierarchy, Without<WidgetNumeric>>,
+    mut numerics: Query<(&mut WidgetNumeric, Option<&WidgetActionBindings>)>,
+    yrs_res: Res<YrsDocResource>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    let drag_value_of = |label: Entity| {
+        labels.get(label).ok()?.parent
+    };
+
+    for focus_event in focus_events.read() {
+        let left = editing.take().filter(|label| focus_event.entity != Some(*label));
+        if let Some((label, entity)) = left.and_then(|label| Some((label, drag_value_of(label)?))) {
+            let Ok((mut numeric, bindings)) = numerics.get_mut(entity) else {
+                continue;
+            };
+            let typed = yrs_res.text_map.lock().ok().and_then(|text_map| {
+                let text_ref = text_map.get(&label)?;
+                Some(text_ref.get_string(&yrs_res.doc.transact()))
+            });
+            let parsed = typed.as_deref().and_then(|typed| match numeric.format {
+                Some(ref format) => format.parse_value(typed),
+                None => ValueFormat::default().parse_value(typed),
+            });
+            let value = parsed.filter(|_| !numeric.disabled).map(|value| numeric.range.snap(value));
+
+            // Whatever was typed, the field goes back to showing the formatted value
+            numeric.set_changed();
+            if let Some(value) = value.filter(|value| *value != numeric.value) {
+                numeric.value = value;
+                if let Some(action) = change_action(bindings, entity, numeric.range.to_json(value)) {
+                    action_events.send(action);
+                }
+            }
+        }
+
+        *editing = focus_event.entity.filter(|label| {
+            drag_value_of(*label).and_then(|entity| numerics.get(entity).ok())
+                .is_some_and(|(numeric, _)| numeric.kind == NumericKind::DragValue)
+        });
+    }
+}
+
+/// System that moves slider thumbs to their values
+pub fn slider_thumb_system(
+    numerics: Query<(&WidgetNumeric, &Widget, &WidgetHierarchy), Changed<WidgetNumeric>>,
+    mut parts: Query<(&Widget, &mut Transform), Without<WidgetNumeric>>,
+) {
+    for (numeric, widget, hierarchy) in numerics.iter() {
+        let NumericKind::Slider(orientation) = numeric.kind else {
+            continue;
+        };
+        let Some(track_size) = widget.blueprint.layout.size else {
+            continue;
+        };
+        let offset = thumb_offset(track_size, orientation, numeric.range.fraction(numeric.value));
+        // The thumb is the slider's only shape part; the other one is its value
+        for &child in &hierarchy.children {
+            if let Ok((part, mut transform)) = parts.get_mut(child) {
+                if matches!(part.blueprint.widget_type, WidgetType::Shape { .. }) {
+                    match orientation {
+                        SliderOrientation::Horizontal => transform.translation.x = offset.x,
+                        SliderOrientation::Vertical => transform.translation.y = offset.y,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System that shows the values of sliders and drag values in their text
+pub fn numeric_label_system(
+    numerics: Query<(&WidgetNumeric, &WidgetHierarchy), Changed<WidgetNumeric>>,
+    mut labels: Query<(&mut Widget, Option<&mut WidgetText>), Without<WidgetNumeric>>,
+    yrs_res: Res<YrsDocResource>,
+    mut text_changed_writer: EventWriter<YrsTextChanged>,
+) {
+    for (numeric, hierarchy) in numerics.iter() {
+        if !numeric.shows_value() {
+            continue;
+        }
+        let content = numeric.display_text();
+        for &child in &hierarchy.children {
+            let Ok((mut widget, mut widget_text)) = labels.get_mut(child) else {
+                continue;
+            };
+            if set_static_text(&yrs_res, child, &mut widget, widget_text.as_deref_mut(), &content) {
+                text_changed_writer.send(YrsTextChanged { entity: child });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::{setup_world, spawn_child, drain_actions};
+    use crate::gui_framework::events::{EntityClicked, EntityDragged, TextFocusChanged};
+    use crate::widgets::blueprint::WidgetType;
+    use crate::widgets::components::{Widget, WidgetHierarchy};
+    use crate::widgets::templates::expand_template_node;
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::Vec2;
+    use bevy_transform::prelude::Transform;
+
+    const MIXER: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Row" }
+
+[[root.children]]
+id = "volume"
+widget_type = { type = "Slider", value = 20.0, min = 0.0, max = 100.0, step = 1.0, format = "{} %" }
+bindings = { change = { event = "change", action = "set_state", params = { path = "mixer.volume" } } }
+
+[[root.children]]
+id = "cutoff"
+widget_type = { type = "Slider", value = 1000.0, min = 20.0, max = 20000.0, logarithmic = true, orientation = "Vertical" }
+
+[[root.children]]
+id = "fade"
+widget_type = { type = "DragValue", value = 0.5, min = 0.0, max = 10.0, step = 0.25, format = "{:.2} s" }
+bindings = { change = { event = "change", action = "set_state", params = { path = "mixer.fade" } } }
+"##;
+
+    /// Test the value math, formatting and validation shared by sliders and drag values
+    #[test]
+    fn test_numeric_range_and_format() {
+        let linear = NumericRange { min: 0.0, max: 100.0, step: Some(5.0), logarithmic: false };
+        assert_eq!(linear.snap(42.0), 40.0);
+        assert_eq!(linear.snap(140.0), 100.0);
+        assert_eq!(linear.value_at(0.26), 25.0);
+        assert_eq!(linear.to_json(25.0), serde_json::json!(25), "Whole-number ranges send integers");
+
+        let log = NumericRange { min: 20.0, max: 20000.0, step: None, logarithmic: true };
+        assert!((log.fraction(632.455532) - 0.5).abs() < 1e-6, "The middle of a logarithmic range is the geometric mean");
+        assert!((log.value_at(1.0 / 3.0) - 200.0).abs() < 1e-6);
+        assert!(log.to_json(200.0).is_f64());
+
+        let format = ValueFormat::parse("{:.1} dB").unwrap();
+        assert_eq!(format.format(-3.04, &log), "-3.0 dB");
+        assert_eq!(format.parse_value(" -6.5 dB "), Some(-6.5));
+        assert_eq!(format.parse_value("12"), Some(12.0), "Typed values do not need the suffix");
+        assert_eq!(format.parse_value("loud"), None);
+        assert_eq!(ValueFormat::default().format(0.5, &NumericRange { step: Some(0.25), ..linear }), "0.50");
+        assert!(matches!(ValueFormat::parse("dB"), Err(ValueFormatError::MissingPlaceholder(_))));
+        assert!(matches!(ValueFormat::parse("{:x}"), Err(ValueFormatError::UnsupportedPlaceholder { .. })));
+
+        let invalid = r##"
+[root]
+widget_type = { type = "Container", direction = "Row" }
+
+[[root.children]]
+widget_type = { type = "Slider", min = 0.0, max = 10.0, logarithmic = true }
+
+[[root.children]]
+widget_type = { type = "DragValue", value = 3.0, min = 5.0, max = 1.0 }
+
+[[root.children]]
+widget_type = { type = "DragValue", min = 0.0, max = 1.0, format = "{:.x}" }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "root.children[0].widget_type.min",
+            "root.children[1].widget_type.max",
+            "root.children[2].widget_type.format",
+        ]);
+        assert!(UiDefinitionLoader.parse_definition(MIXER, UiDefinitionFormat::Toml).unwrap()
+            .validation_report_with_registry(&UiRegistry::new()).errors().next().is_none());
+    }
+
+    /// Test that sliders and drag values expand into a draggable root and their parts
+    #[test]
+    fn test_numeric_expansion() {
+        let ui_def = UiDefinitionLoader.parse_definition(MIXER, UiDefinitionFormat::Toml).unwrap();
+
+        let volume = expand_template_node(&ui_def.root.children[0]);
+        let ids: Vec<_> = volume.iter().map(|part| part.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["volume_track", "volume_thumb", "volume_label"]);
+        assert_eq!(volume[0].behavior.draggable, Some(true));
+        assert!(volume[0].bindings.as_ref().unwrap().contains_key("change"), "The track receives the drags");
+        assert!((volume[1].layout.position.unwrap().x + 42.0).abs() < 1e-3, "20% along 140 pixels of travel, from the middle");
+        assert!(matches!(&volume[2].widget_type, WidgetType::Text { content, editable: false } if content == "20 %"));
+
+        let cutoff = expand_template_node(&ui_def.root.children[1]);
+        assert_eq!(cutoff.len(), 2, "Sliders without a format show no value");
+        assert_eq!(cutoff[0].layout.size, Some(Vec2::new(20.0, 160.0)), "Vertical sliders stand upright");
+        let thumb = cutoff[1].layout.position.unwrap();
+        assert_eq!(thumb.x, 0.0);
+        assert!((thumb.y + 9.29).abs() < 0.01, "1 kHz sits a little above the middle of 20 Hz..20 kHz");
+
+        let fade = expand_template_node(&ui_def.root.children[2]);
+        let ids: Vec<_> = fade.iter().map(|part| part.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["fade_box", "fade_label"]);
+        assert!(matches!(&fade[1].widget_type, WidgetType::Text { content, editable: true } if content == "0.50 s"));
+    }
+
+    /// Test that drags and typed values change the value and fire the change binding with it
+    #[test]
+    fn test_numeric_input_fires_change() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(MIXER, UiDefinitionFormat::Toml).unwrap();
+        let volume = spawn_child(&mut world, &yrs_res, &ui_def, 0);
+        let fade = spawn_child(&mut world, &yrs_res, &ui_def, 2);
+        assert_eq!(world.get::<WidgetNumeric>(volume).unwrap().value, 20.0);
+
+        // Half of the track's 140 pixels of travel is half the range
+        world.send_event(EntityClicked { entity: volume });
+        world.send_event(EntityDragged { entity: volume, delta: Vec2::new(70.0, 12.0) });
+        world.run_system_once(numeric_drag_system).unwrap();
+        assert_eq!(world.get::<WidgetNumeric>(volume).unwrap().value, 70.0);
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].action.as_str(), actions[0].event_type.as_str()), ("set_state", "change"));
+        assert_eq!(actions[0].params.as_ref().unwrap()["value"], serde_json::json!(70));
+
+        world.run_system_once(slider_thumb_system).unwrap();
+        world.run_system_once(numeric_label_system).unwrap();
+        let children = world.get::<WidgetHierarchy>(volume).unwrap().children.clone();
+        assert!((world.get::<Transform>(children[0]).unwrap().translation.x - 28.0).abs() < 1e-3);
+        assert!(matches!(&world.get::<Widget>(children[1]).unwrap().blueprint.widget_type, WidgetType::Text { content, .. } if content == "70 %"));
+
+        // Typed values apply once the text loses focus, snapped to the step
+        let label = world.get::<WidgetHierarchy>(fade).unwrap().children[0];
+        crate::assets::reload::replace_widget_text(&yrs_res, label, "2.4 s");
+        world.send_event(TextFocusChanged { entity: Some(label) });
+        world.send_event(TextFocusChanged { entity: None });
+        world.run_system_once(drag_value_text_system).unwrap();
+        assert_eq!(world.get::<WidgetNumeric>(fade).unwrap().value, 2.5);
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions[0].get_string_param("path").as_deref(), Some("mixer.fade"));
+        assert_eq!(actions[0].params.as_ref().unwrap()["value"], serde_json::json!(2.5));
+
+        world.get_mut::<WidgetNumeric>(fade).unwrap().disabled = true;
+        world.resource_mut::<Events<EntityDragged>>().clear();
+        world.send_event(EntityDragged { entity: fade, delta: Vec2::new(30.0, 0.0) });
+        world.run_system_once(numeric_drag_system).unwrap();
+        assert_eq!(world.get::<WidgetNumeric>(fade).unwrap().value, 2.5, "Disabled widgets ignore drags");
+        assert!(drain_actions(&mut world).is_empty());
+    }
+}
\ No newline at end of file
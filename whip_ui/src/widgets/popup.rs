@@ -0,0 +1,632 @@
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Children, Parent};
+use bevy_math::{Rect, Vec2};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use crate::assets::definitions::WidgetNode;
+use crate::assets::windows::WidgetWindows;
+use crate::gui_framework::components::{OverlayLayer, ShapeData, TextLayoutOutput, Visibility};
+use crate::gui_framework::events::{ActionEvent, PointerPressed};
+use crate::gui_framework::systems::value_action;
+use crate::layout::PositionControl;
+use crate::widgets::blueprint::{PopupPlacement, ShapeType, WidgetType};
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetBehavior, WidgetHierarchy, WidgetLayout, WidgetSourceNode};
+use crate::widgets::scroll_view::{drawn_bounds, world_bounds};
+use crate::widgets::systems::create_shape_vertices;
+
+/// Space between a popup and its anchor when the popup does not set a `gap`
+pub const DEFAULT_POPUP_GAP: f32 = 4.0;
+
+/// Problems with a `Popup` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PopupError {
+    #[error("gap must be a non-negative number, found {0}")]
+    InvalidGap(f32),
+    #[error("anchor must name a widget")]
+    EmptyAnchor,
+}
+
+impl PopupError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            PopupError::InvalidGap(_) => "gap",
+            PopupError::EmptyAnchor => "anchor",
+        }
+    }
+}
+
+/// Events a popup fires with its visibility as the `value` parameter
+pub const POPUP_VALUE_EVENTS: &[&str] = &["dismiss"];
+
+/// Check the anchor and gap of a `Popup`; other widget types pass
+pub fn validate_popup(widget_type: &WidgetType) -> Result<(), PopupError> {
+    let WidgetType::Popup { anchor, gap, .. } = widget_type else {
+        return Ok(());
+    };
+    if anchor.as_ref().is_some_and(|anchor| anchor.trim().is_empty()) {
+        return Err(PopupError::EmptyAnchor);
+    }
+    if let Some(gap) = gap.filter(|gap| !gap.is_finite() || *gap < 0.0) {
+        return Err(PopupError::InvalidGap(gap));
+    }
+    Ok(())
+}
+
+impl PopupPlacement {
+    /// The side a popup flips to when there is no room on this one
+    pub fn opposite(self) -> Self {
+        match self {
+            PopupPlacement::Below => PopupPlacement::Above,
+            PopupPlacement::Above => PopupPlacement::Below,
+            PopupPlacement::Right => PopupPlacement::Left,
+            PopupPlacement::Left => PopupPlacement::Right,
+        }
+    }
+}
+
+/// Where a popup of `size` goes beside `anchor` in a window of `window` size, in world
+/// coordinates, and the side it ended up on.
+///
+/// The popup opens on `placement` when it fits there, flips to the opposite side when only that
+/// one fits, and otherwise takes whichever side has more room. It is then kept inside the window,
+/// favouring its top-left corner when it is larger than the window.
+pub fn place_popup(anchor: Rect, size: Vec2, window: Vec2, placement: PopupPlacement, gap: f32) -> (Rect, PopupPlacement) {
+    // Room beside the anchor, and the room the popup needs, on each side
+    let room = |side: PopupPlacement| match side {
+        PopupPlacement::Below => (anchor.min.y - gap, size.y),
+        PopupPlacement::Above => (window.y - anchor.max.y - gap, size.y),
+        PopupPlacement::Right => (window.x - anchor.max.x - gap, size.x),
+        PopupPlacement::Left => (anchor.min.x - gap, size.x),
+    };
+    let fits = |side: PopupPlacement| {
+        let (available, needed) = room(side);
+        available >= needed
+    };
+    let opposite = placement.opposite();
+    let side = if fits(placement) {
+        placement
+    } else if fits(opposite) || room(opposite).0 > room(placement).0 {
+        opposite
+    } else {
+        placement
+    };
+
+    let min = match side {
+        PopupPlacement::Below => Vec2::new(anchor.min.x, anchor.min.y - gap - size.y),
+        PopupPlacement::Above => Vec2::new(anchor.min.x, anchor.max.y + gap),
+        PopupPlacement::Right => Vec2::new(anchor.max.x + gap, anchor.max.y - size.y),
+        PopupPlacement::Left => Vec2::new(anchor.min.x - gap - size.x, anchor.max.y - size.y),
+    };
+    // World y grows upwards, so the top edge is kept in by clamping last
+    let min = Vec2::new(
+        min.x.min(window.x - size.x).max(0.0),
+        min.y.max(0.0).min(window.y - size.y),
+    );
+    (Rect::from_corners(min, min + size), side)
+}
+
+/// Component on a `Popup` widget holding its placement settings and where it was last put.
+///
+/// A popup is open while its `WidgetBehavior` is visible, so `behavior.visible` state bindings
+/// open and close it. It and everything inside it are in the overlay layer.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WidgetPopup {
+    /// Id of the widget to place the popup beside; the popup's parent when `None`
+    pub anchor: Option<String>,
+    pub placement: PopupPlacement,
+    pub gap: f32,
+    /// Side the popup was last placed on, which is the opposite of `placement` after a flip
+    pub placed: Option<PopupPlacement>,
+    /// Size around the children, measured by `popup_layout_system`
+    pub size: Vec2,
+    /// Whether the children were shown when `popup_overlay_system` last ran
+    pub shown: bool,
+}
+
+impl WidgetPopup {
+    /// The popup state of a `Popup` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let WidgetType::Popup { anchor, placement, gap, .. } = widget_type else {
+            return None;
+        };
+        Some(Self {
+            anchor: anchor.clone(),
+            placement: *placement,
+            // Invalid gaps are reported by validation and replaced by the default here
+            gap: gap.filter(|gap| gap.is_finite() && *gap >= 0.0).unwrap_or(DEFAULT_POPUP_GAP),
+            placed: None,
+            size: Vec2::ZERO,
+            shown: false,
+        })
+    }
+
+    /// The widget this popup is placed beside: the one whose authored id is `anchor`, or else
+    /// `parent`. `None` when the named widget does not exist.
+    pub fn anchor_of<'a>(
+        &self,
+        parent: Option<Entity>,
+        mut widgets: impl Iterator<Item = (Entity, &'a Widget, Option<&'a WidgetSourceNode>)>,
+    ) -> Option<Entity> {
+        let Some(ref anchor) = self.anchor else {
+            return parent;
+        };
+        // Template widgets are spawned under the ids of their parts, so the authored id comes first
+        widgets
+            .find(|(_, widget, source)| source.and_then(|source| source.node.id.as_ref()).unwrap_or(&widget.id) == anchor)
+            .map(|(entity, ..)| entity)
+    }
+}
+
+/// The node a `Popup` is spawned from: closed unless `open`, and taken out of layout together
+/// with everything inside it, since popups place themselves. `None` for other widget types.
+pub fn overlay_node(node: &WidgetNode) -> Option<WidgetNode> {
+    let WidgetType::Popup { open, .. } = node.widget_type else {
+        return None;
+    };
+    let mut popup = node.clone();
+    popup.behavior.visible = Some(open && node.behavior.visible.unwrap_or(true));
+    set_manual(&mut popup);
+    Some(popup)
+}
+
+/// Whether `node` places itself rather than following its parent: manually positioned widgets
+/// and popups are not made Bevy children of their parent
+pub(crate) fn positions_itself(node: &WidgetNode) -> bool {
+    matches!(node.widget_type, WidgetType::Popup { .. })
+        || node.behavior.position_control.as_ref().is_some_and(PositionControl::is_manual)
+}
+
+fn set_manual(node: &mut WidgetNode) {
+    node.behavior.position_control = Some(PositionControl::Manual);
+    for child in &mut node.children {
+        set_manual(child);
+    }
+}
+
+/// Everything inside `popup`: its widgets and the entities they carry, such as scrollbars
+fn popup_contents(popup: Entity, hierarchies: &Query<&WidgetHierarchy>, children_query: &Query<&Children>) -> Vec<Entity> {
+    let mut contents = HashSet::new();
+    let mut pending = vec![popup];
+    while let Some(entity) = pending.pop() {
+        let widget_children = hierarchies.get(entity).into_iter().flat_map(|hierarchy| hierarchy.children.iter());
+        let entity_children = children_query.get(entity).into_iter().flatten();
+        for &child in widget_children.chain(entity_children) {
+            if contents.insert(child) {
+                pending.push(child);
+            }
+        }
+    }
+    contents.into_iter().collect()
+}
+
+/// System that puts popups and their contents in the overlay layer, and hides the contents of
+/// closed popups. Contents are shown again as their own behavior says when the popup opens.
+pub fn popup_overlay_system(
+    mut commands: Commands,
+    mut popups: Query<(Entity, &mut WidgetPopup, &WidgetBehavior, Has<OverlayLayer>)>,
+    hierarchies: Query<&WidgetHierarchy>,
+    children_query: Query<&Children>,
+    mut contents: Query<(Option<&WidgetBehavior>, &mut Visibility, Has<OverlayLayer>), Without<WidgetPopup>>,
+) {
+    for (entity, mut popup, behavior, overlay) in popups.iter_mut() {
+        if !overlay {
+            commands.entity(entity).insert(OverlayLayer);
+        }
+        let open = behavior.visible;
+        let opened = open && !popup.shown;
+        for content in popup_contents(entity, &hierarchies, &children_query) {
+            // Nested popups open and close on their own
+            let Ok((content_behavior, mut visibility, overlay)) = contents.get_mut(content) else {
+                continue;
+            };
+            if !overlay {
+                commands.entity(content).insert(OverlayLayer);
+            }
+            let visible = match (open, content_behavior) {
+                (false, _) => false,
+                (true, Some(content_behavior)) if opened => content_behavior.visible,
+                // Entities without a behavior, such as scrollbars, are shown by their widget
+                _ => continue,
+            };
+            if visibility.0 != visible {
+                visibility.0 = visible;
+            }
+        }
+        if popup.shown != open {
+            popup.shown = open;
+        }
+    }
+}
+
+/// System that stacks the children of open popups in a column inside their padding, sizes the
+/// popup around them, and places it beside its anchor
+pub fn popup_layout_system(
+    mut commands: Commands,
+    mut popups: Query<(Entity, &mut WidgetPopup, &WidgetBehavior, &WidgetLayout, &WidgetHierarchy, Option<&mut ShapeData>, &mut Transform)>,
+    nested: Query<(), With<WidgetPopup>>,
+    parents: Query<&Parent>,
+    visibilities: Query<&Visibility>,
+    children_query: Query<&Children>,
+    drawn: Query<(&GlobalTransform, Option<&ShapeData>, Option<&TextLayoutOutput>), Without<WidgetPopup>>,
+    anchors: Query<(Entity, &Widget, Option<&WidgetSourceNode>, &GlobalTransform, &WidgetLayout, Option<&ShapeData>, Option<&TextLayoutOutput>), Without<WidgetPopup>>,
+    mut transforms: Query<&mut Transform, Without<WidgetPopup>>,
+    widget_windows: WidgetWindows,
+) {
+    for (entity, mut popup, behavior, layout, hierarchy, shape, mut transform) in popups.iter_mut() {
+        if !behavior.visible {
+            continue;
+        }
+
+        // Children move with the popup; nested popups place themselves
+        let children: Vec<Entity> = hierarchy.children.iter().copied().filter(|child| !nested.contains(*child)).collect();
+        for &child in &children {
+            if parents.get(child).map(Parent::get).ok() != Some(entity) {
+                commands.entity(entity).add_child(child);
+            }
+        }
+
+        // Each child's drawing, relative to the child, so it can be moved into place
+        let measured: Vec<(Entity, Rect)> = children.iter()
+            .filter(|child| visibilities.get(**child).map_or(true, Visibility::is_visible))
+            .filter_map(|&child| {
+                let bounds = drawn_bounds(child, &children_query, &drawn)?;
+                let origin = drawn.get(child).ok()?.0.translation().truncate();
+                Some((child, Rect::from_corners(bounds.min - origin, bounds.max - origin)))
+            })
+            .collect();
+
+        let (top, right, bottom, left) = layout.padding.unwrap_or((0.0, 0.0, 0.0, 0.0));
+        let content = measured.iter().fold(Vec2::ZERO, |content, (_, offset)| {
+            Vec2::new(content.x.max(offset.width()), content.y + offset.height())
+        });
+        let size = (content + Vec2::new(left + right, top + bottom)).max(layout.size.unwrap_or(Vec2::ZERO));
+
+        let mut cursor = 0.0;
+        for (child, offset) in &measured {
+            let corner = Vec2::new(-size.x / 2.0 + left, size.y / 2.0 - top - cursor);
+            let translation = corner - Vec2::new(offset.min.x, offset.max.y);
+            if let Ok(mut child_transform) = transforms.get_mut(*child) {
+                if child_transform.translation.truncate() != translation {
+                    child_transform.translation = translation.extend(child_transform.translation.z);
+                }
+            }
+            cursor += offset.height();
+        }
+
+        if popup.size != size {
+            popup.size = size;
+            if let Some(mut shape) = shape {
+                shape.vertices = Arc::new(create_shape_vertices(&ShapeType::Rectangle, size));
+                shape.original_vertices = None;
+            }
+        }
+
+        // Popups without an anchor stay where they were put
+        let Some(anchor) = popup.anchor_of(hierarchy.parent, anchors.iter().map(|(entity, widget, source, ..)| (entity, widget, source))) else {
+            continue;
+        };
+        let Ok((.., anchor_transform, anchor_layout, anchor_shape, anchor_text)) = anchors.get(anchor) else {
+            continue;
+        };
+        let anchor_rect = world_bounds(anchor_transform, anchor_shape, anchor_text)
+            .unwrap_or_else(|| Rect::from_center_size(anchor_transform.translation().truncate(), anchor_layout.computed_size));
+        let (rect, side) = place_popup(anchor_rect, size, widget_windows.size_of(entity), popup.placement, popup.gap);
+        if popup.placed != Some(side) {
+            popup.placed = Some(side);
+        }
+        if transform.translation.truncate() != rect.center() {
+            transform.translation = rect.center().extend(transform.translation.z);
+        }
+    }
+}
+
+/// Whether `entity` is `target` or inside it, following the widget tree and then Bevy's.
+/// With `authored`, only the target's own parts count: other widgets spawned from their own node
+/// inside it, such as the siblings of a popup anchored to its parent, do not.
+fn is_within(
+    entity: Entity,
+    target: Entity,
+    links: &Query<(Option<&WidgetHierarchy>, Option<&Parent>)>,
+    authored: Option<&Query<(), With<WidgetSourceNode>>>,
+) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if entity == target {
+            return true;
+        }
+        if authored.is_some_and(|authored| authored.contains(entity)) {
+            return false;
+        }
+        current = links.get(entity).ok().and_then(|(hierarchy, parent)| {
+            hierarchy.and_then(|hierarchy| hierarchy.parent).or(parent.map(Parent::get))
+        });
+    }
+    false
+}
+
+/// System that closes open popups when the pointer is pressed outside them and their anchor,
+/// firing their `dismiss` binding
+pub fn popup_dismiss_system(
+    mut pressed_events: EventReader<PointerPressed>,
+    mut popups: Query<(Entity, &WidgetPopup, &mut WidgetBehavior, &mut Visibility, Option<&WidgetActionBindings>)>,
+    widgets: Query<(Entity, &Widget, Option<&WidgetSourceNode>)>,
+    links: Query<(Option<&WidgetHierarchy>, Option<&Parent>)>,
+    authored: Query<(), With<WidgetSourceNode>>,
+    widget_windows: WidgetWindows,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for pressed_event in pressed_events.read() {
+        for (entity, popup, mut behavior, mut visibility, bindings) in popups.iter_mut() {
+            if !behavior.visible || widget_windows.window_of(entity).is_some_and(|window| window != pressed_event.window) {
+                continue;
+            }
+            if let Some(pressed) = pressed_event.entity {
+                let parent = links.get(entity).ok().and_then(|(hierarchy, _)| hierarchy?.parent);
+                let anchor = popup.anchor_of(parent, widgets.iter());
+                let on_anchor = anchor.is_some_and(|anchor| is_within(pressed, anchor, &links, Some(&authored)));
+                if on_anchor || is_within(pressed, entity, &links, None) {
+                    continue;
+                }
+            }
+
+            behavior.visible = false;
+            visibility.0 = false;
+            bevy_log::debug!("Popup {:?} dismissed", entity);
+            if let Some(action) = value_action(bindings, entity, "dismiss", serde_json::Value::Bool(false)) {
+                action_events.send(action);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::setup_world;
+    use crate::gui_framework::components::{OverlayLayer, Visibility};
+    use crate::gui_framework::events::{ActionEvent, EntityClicked, PointerPressed};
+    use crate::widgets::blueprint::{PopupPlacement, WidgetType};
+    use crate::widgets::components::{Widget, WidgetBehavior, WidgetHierarchy, WidgetText};
+    use crate::widgets::dropdown::*;
+    use crate::YrsDocResource;
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_input::keyboard::Key;
+    use bevy_math::{Rect, Vec2};
+
+    const MENU: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "menu_button"
+widget_type = { type = "Shape", shape_type = "Rectangle" }
+layout = { size = [80.0, 24.0] }
+behavior = { clickable = true }
+
+[[root.children]]
+id = "other"
+widget_type = { type = "Shape", shape_type = "Rectangle" }
+layout = { size = [80.0, 24.0] }
+behavior = { clickable = true }
+
+[[root.children]]
+id = "menu"
+widget_type = { type = "Popup", anchor = "menu_button", open = true }
+bindings = { dismiss = { event = "dismiss", action = "set_state", params = { path = "menu.open" } } }
+
+[[root.children.children]]
+id = "copy"
+widget_type = { type = "Text", content = "Copy", editable = false }
+"##;
+
+    const ORDER: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "fruit"
+widget_type = { type = "Dropdown", options = ["Apple", { value = "banana", label = "Banana" }, "Blueberry", "Cherry"], placeholder = "Pick a fruit" }
+bindings = { change = { event = "change", action = "set_state", params = { path = "order.fruit" } } }
+"##;
+
+    fn spawn(world: &mut World, yrs_res: &YrsDocResource, content: &str) -> Entity {
+        let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+        assert!(!ui_def.validation_report_with_registry(&UiRegistry::new()).has_errors());
+        let root = {
+            let mut commands = world.commands();
+            spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+        };
+        world.flush();
+        root
+    }
+
+    fn widget_named(world: &mut World, id: &str) -> Entity {
+        world.query::<(Entity, &Widget)>().iter(world)
+            .find(|(_, widget)| widget.id == id)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    fn press(world: &mut World, entity: Option<Entity>) {
+        world.send_event(PointerPressed { window: Entity::PLACEHOLDER, position: Vec2::ZERO, entity });
+    }
+
+    /// Test validation of popups and dropdowns, and where popups are placed beside their anchor
+    #[test]
+    fn test_popup_validation_and_placement() {
+        let invalid = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+widget_type = { type = "Popup", anchor = " ", gap = -2.0 }
+
+[[root.children]]
+widget_type = { type = "Dropdown", options = [] }
+
+[[root.children]]
+widget_type = { type = "Dropdown", options = ["a", "a"] }
+
+[[root.children]]
+widget_type = { type = "Dropdown", options = ["a", "b"], selected = "c" }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert!(paths.contains(&"root.children[0].widget_type.anchor"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[1].widget_type.options"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[2].widget_type.options"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[3].widget_type.selected"), "{:?}", paths);
+        assert_eq!(validate_popup(&WidgetType::Popup { anchor: None, placement: PopupPlacement::Below, gap: Some(-2.0), open: false }),
+            Err(PopupError::InvalidGap(-2.0)));
+
+        let window = Vec2::new(400.0, 300.0);
+        let size = Vec2::new(100.0, 80.0);
+        // Below a button near the top of the window
+        let anchor = Rect::new(50.0, 250.0, 130.0, 274.0);
+        let (rect, side) = place_popup(anchor, size, window, PopupPlacement::Below, 4.0);
+        assert_eq!(side, PopupPlacement::Below);
+        assert_eq!(rect, Rect::new(50.0, 166.0, 150.0, 246.0));
+
+        // A button near the bottom has no room below, so the popup flips above it
+        let anchor = Rect::new(50.0, 20.0, 130.0, 44.0);
+        let (rect, side) = place_popup(anchor, size, window, PopupPlacement::Below, 4.0);
+        assert_eq!(side, PopupPlacement::Above);
+        assert_eq!(rect.min, Vec2::new(50.0, 48.0));
+
+        // Near the right edge it is pushed back inside the window
+        let anchor = Rect::new(350.0, 150.0, 390.0, 174.0);
+        let (rect, side) = place_popup(anchor, size, window, PopupPlacement::Right, 4.0);
+        assert_eq!(side, PopupPlacement::Left);
+        assert_eq!(rect.max.x, 346.0);
+        let (rect, _) = place_popup(anchor, size, window, PopupPlacement::Below, 4.0);
+        assert_eq!(rect.max.x, 400.0);
+
+        // With no room on either side it stays on the side asked for, inside the window
+        let (rect, side) = place_popup(Rect::new(0.0, 100.0, 40.0, 200.0), Vec2::new(100.0, 150.0), window, PopupPlacement::Above, 0.0);
+        assert_eq!(side, PopupPlacement::Above);
+        assert_eq!(rect.max.y, 300.0);
+    }
+
+    /// Test that popups go in the overlay layer and close when pressed outside them and their anchor
+    #[test]
+    fn test_popup_overlay_and_dismiss() {
+        let (mut world, yrs_res) = setup_world();
+        spawn(&mut world, &yrs_res, MENU);
+        let menu = widget_named(&mut world, "menu");
+        let copy = widget_named(&mut world, "copy");
+        let other = widget_named(&mut world, "other");
+        let menu_button = widget_named(&mut world, "menu_button");
+        assert!(world.get::<WidgetPopup>(menu).is_some());
+        assert!(world.get::<WidgetBehavior>(menu).unwrap().visible);
+        assert!(world.get::<bevy_hierarchy::Parent>(menu).is_none(), "Popups place themselves");
+
+        world.run_system_once(popup_overlay_system).unwrap();
+        assert!(world.get::<OverlayLayer>(menu).is_some() && world.get::<OverlayLayer>(copy).is_some());
+        assert!(world.get::<Visibility>(copy).unwrap().is_visible());
+
+        // Presses on the popup and on its anchor keep it open
+        press(&mut world, Some(copy));
+        press(&mut world, Some(menu_button));
+        world.run_system_once(popup_dismiss_system).unwrap();
+        assert!(world.get::<WidgetBehavior>(menu).unwrap().visible);
+        assert!(world.resource::<Events<ActionEvent>>().is_empty());
+
+        press(&mut world, Some(other));
+        world.run_system_once(popup_dismiss_system).unwrap();
+        assert!(!world.get::<WidgetBehavior>(menu).unwrap().visible);
+        let actions: Vec<_> = world.resource_mut::<Events<ActionEvent>>().drain().collect();
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].action.as_str(), actions[0].event_type.as_str()), ("set_state", "dismiss"));
+        assert_eq!(actions[0].get_bool_param("value"), Some(false));
+
+        // Closed popups hide their contents, and show them again when they reopen
+        world.run_system_once(popup_overlay_system).unwrap();
+        assert!(!world.get::<Visibility>(copy).unwrap().is_visible());
+        world.get_mut::<WidgetBehavior>(menu).unwrap().visible = true;
+        world.run_system_once(popup_overlay_system).unwrap();
+        assert!(world.get::<Visibility>(copy).unwrap().is_visible());
+    }
+
+    /// Test keyboard navigation and type-ahead, closed and with the list open
+    #[test]
+    fn test_dropdown_keys_and_type_ahead() {
+        let ui_def = UiDefinitionLoader.parse_definition(ORDER, UiDefinitionFormat::Toml).unwrap();
+        let mut dropdown = WidgetDropdown::from_widget_type(&ui_def.root.children[0].widget_type).unwrap();
+        assert_eq!(dropdown.shown_label(), "Pick a fruit");
+
+        // Closed, keys change the selection right away
+        assert_eq!(dropdown.press_key(&Key::Character("b".into()), 0.0).as_deref(), Some("banana"));
+        assert_eq!(dropdown.shown_label(), "Banana");
+        assert_eq!(dropdown.press_key(&Key::Character("B".into()), 0.5).as_deref(), Some("Blueberry"), "Typing the same letter steps on");
+        assert_eq!(dropdown.press_key(&Key::Character("c".into()), 2.0).as_deref(), Some("Cherry"));
+        assert_eq!(dropdown.press_key(&Key::Character("b".into()), 3.5).as_deref(), Some("banana"));
+        assert_eq!(dropdown.press_key(&Key::Character("l".into()), 3.7).as_deref(), Some("Blueberry"), "Longer searches match the prefix");
+        assert_eq!(dropdown.press_key(&Key::Character("x".into()), 5.0), None);
+        assert_eq!(dropdown.press_key(&Key::Home, 5.0).as_deref(), Some("Apple"));
+        assert_eq!(dropdown.press_key(&Key::ArrowUp, 5.0), None, "The first option stays selected");
+
+        // Open, keys move the highlight and Enter picks it
+        dropdown.press_key(&Key::Enter, 6.0);
+        assert!(dropdown.open);
+        assert_eq!(dropdown.highlighted, Some(0));
+        assert_eq!(dropdown.press_key(&Key::End, 6.0), None);
+        assert_eq!(dropdown.press_key(&Key::ArrowUp, 6.0), None);
+        assert_eq!((dropdown.highlighted, dropdown.selected_value()), (Some(2), Some("Apple")));
+        assert_eq!(dropdown.press_key(&Key::Enter, 6.0).as_deref(), Some("Blueberry"));
+        assert!(!dropdown.open);
+
+        dropdown.press_key(&Key::Space, 7.0);
+        dropdown.press_key(&Key::ArrowDown, 7.0);
+        assert_eq!(dropdown.press_key(&Key::Escape, 7.0), None);
+        assert!(!dropdown.open);
+        assert_eq!(dropdown.selected_value(), Some("Blueberry"), "Escape keeps the selection");
+
+        dropdown.disabled = true;
+        assert_eq!(dropdown.press_key(&Key::ArrowDown, 8.0), None);
+    }
+
+    /// Test that clicking a dropdown opens its list, and picking a row fires `change` and closes it
+    #[test]
+    fn test_dropdown_list_fires_change() {
+        let (mut world, yrs_res) = setup_world();
+        spawn(&mut world, &yrs_res, ORDER);
+        let fruit = widget_named(&mut world, "fruit_box");
+        let label = world.get::<WidgetHierarchy>(fruit).unwrap().children[0];
+
+        world.send_event(EntityClicked { entity: fruit });
+        world.run_system_once(dropdown_click_system).unwrap();
+        assert!(world.get::<WidgetDropdown>(fruit).unwrap().open);
+        world.run_system_once(dropdown_list_system).unwrap();
+
+        let list = world.get::<WidgetDropdown>(fruit).unwrap().list.unwrap();
+        assert_eq!(world.get::<Widget>(list).unwrap().id, "fruit_list");
+        assert!(world.get::<WidgetHierarchy>(fruit).unwrap().children.contains(&list));
+        let rows = world.get::<WidgetHierarchy>(list).unwrap().children.clone();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(world.get::<DropdownRow>(rows[1]).unwrap(), &DropdownRow { dropdown: fruit, index: 1 });
+        let row_label = world.get::<WidgetHierarchy>(rows[1]).unwrap().children[0];
+        assert_eq!(world.get::<WidgetText>(row_label).unwrap().content, "Banana");
+
+        world.resource_mut::<Events<EntityClicked>>().clear();
+        world.send_event(EntityClicked { entity: rows[1] });
+        world.run_system_once(dropdown_click_system).unwrap();
+        let actions: Vec<_> = world.resource_mut::<Events<ActionEvent>>().drain().collect();
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].action.as_str(), actions[0].event_type.as_str()), ("set_state", "change"));
+        assert_eq!(actions[0].get_string_param("value").as_deref(), Some("banana"));
+
+        world.run_system_once(dropdown_list_system).unwrap();
+        assert!(world.get::<Widget>(list).is_none(), "Closed lists are despawned");
+        assert!(world.get::<Widget>(rows[1]).is_none());
+        assert_eq!(world.get::<WidgetDropdown>(fruit).unwrap().list, None);
+
+        world.run_system_once(dropdown_label_system).unwrap();
+        assert_eq!(world.get::<WidgetText>(label).unwrap().content, "Banana");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,748 @@
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryFilter;
+use bevy_hierarchy::{BuildChildren, Children, DespawnRecursiveExt, Parent};
+use bevy_math::{Rect, Vec2, Vec3};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use thiserror::Error;
+use crate::gui_framework::components::{ClipRect, Interaction, Scrollable, ShapeData, TextLayoutOutput, Visibility};
+use crate::gui_framework::events::{EntityDragged, EntityScrolled, TextFocusChanged};
+use crate::layout::{TaffyResource, UiNode};
+use crate::widgets::blueprint::{ScrollAxes, WidgetType};
+use crate::widgets::color::ColorParseError;
+use crate::widgets::components::{WidgetHierarchy, WidgetLayout};
+use crate::widgets::virtual_list::WidgetVirtualList;
+
+/// Scrollbar thickness of scroll views that do not set one
+pub const DEFAULT_SCROLLBAR_WIDTH: f32 = 8.0;
+
+/// Shortest a scrollbar thumb gets, however long the content
+pub const MIN_THUMB_LENGTH: f32 = 20.0;
+
+/// Height of the scrollbars above the scroll view's own shape
+const SCROLLBAR_Z: f32 = 0.5;
+
+/// Problems with a `ScrollView` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ScrollViewError {
+    #[error("scrollbar_width must be a non-negative number, found {0}")]
+    InvalidScrollbarWidth(f32),
+    #[error(transparent)]
+    InvalidScrollbarColor(#[from] ColorParseError),
+}
+
+impl ScrollViewError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            ScrollViewError::InvalidScrollbarWidth(_) => "scrollbar_width",
+            ScrollViewError::InvalidScrollbarColor(_) => "scrollbar_color",
+        }
+    }
+}
+
+/// Check the scrollbar settings of a `ScrollView` or `VirtualList`; other widget types pass
+pub fn validate_scroll_view(widget_type: &WidgetType) -> Result<(), ScrollViewError> {
+    let (WidgetType::ScrollView { scrollbar_width, scrollbar_color, .. } | WidgetType::VirtualList { scrollbar_width, scrollbar_color, .. }) = widget_type else {
+        return Ok(());
+    };
+    if let Some(width) = scrollbar_width.filter(|width| !width.is_finite() || *width < 0.0) {
+        return Err(ScrollViewError::InvalidScrollbarWidth(width));
+    }
+    if let Some(color) = scrollbar_color {
+        color.parse()?;
+    }
+    Ok(())
+}
+
+/// Event asking the scroll views around `entity` to scroll just far enough to show it.
+/// Text fields inside scroll views are scrolled into view when they gain focus.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScrollIntoView {
+    pub entity: Entity,
+}
+
+/// Direction a scrollbar runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// Component on the entity carrying a scroll view's children, which is moved to scroll them
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ScrollContent {
+    pub view: Entity,
+}
+
+/// Component on a scrollbar thumb, which scrolls its view when dragged
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ScrollThumb {
+    pub view: Entity,
+    pub axis: ScrollAxis,
+}
+
+/// Component on a `ScrollView` widget holding its settings and how far it is scrolled.
+///
+/// Content coordinates start at the top-left of the viewport when nothing is scrolled and grow
+/// right and down. Children positioned by layout are moved onto the content entity; manually
+/// positioned children neither scroll nor clip.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetScrollView {
+    pub axes: ScrollAxes,
+    pub scrollbar_width: f32,
+    pub scrollbar_color: Color,
+    /// Top-left of the visible part of the content
+    pub offset: Vec2,
+    /// Size of the visible area, which is the scroll view's own size
+    pub viewport: Vec2,
+    /// Bottom-right corner of the children, measured by `scroll_view_measure_system`
+    pub content_size: Vec2,
+    /// Entity carrying the children; it and the thumbs are spawned by `scroll_view_setup_system`
+    pub content: Option<Entity>,
+    pub horizontal_thumb: Option<Entity>,
+    pub vertical_thumb: Option<Entity>,
+}
+
+impl WidgetScrollView {
+    /// The scroll state of a `ScrollView` or `VirtualList` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let (axes, scrollbar_width, scrollbar_color) = match widget_type {
+            WidgetType::ScrollView { axes, scrollbar_width, scrollbar_color, .. } => (*axes, scrollbar_width, scrollbar_color),
+            // Virtual lists scroll a column of rows
+            WidgetType::VirtualList { scrollbar_width, scrollbar_color, .. } => (ScrollAxes::Vertical, scrollbar_width, scrollbar_color),
+            _ => return None,
+        };
+        // Invalid settings are reported by validation and replaced by the defaults here
+        let scrollbar_color = scrollbar_color.as_ref().and_then(|color| match color.parse() {
+            Ok(color) => Some(color),
+            Err(e) => {
+                bevy_log::warn!("Ignoring invalid ScrollView scrollbar_color: {}", e);
+                None
+            }
+        });
+        Some(Self {
+            axes,
+            scrollbar_width: scrollbar_width.filter(|width| width.is_finite() && *width >= 0.0).unwrap_or(DEFAULT_SCROLLBAR_WIDTH),
+            scrollbar_color: scrollbar_color.unwrap_or(Color::srgba(0.0, 0.0, 0.0, 0.35)),
+            offset: Vec2::ZERO,
+            viewport: Vec2::ZERO,
+            content_size: Vec2::ZERO,
+            content: None,
+            horizontal_thumb: None,
+            vertical_thumb: None,
+        })
+    }
+
+    /// Furthest the content can be scrolled along each axis it scrolls in
+    pub fn max_offset(&self) -> Vec2 {
+        let max = (self.content_size - self.viewport).max(Vec2::ZERO);
+        Vec2::new(
+            if self.axes.horizontal() { max.x } else { 0.0 },
+            if self.axes.vertical() { max.y } else { 0.0 },
+        )
+    }
+
+    /// Scroll to `offset`, kept within the content; returns whether the offset changed
+    pub fn scroll_to(&mut self, offset: Vec2) -> bool {
+        let offset = offset.clamp(Vec2::ZERO, self.max_offset());
+        let changed = offset != self.offset;
+        self.offset = offset;
+        changed
+    }
+
+    /// Scroll the content so that `rect`, in content coordinates, is visible, moving as little as
+    /// possible. Rects larger than the viewport are aligned by their top-left.
+    pub fn reveal(&mut self, rect: Rect) -> bool {
+        let mut offset = self.offset;
+        for axis in 0..2 {
+            if rect.max[axis] > offset[axis] + self.viewport[axis] {
+                offset[axis] = rect.max[axis] - self.viewport[axis];
+            }
+            if rect.min[axis] < offset[axis] {
+                offset[axis] = rect.min[axis];
+            }
+        }
+        self.scroll_to(offset)
+    }
+
+    /// Whether `axis` has a scrollbar
+    pub fn has_scrollbar(&self, axis: ScrollAxis) -> bool {
+        let scrolls = match axis {
+            ScrollAxis::Horizontal => self.axes.horizontal(),
+            ScrollAxis::Vertical => self.axes.vertical(),
+        };
+        scrolls && self.scrollbar_width > 0.0
+    }
+
+    /// Length of the track the thumb of `axis` moves along; with both scrollbars the tracks
+    /// stop short of the corner they share
+    fn track_length(&self, axis: ScrollAxis) -> f32 {
+        let (length, other) = match axis {
+            ScrollAxis::Horizontal => (self.viewport.x, ScrollAxis::Vertical),
+            ScrollAxis::Vertical => (self.viewport.y, ScrollAxis::Horizontal),
+        };
+        let corner = if self.has_scrollbar(other) { self.scrollbar_width } else { 0.0 };
+        (length - corner).max(0.0)
+    }
+
+    /// Start along the track and length of the thumb of `axis`, or `None` when the content fits
+    /// and the thumb is hidden
+    pub fn thumb(&self, axis: ScrollAxis) -> Option<(f32, f32)> {
+        let index = match axis {
+            ScrollAxis::Horizontal => 0,
+            ScrollAxis::Vertical => 1,
+        };
+        let max_offset = self.max_offset()[index];
+        if max_offset <= 0.0 || !self.has_scrollbar(axis) {
+            return None;
+        }
+        let track = self.track_length(axis);
+        let length = (track * self.viewport[index] / self.content_size[index]).clamp(MIN_THUMB_LENGTH.min(track), track);
+        let start = (track - length) * self.offset[index] / max_offset;
+        Some((start, length))
+    }
+
+    /// Content pixels scrolled per pixel a thumb is dragged
+    fn thumb_ratio(&self, axis: ScrollAxis) -> f32 {
+        let index = match axis {
+            ScrollAxis::Horizontal => 0,
+            ScrollAxis::Vertical => 1,
+        };
+        match self.thumb(axis) {
+            Some((_, length)) if self.track_length(axis) > length => self.max_offset()[index] / (self.track_length(axis) - length),
+            _ => 0.0,
+        }
+    }
+
+    /// The content coordinates of a world rect, given the content entity's world position
+    pub fn to_content_rect(&self, content_position: Vec3, world: Rect) -> Rect {
+        content_rect(self.viewport, content_position, world)
+    }
+
+    /// World rect of the viewport for a scroll view at `position`
+    pub fn viewport_rect(&self, position: Vec3) -> Rect {
+        Rect::from_center_size(position.truncate(), self.viewport)
+    }
+}
+
+/// The content coordinates of a world rect in a viewport of size `viewport`. The content entity
+/// sits at the center of the viewport when nothing is scrolled.
+fn content_rect(viewport: Vec2, content_position: Vec3, world: Rect) -> Rect {
+    let origin = content_position.truncate() + Vec2::new(-viewport.x, viewport.y) / 2.0;
+    Rect::new(world.min.x - origin.x, origin.y - world.max.y, world.max.x - origin.x, origin.y - world.min.y)
+}
+
+/// World rect drawn by a shape or text, if it draws anything
+pub(crate) fn world_bounds(transform: &GlobalTransform, shape: Option<&ShapeData>, text: Option<&TextLayoutOutput>) -> Option<Rect> {
+    let shape_points = shape.into_iter().flat_map(|shape| shape.vertices.iter().map(|vertex| Vec2::from(vertex.position)));
+    // Glyph quads are relative to the text's transform, with y growing upwards
+    let text_points = text.into_iter().flat_map(|text| text.glyphs.iter().flat_map(|glyph| glyph.vertices));
+    let origin = transform.translation().truncate();
+    shape_points.chain(text_points)
+        .map(|point| Rect::from_center_size(origin + point, Vec2::ZERO))
+        .reduce(|bounds, point| bounds.union(point))
+}
+
+/// World rect drawn by `entity` and everything under it
+pub(crate) fn drawn_bounds<F: QueryFilter>(
+    entity: Entity,
+    children_query: &Query<&Children>,
+    drawn: &Query<(&GlobalTransform, Option<&ShapeData>, Option<&TextLayoutOutput>), F>,
+) -> Option<Rect> {
+    let mut bounds: Option<Rect> = None;
+    let mut pending = vec![entity];
+    while let Some(entity) = pending.pop() {
+        let entity_bounds = drawn.get(entity).ok().and_then(|(transform, shape, text)| world_bounds(transform, shape, text));
+        if let Some(entity_bounds) = entity_bounds {
+            bounds = Some(bounds.map_or(entity_bounds, |bounds| bounds.union(entity_bounds)));
+        }
+        pending.extend(children_query.get(entity).into_iter().flatten().copied());
+    }
+    bounds
+}
+
+/// System that spawns the content entity and scrollbar thumbs of new scroll views, and the
+/// thumbs a reloaded scroll view is missing
+pub fn scroll_view_setup_system(
+    mut commands: Commands,
+    mut views: Query<(Entity, &mut WidgetScrollView), Changed<WidgetScrollView>>,
+) {
+    for (view_entity, mut view) in views.iter_mut() {
+        if view.content.is_none() {
+            let content = commands.spawn((
+                ScrollContent { view: view_entity },
+                Transform::default(),
+                GlobalTransform::default(),
+            )).id();
+            commands.entity(view_entity).add_child(content);
+            view.content = Some(content);
+        }
+
+        for axis in [ScrollAxis::Horizontal, ScrollAxis::Vertical] {
+            let missing = match axis {
+                ScrollAxis::Horizontal => view.horizontal_thumb.is_none(),
+                ScrollAxis::Vertical => view.vertical_thumb.is_none(),
+            };
+            if !missing || !view.has_scrollbar(axis) {
+                continue;
+            }
+            // Sized and shown by scroll_view_apply_system once the content overflows
+            let thumb = commands.spawn((
+                ScrollThumb { view: view_entity, axis },
+                ShapeData::rectangle(view.scrollbar_width, view.scrollbar_width, view.scrollbar_color),
+                Transform::from_xyz(0.0, 0.0, SCROLLBAR_Z),
+                GlobalTransform::default(),
+                Visibility(false),
+                Interaction { clickable: false, draggable: true },
+            )).id();
+            commands.entity(view_entity).add_child(thumb);
+            match axis {
+                ScrollAxis::Horizontal => view.horizontal_thumb = Some(thumb),
+                ScrollAxis::Vertical => view.vertical_thumb = Some(thumb),
+            }
+        }
+    }
+}
+
+/// System that moves the layout-positioned children of scroll views onto their content entity,
+/// including children spawned later by `repeat` and `when` or put back by hot reload
+pub fn scroll_view_adopt_system(
+    mut commands: Commands,
+    views: Query<(&WidgetScrollView, &WidgetHierarchy, &Children)>,
+) {
+    for (view, hierarchy, children) in views.iter() {
+        let Some(content) = view.content else {
+            continue;
+        };
+        for &child in children.iter().filter(|child| hierarchy.children.contains(*child)) {
+            commands.entity(content).add_child(child);
+        }
+    }
+}
+
+/// System that measures the viewport and content of scroll views, keeping the offset within
+/// content that has shrunk. Virtual lists size their content from their rows instead.
+pub fn scroll_view_measure_system(
+    mut views: Query<(&mut WidgetScrollView, Option<&UiNode>, &WidgetLayout, Has<WidgetVirtualList>)>,
+    taffy_resource: Option<Res<TaffyResource>>,
+    contents: Query<&GlobalTransform, With<ScrollContent>>,
+    children_query: Query<&Children>,
+    drawn: Query<(&GlobalTransform, Option<&ShapeData>, Option<&TextLayoutOutput>, &Visibility), Without<ScrollContent>>,
+) {
+    for (mut view, ui_node, layout, virtual_list) in views.iter_mut() {
+        // Layout decides the size of the viewport when it has laid the scroll view out
+        let laid_out = ui_node.and_then(|ui_node| ui_node.taffy_node).zip(taffy_resource.as_ref()).and_then(|(node, taffy)| {
+            taffy.with_tree(|tree| tree.layout(node).ok().map(|layout| Vec2::new(layout.size.width, layout.size.height)))
+        });
+        let viewport = laid_out.filter(|size| size.x > 0.0 && size.y > 0.0).unwrap_or(layout.computed_size);
+
+        let Some(content_position) = view.content.and_then(|content| contents.get(content).ok()).map(GlobalTransform::translation) else {
+            continue;
+        };
+        // Virtual lists size their content from their rows, most of which are not spawned
+        let (mut content_size, mut pending) = match virtual_list {
+            true => (view.content_size, Vec::new()),
+            false => (Vec2::ZERO, view.content.iter().copied().collect::<Vec<Entity>>()),
+        };
+        while let Some(entity) = pending.pop() {
+            for &child in children_query.get(entity).into_iter().flatten() {
+                // The content of a nested scroll view only shows inside that view
+                let Ok((transform, shape, text, visibility)) = drawn.get(child) else {
+                    continue;
+                };
+                pending.push(child);
+                if !visibility.is_visible() {
+                    continue;
+                }
+                if let Some(bounds) = world_bounds(transform, shape, text) {
+                    content_size = content_size.max(content_rect(viewport, content_position, bounds).max);
+                }
+            }
+        }
+
+        if view.viewport != viewport || view.content_size != content_size {
+            view.viewport = viewport;
+            view.content_size = content_size;
+            let offset = view.offset;
+            view.scroll_to(offset);
+        }
+    }
+}
+
+/// System that scrolls scroll views by the mouse wheel, the trackpad and their dragged thumbs
+pub fn scroll_view_input_system(
+    mut scrolled_events: EventReader<EntityScrolled>,
+    mut dragged_events: EventReader<EntityDragged>,
+    thumbs: Query<&ScrollThumb>,
+    mut views: Query<&mut WidgetScrollView>,
+) {
+    for scrolled_event in scrolled_events.read() {
+        let Ok(mut view) = views.get_mut(scrolled_event.entity) else {
+            continue;
+        };
+        let mut delta = scrolled_event.delta;
+        // A plain mouse wheel scrolls views that only scroll sideways
+        if !view.axes.vertical() && delta.x == 0.0 {
+            delta = Vec2::new(delta.y, 0.0);
+        }
+        let offset = view.offset - delta;
+        if view.bypass_change_detection().scroll_to(offset) {
+            view.set_changed();
+        }
+    }
+
+    for dragged_event in dragged_events.read() {
+        let Ok(thumb) = thumbs.get(dragged_event.entity) else {
+            continue;
+        };
+        let Ok(mut view) = views.get_mut(thumb.view) else {
+            continue;
+        };
+        // Dragging a thumb down (towards negative world y) scrolls the content down
+        let ratio = view.thumb_ratio(thumb.axis);
+        let delta = match thumb.axis {
+            ScrollAxis::Horizontal => Vec2::new(dragged_event.delta.x * ratio, 0.0),
+            ScrollAxis::Vertical => Vec2::new(0.0, -dragged_event.delta.y * ratio),
+        };
+        let offset = view.offset + delta;
+        if view.bypass_change_detection().scroll_to(offset) {
+            view.set_changed();
+        }
+    }
+}
+
+/// System that scrolls the scroll views around entities asked to be shown by `ScrollIntoView`,
+/// and around text fields that gain focus
+pub fn scroll_into_view_system(
+    mut scroll_requests: EventReader<ScrollIntoView>,
+    mut focus_events: EventReader<TextFocusChanged>,
+    parents: Query<&Parent>,
+    contents: Query<(&ScrollContent, &GlobalTransform)>,
+    drawn: Query<(&GlobalTransform, Option<&ShapeData>, Option<&TextLayoutOutput>)>,
+    mut views: Query<&mut WidgetScrollView>,
+) {
+    let targets = scroll_requests.read().map(|request| request.entity)
+        .chain(focus_events.read().filter_map(|focus_event| focus_event.entity));
+
+    for target in targets {
+        let Some(mut bounds) = drawn.get(target).ok().and_then(|(transform, shape, text)| world_bounds(transform, shape, text)) else {
+            continue;
+        };
+        // Every scroll view around the target reveals it, from the innermost out. Scrolling an
+        // inner view moves the target before the outer ones see it.
+        let mut current = target;
+        while let Ok(parent) = parents.get(current) {
+            current = parent.get();
+            let Ok((content, content_transform)) = contents.get(current) else {
+                continue;
+            };
+            let Ok(mut view) = views.get_mut(content.view) else {
+                continue;
+            };
+            let before = view.offset;
+            let rect = view.to_content_rect(content_transform.translation(), bounds);
+            if view.bypass_change_detection().reveal(rect) {
+                view.set_changed();
+            }
+            let moved = view.offset - before;
+            bounds = Rect::from_corners(bounds.min + Vec2::new(-moved.x, moved.y), bounds.max + Vec2::new(-moved.x, moved.y));
+        }
+    }
+}
+
+/// System that moves the content and thumbs of scroll views whose offset or size changed
+pub fn scroll_view_apply_system(
+    views: Query<&WidgetScrollView, Changed<WidgetScrollView>>,
+    mut transforms: Query<&mut Transform, Without<WidgetScrollView>>,
+    mut thumbs: Query<(&mut ShapeData, &mut Visibility), With<ScrollThumb>>,
+) {
+    for view in views.iter() {
+        if let Some(mut transform) = view.content.and_then(|content| transforms.get_mut(content).ok()) {
+            // World y grows upwards, so scrolling down lifts the content
+            transform.translation = Vec3::new(-view.offset.x, view.offset.y, transform.translation.z);
+        }
+
+        for (axis, thumb) in [(ScrollAxis::Horizontal, view.horizontal_thumb), (ScrollAxis::Vertical, view.vertical_thumb)] {
+            let Some(thumb) = thumb else {
+                continue;
+            };
+            let Ok((mut shape, mut visibility)) = thumbs.get_mut(thumb) else {
+                continue;
+            };
+            let Some((start, length)) = view.thumb(axis) else {
+                visibility.set_if_neq(Visibility(false));
+                continue;
+            };
+            visibility.set_if_neq(Visibility(true));
+
+            // Thumbs run along the right and bottom edges, relative to the view's center
+            let half = view.viewport / 2.0;
+            let width = view.scrollbar_width;
+            let (size, center) = match axis {
+                ScrollAxis::Horizontal => (Vec2::new(length, width), Vec2::new(-half.x + start + length / 2.0, -half.y + width / 2.0)),
+                ScrollAxis::Vertical => (Vec2::new(width, length), Vec2::new(half.x - width / 2.0, half.y - start - length / 2.0)),
+            };
+            *shape = ShapeData::rectangle(size.x, size.y, view.scrollbar_color);
+            if let Ok(mut transform) = transforms.get_mut(thumb) {
+                transform.translation = center.extend(transform.translation.z);
+            }
+        }
+    }
+}
+
+/// System that clips everything inside scroll views to their viewports, nested views to the
+/// views around them too, and points wheel input at the visible part of each view
+pub fn scroll_view_clip_system(
+    mut commands: Commands,
+    views: Query<(Entity, &WidgetScrollView, &GlobalTransform, Option<&ClipRect>, Option<&Scrollable>)>,
+    children_query: Query<&Children>,
+    nested_contents: Query<(), With<ScrollContent>>,
+    clips: Query<Option<&ClipRect>>,
+) {
+    for (view_entity, view, transform, outer_clip, scrollable) in views.iter() {
+        let Some(content) = view.content else {
+            continue;
+        };
+        let viewport = ClipRect(view.viewport_rect(transform.translation()));
+        let clip = outer_clip.map_or(viewport, |outer| viewport.intersect(outer));
+
+        let area = Scrollable { area: clip.0 };
+        if scrollable != Some(&area) {
+            commands.entity(view_entity).try_insert(area);
+        }
+
+        // The thumbs are only clipped by the views around this one
+        let thumbs = [view.horizontal_thumb, view.vertical_thumb].into_iter().flatten();
+        for thumb in thumbs {
+            if clips.get(thumb).ok().flatten() != outer_clip {
+                match outer_clip {
+                    Some(outer) => {
+                        commands.entity(thumb).try_insert(*outer);
+                    }
+                    None => {
+                        commands.entity(thumb).remove::<ClipRect>();
+                    }
+                }
+            }
+        }
+
+        let mut pending = vec![content];
+        while let Some(entity) = pending.pop() {
+            for &child in children_query.get(entity).into_iter().flatten() {
+                if clips.get(child).ok().flatten() != Some(&clip) {
+                    commands.entity(child).try_insert(clip);
+                }
+                // Nested views clip their own content
+                if !nested_contents.contains(child) {
+                    pending.push(child);
+                }
+            }
+        }
+    }
+}
+
+/// Apply the reloaded settings of a scroll view, keeping how far it is scrolled, its content
+/// entity and the thumbs it still has scrollbars for
+pub(crate) fn reload_scroll_view(entity: &mut EntityWorldMut, view: Option<WidgetScrollView>) {
+    let live = entity.take::<WidgetScrollView>();
+    match (view, live) {
+        (Some(mut view), Some(live)) => {
+            view.offset = live.offset;
+            view.viewport = live.viewport;
+            view.content_size = live.content_size;
+            view.content = live.content;
+            let mut stale = Vec::new();
+            match view.has_scrollbar(ScrollAxis::Horizontal) {
+                true => view.horizontal_thumb = live.horizontal_thumb,
+                false => stale.extend(live.horizontal_thumb),
+            }
+            match view.has_scrollbar(ScrollAxis::Vertical) {
+                true => view.vertical_thumb = live.vertical_thumb,
+                false => stale.extend(live.vertical_thumb),
+            }
+            let offset = view.offset;
+            view.scroll_to(offset);
+            entity.insert(view);
+            entity.world_scope(|world| {
+                for thumb in stale {
+                    if let Ok(thumb) = world.get_entity_mut(thumb) {
+                        thumb.despawn_recursive();
+                    }
+                }
+            });
+        }
+        (Some(view), None) => {
+            entity.insert(view);
+        }
+        (None, Some(live)) => remove_scroll_parts(entity, &live),
+        (None, None) => {}
+    }
+}
+
+/// Despawn the scrollbars and content entity of a widget that stopped being a scroll view,
+/// handing its children back to it first
+fn remove_scroll_parts(entity: &mut EntityWorldMut, view: &WidgetScrollView) {
+    let entity_id = entity.id();
+    entity.world_scope(|world| {
+        if let Some(content) = view.content {
+            let children: Vec<Entity> = world.get::<Children>(content).map(|children| children.to_vec()).unwrap_or_default();
+            world.entity_mut(entity_id).add_children(&children);
+            for child in children {
+                world.entity_mut(child).remove::<ClipRect>();
+            }
+        }
+        for part in [view.content, view.horizontal_thumb, view.vertical_thumb].into_iter().flatten() {
+            if let Ok(part) = world.get_entity_mut(part) {
+                part.despawn_recursive();
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::{setup_world, spawn_child};
+    use crate::gui_framework::components::{ClipRect, ShapeData, Scrollable, Visibility};
+    use crate::gui_framework::events::{EntityDragged, EntityScrolled};
+    use crate::widgets::blueprint::ScrollAxes;
+    use crate::widgets::components::WidgetHierarchy;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_hierarchy::{Children, Parent};
+    use bevy_math::{Rect, Vec2, Vec3};
+    use bevy_transform::prelude::{GlobalTransform, Transform};
+
+    const LOG: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "log"
+widget_type = { type = "ScrollView", direction = "Column", scrollbar_color = "#33333380" }
+layout = { size = [200.0, 100.0] }
+
+[[root.children.children]]
+id = "first"
+widget_type = { type = "Shape", shape_type = "Rectangle" }
+layout = { size = [200.0, 80.0] }
+style = { background_color = "#EEEEEE" }
+
+[[root.children.children]]
+id = "second"
+widget_type = { type = "Shape", shape_type = "Rectangle" }
+layout = { size = [200.0, 80.0] }
+style = { background_color = "#DDDDDD" }
+"##;
+
+    /// Test the scroll limits, scrollbar thumbs and reveal of a scroll view
+    #[test]
+    fn test_scroll_view_offsets_and_thumbs() {
+        let ui_def = UiDefinitionLoader.parse_definition(LOG, UiDefinitionFormat::Toml).unwrap();
+        let mut view = WidgetScrollView::from_widget_type(&ui_def.root.children[0].widget_type).unwrap();
+        assert_eq!(view.axes, ScrollAxes::Vertical);
+        assert_eq!(view.scrollbar_width, DEFAULT_SCROLLBAR_WIDTH);
+        view.viewport = Vec2::new(200.0, 100.0);
+        view.content_size = Vec2::new(300.0, 400.0);
+
+        assert_eq!(view.max_offset(), Vec2::new(0.0, 300.0), "Only the axes the view scrolls in have room");
+        assert!(view.scroll_to(Vec2::new(50.0, 500.0)));
+        assert_eq!(view.offset, Vec2::new(0.0, 300.0));
+        assert!(!view.scroll_to(Vec2::new(0.0, 300.0)), "Scrolling to the same place changes nothing");
+
+        // The thumb is as long as the visible share of the track and sits at its end
+        assert_eq!(view.thumb(ScrollAxis::Vertical), Some((75.0, 25.0)));
+        assert_eq!(view.thumb(ScrollAxis::Horizontal), None);
+        view.content_size.y = 5000.0;
+        assert_eq!(view.thumb(ScrollAxis::Vertical).map(|(_, length)| length), Some(MIN_THUMB_LENGTH));
+
+        // Revealing moves as little as possible
+        view.scroll_to(Vec2::new(0.0, 100.0));
+        assert!(view.reveal(Rect::new(0.0, 250.0, 100.0, 260.0)));
+        assert_eq!(view.offset.y, 160.0);
+        assert!(view.reveal(Rect::new(0.0, 20.0, 100.0, 40.0)));
+        assert_eq!(view.offset.y, 20.0);
+        assert!(!view.reveal(Rect::new(0.0, 50.0, 100.0, 60.0)), "Visible rects need no scrolling");
+        view.content_size.y = 50.0;
+        assert!(view.scroll_to(view.offset), "Content that shrank pulls the offset back");
+        assert_eq!(view.offset.y, 0.0);
+
+        let invalid = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+widget_type = { type = "ScrollView", direction = "Column", scrollbar_width = -2.0 }
+
+[[root.children]]
+widget_type = { type = "ScrollView", direction = "Row", axes = "Horizontal", scrollbar_color = "#12" }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "root.children[0].widget_type.scrollbar_width",
+            "root.children[1].widget_type.scrollbar_color",
+        ]);
+    }
+
+    /// Test that scroll views carry their children on a content entity that the wheel and the
+    /// scrollbar move, and clip it to the viewport
+    #[test]
+    fn test_scroll_view_wheel_drag_and_clip() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(LOG, UiDefinitionFormat::Toml).unwrap();
+        let log = spawn_child(&mut world, &yrs_res, &ui_def, 0);
+        let children = world.get::<WidgetHierarchy>(log).unwrap().children.clone();
+
+        world.run_system_once(scroll_view_setup_system).unwrap();
+        world.run_system_once(scroll_view_adopt_system).unwrap();
+        let view = world.get::<WidgetScrollView>(log).unwrap().clone();
+        let content = view.content.unwrap();
+        let thumb = view.vertical_thumb.unwrap();
+        assert!(view.horizontal_thumb.is_none(), "Vertical views only get a vertical scrollbar");
+        for child in &children {
+            assert_eq!(world.get::<Parent>(*child).unwrap().get(), content);
+        }
+        assert!(world.get::<Children>(log).unwrap().contains(&thumb));
+
+        // Lay the rows out one under the other below the top of a view centered at the origin
+        world.entity_mut(log).insert(GlobalTransform::default());
+        world.entity_mut(content).insert(GlobalTransform::default());
+        world.entity_mut(children[0]).insert(GlobalTransform::from_xyz(0.0, 10.0, 0.0));
+        world.entity_mut(children[1]).insert(GlobalTransform::from_xyz(0.0, -70.0, 0.0));
+        world.run_system_once(scroll_view_measure_system).unwrap();
+        let view = world.get::<WidgetScrollView>(log).unwrap();
+        assert_eq!(view.viewport, Vec2::new(200.0, 100.0));
+        assert_eq!(view.content_size, Vec2::new(200.0, 160.0));
+
+        // Scrolling the wheel down lifts the content and shows the thumb
+        world.send_event(EntityScrolled { entity: log, delta: Vec2::new(0.0, -40.0) });
+        world.run_system_once(scroll_view_input_system).unwrap();
+        world.run_system_once(scroll_view_apply_system).unwrap();
+        assert_eq!(world.get::<WidgetScrollView>(log).unwrap().offset, Vec2::new(0.0, 40.0));
+        assert_eq!(world.get::<Transform>(content).unwrap().translation, Vec3::new(0.0, 40.0, 0.0));
+        assert_eq!(*world.get::<Visibility>(thumb).unwrap(), Visibility(true));
+        let thumb_height = world.get::<ShapeData>(thumb).unwrap().vertices.iter().map(|vertex| vertex.position[1]).fold(f32::MIN, f32::max) * 2.0;
+        assert_eq!(thumb_height, 62.5);
+
+        // Dragging the thumb up scrolls back to the top, and no further
+        world.send_event(EntityDragged { entity: thumb, delta: Vec2::new(0.0, 500.0) });
+        world.run_system_once(scroll_view_input_system).unwrap();
+        assert_eq!(world.get::<WidgetScrollView>(log).unwrap().offset, Vec2::ZERO);
+
+        world.run_system_once(scroll_view_clip_system).unwrap();
+        let viewport = Rect::new(-100.0, -50.0, 100.0, 50.0);
+        assert_eq!(world.get::<Scrollable>(log).unwrap().area, viewport);
+        for child in &children {
+            assert_eq!(world.get::<ClipRect>(*child), Some(&ClipRect(viewport)));
+        }
+        assert!(world.get::<ClipRect>(thumb).is_none(), "Scrollbars are not clipped by their own view");
+
+        // Asking for the second row scrolls just far enough to show all of it
+        world.send_event(ScrollIntoView { entity: children[1] });
+        world.run_system_once(scroll_into_view_system).unwrap();
+        assert_eq!(world.get::<WidgetScrollView>(log).unwrap().offset, Vec2::new(0.0, 60.0));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,850 @@
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Children, Parent};
+use bevy_math::Vec2;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use serde_json::Value;
+use std::collections::HashSet;
+use thiserror::Error;
+use crate::assets::definitions::WidgetNode;
+use crate::assets::reload::{collect_subtree, despawn_widgets};
+use crate::assets::windows::WidgetWindows;
+use crate::gui_framework::components::{InteractionState, ShapeData, TextLayoutOutput, Visibility};
+use crate::gui_framework::events::{ActionEvent, EntityClicked, EntityDragged};
+use crate::gui_framework::systems::{change_action, value_action};
+use crate::layout::{PositionControl, Styleable, TaffyResource, UiNode};
+use crate::widgets::blueprint::{BehaviorConfig, LayoutConfig, ShapeType, StyleConfig, TabItem, WidgetType};
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetBehavior, WidgetHierarchy, WidgetLayout, WidgetSourceNode};
+use crate::widgets::popup::WidgetPopup;
+use crate::widgets::scroll_view::{world_bounds, WidgetScrollView};
+use crate::widgets::systems::{convert_layout_config_to_taffy_style, spawn_widget_entity_from_node};
+use crate::widgets::templates::{get_widget_templates, TabsTemplate};
+use crate::YrsDocResource;
+
+/// Problems with a `Tabs` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TabsError {
+    #[error("a tabs widget needs at least one tab")]
+    EmptyTabs,
+    #[error("tab ids cannot be empty")]
+    EmptyId,
+    #[error("tab id '{0}' is used more than once")]
+    DuplicateId(String),
+    #[error("active tab '{0}' is not one of the tabs")]
+    UnknownActive(String),
+}
+
+impl TabsError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            TabsError::EmptyTabs | TabsError::EmptyId | TabsError::DuplicateId(_) => "tabs",
+            TabsError::UnknownActive(_) => "active",
+        }
+    }
+}
+
+/// Events a tabs widget fires with the active tab, the closed tab or the new tab order as the
+/// `value` parameter
+pub const TABS_VALUE_EVENTS: &[&str] = &["change", "close", "reorder"];
+
+/// Check the tabs and active tab of a `Tabs` widget; other widget types pass
+pub fn validate_tabs(widget_type: &WidgetType) -> Result<(), TabsError> {
+    let WidgetType::Tabs { tabs, active, .. } = widget_type else {
+        return Ok(());
+    };
+    if tabs.is_empty() {
+        return Err(TabsError::EmptyTabs);
+    }
+    if tabs.iter().any(|tab| tab.id().is_empty()) {
+        return Err(TabsError::EmptyId);
+    }
+    let mut ids = HashSet::new();
+    if let Some(tab) = tabs.iter().find(|tab| !ids.insert(tab.id())) {
+        return Err(TabsError::DuplicateId(tab.id().to_string()));
+    }
+    if let Some(active) = active.as_ref().filter(|active| !ids.contains(active.as_str())) {
+        return Err(TabsError::UnknownActive(active.clone()));
+    }
+    Ok(())
+}
+
+impl TabItem {
+    /// The value sent to the `change` binding when the tab is activated
+    pub fn id(&self) -> &str {
+        match self {
+            TabItem::Id(id) | TabItem::Titled { id, .. } => id,
+        }
+    }
+
+    /// The text shown on the tab's button
+    pub fn title(&self) -> &str {
+        match self {
+            TabItem::Id(title) | TabItem::Titled { title, .. } => title,
+        }
+    }
+}
+
+/// Component on a `Tabs` widget holding its tabs in their current order, the active one and the
+/// buttons of its header row.
+///
+/// The widget's first children in `WidgetHierarchy` are its pages, in the same order as `tabs`;
+/// the header buttons come after them.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetTabs {
+    pub tabs: Vec<TabItem>,
+    pub active: Option<usize>,
+    pub closable: bool,
+    pub reorderable: bool,
+    /// Header button of each tab, in tab order; `tabs_header_system` spawns them
+    pub buttons: Vec<Entity>,
+    /// Width of each tab's button, fitted to its title
+    pub widths: Vec<f32>,
+    /// Tab being dragged along the header, and how far it is from its place
+    pub dragging: Option<(usize, f32)>,
+}
+
+impl WidgetTabs {
+    /// The tabs state of a `Tabs` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let WidgetType::Tabs { tabs, active, closable, reorderable } = widget_type else {
+            return None;
+        };
+        // Unknown active ids are reported by validation and show the first tab here
+        let active = active.as_deref()
+            .and_then(|active| tabs.iter().position(|tab| tab.id() == active))
+            .or((!tabs.is_empty()).then_some(0));
+        Some(Self {
+            tabs: tabs.clone(),
+            active,
+            closable: *closable,
+            reorderable: *reorderable,
+            buttons: Vec::new(),
+            widths: Vec::new(),
+            dragging: None,
+        })
+    }
+
+    /// Id of the active tab
+    pub fn active_id(&self) -> Option<&str> {
+        self.active.and_then(|index| self.tabs.get(index)).map(TabItem::id)
+    }
+
+    /// Index of the tab with `id`
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.id() == id)
+    }
+
+    /// The pages of the tabs, in tab order
+    pub fn pages<'a>(&self, hierarchy: &'a WidgetHierarchy) -> &'a [Entity] {
+        &hierarchy.children[..self.tabs.len().min(hierarchy.children.len())]
+    }
+
+    /// Activate the tab at `index`, returning its id if the active tab changed
+    pub fn activate(&mut self, index: usize) -> Option<String> {
+        if self.active == Some(index) || index >= self.tabs.len() {
+            return None;
+        }
+        self.active = Some(index);
+        self.active_id().map(str::to_string)
+    }
+
+    /// Move the tab at `from` to `to`, together with its button. The active tab stays active.
+    pub fn move_tab(&mut self, from: usize, to: usize) -> bool {
+        if from == to || from >= self.tabs.len() || to >= self.tabs.len() {
+            return false;
+        }
+        move_item(&mut self.tabs, from, to);
+        move_item(&mut self.buttons, from, to);
+        move_item(&mut self.widths, from, to);
+        self.active = self.active.map(|active| {
+            if active == from {
+                to
+            } else if from < active && active <= to {
+                active - 1
+            } else if to <= active && active < from {
+                active + 1
+            } else {
+                active
+            }
+        });
+        true
+    }
+
+    /// Remove the tab at `index` and its button. Closing the active tab activates the one after
+    /// it, or the one before it if it was the last.
+    pub fn close(&mut self, index: usize) -> Option<TabItem> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let tab = self.tabs.remove(index);
+        if index < self.buttons.len() {
+            self.buttons.remove(index);
+        }
+        if index < self.widths.len() {
+            self.widths.remove(index);
+        }
+        self.active = match self.active {
+            _ if self.tabs.is_empty() => None,
+            Some(active) if active > index => Some(active - 1),
+            Some(active) if active == index => Some(index.min(self.tabs.len() - 1)),
+            active => active,
+        };
+        self.dragging = None;
+        Some(tab)
+    }
+
+    /// Drag the tab at `index` by `dx` along the header. It trades places with a neighbour once
+    /// it is over more than half of it. Returns the tab's new index if it moved.
+    pub fn drag(&mut self, index: usize, dx: f32) -> Option<usize> {
+        let mut offset = match self.dragging {
+            Some((dragged, offset)) if dragged == index => offset + dx,
+            _ => dx,
+        };
+        let mut current = index;
+        loop {
+            let neighbour = if offset > 0.0 && current + 1 < self.tabs.len() {
+                current + 1
+            } else if offset < 0.0 && current > 0 {
+                current - 1
+            } else {
+                break;
+            };
+            let width = self.width(neighbour);
+            if offset.abs() <= width / 2.0 {
+                break;
+            }
+            self.move_tab(current, neighbour);
+            offset -= width.copysign(offset);
+            current = neighbour;
+        }
+        self.dragging = Some((current, offset));
+        (current != index).then_some(current)
+    }
+
+    /// Width of the button of the tab at `index`, before its title has been measured too
+    fn width(&self, index: usize) -> f32 {
+        self.widths.get(index).copied().unwrap_or(get_widget_templates().tabs.tab_width)
+    }
+}
+
+fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from < items.len() && to < items.len() {
+        let item = items.remove(from);
+        items.insert(to, item);
+    }
+}
+
+/// Component on a header button of a `Tabs` widget
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabButton {
+    pub tabs: Entity,
+}
+
+/// Component on the close button of a closable tab
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabClose {
+    pub tabs: Entity,
+    pub button: Entity,
+}
+
+/// Node of a part of a tabs widget's header
+fn header_node(id: String, widget_type: WidgetType, size: Option<Vec2>, style: StyleConfig, clickable: bool, draggable: bool) -> WidgetNode {
+    WidgetNode {
+        id: Some(id),
+        widget_type,
+        layout: LayoutConfig { size, ..Default::default() },
+        style,
+        behavior: BehaviorConfig {
+            clickable: Some(clickable),
+            draggable: Some(draggable),
+            interactive: Some(clickable || draggable),
+            z_index: Some(1),
+            position_control: Some(PositionControl::Manual),
+            ..Default::default()
+        },
+        classes: None,
+        style_overrides: None,
+        bindings: None,
+        state_bindings: None,
+        repeat: None,
+        when: None,
+        localized: None,
+        children: vec![],
+    }
+}
+
+/// Spawn the header button of a tab: its title, and a close button if the tabs are closable.
+/// `tabs_layout_system` sizes and places it.
+#[allow(clippy::too_many_arguments)]
+fn spawn_button(
+    commands: &mut Commands,
+    yrs_res: &YrsDocResource,
+    template: &TabsTemplate,
+    tabs_entity: Entity,
+    tabs: &WidgetTabs,
+    tab: &TabItem,
+    id: &str,
+    window_height: f32,
+) -> Entity {
+    let button_id = format!("{}_tab_{}", id, tab.id());
+    let button_size = Vec2::new(template.tab_width, template.header_height);
+    let button_style = StyleConfig { background_color: Some(template.tab_color.clone()), ..Default::default() };
+    let button_node = header_node(button_id.clone(), WidgetType::Shape { shape_type: ShapeType::Rectangle }, Some(button_size), button_style, true, tabs.reorderable);
+    let button = spawn_widget_entity_from_node(commands, &button_node, yrs_res, window_height, None, None);
+
+    let label_style = StyleConfig {
+        text_color: Some(template.text_color.clone()),
+        text_size: Some(template.text_size),
+        ..Default::default()
+    };
+    let label_type = WidgetType::Text { content: tab.title().to_string(), editable: false };
+    let label_node = header_node(format!("{}_label", button_id), label_type, None, label_style.clone(), false, false);
+    let label = spawn_widget_entity_from_node(commands, &label_node, yrs_res, window_height, None, None);
+    // Text is drawn down from its top-left, so this centers the title on its button
+    let label_position = Transform::from_xyz(-template.tab_width / 2.0 + template.padding, template.text_size * 0.6, 0.1);
+    commands.entity(label).insert((label_position, WidgetHierarchy { parent: Some(button), children: vec![] })).set_parent(button);
+
+    let mut parts = vec![label];
+    if tabs.closable {
+        let close_id = format!("{}_close", button_id);
+        let close_size = Vec2::splat(template.close_size);
+        let close_style = StyleConfig { background_color: Some(template.close_color.clone()), ..Default::default() };
+        let close_node = header_node(close_id.clone(), WidgetType::Shape { shape_type: ShapeType::Rectangle }, Some(close_size), close_style, true, false);
+        let close = spawn_widget_entity_from_node(commands, &close_node, yrs_res, window_height, None, None);
+
+        let cross_type = WidgetType::Text { content: "x".to_string(), editable: false };
+        let cross_node = header_node(format!("{}_label", close_id), cross_type, None, label_style, false, false);
+        let cross = spawn_widget_entity_from_node(commands, &cross_node, yrs_res, window_height, None, None);
+        let cross_position = Transform::from_xyz(-template.text_size * 0.3, template.text_size * 0.6, 0.1);
+        commands.entity(cross).insert((cross_position, WidgetHierarchy { parent: Some(close), children: vec![] })).set_parent(close);
+
+        let close_position = Transform::from_xyz(template.tab_width / 2.0 - template.padding - template.close_size / 2.0, 0.0, 0.2);
+        commands.entity(close)
+            .insert((TabClose { tabs: tabs_entity, button }, close_position, WidgetHierarchy { parent: Some(button), children: vec![cross] }))
+            .set_parent(button);
+        parts.push(close);
+    }
+
+    commands.entity(button)
+        .insert((TabButton { tabs: tabs_entity }, WidgetHierarchy { parent: Some(tabs_entity), children: parts }))
+        .set_parent(tabs_entity);
+    button
+}
+
+/// System that spawns the header buttons of new tabs widgets, and of tabs widgets a reload
+/// replaced
+pub fn tabs_header_system(
+    mut commands: Commands,
+    yrs_res: Res<YrsDocResource>,
+    mut tabs_query: Query<(Entity, &mut WidgetTabs, &Widget, &WidgetHierarchy, Option<&WidgetSourceNode>)>,
+    widget_windows: WidgetWindows,
+) {
+    let template = &get_widget_templates().tabs;
+    for (entity, mut tabs, widget, hierarchy, source) in tabs_query.iter_mut() {
+        if !tabs.buttons.is_empty() || tabs.tabs.is_empty() {
+            continue;
+        }
+        let id = source.and_then(|source| source.node.id.clone()).unwrap_or_else(|| widget.id.clone());
+        let window_height = widget_windows.height_of(entity);
+        let buttons: Vec<Entity> = tabs.tabs.iter()
+            .map(|tab| spawn_button(&mut commands, &yrs_res, template, entity, &tabs, tab, &id, window_height))
+            .collect();
+
+        let mut children = hierarchy.children.clone();
+        children.extend(&buttons);
+        commands.entity(entity).insert(WidgetHierarchy { parent: hierarchy.parent, children });
+        tabs.widths = vec![template.tab_width; buttons.len()];
+        tabs.buttons = buttons;
+    }
+}
+
+/// System that fits the header buttons of tabs widgets to their titles, lines them up along the
+/// top edge with the dragged one following the pointer, and colors the active one
+pub fn tabs_layout_system(
+    mut tabs_query: Query<(&mut WidgetTabs, &WidgetLayout)>,
+    mut buttons: Query<(&mut Transform, &mut ShapeData, &WidgetHierarchy), With<TabButton>>,
+    labels: Query<(&GlobalTransform, &TextLayoutOutput)>,
+    mut parts: Query<&mut Transform, Without<TabButton>>,
+) {
+    let template = &get_widget_templates().tabs;
+    let close_space = template.close_size + template.padding;
+    for (mut tabs, layout) in tabs_query.iter_mut() {
+        // Titles are measured once their text has been laid out
+        let widths: Vec<f32> = tabs.buttons.iter()
+            .map(|&button| {
+                let title = buttons.get(button).ok()
+                    .and_then(|(.., hierarchy)| hierarchy.children.first())
+                    .and_then(|&label| labels.get(label).ok())
+                    .and_then(|(transform, text)| world_bounds(transform, None, Some(text)))
+                    .map_or(0.0, |bounds| bounds.width());
+                let close = if tabs.closable { close_space } else { 0.0 };
+                (title + 2.0 * template.padding + close).max(template.tab_width)
+            })
+            .collect();
+        let previous = tabs.widths.clone();
+        if tabs.widths != widths {
+            tabs.widths = widths;
+        }
+
+        let size = layout.size.unwrap_or(layout.computed_size);
+        let y = size.y / 2.0 - template.header_height / 2.0;
+        let mut left = -size.x / 2.0;
+        for (index, (&button, &width)) in tabs.buttons.iter().zip(&tabs.widths).enumerate() {
+            let offset = match tabs.dragging {
+                Some((dragged, offset)) if dragged == index => offset,
+                _ => 0.0,
+            };
+            let center = left + width / 2.0;
+            left += width;
+            let Ok((mut transform, mut shape, hierarchy)) = buttons.get_mut(button) else {
+                continue;
+            };
+            let translation = Vec2::new(center + offset, y);
+            if transform.translation.truncate() != translation {
+                transform.translation = translation.extend(transform.translation.z);
+            }
+
+            let color = if tabs.active == Some(index) { template.active_tab_color.to_color() } else { template.tab_color.to_color() };
+            if previous.get(index) != Some(&width) {
+                *shape = ShapeData::rectangle(width, template.header_height, color);
+            } else if shape.color != color {
+                shape.color = color;
+            }
+
+            // The title sits at the left edge and the close button at the right one
+            let positions = [
+                Vec2::new(-width / 2.0 + template.padding, template.text_size * 0.6),
+                Vec2::new(width / 2.0 - template.padding - template.close_size / 2.0, 0.0),
+            ];
+            for (&part, position) in hierarchy.children.iter().zip(positions) {
+                if let Ok(mut part_transform) = parts.get_mut(part) {
+                    if part_transform.translation.truncate() != position {
+                        part_transform.translation = position.extend(part_transform.translation.z);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System that activates tabs whose button is clicked, firing the `change` binding with the tab's
+/// id, and closes tabs whose close button is clicked, firing `close` with the tab's id. Closing
+/// despawns the tab's page, and fires `change` too if another tab became active.
+#[allow(clippy::too_many_arguments)]
+pub fn tabs_click_system(
+    mut commands: Commands,
+    mut click_events: EventReader<EntityClicked>,
+    buttons: Query<&TabButton>,
+    closes: Query<&TabClose>,
+    mut tabs_query: Query<(&mut WidgetTabs, &WidgetHierarchy, Option<&WidgetActionBindings>)>,
+    hierarchies: Query<&WidgetHierarchy>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+    taffy_resource: Res<TaffyResource>,
+    yrs_res: Res<YrsDocResource>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for click_event in click_events.read() {
+        if let Ok(close) = closes.get(click_event.entity) {
+            let Ok((mut tabs, hierarchy, bindings)) = tabs_query.get_mut(close.tabs) else {
+                continue;
+            };
+            let Some(index) = tabs.buttons.iter().position(|button| *button == close.button) else {
+                continue;
+            };
+            let page = tabs.pages(hierarchy).get(index).copied();
+            let previous = tabs.active_id().map(str::to_string);
+            let Some(tab) = tabs.close(index) else {
+                continue;
+            };
+
+            let mut stale = HashSet::new();
+            collect_subtree(close.button, &hierarchies, &mut stale);
+            if let Some(page) = page {
+                collect_subtree(page, &hierarchies, &mut stale);
+            }
+            despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+            let children = hierarchy.children.iter().copied().filter(|child| !stale.contains(child)).collect();
+            commands.entity(close.tabs).insert(WidgetHierarchy { parent: hierarchy.parent, children });
+
+            bevy_log::debug!("Tabs {:?} closed '{}'", close.tabs, tab.id());
+            if let Some(action) = value_action(bindings, close.tabs, "close", Value::String(tab.id().to_string())) {
+                action_events.send(action);
+            }
+            if tabs.active_id() != previous.as_deref() {
+                let active = tabs.active_id().map_or(Value::Null, |id| Value::String(id.to_string()));
+                if let Some(action) = change_action(bindings, close.tabs, active) {
+                    action_events.send(action);
+                }
+            }
+            continue;
+        }
+
+        let Ok(button) = buttons.get(click_event.entity) else {
+            continue;
+        };
+        let Ok((mut tabs, _, bindings)) = tabs_query.get_mut(button.tabs) else {
+            continue;
+        };
+        let Some(index) = tabs.buttons.iter().position(|entity| *entity == click_event.entity) else {
+            continue;
+        };
+        if let Some(id) = tabs.activate(index) {
+            bevy_log::debug!("Tabs {:?} changed to '{}'", button.tabs, id);
+            if let Some(action) = change_action(bindings, button.tabs, Value::String(id)) {
+                action_events.send(action);
+            }
+        }
+    }
+}
+
+/// System that moves the tabs of reorderable tabs widgets as their buttons are dragged along the
+/// header, firing the `reorder` binding with the tab ids in their new order
+pub fn tabs_drag_system(
+    mut commands: Commands,
+    mut drag_events: EventReader<EntityDragged>,
+    buttons: Query<(&TabButton, Option<&InteractionState>)>,
+    mut tabs_query: Query<(Entity, &mut WidgetTabs, &WidgetHierarchy, Option<&WidgetActionBindings>)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    // A released button drops into its place
+    for (_, mut tabs, ..) in tabs_query.iter_mut() {
+        let Some((index, _)) = tabs.dragging else {
+            continue;
+        };
+        let held = tabs.buttons.get(index)
+            .and_then(|&button| buttons.get(button).ok())
+            .and_then(|(_, interaction)| interaction)
+            .is_some_and(|interaction| interaction.dragged);
+        if !held {
+            tabs.dragging = None;
+        }
+    }
+
+    for drag_event in drag_events.read() {
+        let Ok((button, _)) = buttons.get(drag_event.entity) else {
+            continue;
+        };
+        let Ok((entity, mut tabs, hierarchy, bindings)) = tabs_query.get_mut(button.tabs) else {
+            continue;
+        };
+        if !tabs.reorderable {
+            continue;
+        }
+        let Some(index) = tabs.buttons.iter().position(|button| *button == drag_event.entity) else {
+            continue;
+        };
+        let pages: Vec<(String, Entity)> = tabs.tabs.iter()
+            .map(|tab| tab.id().to_string())
+            .zip(tabs.pages(hierarchy).iter().copied())
+            .collect();
+        if tabs.drag(index, drag_event.delta.x).is_none() {
+            continue;
+        }
+
+        // Pages follow their tabs
+        let mut children: Vec<Entity> = tabs.tabs.iter()
+            .filter_map(|tab| pages.iter().find(|(id, _)| id == tab.id()).map(|(_, page)| *page))
+            .collect();
+        children.extend(hierarchy.children.iter().skip(pages.len()));
+        commands.entity(entity).insert(WidgetHierarchy { parent: hierarchy.parent, children });
+
+        let order = tabs.tabs.iter().map(|tab| Value::String(tab.id().to_string())).collect();
+        if let Some(action) = value_action(bindings, entity, "reorder", Value::Array(order)) {
+            action_events.send(action);
+        }
+    }
+}
+
+/// Everything on `page`: its widgets and the entities they carry, such as scrollbars. Popups
+/// open and close on their own, so the walk stops at them.
+fn page_contents(
+    page: Entity,
+    hierarchies: &Query<&WidgetHierarchy>,
+    children_query: &Query<&Children>,
+    popups: &Query<(), With<WidgetPopup>>,
+    contents: &mut HashSet<Entity>,
+) {
+    let mut pending = vec![page];
+    while let Some(entity) = pending.pop() {
+        if popups.contains(entity) || !contents.insert(entity) {
+            continue;
+        }
+        let widget_children = hierarchies.get(entity).into_iter().flat_map(|hierarchy| hierarchy.children.iter());
+        let entity_children = children_query.get(entity).into_iter().flatten();
+        pending.extend(widget_children.chain(entity_children));
+    }
+}
+
+/// System that shows the active page of each tabs widget and hides the others, taking them out
+/// of layout. Hidden pages keep their widgets and state. Pages inside a hidden page stay hidden
+/// whichever is active, and widgets on a shown page are shown as their own behavior says.
+pub fn tabs_page_system(
+    tabs_query: Query<(Ref<WidgetTabs>, &WidgetHierarchy)>,
+    spawned: Query<(), Added<WidgetBehavior>>,
+    hierarchies: Query<&WidgetHierarchy>,
+    children_query: Query<&Children>,
+    popups: Query<(), With<WidgetPopup>>,
+    mut contents: Query<(
+        &mut Visibility,
+        Option<&WidgetBehavior>,
+        Option<&Widget>,
+        Option<&PositionControl>,
+        Option<&mut Styleable>,
+        Option<&mut WidgetScrollView>,
+    )>,
+) {
+    // Widgets spawned onto a page since, e.g. by `repeat`, are hidden with it too
+    if !tabs_query.iter().any(|(tabs, _)| tabs.is_changed()) && spawned.is_empty() {
+        return;
+    }
+
+    let mut shown = HashSet::new();
+    let mut hidden = HashSet::new();
+    for (tabs, hierarchy) in tabs_query.iter() {
+        for (index, &page) in tabs.pages(hierarchy).iter().enumerate() {
+            let set = if tabs.active == Some(index) { &mut shown } else { &mut hidden };
+            page_contents(page, &hierarchies, &children_query, &popups, set);
+        }
+    }
+
+    for &entity in shown.iter().filter(|entity| !hidden.contains(*entity)).chain(&hidden) {
+        let Ok((mut visibility, behavior, widget, position_control, styleable, scroll_view)) = contents.get_mut(entity) else {
+            continue;
+        };
+        let show = !hidden.contains(&entity);
+        // Entities without a behavior, such as scrollbars, are shown again by their widget
+        let visible = show && behavior.is_none_or(|behavior| behavior.visible);
+        if visibility.0 != visible {
+            visibility.0 = visible;
+        }
+        if show {
+            if let Some(mut scroll_view) = scroll_view {
+                scroll_view.set_changed();
+            }
+        }
+
+        let (Some(mut styleable), Some(widget)) = (styleable, widget) else {
+            continue;
+        };
+        let display = match show {
+            true => convert_layout_config_to_taffy_style(&widget.blueprint.layout, &position_control.cloned().unwrap_or_default()).display,
+            false => taffy::Display::None,
+        };
+        if styleable.0.display != display {
+            styleable.0.display = display;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::setup_world;
+    use crate::gui_framework::components::Visibility;
+    use crate::gui_framework::events::{ActionEvent, EntityClicked};
+    use crate::layout::Styleable;
+    use crate::widgets::blueprint::{TabItem, WidgetType};
+    use crate::widgets::components::{Widget, WidgetHierarchy, WidgetText};
+    use crate::YrsDocResource;
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+    use serde_json::json;
+
+    const VIEWS: &str = r##"
+[state.ui]
+view = "storyboard"
+closed = ""
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "views"
+widget_type = { type = "Tabs", tabs = [{ id = "script", title = "Script" }, { id = "storyboard", title = "Storyboard" }, "timeline", { id = "compositing", title = "Compositing" }], active = { bind = "ui.view" }, closable = true, reorderable = true }
+layout = { size = [400.0, 300.0] }
+bindings = { change = { event = "change", action = "set_state", params = { path = "ui.view" } }, close = { event = "close", action = "set_state", params = { path = "ui.closed" } } }
+
+[[root.children.children]]
+id = "script_page"
+widget_type = { type = "Text", content = "INT. KITCHEN - NIGHT", editable = false }
+
+[[root.children.children]]
+id = "storyboard_page"
+widget_type = { type = "Container", direction = "Row" }
+
+[[root.children.children.children]]
+id = "shot"
+widget_type = { type = "Text", content = "Shot 1", editable = false }
+
+[[root.children.children]]
+id = "timeline_page"
+widget_type = { type = "Text", content = "00:00:00", editable = false }
+
+[[root.children.children]]
+id = "compositing_page"
+widget_type = { type = "Text", content = "Nodes", editable = false }
+"##;
+
+    fn spawn(world: &mut World, yrs_res: &YrsDocResource, ui_def: &UiDefinition) -> Entity {
+        assert!(!ui_def.validation_report_with_registry(&UiRegistry::new()).has_errors());
+        let root = {
+            let mut commands = world.commands();
+            spawn_widget_from_node(&mut commands, &ui_def.root, ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+        };
+        world.flush();
+        root
+    }
+
+    fn widget_named(world: &mut World, id: &str) -> Entity {
+        world.query::<(Entity, &Widget)>().iter(world)
+            .find(|(_, widget)| widget.id == id)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    fn shown(world: &World, entity: Entity) -> bool {
+        let in_layout = world.get::<Styleable>(entity).is_none_or(|styleable| styleable.0.display != taffy::Display::None);
+        assert_eq!(world.get::<Visibility>(entity).unwrap().is_visible(), in_layout);
+        in_layout
+    }
+
+    /// Test validation of tabs and their pages, and moving, closing and dragging tabs
+    #[test]
+    fn test_tabs_validation_and_ordering() {
+        let invalid = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+widget_type = { type = "Tabs", tabs = [] }
+
+[[root.children]]
+widget_type = { type = "Tabs", tabs = ["a", "a"] }
+
+[[root.children]]
+widget_type = { type = "Tabs", tabs = ["a", "b"], active = "c" }
+
+[[root.children]]
+widget_type = { type = "Tabs", tabs = ["a", "b"] }
+
+[[root.children.children]]
+widget_type = { type = "Text", content = "Only one page", editable = false }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert!(paths.contains(&"root.children[0].widget_type.tabs"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[1].widget_type.tabs"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[2].widget_type.active"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[3].children"), "{:?}", paths);
+
+        let ui_def = UiDefinitionLoader.parse_definition(VIEWS, UiDefinitionFormat::Toml).unwrap();
+        let widget_type = &ui_def.root.children[0].widget_type;
+        assert!(matches!(widget_type, WidgetType::Tabs { active: Some(active), .. } if active == "storyboard"), "The bound state value is the first active tab");
+        let mut tabs = WidgetTabs::from_widget_type(widget_type).unwrap();
+        assert_eq!((tabs.tabs[2].id(), tabs.tabs[2].title()), ("timeline", "timeline"));
+        assert_eq!(tabs.active_id(), Some("storyboard"));
+
+        // The active tab stays active wherever it moves
+        assert!(tabs.move_tab(1, 3));
+        let ids: Vec<_> = tabs.tabs.iter().map(TabItem::id).collect();
+        assert_eq!(ids, ["script", "timeline", "compositing", "storyboard"]);
+        assert_eq!(tabs.active, Some(3));
+
+        // Closing the active last tab activates the one before it, closing others keeps it
+        assert_eq!(tabs.close(3).as_ref().map(TabItem::id), Some("storyboard"));
+        assert_eq!(tabs.active_id(), Some("compositing"));
+        tabs.close(0);
+        assert_eq!((tabs.active, tabs.active_id()), (Some(1), Some("compositing")));
+        assert_eq!(tabs.activate(1), None);
+        assert_eq!(tabs.activate(0).as_deref(), Some("timeline"));
+
+        // Dragged tabs trade places once they are over half of their neighbour
+        let mut tabs = WidgetTabs::from_widget_type(widget_type).unwrap();
+        tabs.widths = vec![80.0; 4];
+        assert_eq!(tabs.drag(0, 30.0), None);
+        assert_eq!(tabs.drag(0, 20.0), Some(1));
+        assert_eq!(tabs.tabs[1].id(), "script");
+        assert_eq!(tabs.dragging, Some((1, -30.0)));
+        assert_eq!(tabs.active_id(), Some("storyboard"));
+        assert_eq!(tabs.drag(1, -20.0), Some(0));
+        assert_eq!(tabs.drag(0, 220.0), Some(3), "Long drags pass several tabs");
+    }
+
+    /// Test that only the active page is shown and in layout, that clicking a tab fires `change` and
+    /// switches pages, and that closing a tab despawns its page and fires `close`
+    #[test]
+    fn test_tabs_switch_and_close_pages() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(VIEWS, UiDefinitionFormat::Toml).unwrap();
+        spawn(&mut world, &yrs_res, &ui_def);
+        let views = widget_named(&mut world, "views");
+        let storyboard_page = widget_named(&mut world, "storyboard_page");
+        let shot = widget_named(&mut world, "shot");
+        let timeline_page = widget_named(&mut world, "timeline_page");
+
+        world.run_system_once(tabs_header_system).unwrap();
+        let buttons = world.get::<WidgetTabs>(views).unwrap().buttons.clone();
+        assert_eq!(buttons.len(), 4);
+        assert_eq!(world.get::<WidgetHierarchy>(views).unwrap().children[4..], buttons[..], "Buttons come after the pages");
+        assert_eq!(world.get::<Widget>(buttons[0]).unwrap().id, "views_tab_script");
+        let parts = world.get::<WidgetHierarchy>(buttons[0]).unwrap().children.clone();
+        assert_eq!(world.get::<WidgetText>(parts[0]).unwrap().content, "Script");
+        assert_eq!(world.get::<TabClose>(parts[1]), Some(&TabClose { tabs: views, button: buttons[0] }));
+
+        world.run_system_once(tabs_page_system).unwrap();
+        assert!(shown(&world, storyboard_page) && shown(&world, shot));
+        assert!(!shown(&world, timeline_page));
+
+        world.send_event(EntityClicked { entity: buttons[2] });
+        world.run_system_once(tabs_click_system).unwrap();
+        let actions: Vec<_> = world.resource_mut::<Events<ActionEvent>>().drain().collect();
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].action.as_str(), actions[0].event_type.as_str()), ("set_state", "change"));
+        assert_eq!(actions[0].get_string_param("value").as_deref(), Some("timeline"));
+        world.run_system_once(tabs_page_system).unwrap();
+        assert!(shown(&world, timeline_page));
+        assert!(!shown(&world, storyboard_page) && !shown(&world, shot), "Pages inside a hidden page are hidden too");
+
+        // Closing a hidden tab leaves the active one alone
+        let storyboard_close = world.get::<WidgetHierarchy>(buttons[1]).unwrap().children[1];
+        world.resource_mut::<Events<EntityClicked>>().clear();
+        world.send_event(EntityClicked { entity: storyboard_close });
+        world.run_system_once(tabs_click_system).unwrap();
+        let actions: Vec<_> = world.resource_mut::<Events<ActionEvent>>().drain().collect();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].event_type, "close");
+        assert_eq!(actions[0].get_string_param("value").as_deref(), Some("storyboard"));
+        assert!(world.get::<Widget>(storyboard_page).is_none() && world.get::<Widget>(shot).is_none());
+        assert!(world.get::<Widget>(buttons[1]).is_none());
+        let tabs = world.get::<WidgetTabs>(views).unwrap();
+        assert_eq!((tabs.buttons.len(), tabs.active_id()), (3, Some("timeline")));
+        assert_eq!(world.get::<WidgetHierarchy>(views).unwrap().children.len(), 6);
+    }
+
+    /// Test that the active tab follows the state value it is bound to
+    #[test]
+    fn test_tabs_active_binding() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(VIEWS, UiDefinitionFormat::Toml).unwrap();
+        let bindings = ui_def.root.children[0].state_bindings.as_ref().unwrap();
+        assert_eq!(bindings["widget_type.active"], "ui.view");
+
+        let mut state = UiState::new();
+        state.declare_definition(&ui_def);
+        world.insert_resource(state);
+        spawn(&mut world, &yrs_res, &ui_def);
+        let views = widget_named(&mut world, "views");
+
+        world.resource_mut::<UiState>().set("ui.view", json!("compositing")).unwrap();
+        world.run_system_once(apply_state_bindings_system).unwrap();
+        assert_eq!(world.get::<WidgetTabs>(views).unwrap().active_id(), Some("compositing"));
+
+        // Values that are not tab ids leave the active tab as it is
+        world.resource_mut::<UiState>().set("ui.view", json!("mixing")).unwrap();
+        world.run_system_once(apply_state_bindings_system).unwrap();
+        assert_eq!(world.get::<WidgetTabs>(views).unwrap().active_id(), Some("compositing"));
+    }
+}
\ No newline at end of file
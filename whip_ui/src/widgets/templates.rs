@@ -1,7 +1,10 @@
 use crate::widgets::blueprint::{
     WidgetBlueprint, WidgetType, LayoutConfig, StyleConfig, BehaviorConfig, 
-    ShapeType, ColorDef
+    ShapeType, ColorDef, StateStyles, StyleOverrides
 };
+use crate::widgets::blueprint::{DropdownOption, SliderOrientation};
+use crate::widgets::numeric::{thumb_offset, WidgetNumeric};
+use crate::widgets::toggle::knob_offset;
 use bevy_math::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
@@ -10,12 +13,33 @@ use serde::{Deserialize, Serialize};
 pub struct WidgetTemplates {
     /// Built-in button template
     pub button: ButtonTemplate,
+    /// Built-in checkbox template
+    pub checkbox: ToggleTemplate,
+    /// Built-in toggle switch template
+    pub toggle: ToggleTemplate,
+    /// Built-in slider template
+    pub slider: SliderTemplate,
+    /// Built-in drag value template
+    pub drag_value: DragValueTemplate,
+    /// Built-in text input template
+    pub text_input: TextInputTemplate,
+    /// Built-in dropdown template
+    pub dropdown: DropdownTemplate,
+    /// Built-in tabs header template
+    pub tabs: TabsTemplate,
 }
 
 impl Default for WidgetTemplates {
     fn default() -> Self {
         Self {
             button: ButtonTemplate::default(),
+            checkbox: ToggleTemplate::checkbox(),
+            toggle: ToggleTemplate::switch(),
+            slider: SliderTemplate::default(),
+            drag_value: DragValueTemplate::default(),
+            text_input: TextInputTemplate::default(),
+            dropdown: DropdownTemplate::default(),
+            tabs: TabsTemplate::default(),
         }
     }
 }
@@ -57,6 +81,277 @@ impl Default for ButtonTemplate {
     }
 }
 
+/// Template for checkboxes and toggle switches: a clickable box (or track) whose `checked`
+/// state style shows the value, a knob for switches and a label beside it
+#[derive(Debug, Clone)]
+pub struct ToggleTemplate {
+    /// Suffix of the box or track part's id
+    pub part_name: &'static str,
+    /// Default box or track size [width, height]
+    pub size: Vec2,
+    /// Background while unchecked
+    pub background_color: ColorDef,
+    /// Background while checked
+    pub checked_color: ColorDef,
+    /// Background while disabled
+    pub disabled_color: ColorDef,
+    pub border_width: Option<f32>,
+    pub border_color: Option<ColorDef>,
+    pub border_radius: Option<f32>,
+    /// Color of the knob that slides across the track; checkboxes have no knob
+    pub knob_color: Option<ColorDef>,
+    /// Gap between the knob and the edge of the track
+    pub knob_inset: f32,
+    pub label_color: ColorDef,
+    pub label_size: f32,
+    /// Gap between the box and its label
+    pub label_spacing: f32,
+}
+
+impl ToggleTemplate {
+    /// Defaults for `Checkbox` widgets
+    pub fn checkbox() -> Self {
+        Self {
+            part_name: "box",
+            size: Vec2::new(18.0, 18.0),
+            background_color: ColorDef::Named("white".to_string()),
+            checked_color: ColorDef::Named("royalblue".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            border_width: Some(1.0),
+            border_color: Some(ColorDef::Named("gray".to_string())),
+            border_radius: Some(3.0),
+            knob_color: None,
+            knob_inset: 0.0,
+            label_color: ColorDef::Named("black".to_string()),
+            label_size: 16.0,
+            label_spacing: 8.0,
+        }
+    }
+
+    /// Defaults for `Toggle` widgets
+    pub fn switch() -> Self {
+        Self {
+            part_name: "track",
+            size: Vec2::new(40.0, 22.0),
+            background_color: ColorDef::Named("darkgray".to_string()),
+            checked_color: ColorDef::Named("royalblue".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            border_width: None,
+            border_color: None,
+            border_radius: Some(11.0),
+            knob_color: Some(ColorDef::Named("white".to_string())),
+            knob_inset: 3.0,
+            label_color: ColorDef::Named("black".to_string()),
+            label_size: 16.0,
+            label_spacing: 8.0,
+        }
+    }
+
+    /// State styles showing the value and the disabled state
+    pub fn state_styles(&self) -> StateStyles {
+        StateStyles {
+            checked: Some(StyleOverrides {
+                background_color: Some(self.checked_color.clone()),
+                border_color: self.border_color.as_ref().map(|_| self.checked_color.clone()),
+                ..Default::default()
+            }),
+            ..disabled_state_styles(&self.disabled_color)
+        }
+    }
+}
+
+/// Template for sliders: a track with a round thumb dragged along it, and the value beside the
+/// track when the slider has a format
+#[derive(Debug, Clone)]
+pub struct SliderTemplate {
+    /// Default track size [length, thickness] of horizontal sliders; vertical ones stand it upright
+    pub size: Vec2,
+    pub track_color: ColorDef,
+    /// Track background while disabled
+    pub disabled_color: ColorDef,
+    pub thumb_color: ColorDef,
+    /// Gap between the thumb and the edge of the track
+    pub thumb_inset: f32,
+    pub label_color: ColorDef,
+    pub label_size: f32,
+    /// Gap between the track and the value
+    pub label_spacing: f32,
+}
+
+impl Default for SliderTemplate {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(160.0, 20.0),
+            track_color: ColorDef::Named("lightgray".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            thumb_color: ColorDef::Named("royalblue".to_string()),
+            thumb_inset: 2.0,
+            label_color: ColorDef::Named("black".to_string()),
+            label_size: 16.0,
+            label_spacing: 8.0,
+        }
+    }
+}
+
+/// Template for drag values: a box showing the value as editable text
+#[derive(Debug, Clone)]
+pub struct DragValueTemplate {
+    pub size: Vec2,
+    pub background_color: ColorDef,
+    /// Background while disabled
+    pub disabled_color: ColorDef,
+    pub border_width: Option<f32>,
+    pub border_color: Option<ColorDef>,
+    pub border_radius: Option<f32>,
+    pub text_color: ColorDef,
+    pub text_size: f32,
+}
+
+impl Default for DragValueTemplate {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(80.0, 24.0),
+            background_color: ColorDef::Named("white".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            border_width: Some(1.0),
+            border_color: Some(ColorDef::Named("gray".to_string())),
+            border_radius: Some(3.0),
+            text_color: ColorDef::Named("black".to_string()),
+            text_size: 16.0,
+        }
+    }
+}
+
+/// Template for text inputs: a box with the editable text and its placeholder inside
+#[derive(Debug, Clone)]
+pub struct TextInputTemplate {
+    pub size: Vec2,
+    pub background_color: ColorDef,
+    /// Background while disabled
+    pub disabled_color: ColorDef,
+    pub border_width: Option<f32>,
+    pub border_color: Option<ColorDef>,
+    pub border_radius: Option<f32>,
+    pub text_color: ColorDef,
+    pub text_size: f32,
+    pub placeholder_color: ColorDef,
+    /// Space between the box's left and right edges and its text
+    pub padding: f32,
+}
+
+impl Default for TextInputTemplate {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(200.0, 28.0),
+            background_color: ColorDef::Named("white".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            border_width: Some(1.0),
+            border_color: Some(ColorDef::Named("gray".to_string())),
+            border_radius: Some(3.0),
+            text_color: ColorDef::Named("black".to_string()),
+            text_size: 16.0,
+            placeholder_color: ColorDef::Named("gray".to_string()),
+            padding: 6.0,
+        }
+    }
+}
+
+/// Template for dropdowns: a box with the selected option and an arrow, and the list of options
+/// that opens under it
+#[derive(Debug, Clone)]
+pub struct DropdownTemplate {
+    pub size: Vec2,
+    pub background_color: ColorDef,
+    /// Background while disabled
+    pub disabled_color: ColorDef,
+    pub border_width: Option<f32>,
+    pub border_color: Option<ColorDef>,
+    pub border_radius: Option<f32>,
+    pub text_color: ColorDef,
+    pub text_size: f32,
+    /// Color of the placeholder shown while no option is selected
+    pub placeholder_color: ColorDef,
+    /// Space between the box's edges and its text and arrow, and between the rows' left edges
+    /// and their labels
+    pub padding: f32,
+    pub arrow_color: ColorDef,
+    pub arrow_size: Vec2,
+    /// Background of the option list
+    pub list_color: ColorDef,
+    /// Background of the option under the keyboard highlight
+    pub highlight_color: ColorDef,
+    pub row_height: f32,
+}
+
+impl Default for DropdownTemplate {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(160.0, 28.0),
+            background_color: ColorDef::Named("white".to_string()),
+            disabled_color: ColorDef::Named("gainsboro".to_string()),
+            border_width: Some(1.0),
+            border_color: Some(ColorDef::Named("gray".to_string())),
+            border_radius: Some(3.0),
+            text_color: ColorDef::Named("black".to_string()),
+            text_size: 16.0,
+            placeholder_color: ColorDef::Named("gray".to_string()),
+            padding: 6.0,
+            arrow_color: ColorDef::Named("dimgray".to_string()),
+            arrow_size: Vec2::new(10.0, 6.0),
+            list_color: ColorDef::Named("white".to_string()),
+            highlight_color: ColorDef::Named("lightsteelblue".to_string()),
+            row_height: 24.0,
+        }
+    }
+}
+
+/// Template for the header row of tabs widgets: a button per tab with its title, and a close
+/// button on closable tabs
+#[derive(Debug, Clone)]
+pub struct TabsTemplate {
+    pub header_height: f32,
+    /// Narrowest a tab's button gets, however short its title
+    pub tab_width: f32,
+    /// Background of the inactive tabs' buttons
+    pub tab_color: ColorDef,
+    /// Background of the active tab's button
+    pub active_tab_color: ColorDef,
+    pub text_color: ColorDef,
+    pub text_size: f32,
+    /// Space between a button's edges and its title and close button
+    pub padding: f32,
+    /// Side of the square close button
+    pub close_size: f32,
+    pub close_color: ColorDef,
+}
+
+impl Default for TabsTemplate {
+    fn default() -> Self {
+        Self {
+            header_height: 28.0,
+            tab_width: 80.0,
+            tab_color: ColorDef::Named("gainsboro".to_string()),
+            active_tab_color: ColorDef::Named("white".to_string()),
+            text_color: ColorDef::Named("black".to_string()),
+            text_size: 14.0,
+            padding: 8.0,
+            close_size: 14.0,
+            close_color: ColorDef::Named("silver".to_string()),
+        }
+    }
+}
+
+/// State styles showing that a widget is disabled
+fn disabled_state_styles(disabled_color: &ColorDef) -> StateStyles {
+    StateStyles {
+        disabled: Some(StyleOverrides {
+            background_color: Some(disabled_color.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
 /// Template definition that can be parsed from TOML
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -138,7 +433,7 @@ impl ButtonTemplate {
             widget_type: WidgetType::Shape {
                 shape_type: ShapeType::Rectangle,
             },
-            layout: user_layout.unwrap_or_else(|| LayoutConfig {
+            layout: user_layout.unwrap_or(LayoutConfig {
                 size: Some(size),
                 position: None,
                 margin: None,
@@ -151,7 +446,7 @@ impl ButtonTemplate {
             }),
             style: StyleConfig {
                 background_color: Some(background_color),
-                border_color: border_color,
+                border_color,
                 border_width,
                 border_radius,
                 text_color: None, // Shape doesn't need text color
@@ -159,7 +454,7 @@ impl ButtonTemplate {
                 opacity: None,
                 states: None,
             },
-            behavior: user_behavior.unwrap_or_else(|| BehaviorConfig {
+            behavior: user_behavior.unwrap_or(BehaviorConfig {
                 visible: Some(true),
                 interactive: Some(true),
                 draggable: Some(false),
@@ -218,14 +513,21 @@ impl ButtonTemplate {
 
 /// Global widget templates registry
 static WIDGET_TEMPLATES: std::sync::LazyLock<WidgetTemplates> = 
-    std::sync::LazyLock::new(|| WidgetTemplates::default());
+    std::sync::LazyLock::new(WidgetTemplates::default);
 
 /// Get the global widget templates
 pub fn get_widget_templates() -> &'static WidgetTemplates {
     &WIDGET_TEMPLATES
 }
 
-/// Expand template widgets directly from WidgetNode (unified architecture)
+/// Check whether a widget type is a template that expands into several primitives
+pub fn is_template_widget(widget_type: &WidgetType) -> bool {
+    matches!(widget_type, WidgetType::Button { .. } | WidgetType::Checkbox { .. } | WidgetType::Toggle { .. }
+        | WidgetType::Slider { .. } | WidgetType::DragValue { .. } | WidgetType::TextInput { .. } | WidgetType::Dropdown { .. })
+}
+
+/// Expand the built-in template widgets into their root node and parts; `UiRegistry::new`
+/// registers it for each of them, and other nodes are returned as they are
 pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Vec<crate::assets::definitions::WidgetNode> {
     use crate::widgets::blueprint::{WidgetType, ShapeType};
     use crate::assets::definitions::WidgetNode;
@@ -248,13 +550,17 @@ pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Ve
             let templates = get_widget_templates();
             let button_template = &templates.button;
             
-            // Apply template values with TOML overrides
+            // Apply template values with TOML overrides, then the node's style (classes included)
             let final_text = text.clone().unwrap_or_else(|| button_template.text.clone());
-            let final_bg_color = background_color.clone().unwrap_or_else(|| button_template.background_color.clone());
-            let final_text_color = text_color.clone().unwrap_or_else(|| button_template.text_color.clone());
+            let final_bg_color = background_color.clone()
+                .or_else(|| node.style.background_color.clone())
+                .unwrap_or_else(|| button_template.background_color.clone());
+            let final_text_color = text_color.clone()
+                .or_else(|| node.style.text_color.clone())
+                .unwrap_or_else(|| button_template.text_color.clone());
             // Prioritize layout size over widget_type size over template default
             let final_size = node.layout.size.or(*size).unwrap_or(button_template.size);
-            let final_text_size = text_size.unwrap_or(button_template.text_size);
+            let final_text_size = text_size.or(node.style.text_size).unwrap_or(button_template.text_size);
             
             // Create shape node (button background) - directly from WidgetNode data
             let shape_node = WidgetNode {
@@ -275,9 +581,11 @@ pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Ve
                 },
                 style: crate::widgets::blueprint::StyleConfig {
                     background_color: Some(final_bg_color),
-                    border_color: border_color.clone().or(button_template.border_color.clone()),
-                    border_width: border_width.or(button_template.border_width),
-                    border_radius: border_radius.or(button_template.border_radius),
+                    border_color: border_color.clone()
+                        .or_else(|| node.style.border_color.clone())
+                        .or(button_template.border_color.clone()),
+                    border_width: border_width.or(node.style.border_width).or(button_template.border_width),
+                    border_radius: border_radius.or(node.style.border_radius).or(button_template.border_radius),
                     text_color: None, // Shape doesn't need text color
                     text_size: None,  // Shape doesn't need text size
                     opacity: node.style.opacity,
@@ -295,6 +603,10 @@ pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Ve
                 classes: node.classes.clone(),
                 style_overrides: node.style_overrides.clone(),
                 bindings: node.bindings.clone(), // Button actions go to shape
+                state_bindings: node.state_bindings.clone(), // So do bound visibility and colors
+                repeat: None,
+                when: None,
+                localized: None,
                 children: vec![],
             };
 
@@ -338,12 +650,35 @@ pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Ve
                 classes: None,
                 style_overrides: None,
                 bindings: None, // No direct bindings - parent shape handles interaction
+                state_bindings: None,
+                repeat: None,
+                when: None,
+                localized: node.localized.clone(), // The label shows the localized text
                 children: vec![],
             };
 
             bevy_log::info!("Expanded Button '{}' into Shape + Text components", widget_id);
             vec![shape_node, text_node]
         }
+        WidgetType::Checkbox { checked, label, .. } => {
+            expand_toggle_node(node, &get_widget_templates().checkbox, *checked, label.as_deref())
+        }
+        WidgetType::Toggle { checked, label, .. } => {
+            expand_toggle_node(node, &get_widget_templates().toggle, *checked, label.as_deref())
+        }
+        WidgetType::Slider { orientation, .. } => {
+            expand_slider_node(node, &get_widget_templates().slider, *orientation)
+        }
+        WidgetType::DragValue { .. } => {
+            expand_drag_value_node(node, &get_widget_templates().drag_value)
+        }
+        WidgetType::TextInput { value, placeholder, disabled, .. } => {
+            expand_text_input_node(node, &get_widget_templates().text_input, value, placeholder.as_deref(), *disabled)
+        }
+        WidgetType::Dropdown { options, selected, placeholder, .. } => {
+            let selected = selected.as_deref().and_then(|selected| options.iter().find(|option| option.value() == selected));
+            expand_dropdown_node(node, &get_widget_templates().dropdown, selected.map(DropdownOption::label), placeholder.as_deref())
+        }
         _ => {
             // Not a template widget, return as-is
             vec![node.clone()]
@@ -351,6 +686,368 @@ pub fn expand_template_node(node: &crate::assets::definitions::WidgetNode) -> Ve
     }
 }
 
+/// The node's own state styles (classes included) over a template's
+fn merged_states(node: &crate::assets::definitions::WidgetNode, template_states: StateStyles) -> StateStyles {
+    match &node.style.states {
+        Some(states) => template_states.merged_with(states),
+        None => template_states,
+    }
+}
+
+/// The root of a template widget: a rectangle that takes the node's place in the layout and
+/// receives its input, bindings and classes
+fn template_root(
+    node: &crate::assets::definitions::WidgetNode,
+    part_name: &str,
+    size: Vec2,
+    style: StyleConfig,
+    draggable: bool,
+) -> crate::assets::definitions::WidgetNode {
+    use crate::assets::definitions::WidgetNode;
+
+    WidgetNode {
+        id: Some(format!("{}_{}", node.id.as_deref().unwrap_or("unnamed"), part_name)),
+        widget_type: WidgetType::Shape {
+            shape_type: ShapeType::Rectangle,
+        },
+        layout: LayoutConfig {
+            size: Some(size),
+            ..node.layout.clone()
+        },
+        style,
+        behavior: BehaviorConfig {
+            visible: Some(true),
+            interactive: Some(true),
+            draggable: Some(draggable),
+            clickable: Some(true),
+            focusable: Some(true),
+            z_index: node.behavior.z_index,
+            position_control: node.behavior.position_control.clone(),
+        },
+        classes: node.classes.clone(),
+        style_overrides: node.style_overrides.clone(),
+        bindings: node.bindings.clone(), // The root receives the input
+        state_bindings: node.state_bindings.clone(),
+        repeat: None,
+        when: None,
+        localized: None,
+        children: vec![],
+    }
+}
+
+/// A part of a template widget, placed relative to its root, above it and without input of
+/// its own
+fn template_part(
+    node: &crate::assets::definitions::WidgetNode,
+    part_name: &str,
+    widget_type: WidgetType,
+    size: Option<Vec2>,
+    position: Vec3,
+    style: StyleConfig,
+) -> crate::assets::definitions::WidgetNode {
+    use crate::assets::definitions::WidgetNode;
+
+    WidgetNode {
+        id: Some(format!("{}_{}", node.id.as_deref().unwrap_or("unnamed"), part_name)),
+        widget_type,
+        layout: LayoutConfig {
+            size,
+            position: Some(position),
+            margin: None,
+            padding: None,
+            flex_grow: None,
+            flex_shrink: None,
+            align_self: None,
+            grid_row: None,
+            grid_column: None,
+        },
+        style,
+        behavior: BehaviorConfig {
+            visible: Some(true),
+            interactive: Some(false),
+            draggable: Some(false),
+            clickable: Some(false),
+            focusable: Some(false),
+            z_index: Some(1),
+            position_control: Some(crate::layout::PositionControl::Manual),
+        },
+        classes: None,
+        style_overrides: None,
+        bindings: None,
+        state_bindings: None,
+        repeat: None,
+        when: None,
+        localized: None,
+        children: vec![],
+    }
+}
+
+/// Text part of a template widget, showing the node's localized text if it has any
+fn template_label(
+    node: &crate::assets::definitions::WidgetNode,
+    content: String,
+    editable: bool,
+    position: Vec3,
+    text_color: &ColorDef,
+    text_size: f32,
+) -> crate::assets::definitions::WidgetNode {
+    let mut label = template_part(
+        node,
+        "label",
+        WidgetType::Text { content, editable },
+        None, // Text size determined by content
+        position,
+        StyleConfig {
+            text_color: Some(node.style.text_color.clone().unwrap_or_else(|| text_color.clone())),
+            text_size: Some(node.style.text_size.unwrap_or(text_size)),
+            ..Default::default()
+        },
+    );
+    label.localized = node.localized.clone();
+    label
+}
+
+/// Expand a checkbox or toggle into its box (or track), its knob and its label
+fn expand_toggle_node(
+    node: &crate::assets::definitions::WidgetNode,
+    template: &ToggleTemplate,
+    checked: bool,
+    label: Option<&str>,
+) -> Vec<crate::assets::definitions::WidgetNode> {
+    let size = node.layout.size.unwrap_or(template.size);
+
+    let mut nodes = vec![template_root(node, template.part_name, size, StyleConfig {
+        background_color: Some(node.style.background_color.clone().unwrap_or_else(|| template.background_color.clone())),
+        border_color: node.style.border_color.clone().or_else(|| template.border_color.clone()),
+        border_width: node.style.border_width.or(template.border_width),
+        border_radius: node.style.border_radius.or(template.border_radius),
+        text_color: None,
+        text_size: None,
+        opacity: node.style.opacity,
+        states: Some(merged_states(node, template.state_styles())),
+    }, false)];
+
+    if let Some(ref knob_color) = template.knob_color {
+        let diameter = (size.y - 2.0 * template.knob_inset).max(0.0);
+        nodes.push(template_part(
+            node,
+            "knob",
+            WidgetType::Shape { shape_type: ShapeType::Circle },
+            Some(Vec2::splat(diameter)),
+            Vec3::new(knob_offset(size, checked), 0.0, 0.1),
+            StyleConfig {
+                background_color: Some(knob_color.clone()),
+                ..Default::default()
+            },
+        ));
+    }
+
+    if let Some(label) = label {
+        let position = Vec3::new(size.x / 2.0 + template.label_spacing, 0.0, 0.1);
+        nodes.push(template_label(node, label.to_string(), false, position, &template.label_color, template.label_size));
+    }
+
+    bevy_log::info!("Expanded '{}' into {} components", node.id.as_deref().unwrap_or("unnamed"), nodes.len());
+    nodes
+}
+
+/// Expand a slider into its track, its thumb and, if it has a format, its value
+fn expand_slider_node(
+    node: &crate::assets::definitions::WidgetNode,
+    template: &SliderTemplate,
+    orientation: SliderOrientation,
+) -> Vec<crate::assets::definitions::WidgetNode> {
+    let Some(numeric) = WidgetNumeric::from_widget_type(&node.widget_type) else {
+        return vec![node.clone()];
+    };
+    let size = node.layout.size.unwrap_or(match orientation {
+        SliderOrientation::Horizontal => template.size,
+        SliderOrientation::Vertical => Vec2::new(template.size.y, template.size.x),
+    });
+    let thickness = size.min_element();
+
+    let mut nodes = vec![template_root(node, "track", size, StyleConfig {
+        background_color: Some(node.style.background_color.clone().unwrap_or_else(|| template.track_color.clone())),
+        border_color: node.style.border_color.clone(),
+        border_width: node.style.border_width,
+        border_radius: Some(node.style.border_radius.unwrap_or(thickness / 2.0)),
+        text_color: None,
+        text_size: None,
+        opacity: node.style.opacity,
+        states: Some(merged_states(node, disabled_state_styles(&template.disabled_color))),
+    }, true)];
+
+    // Part positions are in layout coordinates, where y grows downwards
+    let offset = thumb_offset(size, orientation, numeric.range.fraction(numeric.value));
+    nodes.push(template_part(
+        node,
+        "thumb",
+        WidgetType::Shape { shape_type: ShapeType::Circle },
+        Some(Vec2::splat((thickness - 2.0 * template.thumb_inset).max(0.0))),
+        Vec3::new(offset.x, -offset.y, 0.1),
+        StyleConfig {
+            background_color: Some(template.thumb_color.clone()),
+            ..Default::default()
+        },
+    ));
+
+    if numeric.shows_value() {
+        let position = match orientation {
+            SliderOrientation::Horizontal => Vec3::new(size.x / 2.0 + template.label_spacing, 0.0, 0.1),
+            SliderOrientation::Vertical => Vec3::new(0.0, size.y / 2.0 + template.label_spacing, 0.1),
+        };
+        nodes.push(template_label(node, numeric.display_text(), false, position, &template.label_color, template.label_size));
+    }
+
+    bevy_log::info!("Expanded Slider '{}' into {} components", node.id.as_deref().unwrap_or("unnamed"), nodes.len());
+    nodes
+}
+
+/// Expand a drag value into its box and the editable text showing its value
+fn expand_drag_value_node(
+    node: &crate::assets::definitions::WidgetNode,
+    template: &DragValueTemplate,
+) -> Vec<crate::assets::definitions::WidgetNode> {
+    let Some(numeric) = WidgetNumeric::from_widget_type(&node.widget_type) else {
+        return vec![node.clone()];
+    };
+    let size = node.layout.size.unwrap_or(template.size);
+
+    let root = template_root(node, "box", size, StyleConfig {
+        background_color: Some(node.style.background_color.clone().unwrap_or_else(|| template.background_color.clone())),
+        border_color: node.style.border_color.clone().or_else(|| template.border_color.clone()),
+        border_width: node.style.border_width.or(template.border_width),
+        border_radius: node.style.border_radius.or(template.border_radius),
+        text_color: None,
+        text_size: None,
+        opacity: node.style.opacity,
+        states: Some(merged_states(node, disabled_state_styles(&template.disabled_color))),
+    }, true);
+
+    // The text takes its own clicks so that it can be typed into; the rest of the box is dragged
+    let mut label = template_label(node, numeric.display_text(), !numeric.disabled, Vec3::new(0.0, 0.0, 0.1), &template.text_color, template.text_size);
+    label.behavior.interactive = Some(!numeric.disabled);
+    label.behavior.focusable = Some(!numeric.disabled);
+
+    bevy_log::info!("Expanded DragValue '{}' into Shape + Text components", node.id.as_deref().unwrap_or("unnamed"));
+    vec![root, label]
+}
+
+/// Expand a text input into its box, its placeholder if it has one, and its editable text
+fn expand_text_input_node(
+    node: &crate::assets::definitions::WidgetNode,
+    template: &TextInputTemplate,
+    value: &str,
+    placeholder: Option<&str>,
+    disabled: bool,
+) -> Vec<crate::assets::definitions::WidgetNode> {
+    let size = node.layout.size.unwrap_or(template.size);
+    let text_size = node.style.text_size.unwrap_or(template.text_size);
+
+    let mut nodes = vec![template_root(node, "box", size, StyleConfig {
+        background_color: Some(node.style.background_color.clone().unwrap_or_else(|| template.background_color.clone())),
+        border_color: node.style.border_color.clone().or_else(|| template.border_color.clone()),
+        border_width: node.style.border_width.or(template.border_width),
+        border_radius: node.style.border_radius.or(template.border_radius),
+        text_color: None,
+        text_size: None,
+        opacity: node.style.opacity,
+        states: Some(merged_states(node, disabled_state_styles(&template.disabled_color))),
+    }, false)];
+
+    // Text is drawn down from the top-left of its line, so this centers it vertically
+    let position = Vec3::new(-size.x / 2.0 + template.padding, -text_size * 0.6, 0.1);
+
+    if let Some(placeholder) = placeholder {
+        let mut placeholder_node = template_part(
+            node,
+            "placeholder",
+            WidgetType::Text { content: placeholder.to_string(), editable: false },
+            None,
+            position,
+            StyleConfig {
+                text_color: Some(template.placeholder_color.clone()),
+                text_size: Some(text_size),
+                ..Default::default()
+            },
+        );
+        // A localized input translates its placeholder
+        placeholder_node.localized = node.localized.clone();
+        placeholder_node.behavior.visible = Some(value.is_empty());
+        nodes.push(placeholder_node);
+    }
+
+    let mut field = template_part(
+        node,
+        "field",
+        WidgetType::Text { content: value.to_string(), editable: !disabled },
+        None,
+        position,
+        StyleConfig {
+            text_color: Some(node.style.text_color.clone().unwrap_or_else(|| template.text_color.clone())),
+            text_size: Some(text_size),
+            ..Default::default()
+        },
+    );
+    field.behavior.interactive = Some(!disabled);
+    field.behavior.focusable = Some(!disabled);
+    nodes.push(field);
+
+    bevy_log::info!("Expanded TextInput '{}' into {} components", node.id.as_deref().unwrap_or("unnamed"), nodes.len());
+    nodes
+}
+
+/// Expand a dropdown into its box, the label of its selected option (or its placeholder) and its
+/// arrow. The option list is spawned when the dropdown opens.
+fn expand_dropdown_node(
+    node: &crate::assets::definitions::WidgetNode,
+    template: &DropdownTemplate,
+    selected: Option<&str>,
+    placeholder: Option<&str>,
+) -> Vec<crate::assets::definitions::WidgetNode> {
+    let size = node.layout.size.unwrap_or(template.size);
+
+    let root = template_root(node, "box", size, StyleConfig {
+        background_color: Some(node.style.background_color.clone().unwrap_or_else(|| template.background_color.clone())),
+        border_color: node.style.border_color.clone().or_else(|| template.border_color.clone()),
+        border_width: node.style.border_width.or(template.border_width),
+        border_radius: node.style.border_radius.or(template.border_radius),
+        text_color: None,
+        text_size: None,
+        opacity: node.style.opacity,
+        states: Some(merged_states(node, disabled_state_styles(&template.disabled_color))),
+    }, false);
+
+    // Text is drawn down from the top-left of its line, so this centers it vertically
+    let text_size = node.style.text_size.unwrap_or(template.text_size);
+    let position = Vec3::new(-size.x / 2.0 + template.padding, -text_size * 0.6, 0.1);
+    let content = selected.or(placeholder).unwrap_or_default();
+    // dropdown_label_system shows the placeholder in the placeholder color
+    let mut label = template_label(node, content.to_string(), false, position, &template.text_color, template.text_size);
+    // Option labels are shown as written
+    label.localized = None;
+
+    let half = template.arrow_size / 2.0;
+    let arrow = template_part(
+        node,
+        "arrow",
+        WidgetType::Shape {
+            shape_type: ShapeType::Custom {
+                vertices: vec![Vec2::new(-half.x, half.y), Vec2::new(half.x, half.y), Vec2::new(0.0, -half.y)],
+            },
+        },
+        Some(template.arrow_size),
+        Vec3::new(size.x / 2.0 - template.padding - half.x, 0.0, 0.1),
+        StyleConfig {
+            background_color: Some(template.arrow_color.clone()),
+            ..Default::default()
+        },
+    );
+
+    bevy_log::info!("Expanded Dropdown '{}' into Shape + Text + Shape components", node.id.as_deref().unwrap_or("unnamed"));
+    vec![root, label, arrow]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,6 +1094,10 @@ mod tests {
             classes: None,
             style_overrides: None,
             bindings: None,
+            state_bindings: None,
+            repeat: None,
+            when: None,
+            localized: None,
             children: vec![],
         };
         
@@ -428,7 +1129,7 @@ mod tests {
         
         if let WidgetType::Text { content, editable } = &text_node.widget_type {
             assert_eq!(content, "Save File");
-            assert_eq!(*editable, false);
+            assert!(!*editable);
         }
         
         // Verify text has white color and proper positioning
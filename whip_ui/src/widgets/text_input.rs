@@ -0,0 +1,495 @@
+use bevy_ecs::prelude::*;
+use thiserror::Error;
+use yrs::{GetString, Transact};
+use crate::gui_framework::components::{CursorState, Focus, SingleLineText, TextFilter, TextPattern, TextSelection, Visibility};
+use crate::gui_framework::events::{ActionEvent, EntityClicked, TextFocusChanged, TextSubmitted, YrsTextChanged};
+use crate::gui_framework::systems::value_action;
+use crate::widgets::blueprint::{InputFilter, WidgetType};
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetHierarchy};
+use crate::widgets::templates::get_widget_templates;
+use crate::YrsDocResource;
+
+/// Character shown in place of every character of a password field
+pub const PASSWORD_MASK: char = '•';
+
+/// Problems with a `TextInput` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TextInputError {
+    #[error("max_length must be at least 1")]
+    ZeroMaxLength,
+    #[error("value is {length} characters long, more than max_length ({max_length})")]
+    ValueTooLong { length: usize, max_length: usize },
+    #[error("value '{0}' is not accepted by the filter")]
+    ValueRejected(String),
+    #[error("Invalid filter regex: {0}")]
+    InvalidRegex(String),
+}
+
+impl TextInputError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            TextInputError::ZeroMaxLength => "max_length",
+            TextInputError::ValueTooLong { .. } | TextInputError::ValueRejected(_) => "value",
+            TextInputError::InvalidRegex(_) => "filter",
+        }
+    }
+}
+
+/// Events a text input fires with its text as the `value` parameter
+pub const TEXT_INPUT_VALUE_EVENTS: &[&str] = &["change", "submit"];
+
+/// Check the limits, filter and value of a `TextInput`; other widget types pass
+pub fn validate_text_input(widget_type: &WidgetType) -> Result<(), TextInputError> {
+    let WidgetType::TextInput { value, max_length, filter, .. } = widget_type else {
+        return Ok(());
+    };
+    if let Some(max_length) = *max_length {
+        if max_length == 0 {
+            return Err(TextInputError::ZeroMaxLength);
+        }
+        let length = value.chars().count();
+        if length > max_length {
+            return Err(TextInputError::ValueTooLong { length, max_length });
+        }
+    }
+    if let Some(filter) = filter {
+        let filter = filter.compile().map_err(|e| TextInputError::InvalidRegex(e.to_string()))?;
+        if !filter.matches(value) {
+            return Err(TextInputError::ValueRejected(value.clone()));
+        }
+    }
+    Ok(())
+}
+
+impl InputFilter {
+    /// The filter typing, submitted and committed text are checked with
+    pub fn compile(&self) -> Result<TextFilter, regex::Error> {
+        Ok(match self {
+            InputFilter::Integer => TextFilter::Integer,
+            InputFilter::Numeric => TextFilter::Numeric,
+            InputFilter::Regex(pattern) => TextFilter::Regex(TextPattern::new(pattern)?),
+        })
+    }
+}
+
+/// Component on the box of a `TextInput` widget holding its settings and last committed value.
+/// The text being typed lives in the yrs text of its field.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetTextInput {
+    pub placeholder: Option<String>,
+    pub max_length: Option<usize>,
+    pub filter: Option<TextFilter>,
+    pub password: bool,
+    pub disabled: bool,
+    /// Value last sent to the `change` binding, or the authored value
+    pub committed: String,
+}
+
+impl WidgetTextInput {
+    /// The input state of a `TextInput` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        let WidgetType::TextInput { value, placeholder, max_length, filter, password, disabled } = widget_type else {
+            return None;
+        };
+        // Invalid filters are reported by validation and ignored here
+        let filter = filter.as_ref().and_then(|filter| match filter.compile() {
+            Ok(filter) => Some(filter),
+            Err(e) => {
+                bevy_log::warn!("Ignoring invalid TextInput filter: {}", e);
+                None
+            }
+        });
+        Some(Self {
+            placeholder: placeholder.clone(),
+            max_length: *max_length,
+            filter,
+            password: *password,
+            disabled: *disabled,
+            committed: value.clone(),
+        })
+    }
+
+    /// The editable text part of the input; it comes after the placeholder, if there is one
+    pub fn field(hierarchy: &WidgetHierarchy) -> Option<Entity> {
+        hierarchy.children.last().copied()
+    }
+
+    /// Whether `value` is complete enough to submit or commit
+    pub fn accepts(&self, value: &str) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter.matches(value))
+    }
+
+    /// The placeholder part of the input
+    pub fn placeholder_part(&self, hierarchy: &WidgetHierarchy) -> Option<Entity> {
+        self.placeholder.as_ref().and(hierarchy.children.first().copied()).filter(|_| hierarchy.children.len() > 1)
+    }
+}
+
+/// Current text of a text input's field
+pub(crate) fn field_text(yrs_res: &YrsDocResource, field: Entity) -> Option<String> {
+    let text_map = yrs_res.text_map.lock().ok()?;
+    let text_ref = text_map.get(&field)?;
+    Some(text_ref.get_string(&yrs_res.doc.transact()))
+}
+
+/// System that keeps the single-line settings of text input fields in line with their inputs
+pub fn text_input_field_system(
+    mut commands: Commands,
+    inputs: Query<(&WidgetTextInput, &Widget, &WidgetHierarchy), Changed<WidgetTextInput>>,
+    mut fields: Query<Option<&mut SingleLineText>>,
+) {
+    let padding = get_widget_templates().text_input.padding;
+    for (input, widget, hierarchy) in inputs.iter() {
+        let Some(field) = WidgetTextInput::field(hierarchy) else {
+            continue;
+        };
+        let Ok(single_line) = fields.get_mut(field) else {
+            continue;
+        };
+        let settings = SingleLineText {
+            max_length: input.max_length,
+            filter: input.filter.clone(),
+            mask: input.password.then_some(PASSWORD_MASK),
+            width: widget.blueprint.layout.size.map(|size| (size.x - 2.0 * padding).max(0.0)),
+            scroll: 0.0,
+        };
+        match single_line {
+            Some(mut single_line) => *single_line = SingleLineText { scroll: single_line.scroll, ..settings },
+            None => {
+                commands.entity(field).insert(settings);
+            }
+        }
+    }
+}
+
+/// System that focuses a text input's field, with the cursor at the end, when its box is clicked
+pub fn text_input_focus_system(
+    mut commands: Commands,
+    mut click_events: EventReader<EntityClicked>,
+    inputs: Query<(&WidgetTextInput, &WidgetHierarchy)>,
+    fields: Query<&SingleLineText>,
+    yrs_res: Res<YrsDocResource>,
+    mut text_focus_writer: EventWriter<TextFocusChanged>,
+) {
+    for click_event in click_events.read() {
+        let Ok((input, hierarchy)) = inputs.get(click_event.entity) else {
+            continue;
+        };
+        if input.disabled {
+            continue;
+        }
+        let Some(field) = WidgetTextInput::field(hierarchy) else {
+            continue;
+        };
+        let text = field_text(&yrs_res, field).unwrap_or_default();
+        // Cursor positions are in the shown text, which may be masked
+        let end = match fields.get(field) {
+            Ok(single_line) => single_line.display_text(&text).len(),
+            Err(_) => text.len(),
+        };
+        commands.entity(field).insert((
+            Focus,
+            CursorState { position: end, line: 0, x_goal: None },
+            TextSelection { start: end, end },
+        ));
+        text_focus_writer.send(TextFocusChanged { entity: Some(field) });
+    }
+}
+
+/// System that fires the `submit` binding of text inputs when Enter is pressed in them, unless
+/// their filter does not accept the whole text
+pub fn text_input_submit_system(
+    mut submitted_events: EventReader<TextSubmitted>,
+    fields: Query<&WidgetHierarchy, Without<WidgetTextInput>>,
+    inputs: Query<(&WidgetTextInput, Option<&WidgetActionBindings>)>,
+    yrs_res: Res<YrsDocResource>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for submitted_event in submitted_events.read() {
+        let Some(entity) = fields.get(submitted_event.entity).ok().and_then(|hierarchy| hierarchy.parent) else {
+            continue;
+        };
+        let Ok((input, bindings)) = inputs.get(entity) else {
+            continue;
+        };
+        if input.disabled {
+            continue;
+        }
+        let value = field_text(&yrs_res, submitted_event.entity).unwrap_or_default();
+        if !input.accepts(&value) {
+            continue;
+        }
+        bevy_log::debug!("Text input {:?} submitted", entity);
+        if let Some(action) = value_action(bindings, entity, "submit", serde_json::Value::String(value)) {
+            action_events.send(action);
+        }
+    }
+}
+
+/// System that fires the `change` binding of text inputs whose text changed once their field
+/// loses focus. Text their filter does not accept in full goes back to the committed value.
+pub fn text_input_blur_system(
+    mut focus_events: EventReader<TextFocusChanged>,
+    mut editing: Local<Option<Entity>>,
+    fields: Query<&WidgetHierarchy, Without<WidgetTextInput>>,
+    mut inputs: Query<(&mut WidgetTextInput, Option<&WidgetActionBindings>)>,
+    yrs_res: Res<YrsDocResource>,
+    mut action_events: EventWriter<ActionEvent>,
+    mut text_changed_events: EventWriter<YrsTextChanged>,
+) {
+    let input_of = |field: Entity| {
+        fields.get(field).ok()?.parent
+    };
+
+    for focus_event in focus_events.read() {
+        let left = editing.take().filter(|field| focus_event.entity != Some(*field));
+        if let Some((field, entity)) = left.and_then(|field| Some((field, input_of(field)?))) {
+            if let Ok((mut input, bindings)) = inputs.get_mut(entity) {
+                let changed = field_text(&yrs_res, field).filter(|value| !input.disabled && *value != input.committed);
+                if let Some(value) = changed.as_ref().filter(|value| !input.accepts(value)) {
+                    bevy_log::debug!("Text input {:?} does not accept '{}'", entity, value);
+                    crate::assets::reload::replace_widget_text(&yrs_res, field, &input.committed);
+                    text_changed_events.send(YrsTextChanged { entity: field });
+                } else if let Some(value) = changed {
+                    // Only the committed value changes; the field keeps its settings
+                    input.bypass_change_detection().committed = value.clone();
+                    if let Some(action) = value_action(bindings, entity, "change", serde_json::Value::String(value)) {
+                        action_events.send(action);
+                    }
+                }
+            }
+        }
+
+        *editing = focus_event.entity.filter(|field| {
+            input_of(*field).is_some_and(|entity| inputs.contains(entity))
+        });
+    }
+}
+
+/// System that shows the placeholder of text inputs that are empty and not being typed into
+pub fn text_input_placeholder_system(
+    inputs: Query<(&WidgetTextInput, &WidgetHierarchy)>,
+    focused: Query<(), With<Focus>>,
+    mut visibilities: Query<&mut Visibility, Without<WidgetTextInput>>,
+    yrs_res: Res<YrsDocResource>,
+) {
+    for (input, hierarchy) in inputs.iter() {
+        let (Some(placeholder), Some(field)) = (input.placeholder_part(hierarchy), WidgetTextInput::field(hierarchy)) else {
+            continue;
+        };
+        let empty = field_text(&yrs_res, field).unwrap_or_default().is_empty();
+        if let Ok(mut visibility) = visibilities.get_mut(placeholder) {
+            visibility.set_if_neq(Visibility(empty && !focused.contains(field)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::{setup_world, spawn_child, drain_actions};
+    use crate::gui_framework::components::{CursorState, Focus, SingleLineText, TextFilter, Visibility};
+    use crate::gui_framework::events::{EntityClicked, TextFocusChanged, TextSubmitted, YrsTextChanged};
+    use crate::gui_framework::interaction::single_line::{byte_offset, char_index, LineEdit, LineEditKey, LineEditor};
+    use crate::widgets::blueprint::{InputFilter, WidgetType};
+    use crate::widgets::components::WidgetHierarchy;
+    use crate::widgets::templates::expand_template_node;
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+
+    const FORM: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "name"
+widget_type = { type = "TextInput", placeholder = "Your name", max_length = 20 }
+bindings = { submit = { event = "submit", action = "set_state", params = { path = "form.name" } }, change = { event = "change", action = "set_state", params = { path = "form.draft" } } }
+
+[[root.children]]
+id = "pin"
+widget_type = { type = "TextInput", value = "1234", filter = "Integer", password = true }
+
+[[root.children]]
+id = "code"
+widget_type = { type = "TextInput", filter = { Regex = "[A-Z]{2}-[0-9]{3}" } }
+bindings = { submit = { event = "submit", action = "set_state", params = { path = "form.code" } }, change = { event = "change", action = "set_state", params = { path = "form.code" } } }
+"##;
+
+    /// Test single-line editing with a length limit, filters, selections and masking
+    #[test]
+    fn test_line_editing_limits_and_filters() {
+        let limits = SingleLineText {
+            max_length: Some(5),
+            filter: Some(InputFilter::Integer.compile().unwrap()),
+            ..Default::default()
+        };
+        let mut editor = LineEditor { text: "12".to_string(), cursor: 2, anchor: 2 };
+        assert_eq!(editor.apply(&LineEditKey::Insert("3a".to_string()), false, &limits), None, "The filter rejects letters");
+        assert_eq!(
+            editor.apply(&LineEditKey::Insert("345678".to_string()), false, &limits),
+            Some(LineEdit { offset: 2, removed: 0, inserted: "345".to_string() }),
+            "Typing stops at max_length"
+        );
+        assert_eq!(editor.text, "12345");
+
+        // Shift+Home selects back to the start, and Backspace removes the selection
+        editor.apply(&LineEditKey::Left, false, &limits);
+        editor.apply(&LineEditKey::Home, true, &limits);
+        assert_eq!((editor.cursor, editor.anchor), (0, 4));
+        assert_eq!(editor.apply(&LineEditKey::Backspace, false, &limits), Some(LineEdit { offset: 0, removed: 4, inserted: String::new() }));
+        assert_eq!((editor.text.as_str(), editor.cursor), ("5", 0));
+        assert!(editor.apply(&LineEditKey::Insert("-".to_string()), false, &limits).is_some());
+        assert_eq!(editor.text, "-5");
+
+        let regex = InputFilter::Regex("[A-Z]{2}-[0-9]{3}".to_string()).compile().unwrap();
+        assert!(regex.accepts("AB-1"), "Typing only has to start a match");
+        assert!(!regex.matches("AB-1"));
+        assert!(!regex.accepts("AB1"), "Text no continuation can match is rejected");
+        assert!(regex.matches("AB-123"));
+        assert!(!regex.accepts("AB-1234"));
+        assert!(TextFilter::Numeric.accepts("-1.5"));
+        assert!(!TextFilter::Numeric.accepts("1.2.3"));
+        assert!(TextFilter::Integer.accepts("-") && !TextFilter::Integer.matches("-"));
+
+        let password = SingleLineText { mask: Some(PASSWORD_MASK), width: Some(100.0), ..Default::default() };
+        let shown = password.display_text("pw");
+        assert_eq!(shown, "••");
+        assert_eq!((byte_offset(&shown, 1), char_index(&shown, 3)), (3, 1), "The mask is wider in bytes than the text");
+        assert_eq!(password.scroll_to_show(Some(150.0), 200.0), 50.0);
+        assert_eq!(SingleLineText { scroll: 50.0, ..password.clone() }.scroll_to_show(Some(20.0), 200.0), 20.0);
+        assert_eq!(password.scroll_to_show(Some(90.0), 80.0), 0.0, "Short lines are not scrolled");
+    }
+
+    /// Test that text inputs expand into a box, placeholder and field, and are validated
+    #[test]
+    fn test_text_input_expansion_and_validation() {
+        let ui_def = UiDefinitionLoader.parse_definition(FORM, UiDefinitionFormat::Toml).unwrap();
+        assert!(ui_def.validation_report_with_registry(&UiRegistry::new()).errors().next().is_none());
+
+        let name = expand_template_node(&ui_def.root.children[0]);
+        let ids: Vec<_> = name.iter().map(|part| part.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["name_box", "name_placeholder", "name_field"]);
+        assert!(name[0].bindings.as_ref().unwrap().contains_key("submit"), "The box receives the bindings");
+        assert!(matches!(&name[1].widget_type, WidgetType::Text { content, editable: false } if content == "Your name"));
+        assert_eq!(name[1].behavior.visible, Some(true));
+        assert!(matches!(&name[2].widget_type, WidgetType::Text { content, editable: true } if content.is_empty()));
+
+        let pin = expand_template_node(&ui_def.root.children[1]);
+        assert_eq!(pin.len(), 2, "Inputs without a placeholder have no placeholder part");
+
+        let invalid = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+widget_type = { type = "TextInput", max_length = 0 }
+
+[[root.children]]
+widget_type = { type = "TextInput", value = "12a", filter = "Integer" }
+
+[[root.children]]
+widget_type = { type = "TextInput", filter = { Regex = "[a-" } }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "root.children[0].widget_type.max_length",
+            "root.children[1].widget_type.value",
+            "root.children[2].widget_type.filter",
+        ]);
+
+        // The field of a password input is masked and scrolls within the box's padding
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(FORM, UiDefinitionFormat::Toml).unwrap();
+        let pin = spawn_child(&mut world, &yrs_res, &ui_def, 1);
+        world.run_system_once(text_input_field_system).unwrap();
+        let field = WidgetTextInput::field(world.get::<WidgetHierarchy>(pin).unwrap()).unwrap();
+        let single_line = world.get::<SingleLineText>(field).unwrap();
+        assert_eq!(single_line.mask, Some(PASSWORD_MASK));
+        assert_eq!(single_line.width, Some(188.0));
+        assert!(single_line.filter.is_some());
+        assert_eq!(world.get::<WidgetTextInput>(pin).unwrap().committed, "1234");
+    }
+
+    /// Test that partially typed text is neither submitted nor committed
+    #[test]
+    fn test_text_input_requires_whole_match() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(FORM, UiDefinitionFormat::Toml).unwrap();
+        let code = spawn_child(&mut world, &yrs_res, &ui_def, 2);
+        let field = WidgetTextInput::field(world.get::<WidgetHierarchy>(code).unwrap()).unwrap();
+
+        crate::assets::reload::replace_widget_text(&yrs_res, field, "AB-1");
+        world.send_event(TextSubmitted { entity: field });
+        world.run_system_once(text_input_submit_system).unwrap();
+        assert!(drain_actions(&mut world).is_empty(), "Incomplete text is not submitted");
+
+        world.send_event(TextFocusChanged { entity: Some(field) });
+        world.send_event(TextFocusChanged { entity: None });
+        world.run_system_once(text_input_blur_system).unwrap();
+        assert!(drain_actions(&mut world).is_empty(), "Incomplete text is not committed");
+        assert_eq!(field_text(&yrs_res, field).as_deref(), Some(""), "The field goes back to the committed value");
+        let changed: Vec<_> = world.resource_mut::<Events<YrsTextChanged>>().drain().map(|event| event.entity).collect();
+        assert_eq!(changed, [field]);
+
+        world.resource_mut::<Events<TextFocusChanged>>().clear();
+        crate::assets::reload::replace_widget_text(&yrs_res, field, "AB-123");
+        world.send_event(TextFocusChanged { entity: Some(field) });
+        world.send_event(TextFocusChanged { entity: None });
+        world.run_system_once(text_input_blur_system).unwrap();
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].params.as_ref().unwrap()["value"], serde_json::json!("AB-123"));
+    }
+
+    /// Test that Enter fires the submit binding and leaving the field fires change
+    #[test]
+    fn test_text_input_submit_and_change() {
+        let (mut world, yrs_res) = setup_world();
+        let ui_def = UiDefinitionLoader.parse_definition(FORM, UiDefinitionFormat::Toml).unwrap();
+        let name = spawn_child(&mut world, &yrs_res, &ui_def, 0);
+        world.run_system_once(text_input_field_system).unwrap();
+        let children = world.get::<WidgetHierarchy>(name).unwrap().children.clone();
+        let (placeholder, field) = (children[0], children[1]);
+
+        // Clicking the box focuses the field with the cursor at the end
+        crate::assets::reload::replace_widget_text(&yrs_res, field, "Ada");
+        world.send_event(EntityClicked { entity: name });
+        world.run_system_once(text_input_focus_system).unwrap();
+        assert!(world.get::<Focus>(field).is_some());
+        assert_eq!(world.get::<CursorState>(field).unwrap().position, 3);
+        world.run_system_once(text_input_placeholder_system).unwrap();
+        assert_eq!(*world.get::<Visibility>(placeholder).unwrap(), Visibility(false));
+
+        world.send_event(TextSubmitted { entity: field });
+        world.run_system_once(text_input_submit_system).unwrap();
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].event_type, "submit");
+        assert_eq!(actions[0].get_string_param("path").as_deref(), Some("form.name"));
+        assert_eq!(actions[0].params.as_ref().unwrap()["value"], serde_json::json!("Ada"));
+
+        // Leaving the field commits the text
+        world.send_event(TextFocusChanged { entity: None });
+        world.run_system_once(text_input_blur_system).unwrap();
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].event_type.as_str(), actions[0].get_string_param("path").as_deref()), ("change", Some("form.draft")));
+        assert_eq!(world.get::<WidgetTextInput>(name).unwrap().committed, "Ada");
+
+        world.send_event(TextFocusChanged { entity: Some(field) });
+        world.send_event(TextFocusChanged { entity: None });
+        world.run_system_once(text_input_blur_system).unwrap();
+        assert!(drain_actions(&mut world).is_empty(), "Unchanged text is not committed again");
+
+        // An empty field shows its placeholder again once it loses focus
+        world.entity_mut(field).remove::<Focus>();
+        crate::assets::reload::replace_widget_text(&yrs_res, field, "");
+        world.run_system_once(text_input_placeholder_system).unwrap();
+        assert_eq!(*world.get::<Visibility>(placeholder).unwrap(), Visibility(true));
+    }
+}
\ No newline at end of file
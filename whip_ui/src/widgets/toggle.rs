@@ -0,0 +1,224 @@
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_transform::prelude::Transform;
+use crate::gui_framework::events::{ActionEvent, EntityClicked};
+use crate::gui_framework::systems::change_action;
+use crate::widgets::blueprint::WidgetType;
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetHierarchy};
+
+/// Which widget a [`WidgetToggle`] was spawned for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToggleKind {
+    Checkbox,
+    /// Toggle switch, whose knob slides to show the value
+    Switch,
+}
+
+/// Component on the box (or track) of a `Checkbox` or `Toggle` widget holding its value
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct WidgetToggle {
+    pub kind: ToggleKind,
+    pub checked: bool,
+    pub disabled: bool,
+}
+
+impl WidgetToggle {
+    /// The toggle state of a `Checkbox` or `Toggle` widget type
+    pub fn from_widget_type(widget_type: &WidgetType) -> Option<Self> {
+        match *widget_type {
+            WidgetType::Checkbox { checked, disabled, .. } => Some(Self { kind: ToggleKind::Checkbox, checked, disabled }),
+            WidgetType::Toggle { checked, disabled, .. } => Some(Self { kind: ToggleKind::Switch, checked, disabled }),
+            _ => None,
+        }
+    }
+}
+
+/// Events a checkbox or toggle fires with its new value as the `value` parameter
+pub const TOGGLE_VALUE_EVENTS: &[&str] = &["change"];
+
+/// Horizontal offset of a switch knob from the center of a track of `track_size`
+pub fn knob_offset(track_size: Vec2, checked: bool) -> f32 {
+    let travel = ((track_size.x - track_size.y) / 2.0).max(0.0);
+    if checked { travel } else { -travel }
+}
+
+/// System that flips checkboxes and toggles when they are clicked and fires their `change`
+/// binding with the new value
+pub fn toggle_click_system(
+    mut click_events: EventReader<EntityClicked>,
+    mut toggles: Query<(&mut WidgetToggle, Option<&WidgetActionBindings>)>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    for click_event in click_events.read() {
+        let Ok((mut toggle, bindings)) = toggles.get_mut(click_event.entity) else {
+            continue;
+        };
+        if toggle.disabled {
+            continue;
+        }
+        toggle.checked = !toggle.checked;
+        bevy_log::debug!("Toggle {:?} is now {}", click_event.entity, if toggle.checked { "checked" } else { "unchecked" });
+
+        if let Some(action) = change_action(bindings, click_event.entity, serde_json::Value::Bool(toggle.checked)) {
+            action_events.send(action);
+        }
+    }
+}
+
+/// System that slides switch knobs to the side matching their value
+pub fn toggle_knob_system(
+    toggles: Query<(&WidgetToggle, &Widget, &WidgetHierarchy), Changed<WidgetToggle>>,
+    mut parts: Query<(&Widget, &mut Transform), Without<WidgetToggle>>,
+) {
+    for (toggle, widget, hierarchy) in toggles.iter() {
+        if toggle.kind != ToggleKind::Switch {
+            continue;
+        }
+        let Some(track_size) = widget.blueprint.layout.size else {
+            continue;
+        };
+        // The knob is the switch's only shape part; the other one is its label
+        for &child in &hierarchy.children {
+            if let Ok((part, mut transform)) = parts.get_mut(child) {
+                if matches!(part.blueprint.widget_type, WidgetType::Shape { .. }) {
+                    transform.translation.x = knob_offset(track_size, toggle.checked);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::gui_framework::events::EntityClicked;
+    use crate::gui_framework::systems::{style_resolution_system, ResolvedStyle};
+    use crate::widgets::blueprint::{ColorDef, WidgetType};
+    use crate::widgets::components::{Widget, WidgetActionBindings, WidgetHierarchy};
+    use crate::widgets::templates::{expand_template_node, is_template_widget};
+    use crate::test_support::{drain_actions, setup_world, spawn_child};
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_transform::prelude::Transform;
+
+    const SETTINGS: &str = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "autosave"
+widget_type = { type = "Checkbox", label = "Autosave", checked = true }
+bindings = { change = { event = "change", action = "set_state", params = { path = "settings.autosave" } } }
+style = { states = { checked = { background_color = "seagreen" } } }
+
+[[root.children]]
+id = "dark_mode"
+widget_type = { type = "Toggle", label = "Dark mode", disabled = true }
+"##;
+
+    /// Test that checkboxes and toggles expand into a clickable box and non-interactive parts
+    #[test]
+    fn test_toggle_expansion() {
+        let ui_def = UiDefinitionLoader.parse_definition(SETTINGS, UiDefinitionFormat::Toml).unwrap();
+        assert!(!ui_def.validation_report_with_registry(&UiRegistry::new()).has_errors());
+        let (autosave, dark_mode) = (&ui_def.root.children[0], &ui_def.root.children[1]);
+        assert!(is_template_widget(&autosave.widget_type) && is_template_widget(&dark_mode.widget_type));
+
+        let parts = expand_template_node(autosave);
+        let ids: Vec<_> = parts.iter().map(|part| part.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["autosave_box", "autosave_label"]);
+        assert_eq!(parts[0].behavior.clickable, Some(true));
+        assert!(parts[0].bindings.as_ref().unwrap().contains_key("change"), "The box receives the clicks");
+        let states = parts[0].style.states.as_ref().unwrap();
+        assert_eq!(states.checked.as_ref().unwrap().background_color, Some(ColorDef::Named("seagreen".to_string())), "Authored state styles win");
+        assert!(states.disabled.is_some(), "Template state styles stay underneath");
+        assert!(matches!(&parts[1].widget_type, WidgetType::Text { content, .. } if content == "Autosave"));
+        assert_eq!(parts[1].behavior.clickable, Some(false));
+
+        let parts = expand_template_node(dark_mode);
+        let ids: Vec<_> = parts.iter().map(|part| part.id.as_deref().unwrap()).collect();
+        assert_eq!(ids, vec!["dark_mode_track", "dark_mode_knob", "dark_mode_label"]);
+        assert_eq!(parts[1].layout.position.unwrap().x, -9.0, "Unchecked knobs sit on the left of the track");
+
+        let mut unlabeled = autosave.clone();
+        unlabeled.widget_type = WidgetType::Checkbox { checked: false, label: Some(String::new()), disabled: false };
+        let mut invalid = ui_def.clone();
+        invalid.root.children[0] = unlabeled;
+        assert!(invalid.validation_report().has_errors(), "Empty labels are rejected");
+    }
+
+    /// Test that only widgets firing an event with a value may leave `value` out of its bindings
+    #[test]
+    fn test_value_parameter_comes_from_the_widget() {
+        let ui_def = UiDefinitionLoader.parse_definition(SETTINGS, UiDefinitionFormat::Toml).unwrap();
+        let registry = UiRegistry::new();
+        let autosave = &ui_def.root.children[0];
+        let binding = &autosave.bindings.as_ref().unwrap()["change"];
+        assert!(registry.validate_widget_action_binding(&autosave.widget_type, binding).is_ok());
+        assert!(registry.validate_action_binding(binding).is_err(), "Global actions have no widget to fill in the value");
+
+        let button = r##"
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+id = "save"
+widget_type = { type = "Button", text = "Save" }
+bindings = { change = { event = "change", action = "set_state", params = { path = "settings.saved" } } }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(button, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report_with_registry(&registry);
+        let paths: Vec<_> = report.errors().map(|diagnostic| diagnostic.path.as_str()).collect();
+        assert_eq!(paths, vec!["root.children[0].bindings.change"], "Buttons never fill in a value");
+    }
+
+    /// Test that clicks flip the value and fire the change binding with it, unless disabled
+    #[test]
+    fn test_toggle_click_fires_change() {
+        let (mut world, _) = setup_world();
+
+        let ui_def = UiDefinitionLoader.parse_definition(SETTINGS, UiDefinitionFormat::Toml).unwrap();
+        let bindings = ui_def.root.children[0].bindings.clone().unwrap();
+        let autosave = world.spawn((
+            WidgetToggle::from_widget_type(&ui_def.root.children[0].widget_type).unwrap(),
+            WidgetActionBindings { bindings },
+        )).id();
+        let dark_mode = world.spawn(WidgetToggle::from_widget_type(&ui_def.root.children[1].widget_type).unwrap()).id();
+        assert_eq!(world.get::<WidgetToggle>(dark_mode).unwrap().kind, ToggleKind::Switch);
+
+        world.send_event(EntityClicked { entity: autosave });
+        world.send_event(EntityClicked { entity: dark_mode });
+        world.run_system_once(toggle_click_system).unwrap();
+
+        assert!(!world.get::<WidgetToggle>(autosave).unwrap().checked);
+        assert!(!world.get::<WidgetToggle>(dark_mode).unwrap().checked, "Disabled toggles ignore clicks");
+
+        let actions = drain_actions(&mut world);
+        assert_eq!(actions.len(), 1);
+        assert_eq!((actions[0].action.as_str(), actions[0].event_type.as_str()), ("set_state", "change"));
+        assert_eq!(actions[0].get_string_param("path").as_deref(), Some("settings.autosave"));
+        assert_eq!(actions[0].get_bool_param("value"), Some(false));
+    }
+
+    /// Test that a spawned toggle carries its value and shows it through the knob and state style
+    #[test]
+    fn test_spawned_toggle_shows_value() {
+        let (mut world, yrs_res) = setup_world();
+
+        let mut ui_def = UiDefinitionLoader.parse_definition(SETTINGS, UiDefinitionFormat::Toml).unwrap();
+        ui_def.root.children[1].widget_type = WidgetType::Toggle { checked: true, label: None, disabled: false };
+        let track = spawn_child(&mut world, &yrs_res, &ui_def, 1);
+
+        assert_eq!(world.get::<Widget>(track).unwrap().id, "dark_mode_track");
+        assert!(world.get::<WidgetToggle>(track).unwrap().checked);
+        let knob = world.get::<WidgetHierarchy>(track).unwrap().children[0];
+
+        world.get_mut::<Transform>(knob).unwrap().translation.x = 0.0;
+        world.run_system_once(toggle_knob_system).unwrap();
+        assert_eq!(world.get::<Transform>(knob).unwrap().translation.x, 9.0);
+
+        world.run_system_once(style_resolution_system).unwrap();
+        let resolved = world.get::<ResolvedStyle>(track).unwrap();
+        assert_eq!(resolved.style.background_color, Some(ColorDef::Named("royalblue".to_string())));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,757 @@
+use bevy_app::App;
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Children, Parent};
+use bevy_log::error;
+use bevy_math::Vec2;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+use thiserror::Error;
+use crate::assets::definitions::WidgetNode;
+use crate::assets::dynamic::{expand_repeat_item, owning_definition, RepeatConfig};
+use crate::assets::reload::{collect_subtree, despawn_widgets, set_static_text};
+use crate::assets::state::is_valid_state_segment;
+use crate::assets::systems::spawn_child_widget;
+use crate::assets::windows::WidgetWindows;
+use crate::assets::{LoadingUiAssets, UiDefinition, UiRegistry, UiState};
+use crate::gui_framework::components::{ShapeData, Text, TextLayoutOutput};
+use crate::gui_framework::events::YrsTextChanged;
+use crate::layout::{PositionControl, TaffyResource, UiNode};
+use crate::logging::{get_log_store, LogFilter, LogLevel};
+use crate::widgets::blueprint::WidgetType;
+use crate::widgets::components::{Widget, WidgetActionBindings, WidgetHierarchy, WidgetLayout, WidgetText};
+use crate::widgets::scroll_view::{drawn_bounds, validate_scroll_view, ScrollViewError, WidgetScrollView};
+use crate::widgets::templates::is_template_widget;
+use crate::YrsDocResource;
+
+/// Rows kept spawned beyond each edge of the viewport when a list does not set `overscan`
+pub const DEFAULT_OVERSCAN: usize = 4;
+
+/// Height assumed for rows that have not been measured yet, until some have
+pub const ESTIMATED_ROW_HEIGHT: f32 = 24.0;
+
+/// Name the central log store is registered under as a provider
+pub const LOG_STORE_PROVIDER: &str = "log_store";
+
+/// Problems with a `VirtualList` widget type
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum VirtualListError {
+    #[error("a VirtualList needs either items or a provider")]
+    MissingSource,
+    #[error("a VirtualList takes items or a provider, not both")]
+    ConflictingSources,
+    #[error("Invalid item name '{0}'")]
+    InvalidItemName(String),
+    #[error("row_height must be a positive number, found {0}")]
+    InvalidRowHeight(f32),
+    #[error(transparent)]
+    Scrollbar(#[from] ScrollViewError),
+}
+
+impl VirtualListError {
+    /// The `widget_type` field at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            VirtualListError::MissingSource => "items",
+            VirtualListError::ConflictingSources => "provider",
+            VirtualListError::InvalidItemName(_) => "as",
+            VirtualListError::InvalidRowHeight(_) => "row_height",
+            VirtualListError::Scrollbar(e) => e.property(),
+        }
+    }
+}
+
+/// Check the source, rows and scrollbar of a `VirtualList`; other widget types pass
+pub fn validate_virtual_list(widget_type: &WidgetType) -> Result<(), VirtualListError> {
+    let WidgetType::VirtualList { items, provider, item_name, row_height, .. } = widget_type else {
+        return Ok(());
+    };
+    match (items, provider) {
+        (None, None) => return Err(VirtualListError::MissingSource),
+        (Some(_), Some(_)) => return Err(VirtualListError::ConflictingSources),
+        _ => {}
+    }
+    if let Some(name) = item_name.as_ref().filter(|name| !is_valid_state_segment(name)) {
+        return Err(VirtualListError::InvalidItemName(name.clone()));
+    }
+    if let Some(height) = row_height.filter(|height| !height.is_finite() || *height <= 0.0) {
+        return Err(VirtualListError::InvalidRowHeight(height));
+    }
+    validate_scroll_view(widget_type)?;
+    Ok(())
+}
+
+/// Items of a virtual list that live outside `UiState`, such as a log of tens of thousands of
+/// entries. Only the items of rows in view are asked for.
+pub trait VirtualListProvider: Send + Sync + 'static {
+    /// Number of items
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Up to `count` items from `start`, as the values `{{item}}` placeholders read
+    fn items(&self, start: usize, count: usize) -> Vec<Value>;
+
+    /// Number that changes whenever the items do; lists only read items again when it changes
+    /// or they scroll
+    fn revision(&self) -> u64;
+}
+
+/// Providers that virtual lists name with `provider = "name"`. The central log store is
+/// registered as `log_store`.
+#[derive(Resource, Clone)]
+pub struct VirtualListProviders {
+    providers: HashMap<String, Arc<dyn VirtualListProvider>>,
+}
+
+impl Default for VirtualListProviders {
+    fn default() -> Self {
+        let mut providers = Self { providers: HashMap::new() };
+        providers.register(LOG_STORE_PROVIDER, LogStoreProvider);
+        providers
+    }
+}
+
+impl VirtualListProviders {
+    /// Add `provider` under `name`, replacing any provider of that name
+    pub fn register(&mut self, name: impl Into<String>, provider: impl VirtualListProvider) {
+        self.providers.insert(name.into(), Arc::new(provider));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn VirtualListProvider>> {
+        self.providers.get(name)
+    }
+}
+
+/// Extension trait for registering virtual list providers on an `App`
+pub trait VirtualListAppExt {
+    /// Make `provider` available to virtual lists as `name`, creating the provider resource if
+    /// no plugin has yet
+    fn register_virtual_list_provider(&mut self, name: impl Into<String>, provider: impl VirtualListProvider) -> &mut Self;
+}
+
+impl VirtualListAppExt for App {
+    fn register_virtual_list_provider(&mut self, name: impl Into<String>, provider: impl VirtualListProvider) -> &mut Self {
+        self.init_resource::<VirtualListProviders>();
+        self.world_mut().resource_mut::<VirtualListProviders>().register(name, provider);
+        self
+    }
+}
+
+/// The entries of the central log store, oldest first, as `{ id, level, target, message, count }`
+/// items. Lists show nothing until logging has been initialized.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogStoreProvider;
+
+impl VirtualListProvider for LogStoreProvider {
+    fn len(&self) -> usize {
+        get_log_store().map_or(0, |store| store.get_stats().current_logs)
+    }
+
+    fn items(&self, start: usize, count: usize) -> Vec<Value> {
+        let Some(store) = get_log_store() else {
+            return Vec::new();
+        };
+        store.get_logs(LogFilter::for_level(LogLevel::Trace), start, count).into_iter()
+            .map(|log| json!({
+                "id": log.id,
+                "level": log.level.to_string(),
+                "target": log.metadata.target,
+                "message": log.message,
+                "count": log.duplicate_count,
+            }))
+            .collect()
+    }
+
+    fn revision(&self) -> u64 {
+        // Read every frame, so it must not wait for the store's worker like the queries do
+        get_log_store().map_or(0, |store| store.revision())
+    }
+}
+
+/// Where the items of a virtual list come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum VirtualListSource {
+    /// Path of a `List` state value
+    State(String),
+    /// Name of a registered `VirtualListProvider`
+    Provider(String),
+}
+
+/// A spawned row of a virtual list
+#[derive(Debug, Clone)]
+pub struct VirtualRow {
+    /// Index of the item the row shows
+    pub index: usize,
+    /// Number the ids of the row's widgets are scoped with; it stays with the row when it is
+    /// recycled for another item
+    pub slot: usize,
+    pub item: Value,
+    /// The row template filled in with the item
+    pub node: WidgetNode,
+    pub entity: Entity,
+}
+
+/// Component on a `VirtualList` widget holding its row template and the rows spawned for the
+/// items in view. The scrolling itself is done by its `WidgetScrollView`.
+///
+/// Rows leaving the view are recycled for the items coming into it. Rows whose template only
+/// differs in text and bindings keep their entities; the rest are respawned.
+#[derive(Component, Debug, Clone)]
+pub struct WidgetVirtualList {
+    /// `None` when the node names no source, which validation reports
+    pub source: Option<VirtualListSource>,
+    pub repeat: RepeatConfig,
+    /// Node spawned for every row, positioned by `virtual_list_layout_system`
+    pub template: Option<WidgetNode>,
+    /// Prefix of the ids of row widgets
+    pub host_id: String,
+    pub row_height: Option<f32>,
+    pub overscan: usize,
+    /// Number of items
+    pub len: usize,
+    /// Rows spawned, in item order
+    pub rows: Vec<VirtualRow>,
+    /// Items the rows were last brought in line with
+    pub range: Range<usize>,
+    /// Provider revision the items were last read at
+    pub revision: Option<u64>,
+    /// Measured height of every row drawn so far, by item index
+    heights: Vec<Option<f32>>,
+    /// Top of every row in content coordinates, followed by the bottom of the last
+    tops: Vec<f32>,
+    free_slots: Vec<usize>,
+    next_slot: usize,
+}
+
+impl WidgetVirtualList {
+    /// The list state of a `VirtualList` node, whose only child is its row template
+    pub fn from_node(node: &WidgetNode) -> Option<Self> {
+        let WidgetType::VirtualList { items, provider, item_name, row_height, overscan, .. } = &node.widget_type else {
+            return None;
+        };
+        let source = match (provider, items) {
+            (Some(name), _) => Some(VirtualListSource::Provider(name.clone())),
+            (None, Some(path)) => Some(VirtualListSource::State(path.clone())),
+            (None, None) => None,
+        };
+        // Rows are placed by the list, not by layout
+        let template = node.children.first().cloned().map(|mut template| {
+            template.behavior.position_control = Some(PositionControl::Manual);
+            template
+        });
+        Some(Self {
+            source,
+            repeat: RepeatConfig {
+                each: items.clone().or_else(|| provider.clone()).unwrap_or_default(),
+                key: None,
+                item_name: item_name.clone(),
+            },
+            template,
+            host_id: node.id.clone().unwrap_or_else(|| "virtual_list".to_string()),
+            row_height: row_height.filter(|height| height.is_finite() && *height > 0.0),
+            overscan: overscan.unwrap_or(DEFAULT_OVERSCAN),
+            len: 0,
+            rows: Vec::new(),
+            range: 0..0,
+            revision: None,
+            heights: Vec::new(),
+            tops: vec![0.0],
+            free_slots: Vec::new(),
+            next_slot: 0,
+        })
+    }
+
+    /// Change the number of items, forgetting the heights of rows past the end
+    pub fn set_len(&mut self, len: usize) {
+        if len != self.len {
+            self.len = len;
+            self.heights.resize(len, None);
+            self.update_tops();
+        }
+    }
+
+    /// Record the measured height of the row at `index`; returns whether it changed.
+    /// Call `update_tops` once done measuring.
+    pub fn set_height(&mut self, index: usize, height: f32) -> bool {
+        match self.heights.get_mut(index) {
+            Some(measured) if *measured != Some(height) => {
+                *measured = Some(height);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Place every row under the one before it. Rows not measured yet are taken to be as tall as
+    /// the average of those that are.
+    pub fn update_tops(&mut self) {
+        let (sum, count) = self.heights.iter().flatten().fold((0.0, 0), |(sum, count), height| (sum + height, count + 1));
+        let estimate = match count {
+            0 => ESTIMATED_ROW_HEIGHT,
+            count => sum / count as f32,
+        };
+        self.tops.clear();
+        self.tops.push(0.0);
+        let mut top = 0.0;
+        for height in &self.heights {
+            top += self.row_height.or(*height).unwrap_or(estimate);
+            self.tops.push(top);
+        }
+    }
+
+    /// Top of the row at `index` in content coordinates
+    pub fn row_top(&self, index: usize) -> f32 {
+        self.tops.get(index).copied().unwrap_or_else(|| self.content_height())
+    }
+
+    /// Height of all rows together
+    pub fn content_height(&self) -> f32 {
+        self.tops.last().copied().unwrap_or(0.0)
+    }
+
+    /// Items with rows in a viewport of height `viewport` scrolled down by `offset`, widened by
+    /// the overscan on both sides
+    pub fn visible_range(&self, offset: f32, viewport: f32) -> Range<usize> {
+        let tops = &self.tops[..self.len];
+        let first = tops.partition_point(|top| *top <= offset).saturating_sub(1);
+        let end = tops.partition_point(|top| *top < offset + viewport).max(first + 1);
+        first.saturating_sub(self.overscan)..(end + self.overscan).min(self.len)
+    }
+
+    /// The row template filled in with `item`, its ids scoped to the slot
+    pub fn row_node(&self, slot: usize, item: &Value) -> Option<WidgetNode> {
+        let template = std::slice::from_ref(self.template.as_ref()?);
+        match expand_repeat_item(&self.repeat, &self.host_id, template, &slot.to_string(), item) {
+            Ok(mut nodes) => nodes.pop(),
+            Err(e) => {
+                error!("Failed to fill a row of virtual list '{}': {}", self.host_id, e);
+                None
+            }
+        }
+    }
+
+    fn take_slot(&mut self) -> usize {
+        self.free_slots.pop().unwrap_or_else(|| {
+            self.next_slot += 1;
+            self.next_slot - 1
+        })
+    }
+}
+
+/// The parts of a row node that decide its widgets, leaving out what a recycled row can change
+/// in place. Rows with template widgets, dynamic children or bound values are always respawned.
+fn row_shape(node: &WidgetNode) -> Option<WidgetNode> {
+    let fixed = is_template_widget(&node.widget_type)
+        || matches!(node.widget_type, WidgetType::Custom { .. })
+        || node.when.is_some()
+        || node.repeat.is_some()
+        || node.state_bindings.is_some()
+        || node.localized.is_some();
+    if fixed {
+        return None;
+    }
+    let children = node.children.iter().map(row_shape).collect::<Option<Vec<_>>>()?;
+    let mut shape = WidgetNode { children, bindings: None, ..node.clone() };
+    if let WidgetType::Text { content, .. } = &mut shape.widget_type {
+        content.clear();
+    }
+    Some(shape)
+}
+
+/// Whether the widgets spawned for `spawned` can show `node` by changing their text and bindings
+fn can_refill(spawned: &WidgetNode, node: &WidgetNode) -> bool {
+    let shape = |node: &WidgetNode| row_shape(node).and_then(|shape| serde_json::to_value(shape).ok());
+    matches!((shape(spawned), shape(node)), (Some(spawned), Some(node)) if spawned == node)
+}
+
+/// Write the text and bindings of `node` over the widgets of a row spawned from a node of the
+/// same shape
+fn refill_row(
+    commands: &mut Commands,
+    entity: Entity,
+    node: &WidgetNode,
+    hierarchies: &Query<&WidgetHierarchy>,
+    texts: &mut Query<(&mut Widget, Option<&mut WidgetText>)>,
+    yrs_res: &YrsDocResource,
+    text_events: &mut EventWriter<YrsTextChanged>,
+) {
+    if let (WidgetType::Text { content, .. }, Ok((mut widget, mut widget_text))) = (&node.widget_type, texts.get_mut(entity)) {
+        let changed = !matches!(&widget.blueprint.widget_type, WidgetType::Text { content: current, .. } if current == content);
+        if changed && set_static_text(yrs_res, entity, &mut widget, widget_text.as_deref_mut(), content) {
+            text_events.send(YrsTextChanged { entity });
+        }
+    }
+    match &node.bindings {
+        Some(bindings) => {
+            commands.entity(entity).insert(WidgetActionBindings { bindings: bindings.clone() });
+        }
+        None => {
+            commands.entity(entity).remove::<WidgetActionBindings>();
+        }
+    }
+    let children = hierarchies.get(entity).map(|hierarchy| hierarchy.children.clone()).unwrap_or_default();
+    for (child, child_node) in children.into_iter().zip(&node.children) {
+        refill_row(commands, child, child_node, hierarchies, texts, yrs_res, text_events);
+    }
+}
+
+/// System that spawns rows for the items coming into view of virtual lists, and recycles or
+/// despawns the rows of items leaving it. Lists read their items again when their state list
+/// or provider changes.
+#[allow(clippy::too_many_arguments)]
+pub fn virtual_list_rows_system(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    registry: Res<UiRegistry>,
+    yrs_res: Res<YrsDocResource>,
+    loading_assets: Res<LoadingUiAssets>,
+    ui_assets: Res<Assets<UiDefinition>>,
+    taffy_resource: Res<TaffyResource>,
+    providers: Res<VirtualListProviders>,
+    mut lists: Query<(Entity, &mut WidgetVirtualList, &WidgetScrollView)>,
+    hierarchies: Query<&WidgetHierarchy>,
+    parents: Query<&Parent>,
+    ui_nodes: Query<&UiNode>,
+    widget_windows: WidgetWindows,
+    mut texts: Query<(&mut Widget, Option<&mut WidgetText>)>,
+    mut text_events: EventWriter<YrsTextChanged>,
+) {
+    for (host, mut list, view) in lists.iter_mut() {
+        let (Some(content), Some(source)) = (view.content, list.source.clone()) else {
+            continue;
+        };
+
+        // Read the items in view, skipping lists whose items and range are unchanged
+        let (range, items) = match source {
+            VirtualListSource::State(path) => {
+                let values = ui_state.get(&path).and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                list.set_len(values.len());
+                let range = list.visible_range(view.offset.y, view.viewport.y);
+                let unchanged = range == list.range && list.rows.iter().all(|row| values.get(row.index) == Some(&row.item));
+                if unchanged {
+                    continue;
+                }
+                (range.clone(), values[range].to_vec())
+            }
+            VirtualListSource::Provider(name) => {
+                let Some(provider) = providers.get(&name) else {
+                    continue;
+                };
+                let revision = provider.revision();
+                let refreshed = list.revision != Some(revision);
+                if refreshed {
+                    list.set_len(provider.len());
+                    list.revision = Some(revision);
+                }
+                let range = list.visible_range(view.offset.y, view.viewport.y);
+                if !refreshed && range == list.range {
+                    continue;
+                }
+                let items = provider.items(range.start, range.len());
+                (range, items)
+            }
+        };
+
+        let Some(ui_definition) = owning_definition(host, &hierarchies, &loading_assets, &ui_assets) else {
+            continue;
+        };
+        let window_height = widget_windows.height_of(host);
+        let spawn = |commands: &mut Commands, node: &WidgetNode| {
+            let entity = spawn_child_widget(commands, node, ui_definition, &registry, &yrs_res, host, window_height, None);
+            // Rows are manually positioned, so they are carried by the scrolled content by hand
+            commands.entity(content).add_child(entity);
+            entity
+        };
+
+        // Rows still in view keep their item; the others are free to show the items coming in
+        let mut kept = HashMap::new();
+        let mut free = Vec::new();
+        for row in list.rows.drain(..) {
+            match range.contains(&row.index) {
+                true => {
+                    kept.insert(row.index, row);
+                }
+                false => free.push(row),
+            }
+        }
+
+        let mut rows = Vec::new();
+        let mut removed = Vec::new();
+        for (index, item) in range.clone().zip(items) {
+            let row = match kept.remove(&index) {
+                Some(row) if row.item == item => {
+                    rows.push(row);
+                    continue;
+                }
+                Some(row) => Some(row),
+                None => free.pop(),
+            };
+            let slot = row.as_ref().map_or_else(|| list.take_slot(), |row| row.slot);
+            let Some(node) = list.row_node(slot, &item) else {
+                removed.extend(row.map(|row| row.entity));
+                list.free_slots.push(slot);
+                continue;
+            };
+            let entity = match row {
+                Some(row) if can_refill(&row.node, &node) => {
+                    refill_row(&mut commands, row.entity, &node, &hierarchies, &mut texts, &yrs_res, &mut text_events);
+                    row.entity
+                }
+                row => {
+                    removed.extend(row.map(|row| row.entity));
+                    spawn(&mut commands, &node)
+                }
+            };
+            rows.push(VirtualRow { index, slot, item, node, entity });
+        }
+        for row in kept.into_values().chain(free) {
+            removed.push(row.entity);
+            list.free_slots.push(row.slot);
+        }
+
+        let mut stale = HashSet::new();
+        for entity in removed {
+            collect_subtree(entity, &hierarchies, &mut stale);
+        }
+        despawn_widgets(&mut commands, &stale, &parents, &ui_nodes, &taffy_resource, &yrs_res);
+
+        // The widget hierarchy lists the rows in item order
+        let parent = hierarchies.get(host).ok().and_then(|hierarchy| hierarchy.parent);
+        commands.entity(host).insert(WidgetHierarchy {
+            parent,
+            children: rows.iter().map(|row| row.entity).collect(),
+        });
+        list.rows = rows;
+        list.range = range;
+    }
+}
+
+/// System that measures the rows of virtual lists without a `row_height`, sizes their content
+/// to all rows, spawned or not, and places the rows one under the other
+pub fn virtual_list_layout_system(
+    mut lists: Query<(&mut WidgetVirtualList, &mut WidgetScrollView)>,
+    roots: Query<(&WidgetLayout, Has<Text>)>,
+    mut transforms: Query<&mut Transform>,
+    children_query: Query<&Children>,
+    drawn: Query<(&GlobalTransform, Option<&ShapeData>, Option<&TextLayoutOutput>)>,
+) {
+    for (mut list, mut view) in lists.iter_mut() {
+        if list.row_height.is_none() {
+            let measured: Vec<(usize, f32)> = list.rows.iter()
+                .filter_map(|row| Some((row.index, drawn_bounds(row.entity, &children_query, &drawn)?.height())))
+                .collect();
+            let mut changed = false;
+            for (index, height) in measured {
+                changed |= list.set_height(index, height);
+            }
+            if changed {
+                list.update_tops();
+            }
+        }
+
+        let content_size = Vec2::new(view.viewport.x, list.content_height());
+        if view.content_size != content_size {
+            view.content_size = content_size;
+            let offset = view.offset;
+            view.scroll_to(offset);
+        }
+
+        let top_left = Vec2::new(-view.viewport.x, view.viewport.y) / 2.0;
+        for row in &list.rows {
+            let (Ok((layout, is_text)), Ok(mut transform)) = (roots.get(row.entity), transforms.get_mut(row.entity)) else {
+                continue;
+            };
+            // Text is placed by its top-left corner, everything else by its center
+            let corner = top_left - Vec2::new(0.0, list.row_top(row.index));
+            let position = match is_text {
+                true => corner,
+                false => corner + Vec2::new(layout.computed_size.x, -layout.computed_size.y) / 2.0,
+            };
+            if transform.translation.truncate() != position {
+                transform.translation = position.extend(transform.translation.z);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::*;
+    use crate::test_support::setup_world;
+    use crate::gui_framework::events::YrsTextChanged;
+    use crate::widgets::components::{Widget, WidgetHierarchy, WidgetText};
+    use crate::widgets::scroll_view::*;
+    use crate::YrsDocResource;
+    use bevy_asset::Assets;
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_hierarchy::Parent;
+    use bevy_math::Vec2;
+    use bevy_transform::prelude::Transform;
+    use serde_json::{json, Value};
+
+    const LOG: &str = r##"
+[root]
+id = "log"
+widget_type = { type = "VirtualList", provider = "numbers", row_height = 20.0, overscan = 2 }
+layout = { size = [200.0, 100.0] }
+
+[[root.children]]
+id = "label"
+widget_type = { type = "Text", content = "Row {{item.n}}", editable = false }
+"##;
+
+    /// As many numbered items as a long log
+    struct Numbers;
+
+    impl VirtualListProvider for Numbers {
+        fn len(&self) -> usize {
+            50_000
+        }
+
+        fn items(&self, start: usize, count: usize) -> Vec<Value> {
+            (start..(start + count).min(self.len())).map(|n| json!({ "n": n })).collect()
+        }
+
+        fn revision(&self) -> u64 {
+            0
+        }
+    }
+
+    /// Spawn `content` as if it had been loaded, so its rows can find their definition
+    fn spawn_list(world: &mut World, yrs_res: &YrsDocResource, content: &str) -> Entity {
+        let ui_def = UiDefinitionLoader.parse_definition(content, UiDefinitionFormat::Toml).unwrap();
+        let root = {
+            let mut commands = world.commands();
+            spawn_widget_from_node(&mut commands, &ui_def.root, &ui_def, &UiRegistry::new(), yrs_res, None, 300.0, None)
+        };
+        world.flush();
+        let handle = world.resource_mut::<Assets<UiDefinition>>().add(ui_def);
+        world.resource_mut::<LoadingUiAssets>().spawned.push(SpawnedUi {
+            handle,
+            request: LoadUiRequest::new("ui/log.toml"),
+            root,
+        });
+        root
+    }
+
+    fn rows_of(world: &World, list: Entity) -> Vec<Entity> {
+        world.get::<WidgetVirtualList>(list).unwrap().rows.iter().map(|row| row.entity).collect()
+    }
+
+    fn widget_count(world: &mut World) -> usize {
+        world.query::<&Widget>().iter(world).count()
+    }
+
+    /// Test validation of virtual lists and the rows they keep for a scroll position
+    #[test]
+    fn test_virtual_list_validation_and_range() {
+        let ui_def = UiDefinitionLoader.parse_definition(LOG, UiDefinitionFormat::Toml).unwrap();
+        assert!(!ui_def.validation_report().has_errors());
+
+        let mut list = WidgetVirtualList::from_node(&ui_def.root).unwrap();
+        assert_eq!(list.source, Some(VirtualListSource::Provider("numbers".to_string())));
+        list.set_len(50_000);
+        assert_eq!(list.content_height(), 1_000_000.0);
+        assert_eq!(list.visible_range(0.0, 100.0), 0..7, "Five rows fill the viewport, plus the overscan below");
+        assert_eq!(list.visible_range(1000.0, 100.0), 48..57);
+        assert_eq!(list.visible_range(999_990.0, 100.0), 49_997..50_000);
+
+        // Rows without a fixed height are measured, and the rest are estimated from them
+        let mut list = WidgetVirtualList::from_node(&ui_def.root).unwrap();
+        list.row_height = None;
+        list.set_len(10);
+        assert_eq!(list.content_height(), 10.0 * ESTIMATED_ROW_HEIGHT);
+        assert!(list.set_height(0, 40.0));
+        assert!(!list.set_height(0, 40.0), "Measuring the same height changes nothing");
+        assert!(list.set_height(1, 20.0));
+        list.update_tops();
+        assert_eq!(list.row_top(2), 60.0);
+        assert_eq!(list.content_height(), 60.0 + 8.0 * 30.0);
+
+        let invalid = r##"
+[state]
+title = "Untitled"
+
+[root]
+widget_type = { type = "Container", direction = "Column" }
+
+[[root.children]]
+widget_type = { type = "VirtualList" }
+
+[[root.children]]
+widget_type = { type = "VirtualList", items = "title", row_height = 0.0 }
+
+[[root.children.children]]
+widget_type = { type = "Text", content = "{{item}}", editable = false }
+
+[[root.children]]
+widget_type = { type = "VirtualList", items = "title", provider = "log_store" }
+
+[[root.children.children]]
+widget_type = { type = "Text", content = "{{item}}", editable = false }
+"##;
+        let ui_def = UiDefinitionLoader.parse_definition(invalid, UiDefinitionFormat::Toml).unwrap();
+        let report = ui_def.validation_report();
+        let paths: Vec<_> = report.errors().map(|error| error.path.as_str()).collect();
+        assert!(paths.contains(&"root.children[0].widget_type.items"), "A list needs a source: {:?}", paths);
+        assert!(paths.contains(&"root.children[0].children"), "A list needs a row template: {:?}", paths);
+        assert!(paths.contains(&"root.children[1].widget_type.row_height"), "{:?}", paths);
+        assert!(paths.contains(&"root.children[1].widget_type.items"), "Items must be a List: {:?}", paths);
+        assert!(paths.contains(&"root.children[2].widget_type.provider"), "{:?}", paths);
+    }
+
+    /// Test that a list of 50,000 items only spawns the rows in view, and recycles them as it scrolls
+    #[test]
+    fn test_virtual_list_spawns_and_recycles_rows() {
+        let (mut world, yrs_res) = setup_world();
+        let mut providers = VirtualListProviders::default();
+        providers.register("numbers", Numbers);
+        world.insert_resource(providers);
+        let log = spawn_list(&mut world, &yrs_res, LOG);
+        world.run_system_once(scroll_view_setup_system).unwrap();
+        world.run_system_once(scroll_view_measure_system).unwrap();
+        world.run_system_once(virtual_list_rows_system).unwrap();
+
+        let rows = rows_of(&world, log);
+        assert_eq!(rows.len(), 7);
+        assert_eq!(widget_count(&mut world), 8);
+        assert_eq!(world.get::<WidgetHierarchy>(log).unwrap().children, rows);
+        assert_eq!(world.get::<WidgetText>(rows[3]).unwrap().content, "Row 3");
+        let content = world.get::<WidgetScrollView>(log).unwrap().content.unwrap();
+        assert_eq!(world.get::<Parent>(rows[0]).unwrap().get(), content);
+
+        // Rows go one under the other from the top-left of the viewport, and the content is as tall
+        // as every row together
+        world.run_system_once(virtual_list_layout_system).unwrap();
+        assert_eq!(world.get::<WidgetScrollView>(log).unwrap().content_size, Vec2::new(200.0, 1_000_000.0));
+        assert_eq!(world.get::<Transform>(rows[1]).unwrap().translation.truncate(), Vec2::new(-100.0, 30.0));
+
+        // Scrolling far down recycles every row for the items coming into view
+        world.get_mut::<WidgetScrollView>(log).unwrap().scroll_to(Vec2::new(0.0, 1000.0));
+        world.resource_mut::<Events<YrsTextChanged>>().clear();
+        world.run_system_once(virtual_list_rows_system).unwrap();
+        assert_eq!(world.get::<WidgetVirtualList>(log).unwrap().range, 48..57);
+        let scrolled = rows_of(&world, log);
+        assert!(rows.iter().all(|row| scrolled.contains(row)), "Rows leaving the view are reused");
+        assert_eq!(widget_count(&mut world), 10);
+        assert_eq!(world.get::<WidgetText>(scrolled[0]).unwrap().content, "Row 48");
+        assert_eq!(world.resource::<Events<YrsTextChanged>>().len(), 7, "Reused rows are laid out again");
+
+        world.run_system_once(virtual_list_layout_system).unwrap();
+        assert_eq!(world.get::<Transform>(scrolled[2]).unwrap().translation.truncate(), Vec2::new(-100.0, 50.0 - 1000.0));
+
+        // Rows no longer needed are despawned
+        world.get_mut::<WidgetScrollView>(log).unwrap().scroll_to(Vec2::ZERO);
+        world.run_system_once(virtual_list_rows_system).unwrap();
+        assert_eq!(rows_of(&world, log).len(), 7);
+        assert_eq!(widget_count(&mut world), 8);
+    }
+}
\ No newline at end of file
@@ -2,7 +2,6 @@ use bevy_app::App;
 use bevy_log::info;
 use bevy_tasks::IoTaskPool;
 use whip_ui::WhipUiPlugin;
-use tracing;
 
 mod logging_test;
 